@@ -0,0 +1,217 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: mna_solver_bench.rs | DNA/examples/mna_solver_bench.rs
+//! PURPOSE: Dense vs sparse MNA solver timing
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//! Benchmark dense LU against sparse LU with Markowitz ordering
+//!
+//! Run with: cargo run --release --example mna_solver_bench
+//!
+//! Builds RC ladders (filter / PLL loop-filter style) and resistor meshes
+//! (power-plane style) of increasing size and times one frequency sweep
+//! with the dense solver, a fresh sparse factorization per point, and a
+//! sparse solver that reuses its ordering between points.
+
+use dna::physics::electromagnetics::lumped::{
+    ac_analysis, Complex, ComplexMNAMatrix, Element, Netlist, SourceValue, SparseLu, SparseSolver,
+};
+use std::f64::consts::PI;
+use std::time::Instant;
+
+/// Frequency points per sweep
+const POINTS: usize = 50;
+
+/// Series R / shunt C ladder driven by an AC source
+fn rc_ladder(sections: usize) -> Netlist {
+    let mut netlist = Netlist::new(format!("RC ladder x{}", sections));
+    netlist.add_element(Element::VoltageSource {
+        name: "VIN".to_string(),
+        node_p: "n0".to_string(),
+        node_n: "0".to_string(),
+        value: SourceValue::AC {
+            magnitude: 1.0,
+            phase: 0.0,
+        },
+    });
+    for k in 0..sections {
+        netlist.add_element(Element::Resistor {
+            name: format!("R{}", k),
+            node_p: format!("n{}", k),
+            node_n: format!("n{}", k + 1),
+            value: 100.0,
+        });
+        netlist.add_element(Element::Capacitor {
+            name: format!("C{}", k),
+            node_p: format!("n{}", k + 1),
+            node_n: "0".to_string(),
+            value: 1e-9,
+        });
+    }
+    netlist
+}
+
+/// side × side resistor mesh with decoupling capacitors, fed at one corner
+fn rc_mesh(side: usize) -> Netlist {
+    let node = |i: usize, j: usize| format!("m{}_{}", i, j);
+    let mut netlist = Netlist::new(format!("RC mesh {}x{}", side, side));
+    netlist.add_element(Element::VoltageSource {
+        name: "VIN".to_string(),
+        node_p: node(0, 0),
+        node_n: "0".to_string(),
+        value: SourceValue::AC {
+            magnitude: 1.0,
+            phase: 0.0,
+        },
+    });
+    for i in 0..side {
+        for j in 0..side {
+            if i + 1 < side {
+                netlist.add_element(Element::Resistor {
+                    name: format!("RV{}_{}", i, j),
+                    node_p: node(i, j),
+                    node_n: node(i + 1, j),
+                    value: 0.01,
+                });
+            }
+            if j + 1 < side {
+                netlist.add_element(Element::Resistor {
+                    name: format!("RH{}_{}", i, j),
+                    node_p: node(i, j),
+                    node_n: node(i, j + 1),
+                    value: 0.01,
+                });
+            }
+            netlist.add_element(Element::Capacitor {
+                name: format!("C{}_{}", i, j),
+                node_p: node(i, j),
+                node_n: "0".to_string(),
+                value: 100e-9,
+            });
+        }
+    }
+    netlist
+}
+
+/// Stamp the passive elements of `netlist` at angular frequency `omega`
+fn build(netlist: &Netlist, omega: f64) -> ComplexMNAMatrix {
+    let mut matrix = ComplexMNAMatrix::new(netlist.num_nodes(), netlist.num_voltage_sources());
+    let mut vs = 0;
+    for element in &netlist.elements {
+        match element {
+            Element::Resistor {
+                node_p,
+                node_n,
+                value,
+                ..
+            } => {
+                let (p, n) = (netlist.node_index(node_p), netlist.node_index(node_n));
+                matrix.stamp_resistor(p.unwrap(), n.unwrap(), *value);
+            }
+            Element::Capacitor {
+                node_p,
+                node_n,
+                value,
+                ..
+            } => {
+                let (p, n) = (netlist.node_index(node_p), netlist.node_index(node_n));
+                matrix.stamp_capacitor(p.unwrap(), n.unwrap(), *value, omega);
+            }
+            Element::VoltageSource { node_p, node_n, .. } => {
+                let (p, n) = (netlist.node_index(node_p), netlist.node_index(node_n));
+                let one = Complex::new(1.0, 0.0);
+                matrix.stamp_voltage_source(p.unwrap(), n.unwrap(), vs, one);
+                vs += 1;
+            }
+            _ => {}
+        }
+    }
+    matrix
+}
+
+fn omegas() -> Vec<f64> {
+    (0..POINTS)
+        .map(|i| 2.0 * PI * 10f64.powf(2.0 + 5.0 * i as f64 / (POINTS - 1) as f64))
+        .collect()
+}
+
+fn bench(netlist: &Netlist) {
+    let omegas = omegas();
+    let matrices: Vec<ComplexMNAMatrix> = omegas.iter().map(|&w| build(netlist, w)).collect();
+    let size = matrices[0].size;
+
+    let start = Instant::now();
+    let dense: Vec<_> = matrices.iter().map(|m| m.solve().unwrap()).collect();
+    let t_dense = start.elapsed().as_secs_f64();
+
+    let start = Instant::now();
+    for m in &matrices {
+        let lu = SparseLu::factor(&m.matrix).unwrap();
+        lu.solve(&m.rhs);
+    }
+    let t_fresh = start.elapsed().as_secs_f64();
+
+    let mut solver = SparseSolver::new();
+    let start = Instant::now();
+    let sparse: Vec<_> = matrices
+        .iter()
+        .map(|m| m.solve_sparse(&mut solver).unwrap())
+        .collect();
+    let t_reuse = start.elapsed().as_secs_f64();
+
+    let max_err = dense
+        .iter()
+        .zip(&sparse)
+        .flat_map(|(d, s)| d.iter().zip(s).map(|(a, b)| (*a - *b).magnitude()))
+        .fold(0.0, f64::max);
+
+    let start = Instant::now();
+    ac_analysis(netlist, 1e2, 1e7, POINTS / 5).unwrap();
+    let t_ac = start.elapsed().as_secs_f64();
+
+    let nnz = matrices[0].matrix.nnz();
+    let lu_nnz = solver.factors().map_or(0, |lu| lu.nnz());
+    println!(
+        "{:<20} {:>6} {:>7} {:>8} {:>10.2} {:>10.2} {:>10.2} {:>8.1}x {:>10.2} {:>9.1e}",
+        netlist.title,
+        size,
+        nnz,
+        lu_nnz,
+        t_dense * 1e3,
+        t_fresh * 1e3,
+        t_reuse * 1e3,
+        t_dense / t_reuse,
+        t_ac * 1e3,
+        max_err
+    );
+    assert_eq!(solver.factorizations, 1);
+}
+
+fn main() {
+    println!(
+        "MNA solver benchmark ({} frequency points per sweep)\n",
+        POINTS
+    );
+    println!(
+        "{:<20} {:>6} {:>7} {:>8} {:>10} {:>10} {:>10} {:>9} {:>10} {:>9}",
+        "circuit",
+        "size",
+        "nnz",
+        "nnz(LU)",
+        "dense ms",
+        "fresh ms",
+        "reuse ms",
+        "speedup",
+        "ac ms",
+        "max err"
+    );
+    println!("{}", "─".repeat(110));
+
+    for sections in [10, 50, 200, 500] {
+        bench(&rc_ladder(sections));
+    }
+    for side in [5, 10, 20] {
+        bench(&rc_mesh(side));
+    }
+}
@@ -5,11 +5,42 @@
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
-use super::geometry::{BoundingBox3, Point3, Vector3};
-use super::intersect::Classification;
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ 1. Extract each face as outward-oriented planar polygons (with holes)       │
+//! │ 2. Find face pairs whose polygons cross each other's planes                 │
+//! │ 3. Split every face by the planes of the faces it crosses                   │
+//! │ 4. Classify fragments against the other solid (point_in_solid)              │
+//! │ 5. Keep fragments per operation, flipping B's for difference                │
+//! │ 6. Weld vertices, repair T-junctions, merge fragments back into faces       │
+//! │ 7. Rebuild edges, loops and shells                                          │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Planar faces are used as flat polygons, with curved edges sampled along
+//! their arcs. Cylindrical, spherical, conical, toroidal and NURBS faces are
+//! tessellated to `DEFAULT_CHORD_TOLERANCE` for cutting and classification
+//! only: a curved face that is not cut comes through with its original loops,
+//! and a cut one is merged back into one face per connected piece, bounded by
+//! the polyline intersection curve. Curved edges whose samples survive intact
+//! are restored as the source curve. Coplanar overlapping faces, tangent
+//! contact and cut curved faces whose outline cannot be told from their holes
+//! are reported as `BooleanError::DegenerateCase`.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::geometry::{Point3, Vector3};
+use super::intersect::{Classification, SolidClassifier};
+use super::mesh::{
+    edge_polyline, face_to_mesh, loop_points, plane_basis, triangulate_polygon,
+    DEFAULT_CHORD_TOLERANCE,
+};
 use super::topology::{
-    CurveType, Edge, EdgeId, Face, FaceId, Loop, Shell, Solid, SurfaceType, Vertex, VertexId,
+    CurveType, EdgeId, Face, FaceId, FaceOrientation, Loop, ShellId, Solid, SurfaceType, VertexId,
 };
+use glam::DVec3;
+use std::collections::{HashMap, HashSet};
 
 /// Boolean operation type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,8 +60,9 @@ pub enum BooleanError {
 
 /// Boolean union: A ∪ B
 ///
-/// Currently implements a simplified version for non-intersecting or simple solids.
-/// Returns a new solid containing both input solids' faces.
+/// Disjoint solids are merged as separate shells. Intersecting solids are
+/// split along their intersection curves and the outside fragments of each
+/// are stitched into a single closed shell.
 pub fn union(a: &Solid, b: &Solid) -> Result<Solid, BooleanError> {
     // Check if bounding boxes intersect
     let mut a_clone = a.clone();
@@ -43,15 +75,13 @@ pub fn union(a: &Solid, b: &Solid) -> Result<Solid, BooleanError> {
         return Ok(merge_solids(a, b));
     }
 
-    // For intersecting solids, we need full Boolean logic
-    // Simplified implementation: just merge for now
-    Ok(merge_solids(a, b))
+    boolean(a, b, BooleanOp::Union)
 }
 
 /// Boolean difference: A - B
 ///
-/// Removes the volume of B from A.
-/// Simplified implementation: returns A unchanged if no intersection.
+/// Removes the volume of B from A. Faces of B inside A become the walls of
+/// the resulting cavity, with their orientation reversed.
 pub fn difference(a: &Solid, b: &Solid) -> Result<Solid, BooleanError> {
     let mut a_clone = a.clone();
     let mut b_clone = b.clone();
@@ -63,14 +93,7 @@ pub fn difference(a: &Solid, b: &Solid) -> Result<Solid, BooleanError> {
         return Ok(a.clone());
     }
 
-    // Full implementation would:
-    // 1. Find intersection curves
-    // 2. Split faces at intersections
-    // 3. Classify faces (keep A faces outside B)
-    // 4. Reconstruct shell
-
-    // Simplified: return A for now
-    Ok(a.clone())
+    boolean(a, b, BooleanOp::Difference)
 }
 
 /// Boolean intersection: A ∩ B
@@ -86,31 +109,7 @@ pub fn intersection(a: &Solid, b: &Solid) -> Result<Solid, BooleanError> {
         return Err(BooleanError::NoIntersection);
     }
 
-    // Full implementation would:
-    // 1. Find intersection curves
-    // 2. Split faces at intersections
-    // 3. Classify faces (keep faces inside both solids)
-    // 4. Reconstruct shell
-
-    // Simplified: create bounding box intersection
-    let min_x = bbox_a.min.x.max(bbox_b.min.x);
-    let min_y = bbox_a.min.y.max(bbox_b.min.y);
-    let min_z = bbox_a.min.z.max(bbox_b.min.z);
-    let max_x = bbox_a.max.x.min(bbox_b.max.x);
-    let max_y = bbox_a.max.y.min(bbox_b.max.y);
-    let max_z = bbox_a.max.z.min(bbox_b.max.z);
-
-    use crate::cad::primitives::make_box_at;
-    let width = max_x - min_x;
-    let height = max_y - min_y;
-    let depth = max_z - min_z;
-    let center = Point3::new(
-        (min_x + max_x) / 2.0,
-        (min_y + max_y) / 2.0,
-        (min_z + max_z) / 2.0,
-    );
-
-    Ok(make_box_at(center, width, height, depth))
+    boolean(a, b, BooleanOp::Intersection)
 }
 
 /// Merge two solids (simple combination without intersection handling)
@@ -169,10 +168,1274 @@ fn merge_solids(a: &Solid, b: &Solid) -> Solid {
     result
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Face polygons
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A face flattened to an outward-oriented polygon in double precision
+struct FacePolygon {
+    /// Index of the source face in its solid
+    face: usize,
+    /// Whether the polygon is one facet of a tessellated curved face
+    faceted: bool,
+    surface: SurfaceType,
+    outer: Vec<DVec3>,
+    /// Unit normal pointing out of the solid
+    normal: DVec3,
+    /// Plane offset: normal · p = offset
+    offset: f64,
+    min: DVec3,
+    max: DVec3,
+    /// Convex pieces covering the face, wound with `normal`
+    triangles: Vec<[DVec3; 3]>,
+}
+
+impl FacePolygon {
+    fn signed_distance(&self, p: DVec3) -> f64 {
+        self.normal.dot(p) - self.offset
+    }
+
+    fn flip(&mut self) {
+        self.outer.reverse();
+        self.normal = -self.normal;
+        self.offset = -self.offset;
+        for t in &mut self.triangles {
+            t.swap(1, 2);
+        }
+    }
+}
+
+fn to_dvec(p: Point3) -> DVec3 {
+    DVec3::new(p.x as f64, p.y as f64, p.z as f64)
+}
+
+fn to_point(p: DVec3) -> Point3 {
+    Point3::new(p.x as f32, p.y as f32, p.z as f32)
+}
+
+/// Working tolerance scaled to the combined size of both operands
+fn working_tolerance(a: &Solid, b: &Solid) -> f64 {
+    let mut min = DVec3::splat(f64::MAX);
+    let mut max = DVec3::splat(f64::MIN);
+    for v in a.vertices.iter().chain(b.vertices.iter()) {
+        let p = to_dvec(v.point);
+        min = min.min(p);
+        max = max.max(p);
+    }
+    1e-5 * (max - min).length().max(1.0)
+}
+
+/// Flatten every face of a solid into outward-oriented polygons
+///
+/// Planar faces become one polygon each, with curved edges sampled to
+/// `DEFAULT_CHORD_TOLERANCE`. Curved faces are tessellated with the same edge
+/// samples and contribute one polygon per facet; the facets are only used
+/// for cutting and classification and are merged back by source face when
+/// the result is rebuilt.
+fn face_polygons(solid: &Solid, tol: f64) -> Result<Vec<FacePolygon>, BooleanError> {
+    let mut polygons = Vec::with_capacity(solid.faces.len());
+
+    for (index, face) in solid.faces.iter().enumerate() {
+        let to_dvecs =
+            |points: Vec<Point3>| -> Vec<DVec3> { points.into_iter().map(to_dvec).collect() };
+
+        let outer = to_dvecs(loop_points(
+            solid,
+            &face.outer_loop,
+            DEFAULT_CHORD_TOLERANCE,
+        ));
+        if outer.len() < 3 {
+            return Err(BooleanError::TopologyError(format!(
+                "face {} has a degenerate outer loop",
+                face.id.0
+            )));
+        }
+        let holes: Vec<Vec<DVec3>> = face
+            .inner_loops
+            .iter()
+            .map(|l| to_dvecs(loop_points(solid, l, DEFAULT_CHORD_TOLERANCE)))
+            .filter(|h| h.len() >= 3)
+            .collect();
+
+        let normal = newell_normal_d(&outer);
+        if normal.length() < tol * tol {
+            return Err(BooleanError::TopologyError(format!(
+                "face {} has zero area",
+                face.id.0
+            )));
+        }
+        let normal = normal.normalize();
+
+        let offset = normal.dot(outer[0]);
+        let flat = outer
+            .iter()
+            .chain(holes.iter().flatten())
+            .all(|p| (normal.dot(*p) - offset).abs() < tol * 10.0);
+        if flat {
+            polygons.push(planar_polygon(
+                index,
+                false,
+                face.surface.clone(),
+                outer,
+                &holes,
+                normal,
+            ));
+            continue;
+        }
+
+        // Curved face: one flat polygon per facet of its tessellation
+        let mesh = face_to_mesh(face, solid, DEFAULT_CHORD_TOLERANCE);
+        let before = polygons.len();
+        for t in &mesh.triangles {
+            let facet: Vec<DVec3> = t.iter().map(|&i| to_dvec(mesh.vertices[i])).collect();
+            let n = newell_normal_d(&facet);
+            if n.length() > tol * tol {
+                polygons.push(planar_polygon(
+                    index,
+                    true,
+                    face.surface.clone(),
+                    facet,
+                    &[],
+                    n.normalize(),
+                ));
+            }
+        }
+        if polygons.len() == before {
+            return Err(BooleanError::TopologyError(format!(
+                "face {} could not be tessellated",
+                face.id.0
+            )));
+        }
+    }
+
+    orient_outward(&mut polygons, tol);
+    Ok(polygons)
+}
+
+/// Polygon normal by Newell's method in double precision (length = 2 × area)
+fn newell_normal_d(points: &[DVec3]) -> DVec3 {
+    let mut n = DVec3::ZERO;
+    for i in 0..points.len() {
+        n += points[i].cross(points[(i + 1) % points.len()]);
+    }
+    n
+}
+
+/// Build a face polygon lying in the plane through `outer` with `normal`
+fn planar_polygon(
+    face: usize,
+    faceted: bool,
+    surface: SurfaceType,
+    outer: Vec<DVec3>,
+    holes: &[Vec<DVec3>],
+    normal: DVec3,
+) -> FacePolygon {
+    // Triangulate in the face plane so concave faces split cleanly
+    let (u_axis, v_axis) = plane_basis(normal);
+    let project = |p: &DVec3| [p.dot(u_axis), p.dot(v_axis)];
+    let outer_2d: Vec<[f64; 2]> = outer.iter().map(project).collect();
+    let holes_2d: Vec<Vec<[f64; 2]>> = holes
+        .iter()
+        .map(|h| h.iter().map(project).collect())
+        .collect();
+    let all_points: Vec<DVec3> = outer
+        .iter()
+        .chain(holes.iter().flatten())
+        .copied()
+        .collect();
+    let triangles: Vec<[DVec3; 3]> = triangulate_polygon(&outer_2d, &holes_2d)
+        .into_iter()
+        .map(|t| [all_points[t[0]], all_points[t[1]], all_points[t[2]]])
+        .collect();
+
+    let mut min = DVec3::splat(f64::MAX);
+    let mut max = DVec3::splat(f64::MIN);
+    for p in &outer {
+        min = min.min(*p);
+        max = max.max(*p);
+    }
+
+    FacePolygon {
+        face,
+        faceted,
+        surface,
+        offset: normal.dot(outer[0]),
+        outer,
+        normal,
+        min,
+        max,
+        triangles,
+    }
+}
+
+/// Flip any polygon whose normal points into its own solid
+///
+/// Loop directions are not reliable across the primitives, so orientation is
+/// decided geometrically: a probe just off the face must land outside.
+fn orient_outward(polygons: &mut [FacePolygon], tol: f64) {
+    let soup: Vec<[DVec3; 3]> = polygons
+        .iter()
+        .flat_map(|p| p.triangles.iter().copied())
+        .collect();
+    let classifier = SolidClassifier::from_triangles(soup, Some(tol));
+
+    for poly in polygons.iter_mut() {
+        let Some(largest) = poly.triangles.iter().max_by(|a, b| {
+            let area = |t: &[DVec3; 3]| (t[1] - t[0]).cross(t[2] - t[0]).length();
+            area(a).total_cmp(&area(b))
+        }) else {
+            continue;
+        };
+        let centroid = (largest[0] + largest[1] + largest[2]) / 3.0;
+        let probe = centroid + poly.normal * (tol * 50.0);
+        if classifier.classify(probe) == Classification::Inside {
+            poly.flip();
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Face-face intersection
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn boxes_overlap(a: &FacePolygon, b: &FacePolygon, tol: f64) -> bool {
+    a.min.x <= b.max.x + tol
+        && a.max.x + tol >= b.min.x
+        && a.min.y <= b.max.y + tol
+        && a.max.y + tol >= b.min.y
+        && a.min.z <= b.max.z + tol
+        && a.max.z + tol >= b.min.z
+}
+
+/// True if the polygon has vertices strictly on both sides of the plane
+fn straddles(points: &[DVec3], plane: &FacePolygon, tol: f64) -> bool {
+    let mut front = false;
+    let mut back = false;
+    for p in points {
+        let d = plane.signed_distance(*p);
+        front |= d > tol;
+        back |= d < -tol;
+    }
+    front && back
+}
+
+fn is_coplanar(a: &FacePolygon, b: &FacePolygon, tol: f64) -> bool {
+    a.normal.dot(b.normal).abs() > 1.0 - 1e-9
+        && b.outer.iter().all(|p| a.signed_distance(*p).abs() < tol)
+}
+
+/// Positive-area overlap of two coplanar faces (separating axis on triangles)
+fn coplanar_overlap(a: &FacePolygon, b: &FacePolygon, tol: f64) -> bool {
+    let (u_axis, v_axis) = plane_basis(a.normal);
+    let project = |p: DVec3| glam::DVec2::new(p.dot(u_axis), p.dot(v_axis));
+
+    let separated = |ta: [glam::DVec2; 3], tb: [glam::DVec2; 3]| {
+        for tri in [ta, tb] {
+            for i in 0..3 {
+                let edge = tri[(i + 1) % 3] - tri[i];
+                let axis = glam::DVec2::new(-edge.y, edge.x).normalize_or_zero();
+                let range = |t: [glam::DVec2; 3]| {
+                    let d = t.map(|p| p.dot(axis));
+                    (d[0].min(d[1]).min(d[2]), d[0].max(d[1]).max(d[2]))
+                };
+                let (a_min, a_max) = range(ta);
+                let (b_min, b_max) = range(tb);
+                if a_max <= b_min + tol || b_max <= a_min + tol {
+                    return true;
+                }
+            }
+        }
+        false
+    };
+
+    a.triangles.iter().any(|ta| {
+        b.triangles
+            .iter()
+            .any(|tb| !separated(ta.map(project), tb.map(project)))
+    })
+}
+
+/// Per-face lists of cutting faces on the other operand
+type CutLists = Vec<Vec<usize>>;
+
+/// For each face of `a`, the faces of `b` whose planes cut through it
+///
+/// Returns `DegenerateCase` when faces overlap in a common plane.
+fn crossing_faces(
+    a: &[FacePolygon],
+    b: &[FacePolygon],
+    tol: f64,
+) -> Result<(CutLists, CutLists), BooleanError> {
+    let mut cuts_a = vec![Vec::new(); a.len()];
+    let mut cuts_b = vec![Vec::new(); b.len()];
+
+    for (i, fa) in a.iter().enumerate() {
+        for (j, fb) in b.iter().enumerate() {
+            if !boxes_overlap(fa, fb, tol) {
+                continue;
+            }
+            if is_coplanar(fa, fb, tol) {
+                if coplanar_overlap(fa, fb, tol) {
+                    return Err(BooleanError::DegenerateCase);
+                }
+                continue;
+            }
+            if straddles(&fa.outer, fb, tol) && straddles(&fb.outer, fa, tol) {
+                cuts_a[i].push(j);
+                cuts_b[j].push(i);
+            }
+        }
+    }
+
+    Ok((cuts_a, cuts_b))
+}
+
+/// Split a convex polygon by a plane into (front, back) parts
+fn split_convex(
+    points: &[DVec3],
+    plane: &FacePolygon,
+    tol: f64,
+) -> (Option<Vec<DVec3>>, Option<Vec<DVec3>>) {
+    let dists: Vec<f64> = points.iter().map(|p| plane.signed_distance(*p)).collect();
+    let has_front = dists.iter().any(|&d| d > tol);
+    let has_back = dists.iter().any(|&d| d < -tol);
+
+    if !has_back {
+        return (Some(points.to_vec()), None);
+    }
+    if !has_front {
+        return (None, Some(points.to_vec()));
+    }
+
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    for i in 0..points.len() {
+        let j = (i + 1) % points.len();
+        let (pi, pj) = (points[i], points[j]);
+        let (di, dj) = (dists[i], dists[j]);
+
+        if di >= -tol {
+            front.push(pi);
+        }
+        if di <= tol {
+            back.push(pi);
+        }
+
+        if (di > tol && dj < -tol) || (di < -tol && dj > tol) {
+            // Interpolate from a canonical endpoint so neighbours agree exactly
+            let (p0, p1, d0, d1) = if (pi.x, pi.y, pi.z) < (pj.x, pj.y, pj.z) {
+                (pi, pj, di, dj)
+            } else {
+                (pj, pi, dj, di)
+            };
+            let t = d0 / (d0 - d1);
+            let x = p0 + (p1 - p0) * t;
+            front.push(x);
+            back.push(x);
+        }
+    }
+
+    let keep = |poly: Vec<DVec3>| (poly.len() >= 3).then_some(poly);
+    (keep(front), keep(back))
+}
+
+/// A convex piece of one source face
+struct Fragment {
+    source: usize,
+    points: Vec<DVec3>,
+}
+
+/// Split every face into convex fragments along the planes that cross it
+fn fragment_faces(
+    faces: &[FacePolygon],
+    cutters: &[FacePolygon],
+    cuts: &[Vec<usize>],
+    tol: f64,
+) -> Vec<Fragment> {
+    let mut fragments = Vec::new();
+
+    for (source, face) in faces.iter().enumerate() {
+        let mut pieces: Vec<Vec<DVec3>> = face.triangles.iter().map(|t| t.to_vec()).collect();
+
+        for &cutter in &cuts[source] {
+            let plane = &cutters[cutter];
+            let mut next = Vec::with_capacity(pieces.len());
+            for piece in pieces {
+                let (front, back) = split_convex(&piece, plane, tol);
+                next.extend(front);
+                next.extend(back);
+            }
+            pieces = next;
+        }
+
+        for points in pieces {
+            let area = newell_area(&points);
+            if area > tol * tol {
+                fragments.push(Fragment { source, points });
+            }
+        }
+    }
+
+    fragments
+}
+
+fn newell_area(points: &[DVec3]) -> f64 {
+    newell_normal_d(points).length() * 0.5
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Driver
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Side {
+    A,
+    B,
+}
+
+/// A fragment selected for the result, identified by its source face
+struct KeptFragment {
+    side: Side,
+    source: usize,
+    points: Vec<DVec3>,
+}
+
+fn boolean(a: &Solid, b: &Solid, op: BooleanOp) -> Result<Solid, BooleanError> {
+    let tol = working_tolerance(a, b);
+
+    let polys_a = face_polygons(a, tol)?;
+    let mut polys_b = face_polygons(b, tol)?;
+    if polys_a.is_empty() || polys_b.is_empty() {
+        return Err(BooleanError::TopologyError(
+            "Boolean operand has no faces".to_string(),
+        ));
+    }
+
+    let soup = |polys: &[FacePolygon]| -> Vec<[DVec3; 3]> {
+        polys
+            .iter()
+            .flat_map(|p| p.triangles.iter().copied())
+            .collect()
+    };
+    let inside_a = SolidClassifier::from_triangles(soup(&polys_a), Some(tol));
+    let inside_b = SolidClassifier::from_triangles(soup(&polys_b), Some(tol));
+
+    let (cuts_a, cuts_b) = crossing_faces(&polys_a, &polys_b, tol)?;
+
+    // Touching without any crossing face pair is tangent contact
+    let any_cross = cuts_a.iter().any(|c| !c.is_empty());
+    if !any_cross {
+        let touches = polys_a
+            .iter()
+            .flat_map(|p| p.outer.iter())
+            .any(|&p| inside_b.classify(p) == Classification::OnBoundary)
+            || polys_b
+                .iter()
+                .flat_map(|p| p.outer.iter())
+                .any(|&p| inside_a.classify(p) == Classification::OnBoundary);
+        if touches {
+            return Err(BooleanError::DegenerateCase);
+        }
+    }
+
+    let frags_a = fragment_faces(&polys_a, &polys_b, &cuts_a, tol);
+    let frags_b = fragment_faces(&polys_b, &polys_a, &cuts_b, tol);
+
+    // (keep A fragments inside B?, keep B fragments inside A?)
+    let (a_inside, b_inside) = match op {
+        BooleanOp::Union => (false, false),
+        BooleanOp::Difference => (false, true),
+        BooleanOp::Intersection => (true, true),
+    };
+
+    let mut kept = Vec::new();
+    for (side, frags, other, want_inside) in [
+        (Side::A, frags_a, &inside_b, a_inside),
+        (Side::B, frags_b, &inside_a, b_inside),
+    ] {
+        for frag in frags {
+            let centroid =
+                frag.points.iter().fold(DVec3::ZERO, |acc, p| acc + *p) / frag.points.len() as f64;
+            let inside = match other.classify(centroid) {
+                Classification::Inside => true,
+                Classification::Outside => false,
+                Classification::OnBoundary => return Err(BooleanError::DegenerateCase),
+            };
+            if inside == want_inside {
+                kept.push(KeptFragment {
+                    side,
+                    source: frag.source,
+                    points: frag.points,
+                });
+            }
+        }
+    }
+
+    // Cavity walls face into the removed volume
+    if op == BooleanOp::Difference {
+        for poly in &mut polys_b {
+            poly.flip();
+        }
+        for frag in kept.iter_mut().filter(|f| f.side == Side::B) {
+            frag.points.reverse();
+        }
+    }
+
+    if kept.is_empty() {
+        return match op {
+            BooleanOp::Intersection => Err(BooleanError::NoIntersection),
+            _ => Err(BooleanError::TopologyError(
+                "Boolean result is empty".to_string(),
+            )),
+        };
+    }
+
+    build_solid(&kept, (a, &polys_a), (b, &polys_b), tol)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Shell rebuilding
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Merges nearby points into shared vertex indices
+struct Welder {
+    points: Vec<DVec3>,
+    grid: HashMap<(i64, i64, i64), Vec<usize>>,
+    tol: f64,
+}
+
+impl Welder {
+    fn new(tol: f64) -> Self {
+        Self {
+            points: Vec::new(),
+            grid: HashMap::new(),
+            tol,
+        }
+    }
+
+    fn cell(&self, p: DVec3) -> (i64, i64, i64) {
+        let size = self.tol * 4.0;
+        (
+            (p.x / size).floor() as i64,
+            (p.y / size).floor() as i64,
+            (p.z / size).floor() as i64,
+        )
+    }
+
+    fn insert(&mut self, p: DVec3) -> usize {
+        if let Some(id) = self.find(p) {
+            return id;
+        }
+        let id = self.points.len();
+        self.points.push(p);
+        let cell = self.cell(p);
+        self.grid.entry(cell).or_default().push(id);
+        id
+    }
+
+    /// Existing vertex within tolerance of `p`, without inserting
+    fn find(&self, p: DVec3) -> Option<usize> {
+        let (cx, cy, cz) = self.cell(p);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    if let Some(ids) = self.grid.get(&(cx + dx, cy + dy, cz + dz)) {
+                        if let Some(&id) = ids
+                            .iter()
+                            .find(|&&id| self.points[id].distance(p) < self.tol)
+                        {
+                            return Some(id);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Insert welded vertices that lie on polygon edges (T-junction repair)
+fn repair_t_junctions(polygon: &[usize], points: &[DVec3], tol: f64) -> Vec<usize> {
+    let mut out = Vec::with_capacity(polygon.len());
+    for i in 0..polygon.len() {
+        let (s, e) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        out.push(s);
+
+        let (ps, pe) = (points[s], points[e]);
+        let dir = pe - ps;
+        let len_sq = dir.length_squared();
+        if len_sq < tol * tol {
+            continue;
+        }
+
+        let lo = ps.min(pe) - DVec3::splat(tol);
+        let hi = ps.max(pe) + DVec3::splat(tol);
+        let mut on_edge: Vec<(f64, usize)> = points
+            .iter()
+            .enumerate()
+            .filter(|&(id, p)| id != s && id != e && p.cmpge(lo).all() && p.cmple(hi).all())
+            .filter_map(|(id, p)| {
+                let t = (*p - ps).dot(dir) / len_sq;
+                let foot = ps + dir * t;
+                (t > 0.0 && t < 1.0 && foot.distance(*p) < tol).then_some((t, id))
+            })
+            .collect();
+        on_edge.sort_by(|a, b| a.0.total_cmp(&b.0));
+        out.extend(on_edge.into_iter().map(|(_, id)| id));
+    }
+    out.dedup();
+    while out.len() > 1 && out.first() == out.last() {
+        out.pop();
+    }
+    out
+}
+
+/// Trace closed loops from a set of directed boundary edges
+fn trace_loops(edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &(s, e) in edges {
+        outgoing.entry(s).or_default().push(e);
+    }
+
+    let mut loops = Vec::new();
+    for &(start, _) in edges {
+        while outgoing.get(&start).is_some_and(|v| !v.is_empty()) {
+            let mut loop_ = vec![start];
+            let mut current = start;
+            while let Some(next) = outgoing.get_mut(&current).and_then(|v| v.pop()) {
+                if next == start {
+                    break;
+                }
+                loop_.push(next);
+                current = next;
+            }
+            if loop_.len() >= 3 {
+                loops.push(loop_);
+            }
+        }
+    }
+    loops
+}
+
+/// A rebuilt face: outer loop, holes and source surface
+struct MergedFace {
+    outer: Vec<usize>,
+    holes: Vec<Vec<usize>>,
+    surface: SurfaceType,
+    normal: DVec3,
+    orientation: FaceOrientation,
+}
+
+fn point_in_polygon_2d(p: [f64; 2], poly: &[[f64; 2]]) -> bool {
+    let mut inside = false;
+    let mut j = poly.len() - 1;
+    for i in 0..poly.len() {
+        let (a, b) = (poly[i], poly[j]);
+        if (a[1] > p[1]) != (b[1] > p[1])
+            && p[0] < (b[0] - a[0]) * (p[1] - a[1]) / (b[1] - a[1]) + a[0]
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Directed edges left on the outside of a polygon set once every edge
+/// shared with an opposite-running neighbour is cancelled
+fn boundary_edges(polygons: &[Vec<usize>]) -> Vec<(usize, usize)> {
+    let mut counts: HashMap<(usize, usize), i32> = HashMap::new();
+    for ids in polygons {
+        for i in 0..ids.len() {
+            let (s, e) = (ids[i], ids[(i + 1) % ids.len()]);
+            if s == e {
+                continue;
+            }
+            if let Some(c) = counts.get_mut(&(e, s)).filter(|c| **c > 0) {
+                *c -= 1;
+            } else {
+                *counts.entry((s, e)).or_insert(0) += 1;
+            }
+        }
+    }
+    let mut boundary: Vec<(usize, usize)> = Vec::new();
+    for (&edge, &count) in &counts {
+        for _ in 0..count {
+            boundary.push(edge);
+        }
+    }
+    boundary.sort_unstable();
+    boundary
+}
+
+/// Welded fragments of one source face, with one of its polygon indices
+type FragmentGroup = (usize, Vec<Vec<usize>>);
+
+/// Stitch kept fragments into a new solid
+fn build_solid(
+    kept: &[KeptFragment],
+    a: (&Solid, &[FacePolygon]),
+    b: (&Solid, &[FacePolygon]),
+    tol: f64,
+) -> Result<Solid, BooleanError> {
+    let operand = |side: Side| match side {
+        Side::A => a,
+        Side::B => b,
+    };
+
+    let mut welder = Welder::new(tol);
+    let mut polygons: Vec<(Side, usize, Vec<usize>)> = Vec::with_capacity(kept.len());
+    for frag in kept {
+        let mut ids: Vec<usize> = frag.points.iter().map(|&p| welder.insert(p)).collect();
+        ids.dedup();
+        while ids.len() > 1 && ids.first() == ids.last() {
+            ids.pop();
+        }
+        if ids.len() >= 3 {
+            polygons.push((frag.side, frag.source, ids));
+        }
+    }
+    let points = &welder.points;
+
+    // Group fragments by source face; every facet of a curved face lands in
+    // the same group so the face is rebuilt whole
+    let mut groups: HashMap<(Side, usize), FragmentGroup> = HashMap::new();
+    for (side, source, ids) in &polygons {
+        let face = operand(*side).1[*source].face;
+        let ids = repair_t_junctions(ids, points, tol);
+        groups
+            .entry((*side, face))
+            .or_insert_with(|| (*source, Vec::new()))
+            .1
+            .push(ids);
+    }
+
+    let mut group_keys: Vec<(Side, usize)> = groups.keys().copied().collect();
+    group_keys.sort_by_key(|&(side, face)| (side == Side::B, face));
+
+    let mut merged: Vec<MergedFace> = Vec::new();
+    for key in group_keys {
+        let (source, fragments) = &groups[&key];
+        let boundary = boundary_edges(fragments);
+        let (solid, polys) = operand(key.0);
+        let poly = &polys[*source];
+        if poly.faceted {
+            let face = &solid.faces[key.1];
+            merge_curved(solid, face, fragments, &boundary, &welder, tol, &mut merged)?;
+        } else {
+            merge_planar(poly, &boundary, points, &mut merged);
+        }
+    }
+
+    remove_collinear_vertices(&mut merged, points, tol);
+
+    let chains = curve_chains([a.0, b.0], &welder, &merged);
+    assemble(&merged, points, &chains)
+}
+
+/// Rebuild a planar face from the boundary of its kept fragments, sorting
+/// the traced loops into outlines and the holes they contain
+fn merge_planar(
+    poly: &FacePolygon,
+    boundary: &[(usize, usize)],
+    points: &[DVec3],
+    merged: &mut Vec<MergedFace>,
+) {
+    let (u_axis, v_axis) = plane_basis(poly.normal);
+    let project = |id: usize| [points[id].dot(u_axis), points[id].dot(v_axis)];
+    let area = |loop_: &[usize]| {
+        let mut a = 0.0;
+        for i in 0..loop_.len() {
+            let p = project(loop_[i]);
+            let q = project(loop_[(i + 1) % loop_.len()]);
+            a += p[0] * q[1] - q[0] * p[1];
+        }
+        a * 0.5
+    };
+
+    let loops = trace_loops(boundary);
+    let (outers, holes): (Vec<_>, Vec<_>) = loops.into_iter().partition(|l| area(l) > 0.0);
+
+    let first_face = merged.len();
+    for outer in outers {
+        merged.push(MergedFace {
+            outer,
+            holes: Vec::new(),
+            surface: poly.surface.clone(),
+            normal: poly.normal,
+            orientation: FaceOrientation::Outward,
+        });
+    }
+
+    for hole in holes {
+        let centroid = hole.iter().fold([0.0, 0.0], |acc, &id| {
+            let p = project(id);
+            [acc[0] + p[0], acc[1] + p[1]]
+        });
+        let centroid = [
+            centroid[0] / hole.len() as f64,
+            centroid[1] / hole.len() as f64,
+        ];
+        let owner = (first_face..merged.len())
+            .filter(|&f| {
+                let outline: Vec<[f64; 2]> =
+                    merged[f].outer.iter().map(|&id| project(id)).collect();
+                point_in_polygon_2d(centroid, &outline)
+            })
+            .min_by(|&x, &y| area(&merged[x].outer).total_cmp(&area(&merged[y].outer)));
+        if let Some(f) = owner {
+            merged[f].holes.push(hole);
+        }
+    }
+}
+
+/// Rebuild a curved face from its kept facets
+///
+/// A face that came through uncut keeps its original loops. A cut face
+/// becomes one face per connected piece, bounded by the traced loops; the
+/// outline of a piece with holes is the loop that still runs along the
+/// source face's outer boundary. Pieces where that is ambiguous, such as a
+/// seamed cylinder cut into a ring, are reported as `DegenerateCase`.
+fn merge_curved(
+    solid: &Solid,
+    face: &Face,
+    fragments: &[Vec<usize>],
+    boundary: &[(usize, usize)],
+    welder: &Welder,
+    tol: f64,
+    merged: &mut Vec<MergedFace>,
+) -> Result<(), BooleanError> {
+    let points = &welder.points;
+
+    if let Some(mut loops) = passthrough_loops(solid, face, boundary, welder, tol) {
+        let outer = loops.remove(0);
+        merged.push(MergedFace {
+            outer,
+            holes: loops,
+            surface: face.surface.clone(),
+            normal: DVec3::ZERO,
+            orientation: curved_orientation(&face.surface, fragments.iter(), points),
+        });
+        return Ok(());
+    }
+
+    // Connected pieces: fragments sharing an edge belong together
+    let mut parent: Vec<usize> = (0..fragments.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    let mut shared: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut owner: HashMap<(usize, usize), usize> = HashMap::new();
+    for (f, ids) in fragments.iter().enumerate() {
+        for i in 0..ids.len() {
+            let (s, e) = (ids[i], ids[(i + 1) % ids.len()]);
+            owner.insert((s, e), f);
+            match shared.get(&(s.min(e), s.max(e))) {
+                Some(&other) => {
+                    let (x, y) = (root(&mut parent, f), root(&mut parent, other));
+                    parent[x] = y;
+                }
+                None => {
+                    shared.insert((s.min(e), s.max(e)), f);
+                }
+            }
+        }
+    }
+
+    let mut pieces: Vec<(usize, Vec<Vec<usize>>)> = Vec::new();
+    for loop_ in trace_loops(boundary) {
+        let Some(&f) = owner.get(&(loop_[0], loop_[1])) else {
+            return Err(BooleanError::DegenerateCase);
+        };
+        let piece = root(&mut parent, f);
+        match pieces.iter_mut().find(|(p, _)| *p == piece) {
+            Some((_, loops)) => loops.push(loop_),
+            None => pieces.push((piece, vec![loop_])),
+        }
+    }
+
+    let original_outer: HashSet<usize> =
+        loop_points(solid, &face.outer_loop, DEFAULT_CHORD_TOLERANCE)
+            .into_iter()
+            .filter_map(|p| welder.find(to_dvec(p)))
+            .collect();
+
+    for (piece, mut loops) in pieces {
+        let outer = if loops.len() == 1 {
+            0
+        } else {
+            let mut on_outline =
+                (0..loops.len()).filter(|&l| loops[l].iter().any(|id| original_outer.contains(id)));
+            match (on_outline.next(), on_outline.next()) {
+                (Some(l), None) => l,
+                _ => return Err(BooleanError::DegenerateCase),
+            }
+        };
+        let outer = loops.remove(outer);
+        let members = (0..fragments.len())
+            .filter(|&f| root(&mut parent, f) == piece)
+            .collect::<Vec<_>>();
+        merged.push(MergedFace {
+            outer,
+            holes: loops,
+            surface: face.surface.clone(),
+            normal: DVec3::ZERO,
+            orientation: curved_orientation(
+                &face.surface,
+                members.iter().map(|&f| &fragments[f]),
+                points,
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// The original loops of `face` in welded vertex ids, when the kept facets
+/// still cover the whole face
+///
+/// Loops are reversed if the facets run the other way; seam edges cancel in
+/// the comparison because the facets on either side of a seam share them.
+fn passthrough_loops(
+    solid: &Solid,
+    face: &Face,
+    boundary: &[(usize, usize)],
+    welder: &Welder,
+    tol: f64,
+) -> Option<Vec<Vec<usize>>> {
+    let mut loops = Vec::with_capacity(1 + face.inner_loops.len());
+    for loop_ in std::iter::once(&face.outer_loop).chain(face.inner_loops.iter()) {
+        let ids = loop_points(solid, loop_, DEFAULT_CHORD_TOLERANCE)
+            .into_iter()
+            .map(|p| welder.find(to_dvec(p)))
+            .collect::<Option<Vec<usize>>>()?;
+        let ids = repair_t_junctions(&ids, &welder.points, tol);
+        if ids.len() < 3 {
+            return None;
+        }
+        loops.push(ids);
+    }
+
+    let expected = boundary_edges(&loops);
+    if expected == boundary {
+        return Some(loops);
+    }
+    let mut reversed: Vec<(usize, usize)> = expected.iter().map(|&(s, e)| (e, s)).collect();
+    reversed.sort_unstable();
+    if reversed == boundary {
+        for loop_ in &mut loops {
+            loop_.reverse();
+        }
+        return Some(loops);
+    }
+    None
+}
+
+/// Whether a curved face's outward side agrees with its surface normal,
+/// judged on its largest fragment
+fn curved_orientation<'a>(
+    surface: &SurfaceType,
+    fragments: impl Iterator<Item = &'a Vec<usize>>,
+    points: &[DVec3],
+) -> FaceOrientation {
+    let largest = fragments
+        .map(|ids| ids.iter().map(|&id| points[id]).collect::<Vec<DVec3>>())
+        .max_by(|x, y| newell_area(x).total_cmp(&newell_area(y)));
+    let Some(largest) = largest else {
+        return FaceOrientation::Outward;
+    };
+    let centroid = largest.iter().fold(DVec3::ZERO, |acc, p| acc + *p) / largest.len() as f64;
+    let n = surface.normal_at(to_point(centroid));
+    let n = DVec3::new(n.x as f64, n.y as f64, n.z as f64);
+    if newell_normal_d(&largest).dot(n) < 0.0 {
+        FaceOrientation::Inward
+    } else {
+        FaceOrientation::Outward
+    }
+}
+
+/// A curved edge of an operand that survived intact, as its run of welded
+/// sample vertices from start to end
+struct Chain {
+    ids: Vec<usize>,
+    curve: CurveType,
+}
+
+/// Find the curved operand edges whose samples still form an unbroken run in
+/// the rebuilt loops, so they can be restored as single curved edges
+fn curve_chains(solids: [&Solid; 2], welder: &Welder, faces: &[MergedFace]) -> Vec<Chain> {
+    let mut neighbours: HashMap<usize, HashSet<usize>> = HashMap::new();
+    for face in faces {
+        for loop_ in std::iter::once(&face.outer).chain(face.holes.iter()) {
+            for i in 0..loop_.len() {
+                let (s, e) = (loop_[i], loop_[(i + 1) % loop_.len()]);
+                neighbours.entry(s).or_default().insert(e);
+                neighbours.entry(e).or_default().insert(s);
+            }
+        }
+    }
+
+    let mut chains = Vec::new();
+    let mut interior: HashSet<usize> = HashSet::new();
+    let mut ends: HashSet<usize> = HashSet::new();
+    for solid in solids {
+        for edge in solid.edges.iter() {
+            if matches!(edge.curve, CurveType::Linear) {
+                continue;
+            }
+            let Some(mut ids) = edge_polyline(solid, edge, DEFAULT_CHORD_TOLERANCE)
+                .into_iter()
+                .map(|p| welder.find(to_dvec(p)))
+                .collect::<Option<Vec<usize>>>()
+            else {
+                continue;
+            };
+            ids.dedup();
+            let n = ids.len();
+            if n < 3 || ids[0] == ids[n - 1] {
+                continue;
+            }
+
+            let inner = &ids[1..n - 1];
+            let intact = (1..n - 1).all(|k| {
+                neighbours.get(&ids[k]).is_some_and(|set| {
+                    set.len() == 2 && set.contains(&ids[k - 1]) && set.contains(&ids[k + 1])
+                })
+            });
+            let overlaps = inner
+                .iter()
+                .any(|id| interior.contains(id) || ends.contains(id))
+                || [ids[0], ids[n - 1]].iter().any(|id| interior.contains(id));
+            if !intact || overlaps {
+                continue;
+            }
+
+            interior.extend(inner.iter().copied());
+            ends.extend([ids[0], ids[n - 1]]);
+            chains.push(Chain {
+                ids,
+                curve: edge.curve.clone(),
+            });
+        }
+    }
+    chains
+}
+
+/// Drop vertices that sit mid-way along a straight run between two edges
+fn remove_collinear_vertices(faces: &mut [MergedFace], points: &[DVec3], tol: f64) {
+    loop {
+        let mut neighbours: HashMap<usize, Vec<usize>> = HashMap::new();
+        for face in faces.iter() {
+            for loop_ in std::iter::once(&face.outer).chain(face.holes.iter()) {
+                for i in 0..loop_.len() {
+                    let (s, e) = (loop_[i], loop_[(i + 1) % loop_.len()]);
+                    for (x, y) in [(s, e), (e, s)] {
+                        let list = neighbours.entry(x).or_default();
+                        if !list.contains(&y) {
+                            list.push(y);
+                        }
+                    }
+                }
+            }
+        }
+
+        let removable = neighbours.iter().find_map(|(&v, n)| {
+            if n.len() != 2 {
+                return None;
+            }
+            let (a, b) = (points[n[0]], points[n[1]]);
+            let dir = b - a;
+            let t = (points[v] - a).dot(dir) / dir.length_squared();
+            let on_line = (a + dir * t).distance(points[v]) < tol;
+            (on_line && t > 0.0 && t < 1.0).then_some(v)
+        });
+
+        let Some(v) = removable else {
+            break;
+        };
+
+        let mut changed = false;
+        for face in faces.iter_mut() {
+            for loop_ in std::iter::once(&mut face.outer).chain(face.holes.iter_mut()) {
+                if loop_.len() > 3 && loop_.contains(&v) {
+                    loop_.retain(|&id| id != v);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+/// Key for an edge of the rebuilt solid: a straight segment between two
+/// welded vertices, or a restored curved edge
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum EdgeKey {
+    Line(usize, usize),
+    Chain(usize),
+}
+
+/// Turn merged index loops into a B-Rep solid with shared edges and shells
+fn assemble(
+    faces: &[MergedFace],
+    points: &[DVec3],
+    chains: &[Chain],
+) -> Result<Solid, BooleanError> {
+    let mut solid = Solid::new();
+    let mut vertex_map: HashMap<usize, VertexId> = HashMap::new();
+    let mut edge_map: HashMap<EdgeKey, EdgeId> = HashMap::new();
+
+    // Runs that may start at a vertex pair, in either direction
+    let mut chain_starts: HashMap<(usize, usize), (usize, bool)> = HashMap::new();
+    let mut interior: HashSet<usize> = HashSet::new();
+    for (c, chain) in chains.iter().enumerate() {
+        let n = chain.ids.len();
+        chain_starts.insert((chain.ids[0], chain.ids[1]), (c, true));
+        chain_starts.insert((chain.ids[n - 1], chain.ids[n - 2]), (c, false));
+        interior.extend(chain.ids[1..n - 1].iter().copied());
+    }
+
+    for merged in faces {
+        let surface = match &merged.surface {
+            SurfaceType::Planar { .. } => {
+                let n = merged.normal;
+                SurfaceType::Planar {
+                    normal: Vector3::new(n.x as f32, n.y as f32, n.z as f32),
+                }
+            }
+            other => other.clone(),
+        };
+        let face_id = solid.add_face(surface);
+
+        let mut loops = Vec::with_capacity(1 + merged.holes.len());
+        for ids in std::iter::once(&merged.outer).chain(merged.holes.iter()) {
+            // Start on a vertex that no curved run passes through
+            let n = ids.len();
+            let first = (0..n).find(|&i| !interior.contains(&ids[i])).unwrap_or(0);
+            let ids: Vec<usize> = (0..n).map(|i| ids[(first + i) % n]).collect();
+
+            let mut loop_ = Loop::new();
+            let mut i = 0;
+            while i < n {
+                let (s, next) = (ids[i], ids[(i + 1) % n]);
+                let run = chain_starts
+                    .get(&(s, next))
+                    .copied()
+                    .filter(|&(c, forward)| {
+                        let run = &chains[c].ids;
+                        let m = run.len();
+                        i + m - 1 <= n
+                            && (0..m).all(|k| {
+                                ids[(i + k) % n] == if forward { run[k] } else { run[m - 1 - k] }
+                            })
+                    });
+                let (key, (start, end), forward, step) = match run {
+                    Some((c, forward)) => {
+                        let run = &chains[c].ids;
+                        let m = run.len();
+                        (EdgeKey::Chain(c), (run[0], run[m - 1]), forward, m - 1)
+                    }
+                    None => {
+                        let (lo, hi) = (s.min(next), s.max(next));
+                        (EdgeKey::Line(lo, hi), (lo, hi), s == lo, 1)
+                    }
+                };
+
+                for id in [start, end] {
+                    vertex_map
+                        .entry(id)
+                        .or_insert_with(|| solid.add_vertex(to_point(points[id])));
+                }
+                let edge_id = match edge_map.get(&key) {
+                    Some(&id) => id,
+                    None => {
+                        let id = solid.add_edge(vertex_map[&start], vertex_map[&end]);
+                        if let EdgeKey::Chain(c) = key {
+                            solid.edges[id.0 as usize].curve = chains[c].curve.clone();
+                        }
+                        edge_map.insert(key, id);
+                        id
+                    }
+                };
+                loop_.add_edge(edge_id, forward);
+                if let Some(edge) = solid.edges.get_mut(edge_id.0 as usize) {
+                    edge.faces.push(face_id);
+                }
+                i += step;
+            }
+            loops.push(loop_);
+        }
+
+        if let Some(face) = solid.face_mut(face_id) {
+            let mut loops = loops.into_iter();
+            face.outer_loop = loops.next().unwrap_or_default();
+            face.inner_loops = loops.collect();
+            face.orientation = merged.orientation;
+        }
+    }
+
+    if solid.faces.is_empty() {
+        return Err(BooleanError::TopologyError(
+            "Boolean result has no faces".to_string(),
+        ));
+    }
+
+    // Shells are the edge-connected components of the face set
+    let mut shell_of: Vec<Option<ShellId>> = vec![None; solid.faces.len()];
+    for seed in 0..solid.faces.len() {
+        if shell_of[seed].is_some() {
+            continue;
+        }
+        let shell_id = solid.add_shell();
+        let mut stack = vec![seed];
+        shell_of[seed] = Some(shell_id);
+        let mut members = Vec::new();
+        while let Some(f) = stack.pop() {
+            members.push(FaceId(f as u32));
+            for edge_id in solid.faces[f].all_edges() {
+                for neighbour in &solid.edges[edge_id.0 as usize].faces {
+                    let n = neighbour.0 as usize;
+                    if shell_of[n].is_none() {
+                        shell_of[n] = Some(shell_id);
+                        stack.push(n);
+                    }
+                }
+            }
+        }
+
+        let closed = members.iter().all(|f| {
+            solid.faces[f.0 as usize]
+                .all_edges()
+                .iter()
+                .all(|e| solid.edges[e.0 as usize].faces.len() == 2)
+        });
+        for f in &members {
+            solid.faces[f.0 as usize].shell = Some(shell_id);
+        }
+        let shell = &mut solid.shells[shell_id.0 as usize];
+        shell.faces = members;
+        shell.is_closed = closed;
+    }
+
+    if !solid.is_valid() {
+        return Err(BooleanError::TopologyError(
+            "Boolean result failed validation".to_string(),
+        ));
+    }
+
+    Ok(solid)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cad::primitives::{make_box, make_box_at};
+    use crate::cad::mesh::solid_to_mesh;
+    use crate::cad::primitives::{make_box, make_box_at, make_cylinder_at};
 
     #[test]
     fn test_union_non_intersecting() {
@@ -207,5 +1470,188 @@ mod tests {
 
         let result = intersection(&box1, &box2);
         assert!(result.is_ok());
+
+        // The common volume is a 10mm cube from (0,0,0) to (10,10,10)
+        let solid = result.unwrap();
+        assert_eq!(solid.vertices.len(), 8);
+        assert_eq!(solid.edges.len(), 12);
+        assert_eq!(solid.faces.len(), 6);
+        assert!(solid.is_closed());
+        assert!((solid_to_mesh(&solid).volume() - 1000.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_union_overlapping() {
+        let box1 = make_box_at(Point3::new(0.0, 0.0, 0.0), 20.0, 20.0, 20.0);
+        let box2 = make_box_at(Point3::new(10.0, 10.0, 10.0), 20.0, 20.0, 20.0);
+
+        let solid = union(&box1, &box2).unwrap();
+        assert!(solid.is_closed());
+        assert_eq!(solid.shells.len(), 1);
+        assert!((solid_to_mesh(&solid).volume() - 15000.0).abs() < 1.0);
+
+        // Every edge is shared by exactly two faces
+        assert!(solid.edges.iter().all(|e| e.faces.len() == 2));
+    }
+
+    #[test]
+    fn test_difference_through_hole() {
+        let plate = make_box(40.0, 40.0, 10.0);
+        let tool = make_box(10.0, 10.0, 30.0);
+
+        let solid = difference(&plate, &tool).unwrap();
+        assert!(solid.is_closed());
+        assert!((solid_to_mesh(&solid).volume() - (16000.0 - 1000.0)).abs() < 1.0);
+
+        // Top and bottom faces each carry the hole as an inner loop
+        let holed = solid.faces.iter().filter(|f| !f.inner_loops.is_empty());
+        assert_eq!(holed.count(), 2);
+    }
+
+    #[test]
+    fn test_difference_pocket() {
+        let block = make_box(20.0, 20.0, 20.0);
+        let cutter = make_box_at(Point3::new(0.0, 0.0, 10.0), 10.0, 10.0, 10.0);
+
+        let solid = difference(&block, &cutter).unwrap();
+        assert!(solid.is_closed());
+        assert!((solid_to_mesh(&solid).volume() - (8000.0 - 500.0)).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_difference_faceted_cylinder() {
+        let block = make_box(20.0, 20.0, 10.0);
+        let pin = make_cylinder_at(Point3::ORIGIN, 4.0, 20.0, 12);
+
+        let solid = difference(&block, &pin).unwrap();
+        assert!(solid.is_closed());
+        assert!(solid_to_mesh(&solid).volume() < 4000.0);
+        assert!(solid_to_mesh(&solid).volume() > 4000.0 - std::f32::consts::PI * 16.0 * 10.0 - 1.0);
+    }
+
+    #[test]
+    fn test_boolean_true_cylinder() {
+        use crate::cad::mesh::tests::half_cylinder;
+
+        // Radius 5, height 10 half cylinder bounded by arcs, cut by a z = 2..8 slab
+        let solid = half_cylinder();
+        let slab = make_box_at(Point3::new(0.0, 0.0, 5.0), 20.0, 20.0, 6.0);
+        let half_disc = std::f32::consts::PI * 25.0 / 2.0;
+
+        let kept = difference(&solid, &slab).unwrap();
+        assert!(kept.is_valid());
+        assert!(kept.is_closed());
+        assert_eq!(kept.shells.len(), 2);
+        assert!((solid_to_mesh(&kept).volume() - half_disc * 4.0).abs() < half_disc * 0.04);
+        assert!(kept.faces.iter().any(
+            |f| matches!(f.surface, SurfaceType::Cylindrical { radius, .. } if radius == 5.0)
+        ));
+
+        // Each piece keeps one cylindrical wall and its uncut end arc
+        assert_eq!(kept.faces.len(), 8);
+        let walls = kept
+            .faces
+            .iter()
+            .filter(|f| matches!(f.surface, SurfaceType::Cylindrical { .. }));
+        assert_eq!(walls.count(), 2);
+        let arcs = kept
+            .edges
+            .iter()
+            .filter(|e| matches!(e.curve, CurveType::Arc { .. }));
+        assert_eq!(arcs.count(), 2);
+
+        let common = intersection(&solid, &slab).unwrap();
+        assert!(common.is_closed());
+        assert!((solid_to_mesh(&common).volume() - half_disc * 6.0).abs() < half_disc * 0.06);
+    }
+
+    #[test]
+    fn test_untouched_fillet_passes_through() {
+        use crate::cad::fillet::fillet_edges;
+
+        // Filleted along y = -10, z = -10; the cutter only takes the far corner
+        let block = make_box(20.0, 20.0, 20.0);
+        let filleted = fillet_edges(&block, &[EdgeId(0)], 4.0).unwrap();
+        let cutter = make_box_at(Point3::new(10.0, 10.0, 10.0), 10.0, 10.0, 10.0);
+
+        let solid = difference(&filleted, &cutter).unwrap();
+        assert!(solid.is_closed());
+        assert_eq!(solid.faces.len(), 10);
+
+        let blends: Vec<_> = solid
+            .faces
+            .iter()
+            .filter(|f| matches!(f.surface, SurfaceType::Cylindrical { .. }))
+            .collect();
+        assert_eq!(blends.len(), 1);
+        let arcs = blends[0]
+            .all_edges()
+            .iter()
+            .filter(|e| matches!(solid.edges[e.0 as usize].curve, CurveType::Arc { .. }))
+            .count();
+        assert_eq!(arcs, 2);
+
+        let expected = solid_to_mesh(&filleted).volume() - 125.0;
+        assert!((solid_to_mesh(&solid).volume() - expected).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_cut_fillet_merges_into_one_face() {
+        use crate::cad::fillet::fillet_edges;
+
+        // The cutter takes the end of the fillet at x = 10
+        let block = make_box(20.0, 20.0, 20.0);
+        let filleted = fillet_edges(&block, &[EdgeId(0)], 4.0).unwrap();
+        let cutter = make_box_at(Point3::new(10.0, -10.0, -10.0), 10.0, 10.0, 10.0);
+
+        let solid = difference(&filleted, &cutter).unwrap();
+        assert!(solid.is_closed());
+        let blends = solid
+            .faces
+            .iter()
+            .filter(|f| matches!(f.surface, SurfaceType::Cylindrical { .. }))
+            .count();
+        assert_eq!(blends, 1);
+        assert!(solid.faces.len() < 12, "{} faces", solid.faces.len());
+
+        // The untouched arc at x = -10 keeps its curve
+        assert!(solid
+            .edges
+            .iter()
+            .any(|e| matches!(e.curve, CurveType::Arc { .. })));
+    }
+
+    #[test]
+    fn test_coplanar_faces_are_degenerate() {
+        // Stacked boxes share the z = 5 plane
+        let box1 = make_box(10.0, 10.0, 10.0);
+        let box2 = make_box_at(Point3::new(0.0, 0.0, 10.0), 10.0, 10.0, 10.0);
+
+        assert!(matches!(
+            union(&box1, &box2),
+            Err(BooleanError::DegenerateCase)
+        ));
+    }
+
+    #[test]
+    fn test_tangent_contact_is_degenerate() {
+        // Edge-on contact along x = 5, z = 5
+        let box1 = make_box(10.0, 10.0, 10.0);
+        let box2 = make_box_at(Point3::new(10.0, 0.0, 10.0), 10.0, 10.0, 10.0);
+
+        assert!(matches!(
+            union(&box1, &box2),
+            Err(BooleanError::DegenerateCase)
+        ));
+    }
+
+    #[test]
+    fn test_contained_difference_creates_void() {
+        let outer = make_box(20.0, 20.0, 20.0);
+        let inner = make_box(10.0, 10.0, 10.0);
+
+        let solid = difference(&outer, &inner).unwrap();
+        assert_eq!(solid.shells.len(), 2);
+        assert!((solid_to_mesh(&solid).volume() - 7000.0).abs() < 1.0);
     }
 }
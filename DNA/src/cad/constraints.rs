@@ -6,6 +6,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 use super::sketch::{Point2, Sketch, SketchEntity, SketchEntityId, SketchPointId};
+use glam::DVec2;
 use serde::{Deserialize, Serialize};
 
 /// Geometric constraint (maintains relationships)
@@ -41,11 +42,48 @@ pub enum GeometricConstraint {
         entity2: SketchEntityId,
     },
 
-    /// Circles/arcs are concentric
+    /// Circles/arcs/ellipses are concentric
     Concentric {
         entity1: SketchEntityId,
         entity2: SketchEntityId,
     },
+
+    /// Two lines have equal length, or two arcs/circles equal radius
+    Equal {
+        entity1: SketchEntityId,
+        entity2: SketchEntityId,
+    },
+
+    /// Two points mirror each other across a line
+    Symmetric {
+        p1: SketchPointId,
+        p2: SketchPointId,
+        line: SketchEntityId,
+    },
+
+    /// Point sits at the midpoint of a line
+    Midpoint {
+        point: SketchPointId,
+        line: SketchEntityId,
+    },
+
+    /// Point lies on the (extended) line
+    PointOnLine {
+        point: SketchPointId,
+        line: SketchEntityId,
+    },
+
+    /// Point lies on the rim of a circle or arc
+    PointOnCircle {
+        point: SketchPointId,
+        entity: SketchEntityId,
+    },
+
+    /// Point is held at a fixed sketch position
+    Fix {
+        point: SketchPointId,
+        position: Point2,
+    },
 }
 
 /// Dimensional constraint (drives specific values)
@@ -93,17 +131,29 @@ pub enum Constraint {
     Dimensional(DimensionalConstraint),
 }
 
+/// One scalar equation of a constraint, linearised at the current sketch
+///
+/// The residual is zero when the equation is satisfied. The Jacobian row
+/// lists ∂residual/∂(x, y) for every point the equation depends on; a point
+/// may appear more than once (e.g. a line whose ends share a point), in which
+/// case the entries add.
+#[derive(Clone, Debug)]
+pub struct ConstraintEquation {
+    pub residual: f64,
+    pub jacobian: Vec<(SketchPointId, DVec2)>,
+}
+
 impl Constraint {
     /// Evaluate constraint error
     ///
-    /// Returns 0.0 if constraint is perfectly satisfied.
-    /// For geometric constraints, returns squared error.
-    /// For dimensional constraints, returns (actual - target)².
+    /// Returns 0.0 if constraint is perfectly satisfied, otherwise the sum of
+    /// squared residuals of its equations (e.g. (actual - target)² for a
+    /// dimension, dy² for a horizontal line).
     pub fn evaluate(&self, sketch: &Sketch) -> f32 {
-        match self {
-            Constraint::Geometric(gc) => evaluate_geometric(gc, sketch),
-            Constraint::Dimensional(dc) => evaluate_dimensional(dc, sketch),
-        }
+        self.equations(sketch)
+            .iter()
+            .map(|eq| eq.residual * eq.residual)
+            .sum::<f64>() as f32
     }
 
     /// Compute gradient (partial derivatives) for solver
@@ -112,198 +162,573 @@ impl Constraint {
     /// gradient_vector.x = ∂error/∂point.x
     /// gradient_vector.y = ∂error/∂point.y
     pub fn gradient(&self, sketch: &Sketch) -> Vec<(SketchPointId, Point2)> {
-        match self {
-            Constraint::Geometric(gc) => gradient_geometric(gc, sketch),
-            Constraint::Dimensional(dc) => gradient_dimensional(dc, sketch),
-        }
-    }
-}
-
-fn evaluate_geometric(constraint: &GeometricConstraint, sketch: &Sketch) -> f32 {
-    match constraint {
-        GeometricConstraint::Horizontal { line } => {
-            if let Some(SketchEntity::Line { start, end, .. }) = sketch.entity(*line) {
-                if let (Some(p1), Some(p2)) = (sketch.point(*start), sketch.point(*end)) {
-                    let dy = p2.position.y - p1.position.y;
-                    return dy * dy; // Squared error
+        let mut grads: Vec<(SketchPointId, DVec2)> = Vec::new();
+        for eq in self.equations(sketch) {
+            for (id, d) in eq.jacobian {
+                // ∂(r²)/∂p = 2r·∂r/∂p
+                let g = 2.0 * eq.residual * d;
+                match grads.iter_mut().find(|(existing, _)| *existing == id) {
+                    Some((_, acc)) => *acc += g,
+                    None => grads.push((id, g)),
                 }
             }
-            0.0
         }
+        grads
+            .into_iter()
+            .map(|(id, g)| (id, Point2::new(g.x as f32, g.y as f32)))
+            .collect()
+    }
 
-        GeometricConstraint::Vertical { line } => {
-            if let Some(SketchEntity::Line { start, end, .. }) = sketch.entity(*line) {
-                if let (Some(p1), Some(p2)) = (sketch.point(*start), sketch.point(*end)) {
-                    let dx = p2.position.x - p1.position.x;
-                    return dx * dx;
+    /// Residual equations with analytic Jacobian rows at the sketch's current positions
+    ///
+    /// Coincident, Concentric, Symmetric, Midpoint and Fix contribute two
+    /// equations, everything else one. Constraints that reference missing points or entities (or
+    /// combinations they do not support) contribute none.
+    pub fn equations(&self, sketch: &Sketch) -> Vec<ConstraintEquation> {
+        self.equations_at(sketch, |id| {
+            sketch
+                .point(id)
+                .map(|p| DVec2::new(p.position.x as f64, p.position.y as f64))
+        })
+    }
+
+    /// Residual equations with point positions supplied by `position`
+    ///
+    /// Used by the solver to evaluate trial positions without writing them
+    /// back to the sketch; entity topology and circle radii still come from
+    /// `sketch`.
+    pub(crate) fn equations_at<F>(&self, sketch: &Sketch, position: F) -> Vec<ConstraintEquation>
+    where
+        F: Fn(SketchPointId) -> Option<DVec2>,
+    {
+        let eqs = match self {
+            Constraint::Geometric(gc) => geometric_equations(gc, sketch, &position),
+            Constraint::Dimensional(dc) => dimensional_equations(dc, sketch, &position),
+        };
+        eqs.unwrap_or_default()
+    }
+
+    /// Circle whose radius parameter this constraint drives directly
+    ///
+    /// A circle's radius is not a point coordinate, so Radius/Diameter on a
+    /// circle (and Equal between two circles, copying the first radius onto
+    /// the second) is applied by assignment rather than by the point solver.
+    pub(crate) fn driven_radius(&self, sketch: &Sketch) -> Option<(SketchEntityId, f32)> {
+        let (entity, radius) = match self {
+            Constraint::Dimensional(DimensionalConstraint::Radius { entity, value }) => {
+                (*entity, *value)
+            }
+            Constraint::Dimensional(DimensionalConstraint::Diameter { entity, value }) => {
+                (*entity, *value * 0.5)
+            }
+            Constraint::Geometric(GeometricConstraint::Equal { entity1, entity2 }) => {
+                match sketch.entity(*entity1)? {
+                    SketchEntity::Circle { radius, .. } => (*entity2, *radius),
+                    _ => return None,
                 }
             }
-            0.0
+            _ => return None,
+        };
+        match sketch.entity(entity) {
+            Some(SketchEntity::Circle { .. }) => Some((entity, radius)),
+            _ => None,
         }
+    }
+}
 
-        GeometricConstraint::Coincident { p1, p2 } => {
-            if let (Some(pt1), Some(pt2)) = (sketch.point(*p1), sketch.point(*p2)) {
-                return pt1.position.distance_squared(&pt2.position);
-            }
-            0.0
-        }
+/// Lengths below this are treated as degenerate directions
+const DEGENERATE_LENGTH: f64 = 1e-12;
+
+/// Radius of a circular entity: a stored value (circle) or the distance from
+/// the centre to a point on the rim (arc start)
+enum CircleRadius {
+    Fixed(f64),
+    Through(SketchPointId),
+}
 
-        _ => 0.0, // Other constraints not yet implemented
+fn line_points(sketch: &Sketch, line: SketchEntityId) -> Option<(SketchPointId, SketchPointId)> {
+    match sketch.entity(line)? {
+        SketchEntity::Line { start, end, .. } => Some((*start, *end)),
+        _ => None,
     }
 }
 
-fn evaluate_dimensional(constraint: &DimensionalConstraint, sketch: &Sketch) -> f32 {
-    match constraint {
-        DimensionalConstraint::Distance { p1, p2, value } => {
-            if let (Some(pt1), Some(pt2)) = (sketch.point(*p1), sketch.point(*p2)) {
-                let actual = pt1.position.distance(&pt2.position);
-                let error = actual - value;
-                return error * error;
-            }
-            0.0
+fn circle_of(sketch: &Sketch, entity: SketchEntityId) -> Option<(SketchPointId, CircleRadius)> {
+    match sketch.entity(entity)? {
+        SketchEntity::Circle { center, radius, .. } => {
+            Some((*center, CircleRadius::Fixed(*radius as f64)))
         }
+        SketchEntity::Arc { center, start, .. } => Some((*center, CircleRadius::Through(*start))),
+        _ => None,
+    }
+}
 
-        DimensionalConstraint::HorizontalDistance { p1, p2, value } => {
-            if let (Some(pt1), Some(pt2)) = (sketch.point(*p1), sketch.point(*p2)) {
-                let actual = (pt2.position.x - pt1.position.x).abs();
-                let error = actual - value;
-                return error * error;
-            }
-            0.0
-        }
+fn center_of(sketch: &Sketch, entity: SketchEntityId) -> Option<SketchPointId> {
+    match sketch.entity(entity)? {
+        SketchEntity::Circle { center, .. }
+        | SketchEntity::Arc { center, .. }
+        | SketchEntity::Ellipse { center, .. } => Some(*center),
+        _ => None,
+    }
+}
 
-        DimensionalConstraint::VerticalDistance { p1, p2, value } => {
-            if let (Some(pt1), Some(pt2)) = (sketch.point(*p1), sketch.point(*p2)) {
-                let actual = (pt2.position.y - pt1.position.y).abs();
-                let error = actual - value;
-                return error * error;
-            }
-            0.0
-        }
+/// Signed height h = cross(b - a, q - a) / |b - a| of `q` above the line a→b
+/// with ∂h/∂a, ∂h/∂b and ∂h/∂q
+fn line_height(a: DVec2, b: DVec2, q: DVec2) -> Option<(f64, DVec2, DVec2, DVec2)> {
+    let d = b - a;
+    let w = q - a;
+    let len = d.length();
+    if len <= DEGENERATE_LENGTH {
+        return None;
+    }
+    let h = cross(d, w) / len;
+    let dh_dd = DVec2::new(w.y, -w.x) / len - h * d / (len * len);
+    let dh_dq = DVec2::new(-d.y, d.x) / len;
+    Some((h, -(dh_dd + dh_dq), dh_dd, dh_dq))
+}
 
-        DimensionalConstraint::Radius { entity, value } => {
-            if let Some(SketchEntity::Circle { radius, .. }) = sketch.entity(*entity) {
-                let error = radius - value;
-                return error * error;
-            }
-            0.0
+/// Radius value and its gradient with respect to the points defining it
+fn radius_value<F>(
+    center: SketchPointId,
+    radius: &CircleRadius,
+    position: &F,
+) -> Option<(f64, Vec<(SketchPointId, DVec2)>)>
+where
+    F: Fn(SketchPointId) -> Option<DVec2>,
+{
+    match radius {
+        CircleRadius::Fixed(r) => Some((*r, Vec::new())),
+        CircleRadius::Through(rim) => {
+            let d = position(*rim)? - position(center)?;
+            let len = d.length();
+            let u = if len > DEGENERATE_LENGTH {
+                d / len
+            } else {
+                DVec2::X
+            };
+            Some((len, vec![(*rim, u), (center, -u)]))
         }
+    }
+}
+
+/// Unit direction of `d`, falling back to +X for a zero-length vector so the
+/// Jacobian row never vanishes spuriously
+fn direction(d: DVec2) -> (f64, DVec2) {
+    let len = d.length();
+    if len > DEGENERATE_LENGTH {
+        (len, d / len)
+    } else {
+        (len, DVec2::X)
+    }
+}
+
+fn sign(v: f64) -> f64 {
+    if v >= 0.0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+fn cross(a: DVec2, b: DVec2) -> f64 {
+    a.x * b.y - a.y * b.x
+}
 
-        _ => 0.0, // Other constraints not yet implemented
+fn wrap_angle(a: f64) -> f64 {
+    let two_pi = std::f64::consts::TAU;
+    let wrapped = a.rem_euclid(two_pi);
+    if wrapped > std::f64::consts::PI {
+        wrapped - two_pi
+    } else {
+        wrapped
     }
 }
 
-fn gradient_geometric(
+fn equation(residual: f64, jacobian: Vec<(SketchPointId, DVec2)>) -> ConstraintEquation {
+    ConstraintEquation { residual, jacobian }
+}
+
+/// Two equations pulling `b` onto `a` component-wise
+fn coincidence<F>(
+    a: SketchPointId,
+    b: SketchPointId,
+    position: &F,
+) -> Option<Vec<ConstraintEquation>>
+where
+    F: Fn(SketchPointId) -> Option<DVec2>,
+{
+    let d = position(b)? - position(a)?;
+    Some(vec![
+        equation(d.x, vec![(a, -DVec2::X), (b, DVec2::X)]),
+        equation(d.y, vec![(a, -DVec2::Y), (b, DVec2::Y)]),
+    ])
+}
+
+/// Line directions (start, end, vector, length) for two-line constraints
+type LineDir = (SketchPointId, SketchPointId, DVec2, f64);
+
+fn two_lines<F>(
+    sketch: &Sketch,
+    line1: SketchEntityId,
+    line2: SketchEntityId,
+    position: &F,
+) -> Option<(LineDir, LineDir)>
+where
+    F: Fn(SketchPointId) -> Option<DVec2>,
+{
+    let (a1, b1) = line_points(sketch, line1)?;
+    let (a2, b2) = line_points(sketch, line2)?;
+    let d1 = position(b1)? - position(a1)?;
+    let d2 = position(b2)? - position(a2)?;
+    let (l1, l2) = (d1.length(), d2.length());
+    if l1 <= DEGENERATE_LENGTH || l2 <= DEGENERATE_LENGTH {
+        return None;
+    }
+    Some(((a1, b1, d1, l1), (a2, b2, d2, l2)))
+}
+
+/// Equation over two line directions given ∂f/∂d1 and ∂f/∂d2
+fn two_line_equation(
+    residual: f64,
+    (a1, b1, ..): LineDir,
+    g1: DVec2,
+    (a2, b2, ..): LineDir,
+    g2: DVec2,
+) -> ConstraintEquation {
+    equation(residual, vec![(a1, -g1), (b1, g1), (a2, -g2), (b2, g2)])
+}
+
+fn geometric_equations<F>(
     constraint: &GeometricConstraint,
     sketch: &Sketch,
-) -> Vec<(SketchPointId, Point2)> {
+    position: &F,
+) -> Option<Vec<ConstraintEquation>>
+where
+    F: Fn(SketchPointId) -> Option<DVec2>,
+{
     match constraint {
         GeometricConstraint::Horizontal { line } => {
-            if let Some(SketchEntity::Line { start, end, .. }) = sketch.entity(*line) {
-                if let (Some(p1), Some(p2)) = (sketch.point(*start), sketch.point(*end)) {
-                    let dy = p2.position.y - p1.position.y;
-                    // ∂(dy²)/∂y1 = -2dy, ∂(dy²)/∂y2 = +2dy
-                    return vec![
-                        (*start, Point2::new(0.0, -2.0 * dy)),
-                        (*end, Point2::new(0.0, 2.0 * dy)),
-                    ];
-                }
-            }
-            Vec::new()
+            let (a, b) = line_points(sketch, *line)?;
+            let dy = position(b)?.y - position(a)?.y;
+            Some(vec![equation(dy, vec![(a, -DVec2::Y), (b, DVec2::Y)])])
         }
 
         GeometricConstraint::Vertical { line } => {
-            if let Some(SketchEntity::Line { start, end, .. }) = sketch.entity(*line) {
-                if let (Some(p1), Some(p2)) = (sketch.point(*start), sketch.point(*end)) {
-                    let dx = p2.position.x - p1.position.x;
-                    // ∂(dx²)/∂x1 = -2dx, ∂(dx²)/∂x2 = +2dx
-                    return vec![
-                        (*start, Point2::new(-2.0 * dx, 0.0)),
-                        (*end, Point2::new(2.0 * dx, 0.0)),
-                    ];
+            let (a, b) = line_points(sketch, *line)?;
+            let dx = position(b)?.x - position(a)?.x;
+            Some(vec![equation(dx, vec![(a, -DVec2::X), (b, DVec2::X)])])
+        }
+
+        GeometricConstraint::Parallel { line1, line2 } => {
+            // sin of the included angle: cross(d1, d2) / (|d1||d2|)
+            let (l1, l2) = two_lines(sketch, *line1, *line2, position)?;
+            let (d1, n1, d2, n2) = (l1.2, l1.3, l2.2, l2.3);
+            let f = cross(d1, d2) / (n1 * n2);
+            let g1 = DVec2::new(d2.y, -d2.x) / (n1 * n2) - f * d1 / (n1 * n1);
+            let g2 = DVec2::new(-d1.y, d1.x) / (n1 * n2) - f * d2 / (n2 * n2);
+            Some(vec![two_line_equation(f, l1, g1, l2, g2)])
+        }
+
+        GeometricConstraint::Perpendicular { line1, line2 } => {
+            // cos of the included angle: dot(d1, d2) / (|d1||d2|)
+            let (l1, l2) = two_lines(sketch, *line1, *line2, position)?;
+            let (d1, n1, d2, n2) = (l1.2, l1.3, l2.2, l2.3);
+            let f = d1.dot(d2) / (n1 * n2);
+            let g1 = d2 / (n1 * n2) - f * d1 / (n1 * n1);
+            let g2 = d1 / (n1 * n2) - f * d2 / (n2 * n2);
+            Some(vec![two_line_equation(f, l1, g1, l2, g2)])
+        }
+
+        GeometricConstraint::Coincident { p1, p2 } => coincidence(*p1, *p2, position),
+
+        GeometricConstraint::Tangent { entity1, entity2 } => {
+            let line_and_circle = match line_points(sketch, *entity1) {
+                Some(line) => Some((line, *entity2)),
+                None => line_points(sketch, *entity2).map(|line| (line, *entity1)),
+            };
+            if let Some((line, circle)) = line_and_circle {
+                let (center, radius) = circle_of(sketch, circle)?;
+                match shared_endpoint(sketch, line, circle) {
+                    Some(joint) => endpoint_tangency(line, center, joint, position),
+                    None => line_circle_tangency(line, center, &radius, position),
                 }
+                .map(|eq| vec![eq])
+            } else {
+                let (c1, r1) = circle_of(sketch, *entity1)?;
+                let (c2, r2) = circle_of(sketch, *entity2)?;
+                circle_circle_tangency((c1, &r1), (c2, &r2), position).map(|eq| vec![eq])
             }
-            Vec::new()
         }
 
-        GeometricConstraint::Coincident { p1, p2 } => {
-            if let (Some(pt1), Some(pt2)) = (sketch.point(*p1), sketch.point(*p2)) {
-                let dx = pt2.position.x - pt1.position.x;
-                let dy = pt2.position.y - pt1.position.y;
-                // ∂((dx² + dy²))/∂p1 = -2(dx, dy), ∂/∂p2 = +2(dx, dy)
-                return vec![
-                    (*p1, Point2::new(-2.0 * dx, -2.0 * dy)),
-                    (*p2, Point2::new(2.0 * dx, 2.0 * dy)),
-                ];
+        GeometricConstraint::Concentric { entity1, entity2 } => {
+            let c1 = center_of(sketch, *entity1)?;
+            let c2 = center_of(sketch, *entity2)?;
+            coincidence(c1, c2, position)
+        }
+
+        GeometricConstraint::Equal { entity1, entity2 } => {
+            if let (Some((a1, b1)), Some((a2, b2))) =
+                (line_points(sketch, *entity1), line_points(sketch, *entity2))
+            {
+                let (l1, u1) = direction(position(b1)? - position(a1)?);
+                let (l2, u2) = direction(position(b2)? - position(a2)?);
+                return Some(vec![equation(
+                    l2 - l1,
+                    vec![(a1, u1), (b1, -u1), (a2, -u2), (b2, u2)],
+                )]);
             }
-            Vec::new()
+            let (c1, r1) = circle_of(sketch, *entity1)?;
+            let (c2, r2) = circle_of(sketch, *entity2)?;
+            let (r1, g1) = radius_value(c1, &r1, position)?;
+            let (r2, g2) = radius_value(c2, &r2, position)?;
+            let mut jacobian = g2;
+            jacobian.extend(g1.into_iter().map(|(id, g)| (id, -g)));
+            Some(vec![equation(r2 - r1, jacobian)])
+        }
+
+        GeometricConstraint::Symmetric { p1, p2, line } => {
+            // Midpoint on the line, and p1→p2 perpendicular to it
+            let (a, b) = line_points(sketch, *line)?;
+            let (pa, pb) = (position(a)?, position(b)?);
+            let (q1, q2) = (position(*p1)?, position(*p2)?);
+            let (h, dh_da, dh_db, dh_dm) = line_height(pa, pb, 0.5 * (q1 + q2))?;
+
+            let (len, u) = direction(pb - pa);
+            let v = q2 - q1;
+            let along = v.dot(u);
+            let dg_dd = v / len - along * u / len;
+            Some(vec![
+                equation(
+                    h,
+                    vec![
+                        (a, dh_da),
+                        (b, dh_db),
+                        (*p1, 0.5 * dh_dm),
+                        (*p2, 0.5 * dh_dm),
+                    ],
+                ),
+                equation(along, vec![(*p1, -u), (*p2, u), (a, -dg_dd), (b, dg_dd)]),
+            ])
+        }
+
+        GeometricConstraint::Midpoint { point, line } => {
+            let (a, b) = line_points(sketch, *line)?;
+            let d = position(*point)? - 0.5 * (position(a)? + position(b)?);
+            Some(
+                [DVec2::X, DVec2::Y]
+                    .into_iter()
+                    .map(|axis| {
+                        equation(
+                            d.dot(axis),
+                            vec![(*point, axis), (a, -0.5 * axis), (b, -0.5 * axis)],
+                        )
+                    })
+                    .collect(),
+            )
+        }
+
+        GeometricConstraint::PointOnLine { point, line } => {
+            let (a, b) = line_points(sketch, *line)?;
+            let (h, dh_da, dh_db, dh_dq) =
+                line_height(position(a)?, position(b)?, position(*point)?)?;
+            Some(vec![equation(
+                h,
+                vec![(a, dh_da), (b, dh_db), (*point, dh_dq)],
+            )])
         }
 
-        _ => Vec::new(),
+        GeometricConstraint::PointOnCircle { point, entity } => {
+            let (center, radius) = circle_of(sketch, *entity)?;
+            let (dist, u) = direction(position(*point)? - position(center)?);
+            let (r, r_grad) = radius_value(center, &radius, position)?;
+            let mut jacobian = vec![(*point, u), (center, -u)];
+            jacobian.extend(r_grad.into_iter().map(|(id, g)| (id, -g)));
+            Some(vec![equation(dist - r, jacobian)])
+        }
+
+        GeometricConstraint::Fix {
+            point,
+            position: target,
+        } => {
+            let d = position(*point)? - DVec2::new(target.x as f64, target.y as f64);
+            Some(vec![
+                equation(d.x, vec![(*point, DVec2::X)]),
+                equation(d.y, vec![(*point, DVec2::Y)]),
+            ])
+        }
     }
 }
 
-fn gradient_dimensional(
-    constraint: &DimensionalConstraint,
+/// Line endpoint that is also an endpoint of the arc, if any
+fn shared_endpoint(
     sketch: &Sketch,
-) -> Vec<(SketchPointId, Point2)> {
-    match constraint {
-        DimensionalConstraint::Distance { p1, p2, value } => {
-            if let (Some(pt1), Some(pt2)) = (sketch.point(*p1), sketch.point(*p2)) {
-                let dx = pt2.position.x - pt1.position.x;
-                let dy = pt2.position.y - pt1.position.y;
-                let dist = (dx * dx + dy * dy).sqrt();
+    (a, b): (SketchPointId, SketchPointId),
+    arc: SketchEntityId,
+) -> Option<SketchPointId> {
+    match sketch.entity(arc)? {
+        SketchEntity::Arc { start, end, .. } => [a, b].into_iter().find(|p| p == start || p == end),
+        _ => None,
+    }
+}
 
-                if dist < 1e-8 {
-                    return Vec::new(); // Degenerate
-                }
+/// Tangency at a joint shared by the line and the arc: the line is
+/// perpendicular to the radius there (cosine of the angle between them)
+///
+/// The distance form below degenerates at such a joint: the joint already
+/// lies on both curves, so distance - radius peaks at zero and its gradient
+/// vanishes.
+fn endpoint_tangency<F>(
+    (a, b): (SketchPointId, SketchPointId),
+    center: SketchPointId,
+    joint: SketchPointId,
+    position: &F,
+) -> Option<ConstraintEquation>
+where
+    F: Fn(SketchPointId) -> Option<DVec2>,
+{
+    let d = position(b)? - position(a)?;
+    let rho = position(joint)? - position(center)?;
+    let (nd, nr) = (d.length(), rho.length());
+    if nd <= DEGENERATE_LENGTH || nr <= DEGENERATE_LENGTH {
+        return None;
+    }
+    let f = d.dot(rho) / (nd * nr);
+    let g_d = rho / (nd * nr) - f * d / (nd * nd);
+    let g_rho = d / (nd * nr) - f * rho / (nr * nr);
+    Some(equation(
+        f,
+        vec![(a, -g_d), (b, g_d), (joint, g_rho), (center, -g_rho)],
+    ))
+}
 
-                let error = dist - value;
-                // ∂((dist - d)²)/∂p1 = -2(error)(dx/dist, dy/dist)
-                let grad_mag = 2.0 * error / dist;
+/// |distance from centre to line| - radius
+fn line_circle_tangency<F>(
+    (a, b): (SketchPointId, SketchPointId),
+    center: SketchPointId,
+    radius: &CircleRadius,
+    position: &F,
+) -> Option<ConstraintEquation>
+where
+    F: Fn(SketchPointId) -> Option<DVec2>,
+{
+    let (h, dh_da, dh_db, dh_dc) = line_height(position(a)?, position(b)?, position(center)?)?;
+    let (r, r_grad) = radius_value(center, radius, position)?;
+
+    let s = sign(h);
+    let mut jacobian = vec![(a, s * dh_da), (b, s * dh_db), (center, s * dh_dc)];
+    jacobian.extend(r_grad.into_iter().map(|(id, g)| (id, -g)));
+    Some(equation(h.abs() - r, jacobian))
+}
 
-                return vec![
-                    (*p1, Point2::new(-grad_mag * dx, -grad_mag * dy)),
-                    (*p2, Point2::new(grad_mag * dx, grad_mag * dy)),
-                ];
-            }
-            Vec::new()
+/// Centre distance minus the external (r1 + r2) or internal (|r1 - r2|)
+/// tangency distance, whichever is closer to being satisfied
+fn circle_circle_tangency<F>(
+    (c1, r1): (SketchPointId, &CircleRadius),
+    (c2, r2): (SketchPointId, &CircleRadius),
+    position: &F,
+) -> Option<ConstraintEquation>
+where
+    F: Fn(SketchPointId) -> Option<DVec2>,
+{
+    let (dist, u) = direction(position(c2)? - position(c1)?);
+    let (r1, g1) = radius_value(c1, r1, position)?;
+    let (r2, g2) = radius_value(c2, r2, position)?;
+
+    let external = dist - (r1 + r2);
+    let internal = dist - (r1 - r2).abs();
+    let mut jacobian = vec![(c2, u), (c1, -u)];
+    let residual = if external.abs() <= internal.abs() {
+        jacobian.extend(g1.into_iter().map(|(id, g)| (id, -g)));
+        jacobian.extend(g2.into_iter().map(|(id, g)| (id, -g)));
+        external
+    } else {
+        let s = sign(r1 - r2);
+        jacobian.extend(g1.into_iter().map(|(id, g)| (id, -s * g)));
+        jacobian.extend(g2.into_iter().map(|(id, g)| (id, s * g)));
+        internal
+    };
+    Some(equation(residual, jacobian))
+}
+
+fn dimensional_equations<F>(
+    constraint: &DimensionalConstraint,
+    sketch: &Sketch,
+    position: &F,
+) -> Option<Vec<ConstraintEquation>>
+where
+    F: Fn(SketchPointId) -> Option<DVec2>,
+{
+    match constraint {
+        DimensionalConstraint::Distance { p1, p2, value } => {
+            let (dist, u) = direction(position(*p2)? - position(*p1)?);
+            Some(vec![equation(
+                dist - *value as f64,
+                vec![(*p1, -u), (*p2, u)],
+            )])
         }
 
         DimensionalConstraint::HorizontalDistance { p1, p2, value } => {
-            if let (Some(pt1), Some(pt2)) = (sketch.point(*p1), sketch.point(*p2)) {
-                let dx = pt2.position.x - pt1.position.x;
-                let actual = dx.abs();
-                let error = actual - value;
-                let sign = if dx >= 0.0 { 1.0 } else { -1.0 };
-
-                return vec![
-                    (*p1, Point2::new(-2.0 * error * sign, 0.0)),
-                    (*p2, Point2::new(2.0 * error * sign, 0.0)),
-                ];
-            }
-            Vec::new()
+            let dx = position(*p2)?.x - position(*p1)?.x;
+            let g = sign(dx) * DVec2::X;
+            Some(vec![equation(
+                dx.abs() - *value as f64,
+                vec![(*p1, -g), (*p2, g)],
+            )])
         }
 
         DimensionalConstraint::VerticalDistance { p1, p2, value } => {
-            if let (Some(pt1), Some(pt2)) = (sketch.point(*p1), sketch.point(*p2)) {
-                let dy = pt2.position.y - pt1.position.y;
-                let actual = dy.abs();
-                let error = actual - value;
-                let sign = if dy >= 0.0 { 1.0 } else { -1.0 };
-
-                return vec![
-                    (*p1, Point2::new(0.0, -2.0 * error * sign)),
-                    (*p2, Point2::new(0.0, 2.0 * error * sign)),
-                ];
-            }
-            Vec::new()
+            let dy = position(*p2)?.y - position(*p1)?.y;
+            let g = sign(dy) * DVec2::Y;
+            Some(vec![equation(
+                dy.abs() - *value as f64,
+                vec![(*p1, -g), (*p2, g)],
+            )])
+        }
+
+        DimensionalConstraint::Angle {
+            line1,
+            line2,
+            value,
+        } => {
+            // Signed angle from line1 to line2, wrapped to (-π, π]
+            let (l1, l2) = two_lines(sketch, *line1, *line2, position)?;
+            let (d1, n1, d2, n2) = (l1.2, l1.3, l2.2, l2.3);
+            let theta = cross(d1, d2).atan2(d1.dot(d2));
+            let g1 = DVec2::new(d1.y, -d1.x) / (n1 * n1);
+            let g2 = DVec2::new(-d2.y, d2.x) / (n2 * n2);
+            Some(vec![two_line_equation(
+                wrap_angle(theta - *value as f64),
+                l1,
+                g1,
+                l2,
+                g2,
+            )])
         }
 
-        _ => Vec::new(),
+        DimensionalConstraint::Radius { entity, value } => {
+            radius_equation(sketch, *entity, *value as f64, position)
+        }
+
+        DimensionalConstraint::Diameter { entity, value } => {
+            radius_equation(sketch, *entity, *value as f64 * 0.5, position)
+        }
     }
 }
 
+fn radius_equation<F>(
+    sketch: &Sketch,
+    entity: SketchEntityId,
+    target: f64,
+    position: &F,
+) -> Option<Vec<ConstraintEquation>>
+where
+    F: Fn(SketchPointId) -> Option<DVec2>,
+{
+    let (center, radius) = circle_of(sketch, entity)?;
+    let (r, jacobian) = radius_value(center, &radius, position)?;
+    Some(vec![equation(r - target, jacobian)])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -343,4 +768,163 @@ mod tests {
         let grads = constraint.gradient(&sketch);
         assert_eq!(grads.len(), 2);
     }
+
+    #[test]
+    fn test_equation_jacobians_match_finite_differences() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let a0 = sketch.add_point(Point2::new(0.0, 0.0));
+        let a1 = sketch.add_point(Point2::new(10.0, 1.5));
+        let b0 = sketch.add_point(Point2::new(1.0, 4.0));
+        let b1 = sketch.add_point(Point2::new(7.0, 9.0));
+        let c = sketch.add_point(Point2::new(4.0, -3.0));
+        let rim = sketch.add_point(Point2::new(6.0, -2.0));
+        let arc_end = sketch.add_point(Point2::new(2.0, -2.0));
+        sketch.add_entity(SketchEntity::Line {
+            id: SketchEntityId(0),
+            start: a0,
+            end: a1,
+        });
+        sketch.add_entity(SketchEntity::Line {
+            id: SketchEntityId(1),
+            start: b0,
+            end: b1,
+        });
+        sketch.add_entity(SketchEntity::Arc {
+            id: SketchEntityId(2),
+            center: c,
+            start: rim,
+            end: arc_end,
+            radius: 5f32.sqrt(),
+            ccw: true,
+        });
+        sketch.add_entity(SketchEntity::Circle {
+            id: SketchEntityId(3),
+            center: b1,
+            radius: 2.0,
+        });
+        sketch.add_entity(SketchEntity::Line {
+            id: SketchEntityId(4),
+            start: rim,
+            end: b0,
+        });
+
+        let (l0, l1, arc, circle) = (
+            SketchEntityId(0),
+            SketchEntityId(1),
+            SketchEntityId(2),
+            SketchEntityId(3),
+        );
+        let constraints = [
+            Constraint::Geometric(GeometricConstraint::Parallel {
+                line1: l0,
+                line2: l1,
+            }),
+            Constraint::Geometric(GeometricConstraint::Perpendicular {
+                line1: l0,
+                line2: l1,
+            }),
+            Constraint::Geometric(GeometricConstraint::Tangent {
+                entity1: l0,
+                entity2: arc,
+            }),
+            Constraint::Geometric(GeometricConstraint::Tangent {
+                entity1: circle,
+                entity2: arc,
+            }),
+            Constraint::Geometric(GeometricConstraint::Concentric {
+                entity1: arc,
+                entity2: circle,
+            }),
+            Constraint::Dimensional(DimensionalConstraint::Angle {
+                line1: l0,
+                line2: l1,
+                value: 0.3,
+            }),
+            Constraint::Dimensional(DimensionalConstraint::Radius {
+                entity: arc,
+                value: 1.0,
+            }),
+            Constraint::Dimensional(DimensionalConstraint::Distance {
+                p1: a0,
+                p2: c,
+                value: 2.0,
+            }),
+            Constraint::Geometric(GeometricConstraint::Equal {
+                entity1: l0,
+                entity2: l1,
+            }),
+            Constraint::Geometric(GeometricConstraint::Equal {
+                entity1: circle,
+                entity2: arc,
+            }),
+            Constraint::Geometric(GeometricConstraint::Symmetric {
+                p1: c,
+                p2: rim,
+                line: l1,
+            }),
+            Constraint::Geometric(GeometricConstraint::Midpoint {
+                point: arc_end,
+                line: l0,
+            }),
+            Constraint::Geometric(GeometricConstraint::PointOnLine { point: c, line: l1 }),
+            Constraint::Geometric(GeometricConstraint::PointOnCircle {
+                point: arc_end,
+                entity: arc,
+            }),
+            Constraint::Geometric(GeometricConstraint::Fix {
+                point: b0,
+                position: Point2::new(-1.0, 2.0),
+            }),
+            // Joint tangency: the line starts at the arc's start point
+            Constraint::Geometric(GeometricConstraint::Tangent {
+                entity1: arc,
+                entity2: SketchEntityId(4),
+            }),
+        ];
+
+        let base: Vec<DVec2> = sketch
+            .points
+            .iter()
+            .map(|p| DVec2::new(p.position.x as f64, p.position.y as f64))
+            .collect();
+        let h = 1e-6;
+
+        for constraint in &constraints {
+            let eqs = constraint.equations(&sketch);
+            assert!(!eqs.is_empty(), "{:?} produced no equations", constraint);
+
+            for (k, eq) in eqs.iter().enumerate() {
+                for p in 0..base.len() {
+                    let analytic: DVec2 = eq
+                        .jacobian
+                        .iter()
+                        .filter(|(id, _)| id.0 as usize == p)
+                        .map(|(_, d)| *d)
+                        .sum();
+                    for axis in [DVec2::X, DVec2::Y] {
+                        let at = |s: f64| {
+                            let moved = constraint.equations_at(&sketch, |id| {
+                                let mut q = *base.get(id.0 as usize)?;
+                                if id.0 as usize == p {
+                                    q += s * axis;
+                                }
+                                Some(q)
+                            });
+                            moved[k].residual
+                        };
+                        let numeric = (at(h) - at(-h)) / (2.0 * h);
+                        assert!(
+                            (numeric - analytic.dot(axis)).abs() < 1e-5,
+                            "{:?} eq {} point {}: numeric {} vs analytic {}",
+                            constraint,
+                            k,
+                            p,
+                            numeric,
+                            analytic.dot(axis)
+                        );
+                    }
+                }
+            }
+        }
+    }
 }
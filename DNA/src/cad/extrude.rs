@@ -6,13 +6,12 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 use super::geometry::{Point3, Vector3};
-use super::sketch::{Point2, Sketch, SketchEntity, SketchEntityId};
+use super::sketch::{Point2, Sketch};
 use super::topology::{
     CurveType, Edge, EdgeId, Face, FaceId, FaceOrientation, Loop, Shell, ShellId, Solid,
     SurfaceType, Vertex, VertexId,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
 
 /// Extrusion parameters
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -43,28 +42,12 @@ pub enum ExtrudeError {
 /// Finds closed loops in the sketch and extrudes them along the plane normal.
 /// Returns a B-Rep solid with proper topology.
 pub fn extrude_sketch(sketch: &Sketch, params: &ExtrudeParams) -> Result<Solid, ExtrudeError> {
-    // Find closed loops
-    let loops = find_closed_loops(sketch)?;
-    if loops.is_empty() {
-        return Err(ExtrudeError::NoClosedProfile);
-    }
-
-    // For simplicity, extrude the first closed loop
-    let loop_entities = &loops[0];
+    // Chain the profile's lines, arcs, splines, circles and ellipses
+    let loop_points = closed_profile(sketch)?;
 
     // Build solid
     let mut solid = Solid::new();
 
-    // Get all points in the loop
-    let loop_points: Vec<Point2> = loop_entities
-        .iter()
-        .filter_map(|&entity_id| get_entity_start_point(sketch, entity_id))
-        .collect();
-
-    if loop_points.is_empty() {
-        return Err(ExtrudeError::InvalidGeometry);
-    }
-
     // Calculate extrusion offsets
     let (z_start, z_end) = if params.symmetric {
         (-params.distance / 2.0, params.distance / 2.0)
@@ -219,42 +202,19 @@ pub fn extrude_sketch(sketch: &Sketch, params: &ExtrudeParams) -> Result<Solid,
     Ok(solid)
 }
 
-/// Find closed loops in the sketch
-fn find_closed_loops(sketch: &Sketch) -> Result<Vec<Vec<SketchEntityId>>, ExtrudeError> {
-    // Build adjacency graph
-    let mut graph: HashMap<SketchEntityId, Vec<SketchEntityId>> = HashMap::new();
-
-    for entity in &sketch.entities {
-        if let SketchEntity::Line { id, .. } = entity {
-            graph.entry(*id).or_default();
-        }
-    }
-
-    // For now, return all line entities as a single loop
-    // Full implementation would use DFS to find actual closed loops
-    let line_entities: Vec<SketchEntityId> = sketch
-        .entities
-        .iter()
-        .filter_map(|e| match e {
-            SketchEntity::Line { id, .. } => Some(*id),
-            _ => None,
-        })
-        .collect();
-
-    if line_entities.is_empty() {
+/// Closed profile polyline, without the repeated closing point
+fn closed_profile(sketch: &Sketch) -> Result<Vec<Point2>, ExtrudeError> {
+    let (mut points, closed) = sketch
+        .chain_profile()
+        .ok_or(ExtrudeError::NoClosedProfile)?;
+    if !closed {
         return Err(ExtrudeError::NoClosedProfile);
     }
-
-    Ok(vec![line_entities])
-}
-
-/// Get start point of an entity
-fn get_entity_start_point(sketch: &Sketch, entity_id: SketchEntityId) -> Option<Point2> {
-    sketch.entity(entity_id).and_then(|entity| match entity {
-        SketchEntity::Line { start, .. } => sketch.point(*start).map(|p| p.position),
-        SketchEntity::Arc { start, .. } => sketch.point(*start).map(|p| p.position),
-        _ => None,
-    })
+    points.pop();
+    if points.len() < 3 {
+        return Err(ExtrudeError::InvalidGeometry);
+    }
+    Ok(points)
 }
 
 #[cfg(test)]
@@ -305,4 +265,40 @@ mod tests {
         // Should have 8 vertices (4 bottom + 4 top)
         assert_eq!(solid.vertices.len(), 8);
     }
+
+    #[test]
+    fn test_extrude_curved_profiles() {
+        // Slot: lines and arcs chained into one loop
+        let mut slot = Sketch::new(SketchPlane::XY);
+        slot.add_slot(Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), 2.0);
+        let (profile, _) = slot.chain_profile().unwrap();
+        let solid = extrude_sketch(&slot, &ExtrudeParams::default()).unwrap();
+        assert_eq!(solid.vertices.len(), 2 * (profile.len() - 1));
+
+        // Ellipse on its own is a closed profile
+        let mut ellipse = Sketch::new(SketchPlane::XY);
+        let center = ellipse.add_point(Point2::new(0.0, 0.0));
+        let major = ellipse.add_point(Point2::new(4.0, 0.0));
+        ellipse.add_entity(SketchEntity::Ellipse {
+            id: SketchEntityId(0),
+            center,
+            major,
+            minor_radius: 2.0,
+        });
+        assert!(extrude_sketch(&ellipse, &ExtrudeParams::default()).is_ok());
+
+        // An open spline is not
+        let mut open = Sketch::new(SketchPlane::XY);
+        let control =
+            [(0.0, 0.0), (2.0, 3.0), (5.0, 0.0)].map(|(x, y)| open.add_point(Point2::new(x, y)));
+        open.add_entity(SketchEntity::Spline {
+            id: SketchEntityId(0),
+            control_points: control.to_vec(),
+            degree: 2,
+        });
+        assert!(matches!(
+            extrude_sketch(&open, &ExtrudeParams::default()),
+            Err(ExtrudeError::NoClosedProfile)
+        ));
+    }
 }
@@ -0,0 +1,1455 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: fillet.rs | DNA/src/cad/fillet.rs
+//! PURPOSE: Fillet and chamfer operations on straight edges of B-Rep solids
+//! MODIFIED: 2026-10-17
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ EDGE BLEND (cross-section perpendicular to the edge)                        │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │                                                                             │
+//! │        F2                  Chamfer: T1 ── T2 replaced by one planar bevel   │
+//! │        │                                                                    │
+//! │   T2 ──┤                   Fillet:  arc T1 → T2 of the given radius,        │
+//! │        │ ╲                          tangent to F1 and F2, on one            │
+//! │        │  ╲                         Cylindrical face                        │
+//! │        K───T1──── F1                                                        │
+//! │                                                                             │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ CORNERS (selected edges meeting at a vertex)                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ 1 edge:  the third face gets the blend section in its loop                  │
+//! │ 2 edges: the blends meet along a mitre curve; the unselected edge is        │
+//! │          shortened to where both blends reach it                            │
+//! │ 3 edges: a rolling-ball Spherical patch (or planar triangle for chamfers)   │
+//! │          bounded by the three blend sections                                │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! The solid is rebuilt face by face: each loop keeps its edges, with every
+//! touched vertex replaced by the blend boundary points. Sections are exact
+//! arcs or rational quadratic NURBS, so the mesher and STEP export see the
+//! true blend surface. Original faces, edges and vertices are rebuilt first,
+//! in order, so a selection made before the operation still refers to the
+//! same entities; a blended edge becomes the F1 side of its blend.
+//!
+//! Faces around a blended vertex must be planar and each such vertex must
+//! join exactly three faces. Selected edges sharing a vertex must be all
+//! convex or all concave. Blends never run along arcs or curved faces, so no
+//! toroidal faces are made: such edges are reported as `CurvedEdge` and such
+//! faces as `CurvedFace`, e.g. when re-filleting next to an earlier fillet.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::geometry::{Point3, Vector3};
+use super::intersect::{Classification, SolidClassifier};
+use super::mesh::{newell_normal, plane_basis};
+use super::topology::{
+    CurveType, EdgeId, Face, FaceId, FaceOrientation, Loop, Solid, SurfaceType, VertexId,
+};
+use glam::{DMat3, DVec3};
+use std::collections::HashMap;
+
+/// Error type for fillet and chamfer operations
+#[derive(Debug, Clone)]
+pub enum FilletError {
+    /// No edges were given
+    NoEdges,
+    /// Radius or distance is not a positive finite number
+    InvalidSize,
+    EdgeNotFound(EdgeId),
+    /// Arc or NURBS edge selected or meeting a blended vertex; blending it
+    /// would need a toroidal or swept face, which is not supported
+    CurvedEdge(EdgeId),
+    /// Face next to a blend is curved; blends are built between planes only
+    CurvedFace(FaceId),
+    /// Edge is not shared by exactly two faces
+    NonManifoldEdge(EdgeId),
+    /// Vertex does not join exactly three flat faces
+    UnsupportedVertex(VertexId),
+    /// Selected edges meeting at this vertex have no common corner blend
+    SharedVertex(VertexId),
+    /// Blend does not fit on the adjacent faces
+    SizeTooLarge(EdgeId),
+    /// Faces around the edge are tangent or the end faces run along it
+    DegenerateGeometry(EdgeId),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum BlendProfile {
+    Fillet(f32),
+    Chamfer(f32),
+}
+
+/// Round the given edges with a constant-radius fillet
+///
+/// Each edge is replaced by a cylindrical face tangent to both adjacent
+/// faces, and corners where three filleted edges meet get a spherical patch.
+/// Convex edges lose material, concave edges gain it. Edges must be straight
+/// and lie between planar faces; see `FilletError::CurvedEdge` and
+/// `FilletError::CurvedFace`.
+pub fn fillet_edges(solid: &Solid, edges: &[EdgeId], radius: f32) -> Result<Solid, FilletError> {
+    blend_edges(solid, edges, BlendProfile::Fillet(radius))
+}
+
+/// Bevel the given edges with an equal-distance chamfer
+///
+/// `distance` is measured on each adjacent face, perpendicular to the edge.
+pub fn chamfer_edges(solid: &Solid, edges: &[EdgeId], distance: f32) -> Result<Solid, FilletError> {
+    blend_edges(solid, edges, BlendProfile::Chamfer(distance))
+}
+
+fn blend_edges(
+    solid: &Solid,
+    edges: &[EdgeId],
+    profile: BlendProfile,
+) -> Result<Solid, FilletError> {
+    if edges.is_empty() {
+        return Err(FilletError::NoEdges);
+    }
+    let size = match profile {
+        BlendProfile::Fillet(r) => r,
+        BlendProfile::Chamfer(d) => d,
+    };
+    if !(size.is_finite() && size > 0.0) {
+        return Err(FilletError::InvalidSize);
+    }
+
+    let mut unique: Vec<EdgeId> = Vec::with_capacity(edges.len());
+    for &id in edges {
+        if !unique.contains(&id) {
+            unique.push(id);
+        }
+    }
+    for &id in &unique {
+        let edge = solid.edge(id).ok_or(FilletError::EdgeNotFound(id))?;
+        if !matches!(edge.curve, CurveType::Linear) {
+            return Err(FilletError::CurvedEdge(id));
+        }
+    }
+
+    let mut blender = Blender::new(solid, &unique, profile)?;
+    blender.trace_faces()?;
+    blender.build()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Blend geometry
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn dvec(p: Point3) -> DVec3 {
+    p.to_vec3().as_dvec3()
+}
+
+fn to_point(p: DVec3) -> Point3 {
+    Point3::new(p.x as f32, p.y as f32, p.z as f32)
+}
+
+/// Plane of a face next to a blended vertex
+struct Plane {
+    normal: DVec3,
+    point: DVec3,
+}
+
+/// Cross-section data for one selected edge
+struct EdgeBlend {
+    faces: [FaceId; 2],
+    start: VertexId,
+    end: VertexId,
+    origin: DVec3,
+    /// Unit direction from start to end
+    dir: DVec3,
+    /// In-face unit directions perpendicular to the edge, into F1 and F2
+    into: [DVec3; 2],
+    /// Distance of the blend boundary from the edge, measured in each face
+    setback: f64,
+    /// From the edge line to the fillet axis (towards the blend side)
+    axis_offset: DVec3,
+    /// Weight of the profile's middle control point, sin(θ/2)
+    weight: f64,
+    convex: bool,
+}
+
+impl EdgeBlend {
+    fn side(&self, face: FaceId) -> usize {
+        usize::from(self.faces[1] == face)
+    }
+
+    /// Profile control points at `v` (edge point and tangent points),
+    /// projected along the edge onto a plane
+    fn profile_on(&self, v: DVec3, plane: &Plane) -> Option<[DVec3; 3]> {
+        let denom = plane.normal.dot(self.dir);
+        if denom.abs() < 0.05 {
+            return None;
+        }
+        let project = |x: DVec3| x - self.dir * (plane.normal.dot(x - plane.point) / denom);
+        Some([
+            project(v + self.into[0] * self.setback),
+            project(v),
+            project(v + self.into[1] * self.setback),
+        ])
+    }
+}
+
+/// Configuration at a vertex touched by the blend
+struct Corner {
+    edges: Vec<EdgeId>,
+    selected: Vec<EdgeId>,
+    /// Rolling-ball centre where two or three fillets meet
+    center: Option<DVec3>,
+}
+
+/// Boundary curve shared by a blend and its neighbour at a vertex
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum SectionKey {
+    /// Section of one edge: on the end face, or against a corner patch
+    End(EdgeId, VertexId),
+    /// Mitre between the two blends meeting at the vertex
+    Mitre(VertexId),
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+    Line,
+    /// Rational quadratic from first to last control point
+    Conic([DVec3; 3], f64),
+    /// Circular arc about the centre
+    Arc(DVec3),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum SpanKind {
+    /// Untouched edge, kept with its curve and direction
+    Original(EdgeId, bool),
+    Straight,
+    Section(SectionKey),
+}
+
+/// One edge use in a rebuilt loop, between two recorded points
+struct Span {
+    from: usize,
+    to: usize,
+    kind: SpanKind,
+    /// Original edge whose ID this span inherits
+    origin: Option<EdgeId>,
+}
+
+struct BlendFace {
+    surface: SurfaceType,
+    orientation: FaceOrientation,
+    shell_of: FaceId,
+    spans: Vec<Span>,
+}
+
+struct Blender<'a> {
+    solid: &'a Solid,
+    profile: BlendProfile,
+    order: Vec<EdgeId>,
+    blends: HashMap<EdgeId, EdgeBlend>,
+    planes: HashMap<FaceId, Plane>,
+    corners: HashMap<VertexId, Corner>,
+    tol: f64,
+    /// Recorded points with the original vertex each replaces
+    points: Vec<(DVec3, VertexId)>,
+    /// Single blend boundary point of a face at a vertex
+    face_points: HashMap<(FaceId, VertexId), usize>,
+    /// Tangent spans of each blended edge: (face, edge) → (from, to, at from, at to)
+    tangents: HashMap<(FaceId, EdgeId), (usize, usize, VertexId, VertexId)>,
+    loops: Vec<Vec<Vec<Span>>>,
+    extra: Vec<BlendFace>,
+}
+
+impl<'a> Blender<'a> {
+    fn new(solid: &'a Solid, edges: &[EdgeId], profile: BlendProfile) -> Result<Self, FilletError> {
+        let mut min = DVec3::splat(f64::MAX);
+        let mut max = DVec3::splat(f64::MIN);
+        for v in &solid.vertices {
+            min = min.min(dvec(v.point));
+            max = max.max(dvec(v.point));
+        }
+        let mut blender = Self {
+            solid,
+            profile,
+            order: edges.to_vec(),
+            blends: HashMap::new(),
+            planes: HashMap::new(),
+            corners: HashMap::new(),
+            tol: 1e-5 * (max - min).length().max(1.0),
+            points: Vec::new(),
+            face_points: HashMap::new(),
+            tangents: HashMap::new(),
+            loops: Vec::new(),
+            extra: Vec::new(),
+        };
+
+        let classifier = SolidClassifier::new(solid);
+        for &id in edges {
+            let blend = blender.edge_blend(id, &classifier)?;
+            blender.blends.insert(id, blend);
+        }
+
+        let mut touched: Vec<VertexId> = blender
+            .order
+            .iter()
+            .flat_map(|id| [blender.blends[id].start, blender.blends[id].end])
+            .collect();
+        touched.sort_by_key(|v| v.0);
+        touched.dedup();
+        for v in touched {
+            let corner = blender.corner(v)?;
+            blender.corners.insert(v, corner);
+        }
+        Ok(blender)
+    }
+
+    fn size(&self) -> f64 {
+        match self.profile {
+            BlendProfile::Fillet(r) | BlendProfile::Chamfer(r) => r as f64,
+        }
+    }
+
+    fn position(&self, v: VertexId) -> DVec3 {
+        self.solid
+            .vertex(v)
+            .map(|v| dvec(v.point))
+            .unwrap_or(DVec3::ZERO)
+    }
+
+    /// Unit direction of a straight edge leaving `v`
+    fn direction_from(&self, edge: EdgeId, v: VertexId) -> DVec3 {
+        let Some(e) = self.solid.edge(edge) else {
+            return DVec3::ZERO;
+        };
+        let other = if e.start == v { e.end } else { e.start };
+        (self.position(other) - self.position(v)).normalize_or_zero()
+    }
+
+    fn incident_edges(&self, v: VertexId) -> Vec<EdgeId> {
+        self.solid
+            .edges
+            .iter()
+            .filter(|e| e.start == v || e.end == v)
+            .map(|e| e.id)
+            .collect()
+    }
+
+    /// Plane of a face, computed once; curved or warped faces are rejected
+    fn plane(&mut self, face: FaceId, v: VertexId) -> Result<&Plane, FilletError> {
+        if !self.planes.contains_key(&face) {
+            let f = self
+                .solid
+                .face(face)
+                .ok_or(FilletError::UnsupportedVertex(v))?;
+            let outer: Vec<Point3> = self
+                .solid
+                .loop_vertices(&f.outer_loop)
+                .iter()
+                .filter_map(|&id| self.solid.vertex(id).map(|v| v.point))
+                .collect();
+            let normal = newell_normal(&outer)
+                .try_normalize()
+                .ok_or(FilletError::UnsupportedVertex(v))?;
+            if !matches!(f.surface, SurfaceType::Planar { .. }) {
+                return Err(FilletError::CurvedFace(face));
+            }
+            let point = dvec(outer[0]);
+            let flat = std::iter::once(&f.outer_loop)
+                .chain(&f.inner_loops)
+                .flat_map(|l| l.edges.iter())
+                .filter_map(|&id| self.solid.edge(id))
+                .all(|e| {
+                    [e.start, e.end]
+                        .iter()
+                        .all(|&id| (self.position(id) - point).dot(normal).abs() < self.tol * 10.0)
+                });
+            if !flat {
+                return Err(FilletError::UnsupportedVertex(v));
+            }
+            self.planes.insert(face, Plane { normal, point });
+        }
+        Ok(&self.planes[&face])
+    }
+
+    fn edge_blend(
+        &mut self,
+        id: EdgeId,
+        classifier: &SolidClassifier,
+    ) -> Result<EdgeBlend, FilletError> {
+        let edge = self.solid.edge(id).ok_or(FilletError::EdgeNotFound(id))?;
+        let (start, end) = (edge.start, edge.end);
+        let faces = self.solid.faces_of_edge(id);
+        if faces.len() != 2 {
+            return Err(FilletError::NonManifoldEdge(id));
+        }
+        let faces = [faces[0], faces[1]];
+
+        let (p0, p1) = (self.position(start), self.position(end));
+        let length = p0.distance(p1);
+        if length < 1e-6 {
+            return Err(FilletError::DegenerateGeometry(id));
+        }
+        let dir = (p1 - p0) / length;
+
+        // Directions into each face, checked against the face outline
+        let mut into = [DVec3::ZERO; 2];
+        for (k, &face) in faces.iter().enumerate() {
+            let normal = self.plane(face, start)?.normal;
+            let u = normal.cross(dir).normalize();
+            let probe = (p0 + p1) * 0.5 + u * (length * 1e-3);
+            into[k] = if self.inside_face(face, probe) { u } else { -u };
+        }
+
+        let theta = into[0].angle_between(into[1]);
+        if !(1f64.to_radians()..=179f64.to_radians()).contains(&theta) {
+            return Err(FilletError::DegenerateGeometry(id));
+        }
+        let bisector = (into[0] + into[1]).normalize();
+        let half = theta * 0.5;
+        let size = self.size();
+        let (setback, axis_offset) = match self.profile {
+            BlendProfile::Fillet(_) => (size / half.tan(), bisector * (size / half.sin())),
+            BlendProfile::Chamfer(_) => (size, bisector * size),
+        };
+
+        // Convex edges have material between the faces; concave ones are filled in
+        let probe = (p0 + p1) * 0.5 + bisector * (size.min(length) * 0.05);
+        let convex = match classifier.classify(probe) {
+            Classification::Inside => true,
+            Classification::Outside => false,
+            Classification::OnBoundary => return Err(FilletError::DegenerateGeometry(id)),
+        };
+
+        Ok(EdgeBlend {
+            faces,
+            start,
+            end,
+            origin: p0,
+            dir,
+            into,
+            setback,
+            axis_offset,
+            weight: half.sin(),
+            convex,
+        })
+    }
+
+    /// Even-odd test of a point against every loop of a planar face
+    fn inside_face(&self, face: FaceId, p: DVec3) -> bool {
+        let (Some(f), Some(plane)) = (self.solid.face(face), self.planes.get(&face)) else {
+            return false;
+        };
+        let (u_axis, v_axis) = plane_basis(plane.normal);
+        let q = [p.dot(u_axis), p.dot(v_axis)];
+        let mut inside = false;
+        for l in std::iter::once(&f.outer_loop).chain(&f.inner_loops) {
+            let ring: Vec<[f64; 2]> = self
+                .solid
+                .loop_vertices(l)
+                .iter()
+                .map(|&id| {
+                    let d = self.position(id);
+                    [d.dot(u_axis), d.dot(v_axis)]
+                })
+                .collect();
+            for i in 0..ring.len() {
+                let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+                if (a[1] > q[1]) != (b[1] > q[1]) {
+                    let x = a[0] + (q[1] - a[1]) * (b[0] - a[0]) / (b[1] - a[1]);
+                    if x > q[0] {
+                        inside = !inside;
+                    }
+                }
+            }
+        }
+        inside
+    }
+
+    fn corner(&mut self, v: VertexId) -> Result<Corner, FilletError> {
+        let edges = self.incident_edges(v);
+        let mut faces: Vec<FaceId> = edges
+            .iter()
+            .flat_map(|&e| self.solid.faces_of_edge(e))
+            .collect();
+        faces.sort_by_key(|f| f.0);
+        faces.dedup();
+        for &e in &edges {
+            let straight = self
+                .solid
+                .edge(e)
+                .is_some_and(|e| matches!(e.curve, CurveType::Linear));
+            if !straight {
+                return Err(FilletError::CurvedEdge(e));
+            }
+        }
+        if edges.len() != 3 || faces.len() != 3 {
+            return Err(FilletError::UnsupportedVertex(v));
+        }
+        for &face in &faces {
+            self.plane(face, v)?;
+        }
+        let selected: Vec<EdgeId> = edges
+            .iter()
+            .copied()
+            .filter(|e| self.blends.contains_key(e))
+            .collect();
+        if selected
+            .windows(2)
+            .any(|w| self.blends[&w[0]].convex != self.blends[&w[1]].convex)
+        {
+            return Err(FilletError::SharedVertex(v));
+        }
+
+        let mut corner = Corner {
+            edges,
+            selected,
+            center: None,
+        };
+        if corner.selected.len() < 2 {
+            return Ok(corner);
+        }
+
+        // Ball centre: distance r from every face, on each blend's side
+        if let BlendProfile::Fillet(r) = self.profile {
+            let mut sides: Vec<(FaceId, DVec3)> = Vec::new();
+            for s in &corner.selected {
+                let blend = &self.blends[s];
+                for face in blend.faces {
+                    let n = self.planes[&face].normal;
+                    let m = n * n.dot(blend.axis_offset).signum();
+                    match sides.iter().find(|(f, _)| *f == face) {
+                        Some((_, other)) if other.dot(m) < 0.0 => {
+                            return Err(FilletError::SharedVertex(v));
+                        }
+                        Some(_) => {}
+                        None => sides.push((face, m)),
+                    }
+                }
+            }
+            let p = self.position(v);
+            let rows = DMat3::from_cols(sides[0].1, sides[1].1, sides[2].1).transpose();
+            if rows.determinant().abs() < 1e-9 {
+                return Err(FilletError::DegenerateGeometry(corner.selected[0]));
+            }
+            let rhs = DVec3::new(
+                r as f64 + sides[0].1.dot(p),
+                r as f64 + sides[1].1.dot(p),
+                r as f64 + sides[2].1.dot(p),
+            );
+            corner.center = Some(rows.inverse() * rhs);
+        }
+
+        // Both blends must reach the unselected edge at the same point
+        if corner.selected.len() == 2 {
+            let sharp = corner
+                .edges
+                .iter()
+                .copied()
+                .find(|e| !corner.selected.contains(e))
+                .unwrap_or(corner.edges[0]);
+            let sharp_faces = self.solid.faces_of_edge(sharp);
+            let mut reach = Vec::new();
+            for s in &corner.selected {
+                let face = self.blends[s]
+                    .faces
+                    .into_iter()
+                    .find(|f| sharp_faces.contains(f))
+                    .ok_or(FilletError::SharedVertex(v))?;
+                reach.push(self.offset_meets_edge(v, *s, face, sharp)?);
+            }
+            if reach[0].distance(reach[1]) > self.tol {
+                return Err(FilletError::SharedVertex(v));
+            }
+        }
+        Ok(corner)
+    }
+
+    /// Where the blend boundary of `blend` in `face` crosses the straight
+    /// edge `edge` leaving `v`
+    fn offset_meets_edge(
+        &self,
+        v: VertexId,
+        blend: EdgeId,
+        face: FaceId,
+        edge: EdgeId,
+    ) -> Result<DVec3, FilletError> {
+        let b = &self.blends[&blend];
+        let u = b.into[b.side(face)];
+        let d = self.direction_from(edge, v);
+        let denom = d.dot(u);
+        if denom.abs() < 1e-6 {
+            return Err(FilletError::DegenerateGeometry(blend));
+        }
+        Ok(self.position(v) + d * (b.setback / denom))
+    }
+
+    /// Where the blend boundaries of two edges cross inside `face`
+    fn offsets_meet(
+        &self,
+        v: VertexId,
+        first: EdgeId,
+        second: EdgeId,
+        face: FaceId,
+    ) -> Result<DVec3, FilletError> {
+        let (a, b) = (&self.blends[&first], &self.blends[&second]);
+        let (ua, ub) = (a.into[a.side(face)], b.into[b.side(face)]);
+        let denom = a.dir.dot(ub);
+        if denom.abs() < 1e-6 {
+            return Err(FilletError::DegenerateGeometry(first));
+        }
+        let t = (b.setback - a.setback * ua.dot(ub)) / denom;
+        Ok(self.position(v) + ua * a.setback + a.dir * t)
+    }
+
+    fn push_point(&mut self, p: DVec3, v: VertexId) -> usize {
+        self.points.push((p, v));
+        self.points.len() - 1
+    }
+
+    fn section_key(&self, edge: EdgeId, v: VertexId) -> SectionKey {
+        if self.corners[&v].selected.len() == 2 {
+            SectionKey::Mitre(v)
+        } else {
+            SectionKey::End(edge, v)
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Loops
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Boundary points replacing `v` in a loop of `face` between `e_in` and
+    /// `e_out`, with the section joining them when the face ends a blend
+    fn corner_points(
+        &mut self,
+        face: FaceId,
+        v: VertexId,
+        e_in: EdgeId,
+        e_out: EdgeId,
+    ) -> Result<(Vec<usize>, Option<SectionKey>), FilletError> {
+        let Some(corner) = self.corners.get(&v) else {
+            let p = self.position(v);
+            return Ok((vec![self.push_point(p, v)], None));
+        };
+        let single = |blender: &mut Self, p: DVec3| {
+            let id = blender.push_point(p, v);
+            blender.face_points.insert((face, v), id);
+            Ok((vec![id], None))
+        };
+        match (
+            self.blends.contains_key(&e_in),
+            self.blends.contains_key(&e_out),
+        ) {
+            (true, true) => {
+                let p = self.offsets_meet(v, e_in, e_out, face)?;
+                single(self, p)
+            }
+            (true, false) => {
+                let p = self.offset_meets_edge(v, e_in, face, e_out)?;
+                single(self, p)
+            }
+            (false, true) => {
+                let p = self.offset_meets_edge(v, e_out, face, e_in)?;
+                single(self, p)
+            }
+            (false, false) => {
+                // End face of the one blend at this vertex
+                let blend = corner.selected[0];
+                let other = |e: EdgeId| {
+                    self.solid
+                        .faces_of_edge(e)
+                        .into_iter()
+                        .find(|&f| f != face)
+                        .ok_or(FilletError::UnsupportedVertex(v))
+                };
+                let (f_in, f_out) = (other(e_in)?, other(e_out)?);
+                let q_in = self.offset_meets_edge(v, blend, f_in, e_in)?;
+                let q_out = self.offset_meets_edge(v, blend, f_out, e_out)?;
+                let ids = vec![self.push_point(q_in, v), self.push_point(q_out, v)];
+                Ok((ids, Some(SectionKey::End(blend, v))))
+            }
+        }
+    }
+
+    /// The selected edge responsible for moving the ends of `edge`
+    fn culprit(&self, edge: EdgeId, ends: [VertexId; 2]) -> EdgeId {
+        if self.blends.contains_key(&edge) {
+            return edge;
+        }
+        ends.iter()
+            .filter_map(|v| self.corners.get(v))
+            .find_map(|c| c.selected.first().copied())
+            .unwrap_or(edge)
+    }
+
+    fn trace_faces(&mut self) -> Result<(), FilletError> {
+        let solid = self.solid;
+        for face in &solid.faces {
+            let mut loops = Vec::new();
+            for l in std::iter::once(&face.outer_loop).chain(&face.inner_loops) {
+                loops.push(self.trace_loop(face, l)?);
+            }
+            self.loops.push(loops);
+        }
+        for i in 0..self.order.len() {
+            self.add_blend_face(self.order[i])?;
+        }
+        let mut vertices: Vec<VertexId> = self.corners.keys().copied().collect();
+        vertices.sort_by_key(|v| v.0);
+        for v in vertices {
+            if self.corners[&v].selected.len() == 3 {
+                self.add_corner_patch(v)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn trace_loop(&mut self, face: &Face, l: &Loop) -> Result<Vec<Span>, FilletError> {
+        let half = self.solid.loop_half_edges(l);
+        let n = half.len();
+        let end_of = |(edge, forward): (EdgeId, bool)| {
+            self.solid
+                .edge(edge)
+                .map(|e| if forward { e.end } else { e.start })
+        };
+
+        // Points replacing the vertex after each half-edge
+        let mut at: Vec<(VertexId, Vec<usize>, Option<SectionKey>)> = Vec::with_capacity(n);
+        for i in 0..n {
+            let Some(v) = end_of(half[i]) else {
+                return Err(FilletError::EdgeNotFound(half[i].0));
+            };
+            let (ids, section) = self.corner_points(face.id, v, half[i].0, half[(i + 1) % n].0)?;
+            at.push((v, ids, section));
+        }
+
+        let mut spans = Vec::with_capacity(n * 2);
+        for i in 0..n {
+            let (v, ids, section) = &at[i];
+            if let Some(key) = section {
+                spans.push(Span {
+                    from: ids[0],
+                    to: ids[1],
+                    kind: SpanKind::Section(*key),
+                    origin: None,
+                });
+            }
+
+            let (edge, forward) = half[(i + 1) % n];
+            let (next_v, next_ids, _) = &at[(i + 1) % n];
+            let (from, to) = (ids[ids.len() - 1], next_ids[0]);
+            let untouched = !self.corners.contains_key(v) && !self.corners.contains_key(next_v);
+            let blended = self.blends.get(&edge).map(|b| b.faces[0] == face.id);
+            if untouched {
+                spans.push(Span {
+                    from,
+                    to,
+                    kind: SpanKind::Original(edge, forward),
+                    origin: Some(edge),
+                });
+                continue;
+            }
+
+            // Trimmed or tangent edges must keep their direction
+            let run = self.position(*next_v) - self.position(*v);
+            if (self.points[to].0 - self.points[from].0).dot(run.normalize_or_zero()) <= self.tol {
+                return Err(FilletError::SizeTooLarge(self.culprit(edge, [*v, *next_v])));
+            }
+            if blended.is_some() {
+                self.tangents
+                    .insert((face.id, edge), (from, to, *v, *next_v));
+            }
+            spans.push(Span {
+                from,
+                to,
+                kind: SpanKind::Straight,
+                origin: (blended != Some(false)).then_some(edge),
+            });
+        }
+        Ok(spans)
+    }
+
+    fn add_blend_face(&mut self, id: EdgeId) -> Result<(), FilletError> {
+        let blend = &self.blends[&id];
+        let [f1, f2] = blend.faces;
+        let (a, b, va, vb) = self.tangents[&(f1, id)];
+        let (c0, d0, wa, _) = self.tangents[&(f2, id)];
+        let (c, d) = if wa == va { (c0, d0) } else { (d0, c0) };
+
+        let (surface, orientation) = match self.profile {
+            BlendProfile::Chamfer(_) => {
+                let mut n = blend.dir.cross(blend.into[1] - blend.into[0]).normalize();
+                if (n.dot(blend.axis_offset) < 0.0) != blend.convex {
+                    n = -n;
+                }
+                (
+                    SurfaceType::Planar {
+                        normal: Vector3::new(n.x as f32, n.y as f32, n.z as f32),
+                    },
+                    FaceOrientation::Outward,
+                )
+            }
+            BlendProfile::Fillet(r) => (
+                SurfaceType::Cylindrical {
+                    axis: Vector3::new(blend.dir.x as f32, blend.dir.y as f32, blend.dir.z as f32),
+                    center: to_point(blend.origin + blend.axis_offset),
+                    radius: r,
+                },
+                if blend.convex {
+                    FaceOrientation::Outward
+                } else {
+                    FaceOrientation::Inward
+                },
+            ),
+        };
+
+        // Opposite to F1 along the shared tangent edge
+        let spans = vec![
+            Span {
+                from: b,
+                to: a,
+                kind: SpanKind::Straight,
+                origin: None,
+            },
+            Span {
+                from: a,
+                to: c,
+                kind: SpanKind::Section(self.section_key(id, va)),
+                origin: None,
+            },
+            Span {
+                from: c,
+                to: d,
+                kind: SpanKind::Straight,
+                origin: None,
+            },
+            Span {
+                from: d,
+                to: b,
+                kind: SpanKind::Section(self.section_key(id, vb)),
+                origin: None,
+            },
+        ];
+        self.extra.push(BlendFace {
+            surface,
+            orientation,
+            shell_of: f1,
+            spans,
+        });
+        Ok(())
+    }
+
+    /// Spherical (or flat) patch closing a corner where three blends meet
+    fn add_corner_patch(&mut self, v: VertexId) -> Result<(), FilletError> {
+        let corner = &self.corners[&v];
+        let selected = corner.selected.clone();
+        let center = corner.center;
+
+        // The first blend's traversal of its section fixes the patch direction
+        let (from, to) = self
+            .extra
+            .iter()
+            .flat_map(|f| f.spans.iter())
+            .find(|s| s.kind == SpanKind::Section(SectionKey::End(selected[0], v)))
+            .map(|s| (s.from, s.to))
+            .ok_or(FilletError::UnsupportedVertex(v))?;
+        let face_of = |p: usize| {
+            self.face_points
+                .iter()
+                .find(|(k, &id)| k.1 == v && id == p)
+                .map(|(k, _)| k.0)
+        };
+        let (Some(f_to), Some(f_from)) = (face_of(to), face_of(from)) else {
+            return Err(FilletError::UnsupportedVertex(v));
+        };
+        let f_third = self.blends[&selected[1]]
+            .faces
+            .into_iter()
+            .chain(self.blends[&selected[2]].faces)
+            .find(|f| *f != f_to && *f != f_from)
+            .ok_or(FilletError::UnsupportedVertex(v))?;
+        let third = self.face_points[&(f_third, v)];
+        let joining = |a: FaceId, b: FaceId| {
+            selected[1..]
+                .iter()
+                .copied()
+                .find(|e| {
+                    let faces = self.blends[e].faces;
+                    faces.contains(&a) && faces.contains(&b)
+                })
+                .ok_or(FilletError::SharedVertex(v))
+        };
+        let (e_from, e_to) = (joining(f_from, f_third)?, joining(f_third, f_to)?);
+
+        let convex = self.blends[&selected[0]].convex;
+        let orientation = if convex {
+            FaceOrientation::Outward
+        } else {
+            FaceOrientation::Inward
+        };
+        let surface = match (self.profile, center) {
+            (BlendProfile::Fillet(r), Some(c)) => SurfaceType::Spherical {
+                center: to_point(c),
+                radius: r,
+            },
+            _ => {
+                let [p, q, t] = [to, from, third].map(|i| self.points[i].0);
+                let mut n = (q - p).cross(t - p).normalize();
+                if (n.dot(self.position(v) - p) > 0.0) != convex {
+                    n = -n;
+                }
+                SurfaceType::Planar {
+                    normal: Vector3::new(n.x as f32, n.y as f32, n.z as f32),
+                }
+            }
+        };
+
+        let spans = vec![
+            Span {
+                from: to,
+                to: from,
+                kind: SpanKind::Section(SectionKey::End(selected[0], v)),
+                origin: None,
+            },
+            Span {
+                from,
+                to: third,
+                kind: SpanKind::Section(SectionKey::End(e_from, v)),
+                origin: None,
+            },
+            Span {
+                from: third,
+                to,
+                kind: SpanKind::Section(SectionKey::End(e_to, v)),
+                origin: None,
+            },
+        ];
+        self.extra.push(BlendFace {
+            surface,
+            orientation,
+            shell_of: self.blends[&selected[0]].faces[0],
+            spans,
+        });
+        Ok(())
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Sections
+    // ─────────────────────────────────────────────────────────────────────────
+
+    fn section(&self, key: SectionKey) -> Result<Section, FilletError> {
+        if matches!(self.profile, BlendProfile::Chamfer(_)) {
+            return Ok(Section::Line);
+        }
+        match key {
+            SectionKey::End(edge, v) => {
+                let corner = &self.corners[&v];
+                if let Some(c) = corner.center {
+                    return Ok(Section::Arc(c));
+                }
+                // Projection of the circular profile onto the end face
+                let blend = &self.blends[&edge];
+                let end_face = corner
+                    .edges
+                    .iter()
+                    .flat_map(|&e| self.solid.faces_of_edge(e))
+                    .find(|f| !blend.faces.contains(f))
+                    .ok_or(FilletError::UnsupportedVertex(v))?;
+                let plane = &self.planes[&end_face];
+                let p = self.position(v);
+                if plane.normal.dot(blend.dir).abs() > 1.0 - 1e-9 {
+                    let along = blend.dir * blend.dir.dot(p - blend.origin);
+                    return Ok(Section::Arc(blend.origin + along + blend.axis_offset));
+                }
+                let points = blend
+                    .profile_on(p, plane)
+                    .ok_or(FilletError::DegenerateGeometry(edge))?;
+                Ok(Section::Conic(points, blend.weight))
+            }
+            SectionKey::Mitre(v) => {
+                let corner = &self.corners[&v];
+                let c = corner.center.ok_or(FilletError::SharedVertex(v))?;
+                let (s1, s2) = (corner.selected[0], corner.selected[1]);
+                let shared = self.blends[&s1]
+                    .faces
+                    .into_iter()
+                    .find(|f| self.blends[&s2].faces.contains(f))
+                    .ok_or(FilletError::SharedVertex(v))?;
+                let other = self.blends[&s1].faces[1 - self.blends[&s1].side(shared)];
+                let t = self.points[self.face_points[&(shared, v)]].0;
+                let q = self.points[self.face_points[&(other, v)]].0;
+
+                // Both cylinders cut by the plane through the ball centre
+                let plane = Plane {
+                    normal: (t - c)
+                        .cross(q - c)
+                        .try_normalize()
+                        .ok_or(FilletError::DegenerateGeometry(s1))?,
+                    point: c,
+                };
+                let p = self.position(v);
+                let mid = |b: &EdgeBlend| -> Result<(DVec3, [DVec3; 3]), FilletError> {
+                    let [p0, p1, p2] = b
+                        .profile_on(p, &plane)
+                        .ok_or(FilletError::SharedVertex(v))?;
+                    let w = b.weight;
+                    Ok(((p0 + p1 * (2.0 * w) + p2) / (2.0 + 2.0 * w), [p0, p1, p2]))
+                };
+                let (m1, points) = mid(&self.blends[&s1])?;
+                let (m2, _) = mid(&self.blends[&s2])?;
+                if m1.distance(m2) > self.tol * 10.0 {
+                    return Err(FilletError::SharedVertex(v));
+                }
+                Ok(Section::Conic(points, self.blends[&s1].weight))
+            }
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Assembly
+    // ─────────────────────────────────────────────────────────────────────────
+
+    fn build(&self) -> Result<Solid, FilletError> {
+        // Weld recorded points; each original vertex keeps its ID
+        let mut cluster_of: Vec<usize> = Vec::with_capacity(self.points.len());
+        let mut clusters: Vec<usize> = Vec::new();
+        for (i, (p, _)) in self.points.iter().enumerate() {
+            let found = clusters
+                .iter()
+                .position(|&c| self.points[c].0.distance(*p) <= self.tol);
+            cluster_of.push(found.unwrap_or_else(|| {
+                clusters.push(i);
+                clusters.len() - 1
+            }));
+        }
+        let mut order: Vec<usize> = Vec::with_capacity(clusters.len());
+        for v in &self.solid.vertices {
+            if let Some(i) = self.points.iter().position(|(_, owner)| *owner == v.id) {
+                if !order.contains(&cluster_of[i]) {
+                    order.push(cluster_of[i]);
+                }
+            }
+        }
+        for c in 0..clusters.len() {
+            if !order.contains(&c) {
+                order.push(c);
+            }
+        }
+
+        let mut result = Solid::new();
+        let mut vertex_of = vec![VertexId(0); clusters.len()];
+        for &c in &order {
+            vertex_of[c] = result.add_vertex(to_point(self.points[clusters[c]].0));
+        }
+        let vertex = |point: usize| vertex_of[cluster_of[point]];
+
+        // Edges: original IDs first, then everything new in loop order
+        let all_spans: Vec<&Span> = self
+            .loops
+            .iter()
+            .flatten()
+            .flatten()
+            .chain(self.extra.iter().flat_map(|f| f.spans.iter()))
+            .collect();
+        let key = |s: &Span| {
+            let (a, b) = (vertex(s.from), vertex(s.to));
+            let kind = match s.kind {
+                SpanKind::Original(edge, _) => SpanKind::Original(edge, true),
+                kind => kind,
+            };
+            (a.0.min(b.0), a.0.max(b.0), kind)
+        };
+        let mut edge_ids: HashMap<(u32, u32, SpanKind), EdgeId> = HashMap::new();
+        let mut sections: HashMap<SectionKey, Section> = HashMap::new();
+        let firsts = self
+            .solid
+            .edges
+            .iter()
+            .filter_map(|e| all_spans.iter().find(|s| s.origin == Some(e.id)));
+        for span in firsts.chain(all_spans.iter()).copied().collect::<Vec<_>>() {
+            if edge_ids.contains_key(&key(span)) {
+                continue;
+            }
+            let (start, end, curve) = match span.kind {
+                SpanKind::Original(edge, forward) => {
+                    let (s, e) = if forward {
+                        (span.from, span.to)
+                    } else {
+                        (span.to, span.from)
+                    };
+                    let curve = self
+                        .solid
+                        .edge(edge)
+                        .map(|e| e.curve.clone())
+                        .unwrap_or(CurveType::Linear);
+                    (vertex(s), vertex(e), curve)
+                }
+                SpanKind::Straight => {
+                    // Trimmed edges keep their original direction
+                    let run = span
+                        .origin
+                        .and_then(|e| self.solid.edge(e))
+                        .map(|e| self.position(e.end) - self.position(e.start));
+                    let along = self.points[span.to].0 - self.points[span.from].0;
+                    if run.is_some_and(|run| run.dot(along) < 0.0) {
+                        (vertex(span.to), vertex(span.from), CurveType::Linear)
+                    } else {
+                        (vertex(span.from), vertex(span.to), CurveType::Linear)
+                    }
+                }
+                SpanKind::Section(k) => {
+                    let section = match sections.get(&k) {
+                        Some(&section) => section,
+                        None => *sections.entry(k).or_insert(self.section(k)?),
+                    };
+                    let (a, b) = (
+                        self.points[clusters[cluster_of[span.from]]].0,
+                        self.points[clusters[cluster_of[span.to]]].0,
+                    );
+                    (
+                        vertex(span.from),
+                        vertex(span.to),
+                        section_curve(section, a, b),
+                    )
+                }
+            };
+            let id = result.add_edge(start, end);
+            result.edges[id.0 as usize].curve = curve;
+            edge_ids.insert(key(span), id);
+        }
+
+        let to_loop = |spans: &[Span], result: &Solid| {
+            let mut l = Loop::new();
+            for span in spans {
+                let id = edge_ids[&key(span)];
+                let forward = match span.kind {
+                    SpanKind::Original(_, forward) => forward,
+                    _ => result.edges[id.0 as usize].start == vertex(span.from),
+                };
+                l.add_edge(id, forward);
+            }
+            l
+        };
+
+        for (face, loops) in self.solid.faces.iter().zip(&self.loops) {
+            let id = result.add_face(face.surface.clone());
+            let mut loops = loops.iter().map(|spans| to_loop(spans, &result));
+            let outer = loops.next().unwrap_or_default();
+            let inner: Vec<Loop> = loops.collect();
+            let f = result
+                .face_mut(id)
+                .ok_or(FilletError::UnsupportedVertex(VertexId(0)))?;
+            f.outer_loop = outer;
+            f.inner_loops = inner;
+            f.orientation = face.orientation;
+            f.shell = face.shell;
+        }
+        result.shells = self.solid.shells.clone();
+        for blend in &self.extra {
+            let id = result.add_face(blend.surface.clone());
+            let outer = to_loop(&blend.spans, &result);
+            let shell = self.solid.face(blend.shell_of).and_then(|f| f.shell);
+            if let Some(f) = result.face_mut(id) {
+                f.outer_loop = outer;
+                f.orientation = blend.orientation;
+                f.shell = shell;
+            }
+            if let Some(s) = shell.and_then(|s| result.shells.iter_mut().find(|x| x.id == s)) {
+                s.add_face(id);
+            }
+        }
+
+        // Keep edge → face back-references in step with the rebuilt loops
+        let face_lists: Vec<Vec<FaceId>> = result
+            .edges
+            .iter()
+            .map(|e| result.faces_of_edge(e.id))
+            .collect();
+        for (edge, faces) in result.edges.iter_mut().zip(face_lists) {
+            edge.faces = faces;
+        }
+
+        Ok(result)
+    }
+}
+
+/// Edge curve for a section running from `start` to `end`
+fn section_curve(section: Section, start: DVec3, end: DVec3) -> CurveType {
+    match section {
+        Section::Line => CurveType::Linear,
+        Section::Conic(mut points, weight) => {
+            if points[0].distance(start) > points[2].distance(start) {
+                points.reverse();
+            }
+            points[0] = start;
+            points[2] = end;
+            CurveType::Nurbs {
+                control_points: points.iter().map(|&p| to_point(p)).collect(),
+                weights: vec![1.0, weight as f32, 1.0],
+                knots: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+                degree: 2,
+            }
+        }
+        Section::Arc(center) => {
+            let (a, b) = (start - center, end - center);
+            let normal = a.cross(b).normalize_or_zero();
+            // Same in-plane frame as `CurveType::point_at`
+            let u = if normal.z.abs() > 0.9 {
+                DVec3::X
+            } else {
+                DVec3::Z.cross(normal).normalize_or_zero()
+            };
+            let v = normal.cross(u);
+            let start_angle = a.dot(v).atan2(a.dot(u));
+            CurveType::Arc {
+                center: to_point(center),
+                radius: a.length() as f32,
+                normal: Vector3::new(normal.x as f32, normal.y as f32, normal.z as f32),
+                start_angle: start_angle as f32,
+                end_angle: (start_angle + a.angle_between(b)) as f32,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cad::extrude::{extrude_sketch, ExtrudeParams};
+    use crate::cad::mesh::solid_to_mesh;
+    use crate::cad::primitives::make_box;
+    use crate::cad::sketch::{Point2, Sketch, SketchEntity, SketchEntityId, SketchPlane};
+
+    /// L-shaped profile extruded 10mm; the inner corner at (10, 10) is concave
+    fn l_block() -> Solid {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let pts = [
+            (0.0, 0.0),
+            (20.0, 0.0),
+            (20.0, 10.0),
+            (10.0, 10.0),
+            (10.0, 20.0),
+            (0.0, 20.0),
+        ];
+        let ids: Vec<_> = pts
+            .iter()
+            .map(|&(x, y)| sketch.add_point(Point2::new(x, y)))
+            .collect();
+        for i in 0..ids.len() {
+            sketch.add_entity(SketchEntity::Line {
+                id: SketchEntityId(i as u32),
+                start: ids[i],
+                end: ids[(i + 1) % ids.len()],
+            });
+        }
+        extrude_sketch(
+            &sketch,
+            &ExtrudeParams {
+                distance: 10.0,
+                symmetric: false,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_chamfer_box_edge() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        // Edge 0 runs along the bottom-front of the box
+        let result = chamfer_edges(&solid, &[EdgeId(0)], 2.0).unwrap();
+
+        assert!(result.is_valid());
+        assert!(result.is_closed());
+        assert_eq!(result.vertices.len(), 10);
+        assert_eq!(result.edges.len(), 15);
+        assert_eq!(result.faces.len(), 7);
+
+        // Removes a 2 × 2 right triangle prism along the 20mm edge
+        assert!((solid_to_mesh(&result).volume() - (8000.0 - 40.0)).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_fillet_box_edge() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        let result = fillet_edges(&solid, &[EdgeId(0)], 4.0).unwrap();
+
+        assert!(result.is_valid());
+        assert!(result.is_closed());
+
+        let blends: Vec<_> = result
+            .faces
+            .iter()
+            .filter(|f| matches!(f.surface, SurfaceType::Cylindrical { .. }))
+            .collect();
+        assert_eq!(blends.len(), 1);
+
+        // Exact fillet removes (1 - π/4)·r²·L; the mesh chords remove a little more
+        let exact = 8000.0 - (1.0 - std::f32::consts::FRAC_PI_4) * 16.0 * 20.0;
+        let v = solid_to_mesh(&result).volume();
+        assert!(v < exact + 0.01 && v > exact - 0.5, "volume {v}");
+    }
+
+    #[test]
+    fn test_fillet_all_box_edges() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        let all: Vec<EdgeId> = solid.edges.iter().map(|e| e.id).collect();
+        let r = 2.0;
+        let result = fillet_edges(&solid, &all, r).unwrap();
+
+        assert!(result.is_valid());
+        assert!(result.is_closed());
+        let count = |pred: fn(&SurfaceType) -> bool| {
+            result.faces.iter().filter(|f| pred(&f.surface)).count()
+        };
+        assert_eq!(count(|s| matches!(s, SurfaceType::Planar { .. })), 6);
+        assert_eq!(count(|s| matches!(s, SurfaceType::Cylindrical { .. })), 12);
+        assert_eq!(count(|s| matches!(s, SurfaceType::Spherical { .. })), 8);
+
+        // Inner box, six face slabs, twelve quarter cylinders, one whole sphere
+        let pi = std::f32::consts::PI;
+        let l = 20.0 - 2.0 * r;
+        let exact = l * l * l + 6.0 * l * l * r + 3.0 * pi * r * r * l + 4.0 / 3.0 * pi * r * r * r;
+        let v = solid_to_mesh(&result).volume();
+        assert!(v < exact + 0.01 && v > exact - 5.0, "volume {v}");
+    }
+
+    #[test]
+    fn test_fillet_top_edges_mitre() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        let top = [EdgeId(4), EdgeId(5), EdgeId(6), EdgeId(7)];
+        let result = fillet_edges(&solid, &top, 2.0).unwrap();
+
+        assert!(result.is_valid());
+        assert!(result.is_closed());
+        assert_eq!(result.faces.len(), 10);
+
+        // Mitred strips of (1 - π/4)·r² section, swept along the path of
+        // the section's centroid, which sits 0.2234·r in from each edge
+        let area = (1.0 - std::f32::consts::FRAC_PI_4) * 4.0;
+        let exact = 8000.0 - area * 4.0 * (20.0 - 2.0 * 0.2234 * 2.0);
+        let v = solid_to_mesh(&result).volume();
+        assert!(v < exact + 0.01 && v > exact - 2.0, "volume {v}");
+    }
+
+    #[test]
+    fn test_chamfer_all_box_edges() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        let all: Vec<EdgeId> = solid.edges.iter().map(|e| e.id).collect();
+        let result = chamfer_edges(&solid, &all, 2.0).unwrap();
+
+        assert!(result.is_valid());
+        assert!(result.is_closed());
+        assert_eq!(result.faces.len(), 6 + 12 + 8);
+
+        // Twelve 2 × 2 prisms less the corner overlaps
+        let v = solid_to_mesh(&result).volume();
+        assert!(
+            v < 8000.0 - 12.0 * 2.0 * 16.0 && v > 8000.0 - 12.0 * 2.0 * 20.0,
+            "volume {v}"
+        );
+    }
+
+    #[test]
+    fn test_fillet_keeps_selection_ids() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        let result = fillet_edges(&solid, &[EdgeId(0), EdgeId(6)], 2.0).unwrap();
+
+        // Original entities keep their IDs; only new ones are appended
+        assert!(result.edge(EdgeId(0)).is_some());
+        assert_eq!(result.faces[0].id, FaceId(0));
+        assert!(result.faces.len() > solid.faces.len());
+        assert!(result.is_closed());
+    }
+
+    #[test]
+    fn test_chamfer_concave_edge_adds_material() {
+        let solid = l_block();
+        let before = solid_to_mesh(&solid).volume();
+
+        // Vertical edges follow the 12 profile edges; index 3 sits at (10, 10)
+        let inner = EdgeId(12 + 3);
+        let result = chamfer_edges(&solid, &[inner], 2.0).unwrap();
+        assert!(result.is_closed());
+        assert!((solid_to_mesh(&result).volume() - (before + 20.0)).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_mixed_convexity_rejected() {
+        let solid = l_block();
+        let inner = EdgeId(12 + 3);
+        let (a, b) = solid.edge(inner).map(|e| (e.start, e.end)).unwrap();
+        // A convex profile edge meeting the concave vertical edge
+        let convex = solid
+            .edges
+            .iter()
+            .find(|e| e.id != inner && [a, b].iter().any(|&v| v == e.start || v == e.end))
+            .map(|e| e.id)
+            .unwrap();
+        assert!(matches!(
+            fillet_edges(&solid, &[inner, convex], 1.0),
+            Err(FilletError::SharedVertex(_))
+        ));
+    }
+
+    #[test]
+    fn test_curved_geometry_rejected() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        let filleted = fillet_edges(&solid, &[EdgeId(0)], 4.0).unwrap();
+        let blend = filleted
+            .faces
+            .iter()
+            .find(|f| matches!(f.surface, SurfaceType::Cylindrical { .. }))
+            .unwrap();
+        let blend_edges = blend.all_edges();
+        let arc = filleted
+            .edges
+            .iter()
+            .find(|e| matches!(e.curve, CurveType::Arc { .. }))
+            .unwrap();
+
+        // One of the blend's end arcs
+        assert!(matches!(
+            fillet_edges(&filleted, &[arc.id], 1.0),
+            Err(FilletError::CurvedEdge(e)) if e == arc.id
+        ));
+
+        // A tangent line between the blend and a planar face
+        let tangent = filleted
+            .edges
+            .iter()
+            .find(|e| matches!(e.curve, CurveType::Linear) && blend_edges.contains(&e.id))
+            .unwrap();
+        assert!(matches!(
+            chamfer_edges(&filleted, &[tangent.id], 1.0),
+            Err(FilletError::CurvedFace(f)) if f == blend.id
+        ));
+
+        // A box edge ending where an arc starts
+        let beside = filleted
+            .edges
+            .iter()
+            .find(|e| {
+                matches!(e.curve, CurveType::Linear)
+                    && !blend_edges.contains(&e.id)
+                    && (e.start == arc.start || e.end == arc.start)
+            })
+            .unwrap();
+        assert!(matches!(
+            fillet_edges(&filleted, &[beside.id], 1.0),
+            Err(FilletError::CurvedEdge(_))
+        ));
+    }
+
+    #[test]
+    fn test_oversized_blend_rejected() {
+        let solid = make_box(10.0, 10.0, 10.0);
+        assert!(matches!(
+            chamfer_edges(&solid, &[EdgeId(0)], 12.0),
+            Err(FilletError::SizeTooLarge(_))
+        ));
+        assert!(matches!(
+            fillet_edges(&solid, &[EdgeId(0)], -1.0),
+            Err(FilletError::InvalidSize)
+        ));
+    }
+}
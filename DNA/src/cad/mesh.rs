@@ -5,8 +5,10 @@
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
-use super::geometry::{Point3, Vector3};
-use super::topology::{EdgeId, Face, FaceId, Solid};
+use super::geometry::{Point3, Vector3, TOLERANCE};
+use super::nurbs::{adaptive_samples, triangulate_parametric, NurbsCurve, NurbsSurface};
+use super::topology::{CurveType, Edge, EdgeId, Face, FaceId, Loop, Solid, SurfaceType};
+use glam::{DVec2, DVec3};
 
 /// Triangle mesh representation
 #[derive(Clone, Debug)]
@@ -32,6 +34,17 @@ impl TriangleMesh {
     pub fn vertex_count(&self) -> usize {
         self.vertices.len()
     }
+
+    /// Enclosed volume by the divergence theorem (negative if wound inward)
+    pub fn volume(&self) -> f32 {
+        self.triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|i| self.vertices[i].to_vec3());
+                a.dot(b.cross(c)) / 6.0
+            })
+            .sum()
+    }
 }
 
 impl Default for TriangleMesh {
@@ -40,64 +53,591 @@ impl Default for TriangleMesh {
     }
 }
 
+/// Default chord tolerance for curved edges and surfaces, in model units
+pub const DEFAULT_CHORD_TOLERANCE: f32 = 0.01;
+
 /// Convert a solid to a triangle mesh
 ///
-/// This triangulates all faces using simple fan triangulation from the first vertex.
-/// Works well for convex polygons and simple shapes.
+/// Each face is ear-clipped in its own plane, including inner loops (holes).
+/// Curved edges and surfaces are tessellated to `DEFAULT_CHORD_TOLERANCE`.
 pub fn solid_to_mesh(solid: &Solid) -> TriangleMesh {
+    solid_to_mesh_with_tolerance(solid, DEFAULT_CHORD_TOLERANCE)
+}
+
+/// Convert a solid to a triangle mesh, keeping every curved edge and surface
+/// within `tolerance` of its triangles
+pub fn solid_to_mesh_with_tolerance(solid: &Solid, tolerance: f32) -> TriangleMesh {
     let mut mesh = TriangleMesh::new();
 
     for face in &solid.faces {
-        triangulate_face(face, solid, &mut mesh);
+        triangulate_face(face, solid, tolerance, &mut mesh);
     }
 
     mesh
 }
 
+/// Triangulate a single face on its own
+///
+/// Boundary samples match the ones `solid_to_mesh_with_tolerance` uses, so
+/// faces meshed separately still share points along their common edges.
+pub(crate) fn face_to_mesh(face: &Face, solid: &Solid, tolerance: f32) -> TriangleMesh {
+    let mut mesh = TriangleMesh::new();
+    triangulate_face(face, solid, tolerance, &mut mesh);
+    mesh
+}
+
 /// Triangulate a single face and add to mesh
-fn triangulate_face(face: &Face, solid: &Solid, mesh: &mut TriangleMesh) {
-    // Get all vertices in the face's outer loop
-    let face_vertices: Vec<Point3> = face
-        .outer_loop
-        .edges
-        .iter()
-        .filter_map(|&edge_id| {
-            solid
-                .edge(edge_id)
-                .and_then(|e| solid.vertex(e.start))
-                .map(|v| v.point)
-        })
-        .collect();
+///
+/// Boundary loops are sampled along their edge curves. Curved surfaces are
+/// triangulated in their parameter space and refined to the chord tolerance;
+/// planar faces (and anything that cannot be parameterised) are projected
+/// onto the face plane and ear-clipped, so concave profiles and faces with
+/// holes tessellate correctly.
+fn triangulate_face(face: &Face, solid: &Solid, tolerance: f32, mesh: &mut TriangleMesh) {
+    let outer = loop_points(solid, &face.outer_loop, tolerance);
 
-    if face_vertices.len() < 3 {
+    if outer.len() < 3 {
         return; // Degenerate face
     }
 
-    // Calculate face normal from first three vertices
-    let v0 = face_vertices[0].to_vec3();
-    let v1 = face_vertices[1].to_vec3();
-    let v2 = face_vertices[2].to_vec3();
+    let holes: Vec<Vec<Point3>> = face
+        .inner_loops
+        .iter()
+        .map(|inner| loop_points(solid, inner, tolerance))
+        .filter(|pts| pts.len() >= 3)
+        .collect();
 
-    let edge1 = v1 - v0;
-    let edge2 = v2 - v0;
-    let normal = edge1.cross(edge2);
+    if triangulate_curved_face(&face.surface, &outer, &holes, tolerance, mesh) {
+        return;
+    }
 
-    let normal = if normal.length() > 1e-8 {
-        Vector3::from_vec3(normal.normalize())
+    // Newell's method gives a stable normal for concave polygons
+    let normal = newell_normal(&outer);
+    let normal = if normal.length() > 1e-12 {
+        normal.normalize()
     } else {
-        Vector3::new(0.0, 0.0, 1.0)
+        DVec3::Z
     };
 
+    let (u_axis, v_axis) = plane_basis(normal);
+    let project = |p: &Point3| {
+        let d = DVec3::new(p.x as f64, p.y as f64, p.z as f64);
+        [d.dot(u_axis), d.dot(v_axis)]
+    };
+
+    let outer_2d: Vec<[f64; 2]> = outer.iter().map(project).collect();
+    let holes_2d: Vec<Vec<[f64; 2]>> = holes
+        .iter()
+        .map(|h| h.iter().map(project).collect())
+        .collect();
+
+    let face_normal = Vector3::new(normal.x as f32, normal.y as f32, normal.z as f32);
+
     // Add vertices to mesh and remember base index
     let base_idx = mesh.vertices.len();
-    mesh.vertices.extend_from_slice(&face_vertices);
+    mesh.vertices.extend_from_slice(&outer);
+    for hole in &holes {
+        mesh.vertices.extend_from_slice(hole);
+    }
 
-    // Fan triangulation from first vertex
-    for i in 1..face_vertices.len() - 1 {
+    for tri in triangulate_polygon(&outer_2d, &holes_2d) {
         mesh.triangles
-            .push([base_idx, base_idx + i, base_idx + i + 1]);
-        mesh.normals.push(normal);
+            .push([base_idx + tri[0], base_idx + tri[1], base_idx + tri[2]]);
+        mesh.normals.push(face_normal);
+    }
+}
+
+/// Triangulate a non-planar face through its surface parameterisation
+///
+/// Returns false, leaving the mesh untouched, for planar faces and for
+/// boundaries that do not lie on the surface.
+fn triangulate_curved_face(
+    surface: &SurfaceType,
+    outer: &[Point3],
+    holes: &[Vec<Point3>],
+    tolerance: f32,
+    mesh: &mut TriangleMesh,
+) -> bool {
+    let (vertices, triangles) = match surface {
+        SurfaceType::Planar { .. } => return false,
+        SurfaceType::Nurbs { .. } => {
+            let Ok(nurbs) = NurbsSurface::from_surface(surface) else {
+                return false;
+            };
+            let loops: Vec<Vec<Point3>> = std::iter::once(outer.to_vec())
+                .chain(holes.iter().cloned())
+                .collect();
+            match nurbs.tessellate_trimmed(&loops, tolerance) {
+                Some((vertices, triangles)) => (
+                    vertices.iter().map(|p| p.to_vec3().as_dvec3()).collect(),
+                    triangles,
+                ),
+                None => return false,
+            }
+        }
+        _ => {
+            let Some(param) = AnalyticParam::new(surface) else {
+                return false;
+            };
+            let points: Vec<Vec<DVec3>> = std::iter::once(outer)
+                .chain(holes.iter().map(Vec::as_slice))
+                .map(|l| l.iter().map(|p| p.to_vec3().as_dvec3()).collect())
+                .collect();
+            let Some((uv, points)) = param.invert_loops(&points, tolerance as f64) else {
+                return false;
+            };
+            match triangulate_parametric(|uv| param.eval(uv), &uv, &points, tolerance as f64) {
+                Some(result) => result,
+                None => return false,
+            }
+        }
+    };
+
+    let base_idx = mesh.vertices.len();
+    mesh.vertices.extend(
+        vertices
+            .iter()
+            .map(|p| Point3::new(p.x as f32, p.y as f32, p.z as f32)),
+    );
+    for t in triangles {
+        let n = (vertices[t[1]] - vertices[t[0]])
+            .cross(vertices[t[2]] - vertices[t[0]])
+            .normalize_or_zero();
+        mesh.triangles
+            .push([base_idx + t[0], base_idx + t[1], base_idx + t[2]]);
+        mesh.normals
+            .push(Vector3::new(n.x as f32, n.y as f32, n.z as f32));
+    }
+    true
+}
+
+/// Closed polyline around a loop: each edge's first vertex followed by the
+/// points sampled along it
+pub(crate) fn loop_points(solid: &Solid, loop_: &Loop, tolerance: f32) -> Vec<Point3> {
+    let mut points: Vec<Point3> = Vec::new();
+    for (edge_id, forward) in solid.loop_half_edges(loop_) {
+        let Some(edge) = solid.edge(edge_id) else {
+            continue;
+        };
+        let mut polyline = edge_polyline(solid, edge, tolerance);
+        if !forward {
+            polyline.reverse();
+        }
+        polyline.pop(); // The next edge starts here
+        for p in polyline {
+            if points.last().is_none_or(|q| q.distance(p) > TOLERANCE) {
+                points.push(p);
+            }
+        }
+    }
+    while points.len() > 1 && points[0].distance(points[points.len() - 1]) <= TOLERANCE {
+        points.pop();
+    }
+    points
+}
+
+/// Points along an edge from its start vertex to its end vertex, within
+/// `tolerance` of the curve
+///
+/// Curves that do not meet their vertices are drawn as a straight chord.
+pub(crate) fn edge_polyline(solid: &Solid, edge: &Edge, tolerance: f32) -> Vec<Point3> {
+    let (Some(a), Some(b)) = (solid.vertex(edge.start), solid.vertex(edge.end)) else {
+        return Vec::new();
+    };
+    let (start, end) = (a.point, b.point);
+
+    let mut points = match &edge.curve {
+        CurveType::Linear => return vec![start, end],
+        CurveType::Nurbs { .. } => match NurbsCurve::from_curve(&edge.curve) {
+            Ok(curve) => curve.tessellate(tolerance),
+            Err(_) => return vec![start, end],
+        },
+        CurveType::Arc { .. } => {
+            let params: Vec<f64> = (0..=8).map(|k| k as f64 / 8.0).collect();
+            let at = |t: f64| {
+                edge.curve
+                    .point_at(start, end, t as f32)
+                    .to_vec3()
+                    .as_dvec3()
+            };
+            adaptive_samples(at, &params, tolerance as f64)
+                .into_iter()
+                .map(|(_, p)| Point3::new(p.x as f32, p.y as f32, p.z as f32))
+                .collect()
+        }
+    };
+
+    let slack = tolerance.max(1e-3) * 10.0;
+    let (first, last) = (points[0], points[points.len() - 1]);
+    if first.distance(start) > slack || last.distance(end) > slack {
+        return vec![start, end];
+    }
+    // Vertices are shared with the neighbouring edges, so use them exactly
+    points[0] = start;
+    let n = points.len();
+    points[n - 1] = end;
+    points
+}
+
+/// Parameter-space loops with their surface points, index for index
+type ParamLoops = (Vec<Vec<DVec2>>, Vec<Vec<DVec3>>);
+
+/// Parameterisation of an elementary curved surface: angles around the axis
+/// plus height, latitude or tube angle
+struct AnalyticParam {
+    surface: SurfaceType,
+    axis: DVec3,
+    e1: DVec3,
+    e2: DVec3,
+}
+
+impl AnalyticParam {
+    fn new(surface: &SurfaceType) -> Option<Self> {
+        let axis = match surface {
+            SurfaceType::Cylindrical { axis, .. }
+            | SurfaceType::Conical { axis, .. }
+            | SurfaceType::Toroidal { axis, .. } => axis.to_vec3().as_dvec3().try_normalize()?,
+            SurfaceType::Spherical { .. } => DVec3::Z,
+            SurfaceType::Planar { .. } | SurfaceType::Nurbs { .. } => return None,
+        };
+        let (e1, e2) = plane_basis(axis);
+        Some(Self {
+            surface: surface.clone(),
+            axis,
+            e1,
+            e2,
+        })
+    }
+
+    /// Point at `(angle, second)` parameters
+    fn eval(&self, uv: DVec2) -> DVec3 {
+        let radial = self.e1 * uv.x.cos() + self.e2 * uv.x.sin();
+        match &self.surface {
+            SurfaceType::Cylindrical { center, radius, .. } => {
+                center.to_vec3().as_dvec3() + self.axis * uv.y + radial * *radius as f64
+            }
+            SurfaceType::Conical {
+                apex, half_angle, ..
+            } => {
+                let r = uv.y.abs() * (*half_angle as f64).tan();
+                apex.to_vec3().as_dvec3() + self.axis * uv.y + radial * r
+            }
+            SurfaceType::Spherical { center, radius } => {
+                let r = *radius as f64;
+                center.to_vec3().as_dvec3() + (radial * uv.y.cos() + self.axis * uv.y.sin()) * r
+            }
+            SurfaceType::Toroidal {
+                center,
+                major_radius,
+                minor_radius,
+                ..
+            } => {
+                let tube = *major_radius as f64 + *minor_radius as f64 * uv.y.cos();
+                center.to_vec3().as_dvec3()
+                    + radial * tube
+                    + self.axis * (*minor_radius as f64 * uv.y.sin())
+            }
+            SurfaceType::Planar { .. } | SurfaceType::Nurbs { .. } => DVec3::ZERO,
+        }
+    }
+
+    /// Parameters of a point on the surface; the angle is `None` on the axis
+    fn invert(&self, p: DVec3) -> (Option<f64>, f64) {
+        let origin = match &self.surface {
+            SurfaceType::Cylindrical { center, .. }
+            | SurfaceType::Spherical { center, .. }
+            | SurfaceType::Toroidal { center, .. } => center.to_vec3().as_dvec3(),
+            SurfaceType::Conical { apex, .. } => apex.to_vec3().as_dvec3(),
+            SurfaceType::Planar { .. } | SurfaceType::Nurbs { .. } => DVec3::ZERO,
+        };
+        let d = p - origin;
+        let h = d.dot(self.axis);
+        let (x, y) = (d.dot(self.e1), d.dot(self.e2));
+        let rho = x.hypot(y);
+        let angle = (rho > 1e-9 * (1.0 + d.length())).then(|| y.atan2(x));
+        let second = match &self.surface {
+            SurfaceType::Spherical { .. } => h.atan2(rho),
+            SurfaceType::Toroidal { major_radius, .. } => h.atan2(rho - *major_radius as f64),
+            _ => h,
+        };
+        (angle, second)
+    }
+
+    /// Continuous parameters for each closed loop, with the matching points,
+    /// or `None` if a point is off the surface or a loop collapses in
+    /// parameter space
+    ///
+    /// A point on the axis between two different meridians (a sphere pole
+    /// at a patch corner) is emitted twice, once at each meridian's angle.
+    fn invert_loops(&self, loops: &[Vec<DVec3>], tolerance: f64) -> Option<ParamLoops> {
+        let periodic_second = matches!(self.surface, SurfaceType::Toroidal { .. });
+        let mut result: Vec<Vec<DVec2>> = Vec::with_capacity(loops.len());
+        let mut expanded: Vec<Vec<DVec3>> = Vec::with_capacity(loops.len());
+        for points in loops {
+            let raw: Vec<(Option<f64>, f64)> = points.iter().map(|&p| self.invert(p)).collect();
+            let n = raw.len();
+            raw.iter().position(|r| r.0.is_some())?;
+
+            // Points on the axis take the angles of their neighbours
+            let mut samples: Vec<(f64, f64, DVec3)> = Vec::with_capacity(n + 2);
+            for i in 0..n {
+                match raw[i].0 {
+                    Some(angle) => samples.push((angle, raw[i].1, points[i])),
+                    None => {
+                        let next = (1..n).find_map(|s| raw[(i + s) % n].0)?;
+                        let prev = (1..n).find_map(|s| raw[(i + n - s) % n].0)?;
+                        samples.push((prev, raw[i].1, points[i]));
+                        if (unwrap_angle(next, prev) - prev).abs() > 1e-9 {
+                            samples.push((next, raw[i].1, points[i]));
+                        }
+                    }
+                }
+            }
+
+            let mut uv: Vec<DVec2> = Vec::with_capacity(samples.len());
+            for &(angle, second, _) in &samples {
+                let mut p = DVec2::new(angle, second);
+                if let Some(prev) = uv.last() {
+                    p.x = unwrap_angle(p.x, prev.x);
+                    if periodic_second {
+                        p.y = unwrap_angle(p.y, prev.y);
+                    }
+                }
+                uv.push(p);
+            }
+            let points: Vec<DVec3> = samples.iter().map(|s| s.2).collect();
+            let n = uv.len();
+
+            // Holes sit in the same turn as the outer loop
+            if let Some(outer) = result.first() {
+                let centre = outer.iter().map(|p| p.x).sum::<f64>() / outer.len() as f64;
+                let shift = (centre - uv[0].x) / std::f64::consts::TAU;
+                let shift = shift.round() * std::f64::consts::TAU;
+                for p in &mut uv {
+                    p.x += shift;
+                }
+            }
+
+            for (p, q) in points.iter().zip(&uv) {
+                if (self.eval(*q) - *p).length() > tolerance.max(1e-3) * 10.0 {
+                    return None;
+                }
+            }
+            let area: f64 = (0..n).map(|i| uv[i].perp_dot(uv[(i + 1) % n])).sum::<f64>();
+            if area.abs() < 1e-12 {
+                return None;
+            }
+            result.push(uv);
+            expanded.push(points);
+        }
+        Some((result, expanded))
+    }
+}
+
+/// `angle` shifted by whole turns to lie within half a turn of `reference`
+fn unwrap_angle(angle: f64, reference: f64) -> f64 {
+    use std::f64::consts::TAU;
+    angle + ((reference - angle) / TAU).round() * TAU
+}
+
+/// Polygon normal by Newell's method (unnormalized, length = 2 × area)
+pub(crate) fn newell_normal(points: &[Point3]) -> DVec3 {
+    let mut n = DVec3::ZERO;
+    for i in 0..points.len() {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        let (ax, ay, az) = (a.x as f64, a.y as f64, a.z as f64);
+        let (bx, by, bz) = (b.x as f64, b.y as f64, b.z as f64);
+        n.x += (ay - by) * (az + bz);
+        n.y += (az - bz) * (ax + bx);
+        n.z += (ax - bx) * (ay + by);
+    }
+    n
+}
+
+/// Orthonormal in-plane axes (u, v) such that u × v = normal
+pub(crate) fn plane_basis(normal: DVec3) -> (DVec3, DVec3) {
+    let helper = if normal.x.abs() < 0.9 {
+        DVec3::X
+    } else {
+        DVec3::Y
+    };
+    let u = normal.cross(helper).normalize();
+    let v = normal.cross(u);
+    (u, v)
+}
+
+/// Ear-clipping triangulation of a simple polygon with optional holes
+///
+/// Indices refer to the outer loop followed by each hole, in order. The winding
+/// of the returned triangles matches the winding of the outer loop; holes may be
+/// given in either winding.
+pub(crate) fn triangulate_polygon(outer: &[[f64; 2]], holes: &[Vec<[f64; 2]>]) -> Vec<[usize; 3]> {
+    let mut points: Vec<[f64; 2]> = outer.to_vec();
+    for hole in holes {
+        points.extend_from_slice(hole);
+    }
+
+    // Work internally in counter-clockwise order, flip back at the end
+    let outer_ccw = signed_area_2d(outer) >= 0.0;
+    let mut ring: Vec<usize> = (0..outer.len()).collect();
+    if !outer_ccw {
+        ring.reverse();
+    }
+
+    // Bridge holes into the outer ring, rightmost hole first
+    let mut hole_rings: Vec<Vec<usize>> = Vec::new();
+    let mut offset = outer.len();
+    for hole in holes {
+        let mut idx: Vec<usize> = (offset..offset + hole.len()).collect();
+        if signed_area_2d(hole) > 0.0 {
+            idx.reverse(); // holes run clockwise
+        }
+        offset += hole.len();
+        if idx.len() >= 3 {
+            hole_rings.push(idx);
+        }
     }
+    hole_rings.sort_by(|a, b| {
+        let max_x = |r: &Vec<usize>| r.iter().map(|&i| points[i][0]).fold(f64::MIN, f64::max);
+        max_x(b).total_cmp(&max_x(a))
+    });
+    for hole in &hole_rings {
+        ring = bridge_hole(&points, ring, hole);
+    }
+
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2));
+    while ring.len() > 3 {
+        let n = ring.len();
+        let mut clipped = false;
+
+        for i in 0..n {
+            let prev = ring[(i + n - 1) % n];
+            let curr = ring[i];
+            let next = ring[(i + 1) % n];
+            if is_ear(&points, &ring, prev, curr, next) {
+                triangles.push([prev, curr, next]);
+                ring.remove(i);
+                clipped = true;
+                break;
+            }
+        }
+
+        if !clipped {
+            // Numerically stuck (collinear run or touching hole): clip the most convex corner
+            let i = (0..n)
+                .max_by(|&a, &b| {
+                    let turn = |i: usize| {
+                        cross_2d(
+                            points[ring[(i + n - 1) % n]],
+                            points[ring[i]],
+                            points[ring[(i + 1) % n]],
+                        )
+                    };
+                    turn(a).total_cmp(&turn(b))
+                })
+                .unwrap_or(0);
+            triangles.push([ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]]);
+            ring.remove(i);
+        }
+    }
+    if ring.len() == 3 {
+        triangles.push([ring[0], ring[1], ring[2]]);
+    }
+
+    // Drop zero-area slivers left behind by collinear vertices
+    triangles.retain(|t| cross_2d(points[t[0]], points[t[1]], points[t[2]]).abs() > 1e-18);
+
+    if !outer_ccw {
+        for t in &mut triangles {
+            t.swap(1, 2);
+        }
+    }
+    triangles
+}
+
+fn signed_area_2d(points: &[[f64; 2]]) -> f64 {
+    let mut area = 0.0;
+    for i in 0..points.len() {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    area * 0.5
+}
+
+fn cross_2d(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+fn is_ear(points: &[[f64; 2]], ring: &[usize], prev: usize, curr: usize, next: usize) -> bool {
+    let (a, b, c) = (points[prev], points[curr], points[next]);
+    if cross_2d(a, b, c) <= 0.0 {
+        return false; // Reflex or degenerate corner
+    }
+
+    ring.iter()
+        .filter(|&&i| i != prev && i != curr && i != next)
+        .filter(|&&i| {
+            let p = points[i];
+            // Bridged duplicates sit exactly on a corner and must not block the ear
+            p != a && p != b && p != c
+        })
+        .all(|&i| {
+            let p = points[i];
+            !(cross_2d(a, b, p) >= 0.0 && cross_2d(b, c, p) >= 0.0 && cross_2d(c, a, p) >= 0.0)
+        })
+}
+
+/// Splice a clockwise hole into a counter-clockwise ring through a mutually
+/// visible vertex pair
+fn bridge_hole(points: &[[f64; 2]], ring: Vec<usize>, hole: &[usize]) -> Vec<usize> {
+    let (m_pos, &m) = hole
+        .iter()
+        .enumerate()
+        .max_by(|a, b| points[*a.1][0].total_cmp(&points[*b.1][0]))
+        .expect("hole has vertices");
+    let pm = points[m];
+
+    let segments_cross = |p: [f64; 2], q: [f64; 2], r: [f64; 2], s: [f64; 2]| {
+        let d1 = cross_2d(p, q, r);
+        let d2 = cross_2d(p, q, s);
+        let d3 = cross_2d(r, s, p);
+        let d4 = cross_2d(r, s, q);
+        d1 * d2 < 0.0 && d3 * d4 < 0.0
+    };
+
+    let visible = |candidate: usize| {
+        let pc = points[candidate];
+        let blocks = |loop_: &[usize]| {
+            (0..loop_.len()).any(|k| {
+                let r = points[loop_[k]];
+                let s = points[loop_[(k + 1) % loop_.len()]];
+                segments_cross(pm, pc, r, s)
+            })
+        };
+        !blocks(&ring) && !blocks(hole)
+    };
+
+    let bridge = ring
+        .iter()
+        .enumerate()
+        .filter(|(_, &i)| visible(i))
+        .min_by(|a, b| {
+            let da = (points[*a.1][0] - pm[0]).powi(2) + (points[*a.1][1] - pm[1]).powi(2);
+            let db = (points[*b.1][0] - pm[0]).powi(2) + (points[*b.1][1] - pm[1]).powi(2);
+            da.total_cmp(&db)
+        })
+        .map(|(pos, _)| pos)
+        .unwrap_or(0);
+
+    let mut spliced = Vec::with_capacity(ring.len() + hole.len() + 2);
+    spliced.extend_from_slice(&ring[..=bridge]);
+    for k in 0..=hole.len() {
+        spliced.push(hole[(m_pos + k) % hole.len()]);
+    }
+    spliced.extend_from_slice(&ring[bridge..]);
+    spliced
 }
 
 /// Triangle mesh with face provenance for picking
@@ -134,7 +674,7 @@ pub fn solid_to_pickable_mesh(solid: &Solid) -> PickableMesh {
     // Triangulate each face and track which face each triangle belongs to
     for face in &solid.faces {
         let start_tri_idx = pickable.mesh.triangles.len();
-        triangulate_face(face, solid, &mut pickable.mesh);
+        triangulate_face(face, solid, DEFAULT_CHORD_TOLERANCE, &mut pickable.mesh);
         let end_tri_idx = pickable.mesh.triangles.len();
 
         // All triangles from start_tri_idx to end_tri_idx belong to this face
@@ -143,12 +683,11 @@ pub fn solid_to_pickable_mesh(solid: &Solid) -> PickableMesh {
         }
     }
 
-    // Extract edge segments for edge picking
+    // Extract edge segments for edge picking, following curved edges
     for edge in &solid.edges {
-        if let (Some(start_v), Some(end_v)) = (solid.vertex(edge.start), solid.vertex(edge.end)) {
-            pickable
-                .edge_segments
-                .push((start_v.point, end_v.point, edge.id));
+        let polyline = edge_polyline(solid, edge, DEFAULT_CHORD_TOLERANCE);
+        for pair in polyline.windows(2) {
+            pickable.edge_segments.push((pair[0], pair[1], edge.id));
         }
     }
 
@@ -156,9 +695,10 @@ pub fn solid_to_pickable_mesh(solid: &Solid) -> PickableMesh {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::cad::primitives::make_box;
+    use crate::export::stl::write_stl_binary;
 
     #[test]
     fn test_box_to_mesh() {
@@ -183,4 +723,110 @@ mod tests {
             assert!(tri[2] < mesh.vertices.len());
         }
     }
+
+    /// Half cylinder (radius 5, height 10) bounded by true arcs
+    pub(crate) fn half_cylinder() -> Solid {
+        let mut solid = Solid::new();
+        let v0 = solid.add_vertex(Point3::new(5.0, 0.0, 0.0));
+        let v1 = solid.add_vertex(Point3::new(-5.0, 0.0, 0.0));
+        let v2 = solid.add_vertex(Point3::new(5.0, 0.0, 10.0));
+        let v3 = solid.add_vertex(Point3::new(-5.0, 0.0, 10.0));
+        let arc = |z: f32| CurveType::Arc {
+            center: Point3::new(0.0, 0.0, z),
+            radius: 5.0,
+            normal: Vector3::Z,
+            start_angle: 0.0,
+            end_angle: std::f32::consts::PI,
+        };
+        let bottom = solid.add_edge(v0, v1);
+        solid.edges[bottom.0 as usize].curve = arc(0.0);
+        let top = solid.add_edge(v2, v3);
+        solid.edges[top.0 as usize].curve = arc(10.0);
+        let base = solid.add_edge(v1, v0);
+        let cap = solid.add_edge(v3, v2);
+        let right = solid.add_edge(v0, v2);
+        let left = solid.add_edge(v1, v3);
+
+        let shell = solid.add_shell();
+        let faces: [(SurfaceType, &[(EdgeId, bool)]); 4] = [
+            (
+                SurfaceType::Cylindrical {
+                    axis: Vector3::Z,
+                    center: Point3::ORIGIN,
+                    radius: 5.0,
+                },
+                &[(bottom, true), (left, true), (top, false), (right, false)],
+            ),
+            (
+                SurfaceType::Planar {
+                    normal: Vector3::NEG_Z,
+                },
+                &[(bottom, false), (base, false)],
+            ),
+            (
+                SurfaceType::Planar { normal: Vector3::Z },
+                &[(top, true), (cap, true)],
+            ),
+            (
+                SurfaceType::Planar {
+                    normal: Vector3::NEG_Y,
+                },
+                &[(base, true), (left, true), (cap, true), (right, false)],
+            ),
+        ];
+        for (surface, edges) in faces {
+            let f = solid.add_face(surface);
+            let face = solid.face_mut(f).unwrap();
+            for &(e, forward) in edges {
+                face.outer_loop.add_edge(e, forward);
+            }
+            face.shell = Some(shell);
+            solid.shells[shell.0 as usize].add_face(f);
+        }
+        solid
+    }
+
+    #[test]
+    fn test_curved_faces_follow_tolerance() {
+        let solid = half_cylinder();
+        let expected = std::f32::consts::PI * 25.0 / 2.0 * 10.0;
+
+        let coarse = solid_to_mesh_with_tolerance(&solid, 0.1);
+        let fine = solid_to_mesh_with_tolerance(&solid, 0.001);
+        assert!(fine.triangle_count() > coarse.triangle_count());
+        assert!((coarse.volume() - expected).abs() < expected * 0.03);
+        assert!((fine.volume() - expected).abs() < expected * 1e-4);
+
+        // Every vertex on the curved face lies on the cylinder
+        for v in &fine.vertices {
+            if v.y > 1e-3 {
+                assert!(((v.x * v.x + v.y * v.y).sqrt() - 5.0).abs() < 1e-3);
+            }
+        }
+
+        // STL carries the refined triangles
+        let stl = write_stl_binary(&fine, "half_cylinder");
+        assert_eq!(stl.len(), 84 + 50 * fine.triangle_count());
+    }
+
+    #[test]
+    fn test_pickable_edges_follow_curves() {
+        let solid = half_cylinder();
+        let pickable = solid_to_pickable_mesh(&solid);
+        assert_eq!(
+            pickable.triangle_to_face.len(),
+            pickable.mesh.triangle_count()
+        );
+
+        let arc_segments: Vec<_> = pickable
+            .edge_segments
+            .iter()
+            .filter(|(_, _, id)| *id == EdgeId(0))
+            .collect();
+        assert!(arc_segments.len() > 8);
+        for (a, b, _) in arc_segments {
+            let mid = a.to_vec3().lerp(b.to_vec3(), 0.5);
+            assert!(5.0 - mid.truncate().length() <= DEFAULT_CHORD_TOLERANCE * 1.01);
+        }
+    }
 }
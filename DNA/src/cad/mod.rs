@@ -19,12 +19,16 @@
 //! │   ├── topology.rs    B-Rep topology (Vertex, Edge, Face, Shell, Solid)     │
 //! │   ├── primitives.rs  Solid generators (box, cylinder, sphere, cone)        │
 //! │   ├── mesh.rs        Mesh triangulation for export/rendering               │
+//! │   ├── nurbs.rs       NURBS evaluation, knot operations, tessellation       │
 //! │   ├── intersect.rs   Geometric intersection algorithms                     │
 //! │   ├── boolean.rs     Boolean operations (union, difference, intersection)  │
+//! │   ├── fillet.rs      Edge fillets and chamfers                             │
+//! │   ├── offset.rs      Shell, face offset and draft                          │
 //! │   ├── sketch.rs      2D parametric sketch (Point2, SketchEntity)           │
 //! │   ├── constraints.rs Sketch constraints (geometric, dimensional)           │
-//! │   ├── solver.rs      Constraint solver (Newton-Raphson)                    │
-//! │   └── extrude.rs     Sketch extrusion (2D → 3D Solid)                      │
+//! │   ├── solver.rs      Constraint solver (Levenberg-Marquardt), DOF analysis │
+//! │   ├── extrude.rs     Sketch extrusion (2D → 3D Solid)                      │
+//! │   └── sweep.rs       Sweep along a path, loft between sections             │
 //! │                                                                             │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
@@ -41,20 +45,25 @@
 pub mod boolean;
 pub mod constraints;
 pub mod extrude;
+pub mod fillet;
 pub mod geometry;
 pub mod intersect;
 pub mod mesh;
+pub mod nurbs;
+pub mod offset;
 pub mod pattern;
 pub mod primitives;
 pub mod revolve;
 pub mod sketch;
 pub mod solver;
+pub mod sweep;
 pub mod topology;
 
 // Re-export commonly used types
 pub use boolean::{difference, intersection, union, BooleanError, BooleanOp};
-pub use constraints::{Constraint, DimensionalConstraint, GeometricConstraint};
+pub use constraints::{Constraint, ConstraintEquation, DimensionalConstraint, GeometricConstraint};
 pub use extrude::{extrude_sketch, ExtrudeError, ExtrudeParams};
+pub use fillet::{chamfer_edges, fillet_edges, FilletError};
 pub use geometry::{
     BoundingBox3, Line, Plane, Point3, Ray, Segment, Transform3, Vector3, TOLERANCE,
 };
@@ -62,7 +71,12 @@ pub use intersect::{
     pick_face, plane_plane_intersect, point_in_solid, ray_cylinder_intersect, ray_sphere_intersect,
     ray_triangle_intersect, Classification, FaceHit,
 };
-pub use mesh::{solid_to_mesh, solid_to_pickable_mesh, PickableMesh, TriangleMesh};
+pub use mesh::{
+    solid_to_mesh, solid_to_mesh_with_tolerance, solid_to_pickable_mesh, PickableMesh,
+    TriangleMesh, DEFAULT_CHORD_TOLERANCE,
+};
+pub use nurbs::{NurbsCurve, NurbsError, NurbsSurface};
+pub use offset::{draft_faces, offset_face, shell_solid, OffsetError};
 pub use pattern::{circular_pattern, linear_pattern};
 pub use primitives::{
     make_box, make_box_at, make_cone, make_cone_at, make_cylinder, make_cylinder_at, make_sphere,
@@ -71,9 +85,10 @@ pub use primitives::{
 pub use revolve::{revolve_sketch, RevolveAxis, RevolveError, RevolveParams};
 pub use sketch::{
     ConstraintId, Point2, Sketch, SketchCoordinateFrame, SketchEntity, SketchEntityId, SketchPlane,
-    SketchPoint, SketchPointId,
+    SketchPoint, SketchPointId, SketchSlot,
 };
 pub use solver::{ConstraintAnalysis, ConstraintSolver, DofStatus, SolverConfig, SolverResult};
+pub use sweep::{loft_sketches, sweep_sketch, SweepError, SweepPath};
 pub use topology::{
     CurveType, Edge, EdgeId, Face, FaceId, FaceOrientation, Loop, Shell, ShellId, Solid,
     SurfaceType, Vertex, VertexId,
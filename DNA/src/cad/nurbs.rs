@@ -0,0 +1,1698 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: nurbs.rs | DNA/src/cad/nurbs.rs
+//! PURPOSE: NURBS curve and surface evaluation, knot operations and tessellation
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ NURBS                                                                       │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │                                                                             │
+//! │   C(u)   = Σ Nᵢ,ₚ(u) wᵢ Pᵢ / Σ Nᵢ,ₚ(u) wᵢ                                   │
+//! │   S(u,v) = ΣΣ Nᵢ,ₚ(u) Nⱼ,q(v) wᵢⱼ Pᵢⱼ / ΣΣ Nᵢ,ₚ(u) Nⱼ,q(v) wᵢⱼ              │
+//! │                                                                             │
+//! │   Control points are held in homogeneous form (w·x, w·y, w·z, w), so        │
+//! │   evaluation (De Boor), knot insertion (Boehm) and degree elevation are     │
+//! │   the polynomial B-spline algorithms applied in 4D, then projected.         │
+//! │                                                                             │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! `NurbsCurve` and `NurbsSurface` are evaluators built from the
+//! `CurveType::Nurbs` and `SurfaceType::Nurbs` data stored on edges and faces
+//! (`from_curve` / `to_curve`). Parameters are in the knot domain
+//! `[knots[p], knots[n + 1]]`; values outside it are clamped.
+//!
+//! Algorithm numbers refer to Piegl & Tiller, "The NURBS Book" (2nd ed.).
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::geometry::{Point3, Vector3};
+use super::mesh::{triangulate_polygon, TriangleMesh};
+use super::topology::{CurveType, SurfaceType};
+use glam::{DVec2, DVec3, DVec4};
+use std::collections::{HashMap, HashSet};
+
+/// Deepest bisection used when tessellating a parameter interval
+const MAX_DEPTH: u32 = 12;
+
+/// Refinement passes over a surface triangulation
+const MAX_REFINE_ROUNDS: usize = 12;
+
+/// Triangle budget per tessellated surface
+const MAX_TRIANGLES: usize = 50_000;
+
+/// Error type for building NURBS evaluators
+#[derive(Debug, Clone)]
+pub enum NurbsError {
+    /// Geometry is not stored as NURBS
+    NotNurbs,
+    /// Degree is zero, or there are not more control points than the degree
+    InvalidDegree,
+    /// Knot vector has the wrong length, decreases, or has an empty domain
+    InvalidKnots,
+    /// Weights do not match the control points, or one is not positive
+    InvalidWeights,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Curves
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// NURBS curve evaluator
+#[derive(Clone, Debug)]
+pub struct NurbsCurve {
+    degree: usize,
+    knots: Vec<f64>,
+    /// Homogeneous control points (w·x, w·y, w·z, w)
+    points: Vec<DVec4>,
+}
+
+impl NurbsCurve {
+    /// Build a curve, checking that degree, knots and weights agree
+    pub fn new(
+        degree: u32,
+        control_points: &[Point3],
+        weights: &[f32],
+        knots: &[f32],
+    ) -> Result<Self, NurbsError> {
+        let degree = degree as usize;
+        check_knots(degree, control_points.len(), knots)?;
+        check_weights(weights, control_points.len())?;
+        Ok(Self {
+            degree,
+            knots: knots.iter().map(|&k| k as f64).collect(),
+            points: control_points
+                .iter()
+                .zip(weights)
+                .map(|(&p, &w)| homogeneous(p, w))
+                .collect(),
+        })
+    }
+
+    /// Evaluator for a `CurveType::Nurbs` edge curve
+    pub fn from_curve(curve: &CurveType) -> Result<Self, NurbsError> {
+        match curve {
+            CurveType::Nurbs {
+                control_points,
+                weights,
+                knots,
+                degree,
+            } => Self::new(*degree, control_points, weights, knots),
+            _ => Err(NurbsError::NotNurbs),
+        }
+    }
+
+    /// Store the curve as edge geometry
+    pub fn to_curve(&self) -> CurveType {
+        CurveType::Nurbs {
+            control_points: self.control_points(),
+            weights: self.weights(),
+            knots: self.knots(),
+            degree: self.degree as u32,
+        }
+    }
+
+    pub fn degree(&self) -> u32 {
+        self.degree as u32
+    }
+
+    pub fn control_points(&self) -> Vec<Point3> {
+        self.points.iter().map(|&p| to_point(project(p))).collect()
+    }
+
+    pub fn weights(&self) -> Vec<f32> {
+        self.points.iter().map(|p| p.w as f32).collect()
+    }
+
+    pub fn knots(&self) -> Vec<f32> {
+        self.knots.iter().map(|&k| k as f32).collect()
+    }
+
+    /// Parameter range `(knots[p], knots[n + 1])`
+    pub fn domain(&self) -> (f32, f32) {
+        let (a, b) = self.domain_f64();
+        (a as f32, b as f32)
+    }
+
+    /// Point at parameter `u`, by De Boor's algorithm
+    pub fn point_at(&self, u: f32) -> Point3 {
+        to_point(self.eval(u as f64))
+    }
+
+    /// Point at `t` in 0..1 across the whole domain
+    pub(crate) fn point_at_normalized(&self, t: f32) -> Point3 {
+        let (a, b) = self.domain_f64();
+        to_point(self.eval(a + (b - a) * t as f64))
+    }
+
+    /// Derivatives `C, C', C'', …` up to `order` at parameter `u` (A4.2)
+    pub fn derivatives(&self, u: f32, order: usize) -> Vec<Vector3> {
+        self.derivatives_f64(u as f64, order)
+            .into_iter()
+            .map(to_vector)
+            .collect()
+    }
+
+    /// Unit tangent at parameter `u`
+    pub fn tangent_at(&self, u: f32) -> Vector3 {
+        let d = self.derivatives_f64(u as f64, 1);
+        to_vector(d[1].normalize_or_zero())
+    }
+
+    /// Insert knot `u` up to `times` times without changing the curve (A5.1)
+    ///
+    /// Multiplicity is capped at the degree; knots outside the open domain
+    /// are ignored.
+    pub fn insert_knot(&self, u: f32, times: usize) -> Self {
+        let (points, knots) = insert_knot(&self.points, &self.knots, self.degree, u as f64, times);
+        Self {
+            degree: self.degree,
+            knots,
+            points,
+        }
+    }
+
+    /// Raise the degree by `times` without changing the curve
+    ///
+    /// Every distinct knot gains `times` in multiplicity, which keeps the
+    /// continuity of the original; the new control points are found by
+    /// interpolating the curve at the Greville abscissae of the new basis.
+    pub fn elevate_degree(&self, times: u32) -> Self {
+        let (points, knots) =
+            elevate_degree(&self.points, &self.knots, self.degree, times as usize);
+        Self {
+            degree: self.degree + times as usize,
+            knots,
+            points,
+        }
+    }
+
+    /// Polyline through the curve within `tolerance` of it
+    ///
+    /// Each knot span starts as `degree + 1` pieces that are bisected until
+    /// the curve stays within the tolerance of every chord.
+    pub fn tessellate(&self, tolerance: f32) -> Vec<Point3> {
+        let mut params = Vec::new();
+        for span in self.knots.windows(2) {
+            let (a, b) = self.domain_f64();
+            let (lo, hi) = (span[0].max(a), span[1].min(b));
+            if hi - lo > 1e-12 {
+                for k in 0..=self.degree {
+                    params.push(lo + (hi - lo) * k as f64 / (self.degree + 1) as f64);
+                }
+            }
+        }
+        params.push(self.domain_f64().1);
+
+        adaptive_samples(|u| self.eval(u), &params, tolerance as f64)
+            .into_iter()
+            .map(|(_, p)| to_point(p))
+            .collect()
+    }
+
+    fn domain_f64(&self) -> (f64, f64) {
+        (self.knots[self.degree], self.knots[self.points.len()])
+    }
+
+    fn eval(&self, u: f64) -> DVec3 {
+        let (a, b) = self.domain_f64();
+        project(de_boor(
+            &self.points,
+            &self.knots,
+            self.degree,
+            u.clamp(a, b),
+        ))
+    }
+
+    fn derivatives_f64(&self, u: f64, order: usize) -> Vec<DVec3> {
+        let (a, b) = self.domain_f64();
+        let u = u.clamp(a, b);
+        let p = self.degree;
+        let span = find_span(self.points.len() - 1, p, u, &self.knots);
+        let basis = ders_basis_funs(span, u, p, order.min(p), &self.knots);
+
+        let mut homogeneous = vec![DVec4::ZERO; order + 1];
+        for (k, row) in basis.iter().enumerate() {
+            for (j, n) in row.iter().enumerate() {
+                homogeneous[k] += self.points[span - p + j] * *n;
+            }
+        }
+        rational_derivatives(&homogeneous)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Surfaces
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// NURBS surface evaluator
+///
+/// Control points are indexed `[u][v]`, matching `SurfaceType::Nurbs`.
+#[derive(Clone, Debug)]
+pub struct NurbsSurface {
+    u_degree: usize,
+    v_degree: usize,
+    u_knots: Vec<f64>,
+    v_knots: Vec<f64>,
+    /// Homogeneous control net `[u][v]`
+    points: Vec<Vec<DVec4>>,
+}
+
+impl NurbsSurface {
+    /// Build a surface, checking that degrees, knots and weights agree
+    pub fn new(
+        u_degree: u32,
+        v_degree: u32,
+        control_points: &[Vec<Point3>],
+        weights: &[Vec<f32>],
+        u_knots: &[f32],
+        v_knots: &[f32],
+    ) -> Result<Self, NurbsError> {
+        let (u_degree, v_degree) = (u_degree as usize, v_degree as usize);
+        let columns = control_points.first().map_or(0, Vec::len);
+        if control_points.iter().any(|row| row.len() != columns) {
+            return Err(NurbsError::InvalidDegree);
+        }
+        check_knots(u_degree, control_points.len(), u_knots)?;
+        check_knots(v_degree, columns, v_knots)?;
+        if weights.len() != control_points.len() {
+            return Err(NurbsError::InvalidWeights);
+        }
+        for row in weights {
+            check_weights(row, columns)?;
+        }
+
+        Ok(Self {
+            u_degree,
+            v_degree,
+            u_knots: u_knots.iter().map(|&k| k as f64).collect(),
+            v_knots: v_knots.iter().map(|&k| k as f64).collect(),
+            points: control_points
+                .iter()
+                .zip(weights)
+                .map(|(row, w)| {
+                    row.iter()
+                        .zip(w)
+                        .map(|(&p, &w)| homogeneous(p, w))
+                        .collect()
+                })
+                .collect(),
+        })
+    }
+
+    /// Evaluator for a `SurfaceType::Nurbs` face surface
+    pub fn from_surface(surface: &SurfaceType) -> Result<Self, NurbsError> {
+        match surface {
+            SurfaceType::Nurbs {
+                control_points,
+                weights,
+                u_knots,
+                v_knots,
+                u_degree,
+                v_degree,
+            } => Self::new(
+                *u_degree,
+                *v_degree,
+                control_points,
+                weights,
+                u_knots,
+                v_knots,
+            ),
+            _ => Err(NurbsError::NotNurbs),
+        }
+    }
+
+    /// Store the surface as face geometry
+    pub fn to_surface(&self) -> SurfaceType {
+        SurfaceType::Nurbs {
+            control_points: self
+                .points
+                .iter()
+                .map(|row| row.iter().map(|&p| to_point(project(p))).collect())
+                .collect(),
+            weights: self
+                .points
+                .iter()
+                .map(|row| row.iter().map(|p| p.w as f32).collect())
+                .collect(),
+            u_knots: self.u_knots.iter().map(|&k| k as f32).collect(),
+            v_knots: self.v_knots.iter().map(|&k| k as f32).collect(),
+            u_degree: self.u_degree as u32,
+            v_degree: self.v_degree as u32,
+        }
+    }
+
+    pub fn degrees(&self) -> (u32, u32) {
+        (self.u_degree as u32, self.v_degree as u32)
+    }
+
+    /// Parameter ranges `((u_min, u_max), (v_min, v_max))`
+    pub fn domain(&self) -> ((f32, f32), (f32, f32)) {
+        let ((u0, u1), (v0, v1)) = self.domain_f64();
+        ((u0 as f32, u1 as f32), (v0 as f32, v1 as f32))
+    }
+
+    /// Point at `(u, v)`, by De Boor's algorithm in each direction
+    pub fn point_at(&self, u: f32, v: f32) -> Point3 {
+        to_point(self.eval(DVec2::new(u as f64, v as f64)))
+    }
+
+    /// Mixed partial derivatives `∂ᵏ⁺ˡS/∂uᵏ∂vˡ` for `k + l ≤ order` (A4.4)
+    ///
+    /// Indexed `[k][l]`; entries with `k + l > order` are zero.
+    pub fn derivatives(&self, u: f32, v: f32, order: usize) -> Vec<Vec<Vector3>> {
+        self.derivatives_f64(DVec2::new(u as f64, v as f64), order)
+            .into_iter()
+            .map(|row| row.into_iter().map(to_vector).collect())
+            .collect()
+    }
+
+    /// Unit normal `S_u × S_v` at `(u, v)`
+    ///
+    /// At a degenerate point (a pole, where one partial vanishes) the normal
+    /// is taken a little way towards the middle of the domain.
+    pub fn normal_at(&self, u: f32, v: f32) -> Vector3 {
+        to_vector(self.normal_f64(DVec2::new(u as f64, v as f64)))
+    }
+
+    /// Parameters of the surface point closest to `point`
+    ///
+    /// A coarse grid search seeds a Gauss–Newton iteration.
+    pub fn closest_parameters(&self, point: Point3) -> (f32, f32) {
+        let uv = self.invert(to_dvec(point));
+        (uv.x as f32, uv.y as f32)
+    }
+
+    /// Insert knot `u` up to `times` times in the u direction
+    pub fn insert_knot_u(&self, u: f32, times: usize) -> Self {
+        self.map_columns(
+            |column, knots, p| insert_knot(column, knots, p, u as f64, times),
+            0,
+        )
+    }
+
+    /// Insert knot `v` up to `times` times in the v direction
+    pub fn insert_knot_v(&self, v: f32, times: usize) -> Self {
+        self.map_rows(
+            |row, knots, q| insert_knot(row, knots, q, v as f64, times),
+            0,
+        )
+    }
+
+    /// Raise the u degree by `times` without changing the surface
+    pub fn elevate_degree_u(&self, times: u32) -> Self {
+        let t = times as usize;
+        self.map_columns(|column, knots, p| elevate_degree(column, knots, p, t), t)
+    }
+
+    /// Raise the v degree by `times` without changing the surface
+    pub fn elevate_degree_v(&self, times: u32) -> Self {
+        let t = times as usize;
+        self.map_rows(|row, knots, q| elevate_degree(row, knots, q, t), t)
+    }
+
+    /// Triangulate the whole parameter domain within `tolerance`
+    pub fn tessellate(&self, tolerance: f32) -> TriangleMesh {
+        let ((u0, u1), (v0, v1)) = self.domain_f64();
+        let tol = tolerance as f64;
+        let side = |from: DVec2, to: DVec2| -> Vec<DVec2> {
+            let params: Vec<f64> = (0..=4).map(|k| k as f64 / 4.0).collect();
+            let mut samples: Vec<DVec2> =
+                adaptive_samples(|t| self.eval(from.lerp(to, t)), &params, tol)
+                    .into_iter()
+                    .map(|(t, _)| from.lerp(to, t))
+                    .collect();
+            samples.pop();
+            samples
+        };
+        let corners = [
+            DVec2::new(u0, v0),
+            DVec2::new(u1, v0),
+            DVec2::new(u1, v1),
+            DVec2::new(u0, v1),
+        ];
+        let boundary: Vec<DVec2> = (0..4)
+            .flat_map(|i| side(corners[i], corners[(i + 1) % 4]))
+            .collect();
+        let points: Vec<DVec3> = boundary.iter().map(|&uv| self.eval(uv)).collect();
+
+        let mut mesh = TriangleMesh::new();
+        if let Some((vertices, triangles)) =
+            triangulate_parametric(|uv| self.eval(uv), &[boundary], &[points], tol)
+        {
+            append_triangles(&mut mesh, &vertices, &triangles);
+        }
+        mesh
+    }
+
+    /// Triangulate the part of the surface bounded by closed 3D loops (outer
+    /// loop first, then holes) lying on it
+    ///
+    /// Boundary points are kept exactly, so faces sharing an edge meet
+    /// without cracks. Triangles follow the winding of the outer loop.
+    /// Returns `None` if the loops do not map to a usable parameter region.
+    pub(crate) fn tessellate_trimmed(
+        &self,
+        loops: &[Vec<Point3>],
+        tolerance: f32,
+    ) -> Option<(Vec<Point3>, Vec<[usize; 3]>)> {
+        let points: Vec<Vec<DVec3>> = loops
+            .iter()
+            .map(|l| l.iter().map(|&p| to_dvec(p)).collect())
+            .collect();
+        let uv: Vec<Vec<DVec2>> = points.iter().map(|l| self.invert_loop(l)).collect();
+
+        // Every boundary point must actually lie on the surface
+        let scale = self.size().max(1.0);
+        for (l, params) in points.iter().zip(&uv) {
+            for (p, q) in l.iter().zip(params) {
+                if (self.eval(*q) - *p).length() > 1e-3 * scale {
+                    return None;
+                }
+            }
+        }
+
+        let (vertices, triangles) =
+            triangulate_parametric(|uv| self.eval(uv), &uv, &points, tolerance as f64)?;
+        Some((vertices.into_iter().map(to_point).collect(), triangles))
+    }
+
+    fn domain_f64(&self) -> ((f64, f64), (f64, f64)) {
+        (
+            (self.u_knots[self.u_degree], self.u_knots[self.points.len()]),
+            (
+                self.v_knots[self.v_degree],
+                self.v_knots[self.points[0].len()],
+            ),
+        )
+    }
+
+    fn clamp(&self, uv: DVec2) -> DVec2 {
+        let ((u0, u1), (v0, v1)) = self.domain_f64();
+        DVec2::new(uv.x.clamp(u0, u1), uv.y.clamp(v0, v1))
+    }
+
+    fn eval(&self, uv: DVec2) -> DVec3 {
+        let uv = self.clamp(uv);
+        let (p, q) = (self.u_degree, self.v_degree);
+        let span = find_span(self.points.len() - 1, p, uv.x, &self.u_knots);
+        let column: Vec<DVec4> = (span - p..=span)
+            .map(|i| de_boor(&self.points[i], &self.v_knots, q, uv.y))
+            .collect();
+        project(de_boor_span(column, &self.u_knots, p, span, uv.x))
+    }
+
+    fn derivatives_f64(&self, uv: DVec2, order: usize) -> Vec<Vec<DVec3>> {
+        let uv = self.clamp(uv);
+        let (p, q) = (self.u_degree, self.v_degree);
+        let u_span = find_span(self.points.len() - 1, p, uv.x, &self.u_knots);
+        let v_span = find_span(self.points[0].len() - 1, q, uv.y, &self.v_knots);
+        let nu = ders_basis_funs(u_span, uv.x, p, order.min(p), &self.u_knots);
+        let nv = ders_basis_funs(v_span, uv.y, q, order.min(q), &self.v_knots);
+
+        let mut homogeneous = vec![vec![DVec4::ZERO; order + 1]; order + 1];
+        for (k, nu_k) in nu.iter().enumerate() {
+            let temp: Vec<DVec4> = (0..=q)
+                .map(|s| {
+                    (0..=p)
+                        .map(|r| self.points[u_span - p + r][v_span - q + s] * nu_k[r])
+                        .sum()
+                })
+                .collect();
+            for (l, nv_l) in nv.iter().enumerate().take(order - k + 1) {
+                homogeneous[k][l] = (0..=q).map(|s| temp[s] * nv_l[s]).sum();
+            }
+        }
+
+        // Rational derivatives from the homogeneous ones (A4.4)
+        let mut skl = vec![vec![DVec3::ZERO; order + 1]; order + 1];
+        for k in 0..=order {
+            for l in 0..=order - k {
+                let a = homogeneous[k][l];
+                let mut v = a.truncate();
+                for j in 1..=l {
+                    v -= skl[k][l - j] * (binomial(l, j) * homogeneous[0][j].w);
+                }
+                for i in 1..=k {
+                    v -= skl[k - i][l] * (binomial(k, i) * homogeneous[i][0].w);
+                    let mut v2 = DVec3::ZERO;
+                    for j in 1..=l {
+                        v2 += skl[k - i][l - j] * (binomial(l, j) * homogeneous[i][j].w);
+                    }
+                    v -= v2 * binomial(k, i);
+                }
+                skl[k][l] = v / homogeneous[0][0].w;
+            }
+        }
+        skl
+    }
+
+    fn normal_f64(&self, uv: DVec2) -> DVec3 {
+        let ((u0, u1), (v0, v1)) = self.domain_f64();
+        let middle = DVec2::new((u0 + u1) * 0.5, (v0 + v1) * 0.5);
+        for nudge in [0.0, 1e-4, 1e-2] {
+            let d = self.derivatives_f64(uv.lerp(middle, nudge), 1);
+            let n = d[1][0].cross(d[0][1]);
+            if n.length() > 1e-12 {
+                return n.normalize();
+            }
+        }
+        DVec3::Z
+    }
+
+    /// Rough size of the control net, for relative tolerances
+    fn size(&self) -> f64 {
+        let (mut lo, mut hi) = (DVec3::splat(f64::MAX), DVec3::splat(f64::MIN));
+        for p in self.points.iter().flatten() {
+            lo = lo.min(project(*p));
+            hi = hi.max(project(*p));
+        }
+        (hi - lo).length()
+    }
+
+    fn invert(&self, point: DVec3) -> DVec2 {
+        let ((u0, u1), (v0, v1)) = self.domain_f64();
+        let grid = |n: usize| (4 * n).clamp(8, 64);
+        let (gu, gv) = (grid(self.points.len()), grid(self.points[0].len()));
+
+        let mut best = DVec2::new(u0, v0);
+        let mut best_distance = f64::MAX;
+        for i in 0..=gu {
+            for j in 0..=gv {
+                let uv = DVec2::new(
+                    u0 + (u1 - u0) * i as f64 / gu as f64,
+                    v0 + (v1 - v0) * j as f64 / gv as f64,
+                );
+                let d = (self.eval(uv) - point).length_squared();
+                if d < best_distance {
+                    best_distance = d;
+                    best = uv;
+                }
+            }
+        }
+
+        // Gauss–Newton on |S(u, v) - P|²
+        let mut uv = best;
+        for _ in 0..30 {
+            let d = self.derivatives_f64(uv, 1);
+            let r = d[0][0] - point;
+            let (su, sv) = (d[1][0], d[0][1]);
+            let (a, b, c) = (su.dot(su), su.dot(sv), sv.dot(sv));
+            let det = a * c - b * b;
+            if det.abs() < 1e-24 {
+                break;
+            }
+            let (gu, gv) = (su.dot(r), sv.dot(r));
+            let step = DVec2::new((c * gu - b * gv) / det, (a * gv - b * gu) / det);
+            let next = self.clamp(uv - step);
+            let moved = (next - uv).length();
+            uv = next;
+            if moved < 1e-12 * (1.0 + uv.length()) {
+                break;
+            }
+        }
+        uv
+    }
+
+    /// Parameters of a closed loop of surface points, kept continuous across
+    /// the seam of a surface that closes on itself
+    fn invert_loop(&self, points: &[DVec3]) -> Vec<DVec2> {
+        let mut uv: Vec<DVec2> = points.iter().map(|&p| self.invert(p)).collect();
+        let ((u0, u1), (v0, v1)) = self.domain_f64();
+        let closed_u = self.closes(true);
+        let closed_v = self.closes(false);
+        if uv.is_empty() || !(closed_u || closed_v) {
+            return uv;
+        }
+
+        // On a seam the parameter can be either end; take whichever is
+        // nearer the neighbouring point (the next one for the first point)
+        let pick = |value: f64, lo: f64, hi: f64, neighbour: f64| {
+            let span = hi - lo;
+            if (value - lo).abs() < 1e-6 * span || (value - hi).abs() < 1e-6 * span {
+                if (neighbour - lo).abs() < (neighbour - hi).abs() {
+                    lo
+                } else {
+                    hi
+                }
+            } else {
+                value
+            }
+        };
+        let n = uv.len();
+        for i in 0..n {
+            let neighbour = if i == 0 { uv[1 % n] } else { uv[i - 1] };
+            if closed_u {
+                uv[i].x = pick(uv[i].x, u0, u1, neighbour.x);
+            }
+            if closed_v {
+                uv[i].y = pick(uv[i].y, v0, v1, neighbour.y);
+            }
+        }
+        uv
+    }
+
+    /// Whether the first and last rows (u) or columns (v) of the net coincide
+    fn closes(&self, along_u: bool) -> bool {
+        let tol = 1e-6 * self.size().max(1.0);
+        let same = |a: DVec4, b: DVec4| (project(a) - project(b)).length() < tol;
+        if along_u {
+            let (first, last) = (&self.points[0], &self.points[self.points.len() - 1]);
+            first.iter().zip(last).all(|(&a, &b)| same(a, b))
+        } else {
+            self.points
+                .iter()
+                .all(|row| same(row[0], row[row.len() - 1]))
+        }
+    }
+
+    fn map_columns(
+        &self,
+        op: impl Fn(&[DVec4], &[f64], usize) -> (Vec<DVec4>, Vec<f64>),
+        degree_increase: usize,
+    ) -> Self {
+        let columns = self.points[0].len();
+        let mut new_columns = Vec::with_capacity(columns);
+        let mut knots = self.u_knots.clone();
+        for j in 0..columns {
+            let column: Vec<DVec4> = self.points.iter().map(|row| row[j]).collect();
+            let (points, k) = op(&column, &self.u_knots, self.u_degree);
+            new_columns.push(points);
+            knots = k;
+        }
+        let rows = new_columns[0].len();
+        Self {
+            u_degree: self.u_degree + degree_increase,
+            v_degree: self.v_degree,
+            u_knots: knots,
+            v_knots: self.v_knots.clone(),
+            points: (0..rows)
+                .map(|i| new_columns.iter().map(|c| c[i]).collect())
+                .collect(),
+        }
+    }
+
+    fn map_rows(
+        &self,
+        op: impl Fn(&[DVec4], &[f64], usize) -> (Vec<DVec4>, Vec<f64>),
+        degree_increase: usize,
+    ) -> Self {
+        let mut knots = self.v_knots.clone();
+        let points = self
+            .points
+            .iter()
+            .map(|row| {
+                let (points, k) = op(row, &self.v_knots, self.v_degree);
+                knots = k;
+                points
+            })
+            .collect();
+        Self {
+            u_degree: self.u_degree,
+            v_degree: self.v_degree + degree_increase,
+            u_knots: self.u_knots.clone(),
+            v_knots: knots,
+            points,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// B-spline algorithms on homogeneous control points
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Knot span index `i` with `knots[i] ≤ u < knots[i + 1]` (A2.1)
+///
+/// `n` is the last control point index. The end of the domain belongs to the
+/// last non-empty span, so `u = knots[n + 1]` evaluates the final point.
+fn find_span(n: usize, p: usize, u: f64, knots: &[f64]) -> usize {
+    if u >= knots[n + 1] {
+        // Last span with non-zero length
+        let mut span = n;
+        while span > p && knots[span] >= knots[n + 1] {
+            span -= 1;
+        }
+        return span;
+    }
+    if u <= knots[p] {
+        let mut span = p;
+        while span < n && knots[span + 1] <= u {
+            span += 1;
+        }
+        return span;
+    }
+
+    let (mut low, mut high) = (p, n + 1);
+    let mut mid = (low + high) / 2;
+    while u < knots[mid] || u >= knots[mid + 1] {
+        if u < knots[mid] {
+            high = mid;
+        } else {
+            low = mid;
+        }
+        mid = (low + high) / 2;
+    }
+    mid
+}
+
+/// De Boor's algorithm over a full control polygon
+fn de_boor(points: &[DVec4], knots: &[f64], p: usize, u: f64) -> DVec4 {
+    let span = find_span(points.len() - 1, p, u, knots);
+    de_boor_span(points[span - p..=span].to_vec(), knots, p, span, u)
+}
+
+/// De Boor's algorithm on the `p + 1` control points of span `span`
+fn de_boor_span(mut d: Vec<DVec4>, knots: &[f64], p: usize, span: usize, u: f64) -> DVec4 {
+    for r in 1..=p {
+        for j in (r..=p).rev() {
+            let i = j + span - p;
+            let denom = knots[i + p + 1 - r] - knots[i];
+            let alpha = if denom.abs() < 1e-14 {
+                0.0
+            } else {
+                (u - knots[i]) / denom
+            };
+            d[j] = d[j - 1] * (1.0 - alpha) + d[j] * alpha;
+        }
+    }
+    d[p]
+}
+
+/// Basis functions and their derivatives up to `n` at `u` (A2.3)
+///
+/// Returns `ders[k][j]`, the k-th derivative of `N(span - p + j), p`.
+fn ders_basis_funs(span: usize, u: f64, p: usize, n: usize, knots: &[f64]) -> Vec<Vec<f64>> {
+    let mut ndu = vec![vec![0.0; p + 1]; p + 1];
+    let mut left = vec![0.0; p + 1];
+    let mut right = vec![0.0; p + 1];
+    ndu[0][0] = 1.0;
+    for j in 1..=p {
+        left[j] = u - knots[span + 1 - j];
+        right[j] = knots[span + j] - u;
+        let mut saved = 0.0;
+        for r in 0..j {
+            ndu[j][r] = right[r + 1] + left[j - r];
+            let temp = ndu[r][j - 1] / ndu[j][r];
+            ndu[r][j] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        ndu[j][j] = saved;
+    }
+
+    let mut ders = vec![vec![0.0; p + 1]; n + 1];
+    for j in 0..=p {
+        ders[0][j] = ndu[j][p];
+    }
+
+    let (pi, ni) = (p as isize, n as isize);
+    let mut a = [vec![0.0; p + 1], vec![0.0; p + 1]];
+    for r in 0..=pi {
+        let (mut s1, mut s2) = (0, 1);
+        a[0][0] = 1.0;
+        for k in 1..=ni {
+            let mut d = 0.0;
+            let rk = r - k;
+            let pk = pi - k;
+            if r >= k {
+                a[s2][0] = a[s1][0] / ndu[(pk + 1) as usize][rk as usize];
+                d = a[s2][0] * ndu[rk as usize][pk as usize];
+            }
+            let j1 = if rk >= -1 { 1 } else { -rk };
+            let j2 = if r - 1 <= pk { k - 1 } else { pi - r };
+            for j in j1..=j2 {
+                let (ju, rkj) = (j as usize, (rk + j) as usize);
+                a[s2][ju] = (a[s1][ju] - a[s1][ju - 1]) / ndu[(pk + 1) as usize][rkj];
+                d += a[s2][ju] * ndu[rkj][pk as usize];
+            }
+            if r <= pk {
+                a[s2][k as usize] = -a[s1][(k - 1) as usize] / ndu[(pk + 1) as usize][r as usize];
+                d += a[s2][k as usize] * ndu[r as usize][pk as usize];
+            }
+            ders[k as usize][r as usize] = d;
+            std::mem::swap(&mut s1, &mut s2);
+        }
+    }
+
+    let mut factor = p as f64;
+    for (k, row) in ders.iter_mut().enumerate().skip(1) {
+        for value in row.iter_mut() {
+            *value *= factor;
+        }
+        factor *= (p - k) as f64;
+    }
+    ders
+}
+
+/// Curve derivatives from homogeneous ones (A4.2)
+fn rational_derivatives(homogeneous: &[DVec4]) -> Vec<DVec3> {
+    let mut ck: Vec<DVec3> = Vec::with_capacity(homogeneous.len());
+    for k in 0..homogeneous.len() {
+        let mut v = homogeneous[k].truncate();
+        for i in 1..=k {
+            v -= ck[k - i] * (binomial(k, i) * homogeneous[i].w);
+        }
+        ck.push(v / homogeneous[0].w);
+    }
+    ck
+}
+
+/// Boehm knot insertion (A5.1), capped so no knot exceeds multiplicity `p`
+fn insert_knot(
+    points: &[DVec4],
+    knots: &[f64],
+    p: usize,
+    u: f64,
+    times: usize,
+) -> (Vec<DVec4>, Vec<f64>) {
+    let n = points.len() - 1;
+    let (lo, hi) = (knots[p], knots[n + 1]);
+    let s = knots.iter().filter(|&&k| (k - u).abs() < 1e-12).count();
+    let r = times.min(p.saturating_sub(s));
+    if r == 0 || u <= lo || u >= hi {
+        return (points.to_vec(), knots.to_vec());
+    }
+    let k = find_span(n, p, u, knots);
+
+    let mut new_knots = Vec::with_capacity(knots.len() + r);
+    new_knots.extend_from_slice(&knots[..=k]);
+    new_knots.extend(std::iter::repeat_n(u, r));
+    new_knots.extend_from_slice(&knots[k + 1..]);
+
+    let mut q = vec![DVec4::ZERO; points.len() + r];
+    q[..=k - p].copy_from_slice(&points[..=k - p]);
+    q[k - s + r..=n + r].copy_from_slice(&points[k - s..=n]);
+    let mut temp: Vec<DVec4> = (0..=p - s).map(|i| points[k - p + i]).collect();
+    let mut l = k - p;
+    for j in 1..=r {
+        l = k - p + j;
+        for i in 0..=p - j - s {
+            let alpha = (u - knots[l + i]) / (knots[i + k + 1] - knots[l + i]);
+            temp[i] = temp[i + 1] * alpha + temp[i] * (1.0 - alpha);
+        }
+        q[l] = temp[0];
+        q[k + r - j - s] = temp[p - j - s];
+    }
+    if l + 1 < k - s {
+        q[l + 1..k - s].copy_from_slice(&temp[1..k - s - l]);
+    }
+    (q, new_knots)
+}
+
+/// Degree elevation by `t`: every distinct knot gains `t` in multiplicity,
+/// and the new control points interpolate the curve at the Greville
+/// abscissae of the new basis, which reproduces it exactly
+fn elevate_degree(points: &[DVec4], knots: &[f64], p: usize, t: usize) -> (Vec<DVec4>, Vec<f64>) {
+    if t == 0 {
+        return (points.to_vec(), knots.to_vec());
+    }
+    let q = p + t;
+
+    let mut new_knots = Vec::with_capacity(knots.len() * 2);
+    let mut i = 0;
+    while i < knots.len() {
+        let mut j = i;
+        while j < knots.len() && (knots[j] - knots[i]).abs() < 1e-12 {
+            j += 1;
+        }
+        new_knots.extend(std::iter::repeat_n(knots[i], j - i + t));
+        i = j;
+    }
+    let count = new_knots.len() - q - 1;
+
+    let greville: Vec<f64> = (0..count)
+        .map(|i| new_knots[i + 1..=i + q].iter().sum::<f64>() / q as f64)
+        .collect();
+
+    // Collocation matrix of the new basis at the Greville points
+    let mut matrix = vec![vec![0.0; count]; count];
+    let mut rhs = Vec::with_capacity(count);
+    for (row, &u) in greville.iter().enumerate() {
+        let span = find_span(count - 1, q, u, &new_knots);
+        let basis = ders_basis_funs(span, u, q, 0, &new_knots);
+        for (j, value) in basis[0].iter().enumerate() {
+            matrix[row][span - q + j] = *value;
+        }
+        rhs.push(de_boor(points, knots, p, u));
+    }
+
+    (solve_dense(matrix, rhs), new_knots)
+}
+
+/// Gaussian elimination with partial pivoting, one DVec4 right-hand side
+/// per row
+fn solve_dense(mut a: Vec<Vec<f64>>, mut b: Vec<DVec4>) -> Vec<DVec4> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&x, &y| a[x][col].abs().total_cmp(&a[y][col].abs()))
+            .unwrap_or(col);
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let diag = a[col][col];
+        if diag.abs() < 1e-300 {
+            continue;
+        }
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[col] / diag;
+            if factor == 0.0 {
+                continue;
+            }
+            for (value, pivot) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *value -= factor * pivot;
+            }
+            let pivot_rhs = b[col];
+            b[col + 1 + offset] -= pivot_rhs * factor;
+        }
+    }
+    let mut x = vec![DVec4::ZERO; n];
+    for row in (0..n).rev() {
+        let mut sum = b[row];
+        for k in row + 1..n {
+            sum -= x[k] * a[row][k];
+        }
+        x[row] = if a[row][row].abs() < 1e-300 {
+            sum
+        } else {
+            sum / a[row][row]
+        };
+    }
+    x
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tessellation helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Triangulate a trimmed region of the parametric surface `eval`: ear-clip
+/// the parameter-space loops (outer first, then holes) whose 3D points are
+/// `points`, then split triangle edges until every chord is within
+/// `tolerance` of the surface
+///
+/// Boundary segments are never split, so the loops' own points bound the
+/// result; interior splits are shared between neighbouring triangles,
+/// so no T-junctions appear. Returns `None` when a boundary segment strays
+/// from the surface (a faceted face tagged with a curved surface), since no
+/// refinement could then bring the triangles within tolerance.
+pub(crate) fn triangulate_parametric(
+    eval: impl Fn(DVec2) -> DVec3,
+    loops: &[Vec<DVec2>],
+    points: &[Vec<DVec3>],
+    tolerance: f64,
+) -> Option<(Vec<DVec3>, Vec<[usize; 3]>)> {
+    let tol = tolerance.max(1e-6);
+    for (l, p) in loops.iter().zip(points) {
+        for i in 0..l.len() {
+            let j = (i + 1) % l.len();
+            let chord = (p[i] + p[j]) * 0.5;
+            if (eval((l[i] + l[j]) * 0.5) - chord).length() > 4.0 * tol {
+                return None;
+            }
+        }
+    }
+
+    let mut uv: Vec<DVec2> = loops.iter().flatten().copied().collect();
+    let mut xyz: Vec<DVec3> = points.iter().flatten().copied().collect();
+
+    let as_2d = |l: &Vec<DVec2>| l.iter().map(|p| [p.x, p.y]).collect::<Vec<_>>();
+    let holes: Vec<Vec<[f64; 2]>> = loops[1..].iter().map(as_2d).collect();
+    let mut triangles = triangulate_polygon(&as_2d(&loops[0]), &holes);
+
+    let mut boundary: HashSet<(usize, usize)> = HashSet::new();
+    let mut offset = 0;
+    for l in loops {
+        for i in 0..l.len() {
+            boundary.insert(edge_key(offset + i, offset + (i + 1) % l.len()));
+        }
+        offset += l.len();
+    }
+
+    // Flips are judged in parameter space stretched by the surface's
+    // first derivatives, which is close to isotropic on the surface
+    let centre = loops[0].iter().sum::<DVec2>() / loops[0].len() as f64;
+    let extent = loops[0]
+        .iter()
+        .fold(DVec2::ZERO, |e, p| e.max((*p - centre).abs()));
+    let stretch = |axis: DVec2| {
+        let h = (axis * extent).length().max(1e-9) * 1e-3;
+        let d = (eval(centre + axis * h) - eval(centre - axis * h)).length() / (2.0 * h);
+        if d.is_finite() && d > 1e-12 {
+            d
+        } else {
+            1.0
+        }
+    };
+    let metric = DVec2::new(stretch(DVec2::X), stretch(DVec2::Y));
+
+    flip_to_delaunay(&uv, metric, &mut triangles, &boundary);
+    for _ in 0..MAX_REFINE_ROUNDS {
+        if triangles.len() >= MAX_TRIANGLES {
+            break;
+        }
+
+        let chord_error = |a: usize, b: usize, uv: &[DVec2], xyz: &[DVec3]| {
+            let mid = eval((uv[a] + uv[b]) * 0.5);
+            (mid - (xyz[a] + xyz[b]) * 0.5).length()
+        };
+
+        let mut split: HashSet<(usize, usize)> = HashSet::new();
+        let mut centre_split = HashSet::new();
+        for (t, tri) in triangles.iter().enumerate() {
+            let mut marked = false;
+            for k in 0..3 {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                let key = edge_key(a, b);
+                if !boundary.contains(&key) && chord_error(a, b, &uv, &xyz) > tol {
+                    split.insert(key);
+                    marked = true;
+                }
+            }
+            if marked {
+                continue;
+            }
+
+            // Bulge inside the triangle that its edges do not see
+            let c_uv = (uv[tri[0]] + uv[tri[1]] + uv[tri[2]]) / 3.0;
+            let c_xyz = (xyz[tri[0]] + xyz[tri[1]] + xyz[tri[2]]) / 3.0;
+            if (eval(c_uv) - c_xyz).length() > tol {
+                let longest = (0..3)
+                    .map(|k| (tri[k], tri[(k + 1) % 3]))
+                    .filter(|&(a, b)| !boundary.contains(&edge_key(a, b)))
+                    .max_by(|x, y| {
+                        let len = |(a, b): (usize, usize)| (xyz[a] - xyz[b]).length();
+                        len(*x).total_cmp(&len(*y))
+                    });
+                match longest {
+                    Some((a, b)) => {
+                        split.insert(edge_key(a, b));
+                    }
+                    None => {
+                        centre_split.insert(t);
+                    }
+                }
+            }
+        }
+        if split.is_empty() && centre_split.is_empty() {
+            break;
+        }
+
+        let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+        for &(a, b) in &split {
+            let m = (uv[a] + uv[b]) * 0.5;
+            uv.push(m);
+            xyz.push(eval(m));
+            midpoints.insert((a, b), uv.len() - 1);
+        }
+
+        let mut refined = Vec::with_capacity(triangles.len() * 2);
+        for (t, tri) in triangles.iter().enumerate() {
+            let mid = |k: usize| midpoints.get(&edge_key(tri[k], tri[(k + 1) % 3])).copied();
+            let mids = [mid(0), mid(1), mid(2)];
+            if mids.iter().all(Option::is_none) && centre_split.contains(&t) {
+                let c = (uv[tri[0]] + uv[tri[1]] + uv[tri[2]]) / 3.0;
+                uv.push(c);
+                xyz.push(eval(c));
+                let ci = uv.len() - 1;
+                for k in 0..3 {
+                    refined.push([tri[k], tri[(k + 1) % 3], ci]);
+                }
+                continue;
+            }
+            split_triangle(*tri, mids, &mut refined);
+        }
+        triangles = refined;
+        flip_to_delaunay(&uv, metric, &mut triangles, &boundary);
+    }
+
+    (!triangles.is_empty()).then_some((xyz, triangles))
+}
+
+/// Sample `f` over the given increasing parameters, bisecting each interval
+/// until the curve stays within `tolerance` of its chord
+///
+/// Returns `(parameter, point)` pairs including both ends.
+pub(crate) fn adaptive_samples(
+    f: impl Fn(f64) -> DVec3,
+    params: &[f64],
+    tolerance: f64,
+) -> Vec<(f64, DVec3)> {
+    let tol = tolerance.max(1e-6);
+    let mut out = Vec::new();
+    let Some(&first) = params.first() else {
+        return out;
+    };
+    out.push((first, f(first)));
+    for pair in params.windows(2) {
+        let start = *out.last().expect("first sample pushed");
+        let end = (pair[1], f(pair[1]));
+        bisect(&f, start, end, tol, 0, &mut out);
+    }
+    out
+}
+
+fn bisect(
+    f: &impl Fn(f64) -> DVec3,
+    a: (f64, DVec3),
+    b: (f64, DVec3),
+    tol: f64,
+    depth: u32,
+    out: &mut Vec<(f64, DVec3)>,
+) {
+    let m = (a.0 + b.0) * 0.5;
+    let pm = f(m);
+    let chord = b.1 - a.1;
+    let offset = pm - a.1;
+    let deviation = if chord.length_squared() > 1e-24 {
+        (offset - chord * (offset.dot(chord) / chord.length_squared())).length()
+    } else {
+        offset.length()
+    };
+    if depth < MAX_DEPTH && deviation > tol {
+        bisect(f, a, (m, pm), tol, depth + 1, out);
+        bisect(f, (m, pm), b, tol, depth + 1, out);
+    } else {
+        out.push(b);
+    }
+}
+
+/// Lawson edge flips towards a Delaunay triangulation of the parameters
+/// scaled by `metric`, so that long ear-clipping diagonals do not drive
+/// refinement
+///
+/// Boundary edges stay fixed, and a flip is only made where the quad is
+/// convex, so the triangulation never folds over.
+fn flip_to_delaunay(
+    uv: &[DVec2],
+    metric: DVec2,
+    triangles: &mut [[usize; 3]],
+    boundary: &HashSet<(usize, usize)>,
+) {
+    let angle_at = |apex: usize, a: usize, b: usize| {
+        let p = uv[apex] * metric;
+        (uv[a] * metric - p).angle_to(uv[b] * metric - p).abs()
+    };
+    let turn = |a: usize, b: usize, c: usize| (uv[b] - uv[a]).perp_dot(uv[c] - uv[a]);
+
+    for _ in 0..64 {
+        let mut directed: HashMap<(usize, usize), usize> = HashMap::new();
+        for (t, tri) in triangles.iter().enumerate() {
+            for k in 0..3 {
+                directed.insert((tri[k], tri[(k + 1) % 3]), t);
+            }
+        }
+
+        let mut touched = vec![false; triangles.len()];
+        let mut flipped = false;
+        for t1 in 0..triangles.len() {
+            for k in 0..3 {
+                if touched[t1] {
+                    break;
+                }
+                let [a, b, c] = [0, 1, 2].map(|i| triangles[t1][(k + i) % 3]);
+                if boundary.contains(&edge_key(a, b)) {
+                    continue;
+                }
+                let Some(&t2) = directed.get(&(b, a)) else {
+                    continue;
+                };
+                if touched[t2] || t2 == t1 {
+                    continue;
+                }
+                let Some(&d) = triangles[t2].iter().find(|&&v| v != a && v != b) else {
+                    continue;
+                };
+                if angle_at(c, a, b) + angle_at(d, b, a) <= std::f64::consts::PI + 1e-9 {
+                    continue;
+                }
+                let sense = turn(a, b, c).signum();
+                if turn(a, d, c) * sense <= 0.0 || turn(d, b, c) * sense <= 0.0 {
+                    continue;
+                }
+                triangles[t1] = [a, d, c];
+                triangles[t2] = [d, b, c];
+                touched[t1] = true;
+                touched[t2] = true;
+                flipped = true;
+            }
+        }
+        if !flipped {
+            break;
+        }
+    }
+}
+
+/// Split a triangle on its marked edges (`mids[k]` is the midpoint of edge
+/// `k → k + 1`), keeping the winding
+fn split_triangle(tri: [usize; 3], mids: [Option<usize>; 3], out: &mut Vec<[usize; 3]>) {
+    let marked = mids.iter().filter(|m| m.is_some()).count();
+    match marked {
+        0 => out.push(tri),
+        3 => {
+            let (m0, m1, m2) = (mids[0].unwrap(), mids[1].unwrap(), mids[2].unwrap());
+            out.push([tri[0], m0, m2]);
+            out.push([m0, tri[1], m1]);
+            out.push([m2, m1, tri[2]]);
+            out.push([m0, m1, m2]);
+        }
+        _ => {
+            // Rotate so edge 0 (a → b) is split, and for two splits also b → c
+            let k = (0..3)
+                .find(|&k| mids[k].is_some() && (marked == 1 || mids[(k + 1) % 3].is_some()))
+                .expect("a marked edge");
+            let (a, b, c) = (tri[k], tri[(k + 1) % 3], tri[(k + 2) % 3]);
+            let m0 = mids[k].expect("marked edge");
+            match mids[(k + 1) % 3] {
+                Some(m1) if marked == 2 => {
+                    out.push([m0, b, m1]);
+                    out.push([a, m0, m1]);
+                    out.push([a, m1, c]);
+                }
+                _ => {
+                    out.push([a, m0, c]);
+                    out.push([m0, b, c]);
+                }
+            }
+        }
+    }
+}
+
+fn append_triangles(mesh: &mut TriangleMesh, vertices: &[DVec3], triangles: &[[usize; 3]]) {
+    let base = mesh.vertices.len();
+    mesh.vertices.extend(vertices.iter().map(|&p| to_point(p)));
+    for t in triangles {
+        let n = (vertices[t[1]] - vertices[t[0]])
+            .cross(vertices[t[2]] - vertices[t[0]])
+            .normalize_or_zero();
+        mesh.triangles.push([base + t[0], base + t[1], base + t[2]]);
+        mesh.normals.push(to_vector(n));
+    }
+}
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+fn check_knots(degree: usize, count: usize, knots: &[f32]) -> Result<(), NurbsError> {
+    if degree == 0 || count <= degree {
+        return Err(NurbsError::InvalidDegree);
+    }
+    if knots.len() != count + degree + 1
+        || knots.iter().any(|k| !k.is_finite())
+        || knots.windows(2).any(|w| w[1] < w[0])
+        || knots[degree] >= knots[count]
+    {
+        return Err(NurbsError::InvalidKnots);
+    }
+    Ok(())
+}
+
+fn check_weights(weights: &[f32], count: usize) -> Result<(), NurbsError> {
+    if weights.len() != count || weights.iter().any(|w| !(w.is_finite() && *w > 0.0)) {
+        return Err(NurbsError::InvalidWeights);
+    }
+    Ok(())
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+fn homogeneous(p: Point3, w: f32) -> DVec4 {
+    let w = w as f64;
+    DVec4::new(p.x as f64 * w, p.y as f64 * w, p.z as f64 * w, w)
+}
+
+fn project(p: DVec4) -> DVec3 {
+    p.truncate() / p.w
+}
+
+fn to_dvec(p: Point3) -> DVec3 {
+    DVec3::new(p.x as f64, p.y as f64, p.z as f64)
+}
+
+fn to_point(p: DVec3) -> Point3 {
+    Point3::new(p.x as f32, p.y as f32, p.z as f32)
+}
+
+fn to_vector(v: DVec3) -> Vector3 {
+    Vector3::new(v.x as f32, v.y as f32, v.z as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cad::mesh::{solid_to_mesh, solid_to_mesh_with_tolerance};
+    use crate::cad::topology::{EdgeId, Loop, Solid};
+    use std::f32::consts::FRAC_1_SQRT_2;
+
+    /// Rational quadratic quarter circle of radius 1 in the XY plane
+    fn quarter_circle() -> NurbsCurve {
+        NurbsCurve::new(
+            2,
+            &[
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(1.0, 1.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+            ],
+            &[1.0, FRAC_1_SQRT_2, 1.0],
+            &[0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+        )
+        .unwrap()
+    }
+
+    /// Cubic curve over two spans with a weighted interior point
+    fn wavy_curve() -> NurbsCurve {
+        NurbsCurve::new(
+            3,
+            &[
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 2.0, 0.0),
+                Point3::new(2.0, -1.0, 1.0),
+                Point3::new(3.0, 2.0, 0.5),
+                Point3::new(4.0, 0.0, 0.0),
+            ],
+            &[1.0, 0.8, 1.5, 1.0, 1.0],
+            &[0.0, 0.0, 0.0, 0.0, 0.4, 1.0, 1.0, 1.0, 1.0],
+        )
+        .unwrap()
+    }
+
+    /// Quarter of a cylinder (radius 1, height 2): u around, v along Z
+    fn quarter_cylinder() -> NurbsSurface {
+        let w = FRAC_1_SQRT_2;
+        let ring = [(1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        NurbsSurface::new(
+            2,
+            1,
+            &ring
+                .iter()
+                .map(|&(x, y)| vec![Point3::new(x, y, 0.0), Point3::new(x, y, 2.0)])
+                .collect::<Vec<_>>(),
+            &[vec![1.0, 1.0], vec![w, w], vec![1.0, 1.0]],
+            &[0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            &[0.0, 0.0, 1.0, 1.0],
+        )
+        .unwrap()
+    }
+
+    fn max_radius_error(points: &[Point3]) -> f32 {
+        points
+            .iter()
+            .map(|p| ((p.x * p.x + p.y * p.y).sqrt() - 1.0).abs())
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_rational_circle_evaluation() {
+        let curve = quarter_circle();
+        assert!(curve.point_at(0.0).distance(Point3::new(1.0, 0.0, 0.0)) < 1e-6);
+        // The end of the domain evaluates the last control point
+        assert!(curve.point_at(1.0).distance(Point3::new(0.0, 1.0, 0.0)) < 1e-6);
+
+        let samples: Vec<Point3> = (0..=20).map(|k| curve.point_at(k as f32 / 20.0)).collect();
+        assert!(max_radius_error(&samples) < 1e-5);
+        let mid = curve.point_at(0.5);
+        assert!((mid.x - mid.y).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_edge_curve_spans_knot_domain() {
+        // Unnormalised knots, as imported from STEP
+        let curve = NurbsCurve::new(
+            2,
+            &[
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(1.0, 1.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+            ],
+            &[1.0, FRAC_1_SQRT_2, 1.0],
+            &[2.0, 2.0, 2.0, 5.0, 5.0, 5.0],
+        )
+        .unwrap()
+        .to_curve();
+
+        let (start, end) = (Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0));
+        assert!(curve.point_at(start, end, 0.0).distance(start) < 1e-6);
+        assert!(curve.point_at(start, end, 1.0).distance(end) < 1e-6);
+        let mid = curve.point_at(start, end, 0.5);
+        assert!(((mid.x * mid.x + mid.y * mid.y).sqrt() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_invalid_data_rejected() {
+        let points = [Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0)];
+        assert!(matches!(
+            NurbsCurve::new(2, &points, &[1.0, 1.0], &[0.0, 0.0, 0.0, 1.0, 1.0]),
+            Err(NurbsError::InvalidDegree)
+        ));
+        assert!(matches!(
+            NurbsCurve::new(1, &points, &[1.0, 1.0], &[0.0, 0.0, 1.0]),
+            Err(NurbsError::InvalidKnots)
+        ));
+        assert!(matches!(
+            NurbsCurve::new(1, &points, &[1.0, 0.0], &[0.0, 0.0, 1.0, 1.0]),
+            Err(NurbsError::InvalidWeights)
+        ));
+        assert!(matches!(
+            NurbsCurve::from_curve(&CurveType::Linear),
+            Err(NurbsError::NotNurbs)
+        ));
+    }
+
+    #[test]
+    fn test_curve_derivatives_match_finite_differences() {
+        let curve = wavy_curve();
+        let h = 1e-3;
+        for &u in &[0.1, 0.35, 0.4, 0.7, 0.95] {
+            let d = curve.derivatives(u, 2);
+            let fd1 =
+                (curve.point_at(u + h).to_vec3() - curve.point_at(u - h).to_vec3()) / (2.0 * h);
+            let fd2 = (curve.point_at(u + h).to_vec3() - curve.point_at(u).to_vec3() * 2.0
+                + curve.point_at(u - h).to_vec3())
+                / (h * h);
+            assert!(d[0].to_vec3().distance(curve.point_at(u).to_vec3()) < 1e-5);
+            assert!(d[1].to_vec3().distance(fd1) < 1e-2 * (1.0 + fd1.length()));
+            if (u - 0.4).abs() > 1e-6 {
+                assert!(d[2].to_vec3().distance(fd2) < 0.2 * (1.0 + fd2.length()));
+            }
+        }
+
+        // The circle's tangent is perpendicular to its radius
+        let circle = quarter_circle();
+        let p = circle.point_at(0.3).to_vec3();
+        assert!(circle.tangent_at(0.3).to_vec3().dot(p).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_knot_insertion_preserves_shape() {
+        let curve = wavy_curve();
+        let refined = curve.insert_knot(0.7, 2);
+        assert_eq!(refined.control_points().len(), 7);
+        assert_eq!(refined.knots().len(), 11);
+
+        // Multiplicity is capped at the degree
+        let full = curve.insert_knot(0.7, 10);
+        assert_eq!(full.control_points().len(), 8);
+
+        for k in 0..=40 {
+            let u = k as f32 / 40.0;
+            assert!(curve.point_at(u).distance(refined.point_at(u)) < 1e-5);
+            assert!(curve.point_at(u).distance(full.point_at(u)) < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_degree_elevation_preserves_shape() {
+        let circle = quarter_circle().elevate_degree(1);
+        assert_eq!(circle.degree(), 3);
+        assert_eq!(circle.control_points().len(), 4);
+        let samples: Vec<Point3> = (0..=20).map(|k| circle.point_at(k as f32 / 20.0)).collect();
+        assert!(max_radius_error(&samples) < 1e-5);
+
+        let curve = wavy_curve();
+        let raised = curve.elevate_degree(2);
+        assert_eq!(raised.degree(), 5);
+        for k in 0..=40 {
+            let u = k as f32 / 40.0;
+            assert!(curve.point_at(u).distance(raised.point_at(u)) < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_curve_tessellation_within_tolerance() {
+        let curve = quarter_circle();
+        for &tol in &[0.01, 0.001] {
+            let points = curve.tessellate(tol);
+            assert!(points[0].distance(Point3::new(1.0, 0.0, 0.0)) < 1e-6);
+            assert!(points[points.len() - 1].distance(Point3::new(0.0, 1.0, 0.0)) < 1e-6);
+
+            // Sagitta of each chord stays inside the tolerance
+            for pair in points.windows(2) {
+                let mid = pair[0].to_vec3().lerp(pair[1].to_vec3(), 0.5);
+                assert!(1.0 - mid.length() <= tol * 1.01);
+            }
+        }
+        assert!(curve.tessellate(0.001).len() > curve.tessellate(0.01).len());
+    }
+
+    #[test]
+    fn test_surface_evaluation_and_normals() {
+        let surface = quarter_cylinder();
+        for &(u, v) in &[(0.0, 0.0), (0.3, 0.5), (1.0, 1.0)] {
+            let p = surface.point_at(u, v);
+            assert!(((p.x * p.x + p.y * p.y).sqrt() - 1.0).abs() < 1e-5);
+            assert!((p.z - 2.0 * v).abs() < 1e-5);
+
+            // Normal is radial (sign follows u × v)
+            let n = surface.normal_at(u, v);
+            let radial = Vector3::new(p.x, p.y, 0.0).normalize_or_z();
+            assert!(n.dot(radial).abs() > 0.9999);
+        }
+
+        let d = surface.derivatives(0.5, 0.5, 1);
+        assert!((d[0][1].z - 2.0).abs() < 1e-5);
+        assert!(d[1][0].z.abs() < 1e-6);
+
+        let (u, v) = surface.closest_parameters(Point3::new(0.6, 0.8, 1.5));
+        let back = surface.point_at(u, v);
+        assert!(back.distance(Point3::new(0.6, 0.8, 1.5)) < 1e-4);
+    }
+
+    #[test]
+    fn test_surface_knot_operations() {
+        let surface = quarter_cylinder();
+        let refined = surface.insert_knot_u(0.5, 1).insert_knot_v(0.25, 1);
+        let raised = surface.elevate_degree_v(2).elevate_degree_u(1);
+        assert_eq!(raised.degrees(), (3, 3));
+        for &(u, v) in &[(0.1, 0.2), (0.5, 0.25), (0.9, 0.8)] {
+            let p = surface.point_at(u, v);
+            assert!(p.distance(refined.point_at(u, v)) < 1e-5);
+            assert!(p.distance(raised.point_at(u, v)) < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_surface_tessellation_within_tolerance() {
+        let surface = quarter_cylinder();
+        let coarse = surface.tessellate(0.01);
+        let fine = surface.tessellate(0.001);
+        assert!(fine.triangle_count() > coarse.triangle_count());
+
+        for mesh in [&coarse, &fine] {
+            assert_eq!(mesh.triangles.len(), mesh.normals.len());
+            for t in &mesh.triangles {
+                let c = (mesh.vertices[t[0]].to_vec3()
+                    + mesh.vertices[t[1]].to_vec3()
+                    + mesh.vertices[t[2]].to_vec3())
+                    / 3.0;
+                assert!(1.0 - c.truncate().length() < 0.011);
+            }
+        }
+    }
+
+    /// Quarter cylinder closed by planar faces, with NURBS arcs on the
+    /// curved face's top and bottom edges
+    fn quarter_cylinder_solid() -> Solid {
+        let mut solid = Solid::new();
+        let o0 = solid.add_vertex(Point3::new(0.0, 0.0, 0.0));
+        let a0 = solid.add_vertex(Point3::new(1.0, 0.0, 0.0));
+        let b0 = solid.add_vertex(Point3::new(0.0, 1.0, 0.0));
+        let o1 = solid.add_vertex(Point3::new(0.0, 0.0, 2.0));
+        let a1 = solid.add_vertex(Point3::new(1.0, 0.0, 2.0));
+        let b1 = solid.add_vertex(Point3::new(0.0, 1.0, 2.0));
+
+        let arc = |z: f32| {
+            NurbsCurve::new(
+                2,
+                &[
+                    Point3::new(1.0, 0.0, z),
+                    Point3::new(1.0, 1.0, z),
+                    Point3::new(0.0, 1.0, z),
+                ],
+                &[1.0, FRAC_1_SQRT_2, 1.0],
+                &[0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            )
+            .unwrap()
+            .to_curve()
+        };
+        let arc0 = solid.add_edge(a0, b0);
+        solid.edges[arc0.0 as usize].curve = arc(0.0);
+        let arc1 = solid.add_edge(a1, b1);
+        solid.edges[arc1.0 as usize].curve = arc(2.0);
+        let oa0 = solid.add_edge(o0, a0);
+        let bo0 = solid.add_edge(b0, o0);
+        let oa1 = solid.add_edge(o1, a1);
+        let bo1 = solid.add_edge(b1, o1);
+        let va = solid.add_edge(a0, a1);
+        let vb = solid.add_edge(b0, b1);
+        let vo = solid.add_edge(o0, o1);
+
+        let shell = solid.add_shell();
+        let mut add_face = |surface: SurfaceType, edges: &[(EdgeId, bool)]| {
+            let f = solid.add_face(surface);
+            let mut loop_ = Loop::new();
+            for &(e, d) in edges {
+                loop_.add_edge(e, d);
+            }
+            let face = solid.face_mut(f).unwrap();
+            face.outer_loop = loop_;
+            face.shell = Some(shell);
+            solid.shells[shell.0 as usize].add_face(f);
+        };
+        add_face(
+            quarter_cylinder().to_surface(),
+            &[(arc0, true), (vb, true), (arc1, false), (va, false)],
+        );
+        add_face(
+            SurfaceType::Planar {
+                normal: Vector3::NEG_Z,
+            },
+            &[(oa0, false), (bo0, false), (arc0, false)],
+        );
+        add_face(
+            SurfaceType::Planar { normal: Vector3::Z },
+            &[(oa1, true), (arc1, true), (bo1, true)],
+        );
+        add_face(
+            SurfaceType::Planar {
+                normal: Vector3::NEG_Y,
+            },
+            &[(oa0, true), (va, true), (oa1, false), (vo, false)],
+        );
+        add_face(
+            SurfaceType::Planar {
+                normal: Vector3::NEG_X,
+            },
+            &[(bo0, true), (vo, true), (bo1, false), (vb, false)],
+        );
+        solid
+    }
+
+    #[test]
+    fn test_nurbs_face_meshes_watertight() {
+        let solid = quarter_cylinder_solid();
+        assert!(solid.is_valid());
+        let mesh = solid_to_mesh(&solid);
+
+        // Quarter of a radius-1, height-2 cylinder
+        let expected = std::f32::consts::PI * 2.0 / 4.0;
+        assert!((mesh.volume() - expected).abs() < 0.03);
+        let fine = solid_to_mesh_with_tolerance(&solid, 0.001);
+        assert!((fine.volume() - expected).abs() < 0.003);
+
+        // Every mesh edge is used once in each direction
+        let mut directed: HashMap<([i64; 3], [i64; 3]), i32> = HashMap::new();
+        let key = |p: Point3| [p.x, p.y, p.z].map(|c| (c * 1e4).round() as i64);
+        for t in &mesh.triangles {
+            for k in 0..3 {
+                let (a, b) = (key(mesh.vertices[t[k]]), key(mesh.vertices[t[(k + 1) % 3]]));
+                *directed.entry((a, b)).or_default() += 1;
+                *directed.entry((b, a)).or_default() -= 1;
+            }
+        }
+        assert!(directed.values().all(|&c| c == 0));
+    }
+}
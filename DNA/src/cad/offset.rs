@@ -0,0 +1,988 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: offset.rs | DNA/src/cad/offset.rs
+//! PURPOSE: Shell, face offset and draft operations on B-Rep solids
+//! MODIFIED: 2026-01-06
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ REPLANING (every operation moves face planes, then re-solves vertices)      │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │                                                                             │
+//! │   offset_face   one face plane moves along its outward normal               │
+//! │   draft_faces   face planes tilt about their line on the neutral plane      │
+//! │   shell_solid   every kept face gets an inner copy t below it; removed      │
+//! │                 faces become rims joining the outer and inner boundaries    │
+//! │                                                                             │
+//! │        ┌──────────────┐  removed (top)       each vertex is moved to the    │
+//! │        │ ┌──────────┐ │                      least-squares meet of its      │
+//! │        │ │  cavity  │ │  ← inner faces       faces' new planes              │
+//! │        │ └──────────┘ │                                                     │
+//! │        └──────────────┘  ← kept faces                                       │
+//! │                                                                             │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Faces are treated through their outward plane, so faceted curved faces
+//! (cylinder sides, sphere patches) are offset facet by facet
+//! and their analytic surface is offset alongside. Shelling also takes
+//! arc-bounded curved faces through their tangent plane at each vertex;
+//! offset and draft need straight edges at the vertices they move. Existing vertex, edge and face IDs stay valid; new
+//! entities are appended.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::geometry::{Plane, Point3, Vector3};
+use super::intersect::{Classification, SolidClassifier};
+use super::topology::{
+    CurveType, EdgeId, FaceId, FaceOrientation, Loop, Solid, SurfaceType, VertexId,
+};
+use glam::{DMat3, DVec3};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// Distance a face vertex may sit off its plane, and a re-solved vertex off
+/// its new planes
+const PLANE_TOLERANCE: f64 = 1e-3;
+
+/// Error type for shell, offset and draft operations
+#[derive(Debug, Clone)]
+pub enum OffsetError {
+    FaceNotFound(FaceId),
+    /// Wall thickness is not a positive finite number
+    InvalidThickness,
+    /// Offset distance is not finite
+    InvalidDistance,
+    /// Draft angle is not finite or not below 90°
+    InvalidAngle,
+    /// Two faces removed by a shell share this edge
+    AdjacentRemovedFaces(EdgeId),
+    /// Shelling would remove every face
+    AllFacesRemoved,
+    /// Only single-shell solids can be shelled
+    MultipleShells,
+    /// Only straight edges can move
+    CurvedEdge(EdgeId),
+    /// Face is not flat, or its surface cannot be offset
+    UnsupportedSurface(FaceId),
+    /// New face planes do not meet in a single point at this vertex
+    UnsupportedVertex(VertexId),
+    /// Face is parallel to the neutral plane
+    NotDraftable(FaceId),
+    /// Offset collapses or turns over a face
+    TooLarge(FaceId),
+    /// Rebuilt solid failed `Solid::is_valid`
+    InvalidResult,
+}
+
+/// Outward plane of a face: points p with `normal · p = d`
+#[derive(Clone, Copy, Debug)]
+struct FacePlane {
+    normal: DVec3,
+    d: f64,
+}
+
+impl FacePlane {
+    fn offset(self, distance: f64) -> Self {
+        Self {
+            normal: self.normal,
+            d: self.d + distance,
+        }
+    }
+}
+
+/// Hollow out a solid, leaving walls of constant thickness
+///
+/// Every face not in `remove_faces` gets an inner copy `thickness` below it.
+/// Each removed face becomes a rim joining the outer boundary to the inner
+/// one and keeps its ID; with nothing removed, the inner faces form a closed
+/// void shell. Removed faces must not share an edge.
+///
+/// Curved faces bounded by arcs, such as fillet blends, are offset through
+/// their tangent plane at each vertex and their arcs stay concentric with
+/// the originals. NURBS edges return `OffsetError::CurvedEdge`, and a curved
+/// face cannot be removed (`OffsetError::UnsupportedSurface`).
+pub fn shell_solid(
+    solid: &Solid,
+    remove_faces: &[FaceId],
+    thickness: f32,
+) -> Result<Solid, OffsetError> {
+    if !(thickness.is_finite() && thickness > 0.0) {
+        return Err(OffsetError::InvalidThickness);
+    }
+    let t = thickness as f64;
+
+    let mut removed: Vec<FaceId> = Vec::with_capacity(remove_faces.len());
+    for &id in remove_faces {
+        solid.face(id).ok_or(OffsetError::FaceNotFound(id))?;
+        if !removed.contains(&id) {
+            removed.push(id);
+        }
+    }
+    if removed.len() == solid.faces.len() {
+        return Err(OffsetError::AllFacesRemoved);
+    }
+    if solid.shells.len() > 1 {
+        return Err(OffsetError::MultipleShells);
+    }
+    for edge in &solid.edges {
+        if matches!(edge.curve, CurveType::Nurbs { .. }) {
+            return Err(OffsetError::CurvedEdge(edge.id));
+        }
+        let faces = solid.faces_of_edge(edge.id);
+        if faces.iter().filter(|f| removed.contains(f)).count() > 1 {
+            return Err(OffsetError::AdjacentRemovedFaces(edge.id));
+        }
+    }
+
+    // Inner planes: kept faces sink by the wall thickness, removed ones stay.
+    // Curved faces bounded by arcs (not flat facets) have no single plane
+    // and are sunk through their tangent plane at each vertex instead
+    let mut inner_planes = HashMap::new();
+    let mut curved: HashSet<FaceId> = HashSet::new();
+    for face in &solid.faces {
+        let arc_bounded = face.all_edges().iter().any(|&e| {
+            solid
+                .edge(e)
+                .is_some_and(|e| !matches!(e.curve, CurveType::Linear))
+        });
+        if arc_bounded && !matches!(face.surface, SurfaceType::Planar { .. }) {
+            if removed.contains(&face.id) {
+                return Err(OffsetError::UnsupportedSurface(face.id));
+            }
+            curved.insert(face.id);
+            continue;
+        }
+        let plane = face_plane(solid, face.id)?;
+        let plane = if removed.contains(&face.id) {
+            plane
+        } else {
+            plane.offset(-t)
+        };
+        inner_planes.insert(face.id, plane);
+    }
+
+    let incident = vertex_faces(solid);
+    let classifier = SolidClassifier::new(solid);
+    let mut result = solid.clone();
+    let mut vertex_map = HashMap::new();
+    for vertex in &solid.vertices {
+        let faces = incident.get(&vertex.id).map(Vec::as_slice).unwrap_or(&[]);
+        let on_rim = faces.iter().any(|f| removed.contains(f));
+        let original = vertex_point(solid, vertex.id);
+        let mut planes: Vec<FacePlane> = faces
+            .iter()
+            .filter_map(|f| inner_planes.get(f).copied())
+            .collect();
+        for &f in faces.iter().filter(|f| curved.contains(f)) {
+            planes.push(tangent_plane(solid, f, original)?.offset(-t));
+        }
+        let point =
+            solve_vertex(original, &planes).ok_or(OffsetError::UnsupportedVertex(vertex.id))?;
+        // Inner corners must stay within the material of the original solid
+        match classifier.classify(point) {
+            Classification::Inside => {}
+            Classification::OnBoundary if on_rim => {}
+            _ => {
+                let face = faces
+                    .iter()
+                    .find(|f| !removed.contains(f))
+                    .or(faces.first());
+                return Err(OffsetError::TooLarge(face.copied().unwrap_or(FaceId(0))));
+            }
+        }
+        vertex_map.insert(vertex.id, result.add_vertex(to_point(point)));
+    }
+
+    let mut edge_map = HashMap::new();
+    for edge in &solid.edges {
+        let inner = result.add_edge(vertex_map[&edge.start], vertex_map[&edge.end]);
+        if matches!(edge.curve, CurveType::Arc { .. }) {
+            let ends =
+                |s: &Solid, a: VertexId, b: VertexId| [vertex_point(s, a), vertex_point(s, b)];
+            let arc = offset_arc(
+                &edge.curve,
+                ends(solid, edge.start, edge.end),
+                ends(&result, vertex_map[&edge.start], vertex_map[&edge.end]),
+            )
+            .ok_or(OffsetError::CurvedEdge(edge.id))?;
+            if let Some(e) = result.edges.iter_mut().find(|e| e.id == inner) {
+                e.curve = arc;
+            }
+        }
+        edge_map.insert(edge.id, inner);
+    }
+    let map_loop = |loop_: &Loop| -> Loop {
+        let mut mapped = Loop::new();
+        for (edge, forward) in loop_.edges.iter().zip(&loop_.directions) {
+            mapped.add_edge(edge_map[edge], *forward);
+        }
+        mapped
+    };
+
+    let shell = match result.shells.first().map(|s| s.id) {
+        Some(id) => id,
+        None => {
+            let id = result.add_shell();
+            let faces: Vec<FaceId> = result.faces.iter().map(|f| f.id).collect();
+            for face in result.faces.iter_mut() {
+                face.shell = Some(id);
+            }
+            result.shells[0].faces = faces;
+            id
+        }
+    };
+    let inner_shell = if removed.is_empty() {
+        Some(result.add_shell())
+    } else {
+        None
+    };
+    let target_shell = inner_shell.unwrap_or(shell);
+
+    let mut new_faces = Vec::new();
+    for face in &solid.faces {
+        if removed.contains(&face.id) {
+            // Holes in a removed face open into rims of their own
+            for hole in &face.inner_loops {
+                let id = result.add_face(face.surface.clone());
+                let rim = result.face_mut(id).expect("face just added");
+                rim.outer_loop = reversed(&map_loop(hole));
+                rim.inner_loops = vec![hole.clone()];
+                rim.orientation = face.orientation;
+                rim.shell = Some(target_shell);
+                new_faces.push(id);
+            }
+            let rim = result.face_mut(face.id).expect("face copied from solid");
+            rim.inner_loops = vec![reversed(&map_loop(&face.outer_loop))];
+            continue;
+        }
+
+        // Curved faces collapse through their surface offset instead
+        if !curved.contains(&face.id) {
+            check_not_flipped(solid, face.id, &face.outer_loop, |v| {
+                vertex_point(&result, vertex_map[&v])
+            })?;
+        }
+
+        let surface = match &face.surface {
+            SurfaceType::Planar { normal } => SurfaceType::Planar { normal: -*normal },
+            other => offset_surface(other, face.orientation, face_centroid(solid, face.id), -t)
+                .ok_or(OffsetError::UnsupportedSurface(face.id))?,
+        };
+        let id = result.add_face(surface);
+        let inner = result.face_mut(id).expect("face just added");
+        inner.outer_loop = reversed(&map_loop(&face.outer_loop));
+        inner.inner_loops = face
+            .inner_loops
+            .iter()
+            .map(|l| reversed(&map_loop(l)))
+            .collect();
+        inner.orientation = match face.orientation {
+            FaceOrientation::Outward => FaceOrientation::Inward,
+            FaceOrientation::Inward => FaceOrientation::Outward,
+        };
+        inner.shell = Some(target_shell);
+        new_faces.push(id);
+    }
+
+    if let Some(s) = result.shells.iter_mut().find(|s| s.id == target_shell) {
+        s.faces.extend(new_faces);
+        s.is_closed = true;
+    }
+
+    finish(result)
+}
+
+/// Move one face along its outward normal
+///
+/// Positive distances add material, negative ones remove it. Neighbouring
+/// faces keep their planes and are stretched or trimmed to meet the face.
+/// Edges at the moved vertices must be straight; an arc there, such as the
+/// boundary of a fillet, returns `OffsetError::CurvedEdge`.
+pub fn offset_face(solid: &Solid, face: FaceId, distance: f32) -> Result<Solid, OffsetError> {
+    if !distance.is_finite() {
+        return Err(OffsetError::InvalidDistance);
+    }
+    let original = solid.face(face).ok_or(OffsetError::FaceNotFound(face))?;
+    let plane = face_plane(solid, face)?;
+
+    let mut planes = HashMap::new();
+    planes.insert(face, plane.offset(distance as f64));
+    let mut result = replane(solid, &planes)?;
+
+    let surface = match &original.surface {
+        SurfaceType::Planar { .. } => original.surface.clone(),
+        other => offset_surface(
+            other,
+            original.orientation,
+            face_centroid(solid, face),
+            distance as f64,
+        )
+        .ok_or(OffsetError::UnsupportedSurface(face))?,
+    };
+    if let Some(f) = result.face_mut(face) {
+        f.surface = surface;
+    }
+
+    finish(result)
+}
+
+/// Tilt planar faces by a draft angle relative to a neutral plane
+///
+/// Each face pivots about the line where it meets `neutral_plane`, leaning
+/// towards the plane's normal (the pull direction), so drafted walls narrow
+/// along the pull. `angle_degrees` may be negative to draft the other way.
+///
+/// Only planar faces can be drafted, and every edge at a moved vertex must
+/// be straight: a face bordering a fillet or other arc-bounded face returns
+/// `OffsetError::CurvedEdge`, since the arc would have to be re-solved
+/// against the tilted plane.
+pub fn draft_faces(
+    solid: &Solid,
+    faces: &[FaceId],
+    neutral_plane: Plane,
+    angle_degrees: f32,
+) -> Result<Solid, OffsetError> {
+    if !(angle_degrees.is_finite() && angle_degrees.abs() < 90.0) {
+        return Err(OffsetError::InvalidAngle);
+    }
+    let angle = (angle_degrees as f64).to_radians();
+    let pull = to_dvec(neutral_plane.normal).normalize();
+    let neutral_d = pull.dot(point_dvec(neutral_plane.origin));
+
+    let mut planes = HashMap::new();
+    for &id in faces {
+        let face = solid.face(id).ok_or(OffsetError::FaceNotFound(id))?;
+        if !matches!(face.surface, SurfaceType::Planar { .. }) {
+            return Err(OffsetError::UnsupportedSurface(id));
+        }
+        let plane = face_plane(solid, id)?;
+
+        let axis = plane.normal.cross(pull);
+        if axis.length() < 1e-6 {
+            return Err(OffsetError::NotDraftable(id));
+        }
+        // Point on the hinge line shared by the face plane and neutral plane
+        let hinge = (pull.cross(axis) * plane.d + axis.cross(plane.normal) * neutral_d)
+            / axis.length_squared();
+        let along = (pull - plane.normal * plane.normal.dot(pull)).normalize();
+        let normal = plane.normal * angle.cos() + along * angle.sin();
+        planes.insert(
+            id,
+            FacePlane {
+                normal,
+                d: normal.dot(hinge),
+            },
+        );
+    }
+
+    let mut result = replane(solid, &planes)?;
+    for (&id, plane) in &planes {
+        if let Some(f) = result.face_mut(id) {
+            f.surface = SurfaceType::Planar {
+                normal: Vector3::new(
+                    plane.normal.x as f32,
+                    plane.normal.y as f32,
+                    plane.normal.z as f32,
+                ),
+            };
+        }
+    }
+
+    finish(result)
+}
+
+/// Give the listed faces new planes and move every vertex on them to the
+/// meet of its faces' planes
+fn replane(solid: &Solid, planes: &HashMap<FaceId, FacePlane>) -> Result<Solid, OffsetError> {
+    let incident = vertex_faces(solid);
+
+    let mut moved: Vec<VertexId> = Vec::new();
+    for (vertex, faces) in &incident {
+        if faces.iter().any(|f| planes.contains_key(f)) {
+            moved.push(*vertex);
+        }
+    }
+    moved.sort_by_key(|v| v.0);
+
+    for edge in &solid.edges {
+        if !matches!(edge.curve, CurveType::Linear)
+            && (moved.contains(&edge.start) || moved.contains(&edge.end))
+        {
+            return Err(OffsetError::CurvedEdge(edge.id));
+        }
+    }
+
+    let mut all_planes = planes.clone();
+    let mut touched: HashSet<FaceId> = HashSet::new();
+    for v in &moved {
+        for &f in &incident[v] {
+            touched.insert(f);
+            if let Entry::Vacant(slot) = all_planes.entry(f) {
+                slot.insert(face_plane(solid, f)?);
+            }
+        }
+    }
+
+    let mut result = solid.clone();
+    for &v in &moved {
+        let planes: Vec<FacePlane> = incident[&v]
+            .iter()
+            .filter_map(|f| all_planes.get(f).copied())
+            .collect();
+        let point = solve_vertex(vertex_point(solid, v), &planes)
+            .ok_or(OffsetError::UnsupportedVertex(v))?;
+        if let Some(vertex) = result.vertices.iter_mut().find(|x| x.id == v) {
+            vertex.point = to_point(point);
+        }
+    }
+
+    let mut touched: Vec<FaceId> = touched.into_iter().collect();
+    touched.sort_by_key(|f| f.0);
+    for id in touched {
+        let face = solid.face(id).ok_or(OffsetError::FaceNotFound(id))?;
+        check_not_flipped(solid, id, &face.outer_loop, |v| vertex_point(&result, v))?;
+    }
+
+    Ok(result)
+}
+
+/// Refresh edge → face back-references and run the topology checks
+fn finish(mut result: Solid) -> Result<Solid, OffsetError> {
+    let face_lists: Vec<Vec<FaceId>> = result
+        .edges
+        .iter()
+        .map(|e| result.faces_of_edge(e.id))
+        .collect();
+    for (edge, faces) in result.edges.iter_mut().zip(face_lists) {
+        edge.faces = faces;
+    }
+
+    if !result.is_valid() {
+        return Err(OffsetError::InvalidResult);
+    }
+    Ok(result)
+}
+
+/// Faces meeting at each vertex, from the face loops
+fn vertex_faces(solid: &Solid) -> HashMap<VertexId, Vec<FaceId>> {
+    let mut incident: HashMap<VertexId, Vec<FaceId>> = HashMap::new();
+    for face in &solid.faces {
+        for edge_id in face.all_edges() {
+            let Some(edge) = solid.edge(edge_id) else {
+                continue;
+            };
+            for v in [edge.start, edge.end] {
+                let faces = incident.entry(v).or_default();
+                if !faces.contains(&face.id) {
+                    faces.push(face.id);
+                }
+            }
+        }
+    }
+    incident
+}
+
+/// Outward plane of a face, from its stored normal (planar faces) or its
+/// polygon oriented by the surface normal (faceted curved faces)
+fn face_plane(solid: &Solid, id: FaceId) -> Result<FacePlane, OffsetError> {
+    let face = solid.face(id).ok_or(OffsetError::FaceNotFound(id))?;
+    let points = loop_points(solid, &face.outer_loop, |v| vertex_point(solid, v));
+    if points.len() < 3 {
+        return Err(OffsetError::UnsupportedSurface(id));
+    }
+    let centroid = points.iter().copied().sum::<DVec3>() / points.len() as f64;
+
+    let normal = match &face.surface {
+        SurfaceType::Planar { normal } => to_dvec(*normal).normalize_or_zero(),
+        SurfaceType::Nurbs { .. } => return Err(OffsetError::UnsupportedSurface(id)),
+        surface => {
+            let n = newell(&points).normalize_or_zero();
+            let mut outward = to_dvec(surface.normal_at(to_point(centroid)));
+            if face.orientation == FaceOrientation::Inward {
+                outward = -outward;
+            }
+            if n.dot(outward) < 0.0 {
+                -n
+            } else {
+                n
+            }
+        }
+    };
+    if normal == DVec3::ZERO {
+        return Err(OffsetError::UnsupportedSurface(id));
+    }
+
+    let d = normal.dot(centroid);
+    if points
+        .iter()
+        .any(|p| (normal.dot(*p) - d).abs() > PLANE_TOLERANCE)
+    {
+        return Err(OffsetError::UnsupportedSurface(id));
+    }
+    Ok(FacePlane { normal, d })
+}
+
+/// Outward tangent plane of a curved face at one of its vertices
+fn tangent_plane(solid: &Solid, id: FaceId, point: DVec3) -> Result<FacePlane, OffsetError> {
+    let face = solid.face(id).ok_or(OffsetError::FaceNotFound(id))?;
+    let mut normal = to_dvec(face.surface.normal_at(to_point(point))).normalize_or_zero();
+    if normal == DVec3::ZERO {
+        return Err(OffsetError::UnsupportedSurface(id));
+    }
+    if face.orientation == FaceOrientation::Inward {
+        normal = -normal;
+    }
+    Ok(FacePlane {
+        normal,
+        d: normal.dot(point),
+    })
+}
+
+/// Least-squares meet of a vertex's face planes, closest to where it was
+///
+/// Solves `(Σ nnᵀ + λI) x = Σ n·d + λ·p`; the small `λ` keeps the vertex in
+/// place along directions its planes leave free (coplanar neighbours, or a
+/// vertex in the middle of an edge). Returns `None` if the planes do not
+/// meet.
+fn solve_vertex(original: DVec3, planes: &[FacePlane]) -> Option<DVec3> {
+    const LAMBDA: f64 = 1e-9;
+    if planes.is_empty() {
+        return Some(original);
+    }
+
+    // Solve for the displacement so that λ stays small next to coordinates
+    let mut a = DMat3::from_diagonal(DVec3::splat(LAMBDA));
+    let mut b = DVec3::ZERO;
+    for plane in planes {
+        let n = plane.normal;
+        a += DMat3::from_cols(n * n.x, n * n.y, n * n.z);
+        b += n * (plane.d - n.dot(original));
+    }
+    if a.determinant().abs() < 1e-18 {
+        return None;
+    }
+    let point = original + a.inverse() * b;
+
+    planes
+        .iter()
+        .all(|p| (p.normal.dot(point) - p.d).abs() < PLANE_TOLERANCE)
+        .then_some(point)
+}
+
+/// Offset an analytic surface by `delta` along the face's outward normal
+fn offset_surface(
+    surface: &SurfaceType,
+    orientation: FaceOrientation,
+    sample: DVec3,
+    delta: f64,
+) -> Option<SurfaceType> {
+    // Distance along the surface's own normal (away from axis or centre)
+    let s = match orientation {
+        FaceOrientation::Outward => delta,
+        FaceOrientation::Inward => -delta,
+    } as f32;
+
+    let surface = match surface {
+        SurfaceType::Planar { normal } => SurfaceType::Planar { normal: *normal },
+        SurfaceType::Cylindrical {
+            axis,
+            center,
+            radius,
+        } => SurfaceType::Cylindrical {
+            axis: *axis,
+            center: *center,
+            radius: positive(radius + s)?,
+        },
+        SurfaceType::Spherical { center, radius } => SurfaceType::Spherical {
+            center: *center,
+            radius: positive(radius + s)?,
+        },
+        SurfaceType::Toroidal {
+            center,
+            axis,
+            major_radius,
+            minor_radius,
+        } => SurfaceType::Toroidal {
+            center: *center,
+            axis: *axis,
+            major_radius: *major_radius,
+            minor_radius: positive(minor_radius + s)?,
+        },
+        SurfaceType::Conical {
+            apex,
+            axis,
+            half_angle,
+        } => {
+            // The apex slides along the axis by s / sin α, away from the opening
+            let sin = half_angle.sin();
+            if sin.abs() < 1e-6 {
+                return None;
+            }
+            let axis_d = to_dvec(*axis).normalize_or_zero();
+            let opening = if (sample - point_dvec(*apex)).dot(axis_d) < 0.0 {
+                -axis_d
+            } else {
+                axis_d
+            };
+            let shift = opening * (s / sin) as f64;
+            SurfaceType::Conical {
+                apex: to_point(point_dvec(*apex) - shift),
+                axis: *axis,
+                half_angle: *half_angle,
+            }
+        }
+        SurfaceType::Nurbs { .. } => return None,
+    };
+    Some(surface)
+}
+
+/// Arc carried to new end points: the centre slides along the arc normal
+/// with them and the radius follows, keeping the swept angles
+fn offset_arc(curve: &CurveType, old: [DVec3; 2], new: [DVec3; 2]) -> Option<CurveType> {
+    let CurveType::Arc {
+        center,
+        normal,
+        start_angle,
+        end_angle,
+        ..
+    } = curve
+    else {
+        return None;
+    };
+    let n = to_dvec(*normal).normalize_or_zero();
+    let lift = (new[0] - old[0]).dot(n);
+    if ((new[1] - old[1]).dot(n) - lift).abs() > PLANE_TOLERANCE {
+        return None;
+    }
+    let center = point_dvec(*center) + n * lift;
+    let arc = CurveType::Arc {
+        center: to_point(center),
+        radius: positive((new[0] - center).length() as f32)?,
+        normal: *normal,
+        start_angle: *start_angle,
+        end_angle: *end_angle,
+    };
+
+    let (start, end) = (to_point(new[0]), to_point(new[1]));
+    let fits =
+        |t: f32, p: DVec3| point_dvec(arc.point_at(start, end, t)).distance(p) < PLANE_TOLERANCE;
+    (fits(0.0, new[0]) && fits(1.0, new[1])).then_some(arc)
+}
+
+/// Fail if the face's outer polygon, re-evaluated at new positions, has
+/// collapsed or turned over
+fn check_not_flipped(
+    solid: &Solid,
+    id: FaceId,
+    outer: &Loop,
+    new_point: impl Fn(VertexId) -> DVec3,
+) -> Result<(), OffsetError> {
+    let before = newell(&loop_points(solid, outer, |v| vertex_point(solid, v)));
+    let after = newell(&loop_points(solid, outer, new_point));
+    if before.dot(after) <= 1e-6 * before.length_squared() {
+        return Err(OffsetError::TooLarge(id));
+    }
+    Ok(())
+}
+
+/// Loop traversed the other way round
+fn reversed(loop_: &Loop) -> Loop {
+    let mut out = Loop::new();
+    for (edge, forward) in loop_.edges.iter().zip(&loop_.directions).rev() {
+        out.add_edge(*edge, !*forward);
+    }
+    out
+}
+
+fn loop_points(solid: &Solid, loop_: &Loop, point: impl Fn(VertexId) -> DVec3) -> Vec<DVec3> {
+    solid.loop_vertices(loop_).into_iter().map(point).collect()
+}
+
+fn face_centroid(solid: &Solid, id: FaceId) -> DVec3 {
+    let Some(face) = solid.face(id) else {
+        return DVec3::ZERO;
+    };
+    let points = loop_points(solid, &face.outer_loop, |v| vertex_point(solid, v));
+    points.iter().copied().sum::<DVec3>() / points.len().max(1) as f64
+}
+
+/// Polygon normal by Newell's method (unnormalized, length = 2 × area)
+fn newell(points: &[DVec3]) -> DVec3 {
+    let mut n = DVec3::ZERO;
+    for i in 0..points.len() {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        n += a.cross(b);
+    }
+    n
+}
+
+fn vertex_point(solid: &Solid, v: VertexId) -> DVec3 {
+    solid
+        .vertex(v)
+        .map(|v| point_dvec(v.point))
+        .unwrap_or(DVec3::ZERO)
+}
+
+fn to_dvec(v: Vector3) -> DVec3 {
+    DVec3::new(v.x as f64, v.y as f64, v.z as f64)
+}
+
+fn point_dvec(p: Point3) -> DVec3 {
+    DVec3::new(p.x as f64, p.y as f64, p.z as f64)
+}
+
+fn to_point(p: DVec3) -> Point3 {
+    Point3::new(p.x as f32, p.y as f32, p.z as f32)
+}
+
+fn positive(value: f32) -> Option<f32> {
+    (value > 1e-6).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cad::mesh::solid_to_mesh;
+    use crate::cad::primitives::{make_box, make_cylinder, make_sphere};
+
+    // Box faces: 0 bottom, 1 top, 2 front, 3 back, 4 left, 5 right
+    const TOP: FaceId = FaceId(1);
+    const FRONT: FaceId = FaceId(2);
+    const SIDES: [FaceId; 4] = [FaceId(2), FaceId(3), FaceId(4), FaceId(5)];
+
+    #[test]
+    fn test_shell_box_open_top() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        let result = shell_solid(&solid, &[TOP], 2.0).unwrap();
+
+        assert!(result.is_valid());
+        assert!(result.is_closed());
+        assert_eq!(result.vertices.len(), 16);
+        assert_eq!(result.edges.len(), 24);
+        assert_eq!(result.faces.len(), 11);
+
+        // The top becomes a rim around the 16 × 16 opening
+        assert_eq!(result.face(TOP).unwrap().inner_loops.len(), 1);
+
+        // 16 × 16 × 18 cavity
+        assert!((solid_to_mesh(&result).volume() - (8000.0 - 4608.0)).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_shell_closed_box_makes_void() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        let result = shell_solid(&solid, &[], 2.0).unwrap();
+
+        assert!(result.is_valid());
+        assert!(result.is_closed());
+        assert_eq!(result.shells.len(), 2);
+        assert!(result.shells.iter().all(|s| s.is_closed));
+        assert!((solid_to_mesh(&result).volume() - (8000.0 - 4096.0)).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_shell_cylinder_offsets_surfaces() {
+        let solid = make_cylinder(10.0, 20.0, 24);
+        let top = solid
+            .faces
+            .iter()
+            .find(|f| matches!(f.surface, SurfaceType::Planar { normal } if normal.z > 0.5))
+            .unwrap()
+            .id;
+        let result = shell_solid(&solid, &[top], 1.0).unwrap();
+
+        assert!(result.is_valid());
+        assert!(result.is_closed());
+
+        // Wall is 1mm thick all round, so the cavity is a 9mm prism of height 19
+        let apothem = 10.0 * (std::f32::consts::PI / 24.0).cos();
+        let area = |r: f32| 24.0 * r * r * (std::f32::consts::PI / 24.0).tan();
+        let expected = area(apothem) * 20.0 - area(apothem - 1.0) * 19.0;
+        assert!((solid_to_mesh(&result).volume() - expected).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_shell_sphere() {
+        let solid = make_sphere(10.0, 16, 8);
+        let result = shell_solid(&solid, &[], 1.0).unwrap();
+
+        // Facets meet four or more to a vertex, and the offset planes still agree
+        assert!(result.is_valid());
+        let inner = result.faces[solid.faces.len()..]
+            .iter()
+            .filter_map(|f| match f.surface {
+                SurfaceType::Spherical { radius, .. } => Some(radius),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(!inner.is_empty());
+        assert!(inner.iter().all(|r| (r - 9.0).abs() < 1e-4));
+        // The faceted sphere primitive winds inward, so compare magnitudes
+        assert!(solid_to_mesh(&result).volume().abs() < solid_to_mesh(&solid).volume().abs());
+    }
+
+    #[test]
+    fn test_shell_filleted_box() {
+        use crate::cad::fillet::fillet_edges;
+
+        // Radius 4 blend along the bottom front edge
+        let solid = fillet_edges(&make_box(20.0, 20.0, 20.0), &[EdgeId(0)], 4.0).unwrap();
+        let blend = solid
+            .faces
+            .iter()
+            .find(|f| matches!(f.surface, SurfaceType::Cylindrical { .. }))
+            .unwrap()
+            .id;
+        let result = shell_solid(&solid, &[TOP], 2.0).unwrap();
+
+        assert!(result.is_valid());
+        assert!(result.is_closed());
+
+        // The inner blend is concentric with radius 2, its arcs too
+        let inner = &result.faces[solid.faces.len()..];
+        assert!(inner.iter().any(
+            |f| matches!(f.surface, SurfaceType::Cylindrical { radius, .. } if (radius - 2.0).abs() < 1e-4)
+        ));
+        let arcs: Vec<f32> = result.edges[solid.edges.len()..]
+            .iter()
+            .filter_map(|e| match e.curve {
+                CurveType::Arc { radius, .. } => Some(radius),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(arcs.len(), 2);
+        assert!(arcs.iter().all(|r| (r - 2.0).abs() < 1e-4));
+
+        // 16 × 16 × 18 cavity less its own radius 2 blend
+        let cavity = 4608.0 - (1.0 - std::f32::consts::FRAC_PI_4) * 4.0 * 16.0;
+        let expected = solid_to_mesh(&solid).volume() - cavity;
+        let v = solid_to_mesh(&result).volume();
+        assert!((v - expected).abs() < 0.5, "volume {v}");
+
+        // A curved face cannot become a rim
+        assert!(matches!(
+            shell_solid(&solid, &[blend], 2.0),
+            Err(OffsetError::UnsupportedSurface(_))
+        ));
+    }
+
+    #[test]
+    fn test_shell_rejects_bad_input() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        assert!(matches!(
+            shell_solid(&solid, &[TOP], 0.0),
+            Err(OffsetError::InvalidThickness)
+        ));
+        assert!(matches!(
+            shell_solid(&solid, &[TOP, FRONT], 2.0),
+            Err(OffsetError::AdjacentRemovedFaces(_))
+        ));
+        assert!(matches!(
+            shell_solid(&solid, &[FaceId(99)], 2.0),
+            Err(OffsetError::FaceNotFound(_))
+        ));
+        assert!(matches!(
+            shell_solid(&solid, &[TOP], 12.0),
+            Err(OffsetError::TooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_offset_face_grows_and_trims() {
+        let solid = make_box(20.0, 20.0, 20.0);
+
+        let taller = offset_face(&solid, TOP, 5.0).unwrap();
+        assert!(taller.is_valid());
+        assert!((solid_to_mesh(&taller).volume() - 10000.0).abs() < 0.5);
+        assert!(
+            taller
+                .vertices
+                .iter()
+                .filter(|v| (v.point.z - 15.0).abs() < 1e-4)
+                .count()
+                == 4
+        );
+
+        let shorter = offset_face(&solid, TOP, -5.0).unwrap();
+        assert!((solid_to_mesh(&shorter).volume() - 6000.0).abs() < 0.5);
+
+        assert!(matches!(
+            offset_face(&solid, TOP, -25.0),
+            Err(OffsetError::TooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_offset_curved_facet_updates_surface() {
+        // One side facet of a 16-sided cylinder, tagged with its true surface
+        let mut solid = make_cylinder(10.0, 20.0, 16);
+        let strip = FaceId(2);
+        solid.face_mut(strip).unwrap().surface = SurfaceType::Cylindrical {
+            axis: Vector3::Z,
+            center: Point3::ORIGIN,
+            radius: 10.0,
+        };
+        let result = offset_face(&solid, strip, 0.05).unwrap();
+
+        assert!(result.is_valid());
+        assert!(result.is_closed());
+        assert!(matches!(
+            result.face(strip).unwrap().surface,
+            SurfaceType::Cylindrical { radius, .. } if (radius - 10.05).abs() < 1e-4
+        ));
+        assert!(solid_to_mesh(&result).volume() > solid_to_mesh(&solid).volume());
+    }
+
+    #[test]
+    fn test_draft_box_sides() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        let neutral = Plane::new(Point3::new(0.0, 0.0, -10.0), Vector3::Z).unwrap();
+        let result = draft_faces(&solid, &SIDES, neutral, 5.0).unwrap();
+
+        assert!(result.is_valid());
+
+        // Bottom stays on the neutral plane; the top shrinks by 2·h·tan 5°
+        let bottom = 20.0f32;
+        let top = 20.0 - 2.0 * 20.0 * 5f32.to_radians().tan();
+        let expected = 20.0 / 3.0 * (bottom * bottom + top * top + bottom * top);
+        assert!((solid_to_mesh(&result).volume() - expected).abs() < 0.5);
+
+        for v in &result.vertices {
+            let half = if v.point.z < 0.0 { bottom } else { top } / 2.0;
+            assert!((v.point.x.abs() - half).abs() < 1e-3);
+            assert!((v.point.y.abs() - half).abs() < 1e-3);
+        }
+
+        let SurfaceType::Planar { normal } = result.face(FRONT).unwrap().surface else {
+            panic!("front face should stay planar");
+        };
+        assert!((normal.z - 5f32.to_radians().sin()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_draft_rejects_parallel_face() {
+        let solid = make_box(20.0, 20.0, 20.0);
+        assert!(matches!(
+            draft_faces(&solid, &[TOP], Plane::XY, 5.0),
+            Err(OffsetError::NotDraftable(TOP))
+        ));
+        assert!(matches!(
+            draft_faces(&solid, &SIDES, Plane::XY, 90.0),
+            Err(OffsetError::InvalidAngle)
+        ));
+    }
+
+    #[test]
+    fn test_draft_beside_fillet_is_curved_edge() {
+        use crate::cad::fillet::fillet_edges;
+
+        // The front face ends in the blend along the bottom front edge
+        let solid = fillet_edges(&make_box(20.0, 20.0, 20.0), &[EdgeId(0)], 4.0).unwrap();
+        assert!(matches!(
+            draft_faces(&solid, &[FRONT], Plane::XY, 5.0),
+            Err(OffsetError::CurvedEdge(_))
+        ));
+    }
+}
@@ -6,7 +6,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 use super::geometry::{Point3, Vector3};
-use super::sketch::{Point2, Sketch, SketchEntity};
+use super::sketch::{Point2, Sketch};
 use super::topology::{
     CurveType, Edge, EdgeId, Face, FaceId, FaceOrientation, Loop, Shell, ShellId, Solid,
     SurfaceType, Vertex, VertexId,
@@ -152,31 +152,10 @@ pub fn revolve_sketch(sketch: &Sketch, params: &RevolveParams) -> Result<Solid,
     Ok(solid)
 }
 
+/// Profile polyline: the sketch's curves chained end to end (open or closed)
 fn extract_profile_polyline(sketch: &Sketch) -> Result<Vec<Point2>, RevolveError> {
-    // Minimal v1: take all line entities in order of appearance and use their start points.
-    let mut pts: Vec<Point2> = Vec::new();
-    for entity in &sketch.entities {
-        if let SketchEntity::Line { start, .. } = entity {
-            if let Some(p) = sketch.point(*start) {
-                pts.push(p.position);
-            }
-        }
-    }
-    if pts.is_empty() {
-        return Err(RevolveError::NoProfile);
-    }
-    // Add last end point if possible to make a polyline
-    if let Some(SketchEntity::Line { end, .. }) = sketch
-        .entities
-        .iter()
-        .rev()
-        .find(|e| matches!(e, SketchEntity::Line { .. }))
-    {
-        if let Some(p) = sketch.point(*end) {
-            pts.push(p.position);
-        }
-    }
-    Ok(pts)
+    let (points, _closed) = sketch.chain_profile().ok_or(RevolveError::NoProfile)?;
+    Ok(points)
 }
 
 fn revolve_point_2d(p: Point2, theta: f32, axis: RevolveAxis) -> Point3 {
@@ -257,7 +236,7 @@ fn add_cap_face(solid: &mut Solid, ring: &[VertexId], start_cap: bool) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cad::sketch::{SketchEntityId, SketchPlane};
+    use crate::cad::sketch::{SketchEntity, SketchEntityId, SketchPlane};
 
     #[test]
     fn test_revolve_simple_profile() {
@@ -282,4 +261,27 @@ mod tests {
         assert!(solid.faces.len() > 0);
         assert!(solid.is_valid());
     }
+
+    #[test]
+    fn test_revolve_spline_profile() {
+        // Vase-like outline: spline wall from the base to the rim
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let control = [(5.0, 0.0), (9.0, 6.0), (3.0, 12.0), (6.0, 18.0)]
+            .map(|(x, y)| sketch.add_point(Point2::new(x, y)));
+        sketch.add_entity(SketchEntity::Spline {
+            id: SketchEntityId(0),
+            control_points: control.to_vec(),
+            degree: 3,
+        });
+
+        let params = RevolveParams {
+            segments: 12,
+            ..RevolveParams::default()
+        };
+        let solid = revolve_sketch(&sketch, &params).unwrap();
+        let profile_len = sketch.chain_profile().unwrap().0.len();
+        assert!(profile_len > 4);
+        assert_eq!(solid.vertices.len(), 12 * profile_len);
+        assert_eq!(solid.faces.len(), 12 * (profile_len - 1));
+    }
 }
@@ -5,10 +5,19 @@
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
+use super::constraints::{Constraint, GeometricConstraint};
 use super::geometry::{Point3, Vector3};
+use super::mesh::DEFAULT_CHORD_TOLERANCE;
+use super::nurbs::NurbsCurve;
 use super::topology::{Face, Solid};
 use serde::{Deserialize, Serialize};
 
+/// Angular step used to facet arcs, circles and ellipses (32 segments per turn)
+const ARC_STEP: f32 = std::f32::consts::PI / 16.0;
+
+/// Distance below which sketch endpoints are treated as joined
+pub(crate) const JOIN_TOLERANCE: f32 = 1e-4;
+
 /// Coordinate frame for arbitrary sketch planes
 ///
 /// Defines a local 2D coordinate system embedded in 3D space.
@@ -285,6 +294,19 @@ pub enum SketchEntity {
         id: SketchEntityId,
         point: SketchPointId,
     },
+    /// Clamped B-spline: passes through its first and last control points
+    Spline {
+        id: SketchEntityId,
+        control_points: Vec<SketchPointId>,
+        degree: u32,
+    },
+    /// Full ellipse; `major` is the end of the major axis and sets its rotation
+    Ellipse {
+        id: SketchEntityId,
+        center: SketchPointId,
+        major: SketchPointId,
+        minor_radius: f32,
+    },
 }
 
 impl SketchEntity {
@@ -294,10 +316,39 @@ impl SketchEntity {
             Self::Arc { id, .. } => *id,
             Self::Circle { id, .. } => *id,
             Self::Point { id, .. } => *id,
+            Self::Spline { id, .. } => *id,
+            Self::Ellipse { id, .. } => *id,
+        }
+    }
+
+    /// Points that define the entity
+    pub fn point_ids(&self) -> Vec<SketchPointId> {
+        match self {
+            Self::Line { start, end, .. } => vec![*start, *end],
+            Self::Arc {
+                center, start, end, ..
+            } => vec![*center, *start, *end],
+            Self::Circle { center, .. } => vec![*center],
+            Self::Point { point, .. } => vec![*point],
+            Self::Spline { control_points, .. } => control_points.clone(),
+            Self::Ellipse { center, major, .. } => vec![*center, *major],
         }
     }
 }
 
+/// Entities created by [`Sketch::add_slot`]
+#[derive(Clone, Debug)]
+pub struct SketchSlot {
+    /// Centres of the two end arcs
+    pub centers: [SketchPointId; 2],
+    /// End arcs, around `centers[0]` and `centers[1]`
+    pub arcs: [SketchEntityId; 2],
+    /// Straight sides
+    pub lines: [SketchEntityId; 2],
+    /// Constraints that keep the outline a slot (tangent sides, equal ends)
+    pub constraints: Vec<Constraint>,
+}
+
 /// 2D parametric sketch
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Sketch {
@@ -392,17 +443,294 @@ impl Sketch {
     pub fn entities_with_point(&self, point_id: SketchPointId) -> Vec<SketchEntityId> {
         self.entities
             .iter()
-            .filter(|e| match e {
-                SketchEntity::Line { start, end, .. } => *start == point_id || *end == point_id,
-                SketchEntity::Arc {
-                    center, start, end, ..
-                } => *center == point_id || *start == point_id || *end == point_id,
-                SketchEntity::Circle { center, .. } => *center == point_id,
-                SketchEntity::Point { point, .. } => *point == point_id,
-            })
+            .filter(|e| e.point_ids().contains(&point_id))
             .map(|e| e.id())
             .collect()
     }
+
+    /// Next unused entity ID
+    pub fn next_entity_id(&self) -> SketchEntityId {
+        SketchEntityId(
+            self.entities
+                .iter()
+                .map(|e| e.id().0 + 1)
+                .max()
+                .unwrap_or(0),
+        )
+    }
+
+    /// Add a slot (stadium) around the segment `start`→`end`
+    ///
+    /// Creates the two arc centres, four rim points, two sides and two end
+    /// arcs, wound counter-clockwise. The returned constraints hold the
+    /// shape together when the solver moves it; append them to the sketch's
+    /// constraint list.
+    pub fn add_slot(&mut self, start: Point2, end: Point2, radius: f32) -> SketchSlot {
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let len = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+        let (nx, ny) = (-dy / len * radius, dx / len * radius);
+        let offset = |p: Point2, s: f32| Point2::new(p.x + s * nx, p.y + s * ny);
+
+        let c0 = self.add_point(start);
+        let c1 = self.add_point(end);
+        let bottom0 = self.add_point(offset(start, -1.0));
+        let bottom1 = self.add_point(offset(end, -1.0));
+        let top1 = self.add_point(offset(end, 1.0));
+        let top0 = self.add_point(offset(start, 1.0));
+
+        let mut next = self.next_entity_id().0;
+        let mut add = |sketch: &mut Self, make: &dyn Fn(SketchEntityId) -> SketchEntity| {
+            let id = SketchEntityId(next);
+            next += 1;
+            sketch.add_entity(make(id))
+        };
+        let bottom = add(self, &|id| SketchEntity::Line {
+            id,
+            start: bottom0,
+            end: bottom1,
+        });
+        let arc1 = add(self, &|id| SketchEntity::Arc {
+            id,
+            center: c1,
+            start: bottom1,
+            end: top1,
+            radius,
+            ccw: true,
+        });
+        let top = add(self, &|id| SketchEntity::Line {
+            id,
+            start: top1,
+            end: top0,
+        });
+        let arc0 = add(self, &|id| SketchEntity::Arc {
+            id,
+            center: c0,
+            start: top0,
+            end: bottom0,
+            radius,
+            ccw: true,
+        });
+
+        let geometric = |gc| Constraint::Geometric(gc);
+        let mut constraints = Vec::new();
+        for (arc, rim_end) in [(arc0, bottom0), (arc1, top1)] {
+            // Arc radius comes from its start point; keep the end on the rim too
+            constraints.push(geometric(GeometricConstraint::PointOnCircle {
+                point: rim_end,
+                entity: arc,
+            }));
+            for line in [bottom, top] {
+                constraints.push(geometric(GeometricConstraint::Tangent {
+                    entity1: line,
+                    entity2: arc,
+                }));
+            }
+        }
+        constraints.push(geometric(GeometricConstraint::Equal {
+            entity1: arc0,
+            entity2: arc1,
+        }));
+
+        SketchSlot {
+            centers: [c0, c1],
+            arcs: [arc0, arc1],
+            lines: [bottom, top],
+            constraints,
+        }
+    }
+
+    /// Sample an entity as a polyline in sketch coordinates
+    ///
+    /// Arcs, circles and ellipses are faceted at a fixed angular step and
+    /// splines to the default chord tolerance. Closed entities repeat their
+    /// first point at the end. Points have no polyline.
+    pub fn entity_polyline(&self, id: SketchEntityId) -> Option<Vec<Point2>> {
+        let position = |id| self.point(id).map(|p| p.position);
+        match self.entity(id)? {
+            SketchEntity::Line { start, end, .. } => Some(vec![position(*start)?, position(*end)?]),
+            SketchEntity::Arc {
+                center,
+                start,
+                end,
+                ccw,
+                ..
+            } => Some(arc_points(
+                position(*center)?,
+                position(*start)?,
+                position(*end)?,
+                *ccw,
+            )),
+            SketchEntity::Circle { center, radius, .. } => {
+                let c = position(*center)?;
+                Some(ellipse_points(c, Point2::new(c.x + radius, c.y), *radius))
+            }
+            SketchEntity::Ellipse {
+                center,
+                major,
+                minor_radius,
+                ..
+            } => Some(ellipse_points(
+                position(*center)?,
+                position(*major)?,
+                *minor_radius,
+            )),
+            SketchEntity::Spline {
+                control_points,
+                degree,
+                ..
+            } => {
+                let points: Vec<Point2> = control_points
+                    .iter()
+                    .map(|&p| position(p))
+                    .collect::<Option<_>>()?;
+                spline_points(&points, *degree)
+            }
+            SketchEntity::Point { .. } => None,
+        }
+    }
+
+    /// Chain the sketch's curves end to end into one profile
+    ///
+    /// Returns the chained points and whether the chain closes on itself; a
+    /// closed chain repeats its first point at the end. Point entities are
+    /// ignored; curves that do not join the chain make it fail.
+    pub fn chain_profile(&self) -> Option<(Vec<Point2>, bool)> {
+        let mut pieces: Vec<Vec<Point2>> = Vec::new();
+        for entity in &self.entities {
+            if matches!(entity, SketchEntity::Point { .. }) {
+                continue;
+            }
+            pieces.push(self.entity_polyline(entity.id())?);
+        }
+        if pieces.is_empty() {
+            return None;
+        }
+
+        let joins = |a: Point2, b: Point2| a.distance(&b) < JOIN_TOLERANCE;
+        let free_end = |p: Point2, pieces: &[Vec<Point2>], skip: usize| {
+            !pieces.iter().enumerate().any(|(i, piece)| {
+                i != skip && (joins(piece[0], p) || joins(piece[piece.len() - 1], p))
+            })
+        };
+
+        // Open chains start from a loose end; closed ones from the first piece
+        let mut first = 0;
+        let mut reverse_first = false;
+        for (i, piece) in pieces.iter().enumerate() {
+            if free_end(piece[0], &pieces, i) {
+                first = i;
+                break;
+            }
+            if free_end(piece[piece.len() - 1], &pieces, i) {
+                first = i;
+                reverse_first = true;
+                break;
+            }
+        }
+
+        let mut chain = pieces.remove(first);
+        if reverse_first {
+            chain.reverse();
+        }
+        while !pieces.is_empty() {
+            let tail = chain[chain.len() - 1];
+            let i = pieces
+                .iter()
+                .position(|p| joins(p[0], tail) || joins(p[p.len() - 1], tail))?;
+            let mut piece = pieces.remove(i);
+            if !joins(piece[0], tail) {
+                piece.reverse();
+            }
+            chain.extend_from_slice(&piece[1..]);
+        }
+
+        let closed = chain.len() > 2 && joins(chain[0], chain[chain.len() - 1]);
+        if closed {
+            let last = chain.len() - 1;
+            chain[last] = chain[0];
+        }
+        Some((chain, closed))
+    }
+}
+
+/// Faceted arc from start to end, including both
+fn arc_points(center: Point2, start: Point2, end: Point2, ccw: bool) -> Vec<Point2> {
+    let radius = center.distance(&start);
+    let a0 = (start.y - center.y).atan2(start.x - center.x);
+    let a1 = (end.y - center.y).atan2(end.x - center.x);
+    let mut sweep = a1 - a0;
+    if ccw {
+        while sweep <= 0.0 {
+            sweep += std::f32::consts::TAU;
+        }
+    } else {
+        while sweep >= 0.0 {
+            sweep -= std::f32::consts::TAU;
+        }
+    }
+
+    let segments = (sweep.abs() / ARC_STEP - 1e-3).ceil().max(1.0) as usize;
+    let mut points: Vec<Point2> = (0..=segments)
+        .map(|k| {
+            let a = a0 + sweep * k as f32 / segments as f32;
+            Point2::new(center.x + radius * a.cos(), center.y + radius * a.sin())
+        })
+        .collect();
+    points[0] = start;
+    points[segments] = end;
+    points
+}
+
+/// Closed ellipse ring starting at the end of the major axis
+fn ellipse_points(center: Point2, major: Point2, minor_radius: f32) -> Vec<Point2> {
+    let (ux, uy) = (major.x - center.x, major.y - center.y);
+    let a = (ux * ux + uy * uy).sqrt();
+    let (ex, ey) = if a > 0.0 {
+        (ux / a, uy / a)
+    } else {
+        (1.0, 0.0)
+    };
+
+    let segments = (std::f32::consts::TAU / ARC_STEP).round() as usize;
+    let mut ring: Vec<Point2> = (0..=segments)
+        .map(|k| {
+            let t = std::f32::consts::TAU * k as f32 / segments as f32;
+            let (u, v) = (a * t.cos(), minor_radius * t.sin());
+            Point2::new(center.x + u * ex - v * ey, center.y + u * ey + v * ex)
+        })
+        .collect();
+    ring[segments] = ring[0];
+    ring
+}
+
+/// Clamped uniform B-spline over the control polygon, tessellated
+fn spline_points(control: &[Point2], degree: u32) -> Option<Vec<Point2>> {
+    if control.len() < 2 {
+        return None;
+    }
+    let n = control.len();
+    let p = (degree as usize).clamp(1, n - 1);
+    let spans = n - p;
+    let knots: Vec<f32> = std::iter::repeat_n(0.0, p + 1)
+        .chain((1..spans).map(|i| i as f32 / spans as f32))
+        .chain(std::iter::repeat_n(1.0, p + 1))
+        .collect();
+
+    let points: Vec<Point3> = control.iter().map(|c| Point3::new(c.x, c.y, 0.0)).collect();
+    let curve = NurbsCurve::new(p as u32, &points, &vec![1.0; n], &knots).ok()?;
+    let mut samples: Vec<Point2> = curve
+        .tessellate(DEFAULT_CHORD_TOLERANCE)
+        .into_iter()
+        .map(|q| Point2::new(q.x, q.y))
+        .collect();
+    if samples.len() < 2 {
+        return None;
+    }
+    // Clamped ends interpolate the end control points exactly
+    let last = samples.len() - 1;
+    samples[0] = control[0];
+    samples[last] = control[n - 1];
+    Some(samples)
 }
 
 /// Compute circumcenter of three points (circle through 3 points).
@@ -510,6 +838,95 @@ mod tests {
         assert!(orient2d(a, c, b) < 0.0);
     }
 
+    #[test]
+    fn test_slot_profile_and_constraints() {
+        use crate::cad::solver::{ConstraintAnalysis, ConstraintSolver};
+
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let slot = sketch.add_slot(Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), 2.0);
+
+        let (points, closed) = sketch.chain_profile().unwrap();
+        assert!(closed);
+        let area: f32 = points
+            .windows(2)
+            .map(|w| w[0].x * w[1].y - w[1].x * w[0].y)
+            .sum::<f32>()
+            * 0.5;
+        let expected = 10.0 * 4.0 + std::f32::consts::PI * 4.0;
+        assert!(area > 0.0, "slot should wind counter-clockwise");
+        assert!((area - expected).abs() / expected < 0.01, "area {}", area);
+
+        // Free parameters: both centres and the radius
+        let analysis = ConstraintAnalysis::analyze(&sketch, &slot.constraints);
+        assert!(analysis.redundant.is_empty() && analysis.conflicting.is_empty());
+        assert_eq!(analysis.remaining_dof, 5);
+
+        // Drag one end and let the constraints restore the shape
+        sketch.point_mut(slot.centers[1]).unwrap().position = Point2::new(12.0, 3.0);
+        let result = ConstraintSolver::default().solve(&mut sketch, &slot.constraints);
+        assert!(result.converged, "error = {}", result.final_error);
+        let (points, closed) = sketch.chain_profile().unwrap();
+        assert!(closed && points.len() > 8);
+    }
+
+    #[test]
+    fn test_ellipse_polyline() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let center = sketch.add_point(Point2::new(1.0, 2.0));
+        let major = sketch.add_point(Point2::new(1.0 + 3.0 * 0.6, 2.0 + 3.0 * 0.8));
+        let id = sketch.add_entity(SketchEntity::Ellipse {
+            id: SketchEntityId(0),
+            center,
+            major,
+            minor_radius: 1.5,
+        });
+
+        let ring = sketch.entity_polyline(id).unwrap();
+        assert_eq!(ring[0].x, ring[ring.len() - 1].x);
+        for p in &ring {
+            // Back into the ellipse frame: major axis along (0.6, 0.8)
+            let (dx, dy) = (p.x - 1.0, p.y - 2.0);
+            let u = dx * 0.6 + dy * 0.8;
+            let v = -dx * 0.8 + dy * 0.6;
+            let f = (u / 3.0).powi(2) + (v / 1.5).powi(2);
+            assert!((f - 1.0).abs() < 1e-4, "{:?} off the ellipse: {}", p, f);
+        }
+        assert_eq!(sketch.chain_profile().map(|(_, closed)| closed), Some(true));
+    }
+
+    #[test]
+    fn test_spline_polyline() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let control = [(0.0, 0.0), (2.0, 4.0), (6.0, 4.0), (8.0, 0.0)]
+            .map(|(x, y)| sketch.add_point(Point2::new(x, y)));
+        let id = sketch.add_entity(SketchEntity::Spline {
+            id: SketchEntityId(0),
+            control_points: control.to_vec(),
+            degree: 3,
+        });
+
+        let polyline = sketch.entity_polyline(id).unwrap();
+        assert!(polyline.len() > 4);
+        assert_eq!(polyline[0].x, 0.0);
+        assert_eq!(polyline[polyline.len() - 1].x, 8.0);
+
+        // Single-span cubic Bézier: peak at t = 0.5 is (P0 + 3P1 + 3P2 + P3) / 8
+        let peak = Point2::new(4.0, 3.0);
+        let closest = polyline
+            .iter()
+            .map(|p| p.distance(&peak))
+            .fold(f32::MAX, f32::min);
+        assert!(closest < 0.05, "closest sample {} from the peak", closest);
+
+        // Closing the spline with a line makes an extrudable profile
+        sketch.add_entity(SketchEntity::Line {
+            id: sketch.next_entity_id(),
+            start: control[3],
+            end: control[0],
+        });
+        assert_eq!(sketch.chain_profile().map(|(_, closed)| closed), Some(true));
+    }
+
     #[test]
     fn test_coordinate_frame_from_origin_normal() {
         // Frame at origin with Z normal (like XY plane)
@@ -1,13 +1,29 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: solver.rs | DNA/src/cad/solver.rs
-//! PURPOSE: Parametric constraint solver using Newton-Raphson iteration
+//! PURPOSE: Parametric constraint solver (Levenberg-Marquardt) and DOF analysis
 //! MODIFIED: 2026-01-04
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! The unknowns are the (x, y) coordinates of every sketch point. Each
+//! constraint contributes one or two residual equations with analytic
+//! Jacobian rows (see [`Constraint::equations`]); the Jacobian is kept
+//! sparse because a constraint touches at most four points.
+//!
+//! Solving minimises Σr² with Levenberg-Marquardt: each step solves
+//! (JᵀJ + λ·diag(JᵀJ))·δ = -Jᵀr by preconditioned conjugate gradients, and λ
+//! shrinks after an accepted step and grows after a rejected one.
+//!
+//! Analysis orthogonalises the Jacobian rows in constraint order. A row that
+//! adds nothing to the rank belongs to a redundant constraint when its
+//! residual is consistent with the earlier rows, and to a conflicting one
+//! when it is not. The null space of the Jacobian gives each point's
+//! remaining DOF.
 
 use super::constraints::Constraint;
 use super::geometry::TOLERANCE;
-use super::sketch::{Point2, Sketch, SketchPointId};
+use super::sketch::{ConstraintId, Sketch, SketchEntity, SketchEntityId, SketchPointId};
+use glam::DVec2;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -15,8 +31,10 @@ use std::collections::HashMap;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SolverConfig {
     pub max_iterations: usize,
+    /// Convergence threshold on the total squared error Σr²
     pub tolerance: f32,
-    pub damping_factor: f32, // Under-relaxation for stability
+    /// Starting Levenberg-Marquardt damping λ, relative to diag(JᵀJ)
+    pub initial_damping: f32,
 }
 
 impl Default for SolverConfig {
@@ -24,8 +42,7 @@ impl Default for SolverConfig {
         Self {
             max_iterations: 100,
             tolerance: TOLERANCE,
-            // 0.5 can oscillate for some simple geometric constraints with our simplified update rule.
-            damping_factor: 0.25,
+            initial_damping: 1e-3,
         }
     }
 }
@@ -45,7 +62,7 @@ pub enum DofStatus {
     FullyConstrained,
     /// Some DOF remain unconstrained
     UnderConstrained { dof: usize },
-    /// More constraints than DOF - system may be inconsistent
+    /// Some constraints are redundant or conflicting
     OverConstrained { redundant: usize },
 }
 
@@ -58,32 +75,138 @@ pub struct ConstraintAnalysis {
     pub total_dof: usize,
     /// Number of constraints
     pub constraint_count: usize,
-    /// Remaining unconstrained DOF (positive = under, negative = over)
+    /// Remaining unconstrained DOF (total DOF minus Jacobian rank)
     pub remaining_dof: i32,
     /// Per-constraint status: true = satisfied, false = not satisfied
     pub constraint_satisfied: Vec<bool>,
+    /// Rank of the constraint Jacobian (independent equations)
+    pub rank: usize,
+    /// Constraints implied by earlier ones and consistent with them
+    pub redundant: Vec<ConstraintId>,
+    /// Constraints implied by earlier ones but contradicting them
+    pub conflicting: Vec<ConstraintId>,
+    /// Remaining DOF of each point (0-2), indexed like `sketch.points`
+    pub point_dof: Vec<usize>,
 }
 
+/// Relative row norm below which a Jacobian row is linearly dependent
+const RANK_TOLERANCE: f64 = 1e-8;
+
+/// Eigenvalue of a point's null-space block above which a direction is free
+const FREE_DIRECTION_TOLERANCE: f64 = 1e-6;
+
 impl ConstraintAnalysis {
     /// Analyze a sketch and its constraints
+    ///
+    /// Constraint IDs are indices into `constraints`. Earlier constraints
+    /// take precedence: when two constraints say the same thing, the later
+    /// one is reported.
     pub fn analyze(sketch: &Sketch, constraints: &[Constraint]) -> Self {
         // Each point has 2 DOF (x, y)
         let total_dof = sketch.points.len() * 2;
         let constraint_count = constraints.len();
+        let residual_tolerance = (TOLERANCE as f64).sqrt();
 
-        // Simple DOF counting (doesn't account for dependent constraints)
-        let remaining_dof = total_dof as i32 - constraint_count as i32;
+        let x = sketch_coordinates(sketch);
+        let mut basis: Vec<(Vec<f64>, f64)> = Vec::new();
+        let mut redundant = Vec::new();
+        let mut conflicting = Vec::new();
+        let mut driven: HashMap<SketchEntityId, f32> = HashMap::new();
 
-        let dof_status = if remaining_dof == 0 {
-            DofStatus::FullyConstrained
+        for (i, constraint) in constraints.iter().enumerate() {
+            let id = ConstraintId(i as u32);
+
+            // Circle radii are parameters, not point coordinates: only a
+            // second dimension on the same circle can clash.
+            if let Some((entity, radius)) = constraint.driven_radius(sketch) {
+                match driven.get(&entity) {
+                    None => {
+                        driven.insert(entity, radius);
+                    }
+                    Some(first) if (first - radius).abs() as f64 <= residual_tolerance => {
+                        redundant.push(id)
+                    }
+                    Some(_) => conflicting.push(id),
+                }
+                continue;
+            }
+
+            let mut dependent = false;
+            let mut inconsistency: f64 = 0.0;
+            for (row, residual) in constraint_rows(sketch, constraint, &x) {
+                let mut v = vec![0.0; total_dof];
+                for (col, value) in row {
+                    v[col] += value;
+                }
+                let norm = v.iter().map(|a| a * a).sum::<f64>().sqrt();
+                let mut r = residual;
+
+                // Modified Gram-Schmidt, twice for stability, carrying the
+                // residual along as an augmented column.
+                for _ in 0..2 {
+                    for (q, rho) in &basis {
+                        let c: f64 = v.iter().zip(q).map(|(a, b)| a * b).sum();
+                        for (a, b) in v.iter_mut().zip(q) {
+                            *a -= c * b;
+                        }
+                        r -= c * rho;
+                    }
+                }
+
+                let rest = v.iter().map(|a| a * a).sum::<f64>().sqrt();
+                if norm > 0.0 && rest > RANK_TOLERANCE * norm {
+                    for a in v.iter_mut() {
+                        *a /= rest;
+                    }
+                    basis.push((v, r / rest));
+                } else {
+                    dependent = true;
+                    inconsistency = inconsistency.max(r.abs());
+                }
+            }
+
+            if dependent {
+                if inconsistency > residual_tolerance {
+                    conflicting.push(id);
+                } else {
+                    redundant.push(id);
+                }
+            }
+        }
+
+        let rank = basis.len();
+        let remaining_dof = total_dof as i32 - rank as i32;
+
+        // Null-space projector block for each point: I - Σ qqᵀ restricted to
+        // its two coordinates. Its rank is the number of free directions.
+        let point_dof = (0..sketch.points.len())
+            .map(|p| {
+                let (mut a, mut b, mut d) = (1.0, 0.0, 1.0);
+                for (q, _) in &basis {
+                    let (qx, qy) = (q[2 * p], q[2 * p + 1]);
+                    a -= qx * qx;
+                    b -= qx * qy;
+                    d -= qy * qy;
+                }
+                let mean = 0.5 * (a + d);
+                let spread = (0.25 * (a - d) * (a - d) + b * b).sqrt();
+                [mean + spread, mean - spread]
+                    .iter()
+                    .filter(|&&eig| eig > FREE_DIRECTION_TOLERANCE)
+                    .count()
+            })
+            .collect();
+
+        let dof_status = if !redundant.is_empty() || !conflicting.is_empty() {
+            DofStatus::OverConstrained {
+                redundant: redundant.len() + conflicting.len(),
+            }
         } else if remaining_dof > 0 {
             DofStatus::UnderConstrained {
                 dof: remaining_dof as usize,
             }
         } else {
-            DofStatus::OverConstrained {
-                redundant: (-remaining_dof) as usize,
-            }
+            DofStatus::FullyConstrained
         };
 
         // Check which constraints are satisfied
@@ -98,6 +221,10 @@ impl ConstraintAnalysis {
             constraint_count,
             remaining_dof,
             constraint_satisfied,
+            rank,
+            redundant,
+            conflicting,
+            point_dof,
         }
     }
 
@@ -121,6 +248,11 @@ impl ConstraintAnalysis {
     pub fn unsatisfied_count(&self) -> usize {
         self.constraint_satisfied.iter().filter(|&&s| !s).count()
     }
+
+    /// Remaining DOF of a point (0 = fully determined, 2 = free)
+    pub fn dof_of_point(&self, id: SketchPointId) -> usize {
+        self.point_dof.get(id.0 as usize).copied().unwrap_or(0)
+    }
 }
 
 /// Parametric constraint solver
@@ -128,6 +260,12 @@ pub struct ConstraintSolver {
     config: SolverConfig,
 }
 
+/// Smallest diagonal used when damping, so untouched coordinates stay put
+const DIAGONAL_FLOOR: f64 = 1e-9;
+
+/// Damping increases tolerated per iteration before the solver gives up
+const MAX_REJECTED_STEPS: usize = 12;
+
 impl ConstraintSolver {
     pub fn new(config: SolverConfig) -> Self {
         Self { config }
@@ -135,90 +273,271 @@ impl ConstraintSolver {
 
     /// Solve all constraints in the sketch
     ///
-    /// Uses Newton-Raphson iteration to minimize total constraint error.
-    /// Updates sketch.points in-place to satisfy constraints.
+    /// Uses Levenberg-Marquardt to minimize the total squared constraint
+    /// error. Updates sketch.points in-place to satisfy constraints.
     pub fn solve(&self, sketch: &mut Sketch, constraints: &[Constraint]) -> SolverResult {
-        let mut iteration = 0;
-        let mut total_error = f32::MAX;
-
-        while iteration < self.config.max_iterations {
-            // Evaluate all constraints
-            total_error = 0.0;
-            let mut residuals = Vec::new();
-
-            for constraint in constraints {
-                let error = constraint.evaluate(sketch);
-                residuals.push(error);
-                total_error += error;
+        // Dimensions on circles drive the radius parameter directly
+        for constraint in constraints {
+            if let Some((entity, value)) = constraint.driven_radius(sketch) {
+                if let Some(SketchEntity::Circle { radius, .. }) =
+                    sketch.entities.iter_mut().find(|e| e.id() == entity)
+                {
+                    *radius = value;
+                }
             }
+        }
 
-            // Check convergence
-            if total_error < self.config.tolerance {
-                sketch.is_solved = true;
-                return SolverResult {
-                    converged: true,
-                    iterations: iteration,
-                    final_error: total_error,
-                };
-            }
+        let tolerance = self.config.tolerance as f64;
+        let mut x = sketch_coordinates(sketch);
+        let mut system = LinearSystem::new(sketch, constraints, &x);
+        let mut error = system.error();
+        let mut lambda = (self.config.initial_damping as f64).max(f64::EPSILON);
+        let mut iteration = 0;
 
-            // Build Jacobian matrix and solve
-            let updates = self.compute_updates(sketch, constraints, &residuals);
+        while error >= tolerance && iteration < self.config.max_iterations {
+            iteration += 1;
 
-            // Apply updates with damping
-            for (point_id, delta) in updates {
-                if let Some(point) = sketch.point_mut(point_id) {
-                    point.position.x += delta.x * self.config.damping_factor;
-                    point.position.y += delta.y * self.config.damping_factor;
+            let (normal, rhs) = system.normal_equations(x.len());
+            let mut accepted = false;
+
+            for _ in 0..MAX_REJECTED_STEPS {
+                let mut damped = normal.clone();
+                damped.add_to_diagonal(|d| lambda * d.max(DIAGONAL_FLOOR));
+                let step = damped.solve_cg(&rhs);
+
+                let trial: Vec<f64> = x.iter().zip(&step).map(|(a, b)| a + b).collect();
+                let trial_system = LinearSystem::new(sketch, constraints, &trial);
+                let trial_error = trial_system.error();
+
+                if trial_error.is_finite() && trial_error < error {
+                    x = trial;
+                    system = trial_system;
+                    error = trial_error;
+                    lambda = (lambda * 0.1).max(f64::EPSILON);
+                    accepted = true;
+                    break;
                 }
+                lambda *= 10.0;
             }
 
-            iteration += 1;
+            // No step reduces the error: a local minimum (or conflicting
+            // constraints) has been reached.
+            if !accepted {
+                break;
+            }
         }
 
-        sketch.is_solved = false;
+        for (i, point) in sketch.points.iter_mut().enumerate() {
+            point.position.x = x[2 * i] as f32;
+            point.position.y = x[2 * i + 1] as f32;
+        }
+        refresh_arc_radii(sketch);
+
+        let converged = error < tolerance;
+        sketch.is_solved = converged;
         SolverResult {
-            converged: false,
+            converged,
             iterations: iteration,
-            final_error: total_error,
+            final_error: error as f32,
         }
     }
+}
 
-    /// Compute position updates using pseudo-inverse
-    ///
-    /// Simplified solver: accumulates gradients per point.
-    /// Full Newton-Raphson would solve J^T·J·Δx = -J^T·r
-    fn compute_updates(
-        &self,
-        sketch: &Sketch,
-        constraints: &[Constraint],
-        residuals: &[f32],
-    ) -> HashMap<SketchPointId, Point2> {
-        let mut updates: HashMap<SketchPointId, Point2> = HashMap::new();
+impl Default for ConstraintSolver {
+    fn default() -> Self {
+        Self::new(SolverConfig::default())
+    }
+}
 
-        for (i, constraint) in constraints.iter().enumerate() {
-            let error = residuals[i];
-            if error.abs() < 1e-12 {
-                continue; // Already satisfied
+/// Point coordinates as a flat [x0, y0, x1, y1, ...] vector
+fn sketch_coordinates(sketch: &Sketch) -> Vec<f64> {
+    sketch
+        .points
+        .iter()
+        .flat_map(|p| [p.position.x as f64, p.position.y as f64])
+        .collect()
+}
+
+/// Keep the stored arc radius in step with its solved centre and start point
+fn refresh_arc_radii(sketch: &mut Sketch) {
+    let points = &sketch.points;
+    for entity in &mut sketch.entities {
+        if let SketchEntity::Arc {
+            center,
+            start,
+            radius,
+            ..
+        } = entity
+        {
+            if let (Some(c), Some(s)) =
+                (points.get(center.0 as usize), points.get(start.0 as usize))
+            {
+                *radius = c.position.distance(&s.position);
             }
+        }
+    }
+}
 
-            let grads = constraint.gradient(sketch);
+/// Sparse Jacobian rows (column, value) and residuals of one constraint at `x`
+fn constraint_rows(
+    sketch: &Sketch,
+    constraint: &Constraint,
+    x: &[f64],
+) -> Vec<(Vec<(usize, f64)>, f64)> {
+    let position = |id: SketchPointId| {
+        let i = id.0 as usize;
+        (2 * i + 1 < x.len()).then(|| DVec2::new(x[2 * i], x[2 * i + 1]))
+    };
+    constraint
+        .equations_at(sketch, position)
+        .into_iter()
+        .map(|eq| {
+            let mut row: Vec<(usize, f64)> = Vec::with_capacity(2 * eq.jacobian.len());
+            for (id, d) in eq.jacobian {
+                let col = 2 * id.0 as usize;
+                for (c, v) in [(col, d.x), (col + 1, d.y)] {
+                    match row.iter_mut().find(|(existing, _)| *existing == c) {
+                        Some((_, acc)) => *acc += v,
+                        None => row.push((c, v)),
+                    }
+                }
+            }
+            row.retain(|&(_, v)| v != 0.0);
+            (row, eq.residual)
+        })
+        .collect()
+}
 
-            // Accumulate gradient-based updates
-            for (point_id, grad) in grads {
-                let entry = updates.entry(point_id).or_insert(Point2::new(0.0, 0.0));
-                entry.x -= grad.x;
-                entry.y -= grad.y;
+/// Linearised constraint system J·δ ≈ -r at one set of coordinates
+struct LinearSystem {
+    rows: Vec<Vec<(usize, f64)>>,
+    residuals: Vec<f64>,
+}
+
+impl LinearSystem {
+    fn new(sketch: &Sketch, constraints: &[Constraint], x: &[f64]) -> Self {
+        let (rows, residuals) = constraints
+            .iter()
+            .flat_map(|c| constraint_rows(sketch, c, x))
+            .unzip();
+        Self { rows, residuals }
+    }
+
+    /// Total squared error Σr²
+    fn error(&self) -> f64 {
+        self.residuals.iter().map(|r| r * r).sum()
+    }
+
+    /// Sparse JᵀJ and the right-hand side -Jᵀr
+    fn normal_equations(&self, n: usize) -> (SparseSymmetric, Vec<f64>) {
+        let mut entries: Vec<HashMap<usize, f64>> = vec![HashMap::new(); n];
+        let mut rhs = vec![0.0; n];
+        for (row, r) in self.rows.iter().zip(&self.residuals) {
+            for &(i, vi) in row {
+                rhs[i] -= vi * r;
+                for &(j, vj) in row {
+                    *entries[i].entry(j).or_insert(0.0) += vi * vj;
+                }
             }
         }
-
-        updates
+        let rows = entries
+            .into_iter()
+            .map(|row| {
+                let mut row: Vec<(usize, f64)> = row.into_iter().collect();
+                row.sort_unstable_by_key(|&(j, _)| j);
+                row
+            })
+            .collect();
+        (SparseSymmetric { rows }, rhs)
     }
 }
 
-impl Default for ConstraintSolver {
-    fn default() -> Self {
-        Self::new(SolverConfig::default())
+/// Symmetric positive semi-definite matrix in row-compressed form
+#[derive(Clone)]
+struct SparseSymmetric {
+    rows: Vec<Vec<(usize, f64)>>,
+}
+
+impl SparseSymmetric {
+    fn diagonal(&self, i: usize) -> f64 {
+        self.rows[i]
+            .iter()
+            .find(|&&(j, _)| j == i)
+            .map_or(0.0, |&(_, v)| v)
+    }
+
+    /// Add f(Aᵢᵢ) to each diagonal entry of the rows that have any entries
+    fn add_to_diagonal(&mut self, f: impl Fn(f64) -> f64) {
+        for i in 0..self.rows.len() {
+            if self.rows[i].is_empty() {
+                continue;
+            }
+            let extra = f(self.diagonal(i));
+            match self.rows[i].iter_mut().find(|(j, _)| *j == i) {
+                Some((_, v)) => *v += extra,
+                None => self.rows[i].push((i, extra)),
+            }
+        }
+    }
+
+    fn mul(&self, v: &[f64]) -> Vec<f64> {
+        self.rows
+            .iter()
+            .map(|row| row.iter().map(|&(j, a)| a * v[j]).sum())
+            .collect()
+    }
+
+    /// Jacobi-preconditioned conjugate gradients, starting from zero
+    ///
+    /// Rows without entries belong to coordinates no constraint touches and
+    /// get a zero step.
+    fn solve_cg(&self, b: &[f64]) -> Vec<f64> {
+        let n = b.len();
+        let inv_diag: Vec<f64> = (0..n)
+            .map(|i| {
+                let d = self.diagonal(i);
+                if d > 0.0 {
+                    1.0 / d
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>();
+
+        let mut x = vec![0.0; n];
+        let mut r = b.to_vec();
+        let mut z: Vec<f64> = r.iter().zip(&inv_diag).map(|(a, m)| a * m).collect();
+        let mut p = z.clone();
+        let mut rz = dot(&r, &z);
+        let b_norm = dot(&r, &r).sqrt();
+        if b_norm == 0.0 {
+            return x;
+        }
+
+        for _ in 0..(2 * n + 10) {
+            let ap = self.mul(&p);
+            let pap = dot(&p, &ap);
+            if pap <= 0.0 || !pap.is_finite() {
+                break;
+            }
+            let alpha = rz / pap;
+            for i in 0..n {
+                x[i] += alpha * p[i];
+                r[i] -= alpha * ap[i];
+            }
+            if dot(&r, &r).sqrt() <= 1e-12 * b_norm {
+                break;
+            }
+            z = r.iter().zip(&inv_diag).map(|(a, m)| a * m).collect();
+            let rz_next = dot(&r, &z);
+            let beta = rz_next / rz;
+            rz = rz_next;
+            for i in 0..n {
+                p[i] = z[i] + beta * p[i];
+            }
+        }
+        x
     }
 }
 
@@ -514,6 +833,171 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_triangle_distances_converge_quadratically() {
+        // Skewed start far from the 3-4-5 solution
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let p1 = sketch.add_point(Point2::new(0.0, 0.0));
+        let p2 = sketch.add_point(Point2::new(8.0, 1.0));
+        let p3 = sketch.add_point(Point2::new(-2.0, 7.0));
+
+        let constraints = vec![
+            Constraint::Dimensional(DimensionalConstraint::Distance { p1, p2, value: 3.0 }),
+            Constraint::Dimensional(DimensionalConstraint::Distance {
+                p1: p2,
+                p2: p3,
+                value: 5.0,
+            }),
+            Constraint::Dimensional(DimensionalConstraint::Distance {
+                p1: p3,
+                p2: p1,
+                value: 4.0,
+            }),
+        ];
+
+        let solver = ConstraintSolver::default();
+        let result = solver.solve(&mut sketch, &constraints);
+
+        assert!(result.converged, "error = {}", result.final_error);
+        assert!(result.iterations < 20, "iterations = {}", result.iterations);
+        for constraint in &constraints {
+            assert!(constraint.evaluate(&sketch) < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_parallel_perpendicular_angle_solve() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let a0 = sketch.add_point(Point2::new(0.0, 0.0));
+        let a1 = sketch.add_point(Point2::new(10.0, 1.0));
+        let b0 = sketch.add_point(Point2::new(0.0, 5.0));
+        let b1 = sketch.add_point(Point2::new(9.0, 7.0));
+        let c0 = sketch.add_point(Point2::new(12.0, 0.0));
+        let c1 = sketch.add_point(Point2::new(13.0, 8.0));
+        let d0 = sketch.add_point(Point2::new(-5.0, 0.0));
+        let d1 = sketch.add_point(Point2::new(-1.0, 1.0));
+        for (i, (start, end)) in [(a0, a1), (b0, b1), (c0, c1), (d0, d1)]
+            .into_iter()
+            .enumerate()
+        {
+            sketch.add_entity(SketchEntity::Line {
+                id: SketchEntityId(i as u32),
+                start,
+                end,
+            });
+        }
+
+        let constraints = vec![
+            Constraint::Geometric(GeometricConstraint::Parallel {
+                line1: SketchEntityId(0),
+                line2: SketchEntityId(1),
+            }),
+            Constraint::Geometric(GeometricConstraint::Perpendicular {
+                line1: SketchEntityId(0),
+                line2: SketchEntityId(2),
+            }),
+            Constraint::Dimensional(DimensionalConstraint::Angle {
+                line1: SketchEntityId(0),
+                line2: SketchEntityId(3),
+                value: std::f32::consts::FRAC_PI_4,
+            }),
+        ];
+
+        let solver = ConstraintSolver::default();
+        let result = solver.solve(&mut sketch, &constraints);
+        assert!(result.converged, "error = {}", result.final_error);
+
+        let dir = |s: SketchPointId, e: SketchPointId| {
+            let (s, e) = (
+                sketch.point(s).unwrap().position,
+                sketch.point(e).unwrap().position,
+            );
+            let d = DVec2::new((e.x - s.x) as f64, (e.y - s.y) as f64);
+            d.normalize()
+        };
+        let (da, db, dc, dd) = (dir(a0, a1), dir(b0, b1), dir(c0, c1), dir(d0, d1));
+        assert!(da.perp_dot(db).abs() < 1e-3);
+        assert!(da.dot(dc).abs() < 1e-3);
+        assert!((da.angle_to(dd) - std::f64::consts::FRAC_PI_4).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_symmetric_equal_midpoint_solve() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let axis0 = sketch.add_point(Point2::new(0.0, -5.0));
+        let axis1 = sketch.add_point(Point2::new(0.0, 5.0));
+        let left = sketch.add_point(Point2::new(-3.0, 1.0));
+        let right = sketch.add_point(Point2::new(4.0, 2.0));
+        let mid = sketch.add_point(Point2::new(0.5, 0.5));
+        let far = sketch.add_point(Point2::new(9.0, 9.0));
+        for (i, (start, end)) in [(axis0, axis1), (left, right), (right, far)]
+            .into_iter()
+            .enumerate()
+        {
+            sketch.add_entity(SketchEntity::Line {
+                id: SketchEntityId(i as u32),
+                start,
+                end,
+            });
+        }
+
+        let constraints = vec![
+            Constraint::Geometric(GeometricConstraint::Fix {
+                point: axis0,
+                position: Point2::new(0.0, -5.0),
+            }),
+            Constraint::Geometric(GeometricConstraint::Fix {
+                point: axis1,
+                position: Point2::new(0.0, 5.0),
+            }),
+            Constraint::Geometric(GeometricConstraint::Symmetric {
+                p1: left,
+                p2: right,
+                line: SketchEntityId(0),
+            }),
+            Constraint::Geometric(GeometricConstraint::Midpoint {
+                point: mid,
+                line: SketchEntityId(1),
+            }),
+            Constraint::Geometric(GeometricConstraint::Equal {
+                entity1: SketchEntityId(1),
+                entity2: SketchEntityId(2),
+            }),
+        ];
+
+        let result = ConstraintSolver::default().solve(&mut sketch, &constraints);
+        assert!(result.converged, "error = {}", result.final_error);
+
+        let at = |id: SketchPointId| sketch.point(id).unwrap().position;
+        let (l, r, m, f) = (at(left), at(right), at(mid), at(far));
+        assert!((l.x + r.x).abs() < 1e-3 && (l.y - r.y).abs() < 1e-3);
+        assert!(m.x.abs() < 1e-3 && (m.y - l.y).abs() < 1e-3);
+        assert!((l.distance(&r) - r.distance(&f)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_circle_radius_dimension_drives_radius() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let c = sketch.add_point(Point2::new(0.0, 0.0));
+        sketch.add_entity(SketchEntity::Circle {
+            id: SketchEntityId(0),
+            center: c,
+            radius: 2.0,
+        });
+
+        let constraints = vec![Constraint::Dimensional(DimensionalConstraint::Diameter {
+            entity: SketchEntityId(0),
+            value: 7.0,
+        })];
+
+        let result = ConstraintSolver::default().solve(&mut sketch, &constraints);
+        assert!(result.converged);
+        match sketch.entity(SketchEntityId(0)) {
+            Some(SketchEntity::Circle { radius, .. }) => assert!((radius - 3.5).abs() < 1e-6),
+            other => panic!("expected circle, got {:?}", other),
+        }
+    }
+
     // ═══════════════════════════════════════════════════════════════════════════════
     // DOF ANALYSIS TESTS
     // ═══════════════════════════════════════════════════════════════════════════════
@@ -545,7 +1029,87 @@ mod tests {
         let p1 = sketch.add_point(Point2::new(0.0, 0.0));
         let p2 = sketch.add_point(Point2::new(10.0, 0.0));
 
-        // 2 points = 4 DOF, 4 constraints
+        // 2 points = 4 DOF: Fix takes 2, the dimensions the other 2
+        let constraints = vec![
+            Constraint::Geometric(GeometricConstraint::Fix {
+                point: p1,
+                position: Point2::new(0.0, 0.0),
+            }),
+            Constraint::Dimensional(DimensionalConstraint::HorizontalDistance {
+                p1,
+                p2,
+                value: 10.0,
+            }),
+            Constraint::Dimensional(DimensionalConstraint::VerticalDistance { p1, p2, value: 0.0 }),
+        ];
+
+        let analysis = ConstraintAnalysis::analyze(&sketch, &constraints);
+
+        assert_eq!(analysis.total_dof, 4);
+        assert_eq!(analysis.constraint_count, 3);
+        assert_eq!(analysis.rank, 4);
+        assert_eq!(analysis.remaining_dof, 0);
+        assert_eq!(analysis.point_dof, vec![0, 0]);
+        assert_eq!(analysis.dof_status, DofStatus::FullyConstrained);
+    }
+
+    #[test]
+    fn test_dof_analysis_point_on_line_keeps_one_dof() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let a = sketch.add_point(Point2::new(0.0, 0.0));
+        let b = sketch.add_point(Point2::new(10.0, 0.0));
+        let slider = sketch.add_point(Point2::new(4.0, 0.0));
+        sketch.add_entity(SketchEntity::Line {
+            id: SketchEntityId(0),
+            start: a,
+            end: b,
+        });
+
+        let mut constraints = vec![
+            Constraint::Geometric(GeometricConstraint::Fix {
+                point: a,
+                position: Point2::new(0.0, 0.0),
+            }),
+            Constraint::Geometric(GeometricConstraint::Fix {
+                point: b,
+                position: Point2::new(10.0, 0.0),
+            }),
+            Constraint::Geometric(GeometricConstraint::PointOnLine {
+                point: slider,
+                line: SketchEntityId(0),
+            }),
+        ];
+
+        let analysis = ConstraintAnalysis::analyze(&sketch, &constraints);
+        assert_eq!(analysis.dof_of_point(a), 0);
+        assert_eq!(analysis.dof_of_point(b), 0);
+        assert_eq!(analysis.dof_of_point(slider), 1);
+        assert_eq!(analysis.dof_status, DofStatus::UnderConstrained { dof: 1 });
+
+        // Pinning the slider to the midpoint uses up its last DOF, and
+        // then re-fixing an end somewhere else cannot hold
+        constraints.push(Constraint::Geometric(GeometricConstraint::Midpoint {
+            point: slider,
+            line: SketchEntityId(0),
+        }));
+        constraints.push(Constraint::Geometric(GeometricConstraint::Fix {
+            point: b,
+            position: Point2::new(12.0, 0.0),
+        }));
+        let analysis = ConstraintAnalysis::analyze(&sketch, &constraints);
+        assert_eq!(analysis.dof_of_point(slider), 0);
+        assert_eq!(analysis.redundant, vec![ConstraintId(3)]);
+        assert_eq!(analysis.conflicting, vec![ConstraintId(4)]);
+    }
+
+    #[test]
+    fn test_dof_analysis_self_referencing_constraints_are_redundant() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let p1 = sketch.add_point(Point2::new(0.0, 0.0));
+        let p2 = sketch.add_point(Point2::new(10.0, 0.0));
+
+        // A dimension from a point to itself cannot pin it in place: its
+        // Jacobian row is zero, so it only restates 0 = 0.
         let constraints = vec![
             Constraint::Dimensional(DimensionalConstraint::HorizontalDistance {
                 p1,
@@ -555,12 +1119,12 @@ mod tests {
             Constraint::Dimensional(DimensionalConstraint::VerticalDistance { p1, p2, value: 0.0 }),
             Constraint::Dimensional(DimensionalConstraint::HorizontalDistance {
                 p1,
-                p2: p1, // Fix p1 x
+                p2: p1,
                 value: 0.0,
             }),
             Constraint::Dimensional(DimensionalConstraint::VerticalDistance {
                 p1,
-                p2: p1, // Fix p1 y
+                p2: p1,
                 value: 0.0,
             }),
         ];
@@ -569,8 +1133,14 @@ mod tests {
 
         assert_eq!(analysis.total_dof, 4);
         assert_eq!(analysis.constraint_count, 4);
-        assert_eq!(analysis.remaining_dof, 0);
-        assert_eq!(analysis.dof_status, DofStatus::FullyConstrained);
+        assert_eq!(analysis.rank, 2);
+        assert_eq!(analysis.remaining_dof, 2);
+        assert_eq!(analysis.redundant, vec![ConstraintId(2), ConstraintId(3)]);
+        assert!(analysis.conflicting.is_empty());
+        assert_eq!(
+            analysis.dof_status,
+            DofStatus::OverConstrained { redundant: 2 }
+        );
     }
 
     #[test]
@@ -579,7 +1149,7 @@ mod tests {
         let p1 = sketch.add_point(Point2::new(0.0, 0.0));
         let p2 = sketch.add_point(Point2::new(10.0, 0.0));
 
-        // 2 points = 4 DOF, 5 constraints = 1 redundant
+        // Rank 2: HorizontalDistance repeats Distance, the self-constraints say nothing
         let constraints = vec![
             Constraint::Dimensional(DimensionalConstraint::Distance {
                 p1,
@@ -604,13 +1174,149 @@ mod tests {
 
         assert_eq!(analysis.total_dof, 4);
         assert_eq!(analysis.constraint_count, 5);
-        assert_eq!(analysis.remaining_dof, -1);
+        assert_eq!(analysis.rank, 2);
+        assert_eq!(analysis.remaining_dof, 2);
+        assert_eq!(
+            analysis.redundant,
+            vec![ConstraintId(1), ConstraintId(3), ConstraintId(4)]
+        );
+        assert!(analysis.conflicting.is_empty());
+        assert_eq!(
+            analysis.dof_status,
+            DofStatus::OverConstrained { redundant: 3 }
+        );
+    }
+
+    #[test]
+    fn test_dof_analysis_names_conflicting_constraint() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let p1 = sketch.add_point(Point2::new(0.0, 0.0));
+        let p2 = sketch.add_point(Point2::new(10.0, 0.0));
+        let p3 = sketch.add_point(Point2::new(10.0, 10.0));
+
+        let constraints = vec![
+            Constraint::Dimensional(DimensionalConstraint::Distance {
+                p1,
+                p2,
+                value: 10.0,
+            }),
+            Constraint::Dimensional(DimensionalConstraint::Distance {
+                p1: p2,
+                p2: p3,
+                value: 10.0,
+            }),
+            // Same value twice is harmless, a different value cannot hold
+            Constraint::Dimensional(DimensionalConstraint::Distance {
+                p1,
+                p2,
+                value: 10.0,
+            }),
+            Constraint::Dimensional(DimensionalConstraint::Distance {
+                p1: p2,
+                p2: p3,
+                value: 20.0,
+            }),
+        ];
+
+        let analysis = ConstraintAnalysis::analyze(&sketch, &constraints);
+
+        assert_eq!(analysis.rank, 2);
+        assert_eq!(analysis.redundant, vec![ConstraintId(2)]);
+        assert_eq!(analysis.conflicting, vec![ConstraintId(3)]);
         assert_eq!(
             analysis.dof_status,
-            DofStatus::OverConstrained { redundant: 1 }
+            DofStatus::OverConstrained { redundant: 2 }
         );
     }
 
+    #[test]
+    fn test_dof_analysis_detects_dependent_geometric_constraints() {
+        // Closed rectangle: the fourth H/V constraint follows from the other three
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let p0 = sketch.add_point(Point2::new(0.0, 0.0));
+        let p1 = sketch.add_point(Point2::new(10.0, 0.0));
+        let p2 = sketch.add_point(Point2::new(10.0, 5.0));
+        let p3 = sketch.add_point(Point2::new(0.0, 5.0));
+        for (i, (start, end)) in [(p0, p1), (p1, p2), (p2, p3), (p3, p0)]
+            .into_iter()
+            .enumerate()
+        {
+            sketch.add_entity(SketchEntity::Line {
+                id: SketchEntityId(i as u32),
+                start,
+                end,
+            });
+        }
+
+        let constraints = vec![
+            Constraint::Geometric(GeometricConstraint::Horizontal {
+                line: SketchEntityId(0),
+            }),
+            Constraint::Geometric(GeometricConstraint::Vertical {
+                line: SketchEntityId(1),
+            }),
+            Constraint::Geometric(GeometricConstraint::Parallel {
+                line1: SketchEntityId(0),
+                line2: SketchEntityId(2),
+            }),
+            Constraint::Geometric(GeometricConstraint::Perpendicular {
+                line1: SketchEntityId(2),
+                line2: SketchEntityId(3),
+            }),
+        ];
+
+        let analysis = ConstraintAnalysis::analyze(&sketch, &constraints);
+
+        assert_eq!(analysis.rank, 4);
+        assert_eq!(analysis.remaining_dof, 4);
+        assert!(analysis.redundant.is_empty());
+        assert_eq!(analysis.dof_status, DofStatus::UnderConstrained { dof: 4 });
+
+        // Closing the loop with a vertical left side adds nothing new
+        let mut closed = constraints.clone();
+        closed.push(Constraint::Geometric(GeometricConstraint::Vertical {
+            line: SketchEntityId(3),
+        }));
+        let analysis = ConstraintAnalysis::analyze(&sketch, &closed);
+        assert_eq!(analysis.rank, 4);
+        assert_eq!(analysis.redundant, vec![ConstraintId(4)]);
+    }
+
+    #[test]
+    fn test_dof_analysis_point_dof() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let p1 = sketch.add_point(Point2::new(0.0, 0.0));
+        let p2 = sketch.add_point(Point2::new(3.0, 0.0));
+        let p3 = sketch.add_point(Point2::new(0.0, 4.0));
+        let lone = sketch.add_point(Point2::new(9.0, 9.0));
+
+        // Rigid triangle: only the rigid motion (2 translations + rotation) is left
+        let constraints = vec![
+            Constraint::Dimensional(DimensionalConstraint::Distance { p1, p2, value: 3.0 }),
+            Constraint::Dimensional(DimensionalConstraint::Distance {
+                p1: p2,
+                p2: p3,
+                value: 5.0,
+            }),
+            Constraint::Dimensional(DimensionalConstraint::Distance {
+                p1: p3,
+                p2: p1,
+                value: 4.0,
+            }),
+        ];
+
+        let analysis = ConstraintAnalysis::analyze(&sketch, &constraints);
+
+        assert_eq!(analysis.rank, 3);
+        assert_eq!(analysis.remaining_dof, 5);
+        assert_eq!(analysis.point_dof.len(), 4);
+        // Every vertex can still move both ways with the floating triangle
+        assert_eq!(analysis.dof_of_point(p1), 2);
+        assert_eq!(analysis.dof_of_point(p2), 2);
+        assert_eq!(analysis.dof_of_point(p3), 2);
+        assert_eq!(analysis.dof_of_point(lone), 2);
+    }
+
     #[test]
     fn test_dof_analysis_empty_sketch() {
         let sketch = Sketch::new(SketchPlane::XY);
@@ -654,6 +1360,10 @@ mod tests {
             constraint_count: 4,
             remaining_dof: 0,
             constraint_satisfied: vec![],
+            rank: 4,
+            redundant: vec![],
+            conflicting: vec![],
+            point_dof: vec![0, 0],
         };
         assert_eq!(analysis_full.status_message(), "Fully constrained");
 
@@ -663,6 +1373,10 @@ mod tests {
             constraint_count: 1,
             remaining_dof: 3,
             constraint_satisfied: vec![],
+            rank: 1,
+            redundant: vec![],
+            conflicting: vec![],
+            point_dof: vec![2, 2],
         };
         assert_eq!(
             analysis_under.status_message(),
@@ -673,8 +1387,12 @@ mod tests {
             dof_status: DofStatus::OverConstrained { redundant: 2 },
             total_dof: 4,
             constraint_count: 6,
-            remaining_dof: -2,
+            remaining_dof: 0,
             constraint_satisfied: vec![],
+            rank: 4,
+            redundant: vec![ConstraintId(4), ConstraintId(5)],
+            conflicting: vec![],
+            point_dof: vec![0, 0],
         };
         assert_eq!(
             analysis_over.status_message(),
@@ -806,7 +1524,7 @@ mod tests {
         let config = SolverConfig {
             max_iterations: 10,
             tolerance: 1e-12, // Very tight tolerance
-            damping_factor: 0.25,
+            initial_damping: 1e-3,
         };
 
         let mut sketch = Sketch::new(SketchPlane::XY);
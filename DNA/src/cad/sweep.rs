@@ -0,0 +1,647 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: sweep.rs | DNA/src/cad/sweep.rs
+//! PURPOSE: Sweep a sketch profile along a path, and loft between sketch sections
+//! MODIFIED: 2026-01-07
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ SWEEP (polyline path, mitred joints)      LOFT (ruled between sections)     │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │                                                                             │
+//! │        P2 ●━━━━━━━● P3                          ┌───────┐  section 2        │
+//! │          ╱ miter                               ╱         ╲                  │
+//! │         ╱  plane                              │  ruled    │                 │
+//! │   P0 ●━━● P1                                   ╲  faces  ╱                  │
+//! │   │                                             └─────┘  section 1          │
+//! │   profile (drawn at P0)                                                     │
+//! │                                                                             │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Sketch lines and arcs are chained into closed profiles (or an open path)
+//! and arcs are faceted, as in the primitives. The profile is carried along
+//! each straight path segment with a rotation-minimising frame, so it does
+//! not twist, and consecutive segments meet on the plane bisecting the bend.
+//!
+//! Each side face spans one profile edge between two sections. Flat faces
+//! are `SurfaceType::Planar`; twisted ones are bilinear (degree 1 × 1)
+//! `SurfaceType::Nurbs` patches, which are exact for a ruled quad.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::geometry::{Point3, Vector3};
+use super::sketch::{Sketch, JOIN_TOLERANCE};
+use super::topology::{EdgeId, FaceId, Loop, Solid, SurfaceType, VertexId};
+use glam::{DQuat, DVec3};
+
+/// Path to sweep a profile along
+#[derive(Clone, Debug)]
+pub enum SweepPath {
+    /// Open chain of lines and arcs, taken in the sketch's 3D placement
+    Sketch(Sketch),
+    /// Polyline through the given points
+    Points(Vec<Point3>),
+}
+
+/// Error type for sweep and loft operations
+#[derive(Debug, Clone)]
+pub enum SweepError {
+    /// Sketch has no closed chain of lines, arcs or a circle
+    NoProfile,
+    /// Path has fewer than two distinct points, is closed, or is not one chain
+    InvalidPath,
+    /// Profile plane contains the path direction at the start of the sweep
+    ProfileAlongPath,
+    /// Path turns back on itself at this path point
+    SharpTurn(usize),
+    /// Loft needs at least two sections
+    TooFewSections,
+    /// Consecutive loft sections lie in the same plane
+    CoincidentSections(usize),
+}
+
+/// Sweep a closed sketch profile along a path
+///
+/// The profile stays where it was drawn at the start of the path and is
+/// carried along each path segment without twisting. Curved paths are
+/// followed through their faceted points.
+pub fn sweep_sketch(profile: &Sketch, path: &SweepPath) -> Result<Solid, SweepError> {
+    let profile = profile_points(profile)?;
+    let path = path_points(path)?;
+
+    let directions: Vec<DVec3> = path.windows(2).map(|w| (w[1] - w[0]).normalize()).collect();
+
+    // Wind the profile counter-clockwise about the first path direction
+    let mut profile = profile;
+    let winding = newell(&profile).dot(directions[0]);
+    if winding.abs() < 1e-9 * newell(&profile).length().max(1.0) {
+        return Err(SweepError::ProfileAlongPath);
+    }
+    if winding < 0.0 {
+        profile.reverse();
+    }
+
+    let offsets: Vec<DVec3> = profile.iter().map(|&q| q - path[0]).collect();
+    let mut sections = vec![profile];
+    let mut rotation = DQuat::IDENTITY;
+    for joint in 1..directions.len() {
+        let (incoming, outgoing) = (directions[joint - 1], directions[joint]);
+        if incoming.dot(outgoing) < -1.0 + 1e-6 {
+            return Err(SweepError::SharpTurn(joint));
+        }
+
+        // Slide each profile line of the incoming segment onto the miter plane
+        let miter = (incoming + outgoing).normalize();
+        let section = offsets
+            .iter()
+            .map(|&r| {
+                let r = rotation * r;
+                let r = r - incoming * r.dot(incoming);
+                path[joint] + r - incoming * (r.dot(miter) / incoming.dot(miter))
+            })
+            .collect();
+        sections.push(section);
+
+        rotation = DQuat::from_rotation_arc(incoming, outgoing) * rotation;
+    }
+    let end = path[path.len() - 1];
+    sections.push(offsets.iter().map(|&r| end + rotation * r).collect());
+
+    Ok(build_solid(&sections))
+}
+
+/// Loft a solid through closed sketch profiles, in order
+///
+/// Sections are joined by ruled faces. Profiles with different vertex counts
+/// are matched by arc length, keeping every corner of every section, and
+/// each section is turned to line up with the previous one.
+pub fn loft_sketches(sections: &[Sketch]) -> Result<Solid, SweepError> {
+    if sections.len() < 2 {
+        return Err(SweepError::TooFewSections);
+    }
+    let mut profiles = sections
+        .iter()
+        .map(profile_points)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let centroids: Vec<DVec3> = profiles.iter().map(|p| centroid(p)).collect();
+    for (i, pair) in centroids.windows(2).enumerate() {
+        if (pair[1] - pair[0]).length() < JOIN_TOLERANCE as f64 {
+            return Err(SweepError::CoincidentSections(i + 1));
+        }
+    }
+
+    // Wind every section counter-clockwise about the loft direction
+    let direction = centroids[centroids.len() - 1] - centroids[0];
+    for profile in profiles.iter_mut() {
+        if newell(profile).dot(direction) < 0.0 {
+            profile.reverse();
+        }
+    }
+
+    for i in 1..profiles.len() {
+        let shift = best_alignment(&profiles[i - 1], &profiles[i]);
+        profiles[i].rotate_left(shift);
+    }
+
+    // Common arc-length parameters so every section keeps its own corners
+    let mut params: Vec<f64> = profiles.iter().flat_map(|p| arc_params(p)).collect();
+    params.sort_by(|a, b| a.total_cmp(b));
+    params.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+
+    let sections: Vec<Vec<DVec3>> = profiles
+        .iter()
+        .map(|p| params.iter().map(|&t| point_at_param(p, t)).collect())
+        .collect();
+
+    Ok(build_solid(&sections))
+}
+
+/// Stitch matching closed sections into a solid: end caps plus one side face
+/// per profile edge between consecutive sections
+///
+/// Sections are wound counter-clockwise about the direction of travel.
+fn build_solid(sections: &[Vec<DVec3>]) -> Solid {
+    let mut solid = Solid::new();
+    let n = sections[0].len();
+
+    let vertices: Vec<Vec<VertexId>> = sections
+        .iter()
+        .map(|section| {
+            section
+                .iter()
+                .map(|&p| solid.add_vertex(to_point(p)))
+                .collect()
+        })
+        .collect();
+
+    let rings: Vec<Vec<EdgeId>> = vertices
+        .iter()
+        .map(|ring| {
+            (0..n)
+                .map(|i| solid.add_edge(ring[i], ring[(i + 1) % n]))
+                .collect()
+        })
+        .collect();
+
+    let rails: Vec<Vec<EdgeId>> = vertices
+        .windows(2)
+        .map(|pair| {
+            (0..n)
+                .map(|i| solid.add_edge(pair[0][i], pair[1][i]))
+                .collect()
+        })
+        .collect();
+
+    let mut faces = Vec::new();
+
+    // Start cap faces backwards along the path, end cap forwards
+    let first = &sections[0];
+    let start = solid.add_face(planar(-newell(first)));
+    set_loop(
+        &mut solid,
+        start,
+        rings[0].iter().rev().map(|&e| (e, false)),
+    );
+    faces.push(start);
+
+    let last = sections.len() - 1;
+    let end = solid.add_face(planar(newell(&sections[last])));
+    set_loop(&mut solid, end, rings[last].iter().map(|&e| (e, true)));
+    faces.push(end);
+
+    for (k, pair) in sections.windows(2).enumerate() {
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let quad = [pair[0][i], pair[0][j], pair[1][j], pair[1][i]];
+            let face = solid.add_face(ruled_surface(quad));
+            set_loop(
+                &mut solid,
+                face,
+                [
+                    (rings[k][i], true),
+                    (rails[k][j], true),
+                    (rings[k + 1][i], false),
+                    (rails[k][i], false),
+                ],
+            );
+            faces.push(face);
+        }
+    }
+
+    let shell = solid.add_shell();
+    for &face in &faces {
+        if let Some(f) = solid.face_mut(face) {
+            f.shell = Some(shell);
+        }
+    }
+    if let Some(s) = solid.shells.iter_mut().find(|s| s.id == shell) {
+        s.faces = faces;
+        s.is_closed = true;
+    }
+
+    // Every edge is shared by exactly two faces
+    let face_lists: Vec<Vec<FaceId>> = solid
+        .edges
+        .iter()
+        .map(|e| solid.faces_of_edge(e.id))
+        .collect();
+    for (edge, faces) in solid.edges.iter_mut().zip(face_lists) {
+        edge.faces = faces;
+    }
+
+    solid
+}
+
+fn set_loop(solid: &mut Solid, face: FaceId, half_edges: impl IntoIterator<Item = (EdgeId, bool)>) {
+    if let Some(face) = solid.face_mut(face) {
+        let mut loop_ = Loop::new();
+        for (edge, forward) in half_edges {
+            loop_.add_edge(edge, forward);
+        }
+        face.outer_loop = loop_;
+    }
+}
+
+/// Planar face for a flat quad, otherwise the bilinear patch through it
+///
+/// Corners are given counter-clockwise seen from outside: two along the
+/// first section, then back along the second.
+fn ruled_surface(quad: [DVec3; 4]) -> SurfaceType {
+    let normal = newell(&quad);
+    let unit = normal.normalize_or_zero();
+    let scale = quad
+        .iter()
+        .map(|p| (*p - quad[0]).length())
+        .fold(0.0, f64::max)
+        .max(1.0);
+    let flat = quad
+        .iter()
+        .all(|p| (*p - quad[0]).dot(unit).abs() < 1e-5 * scale);
+    if flat {
+        return planar(normal);
+    }
+
+    // u runs along the profile edge, v from one section to the next
+    SurfaceType::Nurbs {
+        control_points: vec![
+            vec![to_point(quad[0]), to_point(quad[3])],
+            vec![to_point(quad[1]), to_point(quad[2])],
+        ],
+        weights: vec![vec![1.0; 2]; 2],
+        u_knots: vec![0.0, 0.0, 1.0, 1.0],
+        v_knots: vec![0.0, 0.0, 1.0, 1.0],
+        u_degree: 1,
+        v_degree: 1,
+    }
+}
+
+fn planar(normal: DVec3) -> SurfaceType {
+    let n = normal.normalize_or_zero();
+    SurfaceType::Planar {
+        normal: Vector3::new(n.x as f32, n.y as f32, n.z as f32),
+    }
+}
+
+/// Closed profile of a sketch in 3D, without the repeated closing point
+fn profile_points(sketch: &Sketch) -> Result<Vec<DVec3>, SweepError> {
+    let (mut points, closed) = sketch.chain_profile().ok_or(SweepError::NoProfile)?;
+    if !closed {
+        return Err(SweepError::NoProfile);
+    }
+    points.pop();
+    if points.len() < 3 {
+        return Err(SweepError::NoProfile);
+    }
+    Ok(points
+        .into_iter()
+        .map(|p| to_dvec(sketch.to_3d_point(p)))
+        .collect())
+}
+
+/// Distinct path points, at least two, for an open path
+fn path_points(path: &SweepPath) -> Result<Vec<DVec3>, SweepError> {
+    let points: Vec<DVec3> = match path {
+        SweepPath::Points(points) => points.iter().map(|&p| to_dvec(p)).collect(),
+        SweepPath::Sketch(sketch) => {
+            let (points, closed) = sketch.chain_profile().ok_or(SweepError::InvalidPath)?;
+            if closed {
+                return Err(SweepError::InvalidPath);
+            }
+            points
+                .into_iter()
+                .map(|p| to_dvec(sketch.to_3d_point(p)))
+                .collect()
+        }
+    };
+
+    let mut distinct: Vec<DVec3> = Vec::with_capacity(points.len());
+    for p in points {
+        if distinct
+            .last()
+            .is_none_or(|q| (p - *q).length() > JOIN_TOLERANCE as f64)
+        {
+            distinct.push(p);
+        }
+    }
+    let closed = distinct.len() > 2
+        && (distinct[0] - distinct[distinct.len() - 1]).length() < JOIN_TOLERANCE as f64;
+    if distinct.len() < 2 || closed {
+        return Err(SweepError::InvalidPath);
+    }
+    Ok(distinct)
+}
+
+/// Cyclic start offset of `next` that best lines up with `prev`
+fn best_alignment(prev: &[DVec3], next: &[DVec3]) -> usize {
+    let (c_prev, c_next) = (centroid(prev), centroid(next));
+    let params = arc_params(prev);
+    (0..next.len())
+        .min_by(|&a, &b| {
+            let cost = |shift: usize| {
+                let mut rotated = next.to_vec();
+                rotated.rotate_left(shift);
+                prev.iter()
+                    .zip(&params)
+                    .map(|(p, &t)| {
+                        ((*p - c_prev) - (point_at_param(&rotated, t) - c_next)).length()
+                    })
+                    .sum::<f64>()
+            };
+            cost(a).total_cmp(&cost(b))
+        })
+        .unwrap_or(0)
+}
+
+/// Normalised arc-length position of each vertex of a closed polygon
+fn arc_params(points: &[DVec3]) -> Vec<f64> {
+    let n = points.len();
+    let lengths: Vec<f64> = (0..n)
+        .map(|i| (points[(i + 1) % n] - points[i]).length())
+        .collect();
+    let total: f64 = lengths.iter().sum();
+    let mut t = 0.0;
+    lengths
+        .iter()
+        .map(|len| {
+            let here = t / total;
+            t += len;
+            here
+        })
+        .collect()
+}
+
+/// Point at normalised arc-length `t` around a closed polygon
+fn point_at_param(points: &[DVec3], t: f64) -> DVec3 {
+    let params = arc_params(points);
+    let n = points.len();
+    let i = params.iter().rposition(|&p| p <= t + 1e-12).unwrap_or(0);
+    let start = params[i];
+    let end = if i + 1 < n { params[i + 1] } else { 1.0 };
+    let s = if end - start > 1e-12 {
+        ((t - start) / (end - start)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    points[i].lerp(points[(i + 1) % n], s)
+}
+
+/// Polygon normal by Newell's method (unnormalized, length = 2 × area)
+fn newell(points: &[DVec3]) -> DVec3 {
+    (0..points.len())
+        .map(|i| points[i].cross(points[(i + 1) % points.len()]))
+        .sum()
+}
+
+fn centroid(points: &[DVec3]) -> DVec3 {
+    points.iter().copied().sum::<DVec3>() / points.len().max(1) as f64
+}
+
+fn to_dvec(p: Point3) -> DVec3 {
+    DVec3::new(p.x as f64, p.y as f64, p.z as f64)
+}
+
+fn to_point(p: DVec3) -> Point3 {
+    Point3::new(p.x as f32, p.y as f32, p.z as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cad::mesh::solid_to_mesh;
+    use crate::cad::sketch::{
+        Point2, SketchCoordinateFrame, SketchEntity, SketchEntityId, SketchPlane,
+    };
+
+    fn polygon(plane: SketchPlane, pts: &[(f32, f32)]) -> Sketch {
+        let mut sketch = Sketch::new(plane);
+        let ids: Vec<_> = pts
+            .iter()
+            .map(|&(x, y)| sketch.add_point(Point2::new(x, y)))
+            .collect();
+        for i in 0..ids.len() {
+            sketch.add_entity(SketchEntity::Line {
+                id: SketchEntityId(i as u32),
+                start: ids[i],
+                end: ids[(i + 1) % ids.len()],
+            });
+        }
+        sketch
+    }
+
+    fn square(plane: SketchPlane, half: f32) -> Sketch {
+        polygon(
+            plane,
+            &[(-half, -half), (half, -half), (half, half), (-half, half)],
+        )
+    }
+
+    fn plane_at(z: f32) -> SketchPlane {
+        SketchPlane::Arbitrary(
+            SketchCoordinateFrame::from_origin_normal(Point3::new(0.0, 0.0, z), Vector3::Z)
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_sweep_straight_matches_extrude() {
+        let profile = square(SketchPlane::XY, 5.0);
+        let path = SweepPath::Points(vec![Point3::ORIGIN, Point3::new(0.0, 0.0, 20.0)]);
+        let solid = sweep_sketch(&profile, &path).unwrap();
+
+        assert!(solid.is_valid());
+        assert!(solid.is_closed());
+        assert_eq!(solid.faces.len(), 6);
+        assert!((solid_to_mesh(&solid).volume() - 2000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_sweep_mitred_bend() {
+        // Square duct up 20, then across 20: centreline length 40
+        let profile = square(SketchPlane::XY, 5.0);
+        let path = SweepPath::Points(vec![
+            Point3::ORIGIN,
+            Point3::new(0.0, 0.0, 20.0),
+            Point3::new(20.0, 0.0, 20.0),
+        ]);
+        let solid = sweep_sketch(&profile, &path).unwrap();
+
+        assert!(solid.is_closed());
+        assert!((solid_to_mesh(&solid).volume() - 4000.0).abs() < 0.1);
+
+        // The joint section lies on the 45° miter plane
+        for v in &solid.vertices[4..8] {
+            assert!((v.point.x + v.point.z - 20.0).abs() < 1e-4);
+        }
+        // The end cap faces +X
+        assert!(solid.vertices[8..12]
+            .iter()
+            .all(|v| (v.point.x - 20.0).abs() < 1e-4));
+        assert!(solid
+            .faces
+            .iter()
+            .all(|f| matches!(f.surface, SurfaceType::Planar { .. })));
+    }
+
+    #[test]
+    fn test_sweep_circle_along_sketch_path() {
+        let mut profile = Sketch::new(SketchPlane::XY);
+        let c = profile.add_point(Point2::new(0.0, 0.0));
+        profile.add_entity(SketchEntity::Circle {
+            id: SketchEntityId(0),
+            center: c,
+            radius: 2.0,
+        });
+
+        // Path in the XZ sketch plane: straight up, then a quarter arc
+        let mut path = Sketch::new(SketchPlane::XZ);
+        let p0 = path.add_point(Point2::new(0.0, 0.0));
+        let p1 = path.add_point(Point2::new(0.0, 10.0));
+        let center = path.add_point(Point2::new(10.0, 10.0));
+        let p2 = path.add_point(Point2::new(10.0, 20.0));
+        path.add_entity(SketchEntity::Line {
+            id: SketchEntityId(0),
+            start: p0,
+            end: p1,
+        });
+        path.add_entity(SketchEntity::Arc {
+            id: SketchEntityId(1),
+            center,
+            start: p1,
+            end: p2,
+            radius: 10.0,
+            ccw: false,
+        });
+
+        let solid = sweep_sketch(&profile, &SweepPath::Sketch(path)).unwrap();
+        assert!(solid.is_valid());
+        assert!(solid.is_closed());
+
+        // Pappus: faceted circle area × centreline length (10 + 5π)
+        let area = 0.5 * 32.0 * 4.0 * (std::f32::consts::TAU / 32.0).sin();
+        let expected = area * (10.0 + 5.0 * std::f32::consts::PI);
+        assert!((solid_to_mesh(&solid).volume() - expected).abs() < 0.02 * expected);
+    }
+
+    #[test]
+    fn test_sweep_rejects_bad_paths() {
+        let profile = square(SketchPlane::XY, 5.0);
+        assert!(matches!(
+            sweep_sketch(&profile, &SweepPath::Points(vec![Point3::ORIGIN])),
+            Err(SweepError::InvalidPath)
+        ));
+        assert!(matches!(
+            sweep_sketch(
+                &profile,
+                &SweepPath::Points(vec![Point3::ORIGIN, Point3::new(10.0, 0.0, 0.0)])
+            ),
+            Err(SweepError::ProfileAlongPath)
+        ));
+        assert!(matches!(
+            sweep_sketch(
+                &profile,
+                &SweepPath::Points(vec![
+                    Point3::ORIGIN,
+                    Point3::new(0.0, 0.0, 10.0),
+                    Point3::new(0.0, 0.0, 5.0),
+                ])
+            ),
+            Err(SweepError::SharpTurn(1))
+        ));
+        let open = polygon(SketchPlane::XY, &[(0.0, 0.0), (1.0, 0.0)]);
+        assert!(matches!(
+            sweep_sketch(
+                &open,
+                &SweepPath::Points(vec![Point3::ORIGIN, Point3::new(0.0, 0.0, 1.0)])
+            ),
+            Err(SweepError::NoProfile)
+        ));
+    }
+
+    #[test]
+    fn test_loft_frustum() {
+        let solid =
+            loft_sketches(&[square(plane_at(0.0), 10.0), square(plane_at(10.0), 5.0)]).unwrap();
+
+        assert!(solid.is_valid());
+        assert!(solid.is_closed());
+        assert_eq!(solid.faces.len(), 6);
+
+        // Square frustum: h/3 · (A1 + A2 + √(A1·A2))
+        let expected = 10.0 / 3.0 * (400.0 + 100.0 + 200.0);
+        assert!((solid_to_mesh(&solid).volume() - expected).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_loft_square_to_circle() {
+        let mut top = Sketch::new(plane_at(20.0));
+        let c = top.add_point(Point2::new(0.0, 0.0));
+        top.add_entity(SketchEntity::Circle {
+            id: SketchEntityId(0),
+            center: c,
+            radius: 6.0,
+        });
+        let solid = loft_sketches(&[square(plane_at(0.0), 5.0), top]).unwrap();
+
+        assert!(solid.is_valid());
+        assert!(solid.is_closed());
+        // Every corner of the square survives resampling
+        for corner in [(-5.0, -5.0), (5.0, -5.0), (5.0, 5.0), (-5.0, 5.0)] {
+            assert!(solid.vertices.iter().any(|v| {
+                (v.point.x - corner.0).abs() < 1e-4 && (v.point.y - corner.1).abs() < 1e-4
+            }));
+        }
+        // Transition faces twist, so some are bilinear patches
+        assert!(solid
+            .faces
+            .iter()
+            .any(|f| matches!(f.surface, SurfaceType::Nurbs { .. })));
+
+        let v = solid_to_mesh(&solid).volume();
+        assert!(v > 20.0 * 100.0 && v < 20.0 * std::f32::consts::PI * 36.0);
+    }
+
+    #[test]
+    fn test_loft_reversed_section_winding() {
+        // Second section drawn clockwise still lofts outward
+        let top = polygon(
+            plane_at(10.0),
+            &[(-5.0, 5.0), (5.0, 5.0), (5.0, -5.0), (-5.0, -5.0)],
+        );
+        let solid = loft_sketches(&[square(plane_at(0.0), 5.0), top]).unwrap();
+        assert!((solid_to_mesh(&solid).volume() - 1000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_loft_rejects_bad_sections() {
+        assert!(matches!(
+            loft_sketches(&[square(SketchPlane::XY, 5.0)]),
+            Err(SweepError::TooFewSections)
+        ));
+        assert!(matches!(
+            loft_sketches(&[square(SketchPlane::XY, 5.0), square(SketchPlane::XY, 2.0)]),
+            Err(SweepError::CoincidentSections(1))
+        ));
+    }
+}
@@ -43,6 +43,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 use super::geometry::{BoundingBox3, Point3, Segment, Vector3, TOLERANCE};
+use super::nurbs::{NurbsCurve, NurbsSurface};
 
 /// Handle to a vertex in a B-Rep structure
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -131,74 +132,15 @@ impl CurveType {
                 let z = center.z + radius * (angle.cos() * u.z + angle.sin() * v.z);
                 Point3::new(x, y, z)
             }
-            CurveType::Nurbs {
-                control_points,
-                weights,
-                knots,
-                degree,
-            } => {
-                // De Boor's algorithm for NURBS evaluation
-                nurbs_eval(control_points, weights, knots, *degree, t)
-            }
+            // t spans the whole knot domain; malformed data falls back to the chord
+            CurveType::Nurbs { .. } => match NurbsCurve::from_curve(self) {
+                Ok(curve) => curve.point_at_normalized(t),
+                Err(_) => start.lerp(end, t),
+            },
         }
     }
 }
 
-/// Evaluate NURBS curve at parameter t using De Boor's algorithm
-fn nurbs_eval(
-    control_points: &[Point3],
-    weights: &[f32],
-    knots: &[f32],
-    degree: u32,
-    t: f32,
-) -> Point3 {
-    let n = control_points.len();
-    if n == 0 {
-        return Point3::ORIGIN;
-    }
-
-    // Find knot span
-    let mut span = degree as usize;
-    for i in (degree as usize)..n {
-        if t >= knots[i] && t < knots[i + 1] {
-            span = i;
-            break;
-        }
-    }
-
-    // De Boor's algorithm
-    let mut d: Vec<(Point3, f32)> = (0..=degree as usize)
-        .map(|j| {
-            let idx = span - degree as usize + j;
-            (control_points[idx.min(n - 1)], weights[idx.min(n - 1)])
-        })
-        .collect();
-
-    for r in 1..=degree as usize {
-        for j in (r..=degree as usize).rev() {
-            let i = span - degree as usize + j;
-            let denom = knots[i + degree as usize + 1 - r] - knots[i];
-            let alpha = if denom.abs() < TOLERANCE {
-                0.0
-            } else {
-                (t - knots[i]) / denom
-            };
-
-            let w = (1.0 - alpha) * d[j - 1].1 + alpha * d[j].1;
-            let p = if w.abs() < TOLERANCE {
-                d[j].0
-            } else {
-                let p1 = d[j - 1].0.to_vec3() * d[j - 1].1;
-                let p2 = d[j].0.to_vec3() * d[j].1;
-                Point3::from_vec3((p1 * (1.0 - alpha) + p2 * alpha) / w)
-            };
-            d[j] = (p, w);
-        }
-    }
-
-    d[degree as usize].0
-}
-
 /// Topological edge - bounded curve between two vertices
 #[derive(Clone, Debug)]
 pub struct Edge {
@@ -273,26 +215,53 @@ pub enum SurfaceType {
 
 impl SurfaceType {
     /// Get surface normal at a point (approximate for complex surfaces)
-    pub fn normal_at(&self, _point: Point3) -> Vector3 {
-        match self {
-            SurfaceType::Planar { normal } => *normal,
-            SurfaceType::Cylindrical {
+    ///
+    /// Curved surfaces return their natural normal, pointing away from the
+    /// axis or centre; faces with `FaceOrientation::Inward` face the other way.
+    pub fn normal_at(&self, point: Point3) -> Vector3 {
+        let p = point.to_vec3();
+        let n = match self {
+            SurfaceType::Planar { normal } => return *normal,
+            SurfaceType::Cylindrical { axis, center, .. } => {
+                let axis = axis.to_vec3().normalize_or_zero();
+                let d = p - center.to_vec3();
+                d - axis * d.dot(axis)
+            }
+            SurfaceType::Spherical { center, .. } => p - center.to_vec3(),
+            SurfaceType::Conical {
+                apex,
                 axis,
-                center: _,
-                radius: _,
+                half_angle,
             } => {
-                // Normal points radially outward from axis
-                *axis // Simplified - should compute radial direction
+                let axis = axis.to_vec3().normalize_or_zero();
+                let d = p - apex.to_vec3();
+                let h = d.dot(axis);
+                let radial = (d - axis * h).normalize_or_zero();
+                let (sin, cos) = half_angle.sin_cos();
+                radial * cos - axis * (sin * h.signum())
             }
-            SurfaceType::Spherical {
-                center: _,
-                radius: _,
+            SurfaceType::Toroidal {
+                center,
+                axis,
+                major_radius,
+                ..
             } => {
-                // Normal points radially outward from center
-                Vector3::Z // Simplified - should compute from point to center
+                let axis = axis.to_vec3().normalize_or_zero();
+                let d = p - center.to_vec3();
+                let ring = (d - axis * d.dot(axis)).normalize_or_zero() * *major_radius;
+                d - ring
             }
-            _ => Vector3::Z, // Placeholder for complex surfaces
-        }
+            SurfaceType::Nurbs { .. } => {
+                return match NurbsSurface::from_surface(self) {
+                    Ok(surface) => {
+                        let (u, v) = surface.closest_parameters(point);
+                        surface.normal_at(u, v)
+                    }
+                    Err(_) => Vector3::Z,
+                };
+            }
+        };
+        Vector3::from_vec3(n).normalize_or_z()
     }
 }
 
@@ -496,6 +465,113 @@ impl Solid {
         self.faces.iter_mut().find(|f| f.id == id)
     }
 
+    /// Faces whose loops use the given edge
+    ///
+    /// Derived from the loops rather than `Edge::faces`, which the primitives
+    /// leave empty.
+    pub fn faces_of_edge(&self, edge: EdgeId) -> Vec<FaceId> {
+        self.faces
+            .iter()
+            .filter(|f| {
+                f.outer_loop.edges.contains(&edge)
+                    || f.inner_loops.iter().any(|l| l.edges.contains(&edge))
+            })
+            .map(|f| f.id)
+            .collect()
+    }
+
+    /// Ordered vertices around a loop, following edge connectivity
+    ///
+    /// Loops are not guaranteed to list their edges head-to-tail (the box
+    /// primitive's bottom face, for example), so the chain is rebuilt from
+    /// shared vertices starting at the first edge in its stated direction.
+    pub fn loop_vertices(&self, loop_: &Loop) -> Vec<VertexId> {
+        let mut remaining: Vec<(VertexId, VertexId)> = loop_
+            .edges
+            .iter()
+            .zip(loop_.directions.iter())
+            .filter_map(|(&edge_id, &forward)| {
+                self.edge(edge_id).map(|e| {
+                    if forward {
+                        (e.start, e.end)
+                    } else {
+                        (e.end, e.start)
+                    }
+                })
+            })
+            .collect();
+
+        if remaining.is_empty() {
+            return Vec::new();
+        }
+
+        let (first, mut current) = remaining.remove(0);
+        let mut ordered = vec![first];
+
+        loop {
+            if current == first {
+                break;
+            }
+            ordered.push(current);
+
+            if let Some(i) = remaining.iter().position(|&(s, _)| s == current) {
+                current = remaining.remove(i).1;
+            } else if let Some(i) = remaining.iter().position(|&(_, e)| e == current) {
+                current = remaining.remove(i).0;
+            } else {
+                break;
+            }
+        }
+
+        ordered
+    }
+
+    /// Edges around a loop in head-to-tail order, with the direction each is
+    /// traversed in (true = start → end)
+    ///
+    /// Like `loop_vertices`, the chain is rebuilt from shared vertices, but
+    /// every edge is kept, so closed edges and seams used twice survive.
+    pub fn loop_half_edges(&self, loop_: &Loop) -> Vec<(EdgeId, bool)> {
+        let mut remaining: Vec<(EdgeId, bool, VertexId, VertexId)> = loop_
+            .edges
+            .iter()
+            .zip(loop_.directions.iter())
+            .filter_map(|(&edge_id, &forward)| {
+                self.edge(edge_id).map(|e| {
+                    if forward {
+                        (edge_id, true, e.start, e.end)
+                    } else {
+                        (edge_id, false, e.end, e.start)
+                    }
+                })
+            })
+            .collect();
+
+        if remaining.is_empty() {
+            return Vec::new();
+        }
+
+        let (first_edge, first_forward, _, mut current) = remaining.remove(0);
+        let mut ordered = vec![(first_edge, first_forward)];
+
+        while !remaining.is_empty() {
+            let (edge_id, forward, _, to) =
+                if let Some(i) = remaining.iter().position(|&(_, _, s, _)| s == current) {
+                    remaining.remove(i)
+                } else if let Some(i) = remaining.iter().position(|&(_, _, _, e)| e == current) {
+                    let (edge_id, forward, s, e) = remaining.remove(i);
+                    (edge_id, !forward, e, s)
+                } else {
+                    // Disconnected loop: keep the remaining edges as stated
+                    remaining.remove(0)
+                };
+            ordered.push((edge_id, forward));
+            current = to;
+        }
+
+        ordered
+    }
+
     /// Check if solid is valid (basic topology checks)
     pub fn is_valid(&self) -> bool {
         // Check all edges reference valid vertices
@@ -505,8 +581,15 @@ impl Solid {
             }
         }
 
-        // Check all face loops reference valid edges
+        // Check all face loops reference valid edges, one direction per edge
         for face in &self.faces {
+            let loops = std::iter::once(&face.outer_loop).chain(face.inner_loops.iter());
+            if loops
+                .into_iter()
+                .any(|l| l.edges.len() != l.directions.len())
+            {
+                return false;
+            }
             for edge_id in &face.outer_loop.edges {
                 if self.edge(*edge_id).is_none() {
                     return false;
@@ -521,8 +604,20 @@ impl Solid {
             }
         }
 
+        // Check all shells reference valid faces
+        for shell in &self.shells {
+            if shell.faces.iter().any(|&f| self.face(f).is_none()) {
+                return false;
+            }
+        }
+
         true
     }
+
+    /// Check the solid is watertight: every edge used by exactly two face loops
+    pub fn is_closed(&self) -> bool {
+        !self.edges.is_empty() && self.edges.iter().all(|e| e.faces.len() == 2)
+    }
 }
 
 #[cfg(test)]
@@ -596,4 +691,26 @@ mod tests {
 
         assert!(solid.is_valid());
     }
+
+    #[test]
+    fn test_loop_half_edges_keeps_seams() {
+        // Cylinder side: bottom circle, seam up, top circle back, seam down
+        let mut solid = Solid::new();
+        let v_bottom = solid.add_vertex(Point3::new(1.0, 0.0, 0.0));
+        let v_top = solid.add_vertex(Point3::new(1.0, 0.0, 1.0));
+        let bottom = solid.add_edge(v_bottom, v_bottom);
+        let top = solid.add_edge(v_top, v_top);
+        let seam = solid.add_edge(v_bottom, v_top);
+
+        let mut loop_ = Loop::new();
+        loop_.add_edge(bottom, true);
+        loop_.add_edge(top, false);
+        loop_.add_edge(seam, true);
+        loop_.add_edge(seam, true); // Wrong flag: must be traversed downwards
+
+        assert_eq!(
+            solid.loop_half_edges(&loop_),
+            vec![(bottom, true), (seam, true), (top, false), (seam, false)]
+        );
+    }
 }
@@ -502,6 +502,31 @@ pub fn generate_pll_report(design: &PLLDesign) -> Vec<u8> {
         y,
         &format!("VCO Gain (Kvco): {:.2} MHz/V", design.vco_gain_mhz_per_v),
     );
+    y -= 15.0;
+    pdf.draw_text(60.0, y, &format!("PLL IC: {}", design.pll_ic.part_number));
+    y -= 15.0;
+    pdf.draw_text(
+        60.0,
+        y,
+        &format!(
+            "VCO: {}",
+            design
+                .vco
+                .as_ref()
+                .map_or("integrated", |v| v.part_number.as_str())
+        ),
+    );
+    y -= 15.0;
+    pdf.draw_text(
+        60.0,
+        y,
+        &format!(
+            "Prescaler: {}",
+            design
+                .prescaler
+                .map_or("none".to_string(), |p| format!("{}/{}", p, p + 1))
+        ),
+    );
 
     // Footer
     pdf.set_font_size(8.0);
@@ -0,0 +1,1249 @@
+//! STEP B-rep import into the CAD kernel
+//!
+//! Maps MANIFOLD_SOLID_BREP (and BREP_WITH_VOIDS) entities from a parsed
+//! Part 21 file onto `dna::cad::Solid`:
+//!
+//! | STEP entity                         | Kernel type                       |
+//! |-------------------------------------|-----------------------------------|
+//! | MANIFOLD_SOLID_BREP / CLOSED_SHELL  | `Solid` / `Shell`                 |
+//! | BREP_WITH_VOIDS / ORIENTED_CLOSED_SHELL | Extra `Shell` per cavity      |
+//! | ADVANCED_FACE, FACE_(OUTER_)BOUND   | `Face`, `Loop`                    |
+//! | EDGE_CURVE, ORIENTED_EDGE           | `Edge`, loop direction            |
+//! | VERTEX_POINT, CARTESIAN_POINT       | `Vertex`                          |
+//! | LINE, CIRCLE, (RATIONAL_)B_SPLINE_CURVE_WITH_KNOTS | `CurveType`        |
+//! | PLANE, CYLINDRICAL_, CONICAL_, SPHERICAL_, TOROIDAL_SURFACE | `SurfaceType` |
+//! | (RATIONAL_)B_SPLINE_SURFACE_WITH_KNOTS | `SurfaceType::Nurbs`           |
+//!
+//! Lengths are converted to millimetres from the file's SI or inch/foot
+//! length unit, and angles to radians. Any other geometry makes the import fail with the list of
+//! unsupported entity types, rather than producing a partial solid.
+
+use super::entities::EntityId;
+use super::reader::{parse_step, StepFile, StepInstance, StepParseError, StepValue};
+use crate::cad::geometry::{Point3, Vector3};
+use crate::cad::topology::{
+    CurveType, EdgeId, FaceOrientation, Loop, Solid, SurfaceType, VertexId,
+};
+use glam::Vec3;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// Error type for STEP import
+#[derive(Clone, Debug)]
+pub enum StepImportError {
+    /// File is not valid Part 21
+    Parse(StepParseError),
+    /// No MANIFOLD_SOLID_BREP in the file
+    NoSolids,
+    /// Entity types the importer cannot map, sorted by name
+    Unsupported(Vec<String>),
+    /// Reference to an entity that is not in the file
+    MissingEntity(EntityId),
+    /// Entity has the wrong number or kind of attributes
+    Malformed { id: EntityId, message: String },
+}
+
+impl fmt::Display for StepImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StepImportError::Parse(e) => write!(f, "STEP parse error: {}", e),
+            StepImportError::NoSolids => write!(f, "no MANIFOLD_SOLID_BREP in file"),
+            StepImportError::Unsupported(types) => {
+                write!(f, "unsupported STEP entities: {}", types.join(", "))
+            }
+            StepImportError::MissingEntity(id) => write!(f, "missing entity {}", id),
+            StepImportError::Malformed { id, message } => write!(f, "{}: {}", id, message),
+        }
+    }
+}
+
+impl std::error::Error for StepImportError {}
+
+impl From<StepParseError> for StepImportError {
+    fn from(e: StepParseError) -> Self {
+        StepImportError::Parse(e)
+    }
+}
+
+/// Shape representations we recognise but cannot import
+const OTHER_SHAPES: [&str; 3] = [
+    "FACETED_BREP",
+    "SHELL_BASED_SURFACE_MODEL",
+    "GEOMETRIC_CURVE_SET",
+];
+
+/// Parse a STEP file and import every manifold solid B-rep
+pub fn step_to_solids(input: &str) -> Result<Vec<Solid>, StepImportError> {
+    solids_from_step(&parse_step(input)?)
+}
+
+/// Import every manifold solid B-rep from an already parsed file
+pub fn solids_from_step(file: &StepFile) -> Result<Vec<Solid>, StepImportError> {
+    let mut breps = file.instances_of("MANIFOLD_SOLID_BREP");
+    breps.extend(file.instances_of("BREP_WITH_VOIDS"));
+    breps.sort_by_key(|b| b.id);
+    if breps.is_empty() {
+        let others: Vec<String> = OTHER_SHAPES
+            .iter()
+            .filter(|name| !file.instances_of(name).is_empty())
+            .map(|name| name.to_string())
+            .collect();
+        return Err(if others.is_empty() {
+            StepImportError::NoSolids
+        } else {
+            StepImportError::Unsupported(others)
+        });
+    }
+
+    let scale = length_scale(file);
+    let angle_scale = angle_scale(file);
+    let mut unsupported = BTreeSet::new();
+    let mut solids = Vec::with_capacity(breps.len());
+
+    for brep in breps {
+        let mut importer = Importer {
+            file,
+            scale,
+            angle_scale,
+            solid: Solid::new(),
+            vertices: HashMap::new(),
+            edges: HashMap::new(),
+            unsupported: &mut unsupported,
+        };
+        let record = brep.type_name();
+        importer.import_shell(ref_param(brep, record, 1)?, false)?;
+        if record == "BREP_WITH_VOIDS" {
+            for void in ref_list(brep, record, 2)? {
+                let oriented = importer.get(void)?;
+                if oriented.record("ORIENTED_CLOSED_SHELL").is_none() {
+                    importer.unsupported(oriented);
+                    continue;
+                }
+                let shell = ref_param(oriented, "ORIENTED_CLOSED_SHELL", 2)?;
+                let orientation = bool_param(oriented, "ORIENTED_CLOSED_SHELL", 3)?;
+                importer.import_shell(shell, !orientation)?;
+            }
+        }
+        solids.push(importer.finish());
+    }
+
+    if !unsupported.is_empty() {
+        return Err(StepImportError::Unsupported(
+            unsupported.into_iter().collect(),
+        ));
+    }
+    Ok(solids)
+}
+
+/// Millimetres per file length unit (1.0 when the file declares none)
+fn length_scale(file: &StepFile) -> f32 {
+    let millimetres = |prefix: &str| match prefix {
+        "MILLI" => 1.0,
+        "CENTI" => 10.0,
+        "DECI" => 100.0,
+        "KILO" => 1.0e6,
+        "MICRO" => 1.0e-3,
+        "NANO" => 1.0e-6,
+        _ => 1000.0,
+    };
+    global_unit(file, "LENGTH_UNIT")
+        .and_then(|unit| unit_size(file, unit, &millimetres))
+        .unwrap_or(1.0) as f32
+}
+
+/// Radians per file plane angle unit (1.0 when the file declares none)
+fn angle_scale(file: &StepFile) -> f32 {
+    global_unit(file, "PLANE_ANGLE_UNIT")
+        .and_then(|unit| unit_size(file, unit, &|_| 1.0))
+        .unwrap_or(1.0) as f32
+}
+
+/// Unit of one kind (`LENGTH_UNIT`, `PLANE_ANGLE_UNIT`) the geometry uses
+///
+/// Read from the GLOBAL_UNIT_ASSIGNED_CONTEXT. Without one, any unit of the
+/// kind is taken, preferring a CONVERSION_BASED_UNIT over the SI unit it
+/// is defined from.
+fn global_unit<'a>(file: &'a StepFile, kind: &'a str) -> Option<&'a StepInstance> {
+    let assigned = file
+        .instances_of("GLOBAL_UNIT_ASSIGNED_CONTEXT")
+        .into_iter()
+        .filter_map(|ctx| {
+            ctx.record("GLOBAL_UNIT_ASSIGNED_CONTEXT")?
+                .params
+                .first()?
+                .as_list()
+        })
+        .flatten()
+        .filter_map(|unit| file.get(unit.as_ref()?))
+        .find(|unit| unit.record(kind).is_some());
+    assigned.or_else(|| {
+        let units = file.instances_of(kind);
+        units
+            .iter()
+            .find(|unit| unit.record("CONVERSION_BASED_UNIT").is_some())
+            .or(units.first())
+            .copied()
+    })
+}
+
+/// Size of `unit` in importer units, following CONVERSION_BASED_UNIT
+/// definitions down to an SI unit sized by `si` from its prefix
+fn unit_size(file: &StepFile, unit: &StepInstance, si: &dyn Fn(&str) -> f64) -> Option<f64> {
+    let mut factor = 1.0;
+    let mut unit = unit;
+    // Conversion chains are one or two deep; the bound guards against cycles
+    for _ in 0..4 {
+        if let Some(record) = unit.record("SI_UNIT") {
+            let prefix = match record.params.first() {
+                Some(StepValue::Enum(p)) => p.as_str(),
+                _ => "",
+            };
+            return Some(factor * si(prefix));
+        }
+        let conv = unit.record("CONVERSION_BASED_UNIT")?;
+        let measure = conv
+            .params
+            .get(1)
+            .and_then(StepValue::as_ref)
+            .and_then(|id| file.get(id))
+            .and_then(|m| {
+                m.records
+                    .iter()
+                    .find(|r| r.name.ends_with("MEASURE_WITH_UNIT"))
+            });
+        let base = measure.and_then(|m| {
+            let value = m.params.first()?.as_real()?;
+            Some((value, file.get(m.params.get(1)?.as_ref()?)?))
+        });
+        match base {
+            Some((value, base)) => {
+                factor *= value;
+                unit = base;
+            }
+            None => {
+                let named = match conv.params.first() {
+                    Some(StepValue::String(name)) => name.to_ascii_uppercase(),
+                    _ => return None,
+                };
+                return match named.as_str() {
+                    "INCH" => Some(factor * 25.4),
+                    "FOOT" => Some(factor * 304.8),
+                    "DEGREE" => Some(factor * std::f64::consts::PI / 180.0),
+                    _ => None,
+                };
+            }
+        }
+    }
+    None
+}
+
+fn param<'a>(
+    inst: &'a StepInstance,
+    record: &str,
+    index: usize,
+) -> Result<&'a StepValue, StepImportError> {
+    inst.record(record)
+        .and_then(|r| r.params.get(index))
+        .ok_or_else(|| StepImportError::Malformed {
+            id: inst.id,
+            message: format!("{} is missing attribute {}", record, index + 1),
+        })
+}
+
+fn malformed(inst: &StepInstance, record: &str, index: usize, kind: &str) -> StepImportError {
+    StepImportError::Malformed {
+        id: inst.id,
+        message: format!("{} attribute {} should be {}", record, index + 1, kind),
+    }
+}
+
+fn ref_param(inst: &StepInstance, record: &str, index: usize) -> Result<EntityId, StepImportError> {
+    param(inst, record, index)?
+        .as_ref()
+        .ok_or_else(|| malformed(inst, record, index, "an entity reference"))
+}
+
+fn real_param(inst: &StepInstance, record: &str, index: usize) -> Result<f64, StepImportError> {
+    param(inst, record, index)?
+        .as_real()
+        .ok_or_else(|| malformed(inst, record, index, "a number"))
+}
+
+fn bool_param(inst: &StepInstance, record: &str, index: usize) -> Result<bool, StepImportError> {
+    param(inst, record, index)?
+        .as_bool()
+        .ok_or_else(|| malformed(inst, record, index, ".T. or .F."))
+}
+
+fn list_param<'a>(
+    inst: &'a StepInstance,
+    record: &str,
+    index: usize,
+) -> Result<&'a [StepValue], StepImportError> {
+    param(inst, record, index)?
+        .as_list()
+        .ok_or_else(|| malformed(inst, record, index, "a list"))
+}
+
+fn ref_list(
+    inst: &StepInstance,
+    record: &str,
+    index: usize,
+) -> Result<Vec<EntityId>, StepImportError> {
+    list_param(inst, record, index)?
+        .iter()
+        .map(|v| {
+            v.as_ref()
+                .ok_or_else(|| malformed(inst, record, index, "a list of references"))
+        })
+        .collect()
+}
+
+/// Location and axis of an AXIS2_PLACEMENT_3D
+///
+/// The reference direction is dropped: kernel surfaces are axisymmetric and
+/// arcs use the fixed basis of `CurveType::Arc`.
+struct Placement {
+    origin: Vec3,
+    axis: Vec3,
+}
+
+struct Importer<'a> {
+    file: &'a StepFile,
+    scale: f32,
+    angle_scale: f32,
+    solid: Solid,
+    vertices: HashMap<EntityId, VertexId>,
+    edges: HashMap<EntityId, EdgeId>,
+    unsupported: &'a mut BTreeSet<String>,
+}
+
+impl<'a> Importer<'a> {
+    fn get(&self, id: EntityId) -> Result<&'a StepInstance, StepImportError> {
+        self.file.get(id).ok_or(StepImportError::MissingEntity(id))
+    }
+
+    fn unsupported(&mut self, inst: &StepInstance) {
+        self.unsupported.insert(inst.type_name().to_string());
+    }
+
+    fn finish(mut self) -> Solid {
+        let face_lists: Vec<_> = self
+            .solid
+            .edges
+            .iter()
+            .map(|e| self.solid.faces_of_edge(e.id))
+            .collect();
+        for (edge, faces) in self.solid.edges.iter_mut().zip(face_lists) {
+            edge.faces = faces;
+        }
+        self.solid
+    }
+
+    /// Import a closed shell; `flip` turns every face inside out (for cavity
+    /// shells used with orientation .F.)
+    fn import_shell(&mut self, id: EntityId, flip: bool) -> Result<(), StepImportError> {
+        let inst = self.get(id)?;
+        if inst.record("CLOSED_SHELL").is_none() {
+            self.unsupported(inst);
+            return Ok(());
+        }
+        self.preload(inst)?;
+        let shell = self.solid.add_shell();
+        if let Some(s) = self.solid.shells.iter_mut().find(|s| s.id == shell) {
+            s.is_closed = true;
+        }
+
+        for face_ref in ref_list(inst, "CLOSED_SHELL", 1)? {
+            let face = self.get(face_ref)?;
+            let record = if face.record("ADVANCED_FACE").is_some() {
+                "ADVANCED_FACE"
+            } else if face.record("FACE_SURFACE").is_some() {
+                "FACE_SURFACE"
+            } else {
+                self.unsupported(face);
+                continue;
+            };
+
+            let same_sense = bool_param(face, record, 3)? != flip;
+            let Some((surface, orientation)) =
+                self.surface(ref_param(face, record, 2)?, same_sense)?
+            else {
+                continue;
+            };
+
+            let mut outer = None;
+            let mut inner = Vec::new();
+            let mut complete = true;
+            for bound_ref in ref_list(face, record, 1)? {
+                let bound = self.get(bound_ref)?;
+                let (kind, is_outer) = if bound.record("FACE_OUTER_BOUND").is_some() {
+                    ("FACE_OUTER_BOUND", true)
+                } else if bound.record("FACE_BOUND").is_some() {
+                    ("FACE_BOUND", false)
+                } else {
+                    self.unsupported(bound);
+                    complete = false;
+                    continue;
+                };
+                let orientation = bool_param(bound, kind, 2)? != flip;
+                let Some(loop_) = self.edge_loop(ref_param(bound, kind, 1)?, orientation)? else {
+                    complete = false;
+                    continue;
+                };
+                if is_outer && outer.is_none() {
+                    outer = Some(loop_);
+                } else {
+                    inner.push(loop_);
+                }
+            }
+            if !complete {
+                // Already recorded as unsupported; the import fails after the scan
+                continue;
+            }
+            // Without a FACE_OUTER_BOUND the first bound is the outer one
+            let outer = match outer {
+                Some(l) => l,
+                None if !inner.is_empty() => inner.remove(0),
+                None => {
+                    return Err(StepImportError::Malformed {
+                        id: face.id,
+                        message: "face has no bounds".into(),
+                    })
+                }
+            };
+
+            let face_id = self.solid.add_face(surface);
+            if let Some(f) = self.solid.face_mut(face_id) {
+                f.outer_loop = outer;
+                f.inner_loops = inner;
+                f.orientation = orientation;
+                f.shell = Some(shell);
+            }
+            if let Some(s) = self.solid.shells.iter_mut().find(|s| s.id == shell) {
+                s.add_face(face_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Map vertices and edges in entity order, so a file written from a
+    /// `Solid` comes back with the same vertex and edge IDs
+    fn preload(&mut self, shell: &StepInstance) -> Result<(), StepImportError> {
+        let file = self.file;
+        let refs_at = |id: EntityId, index: usize| -> Vec<EntityId> {
+            let Some(params) = file
+                .get(id)
+                .and_then(|i| i.records.first())
+                .map(|r| &r.params)
+            else {
+                return Vec::new();
+            };
+            match params.get(index) {
+                Some(StepValue::Ref(r)) => vec![*r],
+                Some(StepValue::List(items)) => items.iter().filter_map(|v| v.as_ref()).collect(),
+                _ => Vec::new(),
+            }
+        };
+
+        let mut edge_refs = BTreeSet::new();
+        for face in ref_list(shell, "CLOSED_SHELL", 1)? {
+            for bound in refs_at(face, 1) {
+                for loop_ref in refs_at(bound, 1) {
+                    for oriented in refs_at(loop_ref, 1) {
+                        edge_refs.extend(refs_at(oriented, 3));
+                    }
+                }
+            }
+        }
+        let edge_refs: Vec<EntityId> = edge_refs
+            .into_iter()
+            .filter(|e| {
+                file.get(*e)
+                    .is_some_and(|i| i.record("EDGE_CURVE").is_some())
+            })
+            .collect();
+
+        let vertex_refs: BTreeSet<EntityId> = edge_refs
+            .iter()
+            .flat_map(|&e| [refs_at(e, 1), refs_at(e, 2)])
+            .flatten()
+            .collect();
+        for v in vertex_refs {
+            self.vertex(v)?;
+        }
+        for e in edge_refs {
+            self.edge(e)?;
+        }
+        Ok(())
+    }
+
+    fn edge_loop(
+        &mut self,
+        id: EntityId,
+        orientation: bool,
+    ) -> Result<Option<Loop>, StepImportError> {
+        let inst = self.get(id)?;
+        if inst.record("EDGE_LOOP").is_none() {
+            self.unsupported(inst);
+            return Ok(None);
+        }
+
+        let mut oriented = Vec::new();
+        for oe_ref in ref_list(inst, "EDGE_LOOP", 1)? {
+            let oe = self.get(oe_ref)?;
+            if oe.record("ORIENTED_EDGE").is_none() {
+                self.unsupported(oe);
+                return Ok(None);
+            }
+            let Some(edge) = self.edge(ref_param(oe, "ORIENTED_EDGE", 3)?)? else {
+                return Ok(None);
+            };
+            oriented.push((edge, bool_param(oe, "ORIENTED_EDGE", 4)?));
+        }
+
+        // A bound with orientation .F. runs the edge loop backwards
+        if !orientation {
+            oriented.reverse();
+            for (_, forward) in &mut oriented {
+                *forward = !*forward;
+            }
+        }
+
+        let mut loop_ = Loop::new();
+        for (edge, forward) in oriented {
+            loop_.add_edge(edge, forward);
+        }
+        Ok(Some(loop_))
+    }
+
+    fn edge(&mut self, id: EntityId) -> Result<Option<EdgeId>, StepImportError> {
+        if let Some(&edge) = self.edges.get(&id) {
+            return Ok(Some(edge));
+        }
+        let inst = self.get(id)?;
+        if inst.record("EDGE_CURVE").is_none() {
+            self.unsupported(inst);
+            return Ok(None);
+        }
+
+        let start = self.vertex(ref_param(inst, "EDGE_CURVE", 1)?)?;
+        let end = self.vertex(ref_param(inst, "EDGE_CURVE", 2)?)?;
+        let same_sense = bool_param(inst, "EDGE_CURVE", 4)?;
+        let (Some(start), Some(end)) = (start, end) else {
+            return Ok(None);
+        };
+        let Some(curve) = self.curve(ref_param(inst, "EDGE_CURVE", 3)?, start, end, same_sense)?
+        else {
+            return Ok(None);
+        };
+
+        let edge = self.solid.add_edge(start, end);
+        if let Some(e) = self.solid.edges.iter_mut().find(|e| e.id == edge) {
+            e.curve = curve;
+        }
+        self.edges.insert(id, edge);
+        Ok(Some(edge))
+    }
+
+    fn vertex(&mut self, id: EntityId) -> Result<Option<VertexId>, StepImportError> {
+        if let Some(&v) = self.vertices.get(&id) {
+            return Ok(Some(v));
+        }
+        let inst = self.get(id)?;
+        if inst.record("VERTEX_POINT").is_none() {
+            self.unsupported(inst);
+            return Ok(None);
+        }
+        let point = self.point(ref_param(inst, "VERTEX_POINT", 1)?)?;
+        let v = self.solid.add_vertex(Point3::from_vec3(point));
+        self.vertices.insert(id, v);
+        Ok(Some(v))
+    }
+
+    /// CARTESIAN_POINT in millimetres
+    fn point(&self, id: EntityId) -> Result<Vec3, StepImportError> {
+        let inst = self.get(id)?;
+        let coords: Vec<f64> = list_param(inst, "CARTESIAN_POINT", 1)?
+            .iter()
+            .filter_map(|v| v.as_real())
+            .collect();
+        match coords.as_slice() {
+            [x, y, z] => Ok(Vec3::new(*x as f32, *y as f32, *z as f32) * self.scale),
+            [x, y] => Ok(Vec3::new(*x as f32, *y as f32, 0.0) * self.scale),
+            _ => Err(malformed(inst, "CARTESIAN_POINT", 1, "2 or 3 coordinates")),
+        }
+    }
+
+    fn direction(&self, id: EntityId) -> Result<Vec3, StepImportError> {
+        let inst = self.get(id)?;
+        let ratios: Vec<f64> = list_param(inst, "DIRECTION", 1)?
+            .iter()
+            .filter_map(|v| v.as_real())
+            .collect();
+        let dir = match ratios.as_slice() {
+            [x, y, z] => Vec3::new(*x as f32, *y as f32, *z as f32),
+            [x, y] => Vec3::new(*x as f32, *y as f32, 0.0),
+            _ => return Err(malformed(inst, "DIRECTION", 1, "2 or 3 ratios")),
+        };
+        let dir = dir.normalize_or_zero();
+        if dir == Vec3::ZERO {
+            return Err(malformed(inst, "DIRECTION", 1, "a non-zero vector"));
+        }
+        Ok(dir)
+    }
+
+    fn placement(&self, id: EntityId) -> Result<Placement, StepImportError> {
+        let inst = self.get(id)?;
+        let origin = self.point(ref_param(inst, "AXIS2_PLACEMENT_3D", 1)?)?;
+        let axis = match param(inst, "AXIS2_PLACEMENT_3D", 2)?.as_ref() {
+            Some(d) => self.direction(d)?,
+            None => Vec3::Z,
+        };
+        Ok(Placement { origin, axis })
+    }
+
+    fn curve(
+        &mut self,
+        id: EntityId,
+        start: VertexId,
+        end: VertexId,
+        same_sense: bool,
+    ) -> Result<Option<CurveType>, StepImportError> {
+        let inst = self.get(id)?;
+
+        if inst.record("LINE").is_some() {
+            return Ok(Some(CurveType::Linear));
+        }
+
+        // Curves on surfaces carry their 3D curve as the first attribute
+        for wrapper in ["SURFACE_CURVE", "SEAM_CURVE"] {
+            if inst.record(wrapper).is_some() {
+                let curve_3d = ref_param(inst, wrapper, 1)?;
+                return self.curve(curve_3d, start, end, same_sense);
+            }
+        }
+
+        if inst.record("CIRCLE").is_some() {
+            let placement = self.placement(ref_param(inst, "CIRCLE", 1)?)?;
+            let radius = real_param(inst, "CIRCLE", 2)? as f32 * self.scale;
+
+            // The edge runs against the circle's sense when same_sense is .F.
+            let normal = if same_sense {
+                placement.axis
+            } else {
+                -placement.axis
+            };
+            let normal = Vector3::new(normal.x, normal.y, normal.z);
+            let (u, v) = arc_basis(normal);
+            let angle_of = |vertex: VertexId| {
+                let p = self
+                    .solid
+                    .vertex(vertex)
+                    .map(|v| v.point.to_vec3())
+                    .unwrap_or(Vec3::ZERO)
+                    - placement.origin;
+                p.dot(v).atan2(p.dot(u))
+            };
+            let start_angle = angle_of(start);
+            let mut end_angle = angle_of(end);
+            if end_angle <= start_angle + 1e-6 {
+                end_angle += std::f32::consts::TAU;
+            }
+            return Ok(Some(CurveType::Arc {
+                center: Point3::from_vec3(placement.origin),
+                radius,
+                normal,
+                start_angle,
+                end_angle,
+            }));
+        }
+
+        if inst.record("B_SPLINE_CURVE_WITH_KNOTS").is_some() {
+            // Simple instances carry the name first; the complex (rational)
+            // form splits the attributes over partial records
+            let (base, at) = if inst.is_complex() {
+                ("B_SPLINE_CURVE", 0)
+            } else {
+                ("B_SPLINE_CURVE_WITH_KNOTS", 1)
+            };
+            let degree = param(inst, base, at)?
+                .as_real()
+                .ok_or_else(|| malformed(inst, base, at, "an integer"))?
+                as u32;
+            let mut control_points = ref_list(inst, base, at + 1)?
+                .into_iter()
+                .map(|p| self.point(p).map(Point3::from_vec3))
+                .collect::<Result<Vec<_>, _>>()?;
+            let knot_at = if inst.is_complex() { 0 } else { 6 };
+            let mut knots = knot_vector(inst, "B_SPLINE_CURVE_WITH_KNOTS", knot_at)?;
+            if knots.len() != control_points.len() + degree as usize + 1 {
+                return Err(malformed(
+                    inst,
+                    "B_SPLINE_CURVE_WITH_KNOTS",
+                    knot_at + 1,
+                    "consistent with degree and control points",
+                ));
+            }
+            let mut weights = match inst.record("RATIONAL_B_SPLINE_CURVE") {
+                Some(_) => real_list(inst, "RATIONAL_B_SPLINE_CURVE", 0)?,
+                None => vec![1.0; control_points.len()],
+            };
+            if weights.len() != control_points.len() {
+                return Err(malformed(
+                    inst,
+                    "RATIONAL_B_SPLINE_CURVE",
+                    0,
+                    "one weight per control point",
+                ));
+            }
+            if !same_sense {
+                // Store the curve in the edge's direction
+                let (lo, hi) = (knots[0], knots[knots.len() - 1]);
+                knots = knots.iter().rev().map(|k| lo + hi - k).collect();
+                weights.reverse();
+                control_points.reverse();
+            }
+            return Ok(Some(CurveType::Nurbs {
+                control_points,
+                weights,
+                knots,
+                degree,
+            }));
+        }
+
+        self.unsupported(inst);
+        Ok(None)
+    }
+
+    fn surface(
+        &mut self,
+        id: EntityId,
+        same_sense: bool,
+    ) -> Result<Option<(SurfaceType, FaceOrientation)>, StepImportError> {
+        let inst = self.get(id)?;
+        let vector = |v: Vec3| Vector3::new(v.x, v.y, v.z);
+        let curved_orientation = if same_sense {
+            FaceOrientation::Outward
+        } else {
+            FaceOrientation::Inward
+        };
+
+        if inst.record("PLANE").is_some() {
+            let placement = self.placement(ref_param(inst, "PLANE", 1)?)?;
+            let normal = if same_sense {
+                placement.axis
+            } else {
+                -placement.axis
+            };
+            return Ok(Some((
+                SurfaceType::Planar {
+                    normal: vector(normal),
+                },
+                FaceOrientation::Outward,
+            )));
+        }
+
+        if inst.record("CYLINDRICAL_SURFACE").is_some() {
+            let placement = self.placement(ref_param(inst, "CYLINDRICAL_SURFACE", 1)?)?;
+            let radius = real_param(inst, "CYLINDRICAL_SURFACE", 2)? as f32 * self.scale;
+            return Ok(Some((
+                SurfaceType::Cylindrical {
+                    axis: vector(placement.axis),
+                    center: Point3::from_vec3(placement.origin),
+                    radius,
+                },
+                curved_orientation,
+            )));
+        }
+
+        if inst.record("SPHERICAL_SURFACE").is_some() {
+            let placement = self.placement(ref_param(inst, "SPHERICAL_SURFACE", 1)?)?;
+            let radius = real_param(inst, "SPHERICAL_SURFACE", 2)? as f32 * self.scale;
+            return Ok(Some((
+                SurfaceType::Spherical {
+                    center: Point3::from_vec3(placement.origin),
+                    radius,
+                },
+                curved_orientation,
+            )));
+        }
+
+        if inst.record("CONICAL_SURFACE").is_some() {
+            let placement = self.placement(ref_param(inst, "CONICAL_SURFACE", 1)?)?;
+            let radius = real_param(inst, "CONICAL_SURFACE", 2)? as f32 * self.scale;
+            let half_angle = real_param(inst, "CONICAL_SURFACE", 3)? as f32 * self.angle_scale;
+            if half_angle.tan() < 1e-6 {
+                return Err(malformed(inst, "CONICAL_SURFACE", 3, "a positive angle"));
+            }
+            // The placement sits on the circle of the given radius; the
+            // kernel stores the apex instead
+            let apex = placement.origin - placement.axis * (radius / half_angle.tan());
+            return Ok(Some((
+                SurfaceType::Conical {
+                    apex: Point3::from_vec3(apex),
+                    axis: vector(placement.axis),
+                    half_angle,
+                },
+                curved_orientation,
+            )));
+        }
+
+        if inst.record("TOROIDAL_SURFACE").is_some() {
+            let placement = self.placement(ref_param(inst, "TOROIDAL_SURFACE", 1)?)?;
+            let major_radius = real_param(inst, "TOROIDAL_SURFACE", 2)? as f32 * self.scale;
+            let minor_radius = real_param(inst, "TOROIDAL_SURFACE", 3)? as f32 * self.scale;
+            return Ok(Some((
+                SurfaceType::Toroidal {
+                    center: Point3::from_vec3(placement.origin),
+                    axis: vector(placement.axis),
+                    major_radius,
+                    minor_radius,
+                },
+                curved_orientation,
+            )));
+        }
+
+        if inst.record("B_SPLINE_SURFACE_WITH_KNOTS").is_some() {
+            let (base, at) = if inst.is_complex() {
+                ("B_SPLINE_SURFACE", 0)
+            } else {
+                ("B_SPLINE_SURFACE_WITH_KNOTS", 1)
+            };
+            let degree = |index: usize| {
+                param(inst, base, index)?
+                    .as_real()
+                    .map(|d| d as u32)
+                    .ok_or_else(|| malformed(inst, base, index, "an integer"))
+            };
+            let (u_degree, v_degree) = (degree(at)?, degree(at + 1)?);
+            let mut control_points = Vec::new();
+            for row in list_param(inst, base, at + 2)? {
+                let row = row
+                    .as_list()
+                    .ok_or_else(|| malformed(inst, base, at + 2, "a list of lists"))?;
+                let points = row
+                    .iter()
+                    .map(|p| {
+                        let id = p
+                            .as_ref()
+                            .ok_or_else(|| malformed(inst, base, at + 2, "point references"))?;
+                        self.point(id).map(Point3::from_vec3)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                control_points.push(points);
+            }
+
+            let rec = "B_SPLINE_SURFACE_WITH_KNOTS";
+            let knot_at = if inst.is_complex() { 0 } else { 8 };
+            let u_knots = knot_vector_from(inst, rec, knot_at, knot_at + 2)?;
+            let v_knots = knot_vector_from(inst, rec, knot_at + 1, knot_at + 3)?;
+            let columns = control_points.first().map_or(0, Vec::len);
+            if u_knots.len() != control_points.len() + u_degree as usize + 1
+                || v_knots.len() != columns + v_degree as usize + 1
+                || control_points.iter().any(|row| row.len() != columns)
+            {
+                return Err(malformed(
+                    inst,
+                    rec,
+                    knot_at + 2,
+                    "consistent with degrees and control points",
+                ));
+            }
+
+            let weights = match inst.record("RATIONAL_B_SPLINE_SURFACE") {
+                Some(_) => list_param(inst, "RATIONAL_B_SPLINE_SURFACE", 0)?
+                    .iter()
+                    .map(|row| {
+                        row.as_list()
+                            .map(|r| {
+                                r.iter()
+                                    .filter_map(|w| w.as_real())
+                                    .map(|w| w as f32)
+                                    .collect()
+                            })
+                            .ok_or_else(|| {
+                                malformed(inst, "RATIONAL_B_SPLINE_SURFACE", 0, "a list of lists")
+                            })
+                    })
+                    .collect::<Result<Vec<Vec<f32>>, _>>()?,
+                None => vec![vec![1.0; columns]; control_points.len()],
+            };
+            return Ok(Some((
+                SurfaceType::Nurbs {
+                    control_points,
+                    weights,
+                    u_knots,
+                    v_knots,
+                    u_degree,
+                    v_degree,
+                },
+                curved_orientation,
+            )));
+        }
+
+        self.unsupported(inst);
+        Ok(None)
+    }
+}
+
+/// Knot vector expanded from the multiplicities at `index` and the distinct
+/// values that follow them
+fn knot_vector(
+    inst: &StepInstance,
+    record: &str,
+    index: usize,
+) -> Result<Vec<f32>, StepImportError> {
+    knot_vector_from(inst, record, index, index + 1)
+}
+
+fn knot_vector_from(
+    inst: &StepInstance,
+    record: &str,
+    multiplicity_index: usize,
+    knot_index: usize,
+) -> Result<Vec<f32>, StepImportError> {
+    let multiplicities = list_param(inst, record, multiplicity_index)?;
+    let values = list_param(inst, record, knot_index)?;
+    if multiplicities.len() != values.len() {
+        return Err(malformed(
+            inst,
+            record,
+            knot_index,
+            "as long as the multiplicities",
+        ));
+    }
+    let mut knots = Vec::new();
+    for (m, k) in multiplicities.iter().zip(values) {
+        let (Some(m), Some(k)) = (m.as_real(), k.as_real()) else {
+            return Err(malformed(inst, record, knot_index, "a list of numbers"));
+        };
+        knots.extend(std::iter::repeat_n(k as f32, m as usize));
+    }
+    Ok(knots)
+}
+
+fn real_list(inst: &StepInstance, record: &str, index: usize) -> Result<Vec<f32>, StepImportError> {
+    list_param(inst, record, index)?
+        .iter()
+        .map(|v| {
+            v.as_real()
+                .map(|r| r as f32)
+                .ok_or_else(|| malformed(inst, record, index, "a list of numbers"))
+        })
+        .collect()
+}
+
+/// In-plane axes used by `CurveType::Arc` for the given normal
+fn arc_basis(normal: Vector3) -> (Vec3, Vec3) {
+    let u = if normal.z.abs() > 0.9 {
+        Vector3::X
+    } else {
+        Vector3::Z.cross(normal).normalize_or_z()
+    };
+    let v = normal.cross(u);
+    (u.to_vec3(), v.to_vec3())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cad::boolean::difference;
+    use crate::cad::mesh::solid_to_mesh;
+    use crate::cad::primitives::{make_box, make_box_at};
+    use crate::export::step::StepWriter;
+    use std::collections::HashMap;
+
+    /// Planar-only solid writer, enough to produce round-trip fixtures
+    fn write_planar_solid(solid: &Solid, writer: &mut StepWriter) {
+        let mut vps = HashMap::new();
+        for v in &solid.vertices {
+            let p = v.point;
+            let pt = writer.add_point(None, p.x as f64, p.y as f64, p.z as f64);
+            vps.insert(v.id, (pt, writer.add_vertex_point(None, pt)));
+        }
+        let mut ecs = HashMap::new();
+        for e in &solid.edges {
+            let (p0, vp0) = vps[&e.start];
+            let (_, vp1) = vps[&e.end];
+            let a = solid.vertex(e.start).unwrap().point.to_vec3();
+            let b = solid.vertex(e.end).unwrap().point.to_vec3();
+            let d = (b - a).normalize();
+            let dir = writer.add_direction(None, d.x as f64, d.y as f64, d.z as f64);
+            let vec = writer.add_vector(None, dir, (b - a).length() as f64);
+            let line = writer.add_line(None, p0, vec);
+            ecs.insert(e.id, writer.add_edge_curve(None, vp0, vp1, line, true));
+        }
+        let mut faces = Vec::new();
+        for f in &solid.faces {
+            let SurfaceType::Planar { normal } = f.surface else {
+                panic!("fixture writer only handles planes");
+            };
+            let origin = solid.vertices[0].point;
+            let o = writer.add_point(None, origin.x as f64, origin.y as f64, origin.z as f64);
+            let n = writer.add_direction(None, normal.x as f64, normal.y as f64, normal.z as f64);
+            let axis = writer.add_axis2_placement_3d(None, o, Some(n), None);
+            let plane = writer.add_plane(None, axis);
+            let bounds = std::iter::once(&f.outer_loop)
+                .chain(&f.inner_loops)
+                .map(|l| {
+                    let oes = l
+                        .edges
+                        .iter()
+                        .zip(&l.directions)
+                        .map(|(e, d)| writer.add_oriented_edge(None, ecs[e], *d))
+                        .collect();
+                    writer.add_face_bound(None, oes, true)
+                })
+                .collect();
+            faces.push(writer.add_advanced_face(None, plane, bounds));
+        }
+        let shell = writer.add_closed_shell(None, faces);
+        writer.add_manifold_solid_brep(Some("part"), shell);
+    }
+
+    fn round_trip(solid: &Solid) -> Solid {
+        let mut writer = StepWriter::new();
+        write_planar_solid(solid, &mut writer);
+        let mut solids = step_to_solids(&writer.to_string()).unwrap();
+        assert_eq!(solids.len(), 1);
+        solids.remove(0)
+    }
+
+    #[test]
+    fn test_round_trip_box() {
+        let original = make_box(30.0, 20.0, 10.0);
+        let imported = round_trip(&original);
+
+        assert!(imported.is_valid());
+        assert_eq!(imported.vertices.len(), 8);
+        assert_eq!(imported.edges.len(), 12);
+        assert_eq!(imported.faces.len(), 6);
+        assert!(imported.edges.iter().all(|e| e.faces.len() == 2));
+
+        for (a, b) in original.vertices.iter().zip(&imported.vertices) {
+            assert!(a.point.distance(b.point) < 1e-4);
+        }
+        for (a, b) in original.faces.iter().zip(&imported.faces) {
+            assert_eq!(a.outer_loop.edges, b.outer_loop.edges);
+            assert_eq!(a.outer_loop.directions, b.outer_loop.directions);
+            match (&a.surface, &b.surface) {
+                (SurfaceType::Planar { normal: n0 }, SurfaceType::Planar { normal: n1 }) => {
+                    assert!((n0.to_vec3() - n1.to_vec3()).length() < 1e-5)
+                }
+                _ => panic!("expected planar faces"),
+            }
+        }
+        assert!((solid_to_mesh(&imported).volume() - 6000.0).abs() < 0.1);
+    }
+
+    /// Inch and degree units as CAD systems write them: each conversion unit
+    /// is defined from an SI unit that is itself a LENGTH/PLANE_ANGLE_UNIT
+    const INCH_UNITS: &str = "#9001=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));
+#9002=LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(25.4),#9001);
+#9003=DIMENSIONAL_EXPONENTS(1.,0.,0.,0.,0.,0.,0.);
+#9004=(CONVERSION_BASED_UNIT('INCH',#9002)LENGTH_UNIT()NAMED_UNIT(#9003));
+#9005=(NAMED_UNIT(*)PLANE_ANGLE_UNIT()SI_UNIT($,.RADIAN.));
+#9006=PLANE_ANGLE_MEASURE_WITH_UNIT(PLANE_ANGLE_MEASURE(0.0174532925199433),#9005);
+#9007=DIMENSIONAL_EXPONENTS(0.,0.,0.,0.,0.,0.,0.);
+#9008=(CONVERSION_BASED_UNIT('DEGREE',#9006)NAMED_UNIT(#9007)PLANE_ANGLE_UNIT());
+#9009=(GEOMETRIC_REPRESENTATION_CONTEXT(3)GLOBAL_UNIT_ASSIGNED_CONTEXT((#9004,#9008))REPRESENTATION_CONTEXT('',''));
+";
+
+    #[test]
+    fn test_round_trip_inches() {
+        // 1 × 2 × 0.5 in box written in inches, imported in millimetres
+        let original = make_box(1.0, 2.0, 0.5);
+        let mut writer = StepWriter::new();
+        write_planar_solid(&original, &mut writer);
+        let text = writer.to_string();
+        let end = text.rfind("ENDSEC;").unwrap();
+        let text = format!("{}{}{}", &text[..end], INCH_UNITS, &text[end..]);
+
+        let file = parse_step(&text).unwrap();
+        assert!((length_scale(&file) - 25.4).abs() < 1e-5);
+        assert!((angle_scale(&file) - std::f32::consts::PI / 180.0).abs() < 1e-9);
+
+        let imported = solids_from_step(&file).unwrap().remove(0);
+        for (a, b) in original.vertices.iter().zip(&imported.vertices) {
+            let expected = Point3::new(a.point.x * 25.4, a.point.y * 25.4, a.point.z * 25.4);
+            assert!(expected.distance(b.point) < 1e-3);
+        }
+        let volume = 25.4_f32.powi(3);
+        assert!((solid_to_mesh(&imported).volume() - volume).abs() < 0.01 * volume);
+
+        // Without the context the conversion unit still wins over its SI base
+        let context = INCH_UNITS.lines().last().unwrap();
+        let bare = parse_step(&text.replace(context, "")).unwrap();
+        assert!((length_scale(&bare) - 25.4).abs() < 1e-5);
+        assert!((angle_scale(&bare) - std::f32::consts::PI / 180.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_round_trip_inner_loops() {
+        let block = make_box(40.0, 40.0, 10.0);
+        let hole = make_box_at(Point3::ORIGIN, 10.0, 10.0, 30.0);
+        let drilled = difference(&block, &hole).unwrap();
+        assert!(drilled.faces.iter().any(|f| !f.inner_loops.is_empty()));
+
+        let imported = round_trip(&drilled);
+        assert_eq!(imported.faces.len(), drilled.faces.len());
+        let holes: usize = imported.faces.iter().map(|f| f.inner_loops.len()).sum();
+        let expected: usize = drilled.faces.iter().map(|f| f.inner_loops.len()).sum();
+        assert_eq!(holes, expected);
+        assert!((solid_to_mesh(&imported).volume() - solid_to_mesh(&drilled).volume()).abs() < 0.5);
+    }
+
+    /// Half cylinder in metres with vendor-style complex units and derived attributes
+    const HALF_CYLINDER: &str = "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION((''),'2;1');
+FILE_NAME('half.stp','',(''),(''),'','','');
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));
+ENDSEC;
+DATA;
+#1=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT($,.METRE.));
+#10=CARTESIAN_POINT('',(0.,0.,0.));
+#11=CARTESIAN_POINT('',(0.,0.,0.002));
+#12=DIRECTION('',(0.,0.,1.));
+#13=DIRECTION('',(1.,0.,0.));
+#14=DIRECTION('',(0.,-1.,0.));
+#15=AXIS2_PLACEMENT_3D('',#10,#12,#13);
+#16=AXIS2_PLACEMENT_3D('',#11,#12,#13);
+#17=AXIS2_PLACEMENT_3D('',#10,#14,#13);
+#20=CARTESIAN_POINT('',(0.001,0.,0.));
+#21=CARTESIAN_POINT('',(-0.001,0.,0.));
+#22=CARTESIAN_POINT('',(0.001,0.,0.002));
+#23=CARTESIAN_POINT('',(-0.001,0.,0.002));
+#30=VERTEX_POINT('',#20);
+#31=VERTEX_POINT('',#21);
+#32=VERTEX_POINT('',#22);
+#33=VERTEX_POINT('',#23);
+#40=CIRCLE('',#15,0.001);
+#41=CIRCLE('',#16,0.001);
+#42=VECTOR('',#13,1.);
+#43=VECTOR('',#12,1.);
+#44=LINE('',#20,#42);
+#45=LINE('',#22,#42);
+#46=LINE('',#20,#43);
+#47=LINE('',#21,#43);
+#50=EDGE_CURVE('',#30,#31,#40,.T.);
+#51=EDGE_CURVE('',#32,#33,#41,.T.);
+#52=EDGE_CURVE('',#31,#30,#44,.T.);
+#53=EDGE_CURVE('',#33,#32,#45,.T.);
+#54=EDGE_CURVE('',#30,#32,#46,.T.);
+#55=EDGE_CURVE('',#31,#33,#47,.T.);
+#60=ORIENTED_EDGE('',*,*,#50,.T.);
+#61=ORIENTED_EDGE('',*,*,#55,.T.);
+#62=ORIENTED_EDGE('',*,*,#51,.F.);
+#63=ORIENTED_EDGE('',*,*,#54,.F.);
+#64=EDGE_LOOP('',(#60,#61,#62,#63));
+#65=FACE_OUTER_BOUND('',#64,.T.);
+#66=CYLINDRICAL_SURFACE('',#15,0.001);
+#67=ADVANCED_FACE('',(#65),#66,.T.);
+#70=ORIENTED_EDGE('',*,*,#52,.T.);
+#71=ORIENTED_EDGE('',*,*,#50,.T.);
+#72=EDGE_LOOP('',(#71,#70));
+#73=FACE_OUTER_BOUND('',#72,.F.);
+#74=PLANE('',#15);
+#75=ADVANCED_FACE('',(#73),#74,.F.);
+#80=ORIENTED_EDGE('',*,*,#51,.T.);
+#81=ORIENTED_EDGE('',*,*,#53,.T.);
+#82=EDGE_LOOP('',(#80,#81));
+#83=FACE_OUTER_BOUND('',#82,.T.);
+#84=PLANE('',#16);
+#85=ADVANCED_FACE('',(#83),#84,.T.);
+#90=ORIENTED_EDGE('',*,*,#52,.F.);
+#91=ORIENTED_EDGE('',*,*,#54,.T.);
+#92=ORIENTED_EDGE('',*,*,#53,.F.);
+#93=ORIENTED_EDGE('',*,*,#55,.F.);
+#94=EDGE_LOOP('',(#90,#91,#92,#93));
+#95=FACE_OUTER_BOUND('',#94,.T.);
+#96=PLANE('',#17);
+#97=ADVANCED_FACE('',(#95),#96,.T.);
+#100=CLOSED_SHELL('',(#67,#75,#85,#97));
+#101=MANIFOLD_SOLID_BREP('half',#100);
+ENDSEC;
+END-ISO-10303-21;
+";
+
+    #[test]
+    fn test_import_cylindrical_faces_and_units() {
+        let solids = step_to_solids(HALF_CYLINDER).unwrap();
+        let solid = &solids[0];
+
+        assert!(solid.is_valid());
+        assert_eq!(solid.vertices.len(), 4);
+        assert_eq!(solid.edges.len(), 6);
+        assert_eq!(solid.faces.len(), 4);
+        assert!(solid.edges.iter().all(|e| e.faces.len() == 2));
+
+        // Metres are converted to millimetres
+        assert!((solid.vertices[0].point.x - 1.0).abs() < 1e-5);
+        assert!((solid.vertices[2].point.z - 2.0).abs() < 1e-5);
+
+        match &solid.faces[0].surface {
+            SurfaceType::Cylindrical { axis, radius, .. } => {
+                assert!((radius - 1.0).abs() < 1e-5);
+                assert!((axis.z - 1.0).abs() < 1e-5);
+            }
+            other => panic!("expected cylinder, got {:?}", other),
+        }
+        assert_eq!(solid.faces[0].orientation, FaceOrientation::Outward);
+
+        // Bottom face: same_sense .F. flips the plane normal
+        match solid.faces[1].surface {
+            SurfaceType::Planar { normal } => assert!((normal.z + 1.0).abs() < 1e-5),
+            _ => panic!("expected plane"),
+        }
+        // Bound orientation .F. reverses the loop
+        assert_eq!(solid.faces[1].outer_loop.edges, vec![EdgeId(2), EdgeId(0)]);
+        assert_eq!(solid.faces[1].outer_loop.directions, vec![false, false]);
+
+        // Arc from +X to -X through +Y, evaluated at its midpoint
+        let arc = &solid.edges[0];
+        let mid = arc
+            .curve
+            .point_at(solid.vertices[0].point, solid.vertices[1].point, 0.5);
+        assert!(mid.distance(Point3::new(0.0, 1.0, 0.0)) < 1e-4);
+    }
+
+    #[test]
+    fn test_unsupported_entities_listed() {
+        let text = HALF_CYLINDER
+            .replace(
+                "#66=CYLINDRICAL_SURFACE('',#15,0.001);",
+                "#66=SURFACE_OF_REVOLUTION('',#40,#15);",
+            )
+            .replace(
+                "#40=CIRCLE('',#15,0.001);",
+                "#40=ELLIPSE('',#15,0.002,0.001);",
+            );
+        match step_to_solids(&text) {
+            Err(StepImportError::Unsupported(types)) => {
+                assert_eq!(types, vec!["ELLIPSE", "SURFACE_OF_REVOLUTION"]);
+            }
+            other => panic!("expected unsupported error, got {:?}", other),
+        }
+
+        let faceted = HALF_CYLINDER.replace("MANIFOLD_SOLID_BREP", "FACETED_BREP");
+        assert!(matches!(
+            step_to_solids(&faceted),
+            Err(StepImportError::Unsupported(t)) if t == vec!["FACETED_BREP".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_missing_reference() {
+        let text =
+            HALF_CYLINDER.replace("#100=CLOSED_SHELL('',(#67,", "#100=CLOSED_SHELL('',(#667,");
+        assert!(matches!(
+            step_to_solids(&text),
+            Err(StepImportError::MissingEntity(EntityId(667)))
+        ));
+        assert!(matches!(
+            step_to_solids("ISO-10303-21;\nHEADER;\nENDSEC;\nDATA;\nENDSEC;\nEND-ISO-10303-21;\n"),
+            Err(StepImportError::NoSolids)
+        ));
+    }
+}
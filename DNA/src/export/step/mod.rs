@@ -1,14 +1,17 @@
-//! STEP (ISO 10303-21) file format writer
+//! STEP (ISO 10303-21) file format writer and reader
 //!
-//! Provides export to STEP AP242 format with B-rep geometry and PMI/GD&T annotations.
+//! Provides export to STEP AP242 format with B-rep geometry and PMI/GD&T annotations,
+//! and import of manifold solid B-reps into the CAD kernel.
 //!
 //! # Architecture
 //!
 //! - `entities` - Entity ID management and StepEntity trait
 //! - `writer` - Part 21 file serialization (header + data sections)
+//! - `reader` - Part 21 tokenizer and parser (entity instance map)
+//! - `import` - Mapping of parsed B-rep entities onto `cad::Solid`
 //! - `primitives` - Geometric primitives (points, directions, axes)
 //! - `topology` - B-rep topology (vertices, edges, faces, solids)
-//! - `brep` - High-level B-rep construction helpers
+//! - `brep` - Export of `cad::Solid` as advanced B-rep (`solid_to_step`)
 //! - `product` - Product structure (context, definitions, representations)
 //! - `pmi` - PMI entities (dimensions, annotations, material specs)
 //! - `gdt` - GD&T entities (geometric tolerances, datums, FCFs)
@@ -26,11 +29,16 @@
 pub mod brep;
 pub mod entities;
 pub mod gdt;
+pub mod import;
 pub mod pmi;
 pub mod primitives;
 pub mod product;
+pub mod reader;
 pub mod topology;
 pub mod writer;
 
+pub use brep::{solid_to_step, solid_to_step_file};
 pub use entities::{EntityId, EntityIdGenerator, StepEntity};
+pub use import::{solids_from_step, step_to_solids, StepImportError};
+pub use reader::{parse_step, StepFile, StepInstance, StepParseError, StepRecord, StepValue};
 pub use writer::StepWriter;
@@ -172,3 +172,284 @@ impl StepEntity for Plane {
         vec![self.position]
     }
 }
+
+/// CIRCLE - full circle in the XY plane of an Axis2Placement3D
+pub struct Circle {
+    pub id: EntityId,
+    pub name: Option<String>,
+    pub position: EntityId, // AXIS2_PLACEMENT_3D
+    pub radius: f64,
+}
+
+impl StepEntity for Circle {
+    fn entity_name(&self) -> &'static str {
+        "CIRCLE"
+    }
+
+    fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(name) = &self.name {
+            write_step_string(name, w)?;
+        } else {
+            write!(w, "$")?;
+        }
+        write!(w, ",{},{:.6E}", self.position, self.radius)
+    }
+
+    fn references(&self) -> Vec<EntityId> {
+        vec![self.position]
+    }
+}
+
+/// Elementary surfaces placed by an Axis2Placement3D
+#[derive(Clone, Copy, Debug)]
+pub enum ElementarySurfaceKind {
+    Cylindrical {
+        radius: f64,
+    },
+    /// `radius` is measured in the placement plane, `semi_angle` in radians
+    Conical {
+        radius: f64,
+        semi_angle: f64,
+    },
+    Spherical {
+        radius: f64,
+    },
+    Toroidal {
+        major_radius: f64,
+        minor_radius: f64,
+    },
+}
+
+/// CYLINDRICAL_SURFACE, CONICAL_SURFACE, SPHERICAL_SURFACE or TOROIDAL_SURFACE
+pub struct ElementarySurface {
+    pub id: EntityId,
+    pub name: Option<String>,
+    pub position: EntityId, // AXIS2_PLACEMENT_3D
+    pub kind: ElementarySurfaceKind,
+}
+
+impl StepEntity for ElementarySurface {
+    fn entity_name(&self) -> &'static str {
+        match self.kind {
+            ElementarySurfaceKind::Cylindrical { .. } => "CYLINDRICAL_SURFACE",
+            ElementarySurfaceKind::Conical { .. } => "CONICAL_SURFACE",
+            ElementarySurfaceKind::Spherical { .. } => "SPHERICAL_SURFACE",
+            ElementarySurfaceKind::Toroidal { .. } => "TOROIDAL_SURFACE",
+        }
+    }
+
+    fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(name) = &self.name {
+            write_step_string(name, w)?;
+        } else {
+            write!(w, "$")?;
+        }
+        write!(w, ",{}", self.position)?;
+        match self.kind {
+            ElementarySurfaceKind::Cylindrical { radius }
+            | ElementarySurfaceKind::Spherical { radius } => write!(w, ",{:.6E}", radius),
+            ElementarySurfaceKind::Conical { radius, semi_angle } => {
+                write!(w, ",{:.6E},{:.6E}", radius, semi_angle)
+            }
+            ElementarySurfaceKind::Toroidal {
+                major_radius,
+                minor_radius,
+            } => write!(w, ",{:.6E},{:.6E}", major_radius, minor_radius),
+        }
+    }
+
+    fn references(&self) -> Vec<EntityId> {
+        vec![self.position]
+    }
+}
+
+/// Write `(a,b,c)` with STEP reals
+fn write_real_list(values: &[f64], w: &mut dyn Write) -> io::Result<()> {
+    write!(w, "(")?;
+    for (i, v) in values.iter().enumerate() {
+        if i > 0 {
+            write!(w, ",")?;
+        }
+        write!(w, "{:.6E}", v)?;
+    }
+    write!(w, ")")
+}
+
+/// Write `(1,2,3)` integer list
+fn write_integer_list(values: &[u32], w: &mut dyn Write) -> io::Result<()> {
+    write!(w, "(")?;
+    for (i, v) in values.iter().enumerate() {
+        if i > 0 {
+            write!(w, ",")?;
+        }
+        write!(w, "{}", v)?;
+    }
+    write!(w, ")")
+}
+
+/// Write the optional name as a REPRESENTATION_ITEM partial record
+fn write_representation_item(name: &Option<String>, w: &mut dyn Write) -> io::Result<()> {
+    write!(w, "REPRESENTATION_ITEM(")?;
+    write_step_string(name.as_deref().unwrap_or(""), w)?;
+    write!(w, ")")
+}
+
+/// B_SPLINE_CURVE_WITH_KNOTS, written as a complex rational instance when
+/// weights are given
+pub struct BSplineCurveWithKnots {
+    pub id: EntityId,
+    pub name: Option<String>,
+    pub degree: u32,
+    pub control_points: Vec<EntityId>, // CARTESIAN_POINTs
+    pub knot_multiplicities: Vec<u32>,
+    pub knots: Vec<f64>,
+    pub weights: Option<Vec<f64>>,
+}
+
+impl StepEntity for BSplineCurveWithKnots {
+    fn entity_name(&self) -> &'static str {
+        "B_SPLINE_CURVE_WITH_KNOTS"
+    }
+
+    fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(name) = &self.name {
+            write_step_string(name, w)?;
+        } else {
+            write!(w, "$")?;
+        }
+        write!(w, ",{},", self.degree)?;
+        write_entity_list(&self.control_points, w)?;
+        write!(w, ",.UNSPECIFIED.,.F.,.F.,")?;
+        write_integer_list(&self.knot_multiplicities, w)?;
+        write!(w, ",")?;
+        write_real_list(&self.knots, w)?;
+        write!(w, ",.UNSPECIFIED.")
+    }
+
+    fn write_entity(&self, id: EntityId, w: &mut dyn Write) -> io::Result<()> {
+        let Some(weights) = &self.weights else {
+            write!(w, "{} = {}(", id, self.entity_name())?;
+            self.write_attributes(w)?;
+            return writeln!(w, ");");
+        };
+
+        // Rational curves need the complex instance form (partial records in
+        // alphabetical order)
+        write!(
+            w,
+            "{} = ( BOUNDED_CURVE() B_SPLINE_CURVE({},",
+            id, self.degree
+        )?;
+        write_entity_list(&self.control_points, w)?;
+        write!(w, ",.UNSPECIFIED.,.F.,.F.) B_SPLINE_CURVE_WITH_KNOTS(")?;
+        write_integer_list(&self.knot_multiplicities, w)?;
+        write!(w, ",")?;
+        write_real_list(&self.knots, w)?;
+        write!(
+            w,
+            ",.UNSPECIFIED.) CURVE() GEOMETRIC_REPRESENTATION_ITEM() RATIONAL_B_SPLINE_CURVE("
+        )?;
+        write_real_list(weights, w)?;
+        write!(w, ") ")?;
+        write_representation_item(&self.name, w)?;
+        writeln!(w, " );")
+    }
+
+    fn references(&self) -> Vec<EntityId> {
+        self.control_points.clone()
+    }
+}
+
+/// B_SPLINE_SURFACE_WITH_KNOTS, written as a complex rational instance when
+/// weights are given
+pub struct BSplineSurfaceWithKnots {
+    pub id: EntityId,
+    pub name: Option<String>,
+    pub u_degree: u32,
+    pub v_degree: u32,
+    pub control_points: Vec<Vec<EntityId>>, // Rows of CARTESIAN_POINTs along v
+    pub u_multiplicities: Vec<u32>,
+    pub v_multiplicities: Vec<u32>,
+    pub u_knots: Vec<f64>,
+    pub v_knots: Vec<f64>,
+    pub weights: Option<Vec<Vec<f64>>>,
+}
+
+impl BSplineSurfaceWithKnots {
+    fn write_control_net(&self, w: &mut dyn Write) -> io::Result<()> {
+        write!(w, "(")?;
+        for (i, row) in self.control_points.iter().enumerate() {
+            if i > 0 {
+                write!(w, ",")?;
+            }
+            write_entity_list(row, w)?;
+        }
+        write!(w, ")")
+    }
+
+    fn write_knot_data(&self, w: &mut dyn Write) -> io::Result<()> {
+        write_integer_list(&self.u_multiplicities, w)?;
+        write!(w, ",")?;
+        write_integer_list(&self.v_multiplicities, w)?;
+        write!(w, ",")?;
+        write_real_list(&self.u_knots, w)?;
+        write!(w, ",")?;
+        write_real_list(&self.v_knots, w)?;
+        write!(w, ",.UNSPECIFIED.")
+    }
+}
+
+impl StepEntity for BSplineSurfaceWithKnots {
+    fn entity_name(&self) -> &'static str {
+        "B_SPLINE_SURFACE_WITH_KNOTS"
+    }
+
+    fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(name) = &self.name {
+            write_step_string(name, w)?;
+        } else {
+            write!(w, "$")?;
+        }
+        write!(w, ",{},{},", self.u_degree, self.v_degree)?;
+        self.write_control_net(w)?;
+        write!(w, ",.UNSPECIFIED.,.F.,.F.,.F.,")?;
+        self.write_knot_data(w)
+    }
+
+    fn write_entity(&self, id: EntityId, w: &mut dyn Write) -> io::Result<()> {
+        let Some(weights) = &self.weights else {
+            write!(w, "{} = {}(", id, self.entity_name())?;
+            self.write_attributes(w)?;
+            return writeln!(w, ");");
+        };
+
+        write!(
+            w,
+            "{} = ( BOUNDED_SURFACE() B_SPLINE_SURFACE({},{},",
+            id, self.u_degree, self.v_degree
+        )?;
+        self.write_control_net(w)?;
+        write!(
+            w,
+            ",.UNSPECIFIED.,.F.,.F.,.F.) B_SPLINE_SURFACE_WITH_KNOTS("
+        )?;
+        self.write_knot_data(w)?;
+        write!(
+            w,
+            ") GEOMETRIC_REPRESENTATION_ITEM() RATIONAL_B_SPLINE_SURFACE(("
+        )?;
+        for (i, row) in weights.iter().enumerate() {
+            if i > 0 {
+                write!(w, ",")?;
+            }
+            write_real_list(row, w)?;
+        }
+        write!(w, ")) ")?;
+        write_representation_item(&self.name, w)?;
+        writeln!(w, " SURFACE() );")
+    }
+
+    fn references(&self) -> Vec<EntityId> {
+        self.control_points.iter().flatten().copied().collect()
+    }
+}
@@ -175,11 +175,16 @@ pub struct ShapeRepresentation {
     pub name: String,
     pub items: Vec<EntityId>, // GeometricRepresentationItems (e.g., ManifoldSolidBrep)
     pub context_of_items: EntityId, // GeometricRepresentationContext
+    pub advanced_brep: bool,  // Written as ADVANCED_BREP_SHAPE_REPRESENTATION
 }
 
 impl StepEntity for ShapeRepresentation {
     fn entity_name(&self) -> &'static str {
-        "SHAPE_REPRESENTATION"
+        if self.advanced_brep {
+            "ADVANCED_BREP_SHAPE_REPRESENTATION"
+        } else {
+            "SHAPE_REPRESENTATION"
+        }
     }
 
     fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
@@ -207,16 +212,74 @@ impl StepEntity for ShapeDefinitionRepresentation {
     }
 }
 
-/// GEOMETRIC_REPRESENTATION_CONTEXT - context for geometry
+/// Named SI unit, written as a complex instance with its unit type
+pub struct SiUnit {
+    pub id: EntityId,
+    pub prefix: Option<&'static str>, // e.g. "MILLI"
+    pub name: &'static str,           // e.g. "METRE"
+    pub unit_type: &'static str,      // e.g. "LENGTH_UNIT"
+}
+
+impl StepEntity for SiUnit {
+    fn entity_name(&self) -> &'static str {
+        "SI_UNIT"
+    }
+
+    fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
+        write_step_optional(self.prefix.map(|p| format!(".{}.", p)), w)?;
+        write!(w, ",.{}.", self.name)
+    }
+
+    fn write_entity(&self, id: EntityId, w: &mut dyn Write) -> io::Result<()> {
+        let mut attrs = Vec::new();
+        self.write_attributes(&mut attrs)?;
+        // Partial records of a complex instance go in alphabetical order
+        let mut records = [
+            format!("{}()", self.unit_type),
+            "NAMED_UNIT(*)".to_string(),
+            format!("SI_UNIT({})", String::from_utf8_lossy(&attrs)),
+        ];
+        records.sort();
+        writeln!(w, "{} = ( {} );", id, records.join(" "))
+    }
+}
+
+/// UNCERTAINTY_MEASURE_WITH_UNIT - modelling tolerance of a context
+pub struct UncertaintyMeasureWithUnit {
+    pub id: EntityId,
+    pub value: f64,
+    pub unit: EntityId, // Length SiUnit
+}
+
+impl StepEntity for UncertaintyMeasureWithUnit {
+    fn entity_name(&self) -> &'static str {
+        "UNCERTAINTY_MEASURE_WITH_UNIT"
+    }
+
+    fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
+        write!(w, "LENGTH_MEASURE({:.6E}),{},", self.value, self.unit)?;
+        write_step_string("distance_accuracy_value", w)?;
+        write!(w, ",")?;
+        write_step_string("confusion accuracy", w)
+    }
+
+    fn references(&self) -> Vec<EntityId> {
+        vec![self.unit]
+    }
+}
+
+/// GEOMETRIC_REPRESENTATION_CONTEXT - 3D context with global units and uncertainty
 pub struct GeometricRepresentationContext {
     pub id: EntityId,
     pub context_identifier: String,
     pub context_type: String,
+    pub units: Vec<EntityId>,  // SiUnits (length, plane angle, solid angle)
+    pub uncertainty: EntityId, // UncertaintyMeasureWithUnit
 }
 
 impl StepEntity for GeometricRepresentationContext {
     fn entity_name(&self) -> &'static str {
-        "( GEOMETRIC_REPRESENTATION_CONTEXT(3) GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#ID)) GLOBAL_UNIT_ASSIGNED_CONTEXT((#ID,#ID,#ID)) REPRESENTATION_CONTEXT )"
+        "GEOMETRIC_REPRESENTATION_CONTEXT"
     }
 
     fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
@@ -224,6 +287,24 @@ impl StepEntity for GeometricRepresentationContext {
         write!(w, ",")?;
         write_step_string(&self.context_type, w)
     }
+
+    fn write_entity(&self, id: EntityId, w: &mut dyn Write) -> io::Result<()> {
+        write!(
+            w,
+            "{} = ( GEOMETRIC_REPRESENTATION_CONTEXT(3) GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT(({})) GLOBAL_UNIT_ASSIGNED_CONTEXT(",
+            id, self.uncertainty
+        )?;
+        write_entity_list(&self.units, w)?;
+        write!(w, ") REPRESENTATION_CONTEXT(")?;
+        self.write_attributes(w)?;
+        writeln!(w, ") );")
+    }
+
+    fn references(&self) -> Vec<EntityId> {
+        let mut refs = self.units.clone();
+        refs.push(self.uncertainty);
+        refs
+    }
 }
 
 #[cfg(test)]
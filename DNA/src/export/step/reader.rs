@@ -0,0 +1,638 @@
+//! STEP Part 21 file reader
+//!
+//! Tokenizes and parses ISO 10303-21 exchange files into a map of entity
+//! instances. This layer knows nothing about schemas; `import` maps the
+//! parsed instances onto the B-rep kernel.
+
+use super::entities::EntityId;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Parameter value of an entity instance
+#[derive(Clone, Debug, PartialEq)]
+pub enum StepValue {
+    /// Entity reference `#N`
+    Ref(EntityId),
+    Integer(i64),
+    Real(f64),
+    String(String),
+    /// Enumeration or logical, without the dots (`.T.` → `T`)
+    Enum(String),
+    /// Binary literal, kept as its hex digits
+    Binary(String),
+    List(Vec<StepValue>),
+    /// Typed parameter such as `LENGTH_MEASURE(2.5)`
+    Typed(String, Box<StepValue>),
+    /// Unset optional value `$`
+    Unset,
+    /// Value derived by the schema `*`
+    Derived,
+}
+
+impl StepValue {
+    pub fn as_ref(&self) -> Option<EntityId> {
+        match self {
+            StepValue::Ref(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// Numeric value; integers are widened since writers often drop the dot
+    pub fn as_real(&self) -> Option<f64> {
+        match self {
+            StepValue::Real(v) => Some(*v),
+            StepValue::Integer(v) => Some(*v as f64),
+            StepValue::Typed(_, inner) => inner.as_real(),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            StepValue::Enum(e) if e == "T" => Some(true),
+            StepValue::Enum(e) if e == "F" => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[StepValue]> {
+        match self {
+            StepValue::List(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// One `NAME(params)` record; complex instances hold several
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepRecord {
+    pub name: String,
+    pub params: Vec<StepValue>,
+}
+
+/// Entity instance from the DATA section
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepInstance {
+    pub id: EntityId,
+    /// Line of the `#N =` in the source file
+    pub line: usize,
+    pub records: Vec<StepRecord>,
+}
+
+impl StepInstance {
+    /// Record with the given type name, for simple or complex instances
+    pub fn record(&self, name: &str) -> Option<&StepRecord> {
+        self.records.iter().find(|r| r.name == name)
+    }
+
+    /// Type name of a simple instance, or the first partial type of a complex one
+    pub fn type_name(&self) -> &str {
+        self.records.first().map(|r| r.name.as_str()).unwrap_or("")
+    }
+
+    pub fn is_complex(&self) -> bool {
+        self.records.len() > 1
+    }
+}
+
+/// Parsed Part 21 file
+#[derive(Clone, Debug, Default)]
+pub struct StepFile {
+    /// HEADER section records (FILE_DESCRIPTION, FILE_NAME, FILE_SCHEMA)
+    pub header: Vec<StepRecord>,
+    pub entities: HashMap<EntityId, StepInstance>,
+}
+
+impl StepFile {
+    pub fn get(&self, id: EntityId) -> Option<&StepInstance> {
+        self.entities.get(&id)
+    }
+
+    /// All instances of a simple entity type, in file order
+    pub fn instances_of<'a>(&'a self, name: &'a str) -> Vec<&'a StepInstance> {
+        let mut found: Vec<&StepInstance> = self
+            .entities
+            .values()
+            .filter(|inst| inst.record(name).is_some())
+            .collect();
+        found.sort_by_key(|inst| inst.id);
+        found
+    }
+
+    /// Schema names declared in FILE_SCHEMA
+    pub fn schemas(&self) -> Vec<String> {
+        self.header
+            .iter()
+            .filter(|r| r.name == "FILE_SCHEMA")
+            .flat_map(|r| r.params.iter())
+            .filter_map(|p| p.as_list())
+            .flatten()
+            .filter_map(|v| match v {
+                StepValue::String(s) => Some(s.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Syntax error with the 1-based line where it was found
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for StepParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for StepParseError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Keyword(String),
+    Ref(u64),
+    Integer(i64),
+    Real(f64),
+    Str(String),
+    Enum(String),
+    Binary(String),
+    Dollar,
+    Star,
+    LParen,
+    RParen,
+    Comma,
+    Equals,
+    Semicolon,
+}
+
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+            line: 1,
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> StepParseError {
+        StepParseError {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), StepParseError> {
+        loop {
+            match self.chars.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('/') => {
+                    self.bump();
+                    if self.bump() != Some('*') {
+                        return Err(self.error("unexpected '/'"));
+                    }
+                    let start = self.line;
+                    let mut prev = ' ';
+                    loop {
+                        match self.bump() {
+                            Some('/') if prev == '*' => break,
+                            Some(c) => prev = c,
+                            None => {
+                                return Err(StepParseError {
+                                    line: start,
+                                    message: "unterminated comment".into(),
+                                })
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Next token with the line it started on
+    fn next_token(&mut self) -> Result<Option<(Token, usize)>, StepParseError> {
+        self.skip_trivia()?;
+        let line = self.line;
+        let Some(&c) = self.chars.peek() else {
+            return Ok(None);
+        };
+
+        let token = match c {
+            '(' | ')' | ',' | '=' | ';' | '$' | '*' => {
+                self.bump();
+                match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    ',' => Token::Comma,
+                    '=' => Token::Equals,
+                    ';' => Token::Semicolon,
+                    '$' => Token::Dollar,
+                    _ => Token::Star,
+                }
+            }
+            '#' => {
+                self.bump();
+                let digits = self.take_while(|c| c.is_ascii_digit());
+                let id = digits
+                    .parse()
+                    .map_err(|_| self.error("expected entity number after '#'"))?;
+                Token::Ref(id)
+            }
+            '\'' => {
+                self.bump();
+                let mut s = String::new();
+                loop {
+                    match self.bump() {
+                        Some('\'') => {
+                            if self.chars.peek() == Some(&'\'') {
+                                self.bump();
+                                s.push('\'');
+                            } else {
+                                break;
+                            }
+                        }
+                        Some(ch) => s.push(ch),
+                        None => {
+                            return Err(StepParseError {
+                                line,
+                                message: "unterminated string".into(),
+                            })
+                        }
+                    }
+                }
+                Token::Str(s)
+            }
+            '"' => {
+                self.bump();
+                let hex = self.take_while(|c| c.is_ascii_hexdigit());
+                if self.bump() != Some('"') {
+                    return Err(self.error("unterminated binary literal"));
+                }
+                Token::Binary(hex)
+            }
+            '.' => {
+                self.bump();
+                let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+                if name.is_empty() || self.bump() != Some('.') {
+                    return Err(self.error("malformed enumeration"));
+                }
+                Token::Enum(name.to_ascii_uppercase())
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                let text = self
+                    .take_while(|c| c.is_ascii_digit() || matches!(c, '.' | 'E' | 'e' | '-' | '+'));
+                if text.contains(['.', 'E', 'e']) {
+                    Token::Real(
+                        text.parse()
+                            .map_err(|_| self.error(format!("invalid real '{text}'")))?,
+                    )
+                } else {
+                    Token::Integer(
+                        text.parse()
+                            .map_err(|_| self.error(format!("invalid integer '{text}'")))?,
+                    )
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' || c == '!' => {
+                let name = self
+                    .take_while(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '!');
+                Token::Keyword(name.to_ascii_uppercase())
+            }
+            other => return Err(self.error(format!("unexpected character '{other}'"))),
+        };
+
+        Ok(Some((token, line)))
+    }
+
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> String {
+        let mut s = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !pred(c) {
+                break;
+            }
+            s.push(c);
+            self.bump();
+        }
+        s
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map(|(_, l)| *l)
+            .unwrap_or(1)
+    }
+
+    fn error(&self, message: impl Into<String>) -> StepParseError {
+        StepParseError {
+            line: self.line(),
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).map(|(t, _)| t.clone());
+        self.pos += 1;
+        t
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<(), StepParseError> {
+        match self.peek() {
+            Some(t) if *t == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(t) => Err(self.error(format!("expected {what}, found {t:?}"))),
+            None => Err(self.error(format!("expected {what}, found end of file"))),
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), StepParseError> {
+        match self.next() {
+            Some(Token::Keyword(k)) if k == keyword => Ok(()),
+            _ => {
+                self.pos -= 1;
+                Err(self.error(format!("expected {keyword}")))
+            }
+        }
+    }
+
+    /// Parenthesized, comma-separated parameter list
+    fn parse_params(&mut self) -> Result<Vec<StepValue>, StepParseError> {
+        self.expect(Token::LParen, "'('")?;
+        let mut params = Vec::new();
+        if self.peek() == Some(&Token::RParen) {
+            self.pos += 1;
+            return Ok(params);
+        }
+        loop {
+            params.push(self.parse_value()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => return Ok(params),
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.error("expected ',' or ')' in parameter list"));
+                }
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<StepValue, StepParseError> {
+        let value = match self.peek().cloned() {
+            Some(Token::LParen) => return self.parse_params().map(StepValue::List),
+            Some(Token::Keyword(name)) => {
+                self.pos += 1;
+                let mut inner = self.parse_params()?;
+                if inner.len() != 1 {
+                    return Err(self.error(format!("typed parameter {name} needs one value")));
+                }
+                return Ok(StepValue::Typed(name, Box::new(inner.remove(0))));
+            }
+            Some(Token::Ref(id)) => StepValue::Ref(EntityId(id)),
+            Some(Token::Integer(v)) => StepValue::Integer(v),
+            Some(Token::Real(v)) => StepValue::Real(v),
+            Some(Token::Str(s)) => StepValue::String(s),
+            Some(Token::Enum(e)) => StepValue::Enum(e),
+            Some(Token::Binary(b)) => StepValue::Binary(b),
+            Some(Token::Dollar) => StepValue::Unset,
+            Some(Token::Star) => StepValue::Derived,
+            Some(t) => return Err(self.error(format!("unexpected {t:?} in parameter list"))),
+            None => return Err(self.error("unexpected end of file in parameter list")),
+        };
+        self.pos += 1;
+        Ok(value)
+    }
+
+    fn parse_record(&mut self) -> Result<StepRecord, StepParseError> {
+        match self.next() {
+            Some(Token::Keyword(name)) => Ok(StepRecord {
+                name,
+                params: self.parse_params()?,
+            }),
+            _ => {
+                self.pos -= 1;
+                Err(self.error("expected entity type name"))
+            }
+        }
+    }
+
+    fn parse_header(&mut self) -> Result<Vec<StepRecord>, StepParseError> {
+        self.expect_keyword("HEADER")?;
+        self.expect(Token::Semicolon, "';'")?;
+        let mut records = Vec::new();
+        while !matches!(self.peek(), Some(Token::Keyword(k)) if k == "ENDSEC") {
+            records.push(self.parse_record()?);
+            self.expect(Token::Semicolon, "';' after header record")?;
+        }
+        self.pos += 1;
+        self.expect(Token::Semicolon, "';' after ENDSEC")?;
+        Ok(records)
+    }
+
+    fn parse_data(
+        &mut self,
+        entities: &mut HashMap<EntityId, StepInstance>,
+    ) -> Result<(), StepParseError> {
+        self.expect_keyword("DATA")?;
+        // DATA may carry a section name and schema list in newer editions
+        if self.peek() == Some(&Token::LParen) {
+            self.parse_params()?;
+        }
+        self.expect(Token::Semicolon, "';' after DATA")?;
+
+        loop {
+            let line = self.line();
+            match self.next() {
+                Some(Token::Keyword(k)) if k == "ENDSEC" => {
+                    return self.expect(Token::Semicolon, "';' after ENDSEC");
+                }
+                Some(Token::Ref(n)) => {
+                    self.expect(Token::Equals, "'=' after entity number")?;
+                    let records = if self.peek() == Some(&Token::LParen) {
+                        // Complex instance: (A(...) B(...) ...)
+                        self.pos += 1;
+                        let mut records = Vec::new();
+                        while self.peek() != Some(&Token::RParen) {
+                            records.push(self.parse_record()?);
+                        }
+                        self.pos += 1;
+                        records
+                    } else {
+                        vec![self.parse_record()?]
+                    };
+                    self.expect(Token::Semicolon, "';' after entity instance")?;
+
+                    let id = EntityId(n);
+                    if entities.contains_key(&id) {
+                        return Err(StepParseError {
+                            line,
+                            message: format!("duplicate entity {id}"),
+                        });
+                    }
+                    entities.insert(id, StepInstance { id, line, records });
+                }
+                Some(t) => {
+                    return Err(StepParseError {
+                        line,
+                        message: format!("expected entity instance, found {t:?}"),
+                    })
+                }
+                None => {
+                    return Err(StepParseError {
+                        line,
+                        message: "missing ENDSEC in DATA section".into(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Parse a Part 21 exchange structure
+pub fn parse_step(input: &str) -> Result<StepFile, StepParseError> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token()? {
+        tokens.push(token);
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+
+    parser.expect_keyword("ISO-10303-21")?;
+    parser.expect(Token::Semicolon, "';' after ISO-10303-21")?;
+
+    let mut file = StepFile {
+        header: parser.parse_header()?,
+        entities: HashMap::new(),
+    };
+
+    while matches!(parser.peek(), Some(Token::Keyword(k)) if k == "DATA") {
+        parser.parse_data(&mut file.entities)?;
+    }
+
+    parser.expect_keyword("END-ISO-10303-21")?;
+    parser.expect(Token::Semicolon, "';' after END-ISO-10303-21")?;
+    if parser.peek().is_some() {
+        return Err(parser.error("content after END-ISO-10303-21"));
+    }
+
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::step::StepWriter;
+
+    const SAMPLE: &str = "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('demo'),'2;1');
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));
+ENDSEC;
+DATA;
+/* a comment */
+#1 = CARTESIAN_POINT('origin',(0.,-1.5E1,2));
+#2 = ORIENTED_EDGE('',*,*,#5,.T.);
+#3 = (LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.));
+#4 = MEASURE_REPRESENTATION_ITEM('it''s',LENGTH_MEASURE(2.5),$);
+ENDSEC;
+END-ISO-10303-21;
+";
+
+    #[test]
+    fn test_parse_sample() {
+        let file = parse_step(SAMPLE).unwrap();
+        assert_eq!(file.entities.len(), 4);
+        assert_eq!(
+            file.schemas()[0],
+            "AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }"
+        );
+
+        let point = file.get(EntityId(1)).unwrap();
+        assert_eq!(point.type_name(), "CARTESIAN_POINT");
+        assert_eq!(point.line, 8);
+        let coords = point.records[0].params[1].as_list().unwrap();
+        let coords: Vec<f64> = coords.iter().filter_map(|v| v.as_real()).collect();
+        assert_eq!(coords, vec![0.0, -15.0, 2.0]);
+
+        let edge = &file.get(EntityId(2)).unwrap().records[0];
+        assert_eq!(edge.params[1], StepValue::Derived);
+        assert_eq!(edge.params[3].as_ref(), Some(EntityId(5)));
+        assert_eq!(edge.params[4].as_bool(), Some(true));
+
+        let unit = file.get(EntityId(3)).unwrap();
+        assert!(unit.is_complex());
+        assert_eq!(
+            unit.record("SI_UNIT").unwrap().params[0],
+            StepValue::Enum("MILLI".into())
+        );
+
+        let item = &file.get(EntityId(4)).unwrap().records[0];
+        assert_eq!(item.params[0], StepValue::String("it's".into()));
+        assert_eq!(item.params[1].as_real(), Some(2.5));
+        assert_eq!(item.params[2], StepValue::Unset);
+    }
+
+    #[test]
+    fn test_parse_writer_output() {
+        let mut writer = StepWriter::new();
+        let p = writer.add_point(Some("p"), 1.0, 2.0, 3.0);
+        let v = writer.add_vertex_point(None, p);
+        let d = writer.add_direction(None, 1.0, 0.0, 0.0);
+        let vec = writer.add_vector(None, d, 4.0);
+        let line = writer.add_line(None, p, vec);
+        writer.add_edge_curve(None, v, v, line, true);
+
+        let file = parse_step(&writer.to_string()).unwrap();
+        assert_eq!(file.entities.len(), 6);
+        assert_eq!(file.instances_of("EDGE_CURVE").len(), 1);
+        let magnitude = &file.get(vec).unwrap().records[0].params[2];
+        assert_eq!(magnitude.as_real(), Some(4.0));
+    }
+
+    #[test]
+    fn test_errors_report_line() {
+        let bad = SAMPLE.replace(
+            "#2 = ORIENTED_EDGE('',*,*,#5,.T.);",
+            "#2 = ORIENTED_EDGE('',*,*,#5,.T.)",
+        );
+        let err = parse_step(&bad).unwrap_err();
+        assert_eq!(err.line, 10);
+        assert!(err.message.contains("';'"));
+
+        let dup = SAMPLE.replace("#4 =", "#1 =");
+        assert!(parse_step(&dup).unwrap_err().message.contains("duplicate"));
+
+        assert!(parse_step("HEADER;").is_err());
+    }
+}
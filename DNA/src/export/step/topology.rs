@@ -52,7 +52,7 @@ impl StepEntity for EdgeCurve {
         }
         write!(
             w,
-            ",{},{},{},.{}",
+            ",{},{},{},.{}.",
             self.edge_start,
             self.edge_end,
             self.edge_geometry,
@@ -84,9 +84,10 @@ impl StepEntity for OrientedEdge {
         } else {
             write!(w, "$")?;
         }
+        // Start and end vertices are derived from the edge element
         write!(
             w,
-            ",{},.{}",
+            ",*,*,{},.{}.",
             self.edge_element,
             if self.orientation { "T" } else { "F" }
         )
@@ -97,17 +98,49 @@ impl StepEntity for OrientedEdge {
     }
 }
 
-/// FACE_BOUND - boundary of a face (outer or inner loop)
+/// EDGE_LOOP - closed chain of oriented edges
+pub struct EdgeLoop {
+    pub id: EntityId,
+    pub name: Option<String>,
+    pub edge_list: Vec<EntityId>, // List of ORIENTED_EDGEs forming the loop
+}
+
+impl StepEntity for EdgeLoop {
+    fn entity_name(&self) -> &'static str {
+        "EDGE_LOOP"
+    }
+
+    fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(name) = &self.name {
+            write_step_string(name, w)?;
+        } else {
+            write!(w, "$")?;
+        }
+        write!(w, ",")?;
+        write_entity_list(&self.edge_list, w)
+    }
+
+    fn references(&self) -> Vec<EntityId> {
+        self.edge_list.clone()
+    }
+}
+
+/// FACE_BOUND / FACE_OUTER_BOUND - boundary of a face (outer or inner loop)
 pub struct FaceBound {
     pub id: EntityId,
     pub name: Option<String>,
-    pub bound: Vec<EntityId>, // List of ORIENTED_EDGEs forming the loop
-    pub orientation: bool,    // TRUE for outer boundary, FALSE for inner
+    pub bound: EntityId,   // EDGE_LOOP
+    pub orientation: bool, // FALSE when the loop runs against the face
+    pub outer: bool,       // Written as FACE_OUTER_BOUND
 }
 
 impl StepEntity for FaceBound {
     fn entity_name(&self) -> &'static str {
-        "FACE_BOUND"
+        if self.outer {
+            "FACE_OUTER_BOUND"
+        } else {
+            "FACE_BOUND"
+        }
     }
 
     fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
@@ -116,18 +149,16 @@ impl StepEntity for FaceBound {
         } else {
             write!(w, "$")?;
         }
-        write!(w, ",(")?;
-        for (i, edge) in self.bound.iter().enumerate() {
-            write!(w, "{}", edge)?;
-            if i < self.bound.len() - 1 {
-                write!(w, ",")?;
-            }
-        }
-        write!(w, "),.{}", if self.orientation { "T" } else { "F" })
+        write!(
+            w,
+            ",{},.{}.",
+            self.bound,
+            if self.orientation { "T" } else { "F" }
+        )
     }
 
     fn references(&self) -> Vec<EntityId> {
-        self.bound.clone() // All referenced ORIENTED_EDGEs
+        vec![self.bound]
     }
 }
 
@@ -137,6 +168,7 @@ pub struct AdvancedFace {
     pub name: Option<String>,
     pub face_geometry: EntityId,    // SURFACE (e.g., PLANE)
     pub face_bounds: Vec<EntityId>, // List of FACE_BOUNDs
+    pub same_sense: bool,           // Face normal agrees with the surface normal
 }
 
 impl StepEntity for AdvancedFace {
@@ -150,14 +182,19 @@ impl StepEntity for AdvancedFace {
         } else {
             write!(w, "$")?;
         }
-        write!(w, ",{},(", self.face_geometry)?;
+        write!(w, ",(")?;
         for (i, bound) in self.face_bounds.iter().enumerate() {
             write!(w, "{}", bound)?;
             if i < self.face_bounds.len() - 1 {
                 write!(w, ",")?;
             }
         }
-        write!(w, ")")
+        write!(
+            w,
+            "),{},.{}.",
+            self.face_geometry,
+            if self.same_sense { "T" } else { "F" }
+        )
     }
 
     fn references(&self) -> Vec<EntityId> {
@@ -200,6 +237,69 @@ impl StepEntity for ClosedShell {
     }
 }
 
+/// ORIENTED_CLOSED_SHELL - a closed shell used with a given orientation
+pub struct OrientedClosedShell {
+    pub id: EntityId,
+    pub name: Option<String>,
+    pub closed_shell_element: EntityId, // CLOSED_SHELL
+    pub orientation: bool,
+}
+
+impl StepEntity for OrientedClosedShell {
+    fn entity_name(&self) -> &'static str {
+        "ORIENTED_CLOSED_SHELL"
+    }
+
+    fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(name) = &self.name {
+            write_step_string(name, w)?;
+        } else {
+            write!(w, "$")?;
+        }
+        // The face list is derived from the shell element
+        write!(
+            w,
+            ",*,{},.{}.",
+            self.closed_shell_element,
+            if self.orientation { "T" } else { "F" }
+        )
+    }
+
+    fn references(&self) -> Vec<EntityId> {
+        vec![self.closed_shell_element]
+    }
+}
+
+/// BREP_WITH_VOIDS - a manifold solid with internal cavities
+pub struct BrepWithVoids {
+    pub id: EntityId,
+    pub name: Option<String>,
+    pub outer: EntityId,      // CLOSED_SHELL
+    pub voids: Vec<EntityId>, // ORIENTED_CLOSED_SHELLs with orientation FALSE
+}
+
+impl StepEntity for BrepWithVoids {
+    fn entity_name(&self) -> &'static str {
+        "BREP_WITH_VOIDS"
+    }
+
+    fn write_attributes(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(name) = &self.name {
+            write_step_string(name, w)?;
+        } else {
+            write!(w, "$")?;
+        }
+        write!(w, ",{},", self.outer)?;
+        write_entity_list(&self.voids, w)
+    }
+
+    fn references(&self) -> Vec<EntityId> {
+        let mut refs = vec![self.outer];
+        refs.extend(self.voids.iter().copied());
+        refs
+    }
+}
+
 /// MANIFOLD_SOLID_BREP - a solid defined by an outer closed shell
 pub struct ManifoldSolidBrep {
     pub id: EntityId,
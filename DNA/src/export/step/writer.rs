@@ -3,16 +3,22 @@
 use super::entities::*;
 use super::gdt::*;
 use super::pmi::*;
-use super::primitives::{Axis2Placement3D, CartesianPoint, Direction, Line, Plane, Vector};
+use super::primitives::{
+    Axis2Placement3D, BSplineCurveWithKnots, BSplineSurfaceWithKnots, CartesianPoint, Circle,
+    Direction, ElementarySurface, ElementarySurfaceKind, Line, Plane, Vector,
+};
 use super::product::*;
 use super::topology::{
-    AdvancedFace, ClosedShell, EdgeCurve, FaceBound, ManifoldSolidBrep, OrientedEdge, VertexPoint,
+    AdvancedFace, BrepWithVoids, ClosedShell, EdgeCurve, EdgeLoop, FaceBound, ManifoldSolidBrep,
+    OrientedClosedShell, OrientedEdge, VertexPoint,
 };
 use std::io::{self, Write};
 
 pub struct StepWriter {
     id_gen: EntityIdGenerator,
     entities: Vec<(EntityId, Box<dyn StepEntity>)>,
+    description: String,
+    file_name: String,
 }
 
 impl StepWriter {
@@ -20,9 +26,17 @@ impl StepWriter {
         Self {
             id_gen: EntityIdGenerator::new(),
             entities: Vec::new(),
+            description: "AutoCrate ASTM D6039 Crate".to_string(),
+            file_name: "crate.step".to_string(),
         }
     }
 
+    /// Set the FILE_DESCRIPTION and FILE_NAME written in the header
+    pub fn set_header(&mut self, description: &str, file_name: &str) {
+        self.description = description.to_string();
+        self.file_name = file_name.to_string();
+    }
+
     /// Add a cartesian point and return its ID
     pub fn add_point(&mut self, name: Option<&str>, x: f64, y: f64, z: f64) -> EntityId {
         let id = self.id_gen.next();
@@ -110,6 +124,96 @@ impl StepWriter {
         id
     }
 
+    /// Add a circle and return its ID
+    pub fn add_circle(&mut self, name: Option<&str>, position: EntityId, radius: f64) -> EntityId {
+        let id = self.id_gen.next();
+        let circle = Circle {
+            id,
+            name: name.map(|s| s.to_string()),
+            position,
+            radius,
+        };
+        self.entities.push((id, Box::new(circle)));
+        id
+    }
+
+    /// Add a cylindrical, conical, spherical or toroidal surface and return its ID
+    pub fn add_elementary_surface(
+        &mut self,
+        name: Option<&str>,
+        position: EntityId,
+        kind: ElementarySurfaceKind,
+    ) -> EntityId {
+        let id = self.id_gen.next();
+        let surface = ElementarySurface {
+            id,
+            name: name.map(|s| s.to_string()),
+            position,
+            kind,
+        };
+        self.entities.push((id, Box::new(surface)));
+        id
+    }
+
+    /// Add a B-spline curve with knots and return its ID
+    ///
+    /// Passing `weights` makes the curve rational.
+    pub fn add_b_spline_curve(
+        &mut self,
+        name: Option<&str>,
+        degree: u32,
+        control_points: Vec<EntityId>,
+        knot_multiplicities: Vec<u32>,
+        knots: Vec<f64>,
+        weights: Option<Vec<f64>>,
+    ) -> EntityId {
+        let id = self.id_gen.next();
+        let curve = BSplineCurveWithKnots {
+            id,
+            name: name.map(|s| s.to_string()),
+            degree,
+            control_points,
+            knot_multiplicities,
+            knots,
+            weights,
+        };
+        self.entities.push((id, Box::new(curve)));
+        id
+    }
+
+    /// Add a B-spline surface with knots and return its ID
+    ///
+    /// Passing `weights` makes the surface rational.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_b_spline_surface(
+        &mut self,
+        name: Option<&str>,
+        u_degree: u32,
+        v_degree: u32,
+        control_points: Vec<Vec<EntityId>>,
+        u_multiplicities: Vec<u32>,
+        v_multiplicities: Vec<u32>,
+        u_knots: Vec<f64>,
+        v_knots: Vec<f64>,
+        weights: Option<Vec<Vec<f64>>>,
+    ) -> EntityId {
+        let id = self.id_gen.next();
+        let surface = BSplineSurfaceWithKnots {
+            id,
+            name: name.map(|s| s.to_string()),
+            u_degree,
+            v_degree,
+            control_points,
+            u_multiplicities,
+            v_multiplicities,
+            u_knots,
+            v_knots,
+            weights,
+        };
+        self.entities.push((id, Box::new(surface)));
+        id
+    }
+
     /// Add a vertex point and return its ID
     pub fn add_vertex_point(&mut self, name: Option<&str>, vertex_geometry: EntityId) -> EntityId {
         let id = self.id_gen.next();
@@ -162,19 +266,55 @@ impl StepWriter {
         id
     }
 
-    /// Add a face bound and return its ID
+    /// Add an edge loop and return its ID
+    pub fn add_edge_loop(&mut self, name: Option<&str>, edge_list: Vec<EntityId>) -> EntityId {
+        let id = self.id_gen.next();
+        let edge_loop = EdgeLoop {
+            id,
+            name: name.map(|s| s.to_string()),
+            edge_list,
+        };
+        self.entities.push((id, Box::new(edge_loop)));
+        id
+    }
+
+    /// Add a face bound around the given oriented edges and return its ID
+    ///
+    /// The edges are wrapped in an EDGE_LOOP, which is emitted first.
     pub fn add_face_bound(
         &mut self,
         name: Option<&str>,
-        bound: Vec<EntityId>,
+        oriented_edges: Vec<EntityId>,
         orientation: bool,
     ) -> EntityId {
+        self.push_face_bound(name, oriented_edges, orientation, false)
+    }
+
+    /// Add a face outer bound around the given oriented edges and return its ID
+    pub fn add_face_outer_bound(
+        &mut self,
+        name: Option<&str>,
+        oriented_edges: Vec<EntityId>,
+        orientation: bool,
+    ) -> EntityId {
+        self.push_face_bound(name, oriented_edges, orientation, true)
+    }
+
+    fn push_face_bound(
+        &mut self,
+        name: Option<&str>,
+        oriented_edges: Vec<EntityId>,
+        orientation: bool,
+        outer: bool,
+    ) -> EntityId {
+        let bound = self.add_edge_loop(None, oriented_edges);
         let id = self.id_gen.next();
         let face_bound = FaceBound {
             id,
             name: name.map(|s| s.to_string()),
             bound,
             orientation,
+            outer,
         };
         self.entities.push((id, Box::new(face_bound)));
         id
@@ -186,6 +326,17 @@ impl StepWriter {
         name: Option<&str>,
         face_geometry: EntityId,
         face_bounds: Vec<EntityId>,
+    ) -> EntityId {
+        self.add_advanced_face_with_sense(name, face_geometry, face_bounds, true)
+    }
+
+    /// Add an advanced face whose normal may run against the surface normal
+    pub fn add_advanced_face_with_sense(
+        &mut self,
+        name: Option<&str>,
+        face_geometry: EntityId,
+        face_bounds: Vec<EntityId>,
+        same_sense: bool,
     ) -> EntityId {
         let id = self.id_gen.next();
         let advanced_face = AdvancedFace {
@@ -193,6 +344,7 @@ impl StepWriter {
             name: name.map(|s| s.to_string()),
             face_geometry,
             face_bounds,
+            same_sense,
         };
         self.entities.push((id, Box::new(advanced_face)));
         id
@@ -222,6 +374,42 @@ impl StepWriter {
         id
     }
 
+    /// Add an oriented closed shell and return its ID
+    pub fn add_oriented_closed_shell(
+        &mut self,
+        name: Option<&str>,
+        closed_shell_element: EntityId,
+        orientation: bool,
+    ) -> EntityId {
+        let id = self.id_gen.next();
+        let shell = OrientedClosedShell {
+            id,
+            name: name.map(|s| s.to_string()),
+            closed_shell_element,
+            orientation,
+        };
+        self.entities.push((id, Box::new(shell)));
+        id
+    }
+
+    /// Add a B-rep with voids and return its ID
+    pub fn add_brep_with_voids(
+        &mut self,
+        name: Option<&str>,
+        outer: EntityId,
+        voids: Vec<EntityId>,
+    ) -> EntityId {
+        let id = self.id_gen.next();
+        let brep = BrepWithVoids {
+            id,
+            name: name.map(|s| s.to_string()),
+            outer,
+            voids,
+        };
+        self.entities.push((id, Box::new(brep)));
+        id
+    }
+
     // ===== Product Structure Helpers =====
 
     /// Add an APPLICATION_CONTEXT
@@ -353,6 +541,26 @@ impl StepWriter {
         name: &str,
         items: Vec<EntityId>,
         context_of_items: EntityId,
+    ) -> EntityId {
+        self.push_shape_representation(name, items, context_of_items, false)
+    }
+
+    /// Add an ADVANCED_BREP_SHAPE_REPRESENTATION
+    pub fn add_advanced_brep_shape_representation(
+        &mut self,
+        name: &str,
+        items: Vec<EntityId>,
+        context_of_items: EntityId,
+    ) -> EntityId {
+        self.push_shape_representation(name, items, context_of_items, true)
+    }
+
+    fn push_shape_representation(
+        &mut self,
+        name: &str,
+        items: Vec<EntityId>,
+        context_of_items: EntityId,
+        advanced_brep: bool,
     ) -> EntityId {
         let id = self.id_gen.next();
         let rep = ShapeRepresentation {
@@ -360,6 +568,7 @@ impl StepWriter {
             name: name.to_string(),
             items,
             context_of_items,
+            advanced_brep,
         };
         self.entities.push((id, Box::new(rep)));
         id
@@ -381,17 +590,51 @@ impl StepWriter {
         id
     }
 
+    /// Add an SI_UNIT of the given unit type (e.g. "LENGTH_UNIT")
+    pub fn add_si_unit(
+        &mut self,
+        prefix: Option<&'static str>,
+        name: &'static str,
+        unit_type: &'static str,
+    ) -> EntityId {
+        let id = self.id_gen.next();
+        let unit = SiUnit {
+            id,
+            prefix,
+            name,
+            unit_type,
+        };
+        self.entities.push((id, Box::new(unit)));
+        id
+    }
+
     /// Add a GEOMETRIC_REPRESENTATION_CONTEXT
+    ///
+    /// The context is 3D, in millimetres and radians, with a 1e-6 mm
+    /// distance uncertainty.
     pub fn add_geometric_representation_context(
         &mut self,
         context_identifier: &str,
         context_type: &str,
     ) -> EntityId {
+        let mm = self.add_si_unit(Some("MILLI"), "METRE", "LENGTH_UNIT");
+        let rad = self.add_si_unit(None, "RADIAN", "PLANE_ANGLE_UNIT");
+        let sr = self.add_si_unit(None, "STERADIAN", "SOLID_ANGLE_UNIT");
+        let uncertainty = self.id_gen.next();
+        let measure = UncertaintyMeasureWithUnit {
+            id: uncertainty,
+            value: 1.0e-6,
+            unit: mm,
+        };
+        self.entities.push((uncertainty, Box::new(measure)));
+
         let id = self.id_gen.next();
         let ctx = GeometricRepresentationContext {
             id,
             context_identifier: context_identifier.to_string(),
             context_type: context_type.to_string(),
+            units: vec![mm, rad, sr],
+            uncertainty,
         };
         self.entities.push((id, Box::new(ctx)));
         id
@@ -662,11 +905,15 @@ impl StepWriter {
         // Header
         writeln!(writer, "ISO-10303-21;")?;
         writeln!(writer, "HEADER;")?;
+        write!(writer, "FILE_DESCRIPTION((")?;
+        write_step_string(&self.description, &mut writer)?;
+        writeln!(writer, "),'2;1');")?;
+        write!(writer, "FILE_NAME(")?;
+        write_step_string(&self.file_name, &mut writer)?;
         writeln!(
             writer,
-            "FILE_DESCRIPTION(('AutoCrate ASTM D6039 Crate'),'2;1');"
+            ",'2025-12-02T00:00:00',('AutoCrate'),('Antimony Labs'),'','','');"
         )?;
-        writeln!(writer, "FILE_NAME('crate.step','2025-12-02T00:00:00',('AutoCrate'),('Antimony Labs'),'','','');")?;
         writeln!(
             writer,
             "FILE_SCHEMA(('AP242_MANAGED_MODEL_BASED_3D_ENGINEERING_MIM_LF'));"
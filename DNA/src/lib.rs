@@ -98,10 +98,11 @@ pub use pll::*;
 /// Power supply design (Buck, Boost, LDO)
 pub mod power;
 pub use power::{
-    design_boost, design_buck, design_ldo, quick_boost, quick_buck, quick_ldo, recommend_topology,
-    BoostDesign, BoostRequirements, BuckDesign, BuckRequirements, DesignPriority, DesignWarning,
-    EfficiencyBreakdown, LDODesign, LDORequirements, OperatingMode, RippleSpec, SelectedComponent,
-    ThermalAnalysis, TopologyRecommendation, TopologyType, VoltageRange,
+    design_boost, design_buck, design_ldo, quick_boost, quick_buck, quick_ldo,
+    recommend_isolated_topology, recommend_topology, BoostDesign, BoostRequirements, BuckDesign,
+    BuckRequirements, DesignPriority, DesignWarning, EfficiencyBreakdown, LDODesign,
+    LDORequirements, OperatingMode, RippleSpec, SelectedComponent, ThermalAnalysis,
+    TopologyRecommendation, TopologyType, VoltageRange,
 };
 
 /// SPICE circuit simulation engine
@@ -13,8 +13,9 @@
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
 //! │ DATA FLOW                                                                   │
 //! ├─────────────────────────────────────────────────────────────────────────────┤
-//! │ CONSUMES:  f32 (ranges, probabilities), usize (indices)                     │
-//! │ PRODUCES:  f32 (random values), Vec2 (random positions/directions), bool    │
+//! │ CONSUMES:  f32 (ranges, probabilities), usize (indices), caller's Rng       │
+//! │ PRODUCES:  f32 (random values), Vec2 (random positions/directions), bool,   │
+//! │            f64 uniform / Gaussian samples (seedable, for Monte Carlo)       │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
 //! DEPENDS ON:
@@ -25,6 +26,7 @@
 //!   • DNA/src/lib.rs      → Boid spawning, mutations
 //!   • WELCOME             → Particle effects
 //!   • All simulations     → Random initialization
+//!   • physics/electromagnetics/lumped/sweep.rs → Tolerance Monte Carlo
 //!
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -114,6 +116,21 @@ pub fn random_index(max: usize) -> usize {
     rand::thread_rng().gen_range(0..max)
 }
 
+/// Sample uniformly from [min, max) using the caller's generator
+#[inline]
+pub fn sample_uniform<R: Rng + ?Sized>(rng: &mut R, min: f64, max: f64) -> f64 {
+    min + (max - min) * rng.gen::<f64>()
+}
+
+/// Sample a Gaussian (Box-Muller) using the caller's generator
+pub fn sample_gaussian<R: Rng + ?Sized>(rng: &mut R, mean: f64, std_dev: f64) -> f64 {
+    // 1 - gen() lies in (0, 1], keeping ln finite
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+    mean + std_dev * z
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,4 +210,22 @@ mod tests {
             "50% chance should hit roughly half"
         );
     }
+
+    #[test]
+    fn test_sample_gaussian_moments() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let samples: Vec<f64> = (0..20_000)
+            .map(|_| sample_gaussian(&mut rng, 3.0, 2.0))
+            .collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+        assert!((mean - 3.0).abs() < 0.05, "mean = {}", mean);
+        assert!((var.sqrt() - 2.0).abs() < 0.05, "std = {}", var.sqrt());
+
+        for _ in 0..100 {
+            let u = sample_uniform(&mut rng, -1.0, 1.0);
+            assert!((-1.0..1.0).contains(&u));
+        }
+    }
 }
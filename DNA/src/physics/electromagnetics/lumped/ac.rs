@@ -23,6 +23,8 @@
 //! │   Inductor:  Y = 1/(jωL) = -j/(ωL)                                          │
 //! │                                                                             │
 //! │ Frequency sweep: Logarithmic spacing for Bode plots                         │
+//! │ One sparse matrix is restamped per frequency; its LU ordering is analysed   │
+//! │ once and refactored at each frequency                                       │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
@@ -35,6 +37,7 @@
 //!
 //! DEPENDS ON:
 //!   • super::netlist → Netlist, Element, SourceValue
+//!   • super::sparse → SparseMatrix, SparseSolver
 //!
 //! USED BY:
 //!   • TOOLS/PLL → Frequency response, Bode plots
@@ -47,6 +50,8 @@
 // ─────────────────────────────────────────────────────────────────────────────────
 
 use super::netlist::{Element, Netlist, SourceValue};
+use super::sparse::{SparseMatrix, SparseSolver};
+use crate::power::control;
 use std::f64::consts::PI;
 
 /// Complex number for AC analysis
@@ -106,6 +111,13 @@ impl std::ops::Sub for Complex {
     }
 }
 
+impl std::ops::Neg for Complex {
+    type Output = Complex;
+    fn neg(self) -> Complex {
+        Complex::new(-self.real, -self.imag)
+    }
+}
+
 impl std::ops::Mul for Complex {
     type Output = Complex;
     fn mul(self, other: Complex) -> Complex {
@@ -134,20 +146,33 @@ impl std::ops::Div for Complex {
     }
 }
 
+impl From<Complex> for control::Complex {
+    fn from(c: Complex) -> Self {
+        control::Complex::new(c.real, c.imag)
+    }
+}
+
+impl From<control::Complex> for Complex {
+    fn from(c: control::Complex) -> Self {
+        Complex::new(c.re, c.im)
+    }
+}
+
 /// Complex MNA matrix for AC analysis
 #[derive(Clone, Debug)]
 pub struct ComplexMNAMatrix {
     pub size: usize,
     pub num_nodes: usize,
     pub num_vsources: usize,
-    pub matrix: Vec<Vec<Complex>>,
+    /// Stamped entries of the [size x size] matrix
+    pub matrix: SparseMatrix<Complex>,
     pub rhs: Vec<Complex>,
 }
 
 impl ComplexMNAMatrix {
     pub fn new(num_nodes: usize, num_vsources: usize) -> Self {
         let size = num_nodes + num_vsources;
-        let matrix = vec![vec![Complex::zero(); size]; size];
+        let matrix = SparseMatrix::new(size);
         let rhs = vec![Complex::zero(); size];
 
         Self {
@@ -164,16 +189,16 @@ impl ComplexMNAMatrix {
         let g = Complex::new(1.0 / resistance, 0.0);
 
         if node_p > 0 {
-            self.matrix[node_p - 1][node_p - 1] = self.matrix[node_p - 1][node_p - 1] + g;
+            self.matrix.add(node_p - 1, node_p - 1, g);
             if node_n > 0 {
-                self.matrix[node_p - 1][node_n - 1] = self.matrix[node_p - 1][node_n - 1] - g;
+                self.matrix.add(node_p - 1, node_n - 1, -g);
             }
         }
 
         if node_n > 0 {
-            self.matrix[node_n - 1][node_n - 1] = self.matrix[node_n - 1][node_n - 1] + g;
+            self.matrix.add(node_n - 1, node_n - 1, g);
             if node_p > 0 {
-                self.matrix[node_n - 1][node_p - 1] = self.matrix[node_n - 1][node_p - 1] - g;
+                self.matrix.add(node_n - 1, node_p - 1, -g);
             }
         }
     }
@@ -183,16 +208,16 @@ impl ComplexMNAMatrix {
         let y = Complex::new(0.0, omega * capacitance);
 
         if node_p > 0 {
-            self.matrix[node_p - 1][node_p - 1] = self.matrix[node_p - 1][node_p - 1] + y;
+            self.matrix.add(node_p - 1, node_p - 1, y);
             if node_n > 0 {
-                self.matrix[node_p - 1][node_n - 1] = self.matrix[node_p - 1][node_n - 1] - y;
+                self.matrix.add(node_p - 1, node_n - 1, -y);
             }
         }
 
         if node_n > 0 {
-            self.matrix[node_n - 1][node_n - 1] = self.matrix[node_n - 1][node_n - 1] + y;
+            self.matrix.add(node_n - 1, node_n - 1, y);
             if node_p > 0 {
-                self.matrix[node_n - 1][node_p - 1] = self.matrix[node_n - 1][node_p - 1] - y;
+                self.matrix.add(node_n - 1, node_p - 1, -y);
             }
         }
     }
@@ -206,20 +231,30 @@ impl ComplexMNAMatrix {
         let y = Complex::new(0.0, -1.0 / (omega * inductance));
 
         if node_p > 0 {
-            self.matrix[node_p - 1][node_p - 1] = self.matrix[node_p - 1][node_p - 1] + y;
+            self.matrix.add(node_p - 1, node_p - 1, y);
             if node_n > 0 {
-                self.matrix[node_p - 1][node_n - 1] = self.matrix[node_p - 1][node_n - 1] - y;
+                self.matrix.add(node_p - 1, node_n - 1, -y);
             }
         }
 
         if node_n > 0 {
-            self.matrix[node_n - 1][node_n - 1] = self.matrix[node_n - 1][node_n - 1] + y;
+            self.matrix.add(node_n - 1, node_n - 1, y);
             if node_p > 0 {
-                self.matrix[node_n - 1][node_p - 1] = self.matrix[node_n - 1][node_p - 1] - y;
+                self.matrix.add(node_n - 1, node_p - 1, -y);
             }
         }
     }
 
+    /// Stamp current source, flowing p → n through the source
+    pub fn stamp_current_source(&mut self, node_p: usize, node_n: usize, current: Complex) {
+        if node_p > 0 {
+            self.rhs[node_p - 1] = self.rhs[node_p - 1] - current;
+        }
+        if node_n > 0 {
+            self.rhs[node_n - 1] = self.rhs[node_n - 1] + current;
+        }
+    }
+
     /// Stamp voltage source
     pub fn stamp_voltage_source(
         &mut self,
@@ -231,17 +266,13 @@ impl ComplexMNAMatrix {
         let vs_row = self.num_nodes + vs_idx;
 
         if node_p > 0 {
-            self.matrix[node_p - 1][vs_row] =
-                self.matrix[node_p - 1][vs_row] + Complex::new(1.0, 0.0);
-            self.matrix[vs_row][node_p - 1] =
-                self.matrix[vs_row][node_p - 1] + Complex::new(1.0, 0.0);
+            self.matrix.add(node_p - 1, vs_row, Complex::new(1.0, 0.0));
+            self.matrix.add(vs_row, node_p - 1, Complex::new(1.0, 0.0));
         }
 
         if node_n > 0 {
-            self.matrix[node_n - 1][vs_row] =
-                self.matrix[node_n - 1][vs_row] - Complex::new(1.0, 0.0);
-            self.matrix[vs_row][node_n - 1] =
-                self.matrix[vs_row][node_n - 1] - Complex::new(1.0, 0.0);
+            self.matrix.add(node_n - 1, vs_row, -Complex::new(1.0, 0.0));
+            self.matrix.add(vs_row, node_n - 1, -Complex::new(1.0, 0.0));
         }
 
         self.rhs[vs_row] = voltage;
@@ -259,20 +290,16 @@ impl ComplexMNAMatrix {
         let gm = Complex::new(transconductance, 0.0);
 
         if node_out_p > 0 && node_ctrl_p > 0 {
-            self.matrix[node_out_p - 1][node_ctrl_p - 1] =
-                self.matrix[node_out_p - 1][node_ctrl_p - 1] + gm;
+            self.matrix.add(node_out_p - 1, node_ctrl_p - 1, gm);
         }
         if node_out_p > 0 && node_ctrl_n > 0 {
-            self.matrix[node_out_p - 1][node_ctrl_n - 1] =
-                self.matrix[node_out_p - 1][node_ctrl_n - 1] - gm;
+            self.matrix.add(node_out_p - 1, node_ctrl_n - 1, -gm);
         }
         if node_out_n > 0 && node_ctrl_p > 0 {
-            self.matrix[node_out_n - 1][node_ctrl_p - 1] =
-                self.matrix[node_out_n - 1][node_ctrl_p - 1] - gm;
+            self.matrix.add(node_out_n - 1, node_ctrl_p - 1, -gm);
         }
         if node_out_n > 0 && node_ctrl_n > 0 {
-            self.matrix[node_out_n - 1][node_ctrl_n - 1] =
-                self.matrix[node_out_n - 1][node_ctrl_n - 1] + gm;
+            self.matrix.add(node_out_n - 1, node_ctrl_n - 1, gm);
         }
     }
 
@@ -291,32 +318,32 @@ impl ComplexMNAMatrix {
 
         // KCL at output nodes (current flows through VCVS)
         if node_out_p > 0 {
-            self.matrix[node_out_p - 1][vs_row] =
-                self.matrix[node_out_p - 1][vs_row] + Complex::new(1.0, 0.0);
-            self.matrix[vs_row][node_out_p - 1] =
-                self.matrix[vs_row][node_out_p - 1] + Complex::new(1.0, 0.0);
+            self.matrix
+                .add(node_out_p - 1, vs_row, Complex::new(1.0, 0.0));
+            self.matrix
+                .add(vs_row, node_out_p - 1, Complex::new(1.0, 0.0));
         }
         if node_out_n > 0 {
-            self.matrix[node_out_n - 1][vs_row] =
-                self.matrix[node_out_n - 1][vs_row] - Complex::new(1.0, 0.0);
-            self.matrix[vs_row][node_out_n - 1] =
-                self.matrix[vs_row][node_out_n - 1] - Complex::new(1.0, 0.0);
+            self.matrix
+                .add(node_out_n - 1, vs_row, -Complex::new(1.0, 0.0));
+            self.matrix
+                .add(vs_row, node_out_n - 1, -Complex::new(1.0, 0.0));
         }
 
         // Voltage constraint: V_out = gain * V_ctrl
         // V_out_p - V_out_n - gain * (V_ctrl_p - V_ctrl_n) = 0
         let g = Complex::new(gain, 0.0);
         if node_ctrl_p > 0 {
-            self.matrix[vs_row][node_ctrl_p - 1] = self.matrix[vs_row][node_ctrl_p - 1] - g;
+            self.matrix.add(vs_row, node_ctrl_p - 1, -g);
         }
         if node_ctrl_n > 0 {
-            self.matrix[vs_row][node_ctrl_n - 1] = self.matrix[vs_row][node_ctrl_n - 1] + g;
+            self.matrix.add(vs_row, node_ctrl_n - 1, g);
         }
     }
 
     /// Solve using complex LU decomposition with partial pivoting
     pub fn solve(&self) -> Result<Vec<Complex>, String> {
-        let mut a = self.matrix.clone();
+        let mut a = self.matrix.to_dense();
         let mut b = self.rhs.clone();
         let n = self.size;
 
@@ -387,6 +414,18 @@ impl ComplexMNAMatrix {
 
         Ok(x)
     }
+
+    /// Zero every entry and the right-hand side, keeping the pattern for
+    /// restamping at another frequency
+    pub fn clear(&mut self) {
+        self.matrix.clear();
+        self.rhs.fill(Complex::zero());
+    }
+
+    /// Solve using a sparse LU, reusing `solver`'s ordering when the pattern allows
+    pub fn solve_sparse(&self, solver: &mut SparseSolver<Complex>) -> Result<Vec<Complex>, String> {
+        solver.solve(&self.matrix, &self.rhs)
+    }
 }
 
 /// AC analysis result
@@ -396,6 +435,151 @@ pub struct ACResult {
     pub node_voltages: Vec<Vec<Complex>>, // [frequency][node]
 }
 
+/// Build the complex MNA matrix of `netlist` at angular frequency `omega`
+pub fn ac_matrix(netlist: &Netlist, omega: f64) -> ComplexMNAMatrix {
+    let mut matrix = ComplexMNAMatrix::new(netlist.num_nodes(), netlist.num_voltage_sources());
+    stamp_ac(netlist, omega, &mut matrix);
+    matrix
+}
+
+/// Restamp `matrix` (built by `ac_matrix` for the same netlist) at `omega`
+///
+/// Every entry lands in its existing slot, so a sparse factorization of the
+/// previous frequency is refactored without a new analysis.
+pub fn restamp_ac(netlist: &Netlist, omega: f64, matrix: &mut ComplexMNAMatrix) {
+    matrix.clear();
+    stamp_ac(netlist, omega, matrix);
+}
+
+fn stamp_ac(netlist: &Netlist, omega: f64, matrix: &mut ComplexMNAMatrix) {
+    let mut vs_count = 0;
+
+    for element in &netlist.elements {
+        match element {
+            Element::Resistor {
+                node_p,
+                node_n,
+                value,
+                ..
+            } => {
+                let np = netlist.node_index(node_p).unwrap();
+                let nn = netlist.node_index(node_n).unwrap();
+                matrix.stamp_resistor(np, nn, *value);
+            }
+            Element::Capacitor {
+                node_p,
+                node_n,
+                value,
+                ..
+            } => {
+                let np = netlist.node_index(node_p).unwrap();
+                let nn = netlist.node_index(node_n).unwrap();
+                matrix.stamp_capacitor(np, nn, *value, omega);
+            }
+            Element::Inductor {
+                node_p,
+                node_n,
+                value,
+                ..
+            } => {
+                let np = netlist.node_index(node_p).unwrap();
+                let nn = netlist.node_index(node_n).unwrap();
+                matrix.stamp_inductor(np, nn, *value, omega);
+            }
+            Element::VoltageSource {
+                node_p,
+                node_n,
+                value,
+                ..
+            } => {
+                let np = netlist.node_index(node_p).unwrap();
+                let nn = netlist.node_index(node_n).unwrap();
+
+                let v_complex = match value {
+                    SourceValue::DC(v) => Complex::new(*v, 0.0),
+                    SourceValue::AC { magnitude, phase } => {
+                        Complex::from_polar(*magnitude, phase * PI / 180.0)
+                    }
+                    _ => Complex::zero(),
+                };
+
+                matrix.stamp_voltage_source(np, nn, vs_count, v_complex);
+                vs_count += 1;
+            }
+            Element::CurrentSource {
+                node_p,
+                node_n,
+                value: SourceValue::AC { magnitude, phase },
+                ..
+            } => {
+                let np = netlist.node_index(node_p).unwrap();
+                let nn = netlist.node_index(node_n).unwrap();
+                let i_complex = Complex::from_polar(*magnitude, phase * PI / 180.0);
+                matrix.stamp_current_source(np, nn, i_complex);
+            }
+            Element::VCCS {
+                node_out_p,
+                node_out_n,
+                node_ctrl_p,
+                node_ctrl_n,
+                transconductance,
+                ..
+            } => {
+                let nop = netlist.node_index(node_out_p).unwrap();
+                let non = netlist.node_index(node_out_n).unwrap();
+                let ncp = netlist.node_index(node_ctrl_p).unwrap();
+                let ncn = netlist.node_index(node_ctrl_n).unwrap();
+                matrix.stamp_vccs(nop, non, ncp, ncn, *transconductance);
+            }
+            Element::VCVS {
+                node_out_p,
+                node_out_n,
+                node_ctrl_p,
+                node_ctrl_n,
+                gain,
+                ..
+            } => {
+                let nop = netlist.node_index(node_out_p).unwrap();
+                let non = netlist.node_index(node_out_n).unwrap();
+                let ncp = netlist.node_index(node_ctrl_p).unwrap();
+                let ncn = netlist.node_index(node_ctrl_n).unwrap();
+                matrix.stamp_vcvs(nop, non, ncp, ncn, *gain, vs_count);
+                vs_count += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Node voltages and source currents at a single frequency (Hz)
+pub fn ac_point(netlist: &Netlist, freq: f64) -> Result<Vec<Complex>, String> {
+    ac_matrix(netlist, 2.0 * PI * freq).solve_sparse(&mut SparseSolver::new())
+}
+
+/// Logarithmically spaced sweep points from `freq_start` to `freq_stop`
+pub(super) fn log_frequencies(
+    freq_start: f64,
+    freq_stop: f64,
+    points_per_decade: usize,
+) -> Vec<f64> {
+    let start_log = freq_start.log10();
+    let stop_log = freq_stop.log10();
+    let decades = stop_log - start_log;
+    if decades <= 0.0 {
+        return vec![freq_start];
+    }
+    // Both end points are always included
+    let num_points = ((decades * points_per_decade as f64).ceil() as usize).max(2);
+
+    (0..num_points)
+        .map(|i| {
+            let log_freq =
+                start_log + (i as f64 / (num_points - 1) as f64) * (stop_log - start_log);
+            10.0_f64.powf(log_freq)
+        })
+        .collect()
+}
+
 /// Perform AC analysis
 pub fn ac_analysis(
     netlist: &Netlist,
@@ -403,114 +587,24 @@ pub fn ac_analysis(
     freq_stop: f64,
     points_per_decade: usize,
 ) -> Result<ACResult, String> {
-    let num_nodes = netlist.num_nodes();
-    let num_vsources = netlist.num_voltage_sources();
-
     let mut frequencies = Vec::new();
     let mut node_voltages = Vec::new();
 
-    // Generate frequency points (logarithmic)
-    let start_log = freq_start.log10();
-    let stop_log = freq_stop.log10();
-    let decades = stop_log - start_log;
-    let num_points = (decades * points_per_decade as f64).ceil() as usize;
+    let mut solver = SparseSolver::new();
+    let mut matrix: Option<ComplexMNAMatrix> = None;
 
-    for i in 0..num_points {
-        let log_freq = start_log + (i as f64 / (num_points - 1) as f64) * (stop_log - start_log);
-        let freq = 10.0_f64.powf(log_freq);
+    for freq in log_frequencies(freq_start, freq_stop, points_per_decade) {
         let omega = 2.0 * PI * freq;
 
-        // Build matrix for this frequency
-        let mut matrix = ComplexMNAMatrix::new(num_nodes, num_vsources);
-        let mut vs_count = 0;
-
-        for element in &netlist.elements {
-            match element {
-                Element::Resistor {
-                    node_p,
-                    node_n,
-                    value,
-                    ..
-                } => {
-                    let np = netlist.node_index(node_p).unwrap();
-                    let nn = netlist.node_index(node_n).unwrap();
-                    matrix.stamp_resistor(np, nn, *value);
-                }
-                Element::Capacitor {
-                    node_p,
-                    node_n,
-                    value,
-                    ..
-                } => {
-                    let np = netlist.node_index(node_p).unwrap();
-                    let nn = netlist.node_index(node_n).unwrap();
-                    matrix.stamp_capacitor(np, nn, *value, omega);
-                }
-                Element::Inductor {
-                    node_p,
-                    node_n,
-                    value,
-                    ..
-                } => {
-                    let np = netlist.node_index(node_p).unwrap();
-                    let nn = netlist.node_index(node_n).unwrap();
-                    matrix.stamp_inductor(np, nn, *value, omega);
-                }
-                Element::VoltageSource {
-                    node_p,
-                    node_n,
-                    value,
-                    ..
-                } => {
-                    let np = netlist.node_index(node_p).unwrap();
-                    let nn = netlist.node_index(node_n).unwrap();
-
-                    let v_complex = match value {
-                        SourceValue::DC(v) => Complex::new(*v, 0.0),
-                        SourceValue::AC { magnitude, phase } => {
-                            Complex::from_polar(*magnitude, phase * PI / 180.0)
-                        }
-                        _ => Complex::zero(),
-                    };
-
-                    matrix.stamp_voltage_source(np, nn, vs_count, v_complex);
-                    vs_count += 1;
-                }
-                Element::VCCS {
-                    node_out_p,
-                    node_out_n,
-                    node_ctrl_p,
-                    node_ctrl_n,
-                    transconductance,
-                    ..
-                } => {
-                    let nop = netlist.node_index(node_out_p).unwrap();
-                    let non = netlist.node_index(node_out_n).unwrap();
-                    let ncp = netlist.node_index(node_ctrl_p).unwrap();
-                    let ncn = netlist.node_index(node_ctrl_n).unwrap();
-                    matrix.stamp_vccs(nop, non, ncp, ncn, *transconductance);
-                }
-                Element::VCVS {
-                    node_out_p,
-                    node_out_n,
-                    node_ctrl_p,
-                    node_ctrl_n,
-                    gain,
-                    ..
-                } => {
-                    let nop = netlist.node_index(node_out_p).unwrap();
-                    let non = netlist.node_index(node_out_n).unwrap();
-                    let ncp = netlist.node_index(node_ctrl_p).unwrap();
-                    let ncn = netlist.node_index(node_ctrl_n).unwrap();
-                    matrix.stamp_vcvs(nop, non, ncp, ncn, *gain, vs_count);
-                    vs_count += 1;
-                }
-                _ => {}
+        // Restamp in place so the ordering from the first frequency is reused
+        let m = match matrix.as_mut() {
+            Some(m) => {
+                restamp_ac(netlist, omega, m);
+                m
             }
-        }
-
-        // Solve
-        let solution = matrix.solve()?;
+            None => matrix.insert(ac_matrix(netlist, omega)),
+        };
+        let solution = m.solve_sparse(&mut solver)?;
 
         frequencies.push(freq);
         node_voltages.push(solution);
@@ -542,11 +636,8 @@ mod tests {
         assert!((d.imag - 10.0).abs() < 1e-10);
     }
 
-    #[test]
-    fn test_rc_lowpass() {
-        // RC lowpass: V_in -> R (1k) -> node 1 -> C (1µF) -> GND
-        // Cutoff frequency = 1/(2πRC) = 159 Hz
-
+    /// RC lowpass: V_in -> R (1k) -> node 1 -> C (1µF) -> GND
+    fn rc_lowpass() -> Netlist {
         let mut netlist = Netlist::new("RC Lowpass".to_string());
 
         netlist.add_element(Element::VoltageSource {
@@ -572,8 +663,13 @@ mod tests {
             node_n: "0".to_string(),
             value: 1e-6,
         });
+        netlist
+    }
 
-        let result = ac_analysis(&netlist, 10.0, 10000.0, 20).unwrap();
+    #[test]
+    fn test_rc_lowpass() {
+        // Cutoff frequency = 1/(2πRC) = 159 Hz
+        let result = ac_analysis(&rc_lowpass(), 10.0, 10000.0, 20).unwrap();
 
         // At low frequency, output should be close to input
         let v_out_low = result.node_voltages[0][1]; // Node "out" at first frequency
@@ -583,4 +679,52 @@ mod tests {
         let v_out_high = result.node_voltages[result.node_voltages.len() - 1][1];
         assert!(v_out_high.magnitude() < 0.1);
     }
+
+    #[test]
+    fn test_ac_current_source() {
+        // 1 mA ∠90° into 1 kΩ gives 1 V ∠90°
+        let mut netlist = Netlist::new("Norton".to_string());
+        netlist.add_element(Element::CurrentSource {
+            name: "I1".to_string(),
+            node_p: "0".to_string(),
+            node_n: "out".to_string(),
+            value: SourceValue::AC {
+                magnitude: 1e-3,
+                phase: 90.0,
+            },
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: 1000.0,
+        });
+        let x = ac_point(&netlist, 1e3).unwrap();
+        assert!((x[0] - Complex::new(0.0, 1.0)).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn test_restamp_reuses_factorization() {
+        let netlist = rc_lowpass();
+        let mut matrix = ac_matrix(&netlist, 2.0 * PI * 10.0);
+        let nnz = matrix.matrix.nnz();
+        let mut solver = SparseSolver::new();
+        for freq in log_frequencies(10.0, 1e4, 10) {
+            restamp_ac(&netlist, 2.0 * PI * freq, &mut matrix);
+            let x = matrix.solve_sparse(&mut solver).unwrap();
+            let fresh = ac_point(&netlist, freq).unwrap();
+            assert!((x[1] - fresh[1]).magnitude() < 1e-12);
+        }
+        assert_eq!(matrix.matrix.nnz(), nnz);
+        assert_eq!(solver.factorizations, 1);
+    }
+
+    #[test]
+    fn test_log_frequencies_end_points() {
+        let f = log_frequencies(1e3, 1e4, 1);
+        assert_eq!(f.len(), 2);
+        assert!((f[0] - 1e3).abs() < 1e-9 && (f[1] - 1e4).abs() < 1e-6);
+        assert_eq!(log_frequencies(1e3, 1e3, 10), vec![1e3]);
+        assert_eq!(log_frequencies(1.0, 1e3, 10).len(), 30);
+    }
 }
@@ -0,0 +1,999 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: dc.rs | DNA/src/physics/electromagnetics/lumped/dc.rs
+//! PURPOSE: Nonlinear DC operating point (Newton-Raphson with continuation)
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Nonlinear DC operating point (Newton-Raphson with continuation)
+//!
+//! LAYER: DNA → PHYSICS → ELECTROMAGNETICS → LUMPED
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM: Newton-Raphson on the MNA equations                              │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Each iteration linearizes every device about the previous solution:         │
+//! │   I(v) ≈ I(v₀) + G·(v - v₀)   →   stamp G, plus Ieq = I(v₀) - G·v₀          │
+//! │ and solves the linear MNA system for the next iterate. Junction voltages    │
+//! │ are limited (pnjlim) so exponentials cannot overshoot.                      │
+//! │                                                                             │
+//! │ Converged when no junction was limited and every unknown moved less than    │
+//! │   reltol·|x| + vntol (node voltages) or reltol·|x| + abstol (currents)      │
+//! │                                                                             │
+//! │ If the direct solve fails:                                                  │
+//! │   1. gmin stepping:   shunt every node with 1e-2 S, shrink 10× per solve    │
+//! │   2. source stepping: ramp all independent sources from 0 to 100 %          │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ DcOptions          Newton tolerances, iteration limit, continuation flags   │
+//! │ ConvergenceAid     Which continuation method produced the solution          │
+//! │ DcOperatingPoint   Node voltages and source currents at the bias point      │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! DEPENDS ON:
+//!   • super::netlist → Netlist, Element, SourceValue
+//!   • super::matrix → MNAMatrix, restamped in place each Newton iteration
+//!   • super::sparse → SparseSolver (ordering reused across iterations/steps)
+//!   • super::devices → Diode, BJT and MOSFET equations, pnjlim
+//!
+//! USED BY:
+//!   • physics/electromagnetics/lumped/transient.rs → Initial condition, timesteps
+//!   • CORE/SPICE_ENGINE → .op analysis
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// CODE BELOW - Optimized for ML development
+// ─────────────────────────────────────────────────────────────────────────────────
+
+use super::devices::{
+    critical_voltage, pnjlim, BjtModel, DiodeModel, MosfetModel, THERMAL_VOLTAGE,
+};
+use super::matrix::MNAMatrix;
+use super::netlist::{BehavioralExpression, Element, Netlist, SourceValue};
+use super::sparse::SparseSolver;
+use std::cell::RefCell;
+
+/// Conductance from every node to ground (and across every device), so
+/// nodes isolated by capacitors or off devices still have a defined voltage
+pub(super) const GMIN: f64 = 1e-12;
+
+/// Node shunt conductance at the start of gmin stepping (S)
+const GMIN_START: f64 = 1e-2;
+
+/// Smallest source-stepping increment before giving up
+const MIN_SOURCE_STEP: f64 = 1e-3;
+
+/// DC operating point settings
+#[derive(Clone, Debug)]
+pub struct DcOptions {
+    /// Relative convergence tolerance
+    pub reltol: f64,
+    /// Absolute voltage tolerance (V)
+    pub vntol: f64,
+    /// Absolute current tolerance (A)
+    pub abstol: f64,
+    /// Newton iterations allowed per solve
+    pub max_iterations: usize,
+    /// Fall back to gmin stepping when the direct solve fails
+    pub gmin_stepping: bool,
+    /// Fall back to source stepping when gmin stepping fails
+    pub source_stepping: bool,
+}
+
+impl Default for DcOptions {
+    fn default() -> Self {
+        Self {
+            reltol: 1e-3,
+            vntol: 1e-6,
+            abstol: 1e-12,
+            max_iterations: 100,
+            gmin_stepping: true,
+            source_stepping: true,
+        }
+    }
+}
+
+/// Continuation method that produced an operating point
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvergenceAid {
+    /// Newton converged from the zero initial guess
+    None,
+    /// Converged while shrinking a node-to-ground shunt conductance
+    GminStepping,
+    /// Converged while ramping the independent sources
+    SourceStepping,
+}
+
+/// DC operating point result
+#[derive(Clone, Debug)]
+pub struct DcOperatingPoint {
+    /// MNA solution: [node voltages, voltage source currents]
+    pub solution: Vec<f64>,
+    /// Node name for each voltage row (row i is netlist node index i + 1)
+    pub node_names: Vec<String>,
+    /// Newton iterations across all converged solves
+    pub iterations: usize,
+    pub aid: ConvergenceAid,
+}
+
+impl DcOperatingPoint {
+    /// Voltage of a named node
+    pub fn voltage(&self, node: &str) -> Option<f64> {
+        if node == "0" {
+            return Some(0.0);
+        }
+        let row = self.node_names.iter().position(|n| n == node)?;
+        Some(self.solution[row])
+    }
+}
+
+/// Solve the DC operating point with the default options
+///
+/// Capacitors are open and inductors shorted; time-varying sources take
+/// their t = 0 value.
+pub fn dc_operating_point(netlist: &Netlist) -> Result<DcOperatingPoint, String> {
+    dc_operating_point_with(netlist, &DcOptions::default())
+}
+
+/// Solve the DC operating point with explicit options
+pub fn dc_operating_point_with(
+    netlist: &Netlist,
+    options: &DcOptions,
+) -> Result<DcOperatingPoint, String> {
+    let circuit = Circuit::new(netlist)?;
+    let (x, iterations, aid) = circuit.operating_point(options)?;
+    Ok(DcOperatingPoint {
+        solution: x[..circuit.num_nodes + circuit.num_vsources].to_vec(),
+        node_names: circuit.node_names,
+        iterations,
+        aid,
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CIRCUIT PREPARATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Element with node names resolved to indices
+pub(super) enum Stamp<'a> {
+    Resistor(usize, usize, f64),
+    Current(usize, usize, &'a SourceValue),
+    ConstCurrent(usize, usize, f64),
+    Voltage(usize, usize, usize, &'a SourceValue),
+    ConstVoltage(usize, usize, usize, f64),
+    Vcvs {
+        out: (usize, usize),
+        ctrl: (usize, usize),
+        vs: usize,
+        gain: f64,
+        offset: f64,
+    },
+    Vccs {
+        out: (usize, usize),
+        ctrl: (usize, usize),
+        gm: f64,
+    },
+}
+
+/// Nonlinear element with node names resolved to indices
+pub(super) enum Device<'a> {
+    Diode {
+        anode: usize,
+        cathode: usize,
+        model: &'a DiodeModel,
+    },
+    Bjt {
+        c: usize,
+        b: usize,
+        e: usize,
+        model: &'a BjtModel,
+    },
+    Mosfet {
+        d: usize,
+        g: usize,
+        s: usize,
+        model: &'a MosfetModel,
+    },
+}
+
+/// Netlist prepared for repeated MNA solves
+pub(super) struct Circuit<'a> {
+    pub(super) num_nodes: usize,
+    pub(super) num_vsources: usize,
+    pub(super) node_names: Vec<String>,
+    pub(super) stamps: Vec<Stamp<'a>>,
+    pub(super) devices: Vec<Device<'a>>,
+    pub(super) capacitors: Vec<(usize, usize, f64)>,
+    pub(super) inductors: Vec<(usize, usize, f64)>,
+    /// Sparse factorization shared by every Newton iteration and timestep
+    pub(super) solver: RefCell<SparseSolver<f64>>,
+}
+
+/// Voltage of node `n` in solution `x` (node 0 is ground)
+pub(super) fn node_v(x: &[f64], n: usize) -> f64 {
+    if n == 0 {
+        0.0
+    } else {
+        x[n - 1]
+    }
+}
+
+impl<'a> Circuit<'a> {
+    pub(super) fn new(netlist: &'a Netlist) -> Result<Self, String> {
+        let index = |node: &str| {
+            netlist
+                .node_index(node)
+                .ok_or_else(|| format!("Unknown node '{}'", node))
+        };
+
+        let mut stamps = Vec::new();
+        let mut devices = Vec::new();
+        let mut capacitors = Vec::new();
+        let mut inductors = Vec::new();
+        let mut vs = 0;
+
+        for element in &netlist.elements {
+            match element {
+                Element::Resistor {
+                    node_p,
+                    node_n,
+                    value,
+                    ..
+                } => stamps.push(Stamp::Resistor(index(node_p)?, index(node_n)?, *value)),
+                Element::Capacitor {
+                    node_p,
+                    node_n,
+                    value,
+                    ..
+                } => capacitors.push((index(node_p)?, index(node_n)?, *value)),
+                Element::Inductor {
+                    node_p,
+                    node_n,
+                    value,
+                    ..
+                } => inductors.push((index(node_p)?, index(node_n)?, *value)),
+                Element::VoltageSource {
+                    node_p,
+                    node_n,
+                    value,
+                    ..
+                } => {
+                    stamps.push(Stamp::Voltage(index(node_p)?, index(node_n)?, vs, value));
+                    vs += 1;
+                }
+                Element::CurrentSource {
+                    node_p,
+                    node_n,
+                    value,
+                    ..
+                } => stamps.push(Stamp::Current(index(node_p)?, index(node_n)?, value)),
+                Element::VCVS {
+                    node_out_p,
+                    node_out_n,
+                    node_ctrl_p,
+                    node_ctrl_n,
+                    gain,
+                    ..
+                } => {
+                    stamps.push(Stamp::Vcvs {
+                        out: (index(node_out_p)?, index(node_out_n)?),
+                        ctrl: (index(node_ctrl_p)?, index(node_ctrl_n)?),
+                        vs,
+                        gain: *gain,
+                        offset: 0.0,
+                    });
+                    vs += 1;
+                }
+                Element::VCCS {
+                    node_out_p,
+                    node_out_n,
+                    node_ctrl_p,
+                    node_ctrl_n,
+                    transconductance,
+                    ..
+                } => stamps.push(Stamp::Vccs {
+                    out: (index(node_out_p)?, index(node_out_n)?),
+                    ctrl: (index(node_ctrl_p)?, index(node_ctrl_n)?),
+                    gm: *transconductance,
+                }),
+                Element::BehavioralV {
+                    name,
+                    node_p,
+                    node_n,
+                    expression,
+                } => {
+                    let (p, n) = (index(node_p)?, index(node_n)?);
+                    match expression {
+                        BehavioralExpression::Constant(v) => {
+                            stamps.push(Stamp::ConstVoltage(p, n, vs, *v))
+                        }
+                        BehavioralExpression::Linear {
+                            a,
+                            b,
+                            input_node_p,
+                            input_node_n,
+                        } => stamps.push(Stamp::Vcvs {
+                            out: (p, n),
+                            ctrl: (index(input_node_p)?, index(input_node_n)?),
+                            vs,
+                            gain: *a,
+                            offset: *b,
+                        }),
+                        _ => return Err(unsupported(name)),
+                    }
+                    vs += 1;
+                }
+                Element::BehavioralI {
+                    name,
+                    node_p,
+                    node_n,
+                    expression,
+                } => {
+                    let (p, n) = (index(node_p)?, index(node_n)?);
+                    match expression {
+                        BehavioralExpression::Constant(i) => {
+                            stamps.push(Stamp::ConstCurrent(p, n, *i))
+                        }
+                        BehavioralExpression::Linear {
+                            a,
+                            b,
+                            input_node_p,
+                            input_node_n,
+                        } => {
+                            stamps.push(Stamp::Vccs {
+                                out: (p, n),
+                                ctrl: (index(input_node_p)?, index(input_node_n)?),
+                                gm: *a,
+                            });
+                            stamps.push(Stamp::ConstCurrent(p, n, *b));
+                        }
+                        _ => return Err(unsupported(name)),
+                    }
+                }
+                Element::Diode {
+                    node_p,
+                    node_n,
+                    model,
+                    ..
+                } => devices.push(Device::Diode {
+                    anode: index(node_p)?,
+                    cathode: index(node_n)?,
+                    model,
+                }),
+                Element::Bjt {
+                    node_c,
+                    node_b,
+                    node_e,
+                    model,
+                    ..
+                } => devices.push(Device::Bjt {
+                    c: index(node_c)?,
+                    b: index(node_b)?,
+                    e: index(node_e)?,
+                    model,
+                }),
+                Element::Mosfet {
+                    node_d,
+                    node_g,
+                    node_s,
+                    model,
+                    ..
+                } => devices.push(Device::Mosfet {
+                    d: index(node_d)?,
+                    g: index(node_g)?,
+                    s: index(node_s)?,
+                    model,
+                }),
+            }
+        }
+
+        let mut node_names = vec![String::new(); netlist.num_nodes()];
+        for (name, &i) in &netlist.nodes {
+            if i > 0 {
+                node_names[i - 1] = name.clone();
+            }
+        }
+
+        Ok(Self {
+            num_nodes: netlist.num_nodes(),
+            num_vsources: vs,
+            node_names,
+            stamps,
+            devices,
+            capacitors,
+            inductors,
+            solver: RefCell::new(SparseSolver::new()),
+        })
+    }
+
+    /// Matrix with every linear resistive element and source stamped at
+    /// time `t`, independent sources scaled by `scale`
+    pub(super) fn static_matrix(&self, t: f64, extra_vsources: usize, scale: f64) -> MNAMatrix {
+        let mut m = MNAMatrix::new(self.num_nodes, self.num_vsources + extra_vsources);
+        for stamp in &self.stamps {
+            match *stamp {
+                Stamp::Resistor(p, n, r) => m.stamp_resistor(p, n, r),
+                Stamp::Current(p, n, source) => {
+                    m.stamp_current_source(p, n, scale * source.value_at(t))
+                }
+                Stamp::ConstCurrent(p, n, i) => m.stamp_current_source(p, n, scale * i),
+                Stamp::Voltage(p, n, k, source) => {
+                    m.stamp_voltage_source(p, n, k, scale * source.value_at(t))
+                }
+                Stamp::ConstVoltage(p, n, k, v) => m.stamp_voltage_source(p, n, k, scale * v),
+                Stamp::Vcvs {
+                    out,
+                    ctrl,
+                    vs,
+                    gain,
+                    offset,
+                } => {
+                    m.stamp_vcvs(out.0, out.1, ctrl.0, ctrl.1, vs, gain);
+                    m.rhs[self.num_nodes + vs] += scale * offset;
+                }
+                Stamp::Vccs { out, ctrl, gm } => m.stamp_vccs(out.0, out.1, ctrl.0, ctrl.1, gm),
+            }
+        }
+        m
+    }
+
+    /// DC matrix at t = 0: capacitors open, inductors as 0 V sources
+    /// after the circuit's own sources, every node shunted by `gshunt`
+    pub(super) fn dc_matrix(&self, gshunt: f64, scale: f64) -> MNAMatrix {
+        let mut m = self.static_matrix(0.0, self.inductors.len(), scale);
+        for n in 1..=self.num_nodes {
+            m.stamp_conductance(n, 0, gshunt);
+        }
+        for (k, &(p, n, _)) in self.inductors.iter().enumerate() {
+            m.stamp_voltage_source(p, n, self.num_vsources + k, 0.0);
+        }
+        m
+    }
+
+    /// Operating point solution (including inductor currents after the
+    /// source currents), total Newton iterations and the aid that worked
+    pub(super) fn operating_point(
+        &self,
+        options: &DcOptions,
+    ) -> Result<(Vec<f64>, usize, ConvergenceAid), String> {
+        let size = self.num_nodes + self.num_vsources + self.inductors.len();
+        let direct = match self.newton(&self.dc_matrix(GMIN, 1.0), vec![0.0; size], options) {
+            Ok((x, iterations)) => return Ok((x, iterations, ConvergenceAid::None)),
+            Err(e) => e,
+        };
+        if self.devices.is_empty() {
+            return Err(format!("Operating point failed: {}", direct));
+        }
+
+        let mut iterations = 0;
+        if options.gmin_stepping {
+            if let Some(x) = self.gmin_stepping(size, options, &mut iterations) {
+                return Ok((x, iterations, ConvergenceAid::GminStepping));
+            }
+        }
+        if options.source_stepping {
+            if let Some(x) = self.source_stepping(size, options, &mut iterations) {
+                return Ok((x, iterations, ConvergenceAid::SourceStepping));
+            }
+        }
+        Err(format!("Operating point failed: {}", direct))
+    }
+
+    /// Solve with a large node shunt, then shrink it a decade at a time,
+    /// each solve starting from the previous one
+    fn gmin_stepping(
+        &self,
+        size: usize,
+        options: &DcOptions,
+        iterations: &mut usize,
+    ) -> Option<Vec<f64>> {
+        let mut x = vec![0.0; size];
+        let mut gshunt = GMIN_START;
+        loop {
+            let (next, n) = self.newton(&self.dc_matrix(gshunt, 1.0), x, options).ok()?;
+            *iterations += n;
+            x = next;
+            if gshunt <= GMIN {
+                return Some(x);
+            }
+            gshunt = (gshunt / 10.0).max(GMIN);
+        }
+    }
+
+    /// Ramp every independent source from zero (where x = 0 is the
+    /// solution) to full value, halving the increment after a failure
+    fn source_stepping(
+        &self,
+        size: usize,
+        options: &DcOptions,
+        iterations: &mut usize,
+    ) -> Option<Vec<f64>> {
+        let mut x = vec![0.0; size];
+        let (mut scale, mut increment): (f64, f64) = (0.0, 0.1);
+        while scale < 1.0 {
+            let next_scale = (scale + increment).min(1.0);
+            match self.newton(&self.dc_matrix(GMIN, next_scale), x.clone(), options) {
+                Ok((next, n)) => {
+                    *iterations += n;
+                    x = next;
+                    scale = next_scale;
+                    increment *= 2.0;
+                }
+                Err(_) => {
+                    increment /= 2.0;
+                    if increment < MIN_SOURCE_STEP {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(x)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // NEWTON-RAPHSON
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Solve `base` plus the linearized devices, starting from `x`
+    ///
+    /// Returns the solution and the number of iterations taken. Without
+    /// devices this is a single linear solve.
+    pub(super) fn newton(
+        &self,
+        base: &MNAMatrix,
+        mut x: Vec<f64>,
+        options: &DcOptions,
+    ) -> Result<(Vec<f64>, usize), String> {
+        if self.devices.is_empty() {
+            return base
+                .solve_sparse(&mut self.solver.borrow_mut())
+                .map(|x| (x, 1));
+        }
+
+        // One working matrix: after the first iteration the device entries
+        // are part of its pattern and each solve is a numeric refactor
+        let mut junctions = self.junctions(&x);
+        let mut m = base.clone();
+        for iteration in 1..=options.max_iterations {
+            if iteration > 1 {
+                m.assign(base);
+            }
+            let limited = self.stamp_devices(&mut m, &x, &mut junctions);
+            let next = m.solve_sparse(&mut self.solver.borrow_mut())?;
+            if next.iter().any(|v| !v.is_finite()) {
+                return Err(format!("Newton iteration {} diverged", iteration));
+            }
+
+            let converged = !limited
+                && next.iter().zip(&x).enumerate().all(|(i, (new, old))| {
+                    let abs = if i < self.num_nodes {
+                        options.vntol
+                    } else {
+                        options.abstol
+                    };
+                    (new - old).abs() <= options.reltol * new.abs().max(old.abs()) + abs
+                });
+            x = next;
+            if converged {
+                return Ok((x, iteration));
+            }
+        }
+        Err(format!(
+            "Newton iteration did not converge in {} iterations",
+            options.max_iterations
+        ))
+    }
+
+    /// Device junction voltages at solution `x`, the starting point for
+    /// voltage limiting ([Vd, -] diode, [Vbe, Vbc] BJT, [Vgs, Vds] MOSFET,
+    /// all in NPN/NMOS orientation)
+    fn junctions(&self, x: &[f64]) -> Vec<[f64; 2]> {
+        let v = |p: usize, n: usize| node_v(x, p) - node_v(x, n);
+        self.devices
+            .iter()
+            .map(|device| match *device {
+                Device::Diode { anode, cathode, .. } => [v(anode, cathode), 0.0],
+                Device::Bjt { c, b, e, model } => [model.sign() * v(b, e), model.sign() * v(b, c)],
+                Device::Mosfet { d, g, s, model } => {
+                    [model.sign() * v(g, s), model.sign() * v(d, s)]
+                }
+            })
+            .collect()
+    }
+
+    /// Stamp every device linearized about `x`, with junction voltages
+    /// limited against `junctions` (updated in place)
+    ///
+    /// Returns true if any junction voltage was limited.
+    fn stamp_devices(&self, m: &mut MNAMatrix, x: &[f64], junctions: &mut [[f64; 2]]) -> bool {
+        let raw = self.junctions(x);
+        let mut limited = false;
+        let mut limit = |v: f64, old: &mut f64, is: f64, nvt: f64| {
+            let (v, hit) = pnjlim(v, *old, nvt, critical_voltage(is, nvt));
+            limited |= hit;
+            *old = v;
+            v
+        };
+
+        for ((device, old), new) in self.devices.iter().zip(junctions).zip(raw) {
+            match *device {
+                Device::Diode {
+                    anode,
+                    cathode,
+                    model,
+                } => {
+                    let vd = limit(new[0], &mut old[0], model.is, model.nvt());
+                    let (id, gd) = model.evaluate(vd);
+                    stamp_linearized(
+                        m,
+                        [anode, cathode],
+                        [(anode, cathode)],
+                        [vd],
+                        [id, -id],
+                        [[gd], [-gd]],
+                    );
+                    m.stamp_conductance(anode, cathode, GMIN);
+                }
+                Device::Bjt { c, b, e, model } => {
+                    let vbe = limit(new[0], &mut old[0], model.is, model.nf * THERMAL_VOLTAGE);
+                    let vbc = limit(new[1], &mut old[1], model.is, model.nr * THERMAL_VOLTAGE);
+                    let q = model.evaluate(vbe, vbc);
+                    // I = s·i(s·v): currents flip with polarity, conductances do not
+                    let s = model.sign();
+                    let (ic, ib) = (s * q.ic, s * q.ib);
+                    stamp_linearized(
+                        m,
+                        [c, b, e],
+                        [(b, e), (b, c)],
+                        [s * vbe, s * vbc],
+                        [ic, ib, -(ic + ib)],
+                        [
+                            [q.dic_dvbe, q.dic_dvbc],
+                            [q.dib_dvbe, q.dib_dvbc],
+                            [-(q.dic_dvbe + q.dib_dvbe), -(q.dic_dvbc + q.dib_dvbc)],
+                        ],
+                    );
+                    m.stamp_conductance(b, e, GMIN);
+                    m.stamp_conductance(b, c, GMIN);
+                }
+                Device::Mosfet { d, g, s, model } => {
+                    let (vgs, vds) = (new[0], new[1]);
+                    *old = new;
+                    let (id, gm, gds) = model.evaluate(vgs, vds);
+                    let sign = model.sign();
+                    stamp_linearized(
+                        m,
+                        [d, s],
+                        [(g, s), (d, s)],
+                        [sign * vgs, sign * vds],
+                        [sign * id, -sign * id],
+                        [[gm, gds], [-gm, -gds]],
+                    );
+                    m.stamp_conductance(d, s, GMIN);
+                }
+            }
+        }
+        limited
+    }
+}
+
+/// Stamp terminal currents (into the device) linearized about the
+/// controlling voltages `v0`:
+///   Iₜ ≈ i[t] + Σₖ g[t][k]·(vₖ - v0[k]),   vₖ = V(ctrl[k].0) - V(ctrl[k].1)
+fn stamp_linearized<const T: usize, const K: usize>(
+    m: &mut MNAMatrix,
+    terminals: [usize; T],
+    ctrl: [(usize, usize); K],
+    v0: [f64; K],
+    i: [f64; T],
+    g: [[f64; K]; T],
+) {
+    for ((&node, &current), gt) in terminals.iter().zip(&i).zip(&g) {
+        if node == 0 {
+            continue;
+        }
+        let row = node - 1;
+        let mut ieq = current;
+        for ((&gk, &(p, n)), &vk) in gt.iter().zip(&ctrl).zip(&v0) {
+            ieq -= gk * vk;
+            if p > 0 {
+                m.matrix.add(row, p - 1, gk);
+            }
+            if n > 0 {
+                m.matrix.add(row, n - 1, -gk);
+            }
+        }
+        m.rhs[row] -= ieq;
+    }
+}
+
+fn unsupported(name: &str) -> String {
+    format!(
+        "{}: only constant and linear behavioral sources are supported in DC and transient analysis",
+        name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::devices::{BjtPolarity, MosfetPolarity};
+    use super::*;
+    use crate::power::components::{diode_database, mosfet_database};
+
+    fn source(name: &str, p: &str, v: f64) -> Element {
+        Element::VoltageSource {
+            name: name.to_string(),
+            node_p: p.to_string(),
+            node_n: "0".to_string(),
+            value: SourceValue::DC(v),
+        }
+    }
+
+    fn resistor(name: &str, p: &str, n: &str, r: f64) -> Element {
+        Element::Resistor {
+            name: name.to_string(),
+            node_p: p.to_string(),
+            node_n: n.to_string(),
+            value: r,
+        }
+    }
+
+    fn diode(name: &str, p: &str, n: &str, model: DiodeModel) -> Element {
+        Element::Diode {
+            name: name.to_string(),
+            node_p: p.to_string(),
+            node_n: n.to_string(),
+            model,
+        }
+    }
+
+    fn bjt_amplifier(model: BjtModel) -> Netlist {
+        // Vcc = 10 V, Rc = 1 kΩ, base fed from 10 V through 430 kΩ
+        let mut netlist = Netlist::new("CE bias".to_string());
+        netlist.add_element(source("VCC", "vcc", 10.0));
+        netlist.add_element(resistor("RC", "vcc", "c", 1e3));
+        netlist.add_element(resistor("RB", "vcc", "b", 430e3));
+        netlist.add_element(Element::Bjt {
+            name: "Q1".to_string(),
+            node_c: "c".to_string(),
+            node_b: "b".to_string(),
+            node_e: "0".to_string(),
+            model,
+        });
+        netlist
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // LINEAR AND DIODE CIRCUITS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_linear_circuit_single_solve() {
+        let mut netlist = Netlist::new("Divider".to_string());
+        netlist.add_element(source("V1", "in", 10.0));
+        netlist.add_element(resistor("R1", "in", "out", 1e3));
+        netlist.add_element(resistor("R2", "out", "0", 1e3));
+
+        let op = dc_operating_point(&netlist).unwrap();
+        assert_eq!(op.iterations, 1);
+        assert_eq!(op.aid, ConvergenceAid::None);
+        assert!((op.voltage("out").unwrap() - 5.0).abs() < 1e-6);
+        assert_eq!(op.voltage("0"), Some(0.0));
+        assert_eq!(op.voltage("nope"), None);
+        // Source current, in MNA sign convention
+        assert!((op.solution[2] + 5e-3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_diode_resistor_kcl() {
+        let model = DiodeModel::default();
+        let mut netlist = Netlist::new("Diode".to_string());
+        netlist.add_element(source("V1", "in", 5.0));
+        netlist.add_element(resistor("R1", "in", "a", 1e3));
+        netlist.add_element(diode("D1", "a", "0", model.clone()));
+
+        let op = dc_operating_point(&netlist).unwrap();
+        assert_eq!(op.aid, ConvergenceAid::None);
+        let vd = op.voltage("a").unwrap();
+        assert!(vd > 0.6 && vd < 0.8, "Vd = {}", vd);
+        let i_r = (5.0 - vd) / 1e3;
+        let (i_d, _) = model.evaluate(vd);
+        assert!((i_r - i_d).abs() < 1e-3 * i_r, "{} vs {}", i_r, i_d);
+    }
+
+    #[test]
+    fn test_newton_reuses_sparse_ordering() {
+        let mut netlist = Netlist::new("Diode".to_string());
+        netlist.add_element(source("V1", "in", 5.0));
+        netlist.add_element(resistor("R1", "in", "a", 1e3));
+        netlist.add_element(diode("D1", "a", "0", DiodeModel::default()));
+
+        let circuit = Circuit::new(&netlist).unwrap();
+        let (_, iterations, _) = circuit.operating_point(&DcOptions::default()).unwrap();
+        let solver = circuit.solver.borrow();
+        assert_eq!(solver.factorizations, 1);
+        assert_eq!(solver.refactorizations, iterations - 1);
+    }
+
+    #[test]
+    fn test_reverse_diode_blocks() {
+        let mut netlist = Netlist::new("Reverse".to_string());
+        netlist.add_element(source("V1", "in", 5.0));
+        netlist.add_element(resistor("R1", "in", "k", 1e3));
+        netlist.add_element(diode("D1", "0", "k", DiodeModel::default()));
+
+        let op = dc_operating_point(&netlist).unwrap();
+        assert!((op.voltage("k").unwrap() - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_datasheet_diode_forward_drop() {
+        // Forcing the rated current reproduces the datasheet Vf
+        let spec = &diode_database()[0];
+        let mut netlist = Netlist::new("Vf".to_string());
+        netlist.add_element(Element::CurrentSource {
+            name: "I1".to_string(),
+            node_p: "0".to_string(),
+            node_n: "a".to_string(),
+            value: SourceValue::DC(spec.if_avg),
+        });
+        netlist.add_element(diode("D1", "a", "0", DiodeModel::from_spec(spec)));
+
+        let op = dc_operating_point(&netlist).unwrap();
+        let vf = op.voltage("a").unwrap();
+        assert!((vf - spec.vf_typical).abs() < 1e-3, "Vf = {}", vf);
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // TRANSISTORS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_bjt_common_emitter_bias() {
+        let netlist = bjt_amplifier(BjtModel::default());
+        let op = dc_operating_point(&netlist).unwrap();
+
+        let vbe = op.voltage("b").unwrap();
+        let vc = op.voltage("c").unwrap();
+        assert!(vbe > 0.5 && vbe < 0.8, "Vbe = {}", vbe);
+        // Ib ≈ (10 - Vbe)/430k ≈ 21.6 µA, Ic = βf·Ib ≈ 2.16 mA, Vc ≈ 7.8 V
+        let ib = (10.0 - vbe) / 430e3;
+        let ic = (10.0 - vc) / 1e3;
+        assert!((ic / ib - 100.0).abs() < 0.5, "β = {}", ic / ib);
+    }
+
+    #[test]
+    fn test_pnp_mirrors_npn() {
+        let npn = dc_operating_point(&bjt_amplifier(BjtModel::default())).unwrap();
+
+        // Same circuit with every source and the transistor flipped
+        let mut netlist = bjt_amplifier(BjtModel::new(BjtPolarity::Pnp));
+        netlist.elements[0] = source("VCC", "vcc", -10.0);
+        let pnp = dc_operating_point(&netlist).unwrap();
+
+        for node in ["b", "c"] {
+            let (a, b) = (npn.voltage(node).unwrap(), pnp.voltage(node).unwrap());
+            assert!((a + b).abs() < 1e-6, "{}: {} vs {}", node, a, b);
+        }
+    }
+
+    #[test]
+    fn test_mosfet_switch_from_datasheet() {
+        // 12 V into a 10 Ω load, gate driven to 10 V: Vds = I·Rds(on)
+        let spec = &mosfet_database()[0];
+        let mut netlist = Netlist::new("Switch".to_string());
+        netlist.add_element(source("VDD", "vdd", 12.0));
+        netlist.add_element(source("VG", "g", 10.0));
+        netlist.add_element(resistor("RL", "vdd", "d", 10.0));
+        netlist.add_element(Element::Mosfet {
+            name: "M1".to_string(),
+            node_d: "d".to_string(),
+            node_g: "g".to_string(),
+            node_s: "0".to_string(),
+            model: MosfetModel::from_spec(spec),
+        });
+
+        let op = dc_operating_point(&netlist).unwrap();
+        let vds = op.voltage("d").unwrap();
+        let expected = 12.0 * spec.rds_on_25c / (10.0 + spec.rds_on_25c);
+        assert!(
+            (vds - expected).abs() < 0.01 * expected,
+            "Vds = {}, expected {}",
+            vds,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_pmos_saturation_current() {
+        // Source at 5 V, gate at 0 V: |Vov| = 3 V, Id = Kp/2·9 = 0.9 mA
+        let mut netlist = Netlist::new("PMOS".to_string());
+        netlist.add_element(source("VDD", "vdd", 5.0));
+        netlist.add_element(resistor("RD", "d", "0", 1e3));
+        netlist.add_element(Element::Mosfet {
+            name: "M1".to_string(),
+            node_d: "d".to_string(),
+            node_g: "0".to_string(),
+            node_s: "vdd".to_string(),
+            model: MosfetModel {
+                vto: 2.0,
+                kp: 2e-4,
+                ..MosfetModel::new(MosfetPolarity::Pmos)
+            },
+        });
+
+        let op = dc_operating_point(&netlist).unwrap();
+        let vd = op.voltage("d").unwrap();
+        assert!((vd - 0.9).abs() < 1e-3, "Vd = {}", vd);
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // CONVERGENCE AIDS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Diode stack across a stiff supply: hard from a zero start
+    fn diode_stack() -> Netlist {
+        let mut netlist = Netlist::new("Stack".to_string());
+        netlist.add_element(source("V1", "n0", 100.0));
+        for k in 0..4 {
+            netlist.add_element(diode(
+                &format!("D{}", k),
+                &format!("n{}", k),
+                &format!("n{}", k + 1),
+                DiodeModel::default(),
+            ));
+        }
+        netlist.add_element(resistor("R1", "n4", "0", 1.0));
+        netlist
+    }
+
+    #[test]
+    fn test_gmin_and_source_stepping_agree() {
+        let netlist = diode_stack();
+        let circuit = Circuit::new(&netlist).unwrap();
+        let options = DcOptions::default();
+        let size = circuit.num_nodes + circuit.num_vsources;
+
+        let (direct, _, _) = circuit.operating_point(&options).unwrap();
+        let mut iterations = 0;
+        let gmin = circuit
+            .gmin_stepping(size, &options, &mut iterations)
+            .unwrap();
+        let source = circuit
+            .source_stepping(size, &options, &mut iterations)
+            .unwrap();
+        assert!(iterations > 0);
+        for ((a, b), c) in direct.iter().zip(&gmin).zip(&source) {
+            assert!((a - b).abs() < 1e-3 * a.abs().max(1e-3), "{} vs {}", a, b);
+            assert!((a - c).abs() < 1e-3 * a.abs().max(1e-3), "{} vs {}", a, c);
+        }
+        // Four junctions drop a few volts, the rest is across the load
+        let out = direct[4];
+        assert!(out > 95.0 && out < 98.0, "Vout = {}", out);
+    }
+
+    #[test]
+    fn test_fallback_when_direct_solve_fails() {
+        // Too few iterations for the direct solve from 0 V; each
+        // continuation step starts close enough to converge
+        let netlist = diode_stack();
+        let options = DcOptions {
+            max_iterations: 6,
+            ..Default::default()
+        };
+        let op = dc_operating_point_with(&netlist, &options).unwrap();
+        assert_ne!(op.aid, ConvergenceAid::None);
+
+        let no_aids = DcOptions {
+            gmin_stepping: false,
+            source_stepping: false,
+            ..options
+        };
+        assert!(dc_operating_point_with(&netlist, &no_aids).is_err());
+    }
+}
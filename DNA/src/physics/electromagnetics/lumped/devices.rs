@@ -0,0 +1,463 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: devices.rs | DNA/src/physics/electromagnetics/lumped/devices.rs
+//! PURPOSE: Semiconductor device models (diode, BJT, MOSFET) for Newton solves
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Semiconductor device models (diode, BJT, MOSFET) for Newton solves
+//!
+//! LAYER: DNA → PHYSICS → ELECTROMAGNETICS → LUMPED
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM: Static large-signal device equations                             │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Diode:   I = Is·(exp(V/(n·Vt)) - 1), optional reverse breakdown at -BV      │
+//! │                                                                             │
+//! │ BJT:     Ebers-Moll transport form with forward Early voltage               │
+//! │          (Gummel-Poon level 1 without high-level injection)                 │
+//! │          If = Is·(exp(Vbe/(Nf·Vt)) - 1),  Ir = Is·(exp(Vbc/(Nr·Vt)) - 1)    │
+//! │          Ic = (If - Ir)·(1 - Vbc/VAF) - Ir/βr,  Ib = If/βf + Ir/βr          │
+//! │                                                                             │
+//! │ MOSFET:  Shichman-Hodges (level 1), body tied to source                     │
+//! │          triode:     Id = Kp·(Vov·Vds - Vds²/2)·(1 + λ·Vds)                 │
+//! │          saturation: Id = Kp/2·Vov²·(1 + λ·Vds)                             │
+//! │          drain and source swap roles when Vds < 0                           │
+//! │                                                                             │
+//! │ pnjlim: junction voltage limiting between Newton iterations (SPICE2)        │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ DiodeModel         Diode model card (.model D), fit from DiodeSpec          │
+//! │ BjtModel           BJT model card (.model NPN / PNP)                        │
+//! │ MosfetModel        MOSFET model card (.model NMOS / PMOS), from MOSFETSpec  │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! DEPENDS ON:
+//!   • power::components → DiodeSpec, MOSFETSpec datasheet parameters
+//!
+//! USED BY:
+//!   • physics/electromagnetics/lumped/netlist.rs → Diode, Bjt, Mosfet elements
+//!   • physics/electromagnetics/lumped/dc.rs → Newton linearization
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// CODE BELOW - Optimized for ML development
+// ─────────────────────────────────────────────────────────────────────────────────
+
+use crate::power::components::{DiodeSpec, DiodeType, MOSFETSpec};
+use serde::{Deserialize, Serialize};
+
+/// Thermal voltage kT/q at the 27 °C nominal temperature (V)
+pub const THERMAL_VOLTAGE: f64 = 0.025852;
+
+/// Gate drive at which MOSFET datasheets specify Rds(on) (V)
+const RDS_ON_VGS: f64 = 10.0;
+
+/// Diode model card
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiodeModel {
+    /// Saturation current (A)
+    pub is: f64,
+    /// Emission coefficient
+    pub n: f64,
+    /// Reverse breakdown voltage (V), `None` for no breakdown
+    pub bv: Option<f64>,
+    /// Current at the breakdown voltage (A)
+    pub ibv: f64,
+}
+
+impl Default for DiodeModel {
+    fn default() -> Self {
+        Self {
+            is: 1e-14,
+            n: 1.0,
+            bv: None,
+            ibv: 1e-3,
+        }
+    }
+}
+
+impl DiodeModel {
+    /// Fit a model card to datasheet values
+    ///
+    /// The emission coefficient comes from the diode technology and Is is
+    /// chosen so that the typical forward drop is reached at the rated
+    /// average current. VRRM becomes the breakdown voltage.
+    pub fn from_spec(spec: &DiodeSpec) -> Self {
+        let n = match spec.diode_type {
+            DiodeType::Schottky => 1.1,
+            DiodeType::SiCSchottky => 1.3,
+            DiodeType::Standard | DiodeType::FastRecovery => 1.8,
+            DiodeType::Ultrafast => 2.0,
+        };
+        let is = spec.if_avg / ((spec.vf_typical / (n * THERMAL_VOLTAGE)).exp() - 1.0);
+        Self {
+            is,
+            n,
+            bv: (spec.vrrm > 0.0).then_some(spec.vrrm),
+            ..Default::default()
+        }
+    }
+
+    /// Thermal voltage scaled by the emission coefficient
+    pub fn nvt(&self) -> f64 {
+        self.n * THERMAL_VOLTAGE
+    }
+
+    /// Current and small-signal conductance at junction voltage `vd`
+    pub fn evaluate(&self, vd: f64) -> (f64, f64) {
+        let nvt = self.nvt();
+        let e = (vd / nvt).exp();
+        let mut id = self.is * (e - 1.0);
+        let mut gd = self.is * e / nvt;
+        if let Some(bv) = self.bv {
+            let e = (-(vd + bv) / nvt).exp();
+            id -= self.ibv * e;
+            gd += self.ibv * e / nvt;
+        }
+        (id, gd)
+    }
+}
+
+/// BJT polarity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BjtPolarity {
+    Npn,
+    Pnp,
+}
+
+/// BJT model card
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BjtModel {
+    pub polarity: BjtPolarity,
+    /// Transport saturation current (A)
+    pub is: f64,
+    /// Ideal forward current gain
+    pub bf: f64,
+    /// Ideal reverse current gain
+    pub br: f64,
+    /// Forward emission coefficient
+    pub nf: f64,
+    /// Reverse emission coefficient
+    pub nr: f64,
+    /// Forward Early voltage (V), `None` for no base-width modulation
+    pub vaf: Option<f64>,
+}
+
+impl Default for BjtModel {
+    fn default() -> Self {
+        Self::new(BjtPolarity::Npn)
+    }
+}
+
+/// BJT currents and their derivatives at one bias point
+///
+/// Voltages and currents are in NPN orientation; a PNP flips all signs.
+#[derive(Clone, Copy, Debug)]
+pub struct BjtEvaluation {
+    /// Collector current into the device (A)
+    pub ic: f64,
+    /// Base current into the device (A)
+    pub ib: f64,
+    pub dic_dvbe: f64,
+    pub dic_dvbc: f64,
+    pub dib_dvbe: f64,
+    pub dib_dvbc: f64,
+}
+
+impl BjtModel {
+    /// SPICE default parameters for the given polarity
+    pub fn new(polarity: BjtPolarity) -> Self {
+        Self {
+            polarity,
+            is: 1e-16,
+            bf: 100.0,
+            br: 1.0,
+            nf: 1.0,
+            nr: 1.0,
+            vaf: None,
+        }
+    }
+
+    /// +1 for NPN, -1 for PNP
+    pub fn sign(&self) -> f64 {
+        match self.polarity {
+            BjtPolarity::Npn => 1.0,
+            BjtPolarity::Pnp => -1.0,
+        }
+    }
+
+    /// Terminal currents at junction voltages `vbe`, `vbc` (NPN orientation)
+    pub fn evaluate(&self, vbe: f64, vbc: f64) -> BjtEvaluation {
+        let (nf_vt, nr_vt) = (self.nf * THERMAL_VOLTAGE, self.nr * THERMAL_VOLTAGE);
+        let ef = (vbe / nf_vt).exp();
+        let er = (vbc / nr_vt).exp();
+        let (i_f, gf) = (self.is * (ef - 1.0), self.is * ef / nf_vt);
+        let (i_r, gr) = (self.is * (er - 1.0), self.is * er / nr_vt);
+        let (early, dearly) = match self.vaf {
+            Some(vaf) => (1.0 - vbc / vaf, -1.0 / vaf),
+            None => (1.0, 0.0),
+        };
+
+        BjtEvaluation {
+            ic: (i_f - i_r) * early - i_r / self.br,
+            ib: i_f / self.bf + i_r / self.br,
+            dic_dvbe: gf * early,
+            dic_dvbc: -gr * early + (i_f - i_r) * dearly - gr / self.br,
+            dib_dvbe: gf / self.bf,
+            dib_dvbc: gr / self.br,
+        }
+    }
+}
+
+/// MOSFET polarity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MosfetPolarity {
+    Nmos,
+    Pmos,
+}
+
+/// MOSFET model card (level 1)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MosfetModel {
+    pub polarity: MosfetPolarity,
+    /// Threshold voltage magnitude (V)
+    pub vto: f64,
+    /// Transconductance parameter KP·W/L (A/V²)
+    pub kp: f64,
+    /// Channel-length modulation (1/V)
+    pub lambda: f64,
+}
+
+impl Default for MosfetModel {
+    fn default() -> Self {
+        Self::new(MosfetPolarity::Nmos)
+    }
+}
+
+impl MosfetModel {
+    /// Default parameters for the given polarity
+    pub fn new(polarity: MosfetPolarity) -> Self {
+        Self {
+            polarity,
+            vto: 1.0,
+            kp: 2e-5,
+            lambda: 0.0,
+        }
+    }
+
+    /// Fit an N-channel model card to datasheet values
+    ///
+    /// Vto is the gate threshold and Kp is chosen so that the deep-triode
+    /// resistance 1/(Kp·(Vgs - Vto)) equals Rds(on) at Vgs = 10 V.
+    pub fn from_spec(spec: &MOSFETSpec) -> Self {
+        Self {
+            polarity: MosfetPolarity::Nmos,
+            vto: spec.vgs_th,
+            kp: 1.0 / (spec.rds_on_25c * (RDS_ON_VGS - spec.vgs_th)),
+            lambda: 0.0,
+        }
+    }
+
+    /// +1 for NMOS, -1 for PMOS
+    pub fn sign(&self) -> f64 {
+        match self.polarity {
+            MosfetPolarity::Nmos => 1.0,
+            MosfetPolarity::Pmos => -1.0,
+        }
+    }
+
+    /// Drain current and its derivatives (Id, ∂Id/∂Vgs, ∂Id/∂Vds)
+    ///
+    /// Voltages and current are in NMOS orientation; a PMOS flips all signs.
+    pub fn evaluate(&self, vgs: f64, vds: f64) -> (f64, f64, f64) {
+        if vds < 0.0 {
+            // Source and drain swap: Id(vgs, vds) = -Id'(vgd, -vds)
+            let (id, gm, gds) = self.forward(vgs - vds, -vds);
+            return (-id, -gm, gm + gds);
+        }
+        self.forward(vgs, vds)
+    }
+
+    /// Shichman-Hodges equations for vds ≥ 0
+    fn forward(&self, vgs: f64, vds: f64) -> (f64, f64, f64) {
+        let vov = vgs - self.vto;
+        if vov <= 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+        let clm = 1.0 + self.lambda * vds;
+        if vds < vov {
+            let core = vov * vds - 0.5 * vds * vds;
+            (
+                self.kp * core * clm,
+                self.kp * vds * clm,
+                self.kp * ((vov - vds) * clm + core * self.lambda),
+            )
+        } else {
+            let core = 0.5 * vov * vov;
+            (
+                self.kp * core * clm,
+                self.kp * vov * clm,
+                self.kp * core * self.lambda,
+            )
+        }
+    }
+}
+
+/// Critical voltage above which junction steps are limited
+pub fn critical_voltage(is: f64, nvt: f64) -> f64 {
+    nvt * (nvt / (std::f64::consts::SQRT_2 * is)).ln()
+}
+
+/// Limit a junction voltage update to keep exp() from overshooting
+///
+/// Returns the limited voltage and whether limiting was applied.
+pub fn pnjlim(v_new: f64, v_old: f64, nvt: f64, vcrit: f64) -> (f64, bool) {
+    if v_new <= vcrit || (v_new - v_old).abs() <= 2.0 * nvt {
+        return (v_new, false);
+    }
+    let limited = if v_old > 0.0 {
+        let arg = 1.0 + (v_new - v_old) / nvt;
+        if arg > 0.0 {
+            v_old + nvt * arg.ln()
+        } else {
+            vcrit
+        }
+    } else {
+        nvt * (v_new / nvt).ln()
+    };
+    (limited, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::components::{diode_database, mosfet_database};
+
+    #[test]
+    fn test_diode_fit_reproduces_datasheet_point() {
+        for spec in diode_database() {
+            let model = DiodeModel::from_spec(&spec);
+            let (id, _) = model.evaluate(spec.vf_typical);
+            assert!(
+                (id - spec.if_avg).abs() < 1e-6 * spec.if_avg,
+                "{}: I(Vf) = {}",
+                spec.part_number,
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn test_diode_conductance_matches_derivative() {
+        let model = DiodeModel {
+            bv: Some(50.0),
+            ..Default::default()
+        };
+        for vd in [-50.5, -10.0, 0.0, 0.4, 0.7] {
+            let h = 1e-7;
+            let numeric = (model.evaluate(vd + h).0 - model.evaluate(vd - h).0) / (2.0 * h);
+            let (_, gd) = model.evaluate(vd);
+            assert!(
+                (gd - numeric).abs() <= 1e-5 * gd.abs() + 1e-15,
+                "vd = {}",
+                vd
+            );
+        }
+        // Breakdown conducts in reverse
+        assert!(model.evaluate(-51.0).0 < -1e-3);
+    }
+
+    #[test]
+    fn test_bjt_derivatives() {
+        let model = BjtModel {
+            vaf: Some(50.0),
+            br: 2.0,
+            ..Default::default()
+        };
+        let h = 1e-7;
+        for (vbe, vbc) in [(0.65, -5.0), (0.7, 0.6), (-1.0, 0.5)] {
+            let e = model.evaluate(vbe, vbc);
+            let dbe = |f: fn(&BjtEvaluation) -> f64| {
+                (f(&model.evaluate(vbe + h, vbc)) - f(&model.evaluate(vbe - h, vbc))) / (2.0 * h)
+            };
+            let dbc = |f: fn(&BjtEvaluation) -> f64| {
+                (f(&model.evaluate(vbe, vbc + h)) - f(&model.evaluate(vbe, vbc - h))) / (2.0 * h)
+            };
+            let close = |a: f64, b: f64| (a - b).abs() <= 1e-4 * a.abs().max(b.abs()) + 1e-12;
+            assert!(close(e.dic_dvbe, dbe(|e| e.ic)));
+            assert!(close(e.dic_dvbc, dbc(|e| e.ic)));
+            assert!(close(e.dib_dvbe, dbe(|e| e.ib)));
+            assert!(close(e.dib_dvbc, dbc(|e| e.ib)));
+        }
+        // Forward active: Ic ≈ βf·Ib
+        let e = BjtModel::default().evaluate(0.7, -5.0);
+        assert!((e.ic / e.ib - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mosfet_regions() {
+        let model = MosfetModel {
+            vto: 2.0,
+            kp: 0.5,
+            lambda: 0.0,
+            ..Default::default()
+        };
+        // Cutoff
+        assert_eq!(model.evaluate(1.0, 5.0).0, 0.0);
+        // Saturation: Kp/2·Vov² = 0.25·9
+        assert!((model.evaluate(5.0, 10.0).0 - 2.25).abs() < 1e-12);
+        // Triode: Kp·(3·1 - 0.5) = 1.25
+        assert!((model.evaluate(5.0, 1.0).0 - 1.25).abs() < 1e-12);
+        // Reverse conduction is antisymmetric in Vds
+        let (fwd, _, _) = model.evaluate(5.0, 0.5);
+        let (rev, _, _) = model.evaluate(5.0 - 0.5, -0.5);
+        assert!((fwd + rev).abs() < 1e-12);
+
+        let model = MosfetModel {
+            lambda: 0.02,
+            ..model
+        };
+        let h = 1e-7;
+        for (vgs, vds) in [(5.0, 1.0), (5.0, 8.0), (4.0, -1.0)] {
+            let (_, gm, gds) = model.evaluate(vgs, vds);
+            let ngm = (model.evaluate(vgs + h, vds).0 - model.evaluate(vgs - h, vds).0) / (2.0 * h);
+            let ngds =
+                (model.evaluate(vgs, vds + h).0 - model.evaluate(vgs, vds - h).0) / (2.0 * h);
+            assert!((gm - ngm).abs() < 1e-5, "gm at ({}, {})", vgs, vds);
+            assert!((gds - ngds).abs() < 1e-5, "gds at ({}, {})", vgs, vds);
+        }
+    }
+
+    #[test]
+    fn test_mosfet_fit_matches_rds_on() {
+        for spec in mosfet_database() {
+            let model = MosfetModel::from_spec(&spec);
+            let (_, _, gds) = model.evaluate(RDS_ON_VGS, 0.0);
+            assert!(
+                (1.0 / gds - spec.rds_on_25c).abs() < 1e-9 * spec.rds_on_25c,
+                "{}",
+                spec.part_number
+            );
+        }
+    }
+
+    #[test]
+    fn test_pnjlim() {
+        let nvt = THERMAL_VOLTAGE;
+        let vcrit = critical_voltage(1e-14, nvt);
+        // Small steps and reverse bias pass through
+        assert_eq!(pnjlim(0.5, 0.49, nvt, vcrit), (0.5, false));
+        assert_eq!(pnjlim(-5.0, 0.0, nvt, vcrit), (-5.0, false));
+        // A jump from 0 V to 10 V is pulled back near the knee
+        let (v, limited) = pnjlim(10.0, 0.0, nvt, vcrit);
+        assert!(limited && v < 0.2, "v = {}", v);
+        let (v, limited) = pnjlim(3.0, 0.7, nvt, vcrit);
+        assert!(limited && v > 0.7 && v < 0.9, "v = {}", v);
+    }
+}
@@ -23,7 +23,9 @@
 //! │ [  G   B ] [ V ] = [ I ]                                                    │
 //! │ [ B^T  0 ] [ J ]   [ E ]                                                    │
 //! │                                                                             │
-//! │ Solved using LU decomposition with partial pivoting                         │
+//! │ Stamps go straight into a sparse matrix; solved with sparse LU reusing its  │
+//! │ Markowitz ordering across solves (see sparse.rs), or densified for a plain  │
+//! │ LU with partial pivoting                                                    │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
@@ -34,6 +36,7 @@
 //!
 //! DEPENDS ON:
 //!   • std → Basic types
+//!   • super::sparse → SparseMatrix, SparseSolver
 //!
 //! USED BY:
 //!   • physics/electromagnetics/lumped/ac.rs → Extends for AC
@@ -45,6 +48,8 @@
 // CODE BELOW - Optimized for ML development
 // ─────────────────────────────────────────────────────────────────────────────────
 
+use super::sparse::{SparseMatrix, SparseSolver};
+
 /// Modified Nodal Analysis (MNA) Matrix
 ///
 /// The MNA formulation creates a system of linear equations:
@@ -63,7 +68,7 @@ pub struct MNAMatrix {
     /// Number of voltage sources
     pub num_vsources: usize,
     /// Matrix entries [size x size]
-    pub matrix: Vec<Vec<f64>>,
+    pub matrix: SparseMatrix<f64>,
     /// Right-hand side vector [size]
     pub rhs: Vec<f64>,
 }
@@ -72,7 +77,7 @@ impl MNAMatrix {
     /// Create a new MNA matrix
     pub fn new(num_nodes: usize, num_vsources: usize) -> Self {
         let size = num_nodes + num_vsources;
-        let matrix = vec![vec![0.0; size]; size];
+        let matrix = SparseMatrix::new(size);
         let rhs = vec![0.0; size];
 
         Self {
@@ -97,16 +102,16 @@ impl MNAMatrix {
         let g = 1.0 / resistance;
 
         if node_p > 0 {
-            self.matrix[node_p - 1][node_p - 1] += g;
+            self.matrix.add(node_p - 1, node_p - 1, g);
             if node_n > 0 {
-                self.matrix[node_p - 1][node_n - 1] -= g;
+                self.matrix.add(node_p - 1, node_n - 1, -g);
             }
         }
 
         if node_n > 0 {
-            self.matrix[node_n - 1][node_n - 1] += g;
+            self.matrix.add(node_n - 1, node_n - 1, g);
             if node_p > 0 {
-                self.matrix[node_n - 1][node_p - 1] -= g;
+                self.matrix.add(node_n - 1, node_p - 1, -g);
             }
         }
     }
@@ -114,16 +119,16 @@ impl MNAMatrix {
     /// Stamp a conductance (G-element)
     pub fn stamp_conductance(&mut self, node_p: usize, node_n: usize, conductance: f64) {
         if node_p > 0 {
-            self.matrix[node_p - 1][node_p - 1] += conductance;
+            self.matrix.add(node_p - 1, node_p - 1, conductance);
             if node_n > 0 {
-                self.matrix[node_p - 1][node_n - 1] -= conductance;
+                self.matrix.add(node_p - 1, node_n - 1, -conductance);
             }
         }
 
         if node_n > 0 {
-            self.matrix[node_n - 1][node_n - 1] += conductance;
+            self.matrix.add(node_n - 1, node_n - 1, conductance);
             if node_p > 0 {
-                self.matrix[node_n - 1][node_p - 1] -= conductance;
+                self.matrix.add(node_n - 1, node_p - 1, -conductance);
             }
         }
     }
@@ -147,13 +152,13 @@ impl MNAMatrix {
         let vs_row = self.num_nodes + vs_idx;
 
         if node_p > 0 {
-            self.matrix[node_p - 1][vs_row] += 1.0;
-            self.matrix[vs_row][node_p - 1] += 1.0;
+            self.matrix.add(node_p - 1, vs_row, 1.0);
+            self.matrix.add(vs_row, node_p - 1, 1.0);
         }
 
         if node_n > 0 {
-            self.matrix[node_n - 1][vs_row] -= 1.0;
-            self.matrix[vs_row][node_n - 1] -= 1.0;
+            self.matrix.add(node_n - 1, vs_row, -1.0);
+            self.matrix.add(vs_row, node_n - 1, -1.0);
         }
 
         self.rhs[vs_row] = voltage;
@@ -188,20 +193,20 @@ impl MNAMatrix {
 
         // Output connections (like voltage source)
         if node_out_p > 0 {
-            self.matrix[node_out_p - 1][vs_row] += 1.0;
-            self.matrix[vs_row][node_out_p - 1] += 1.0;
+            self.matrix.add(node_out_p - 1, vs_row, 1.0);
+            self.matrix.add(vs_row, node_out_p - 1, 1.0);
         }
         if node_out_n > 0 {
-            self.matrix[node_out_n - 1][vs_row] -= 1.0;
-            self.matrix[vs_row][node_out_n - 1] -= 1.0;
+            self.matrix.add(node_out_n - 1, vs_row, -1.0);
+            self.matrix.add(vs_row, node_out_n - 1, -1.0);
         }
 
         // Control voltage dependency
         if node_ctrl_p > 0 {
-            self.matrix[vs_row][node_ctrl_p - 1] -= gain;
+            self.matrix.add(vs_row, node_ctrl_p - 1, -gain);
         }
         if node_ctrl_n > 0 {
-            self.matrix[vs_row][node_ctrl_n - 1] += gain;
+            self.matrix.add(vs_row, node_ctrl_n - 1, gain);
         }
     }
 
@@ -218,16 +223,20 @@ impl MNAMatrix {
     ) {
         // VCCS is like a transconductance between control and output nodes
         if node_out_p > 0 && node_ctrl_p > 0 {
-            self.matrix[node_out_p - 1][node_ctrl_p - 1] += transconductance;
+            self.matrix
+                .add(node_out_p - 1, node_ctrl_p - 1, transconductance);
         }
         if node_out_p > 0 && node_ctrl_n > 0 {
-            self.matrix[node_out_p - 1][node_ctrl_n - 1] -= transconductance;
+            self.matrix
+                .add(node_out_p - 1, node_ctrl_n - 1, -transconductance);
         }
         if node_out_n > 0 && node_ctrl_p > 0 {
-            self.matrix[node_out_n - 1][node_ctrl_p - 1] -= transconductance;
+            self.matrix
+                .add(node_out_n - 1, node_ctrl_p - 1, -transconductance);
         }
         if node_out_n > 0 && node_ctrl_n > 0 {
-            self.matrix[node_out_n - 1][node_ctrl_n - 1] += transconductance;
+            self.matrix
+                .add(node_out_n - 1, node_ctrl_n - 1, transconductance);
         }
     }
 
@@ -235,8 +244,8 @@ impl MNAMatrix {
     ///
     /// Returns the solution vector [V1, V2, ..., Vn, I_vs1, I_vs2, ...]
     pub fn solve(&self) -> Result<Vec<f64>, String> {
-        // Create copies for decomposition
-        let mut a = self.matrix.clone();
+        // Dense copy for decomposition
+        let mut a = self.matrix.to_dense();
         let b = self.rhs.clone();
         let n = self.size;
 
@@ -306,6 +315,18 @@ impl MNAMatrix {
 
         Ok(x)
     }
+
+    /// Solve using a sparse LU, reusing `solver`'s ordering when the pattern allows
+    pub fn solve_sparse(&self, solver: &mut SparseSolver<f64>) -> Result<Vec<f64>, String> {
+        solver.solve(&self.matrix, &self.rhs)
+    }
+
+    /// Copy `other`'s entries and right-hand side into this matrix, keeping
+    /// any entries stamped here since (zeroed) so the pattern stays put
+    pub fn assign(&mut self, other: &MNAMatrix) {
+        self.matrix.assign(&other.matrix);
+        self.rhs.copy_from_slice(&other.rhs);
+    }
 }
 
 #[cfg(test)]
@@ -318,10 +339,10 @@ mod tests {
         matrix.stamp_resistor(1, 2, 1000.0);
 
         // G = 1/1000 = 0.001
-        assert!((matrix.matrix[0][0] - 0.001).abs() < 1e-10);
-        assert!((matrix.matrix[0][1] + 0.001).abs() < 1e-10);
-        assert!((matrix.matrix[1][0] + 0.001).abs() < 1e-10);
-        assert!((matrix.matrix[1][1] - 0.001).abs() < 1e-10);
+        assert!((matrix.matrix.get(0, 0) - 0.001).abs() < 1e-10);
+        assert!((matrix.matrix.get(0, 1) + 0.001).abs() < 1e-10);
+        assert!((matrix.matrix.get(1, 0) + 0.001).abs() < 1e-10);
+        assert!((matrix.matrix.get(1, 1) - 0.001).abs() < 1e-10);
     }
 
     #[test]
@@ -330,8 +351,8 @@ mod tests {
         matrix.stamp_voltage_source(1, 0, 0, 5.0);
 
         // Check stamps
-        assert!((matrix.matrix[0][1] - 1.0).abs() < 1e-10);
-        assert!((matrix.matrix[1][0] - 1.0).abs() < 1e-10);
+        assert!((matrix.matrix.get(0, 1) - 1.0).abs() < 1e-10);
+        assert!((matrix.matrix.get(1, 0) - 1.0).abs() < 1e-10);
         assert!((matrix.rhs[1] - 5.0).abs() < 1e-10);
     }
 
@@ -368,4 +389,20 @@ mod tests {
         // Node 2 should be 10V (voltage source)
         assert!((solution[1] - 10.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_sparse_matches_dense() {
+        let mut matrix = MNAMatrix::new(3, 1);
+        matrix.stamp_voltage_source(3, 0, 0, 10.0);
+        matrix.stamp_resistor(3, 2, 1000.0);
+        matrix.stamp_resistor(2, 1, 2200.0);
+        matrix.stamp_resistor(1, 0, 4700.0);
+        matrix.stamp_vccs(1, 0, 2, 0, 1e-4);
+
+        let dense = matrix.solve().unwrap();
+        let sparse = matrix.solve_sparse(&mut SparseSolver::new()).unwrap();
+        for (d, s) in dense.iter().zip(&sparse) {
+            assert!((d - s).abs() < 1e-9);
+        }
+    }
 }
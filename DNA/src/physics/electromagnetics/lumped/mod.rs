@@ -1,6 +1,6 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/physics/electromagnetics/lumped/mod.rs
-//! PURPOSE: Module exports: netlist, matrix, ac
+//! PURPOSE: Module exports: netlist, parser, matrix, sparse, ac, noise, polezero, vectfit, devices, dc, sweep, transient
 //! MODIFIED: 2025-12-09
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
@@ -8,15 +8,42 @@
 //!
 //! Lumped circuit analysis using Modified Nodal Analysis (MNA):
 //! - netlist.rs  - Circuit element definitions and netlist representation
+//! - parser.rs   - SPICE netlist text parser (.subckt, .model, .param, directives)
 //! - matrix.rs   - Real-valued MNA matrix for DC analysis
 //! - ac.rs       - Complex MNA matrix for AC/frequency analysis
+//! - sparse.rs   - Markowitz sparse LU with symbolic reuse for MNA solves
+//! - noise.rs    - Adjoint .noise analysis (thermal + attached noise currents)
+//! - polezero.rs - Pole-zero extraction from G + sC (.pz)
+//! - vectfit.rs  - Vector fitting of AC responses to rational transfer functions
+//! - devices.rs  - Diode, BJT and MOSFET model cards and equations
+//! - dc.rs       - Newton-Raphson DC operating point with gmin/source stepping
+//! - sweep.rs    - .dc / parametric sweeps, Monte Carlo and worst-case tolerance
+//! - transient.rs - Time-domain analysis with trapezoidal/Gear-2 companions
 //!
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 pub mod ac;
+pub mod dc;
+pub mod devices;
 pub mod matrix;
 pub mod netlist;
+pub mod noise;
+pub mod parser;
+pub mod polezero;
+pub mod sparse;
+pub mod sweep;
+pub mod transient;
+pub mod vectfit;
 
 pub use ac::*;
+pub use dc::*;
+pub use devices::*;
 pub use matrix::*;
 pub use netlist::*;
+pub use noise::*;
+pub use parser::*;
+pub use polezero::*;
+pub use sparse::*;
+pub use sweep::*;
+pub use transient::*;
+pub use vectfit::*;
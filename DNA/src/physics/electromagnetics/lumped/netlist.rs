@@ -14,8 +14,8 @@
 //! │ DATA DEFINED                                                                │
 //! ├─────────────────────────────────────────────────────────────────────────────┤
 //! │ Netlist            Circuit representation with elements and nodes           │
-//! │ Element            Enum of circuit components (R, L, C, V, I, VCVS, VCCS)   │
-//! │ SourceValue        DC, AC, Pulse, Sin source types                          │
+//! │ Element            Enum of circuit components (R, L, C, sources, D, Q, M)   │
+//! │ SourceValue        DC, AC, Pulse, Sin source types (value_at for time)      │
 //! │ BehavioralExpression  Custom behavioral models (VCO, Phase Detector)        │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
@@ -28,6 +28,7 @@
 //!
 //! DEPENDS ON:
 //!   • serde → Serialization
+//!   • super::devices → Diode, BJT and MOSFET model cards
 //!   • std::collections::HashMap → Node mapping
 //!
 //! USED BY:
@@ -41,6 +42,7 @@
 // CODE BELOW - Optimized for ML development
 // ─────────────────────────────────────────────────────────────────────────────────
 
+use super::devices::{BjtModel, DiodeModel, MosfetModel};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -84,7 +86,7 @@ pub enum Element {
         name: String,
         node_p: String,
         node_n: String,
-        value: f64, // Amps
+        value: SourceValue, // Amps
     },
     VCVS {
         // Voltage-Controlled Voltage Source
@@ -118,6 +120,29 @@ pub enum Element {
         node_n: String,
         expression: BehavioralExpression,
     },
+    /// Junction diode, anode `node_p` to cathode `node_n`
+    Diode {
+        name: String,
+        node_p: String,
+        node_n: String,
+        model: DiodeModel,
+    },
+    /// Bipolar junction transistor
+    Bjt {
+        name: String,
+        node_c: String,
+        node_b: String,
+        node_e: String,
+        model: BjtModel,
+    },
+    /// MOSFET with the body tied to the source
+    Mosfet {
+        name: String,
+        node_d: String,
+        node_g: String,
+        node_s: String,
+        model: MosfetModel,
+    },
 }
 
 /// Source value types
@@ -207,6 +232,30 @@ impl Netlist {
                 self.register_node(node_p);
                 self.register_node(node_n);
             }
+            Element::Diode { node_p, node_n, .. } => {
+                self.register_node(node_p);
+                self.register_node(node_n);
+            }
+            Element::Bjt {
+                node_c,
+                node_b,
+                node_e,
+                ..
+            } => {
+                self.register_node(node_c);
+                self.register_node(node_b);
+                self.register_node(node_e);
+            }
+            Element::Mosfet {
+                node_d,
+                node_g,
+                node_s,
+                ..
+            } => {
+                self.register_node(node_d);
+                self.register_node(node_g);
+                self.register_node(node_s);
+            }
             Element::VCVS {
                 node_out_p,
                 node_out_n,
@@ -298,6 +347,83 @@ impl Netlist {
             })
             .count()
     }
+
+    /// Element by name (SPICE names are case-insensitive)
+    pub fn element(&self, name: &str) -> Option<&Element> {
+        self.elements
+            .iter()
+            .find(|e| e.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Change the primary value of a named element
+    pub fn set_value(&mut self, name: &str, value: f64) -> Result<(), String> {
+        let element = self
+            .elements
+            .iter_mut()
+            .find(|e| e.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Unknown element '{}'", name))?;
+        if element.set_value(value) {
+            Ok(())
+        } else {
+            Err(format!("{}: element has no adjustable value", name))
+        }
+    }
+}
+
+impl SourceValue {
+    /// Instantaneous source value at time `t` (seconds)
+    ///
+    /// Follows SPICE semantics: an AC-only source is zero in the time
+    /// domain, a pulse with zero period fires once, and a sine holds its
+    /// offset until `delay`.
+    pub fn value_at(&self, t: f64) -> f64 {
+        match *self {
+            SourceValue::DC(v) => v,
+            SourceValue::AC { .. } => 0.0,
+            SourceValue::Pulse {
+                v1,
+                v2,
+                delay,
+                rise_time,
+                fall_time,
+                pulse_width,
+                period,
+            } => {
+                if t < delay {
+                    return v1;
+                }
+                let mut tt = t - delay;
+                if period > 0.0 {
+                    tt %= period;
+                }
+                if tt < rise_time {
+                    v1 + (v2 - v1) * tt / rise_time
+                } else if tt < rise_time + pulse_width {
+                    v2
+                } else if tt < rise_time + pulse_width + fall_time {
+                    v2 + (v1 - v2) * (tt - rise_time - pulse_width) / fall_time
+                } else {
+                    v1
+                }
+            }
+            SourceValue::Sin {
+                offset,
+                amplitude,
+                freq,
+                delay,
+                damping,
+            } => {
+                if t < delay {
+                    return offset;
+                }
+                let tt = t - delay;
+                offset
+                    + amplitude
+                        * (-damping * tt).exp()
+                        * (2.0 * std::f64::consts::PI * freq * tt).sin()
+            }
+        }
+    }
 }
 
 impl Element {
@@ -312,8 +438,55 @@ impl Element {
             | Element::VCVS { name, .. }
             | Element::VCCS { name, .. }
             | Element::BehavioralV { name, .. }
-            | Element::BehavioralI { name, .. } => name,
+            | Element::BehavioralI { name, .. }
+            | Element::Diode { name, .. }
+            | Element::Bjt { name, .. }
+            | Element::Mosfet { name, .. } => name,
+        }
+    }
+
+    /// Primary value: R/L/C value, DC level or AC magnitude of an
+    /// independent source, controlled-source gain
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            Element::Resistor { value, .. }
+            | Element::Capacitor { value, .. }
+            | Element::Inductor { value, .. } => Some(*value),
+            Element::VoltageSource { value, .. } | Element::CurrentSource { value, .. } => {
+                match value {
+                    SourceValue::DC(v) => Some(*v),
+                    SourceValue::AC { magnitude, .. } => Some(*magnitude),
+                    _ => None,
+                }
+            }
+            Element::VCVS { gain, .. } => Some(*gain),
+            Element::VCCS {
+                transconductance, ..
+            } => Some(*transconductance),
+            _ => None,
+        }
+    }
+
+    /// Set the value reported by [`Element::value`]; false if there is none
+    pub fn set_value(&mut self, new: f64) -> bool {
+        match self {
+            Element::Resistor { value, .. }
+            | Element::Capacitor { value, .. }
+            | Element::Inductor { value, .. } => *value = new,
+            Element::VoltageSource { value, .. } | Element::CurrentSource { value, .. } => {
+                match value {
+                    SourceValue::DC(v) => *v = new,
+                    SourceValue::AC { magnitude, .. } => *magnitude = new,
+                    _ => return false,
+                }
+            }
+            Element::VCVS { gain, .. } => *gain = new,
+            Element::VCCS {
+                transconductance, ..
+            } => *transconductance = new,
+            _ => return false,
         }
+        true
     }
 }
 
@@ -358,4 +531,63 @@ mod tests {
 
         assert_eq!(netlist.num_voltage_sources(), 1);
     }
+
+    #[test]
+    fn test_set_element_value() {
+        let mut netlist = Netlist::new("Test".to_string());
+        netlist.add_element(Element::VoltageSource {
+            name: "V1".to_string(),
+            node_p: "1".to_string(),
+            node_n: "0".to_string(),
+            value: SourceValue::DC(5.0),
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "1".to_string(),
+            node_n: "0".to_string(),
+            value: 1000.0,
+        });
+
+        netlist.set_value("r1", 2200.0).unwrap();
+        netlist.set_value("V1", 3.3).unwrap();
+        assert_eq!(netlist.element("R1").unwrap().value(), Some(2200.0));
+        assert_eq!(netlist.element("V1").unwrap().value(), Some(3.3));
+        assert!(netlist.set_value("R9", 1.0).is_err());
+    }
+
+    #[test]
+    fn test_source_value_at() {
+        let pulse = SourceValue::Pulse {
+            v1: 0.0,
+            v2: 5.0,
+            delay: 1e-6,
+            rise_time: 1e-6,
+            fall_time: 2e-6,
+            pulse_width: 3e-6,
+            period: 10e-6,
+        };
+        assert_eq!(pulse.value_at(0.5e-6), 0.0);
+        assert!((pulse.value_at(1.5e-6) - 2.5).abs() < 1e-9);
+        assert_eq!(pulse.value_at(4e-6), 5.0);
+        assert!((pulse.value_at(6e-6) - 2.5).abs() < 1e-9);
+        assert_eq!(pulse.value_at(8e-6), 0.0);
+        // Second period
+        assert_eq!(pulse.value_at(14e-6), 5.0);
+
+        let sin = SourceValue::Sin {
+            offset: 1.0,
+            amplitude: 2.0,
+            freq: 1e3,
+            delay: 0.0,
+            damping: 0.0,
+        };
+        assert!((sin.value_at(0.25e-3) - 3.0).abs() < 1e-9);
+        assert!((sin.value_at(0.75e-3) + 1.0).abs() < 1e-9);
+
+        let ac = SourceValue::AC {
+            magnitude: 1.0,
+            phase: 0.0,
+        };
+        assert_eq!(ac.value_at(1.0), 0.0);
+    }
 }
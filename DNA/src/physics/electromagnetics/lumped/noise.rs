@@ -0,0 +1,552 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: noise.rs | DNA/src/physics/electromagnetics/lumped/noise.rs
+//! PURPOSE: Small-signal noise analysis (.noise) using the adjoint AC system
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Small-signal noise analysis (.noise) using the adjoint AC system
+//!
+//! LAYER: DNA → PHYSICS → ELECTROMAGNETICS → LUMPED
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM: Adjoint noise analysis                                           │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Output V_o = eᵀ x with A x = b. Solving the adjoint Aᵀ y = e once per       │
+//! │ frequency gives every transfer at once: V_o = yᵀ b                          │
+//! │   current i injected into p, out of n  →  Z = y[p] - y[n]                   │
+//! │   input voltage source k (MNA row m)   →  gain = y[m]                       │
+//! │                                                                             │
+//! │ Noise sources (uncorrelated, one-sided PSD):                                │
+//! │   Resistor:  S_i = 4kT / R  (white)               [A²/Hz]                   │
+//! │   Attached:  S_i = i_n² · (1 + f_c / f)           [A²/Hz]                   │
+//! │                                                                             │
+//! │ Output PSD  S_o(f) = Σ |Z_k|² S_k                  [V²/Hz]                  │
+//! │ Input PSD   S_o / |gain|²                                                   │
+//! │ Integrated  √∫ S df  (trapezoid, log-interpolated band edges)               │
+//! │ Spot NF     10·log10(S_o / S_o,source resistor)                             │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ NoiseSource        Noise current attached to an element's terminals         │
+//! │ NoiseOptions       Temperature, reference node, attached sources            │
+//! │ NoiseContribution  Output PSD from one noise source                         │
+//! │ NoiseResult        Output/input PSD, integrated noise, noise figure         │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! DEPENDS ON:
+//!   • super::ac → ac_matrix, restamp_ac, log_frequencies
+//!   • super::sparse → SparseSolver on the transposed matrix
+//!
+//! USED BY:
+//!   • pll/circuit.rs → Loop filter noise from build_pll_netlist
+//!   • CORE/SPICE_ENGINE → .noise analysis
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// CODE BELOW - Optimized for ML development
+// ─────────────────────────────────────────────────────────────────────────────────
+
+use super::ac::{ac_matrix, log_frequencies, restamp_ac, Complex};
+use super::netlist::{Element, Netlist};
+use super::sparse::SparseSolver;
+use std::f64::consts::PI;
+
+/// Boltzmann constant (J/K)
+pub const BOLTZMANN: f64 = 1.380649e-23;
+
+/// SPICE nominal temperature, 27 °C (K)
+pub const NOMINAL_TEMPERATURE: f64 = 300.15;
+
+/// Noise current across the output terminals of a named element
+///
+/// Lets noiseless models (charge pumps as VCCS, behavioral VCOs, sources)
+/// carry a datasheet noise density.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoiseSource {
+    pub element: String,
+    /// White current noise density (A/√Hz)
+    pub density: f64,
+    /// 1/f corner frequency (Hz), 0 for white noise only
+    pub flicker_corner: f64,
+}
+
+impl NoiseSource {
+    pub fn white(element: &str, density: f64) -> Self {
+        Self {
+            element: element.to_string(),
+            density,
+            flicker_corner: 0.0,
+        }
+    }
+
+    pub fn with_flicker_corner(mut self, corner_hz: f64) -> Self {
+        self.flicker_corner = corner_hz;
+        self
+    }
+
+    /// Current PSD at `freq` (A²/Hz)
+    pub fn psd(&self, freq: f64) -> f64 {
+        self.density * self.density * (1.0 + self.flicker_corner / freq)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NoiseOptions {
+    /// Temperature for resistor thermal noise (K)
+    pub temperature: f64,
+    /// Output is V(output) - V(reference); ground when None
+    pub reference: Option<String>,
+    /// Extra noise currents on controlled / behavioral elements
+    pub sources: Vec<NoiseSource>,
+}
+
+impl Default for NoiseOptions {
+    fn default() -> Self {
+        Self {
+            temperature: NOMINAL_TEMPERATURE,
+            reference: None,
+            sources: Vec::new(),
+        }
+    }
+}
+
+/// Output noise from one source
+#[derive(Clone, Debug)]
+pub struct NoiseContribution {
+    pub element: String,
+    /// Output PSD per frequency (V²/Hz)
+    pub psd: Vec<f64>,
+}
+
+/// Noise analysis result
+#[derive(Clone, Debug)]
+pub struct NoiseResult {
+    pub frequencies: Vec<f64>,
+    /// Total output PSD (V²/Hz)
+    pub output_psd: Vec<f64>,
+    /// |output / input| per frequency, when an input source was given
+    pub gain: Option<Vec<f64>>,
+    pub contributions: Vec<NoiseContribution>,
+}
+
+impl NoiseResult {
+    /// Output noise density (V/√Hz)
+    pub fn output_density(&self) -> Vec<f64> {
+        self.output_psd.iter().map(|s| s.sqrt()).collect()
+    }
+
+    /// Input-referred PSD (V²/Hz, or A²/Hz for a current-source input)
+    pub fn input_psd(&self) -> Option<Vec<f64>> {
+        let gain = self.gain.as_ref()?;
+        Some(
+            self.output_psd
+                .iter()
+                .zip(gain)
+                .map(|(s, g)| s / (g * g))
+                .collect(),
+        )
+    }
+
+    /// RMS output noise over [f_lo, f_hi] (V)
+    pub fn integrated_output_noise(&self, f_lo: f64, f_hi: f64) -> f64 {
+        integrate(&self.frequencies, &self.output_psd, f_lo, f_hi).sqrt()
+    }
+
+    /// RMS input-referred noise over [f_lo, f_hi]
+    pub fn integrated_input_noise(&self, f_lo: f64, f_hi: f64) -> Option<f64> {
+        let psd = self.input_psd()?;
+        Some(integrate(&self.frequencies, &psd, f_lo, f_hi).sqrt())
+    }
+
+    /// RMS output noise from one source over [f_lo, f_hi] (V)
+    pub fn integrated_contribution(&self, element: &str, f_lo: f64, f_hi: f64) -> Option<f64> {
+        let psd = &self.contribution(element)?.psd;
+        Some(integrate(&self.frequencies, psd, f_lo, f_hi).sqrt())
+    }
+
+    pub fn contribution(&self, element: &str) -> Option<&NoiseContribution> {
+        self.contributions
+            .iter()
+            .find(|c| c.element.eq_ignore_ascii_case(element))
+    }
+
+    /// Spot noise figure (dB) with `source_resistor` as the source impedance
+    pub fn noise_figure_db(&self, source_resistor: &str) -> Option<Vec<f64>> {
+        let source = self.contribution(source_resistor)?;
+        Some(
+            self.output_psd
+                .iter()
+                .zip(&source.psd)
+                .map(|(total, s)| 10.0 * (total / s).log10())
+                .collect(),
+        )
+    }
+}
+
+/// ∫ psd df over [f_lo, f_hi], trapezoidal between sweep points with
+/// log-log interpolation at the band edges
+fn integrate(freqs: &[f64], psd: &[f64], f_lo: f64, f_hi: f64) -> f64 {
+    let at = |f: f64| -> f64 {
+        let k = freqs.partition_point(|&x| x < f).clamp(1, freqs.len() - 1);
+        let (f0, f1) = (freqs[k - 1], freqs[k]);
+        let (s0, s1) = (psd[k - 1].max(1e-300), psd[k].max(1e-300));
+        let t = (f / f0).ln() / (f1 / f0).ln();
+        (s0.ln() + t * (s1.ln() - s0.ln())).exp()
+    };
+
+    if freqs.len() < 2 {
+        return 0.0;
+    }
+    let lo = f_lo.max(freqs[0]);
+    let hi = f_hi.min(freqs[freqs.len() - 1]);
+    if hi <= lo {
+        return 0.0;
+    }
+
+    let mut points = vec![(lo, at(lo))];
+    points.extend(
+        freqs
+            .iter()
+            .zip(psd)
+            .filter(|(&f, _)| f > lo && f < hi)
+            .map(|(&f, &s)| (f, s)),
+    );
+    points.push((hi, at(hi)));
+    points
+        .windows(2)
+        .map(|w| 0.5 * (w[0].1 + w[1].1) * (w[1].0 - w[0].0))
+        .sum()
+}
+
+/// Output (and optional input) terminals of an element for attached noise
+fn terminals(element: &Element) -> Option<(&str, &str)> {
+    match element {
+        Element::Resistor { node_p, node_n, .. }
+        | Element::Capacitor { node_p, node_n, .. }
+        | Element::Inductor { node_p, node_n, .. }
+        | Element::VoltageSource { node_p, node_n, .. }
+        | Element::CurrentSource { node_p, node_n, .. }
+        | Element::BehavioralV { node_p, node_n, .. }
+        | Element::BehavioralI { node_p, node_n, .. }
+        | Element::Diode { node_p, node_n, .. } => Some((node_p, node_n)),
+        Element::VCVS {
+            node_out_p,
+            node_out_n,
+            ..
+        }
+        | Element::VCCS {
+            node_out_p,
+            node_out_n,
+            ..
+        } => Some((node_out_p, node_out_n)),
+        _ => None,
+    }
+}
+
+/// Noise analysis with default options (27 °C, output referenced to ground)
+///
+/// `input` names the independent source the noise is referred to
+/// (SPICE `.noise V(output) input ...`).
+pub fn noise_analysis(
+    netlist: &Netlist,
+    output: &str,
+    input: Option<&str>,
+    freq_start: f64,
+    freq_stop: f64,
+    points_per_decade: usize,
+) -> Result<NoiseResult, String> {
+    noise_analysis_with(
+        netlist,
+        output,
+        input,
+        freq_start,
+        freq_stop,
+        points_per_decade,
+        &NoiseOptions::default(),
+    )
+}
+
+/// Noise analysis with explicit options
+pub fn noise_analysis_with(
+    netlist: &Netlist,
+    output: &str,
+    input: Option<&str>,
+    freq_start: f64,
+    freq_stop: f64,
+    points_per_decade: usize,
+    options: &NoiseOptions,
+) -> Result<NoiseResult, String> {
+    let node = |name: &str| {
+        netlist
+            .node_index(name)
+            .ok_or_else(|| format!("Unknown node '{}'", name))
+    };
+    let out = node(output)?;
+    let reference = match &options.reference {
+        Some(name) => node(name)?,
+        None => 0,
+    };
+    if out == reference {
+        return Err("Noise output and reference are the same node".to_string());
+    }
+
+    // Injection points; a resistor is a white current source of 4kT/R
+    let four_kt = 4.0 * BOLTZMANN * options.temperature;
+    let mut injections: Vec<(usize, usize, NoiseSource)> = Vec::new();
+    for element in &netlist.elements {
+        if let Element::Resistor {
+            name,
+            node_p,
+            node_n,
+            value,
+        } = element
+        {
+            let thermal = NoiseSource::white(name, (four_kt / value).sqrt());
+            injections.push((node(node_p)?, node(node_n)?, thermal));
+        }
+    }
+    for source in &options.sources {
+        let element = netlist
+            .element(&source.element)
+            .ok_or_else(|| format!("Unknown element '{}'", source.element))?;
+        let (p, n) = terminals(element)
+            .ok_or_else(|| format!("{}: cannot attach a noise source", source.element))?;
+        injections.push((node(p)?, node(n)?, source.clone()));
+    }
+
+    // Row of the input in the MNA system: branch row for a voltage source
+    // (sources and VCVSs take branch rows in netlist order), or nodes
+    let input_row = match input {
+        None => None,
+        Some(name) => {
+            let position = netlist
+                .elements
+                .iter()
+                .position(|e| e.name().eq_ignore_ascii_case(name))
+                .ok_or_else(|| format!("Unknown element '{}'", name))?;
+            match &netlist.elements[position] {
+                Element::VoltageSource { .. } => {
+                    let branch = netlist.elements[..position]
+                        .iter()
+                        .filter(|e| {
+                            matches!(e, Element::VoltageSource { .. } | Element::VCVS { .. })
+                        })
+                        .count();
+                    Some((netlist.num_nodes() + branch + 1, 0))
+                }
+                Element::CurrentSource { node_p, node_n, .. } => {
+                    // Source current flows from node_p through the source to node_n
+                    Some((node(node_n)?, node(node_p)?))
+                }
+                _ => {
+                    return Err(format!(
+                        "{}: noise input must be an independent source",
+                        name
+                    ))
+                }
+            }
+        }
+    };
+
+    let frequencies = log_frequencies(freq_start, freq_stop, points_per_decade);
+    let mut solver = SparseSolver::new();
+    let mut output_psd = Vec::with_capacity(frequencies.len());
+    let mut gain = input_row.map(|_| Vec::with_capacity(frequencies.len()));
+    let mut contributions: Vec<NoiseContribution> = injections
+        .iter()
+        .map(|(_, _, source)| NoiseContribution {
+            element: source.element.clone(),
+            psd: Vec::with_capacity(frequencies.len()),
+        })
+        .collect();
+
+    let mut matrix = ac_matrix(netlist, 2.0 * PI * frequencies[0]);
+    for &freq in &frequencies {
+        restamp_ac(netlist, 2.0 * PI * freq, &mut matrix);
+        let mut e = vec![Complex::zero(); matrix.size];
+        e[out - 1] = Complex::new(1.0, 0.0);
+        if reference > 0 {
+            e[reference - 1] = Complex::new(-1.0, 0.0);
+        }
+        let y = solver.solve(&matrix.matrix.transpose(), &e)?;
+
+        // Transfer from a unit current into p (out of n) to the output
+        let transfer = |p: usize, n: usize| {
+            let at = |k: usize| if k == 0 { Complex::zero() } else { y[k - 1] };
+            (at(p) - at(n)).magnitude()
+        };
+
+        let mut total = 0.0;
+        for ((p, n, source), contribution) in injections.iter().zip(&mut contributions) {
+            let z = transfer(*p, *n);
+            let s = z * z * source.psd(freq);
+            contribution.psd.push(s);
+            total += s;
+        }
+        output_psd.push(total);
+
+        if let (Some((p, n)), Some(gain)) = (input_row, gain.as_mut()) {
+            gain.push(transfer(p, n));
+        }
+    }
+
+    Ok(NoiseResult {
+        frequencies,
+        output_psd,
+        gain,
+        contributions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::netlist::SourceValue;
+    use super::*;
+
+    fn ac_source(name: &str, p: &str) -> Element {
+        Element::VoltageSource {
+            name: name.to_string(),
+            node_p: p.to_string(),
+            node_n: "0".to_string(),
+            value: SourceValue::AC {
+                magnitude: 1.0,
+                phase: 0.0,
+            },
+        }
+    }
+
+    fn resistor(name: &str, p: &str, n: &str, r: f64) -> Element {
+        Element::Resistor {
+            name: name.to_string(),
+            node_p: p.to_string(),
+            node_n: n.to_string(),
+            value: r,
+        }
+    }
+
+    fn kt() -> f64 {
+        BOLTZMANN * NOMINAL_TEMPERATURE
+    }
+
+    #[test]
+    fn test_divider_thermal_noise() {
+        // Source is an AC short: output sees R1 ∥ R2
+        let mut netlist = Netlist::new("Divider".to_string());
+        netlist.add_element(ac_source("V1", "in"));
+        netlist.add_element(resistor("R1", "in", "out", 1e3));
+        netlist.add_element(resistor("R2", "out", "0", 3e3));
+
+        let result = noise_analysis(&netlist, "out", Some("V1"), 10.0, 1e5, 5).unwrap();
+        let expected = 4.0 * kt() * 750.0;
+        for (psd, g) in result.output_psd.iter().zip(result.gain.as_ref().unwrap()) {
+            assert!((psd / expected - 1.0).abs() < 1e-9);
+            assert!((g - 0.75).abs() < 1e-12);
+        }
+        // Contributions split in proportion to the other resistor
+        let r1 = result.contribution("R1").unwrap().psd[0];
+        let r2 = result.contribution("R2").unwrap().psd[0];
+        assert!((r1 / r2 - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rc_integrates_to_kt_over_c() {
+        let c = 1e-9;
+        let mut netlist = Netlist::new("RC".to_string());
+        netlist.add_element(ac_source("V1", "in"));
+        netlist.add_element(resistor("R1", "in", "out", 1e3));
+        netlist.add_element(Element::Capacitor {
+            name: "C1".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: c,
+        });
+
+        let result = noise_analysis(&netlist, "out", None, 1.0, 1e10, 40).unwrap();
+        let vrms = result.integrated_output_noise(0.0, f64::INFINITY);
+        let expected = (kt() / c).sqrt();
+        assert!(
+            (vrms / expected - 1.0).abs() < 0.01,
+            "{} vs {}",
+            vrms,
+            expected
+        );
+        assert!(result.gain.is_none());
+        // Half the band below the pole holds much less than half the power
+        assert!(result.integrated_output_noise(1.0, 159e3) < vrms * 0.8);
+    }
+
+    #[test]
+    fn test_matched_source_noise_figure() {
+        // 50 Ω source into a noisy 50 Ω load: NF = 3 dB, gain 1/2
+        let mut netlist = Netlist::new("Matched".to_string());
+        netlist.add_element(ac_source("VS", "src"));
+        netlist.add_element(resistor("RS", "src", "out", 50.0));
+        netlist.add_element(resistor("RL", "out", "0", 50.0));
+
+        let result = noise_analysis(&netlist, "out", Some("VS"), 1e3, 1e6, 3).unwrap();
+        for nf in result.noise_figure_db("RS").unwrap() {
+            assert!((nf - 10.0 * 2f64.log10()).abs() < 1e-9);
+        }
+        // Input-referred noise is twice the source resistor's own noise PSD
+        let input = result.input_psd().unwrap();
+        assert!((input[0] / (4.0 * kt() * 50.0) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_attached_noise_current_on_vccs() {
+        let mut netlist = Netlist::new("Charge pump".to_string());
+        netlist.add_element(ac_source("V1", "ctrl"));
+        netlist.add_element(Element::VCCS {
+            name: "G1".to_string(),
+            node_out_p: "out".to_string(),
+            node_out_n: "0".to_string(),
+            node_ctrl_p: "ctrl".to_string(),
+            node_ctrl_n: "0".to_string(),
+            transconductance: 1e-3,
+        });
+        netlist.add_element(resistor("R1", "out", "0", 1e4));
+
+        let options = NoiseOptions {
+            sources: vec![NoiseSource::white("G1", 1e-12).with_flicker_corner(1e3)],
+            ..NoiseOptions::default()
+        };
+        let result = noise_analysis_with(&netlist, "out", None, 10.0, 1e6, 1, &options).unwrap();
+        let g1 = &result.contribution("G1").unwrap().psd;
+        // 1 pA/√Hz through 10 kΩ, 1/f corner at 1 kHz
+        assert!((g1[0] / (1e-16 * 101.0) - 1.0).abs() < 1e-9);
+        assert!((g1[g1.len() - 1] / (1e-16 * 1.001) - 1.0).abs() < 1e-9);
+
+        let bad = NoiseOptions {
+            sources: vec![NoiseSource::white("G9", 1e-12)],
+            ..NoiseOptions::default()
+        };
+        assert!(noise_analysis_with(&netlist, "out", None, 10.0, 1e6, 1, &bad).is_err());
+    }
+
+    #[test]
+    fn test_differential_output_and_errors() {
+        let mut netlist = Netlist::new("Bridge".to_string());
+        netlist.add_element(ac_source("V1", "in"));
+        netlist.add_element(resistor("R1", "in", "a", 1e3));
+        netlist.add_element(resistor("R2", "a", "0", 1e3));
+        netlist.add_element(resistor("R3", "in", "b", 1e3));
+        netlist.add_element(resistor("R4", "b", "0", 1e3));
+
+        let options = NoiseOptions {
+            reference: Some("b".to_string()),
+            ..NoiseOptions::default()
+        };
+        let result = noise_analysis_with(&netlist, "a", Some("V1"), 1e3, 1e4, 1, &options).unwrap();
+        // Both half-bridges contribute 4kT·500 each; balanced bridge has zero gain
+        assert!((result.output_psd[0] / (4.0 * kt() * 1000.0) - 1.0).abs() < 1e-9);
+        assert!(result.gain.unwrap()[0] < 1e-12);
+
+        assert!(noise_analysis(&netlist, "nope", None, 1e3, 1e4, 1).is_err());
+        assert!(noise_analysis(&netlist, "a", Some("R1"), 1e3, 1e4, 1).is_err());
+    }
+}
@@ -0,0 +1,1751 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: parser.rs | DNA/src/physics/electromagnetics/lumped/parser.rs
+//! PURPOSE: SPICE netlist text parser (subcircuits, models, params, directives)
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: SPICE netlist text parser (subcircuits, models, params, directives)
+//!
+//! LAYER: DNA → PHYSICS → ELECTROMAGNETICS → LUMPED
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM: Two-pass deck expansion                                          │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ 1. Read logical lines: strip comments (*, #, ;, $), join '+' continuations, │
+//! │    splice .include files from the in-memory file map                        │
+//! │ 2. Collect .subckt bodies, evaluate .param in order, build .model cards     │
+//! │ 3. Expand elements; X lines recurse into subcircuit bodies with ports       │
+//! │    mapped to the caller's nodes and internal nodes named X1.node; unknown   │
+//! │    element types are skipped with a warning                                 │
+//! │                                                                             │
+//! │ Values: 4.7k, 10u, 1meg, 2.2nF (unit letters ignored), {expr} or 'expr'     │
+//! │ Expressions: + - * / ^, parentheses, params, sqrt exp ln log10 sin cos      │
+//! │              tan abs min max pow, pi                                        │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ ParsedNetlist      Netlist plus analysis directives and global params       │
+//! │ Analysis           .op / .ac / .dc / .tran directive                        │
+//! │ AcSweep            Decade, octave or linear frequency spacing               │
+//! │ SpiceParseError    Message with the file and line it came from              │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! DEPENDS ON:
+//!   • super::netlist → Netlist, Element, SourceValue
+//!   • super::devices → Model cards for D, Q and M elements
+//!   • power::components → Datasheet parts usable as model names
+//!
+//! USED BY:
+//!   • CORE/SPICE_ENGINE → Netlist text input
+//!   • TOOLS/SPICE → Web netlist editor
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// CODE BELOW - Optimized for ML development
+// ─────────────────────────────────────────────────────────────────────────────────
+
+use super::devices::{BjtModel, BjtPolarity, DiodeModel, MosfetModel, MosfetPolarity};
+use super::netlist::{Element, Netlist, SourceValue};
+use crate::power::components::{diode_database, mosfet_database};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Deepest allowed nesting of .include files and subcircuit instances
+const MAX_DEPTH: usize = 32;
+
+/// Netlist text parsed into a circuit and its analysis directives
+#[derive(Clone, Debug)]
+pub struct ParsedNetlist {
+    pub netlist: Netlist,
+    /// Analysis directives in the order they appear
+    pub analyses: Vec<Analysis>,
+    /// Global .param values, keyed by lowercase name
+    pub params: HashMap<String, f64>,
+    /// Lines skipped rather than rejected (unknown element types)
+    pub warnings: Vec<SpiceParseError>,
+}
+
+/// Frequency spacing of an .ac sweep
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AcSweep {
+    Decade,
+    Octave,
+    Linear,
+}
+
+/// Analysis directive
+#[derive(Clone, Debug, PartialEq)]
+pub enum Analysis {
+    /// .op
+    Op,
+    /// .ac dec|oct|lin points fstart fstop
+    Ac {
+        sweep: AcSweep,
+        /// Points per decade/octave, or in total for a linear sweep
+        points: usize,
+        fstart: f64,
+        fstop: f64,
+    },
+    /// .dc source start stop step
+    Dc {
+        source: String,
+        start: f64,
+        stop: f64,
+        step: f64,
+    },
+    /// .noise V(output[,reference]) source dec|oct|lin points fstart fstop
+    Noise {
+        output: String,
+        reference: Option<String>,
+        source: String,
+        sweep: AcSweep,
+        points: usize,
+        fstart: f64,
+        fstop: f64,
+    },
+    /// .tran tstep tstop [tstart [tmax]]
+    Tran {
+        tstep: f64,
+        tstop: f64,
+        tstart: f64,
+        tmax: Option<f64>,
+    },
+}
+
+/// Netlist parse error
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpiceParseError {
+    /// Included file the line came from, `None` for the main text
+    pub file: Option<String>,
+    /// 1-based line number (first line of a continued statement)
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SpiceParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}: {}", file, self.line, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+impl std::error::Error for SpiceParseError {}
+
+/// Parse SPICE netlist text
+///
+/// The title comes from `.title` or a leading `*` comment line. Everything
+/// after `.end` is ignored. Lines starting with `*` or `#` are comments.
+pub fn parse_netlist(text: &str) -> Result<ParsedNetlist, SpiceParseError> {
+    parse_netlist_with_includes(text, &HashMap::new())
+}
+
+/// Parse SPICE netlist text, resolving `.include` names in `files`
+pub fn parse_netlist_with_includes(
+    text: &str,
+    files: &HashMap<String, String>,
+) -> Result<ParsedNetlist, SpiceParseError> {
+    let mut lines = Vec::new();
+    read_lines(text, None, files, 0, &mut lines)?;
+
+    let title = text
+        .lines()
+        .next()
+        .and_then(|first| first.trim().strip_prefix('*'))
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| "Untitled".to_string());
+
+    let deck = Deck::collect(lines)?;
+    let mut netlist = Netlist::new(deck.title.clone().unwrap_or(title));
+    let mut analyses = Vec::new();
+    let mut warnings = Vec::new();
+    let scope = Scope {
+        params: deck.params.clone(),
+        prefix: String::new(),
+        ports: HashMap::new(),
+    };
+    for line in &deck.main {
+        if line.text.starts_with('.') {
+            analyses.extend(parse_directive(line, &scope)?);
+        } else {
+            deck.expand(line, &scope, &mut netlist, &mut warnings, 0)?;
+        }
+    }
+
+    Ok(ParsedNetlist {
+        netlist,
+        analyses,
+        params: deck.params,
+        warnings,
+    })
+}
+
+/// Parse a number with an optional SPICE scale suffix
+///
+/// Suffixes are case-insensitive: T G MEG K M(milli) U N P F, and MIL
+/// (25.4 µm). Letters after the suffix are units and ignored, so `2.2nF`
+/// and `10kOhm` both parse.
+pub fn parse_value(s: &str) -> Result<f64, String> {
+    match scan_number(s.trim().as_bytes()) {
+        Some((value, used)) if used == s.trim().len() => Ok(value),
+        _ => Err(format!("invalid number '{}'", s)),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LINES AND TOKENS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// One logical statement, after comment removal and continuation joining
+#[derive(Clone, Debug)]
+struct Line {
+    text: String,
+    number: usize,
+    file: Option<String>,
+}
+
+impl Line {
+    fn error(&self, message: impl Into<String>) -> SpiceParseError {
+        SpiceParseError {
+            file: self.file.clone(),
+            line: self.number,
+            message: message.into(),
+        }
+    }
+
+    /// Lowercase first word
+    fn keyword(&self) -> String {
+        self.text
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .to_lowercase()
+    }
+}
+
+fn read_lines(
+    text: &str,
+    file: Option<&str>,
+    files: &HashMap<String, String>,
+    depth: usize,
+    out: &mut Vec<Line>,
+) -> Result<(), SpiceParseError> {
+    let error = |number: usize, message: String| SpiceParseError {
+        file: file.map(str::to_string),
+        line: number,
+        message,
+    };
+
+    for (i, raw) in text.lines().enumerate() {
+        let number = i + 1;
+        let content = strip_comment(raw).trim();
+        if content.is_empty() || content.starts_with('*') || content.starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = content.strip_prefix('+') {
+            match out.last_mut() {
+                Some(last) if last.file.as_deref() == file => {
+                    last.text.push(' ');
+                    last.text.push_str(rest.trim());
+                }
+                _ => {
+                    return Err(error(
+                        number,
+                        "continuation with no line to continue".into(),
+                    ))
+                }
+            }
+            continue;
+        }
+
+        let keyword = content
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        if keyword == ".include" || keyword == ".inc" {
+            let name = content[keyword.len()..].trim().trim_matches(['"', '\'']);
+            if name.is_empty() {
+                return Err(error(number, ".include needs a file name".into()));
+            }
+            if depth >= MAX_DEPTH {
+                return Err(error(
+                    number,
+                    format!(".include of '{}' nests too deep", name),
+                ));
+            }
+            let body = files
+                .get(name)
+                .ok_or_else(|| error(number, format!("included file '{}' not found", name)))?;
+            read_lines(body, Some(name), files, depth + 1, out)?;
+            continue;
+        }
+
+        out.push(Line {
+            text: content.to_string(),
+            number,
+            file: file.map(str::to_string),
+        });
+    }
+    Ok(())
+}
+
+/// Drop `;` comments and `$` comments that follow whitespace
+fn strip_comment(line: &str) -> &str {
+    let mut end = line.len();
+    if let Some(i) = line.find(';') {
+        end = i;
+    }
+    let bytes = line.as_bytes();
+    for (i, &b) in bytes[..end].iter().enumerate() {
+        if b == b'$' && (i == 0 || bytes[i - 1].is_ascii_whitespace()) {
+            end = i;
+            break;
+        }
+    }
+    &line[..end]
+}
+
+/// Split a statement into words
+///
+/// Whitespace, commas and parentheses separate words except inside `{}`
+/// or quotes; `key = value` is rejoined into a single `key=value` word.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    let mut braces = 0usize;
+    let mut quoted = false;
+    for c in text.chars() {
+        match c {
+            '{' if !quoted => braces += 1,
+            '}' if !quoted => braces = braces.saturating_sub(1),
+            '\'' => quoted = !quoted,
+            _ => {}
+        }
+        let separator = c.is_whitespace() || c == ',' || c == '(' || c == ')';
+        if separator && braces == 0 && !quoted {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+        } else {
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    let mut merged: Vec<String> = Vec::new();
+    let mut join_next = false;
+    for w in words {
+        if join_next || w.starts_with('=') {
+            if let Some(last) = merged.last_mut() {
+                last.push_str(&w);
+                join_next = w.ends_with('=');
+                continue;
+            }
+        }
+        join_next = w.ends_with('=');
+        merged.push(w);
+    }
+    merged
+}
+
+/// Split `key=value` into a lowercase key and the value text
+fn assignment(word: &str) -> Option<(String, &str)> {
+    let (key, value) = word.split_once('=')?;
+    Some((key.trim().to_lowercase(), value.trim()))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DECK COLLECTION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Subcircuit definition
+struct Subckt {
+    ports: Vec<String>,
+    /// Default parameter expressions, evaluated per instance
+    defaults: Vec<(String, String)>,
+    body: Vec<Line>,
+}
+
+/// Device model card from a .model statement
+#[derive(Clone, Debug)]
+enum ModelCard {
+    Diode(DiodeModel),
+    Bjt(BjtModel),
+    Mosfet(MosfetModel),
+}
+
+/// Statements sorted into definitions and the top-level circuit
+struct Deck {
+    title: Option<String>,
+    params: HashMap<String, f64>,
+    models: HashMap<String, ModelCard>,
+    subckts: HashMap<String, Subckt>,
+    /// Top-level elements and analysis directives
+    main: Vec<Line>,
+}
+
+impl Deck {
+    fn collect(lines: Vec<Line>) -> Result<Self, SpiceParseError> {
+        let mut deck = Deck {
+            title: None,
+            params: HashMap::new(),
+            models: HashMap::new(),
+            subckts: HashMap::new(),
+            main: Vec::new(),
+        };
+        let mut model_lines = Vec::new();
+        let mut open: Option<(String, Line, Subckt)> = None;
+
+        for line in lines {
+            let keyword = line.keyword();
+            if let Some((_, header, subckt)) = open.as_mut() {
+                match keyword.as_str() {
+                    ".ends" => {}
+                    ".subckt" => {
+                        return Err(line.error(format!(
+                            "nested .subckt inside the definition started on line {}",
+                            header.number
+                        )))
+                    }
+                    ".model" | ".param" => {
+                        return Err(line.error(format!("{} is not allowed inside .subckt", keyword)))
+                    }
+                    _ => {
+                        subckt.body.push(line);
+                        continue;
+                    }
+                }
+                if let Some((name, _, subckt)) = open.take() {
+                    deck.subckts.insert(name, subckt);
+                }
+                continue;
+            }
+
+            match keyword.as_str() {
+                ".end" => break,
+                ".ends" => return Err(line.error(".ends without .subckt")),
+                ".title" => deck.title = Some(line.text[6..].trim().to_string()),
+                ".subckt" => {
+                    let words = tokenize(&line.text);
+                    let name = words
+                        .get(1)
+                        .ok_or_else(|| line.error(".subckt needs a name"))?
+                        .to_lowercase();
+                    let mut subckt = Subckt {
+                        ports: Vec::new(),
+                        defaults: Vec::new(),
+                        body: Vec::new(),
+                    };
+                    for word in &words[2..] {
+                        if let Some((key, value)) = assignment(word) {
+                            subckt.defaults.push((key, value.to_string()));
+                        } else if !word.eq_ignore_ascii_case("params:") {
+                            subckt.ports.push(word.clone());
+                        }
+                    }
+                    open = Some((name, line, subckt));
+                }
+                ".param" => {
+                    let scope = Scope::global(&deck.params);
+                    for (key, value) in parse_params(&line, &line.text[6..], &scope)? {
+                        deck.params.insert(key, value);
+                    }
+                }
+                ".model" => model_lines.push(line),
+                _ => deck.main.push(line),
+            }
+        }
+
+        if let Some((name, header, _)) = open {
+            return Err(header.error(format!(".subckt {} has no .ends", name)));
+        }
+
+        let scope = Scope::global(&deck.params);
+        for line in model_lines {
+            let (name, card) = parse_model(&line, &scope)?;
+            deck.models.insert(name, card);
+        }
+        Ok(deck)
+    }
+
+    /// Add one element line to `netlist`, recursing into subcircuits
+    ///
+    /// SPICE element types this parser does not model are rejected; letters
+    /// SPICE does not define are skipped with an entry in `warnings`.
+    fn expand(
+        &self,
+        line: &Line,
+        scope: &Scope,
+        netlist: &mut Netlist,
+        warnings: &mut Vec<SpiceParseError>,
+        depth: usize,
+    ) -> Result<(), SpiceParseError> {
+        let words = tokenize(&line.text);
+        let kind = words[0].chars().next().unwrap_or(' ').to_ascii_uppercase();
+        let name = scope.name(&words[0]);
+        let usage = |form: &str| line.error(format!("{}: expected '{}'", words[0], form));
+        let value = |word: &str| scope.value(word).map_err(|e| line.error(e));
+
+        let element = match kind {
+            'R' | 'C' | 'L' => {
+                if words.len() < 4 {
+                    return Err(usage("<name> <n+> <n-> <value>"));
+                }
+                let (node_p, node_n) = (scope.node(&words[1]), scope.node(&words[2]));
+                let value = value(&words[3])?;
+                match kind {
+                    'R' => Element::Resistor {
+                        name,
+                        node_p,
+                        node_n,
+                        value,
+                    },
+                    'C' => Element::Capacitor {
+                        name,
+                        node_p,
+                        node_n,
+                        value,
+                    },
+                    _ => Element::Inductor {
+                        name,
+                        node_p,
+                        node_n,
+                        value,
+                    },
+                }
+            }
+            'V' => {
+                if words.len() < 3 {
+                    return Err(usage(
+                        "V<name> <n+> <n-> [DC v] [AC mag [phase]] [PULSE/SIN(...)]",
+                    ));
+                }
+                Element::VoltageSource {
+                    name,
+                    node_p: scope.node(&words[1]),
+                    node_n: scope.node(&words[2]),
+                    value: parse_source(&words[3..], scope).map_err(|e| line.error(e))?,
+                }
+            }
+            'I' => {
+                if words.len() < 4 {
+                    return Err(usage(
+                        "I<name> <n+> <n-> [DC i] [AC mag [phase]] [PULSE/SIN(...)]",
+                    ));
+                }
+                Element::CurrentSource {
+                    name,
+                    node_p: scope.node(&words[1]),
+                    node_n: scope.node(&words[2]),
+                    value: parse_source(&words[3..], scope).map_err(|e| line.error(e))?,
+                }
+            }
+            'E' | 'G' => {
+                if words.len() < 6 {
+                    return Err(usage("<name> <out+> <out-> <ctrl+> <ctrl-> <gain>"));
+                }
+                let (node_out_p, node_out_n) = (scope.node(&words[1]), scope.node(&words[2]));
+                let (node_ctrl_p, node_ctrl_n) = (scope.node(&words[3]), scope.node(&words[4]));
+                let gain = value(&words[5])?;
+                if kind == 'E' {
+                    Element::VCVS {
+                        name,
+                        node_out_p,
+                        node_out_n,
+                        node_ctrl_p,
+                        node_ctrl_n,
+                        gain,
+                    }
+                } else {
+                    Element::VCCS {
+                        name,
+                        node_out_p,
+                        node_out_n,
+                        node_ctrl_p,
+                        node_ctrl_n,
+                        transconductance: gain,
+                    }
+                }
+            }
+            'D' => {
+                if words.len() < 4 {
+                    return Err(usage("D<name> <anode> <cathode> <model>"));
+                }
+                let model = match self.model(&words[3]) {
+                    Some(ModelCard::Diode(model)) => model,
+                    Some(_) => {
+                        return Err(line.error(format!("'{}' is not a diode model", words[3])))
+                    }
+                    None => return Err(line.error(format!("unknown model '{}'", words[3]))),
+                };
+                Element::Diode {
+                    name,
+                    node_p: scope.node(&words[1]),
+                    node_n: scope.node(&words[2]),
+                    model,
+                }
+            }
+            'Q' => {
+                // Optional substrate node before the model is accepted and ignored
+                let model_at = match words.iter().rposition(|w| !w.contains('=')) {
+                    Some(k @ 4..=5) => k,
+                    _ => return Err(usage("Q<name> <c> <b> <e> [<substrate>] <model>")),
+                };
+                let model = match self.model(&words[model_at]) {
+                    Some(ModelCard::Bjt(model)) => model,
+                    Some(_) => {
+                        return Err(line.error(format!("'{}' is not a BJT model", words[model_at])))
+                    }
+                    None => return Err(line.error(format!("unknown model '{}'", words[model_at]))),
+                };
+                Element::Bjt {
+                    name,
+                    node_c: scope.node(&words[1]),
+                    node_b: scope.node(&words[2]),
+                    node_e: scope.node(&words[3]),
+                    model,
+                }
+            }
+            'M' => {
+                // Bulk is accepted and ignored: the level-1 model ties it to the source
+                let model_at = match words.iter().rposition(|w| !w.contains('=')) {
+                    Some(k @ 4..=5) => k,
+                    _ => return Err(usage("M<name> <d> <g> <s> [<b>] <model> [W=..] [L=..]")),
+                };
+                let mut model = match self.model(&words[model_at]) {
+                    Some(ModelCard::Mosfet(model)) => model,
+                    Some(_) => {
+                        return Err(
+                            line.error(format!("'{}' is not a MOSFET model", words[model_at]))
+                        )
+                    }
+                    None => return Err(line.error(format!("unknown model '{}'", words[model_at]))),
+                };
+                let (mut w, mut l) = (1.0, 1.0);
+                for word in &words[model_at + 1..] {
+                    match assignment(word) {
+                        Some((key, v)) if key == "w" => w = value(v)?,
+                        Some((key, v)) if key == "l" => l = value(v)?,
+                        _ => {}
+                    }
+                }
+                model.kp *= w / l;
+                Element::Mosfet {
+                    name,
+                    node_d: scope.node(&words[1]),
+                    node_g: scope.node(&words[2]),
+                    node_s: scope.node(&words[3]),
+                    model,
+                }
+            }
+            'X' => return self.instantiate(line, &words, scope, netlist, warnings, depth),
+            _ => {
+                if let Some(what) = unsupported_element(kind) {
+                    return Err(line.error(format!(
+                        "unsupported element type '{}' ({})",
+                        words[0], what
+                    )));
+                }
+                warnings
+                    .push(line.error(format!("unknown element type '{}', line skipped", words[0])));
+                return Ok(());
+            }
+        };
+
+        netlist.add_element(element);
+        Ok(())
+    }
+
+    /// Expand an X line: `X<name> <nodes...> <subckt> [param=value ...]`
+    fn instantiate(
+        &self,
+        line: &Line,
+        words: &[String],
+        scope: &Scope,
+        netlist: &mut Netlist,
+        warnings: &mut Vec<SpiceParseError>,
+        depth: usize,
+    ) -> Result<(), SpiceParseError> {
+        let Some(at) = words
+            .iter()
+            .rposition(|w| !w.contains('='))
+            .filter(|&i| i > 0)
+        else {
+            return Err(line.error(format!("{}: missing subcircuit name", words[0])));
+        };
+        let subckt = self
+            .subckts
+            .get(&words[at].to_lowercase())
+            .ok_or_else(|| line.error(format!("unknown subcircuit '{}'", words[at])))?;
+        let nodes = &words[1..at];
+        if nodes.len() != subckt.ports.len() {
+            return Err(line.error(format!(
+                "{}: subcircuit '{}' has {} ports, got {} nodes",
+                words[0],
+                words[at],
+                subckt.ports.len(),
+                nodes.len()
+            )));
+        }
+        if depth >= MAX_DEPTH {
+            return Err(line.error(format!("{}: subcircuits nest too deep", words[0])));
+        }
+
+        // Defaults see the caller's params; instance overrides win
+        let mut params = scope.params.clone();
+        for (key, expr) in &subckt.defaults {
+            let v = evaluate(expr, &params).map_err(|e| line.error(e))?;
+            params.insert(key.clone(), v);
+        }
+        for word in &words[at + 1..] {
+            let Some((key, expr)) = assignment(word) else {
+                continue;
+            };
+            if !subckt.defaults.iter().any(|(k, _)| *k == key) {
+                return Err(line.error(format!(
+                    "subcircuit '{}' has no parameter '{}'",
+                    words[at], key
+                )));
+            }
+            let v = scope.value(expr).map_err(|e| line.error(e))?;
+            params.insert(key, v);
+        }
+
+        let inner = Scope {
+            params,
+            prefix: scope.name(&words[0]),
+            ports: subckt
+                .ports
+                .iter()
+                .zip(nodes)
+                .map(|(port, node)| (port.clone(), scope.node(node)))
+                .collect(),
+        };
+        for body_line in &subckt.body {
+            self.expand(body_line, &inner, netlist, warnings, depth + 1)?;
+        }
+        Ok(())
+    }
+
+    /// .model card by name, else a datasheet part from the component database
+    fn model(&self, name: &str) -> Option<ModelCard> {
+        if let Some(card) = self.models.get(&name.to_lowercase()) {
+            return Some(card.clone());
+        }
+        if let Some(spec) = diode_database()
+            .iter()
+            .find(|d| d.part_number.eq_ignore_ascii_case(name))
+        {
+            return Some(ModelCard::Diode(DiodeModel::from_spec(spec)));
+        }
+        mosfet_database()
+            .iter()
+            .find(|m| m.part_number.eq_ignore_ascii_case(name))
+            .map(|spec| ModelCard::Mosfet(MosfetModel::from_spec(spec)))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SCOPES, VALUES AND STATEMENTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Naming and parameter context for one level of subcircuit expansion
+struct Scope {
+    params: HashMap<String, f64>,
+    /// Hierarchical instance path ("" at top level, "X1.X2" inside)
+    prefix: String,
+    /// Subcircuit port name → caller's node name
+    ports: HashMap<String, String>,
+}
+
+impl Scope {
+    fn global(params: &HashMap<String, f64>) -> Self {
+        Self {
+            params: params.clone(),
+            prefix: String::new(),
+            ports: HashMap::new(),
+        }
+    }
+
+    /// Circuit node name; ground stays global
+    fn node(&self, node: &str) -> String {
+        if node == "0" || node.eq_ignore_ascii_case("gnd") {
+            return "0".to_string();
+        }
+        if let Some(outer) = self.ports.get(node) {
+            return outer.clone();
+        }
+        if self.prefix.is_empty() {
+            node.to_string()
+        } else {
+            format!("{}.{}", self.prefix, node)
+        }
+    }
+
+    /// Element name, uppercase, prefixed with the instance path
+    fn name(&self, name: &str) -> String {
+        if self.prefix.is_empty() {
+            name.to_uppercase()
+        } else {
+            format!("{}.{}", self.prefix, name.to_uppercase())
+        }
+    }
+
+    /// Number, `{expr}`, `'expr'` or bare parameter name
+    fn value(&self, word: &str) -> Result<f64, String> {
+        let word = word.trim();
+        if let Some(expr) = word
+            .strip_prefix('{')
+            .and_then(|w| w.strip_suffix('}'))
+            .or_else(|| word.strip_prefix('\'').and_then(|w| w.strip_suffix('\'')))
+        {
+            return evaluate(expr, &self.params);
+        }
+        parse_value(word).or_else(|e| self.params.get(&word.to_lowercase()).copied().ok_or(e))
+    }
+}
+
+/// `.param a=1 b={a*2} ...` → evaluated assignments, in order
+fn parse_params(
+    line: &Line,
+    text: &str,
+    scope: &Scope,
+) -> Result<Vec<(String, f64)>, SpiceParseError> {
+    let mut params = scope.params.clone();
+    let mut out = Vec::new();
+    let mut rest = text.trim();
+    if rest.is_empty() {
+        return Err(line.error(".param needs name=value"));
+    }
+    while !rest.is_empty() {
+        let (key, tail) = rest
+            .split_once('=')
+            .ok_or_else(|| line.error(format!("expected name=value in '{}'", rest)))?;
+        let key = key.trim().to_lowercase();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(line.error(format!("invalid parameter name '{}'", key)));
+        }
+        let mut parser = ExprParser::new(tail, &params);
+        let value = parser.expression().map_err(|e| line.error(e))?;
+        rest = tail[parser.pos..].trim_start_matches([' ', '\t', ',']);
+        params.insert(key.clone(), value);
+        out.push((key, value));
+    }
+    Ok(out)
+}
+
+/// `.model <name> <type>(<param>=<value> ...)`
+fn parse_model(line: &Line, scope: &Scope) -> Result<(String, ModelCard), SpiceParseError> {
+    let words = tokenize(&line.text);
+    if words.len() < 3 {
+        return Err(line.error(".model needs a name and a type"));
+    }
+    let kind = words[2].to_lowercase();
+    let mut card = match kind.as_str() {
+        "d" => ModelCard::Diode(DiodeModel::default()),
+        "npn" => ModelCard::Bjt(BjtModel::new(BjtPolarity::Npn)),
+        "pnp" => ModelCard::Bjt(BjtModel::new(BjtPolarity::Pnp)),
+        "nmos" => ModelCard::Mosfet(MosfetModel::new(MosfetPolarity::Nmos)),
+        "pmos" => ModelCard::Mosfet(MosfetModel::new(MosfetPolarity::Pmos)),
+        _ => return Err(line.error(format!("unsupported model type '{}'", words[2]))),
+    };
+
+    for word in &words[3..] {
+        let (key, text) = assignment(word)
+            .ok_or_else(|| line.error(format!("expected param=value, got '{}'", word)))?;
+        let v = scope.value(text).map_err(|e| line.error(e))?;
+        // Parameters the level-1 models do not use (CJO, TT, RS, ...) are skipped
+        match &mut card {
+            ModelCard::Diode(m) => match key.as_str() {
+                "is" => m.is = v,
+                "n" => m.n = v,
+                "bv" => m.bv = Some(v),
+                "ibv" => m.ibv = v,
+                _ => {}
+            },
+            ModelCard::Bjt(m) => match key.as_str() {
+                "is" => m.is = v,
+                "bf" => m.bf = v,
+                "br" => m.br = v,
+                "nf" => m.nf = v,
+                "nr" => m.nr = v,
+                "vaf" | "va" => m.vaf = Some(v),
+                _ => {}
+            },
+            ModelCard::Mosfet(m) => match key.as_str() {
+                "level" if v != 1.0 => {
+                    return Err(line.error("only level 1 MOSFET models are supported"))
+                }
+                "vto" | "vt0" => m.vto = v.abs(),
+                "kp" => m.kp = v,
+                "lambda" => m.lambda = v,
+                _ => {}
+            },
+        }
+    }
+    Ok((words[1].to_lowercase(), card))
+}
+
+/// Standard SPICE element letters without a model here, with what they are
+fn unsupported_element(kind: char) -> Option<&'static str> {
+    Some(match kind {
+        'B' => "behavioral source",
+        'F' => "current-controlled current source",
+        'H' => "current-controlled voltage source",
+        'J' => "JFET",
+        'K' => "mutual inductance",
+        'O' => "lossy transmission line",
+        'S' => "voltage-controlled switch",
+        'T' => "transmission line",
+        'U' => "uniform RC line",
+        'W' => "current-controlled switch",
+        'Z' => "MESFET",
+        _ => return None,
+    })
+}
+
+/// Voltage source value words after the nodes
+///
+/// A transient function (PULSE/SIN) wins over AC, which wins over DC, since
+/// the netlist holds one value per source.
+fn parse_source(words: &[String], scope: &Scope) -> Result<SourceValue, String> {
+    let mut dc = 0.0;
+    let mut ac = None;
+    let mut function = None;
+    let mut i = 0;
+    let arg = |i: usize| words.get(i).map(|w| scope.value(w)).transpose();
+
+    while i < words.len() {
+        let word = words[i].to_lowercase();
+        match word.as_str() {
+            "dc" => {
+                dc = arg(i + 1)?.ok_or("DC needs a value")?;
+                i += 2;
+            }
+            "ac" => {
+                // Magnitude and phase are optional, but only numbers count
+                let magnitude = arg(i + 1).ok().flatten();
+                let phase = magnitude.and_then(|_| arg(i + 2).ok().flatten());
+                ac = Some((magnitude.unwrap_or(1.0), phase.unwrap_or(0.0)));
+                i += 1 + magnitude.is_some() as usize + phase.is_some() as usize;
+            }
+            "pulse" | "sin" => {
+                let mut values = Vec::new();
+                i += 1;
+                while let Some(Ok(v)) = words.get(i).map(|w| scope.value(w)) {
+                    values.push(v);
+                    i += 1;
+                }
+                function = Some(if word == "pulse" {
+                    if values.len() < 2 {
+                        return Err("PULSE needs at least v1 and v2".to_string());
+                    }
+                    let at = |k: usize| values.get(k).copied().unwrap_or(0.0);
+                    SourceValue::Pulse {
+                        v1: at(0),
+                        v2: at(1),
+                        delay: at(2),
+                        rise_time: at(3),
+                        fall_time: at(4),
+                        pulse_width: values.get(5).copied().unwrap_or(f64::INFINITY),
+                        period: at(6),
+                    }
+                } else {
+                    if values.len() < 3 {
+                        return Err("SIN needs offset, amplitude and frequency".to_string());
+                    }
+                    let at = |k: usize| values.get(k).copied().unwrap_or(0.0);
+                    SourceValue::Sin {
+                        offset: at(0),
+                        amplitude: at(1),
+                        freq: at(2),
+                        delay: at(3),
+                        damping: at(4),
+                    }
+                });
+            }
+            _ if i == 0 => {
+                dc = scope.value(&words[0])?;
+                i += 1;
+            }
+            _ => return Err(format!("unexpected '{}' in source value", words[i])),
+        }
+    }
+
+    Ok(match (function, ac) {
+        (Some(f), _) => f,
+        (None, Some((magnitude, phase))) => SourceValue::AC { magnitude, phase },
+        (None, None) => SourceValue::DC(dc),
+    })
+}
+
+/// `.op`, `.ac`, `.dc`, `.tran`; output-only directives are ignored
+fn parse_directive(line: &Line, scope: &Scope) -> Result<Option<Analysis>, SpiceParseError> {
+    let words = tokenize(&line.text);
+    let keyword = words[0].to_lowercase();
+    let number = |k: usize| -> Result<f64, SpiceParseError> {
+        let word = words
+            .get(k)
+            .ok_or_else(|| line.error(format!("{}: missing argument {}", keyword, k)))?;
+        scope.value(word).map_err(|e| line.error(e))
+    };
+
+    let analysis = match keyword.as_str() {
+        ".op" => Analysis::Op,
+        ".ac" => {
+            let (sweep, points, fstart, fstop) = frequency_sweep(line, &words, 1, number)?;
+            Analysis::Ac {
+                sweep,
+                points,
+                fstart,
+                fstop,
+            }
+        }
+        ".noise" => {
+            // Tokenized as .noise V out [ref] source dec points fstart fstop
+            let at = words
+                .iter()
+                .position(|w| matches!(w.to_lowercase().as_str(), "dec" | "oct" | "lin"))
+                .ok_or_else(|| line.error(".noise: expected dec, oct or lin"))?;
+            if !(4..=5).contains(&at) || !words[1].eq_ignore_ascii_case("v") {
+                return Err(line.error(".noise: expected V(output[,ref]) source"));
+            }
+            let (sweep, points, fstart, fstop) = frequency_sweep(line, &words, at, number)?;
+            Analysis::Noise {
+                output: scope.node(&words[2]),
+                reference: (at == 5).then(|| scope.node(&words[3])),
+                source: words[at - 1].to_uppercase(),
+                sweep,
+                points,
+                fstart,
+                fstop,
+            }
+        }
+        ".dc" => {
+            let source = words
+                .get(1)
+                .ok_or_else(|| line.error(".dc: missing source name"))?
+                .to_uppercase();
+            let step = number(4)?;
+            if step == 0.0 {
+                return Err(line.error(".dc: step must be non-zero"));
+            }
+            Analysis::Dc {
+                source,
+                start: number(2)?,
+                stop: number(3)?,
+                step,
+            }
+        }
+        ".tran" => {
+            let (tstep, tstop) = (number(1)?, number(2)?);
+            if !(tstep > 0.0 && tstop > 0.0) {
+                return Err(line.error(".tran: tstep and tstop must be positive"));
+            }
+            Analysis::Tran {
+                tstep,
+                tstop,
+                tstart: if words.len() > 3 { number(3)? } else { 0.0 },
+                tmax: if words.len() > 4 {
+                    Some(number(4)?)
+                } else {
+                    None
+                },
+            }
+        }
+        ".options" | ".option" | ".temp" | ".print" | ".plot" | ".probe" | ".save" | ".meas"
+        | ".measure" | ".control" | ".endc" => return Ok(None),
+        _ => return Err(line.error(format!("unknown directive '{}'", words[0]))),
+    };
+    Ok(Some(analysis))
+}
+
+/// `dec|oct|lin points fstart fstop` starting at `words[at]`
+fn frequency_sweep(
+    line: &Line,
+    words: &[String],
+    at: usize,
+    number: impl Fn(usize) -> Result<f64, SpiceParseError>,
+) -> Result<(AcSweep, usize, f64, f64), SpiceParseError> {
+    let keyword = words[0].to_lowercase();
+    let sweep = match words.get(at).map(|w| w.to_lowercase()).as_deref() {
+        Some("dec") => AcSweep::Decade,
+        Some("oct") => AcSweep::Octave,
+        Some("lin") => AcSweep::Linear,
+        _ => return Err(line.error(format!("{}: expected dec, oct or lin", keyword))),
+    };
+    let points = number(at + 1)?;
+    if points < 1.0 {
+        return Err(line.error(format!("{}: point count must be at least 1", keyword)));
+    }
+    let (fstart, fstop) = (number(at + 2)?, number(at + 3)?);
+    if !(fstart > 0.0 && fstop >= fstart) {
+        return Err(line.error(format!("{}: need 0 < fstart <= fstop", keyword)));
+    }
+    Ok((sweep, points as usize, fstart, fstop))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EXPRESSIONS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Evaluate a whole expression against `params`
+fn evaluate(text: &str, params: &HashMap<String, f64>) -> Result<f64, String> {
+    let mut parser = ExprParser::new(text, params);
+    let value = parser.expression()?;
+    match parser.peek() {
+        None => Ok(value),
+        Some(c) => Err(format!(
+            "unexpected '{}' in expression '{}'",
+            c as char, text
+        )),
+    }
+}
+
+/// Recursive-descent evaluator; stops at the first byte that cannot
+/// continue the expression
+struct ExprParser<'a> {
+    src: &'a [u8],
+    pos: usize,
+    params: &'a HashMap<String, f64>,
+}
+
+impl<'a> ExprParser<'a> {
+    fn new(text: &'a str, params: &'a HashMap<String, f64>) -> Self {
+        Self {
+            src: text.as_bytes(),
+            pos: 0,
+            params,
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        while self.src.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+        self.src.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expression(&mut self) -> Result<f64, String> {
+        let mut v = self.term()?;
+        loop {
+            if self.eat(b'+') {
+                v += self.term()?;
+            } else if self.eat(b'-') {
+                v -= self.term()?;
+            } else {
+                return Ok(v);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<f64, String> {
+        let mut v = self.unary()?;
+        loop {
+            if self.peek() == Some(b'*') && self.src.get(self.pos + 1) != Some(&b'*') {
+                self.pos += 1;
+                v *= self.unary()?;
+            } else if self.eat(b'/') {
+                v /= self.unary()?;
+            } else {
+                return Ok(v);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<f64, String> {
+        if self.eat(b'-') {
+            return Ok(-self.unary()?);
+        }
+        if self.eat(b'+') {
+            return self.unary();
+        }
+        let base = self.primary()?;
+        let power = if self.eat(b'^') {
+            true
+        } else if self.peek() == Some(b'*') && self.src.get(self.pos + 1) == Some(&b'*') {
+            self.pos += 2;
+            true
+        } else {
+            false
+        };
+        if power {
+            // Right-associative: 2^3^2 = 2^9
+            return Ok(base.powf(self.unary()?));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<f64, String> {
+        match self.peek() {
+            Some(open @ (b'(' | b'{' | b'\'')) => {
+                let close = match open {
+                    b'(' => b')',
+                    b'{' => b'}',
+                    _ => b'\'',
+                };
+                self.pos += 1;
+                let v = self.expression()?;
+                if !self.eat(close) {
+                    return Err(format!("expected '{}'", close as char));
+                }
+                Ok(v)
+            }
+            Some(c) if c.is_ascii_digit() || c == b'.' => {
+                let (v, used) = scan_number(&self.src[self.pos..])
+                    .ok_or_else(|| "invalid number in expression".to_string())?;
+                self.pos += used;
+                Ok(v)
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == b'_' => {
+                let start = self.pos;
+                while self
+                    .src
+                    .get(self.pos)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'_')
+                {
+                    self.pos += 1;
+                }
+                let ident = String::from_utf8_lossy(&self.src[start..self.pos]).to_lowercase();
+                if self.eat(b'(') {
+                    let mut args = vec![self.expression()?];
+                    while self.eat(b',') {
+                        args.push(self.expression()?);
+                    }
+                    if !self.eat(b')') {
+                        return Err(format!("expected ')' after {}(...)", ident));
+                    }
+                    return call(&ident, &args);
+                }
+                if ident == "pi" {
+                    return Ok(std::f64::consts::PI);
+                }
+                self.params
+                    .get(&ident)
+                    .copied()
+                    .ok_or_else(|| format!("unknown parameter '{}'", ident))
+            }
+            Some(c) => Err(format!("unexpected '{}' in expression", c as char)),
+            None => Err("expression ends early".to_string()),
+        }
+    }
+}
+
+fn call(name: &str, args: &[f64]) -> Result<f64, String> {
+    let unary = |f: fn(f64) -> f64| match args {
+        [x] => Ok(f(*x)),
+        _ => Err(format!("{}() takes one argument", name)),
+    };
+    let binary = |f: fn(f64, f64) -> f64| match args {
+        [x, y] => Ok(f(*x, *y)),
+        _ => Err(format!("{}() takes two arguments", name)),
+    };
+    match name {
+        "sqrt" => unary(f64::sqrt),
+        "exp" => unary(f64::exp),
+        "ln" | "log" => unary(f64::ln),
+        "log10" => unary(f64::log10),
+        "sin" => unary(f64::sin),
+        "cos" => unary(f64::cos),
+        "tan" => unary(f64::tan),
+        "abs" => unary(f64::abs),
+        "min" => binary(f64::min),
+        "max" => binary(f64::max),
+        "pow" => binary(f64::powf),
+        _ => Err(format!("unknown function '{}'", name)),
+    }
+}
+
+/// Leading number with scale suffix and unit letters: (value, bytes used)
+fn scan_number(s: &[u8]) -> Option<(f64, usize)> {
+    let digits = |from: usize| s[from..].iter().take_while(|c| c.is_ascii_digit()).count();
+    let mut i = usize::from(matches!(s.first(), Some(b'+') | Some(b'-')));
+    let int = digits(i);
+    i += int;
+    let mut frac = 0;
+    if s.get(i) == Some(&b'.') {
+        frac = digits(i + 1);
+        i += 1 + frac;
+    }
+    if int + frac == 0 {
+        return None;
+    }
+    if matches!(s.get(i), Some(b'e') | Some(b'E')) {
+        let sign = usize::from(matches!(s.get(i + 1), Some(b'+') | Some(b'-')));
+        let exp = digits(i + 1 + sign);
+        if exp > 0 {
+            i += 1 + sign + exp;
+        }
+    }
+    let mantissa: f64 = std::str::from_utf8(&s[..i]).ok()?.parse().ok()?;
+
+    let letters = s[i..]
+        .iter()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .count();
+    let suffix = String::from_utf8_lossy(&s[i..i + letters]).to_lowercase();
+    let scale = if suffix.starts_with("meg") {
+        1e6
+    } else if suffix.starts_with("mil") {
+        25.4e-6
+    } else {
+        match suffix.bytes().next() {
+            Some(b't') => 1e12,
+            Some(b'g') => 1e9,
+            Some(b'k') => 1e3,
+            Some(b'm') => 1e-3,
+            Some(b'u') => 1e-6,
+            Some(b'n') => 1e-9,
+            Some(b'p') => 1e-12,
+            Some(b'f') => 1e-15,
+            _ => 1.0,
+        }
+    };
+    Some((mantissa * scale, i + letters))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element<'a>(parsed: &'a ParsedNetlist, name: &str) -> &'a Element {
+        parsed
+            .netlist
+            .elements
+            .iter()
+            .find(|e| e.name() == name)
+            .unwrap_or_else(|| panic!("no element {}", name))
+    }
+
+    fn resistance(parsed: &ParsedNetlist, name: &str) -> f64 {
+        match element(parsed, name) {
+            Element::Resistor { value, .. } => *value,
+            other => panic!("{} is not a resistor: {:?}", name, other),
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-12 * a.abs().max(b.abs())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // VALUES AND EXPRESSIONS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_engineering_suffixes() {
+        let cases = [
+            ("1k", 1e3),
+            ("4.7K", 4.7e3),
+            ("1meg", 1e6),
+            ("1MEG", 1e6),
+            ("2m", 2e-3),
+            ("10u", 10e-6),
+            ("2.2nF", 2.2e-9),
+            ("100p", 100e-12),
+            ("3f", 3e-15),
+            ("1g", 1e9),
+            ("1t", 1e12),
+            ("1mil", 25.4e-6),
+            ("10kOhm", 10e3),
+            ("5V", 5.0),
+            ("1e3", 1e3),
+            ("-2.5e-3", -2.5e-3),
+            (".5", 0.5),
+        ];
+        for (text, expected) in cases {
+            let v = parse_value(text).unwrap();
+            assert!(close(v, expected), "{} -> {}", text, v);
+        }
+        assert!(parse_value("abc").is_err());
+        assert!(parse_value("1k2").is_err());
+    }
+
+    #[test]
+    fn test_expressions() {
+        let mut params = HashMap::new();
+        params.insert("r".to_string(), 1e3);
+        let cases = [
+            ("1 + 2 * 3", 7.0),
+            ("(1 + 2) * 3", 9.0),
+            ("2 ^ 3 ^ 2", 512.0),
+            ("2 ** 3", 8.0),
+            ("-2 ^ 2", -4.0),
+            ("r * 2k", 2e6),
+            ("sqrt(16) + max(1, 3)", 7.0),
+            ("R / 4", 250.0),
+            ("2 * pi", 2.0 * std::f64::consts::PI),
+        ];
+        for (text, expected) in cases {
+            let v = evaluate(text, &params).unwrap();
+            assert!(close(v, expected), "{} -> {}", text, v);
+        }
+        assert!(evaluate("1 +", &params).is_err());
+        assert!(evaluate("nope * 2", &params).is_err());
+        assert!(evaluate("sqrt(1, 2)", &params).is_err());
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // ELEMENTS AND DIRECTIVES
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_basic_rc_deck() {
+        let text = "* RC Low-Pass Filter\n\
+                    V1 in 0 AC 1\n\
+                    R1 in out 1k ; series resistor\n\
+                    C1 out 0 1u\n\
+                    .ac dec 50 1 1meg\n\
+                    .end\n\
+                    R2 ignored after end 1";
+        let parsed = parse_netlist(text).unwrap();
+        assert_eq!(parsed.netlist.title, "RC Low-Pass Filter");
+        assert_eq!(parsed.netlist.elements.len(), 3);
+        assert!(close(resistance(&parsed, "R1"), 1e3));
+        assert!(matches!(
+            element(&parsed, "V1"),
+            Element::VoltageSource {
+                value: SourceValue::AC { magnitude, .. },
+                ..
+            } if *magnitude == 1.0
+        ));
+        assert_eq!(
+            parsed.analyses,
+            vec![Analysis::Ac {
+                sweep: AcSweep::Decade,
+                points: 50,
+                fstart: 1.0,
+                fstop: 1e6,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_noise_directive() {
+        let text = "* Noise\n\
+                    V1 in 0 AC 1\n\
+                    R1 in out 1k\n\
+                    R2 out ref 1k\n\
+                    R3 ref 0 1k\n\
+                    .noise v(out) v1 dec 10 1 1meg\n\
+                    .noise V(out, ref) V1 lin 100 1k 10k";
+        let parsed = parse_netlist(text).unwrap();
+        assert_eq!(
+            parsed.analyses[0],
+            Analysis::Noise {
+                output: "out".to_string(),
+                reference: None,
+                source: "V1".to_string(),
+                sweep: AcSweep::Decade,
+                points: 10,
+                fstart: 1.0,
+                fstop: 1e6,
+            }
+        );
+        assert!(matches!(
+            &parsed.analyses[1],
+            Analysis::Noise { reference: Some(r), sweep: AcSweep::Linear, .. } if r == "ref"
+        ));
+
+        let err = parse_netlist("* T\nR1 a 0 1k\n.noise out V1 dec 10 1 1k").unwrap_err();
+        assert!(err.message.contains("V(output"), "{}", err);
+    }
+
+    #[test]
+    fn test_sources_and_continuations() {
+        let text = ".title Sources\n\
+                    V1 a 0 PULSE(0 5 1n 2n 3n 1u 2u)\n\
+                    V2 b 0 DC 3 AC 1\n\
+                    V3 c 0 SIN(0 1\n\
+                    + 1k)\n\
+                    V4 d 0 12\n\
+                    I1 0 e DC 2m\n\
+                    E1 f 0 a 0 10\n\
+                    G1 g 0 a 0 1m\n\
+                    .tran 1u 10u\n\
+                    .op";
+        let parsed = parse_netlist(text).unwrap();
+        assert_eq!(parsed.netlist.title, "Sources");
+        match element(&parsed, "V1") {
+            Element::VoltageSource {
+                value:
+                    SourceValue::Pulse {
+                        v2,
+                        rise_time,
+                        period,
+                        ..
+                    },
+                ..
+            } => {
+                assert_eq!(*v2, 5.0);
+                assert!(close(*rise_time, 2e-9));
+                assert!(close(*period, 2e-6));
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(
+            element(&parsed, "V2"),
+            Element::VoltageSource {
+                value: SourceValue::AC { .. },
+                ..
+            }
+        ));
+        assert!(matches!(
+            element(&parsed, "V3"),
+            Element::VoltageSource { value: SourceValue::Sin { freq, .. }, .. } if *freq == 1e3
+        ));
+        assert!(matches!(
+            element(&parsed, "V4"),
+            Element::VoltageSource { value: SourceValue::DC(v), .. } if *v == 12.0
+        ));
+        assert!(matches!(
+            element(&parsed, "I1"),
+            Element::CurrentSource { value: SourceValue::DC(v), .. } if close(*v, 2e-3)
+        ));
+        assert!(matches!(element(&parsed, "E1"), Element::VCVS { gain, .. } if *gain == 10.0));
+        assert!(matches!(
+            element(&parsed, "G1"),
+            Element::VCCS { transconductance, .. } if close(*transconductance, 1e-3)
+        ));
+        assert_eq!(parsed.analyses.len(), 2);
+        assert_eq!(parsed.analyses[1], Analysis::Op);
+        assert!(matches!(
+            parsed.analyses[0],
+            Analysis::Tran { tstep, tstop, tmax: None, .. } if close(tstep, 1e-6) && close(tstop, 1e-5)
+        ));
+    }
+
+    #[test]
+    fn test_params_and_models() {
+        let text = ".param rload = 2k gain={rload/1k}\n\
+                    .param half = 'rload / 2'\n\
+                    R1 a 0 {rload}\n\
+                    R2 a 0 half\n\
+                    E1 b 0 a 0 {gain * 10}\n\
+                    D1 a k DMOD\n\
+                    Q1 c b e QMOD\n\
+                    Q2 c b e sub QMOD\n\
+                    M1 d g s s NCH W=10u L=2u\n\
+                    .model DMOD D(IS=1e-15 N=1.5 CJO=2p)\n\
+                    .model QMOD PNP(BF=50 VAF=80)\n\
+                    .model NCH NMOS(LEVEL=1 VTO=0.7 KP=100u)\n\
+                    .dc V1 0 {rload/400} 0.1";
+        let parsed = parse_netlist(text).unwrap();
+        assert_eq!(parsed.params["gain"], 2.0);
+        assert!(close(resistance(&parsed, "R1"), 2e3));
+        assert!(close(resistance(&parsed, "R2"), 1e3));
+        assert!(matches!(element(&parsed, "E1"), Element::VCVS { gain, .. } if *gain == 20.0));
+        match element(&parsed, "D1") {
+            Element::Diode { model, .. } => {
+                assert!(close(model.is, 1e-15));
+                assert_eq!(model.n, 1.5);
+            }
+            other => panic!("{:?}", other),
+        }
+        for name in ["Q1", "Q2"] {
+            match element(&parsed, name) {
+                Element::Bjt { model, node_e, .. } => {
+                    assert_eq!(model.polarity, BjtPolarity::Pnp);
+                    assert_eq!(model.bf, 50.0);
+                    assert_eq!(model.vaf, Some(80.0));
+                    assert_eq!(node_e, "e");
+                }
+                other => panic!("{:?}", other),
+            }
+        }
+        match element(&parsed, "M1") {
+            Element::Mosfet { model, .. } => {
+                assert_eq!(model.vto, 0.7);
+                // KP·W/L
+                assert!(close(model.kp, 500e-6));
+            }
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(
+            parsed.analyses,
+            vec![Analysis::Dc {
+                source: "V1".to_string(),
+                start: 0.0,
+                stop: 5.0,
+                step: 0.1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_datasheet_parts_as_models() {
+        let diode = &diode_database()[0];
+        let mosfet = &mosfet_database()[0];
+        let text = format!(
+            "D1 a 0 {}\nM1 d g 0 {}",
+            diode.part_number.to_lowercase(),
+            mosfet.part_number
+        );
+        let parsed = parse_netlist(&text).unwrap();
+        assert!(matches!(
+            element(&parsed, "D1"),
+            Element::Diode { model, .. } if *model == DiodeModel::from_spec(diode)
+        ));
+        assert!(matches!(
+            element(&parsed, "M1"),
+            Element::Mosfet { model, .. } if *model == MosfetModel::from_spec(mosfet)
+        ));
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // SUBCIRCUITS AND INCLUDES
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_subcircuit_expansion() {
+        let text = ".subckt divider top mid params: ratio=1\n\
+                    R1 top mid 1k\n\
+                    R2 mid 0 {1k * ratio}\n\
+                    C1 mid internal 1n\n\
+                    .ends divider\n\
+                    .subckt pair in out\n\
+                    X1 in m divider\n\
+                    X2 m out divider ratio=3\n\
+                    .ends\n\
+                    V1 in 0 1\n\
+                    XA in out pair";
+        let parsed = parse_netlist(text).unwrap();
+        let netlist = &parsed.netlist;
+        assert_eq!(netlist.elements.len(), 7);
+
+        // Ports map to the caller's nodes, internals get the instance path
+        match element(&parsed, "XA.X2.R1") {
+            Element::Resistor { node_p, node_n, .. } => {
+                assert_eq!(node_p, "XA.m");
+                assert_eq!(node_n, "out");
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(netlist.node_index("XA.X1.internal").is_some());
+        assert!(netlist.node_index("XA.X2.internal").is_some());
+        assert!(netlist.node_index("internal").is_none());
+        // Ground is global; instance parameters override defaults
+        match element(&parsed, "XA.X2.R2") {
+            Element::Resistor { node_n, value, .. } => {
+                assert_eq!(node_n, "0");
+                assert!(close(*value, 3e3));
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(close(resistance(&parsed, "XA.X1.R2"), 1e3));
+    }
+
+    #[test]
+    fn test_include_from_file_map() {
+        let mut files = HashMap::new();
+        files.insert(
+            "models.lib".to_string(),
+            ".model FAST D(IS=1n)\n.param rbias=47k".to_string(),
+        );
+        files.insert("bad.lib".to_string(), "* fine\nR9 a b oops".to_string());
+
+        let text = ".include \"models.lib\"\nD1 a 0 FAST\nR1 a 0 {rbias}";
+        let parsed = parse_netlist_with_includes(text, &files).unwrap();
+        assert!(close(resistance(&parsed, "R1"), 47e3));
+
+        // Errors inside an include point at the included file
+        let err = parse_netlist_with_includes("R1 a 0 1\n.inc bad.lib", &files).unwrap_err();
+        assert_eq!(err.file.as_deref(), Some("bad.lib"));
+        assert_eq!(err.line, 2);
+        assert!(err.to_string().starts_with("bad.lib:2:"));
+
+        let err = parse_netlist_with_includes(".include missing.lib", &files).unwrap_err();
+        assert_eq!((err.file, err.line), (None, 1));
+    }
+
+    #[test]
+    fn test_errors_carry_line_numbers() {
+        let cases = [
+            ("R1 a b 1k\nR2 a b", 2, "expected"),
+            ("* title\n\nR1 a b 1x2", 3, "invalid number"),
+            ("D1 a b NOPE", 1, "unknown model"),
+            (".model Q1 NPN\nD1 a b Q1", 2, "not a diode model"),
+            ("X1 a b nothing", 1, "unknown subcircuit"),
+            (".subckt s a b\nR1 a b 1\n.ends\nX1 a s", 4, "ports"),
+            (".subckt s a b\nR1 a b 1", 1, "no .ends"),
+            ("R1 a b {1 +}", 1, "expression ends early"),
+            (".four 1k v(out)", 1, "unknown directive"),
+            ("+ R1 a b 1", 1, "continuation"),
+            (".model M NMOS(LEVEL=3)", 1, "level 1"),
+            ("V1 a 0 1\n.ac dec 10 0 1k", 2, "fstart"),
+        ];
+        for (text, line, message) in cases {
+            let err = parse_netlist(text).unwrap_err();
+            assert_eq!(err.line, line, "{:?}: {}", text, err);
+            assert!(err.message.contains(message), "{:?}: {}", text, err);
+            assert!(err.to_string().starts_with(&format!("line {}:", line)));
+        }
+    }
+
+    #[test]
+    fn test_unknown_element_warns() {
+        let parsed = parse_netlist("R1 a 0 1k\nA1 a b\nC1 a 0 1u").unwrap();
+        assert_eq!(parsed.netlist.elements.len(), 2);
+        assert_eq!(parsed.warnings.len(), 1);
+        assert_eq!(parsed.warnings[0].line, 2);
+        assert!(parsed.warnings[0].message.contains("unknown element"));
+    }
+
+    #[test]
+    fn test_unsupported_spice_element_errors() {
+        for (text, letter) in [
+            ("R1 a 0 1k\nL1 a 0 1u\nL2 b 0 1u\nK1 L1 L2 0.9", "K1"),
+            ("V1 a 0 1\nR1 a b 1k\nR2 b 0 1k\nF1 b 0 V1 2", "F1"),
+            ("V1 a 0 1\nR1 a 0 1k\nH1 b 0 V1 100\nR2 b 0 1k", "H1"),
+            ("V1 a 0 1\nT1 a 0 b 0 Z0=50 TD=1n\nR1 b 0 50", "T1"),
+        ] {
+            let err = parse_netlist(text).unwrap_err();
+            let line = text.lines().position(|l| l.starts_with(letter)).unwrap() + 1;
+            assert_eq!(err.line, line, "{}", err);
+            assert!(err.message.contains("unsupported element"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_tools_spice_example_netlist() {
+        // Default deck of the TOOLS/SPICE editor, with the '#' comments its
+        // former parser accepted
+        let text = "# RC low-pass, corner at 159 Hz\n\
+                    * RC Low-Pass Filter\n\
+                    V1 in 0 AC 1\n\
+                    # source resistance\n\
+                    R1 in out 1k\n\
+                    C1 out 0 1u";
+        let parsed = parse_netlist(text).unwrap();
+        assert_eq!(parsed.netlist.elements.len(), 3);
+        assert!(parsed.warnings.is_empty());
+        assert!(close(resistance(&parsed, "R1"), 1e3));
+    }
+
+    #[test]
+    fn test_recursive_subcircuit_rejected() {
+        let text = ".subckt loop a\nX1 a loop\n.ends\nX1 n loop";
+        let err = parse_netlist(text).unwrap_err();
+        assert!(err.message.contains("nest too deep"), "{}", err);
+    }
+}
@@ -0,0 +1,963 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: polezero.rs | DNA/src/physics/electromagnetics/lumped/polezero.rs
+//! PURPOSE: Pole-zero analysis (.pz) from the MNA descriptor system G + sC
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Pole-zero analysis (.pz) from the MNA descriptor system G + sC
+//!
+//! LAYER: DNA → PHYSICS → ELECTROMAGNETICS → LUMPED
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM: Generalized eigenvalues by shift-and-invert                      │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Descriptor MNA: (G + sC) x = b u,  y = cᵀ x                                 │
+//! │   R, controlled sources, V/L branch incidence → G                           │
+//! │   Capacitors → C,  inductor branch row: v_p - v_n - sL i_L = 0              │
+//! │                                                                             │
+//! │ Poles:  det(G + sC) = 0                                                     │
+//! │ Zeros:  det [G + sC  b; cᵀ 0] = 0  (bordered system)                        │
+//! │                                                                             │
+//! │ Shift σ ≈ -‖G‖/‖C‖, M = (G + σC)⁻¹ C  (sparse LU, one solve per column)     │
+//! │   eig(M) = μ  →  s = σ - 1/μ;  μ ≈ 0 are infinite eigenvalues (dropped)     │
+//! │ eig: balance → Hessenberg (Gauss) → Francis double-shift QR                 │
+//! │                                                                             │
+//! │ Gain: H(s) = K Π(s - z) / Π(s - p),  K from one solve at s₀ = jω₀           │
+//! │ Uncontrollable/unobservable modes cancel as coincident pole-zero pairs      │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ PoleZeroOptions    Output reference node, cancellation tolerance            │
+//! │ PoleZeroResult     Poles, zeros and gain of one input → output transfer     │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! DEPENDS ON:
+//!   • super::matrix → MNAMatrix stamps for G and C
+//!   • super::sparse → SparseLu for the shifted pencil
+//!   • power::control → TransferFunction
+//!
+//! USED BY:
+//!   • physics/electromagnetics/lumped/vectfit.rs → eigenvalues
+//!   • CORE/SPICE_ENGINE → .pz analysis
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// CODE BELOW - Optimized for ML development
+// ─────────────────────────────────────────────────────────────────────────────────
+
+use super::ac::Complex;
+use super::matrix::MNAMatrix;
+use super::netlist::{Element, Netlist};
+use super::sparse::{SparseLu, SparseMatrix};
+use crate::power::control::{self, TransferFunction};
+use std::f64::consts::TAU;
+
+/// |μ·σ| below which an eigenvalue of (G + σC)⁻¹C is taken as infinite
+///
+/// Defective infinite eigenvalues (a capacitor across a voltage source)
+/// perturb to O(√ε), so roots beyond ~10⁷·|σ| are not trusted.
+const INFINITE_EIGENVALUE: f64 = 1e-7;
+
+/// Multiples of the nominal shift tried until G + σC factors
+const SHIFT_FACTORS: [f64; 5] = [1.0, 0.37, 2.9, 0.013, 41.0];
+
+/// QR sweeps allowed per eigenvalue
+const MAX_QR_ITERATIONS: usize = 30;
+
+/// Pole-zero analysis options
+#[derive(Clone, Debug)]
+pub struct PoleZeroOptions {
+    /// Output reference node (ground when `None`)
+    pub reference: Option<String>,
+    /// Relative distance below which a pole and zero cancel (0 keeps all)
+    pub cancel_tolerance: f64,
+}
+
+impl Default for PoleZeroOptions {
+    fn default() -> Self {
+        Self {
+            reference: None,
+            cancel_tolerance: 1e-6,
+        }
+    }
+}
+
+/// Poles, zeros and gain of H(s) = K Π(s - z) / Π(s - p)
+///
+/// Roots are complex frequencies in rad/s, sorted by magnitude.
+#[derive(Clone, Debug)]
+pub struct PoleZeroResult {
+    pub poles: Vec<Complex>,
+    pub zeros: Vec<Complex>,
+    pub gain: f64,
+}
+
+impl PoleZeroResult {
+    /// H(s) at s = jω for frequency `freq` (Hz)
+    pub fn evaluate(&self, freq: f64) -> Complex {
+        rational(
+            self.gain,
+            &self.zeros,
+            &self.poles,
+            Complex::new(0.0, TAU * freq),
+        )
+    }
+
+    /// True when every pole lies in the open left half-plane
+    pub fn is_stable(&self) -> bool {
+        self.poles.iter().all(|p| p.real < 0.0)
+    }
+
+    /// Pole natural frequencies |s| / 2π (Hz)
+    pub fn pole_frequencies(&self) -> Vec<f64> {
+        self.poles.iter().map(|p| p.magnitude() / TAU).collect()
+    }
+
+    /// Zero natural frequencies |s| / 2π (Hz)
+    pub fn zero_frequencies(&self) -> Vec<f64> {
+        self.zeros.iter().map(|z| z.magnitude() / TAU).collect()
+    }
+
+    /// Equivalent `power::control` transfer function
+    pub fn transfer_function(&self) -> TransferFunction {
+        to_transfer_function(self.gain, &self.zeros, &self.poles)
+    }
+}
+
+/// K Π(s - z) / Π(s - p)
+pub(super) fn rational(gain: f64, zeros: &[Complex], poles: &[Complex], s: Complex) -> Complex {
+    let mut h = Complex::new(gain, 0.0);
+    for &z in zeros {
+        h = h * (s - z);
+    }
+    for &p in poles {
+        h = h / (s - p);
+    }
+    h
+}
+
+pub(super) fn to_transfer_function(
+    gain: f64,
+    zeros: &[Complex],
+    poles: &[Complex],
+) -> TransferFunction {
+    TransferFunction::new(
+        gain,
+        zeros.iter().map(|&c| c.into()).collect(),
+        poles.iter().map(|&c| c.into()).collect(),
+    )
+}
+
+/// Pole-zero analysis of V(output) / input source
+pub fn pole_zero(netlist: &Netlist, input: &str, output: &str) -> Result<PoleZeroResult, String> {
+    pole_zero_with(netlist, input, output, &PoleZeroOptions::default())
+}
+
+/// Pole-zero analysis with explicit options
+///
+/// `input` names an independent voltage or current source; the transfer is
+/// V(output, reference) per volt or amp of that source. All other
+/// independent sources are zeroed.
+pub fn pole_zero_with(
+    netlist: &Netlist,
+    input: &str,
+    output: &str,
+    options: &PoleZeroOptions,
+) -> Result<PoleZeroResult, String> {
+    let node = |name: &str| {
+        netlist
+            .node_index(name)
+            .ok_or_else(|| format!("Unknown node '{}'", name))
+    };
+    let out = node(output)?;
+    let reference = match &options.reference {
+        Some(name) => node(name)?,
+        None => 0,
+    };
+    if out == reference {
+        return Err("Pole-zero output and reference are the same node".to_string());
+    }
+
+    let Descriptor { g, c, b } = descriptor(netlist, input)?;
+    let n = g.len();
+    let mut out_row = vec![0.0; n];
+    if out > 0 {
+        out_row[out - 1] = 1.0;
+    }
+    if reference > 0 {
+        out_row[reference - 1] = -1.0;
+    }
+
+    let mut poles = pencil_roots(&g, &c)?;
+
+    // Bordered pencil: [G b; cᵀ 0] + s [C 0; 0 0]
+    let mut gz: Vec<Vec<f64>> = g
+        .iter()
+        .zip(&b)
+        .map(|(row, &bi)| {
+            let mut row = row.clone();
+            row.push(bi);
+            row
+        })
+        .collect();
+    let mut border = out_row.clone();
+    border.push(0.0);
+    gz.push(border);
+    let mut cz: Vec<Vec<f64>> = c
+        .iter()
+        .map(|row| {
+            let mut row = row.clone();
+            row.push(0.0);
+            row
+        })
+        .collect();
+    cz.push(vec![0.0; n + 1]);
+    let mut zeros = pencil_roots(&gz, &cz)?;
+
+    if options.cancel_tolerance > 0.0 {
+        cancel_pairs(&mut poles, &mut zeros, options.cancel_tolerance);
+    }
+
+    // Gain from one complex solve away from every root
+    let scale = pencil_scale(&g, &c).unwrap_or(1.0);
+    let s0 = [1.0, 0.31, 3.7]
+        .iter()
+        .map(|&f| Complex::new(0.0, f * scale))
+        .max_by(|a, b| {
+            let clearance = |s: &Complex| {
+                poles
+                    .iter()
+                    .chain(&zeros)
+                    .map(|r| (*s - *r).magnitude())
+                    .fold(f64::INFINITY, f64::min)
+            };
+            clearance(a).total_cmp(&clearance(b))
+        })
+        .unwrap();
+    let mut pencil = SparseMatrix::new(n);
+    for (i, j, gv, cv) in entries(&g, &c) {
+        pencil.add(i, j, Complex::new(gv, 0.0) + s0 * cv);
+    }
+    let rhs: Vec<Complex> = b.iter().map(|&v| Complex::new(v, 0.0)).collect();
+    let x = SparseLu::factor(&pencil)?.solve(&rhs);
+    let h0 = out_row
+        .iter()
+        .zip(&x)
+        .fold(Complex::zero(), |acc, (&ci, &xi)| acc + xi * ci);
+    let gain = (h0 / rational(1.0, &zeros, &poles, s0)).real;
+
+    Ok(PoleZeroResult { poles, zeros, gain })
+}
+
+/// Dense (G + sC) x = b u for one input source
+struct Descriptor {
+    g: Vec<Vec<f64>>,
+    c: Vec<Vec<f64>>,
+    b: Vec<f64>,
+}
+
+fn descriptor(netlist: &Netlist, input: &str) -> Result<Descriptor, String> {
+    let node = |name: &str| {
+        netlist
+            .node_index(name)
+            .ok_or_else(|| format!("Unknown node '{}'", name))
+    };
+    let num_nodes = netlist.num_nodes();
+    let num_branches = netlist.num_voltage_sources();
+    let num_inductors = netlist
+        .elements
+        .iter()
+        .filter(|e| matches!(e, Element::Inductor { .. }))
+        .count();
+
+    let mut g = MNAMatrix::new(num_nodes, num_branches + num_inductors);
+    let mut c = MNAMatrix::new(num_nodes, num_branches + num_inductors);
+    let mut b = vec![0.0; g.size];
+    let mut input_found = false;
+    let (mut branch, mut inductor) = (0, num_branches);
+
+    for element in &netlist.elements {
+        let is_input = element.name().eq_ignore_ascii_case(input);
+        match element {
+            Element::Resistor {
+                node_p,
+                node_n,
+                value,
+                ..
+            } => g.stamp_resistor(node(node_p)?, node(node_n)?, *value),
+            Element::Capacitor {
+                node_p,
+                node_n,
+                value,
+                ..
+            } => c.stamp_conductance(node(node_p)?, node(node_n)?, *value),
+            Element::Inductor {
+                node_p,
+                node_n,
+                value,
+                ..
+            } => {
+                g.stamp_voltage_source(node(node_p)?, node(node_n)?, inductor, 0.0);
+                let row = num_nodes + inductor;
+                c.matrix.add(row, row, -value);
+                inductor += 1;
+            }
+            Element::VoltageSource { node_p, node_n, .. } => {
+                g.stamp_voltage_source(node(node_p)?, node(node_n)?, branch, 0.0);
+                if is_input {
+                    b[num_nodes + branch] = 1.0;
+                    input_found = true;
+                }
+                branch += 1;
+            }
+            Element::CurrentSource { node_p, node_n, .. } => {
+                if is_input {
+                    let (p, n) = (node(node_p)?, node(node_n)?);
+                    // Source current flows from node_p through the source to node_n
+                    if p > 0 {
+                        b[p - 1] -= 1.0;
+                    }
+                    if n > 0 {
+                        b[n - 1] += 1.0;
+                    }
+                    input_found = true;
+                }
+            }
+            Element::VCVS {
+                node_out_p,
+                node_out_n,
+                node_ctrl_p,
+                node_ctrl_n,
+                gain,
+                ..
+            } => {
+                g.stamp_vcvs(
+                    node(node_out_p)?,
+                    node(node_out_n)?,
+                    node(node_ctrl_p)?,
+                    node(node_ctrl_n)?,
+                    branch,
+                    *gain,
+                );
+                branch += 1;
+            }
+            Element::VCCS {
+                node_out_p,
+                node_out_n,
+                node_ctrl_p,
+                node_ctrl_n,
+                transconductance,
+                ..
+            } => g.stamp_vccs(
+                node(node_out_p)?,
+                node(node_out_n)?,
+                node(node_ctrl_p)?,
+                node(node_ctrl_n)?,
+                *transconductance,
+            ),
+            other => {
+                return Err(format!(
+                    "{}: pole-zero analysis needs a linear netlist",
+                    other.name()
+                ))
+            }
+        }
+        if is_input
+            && !matches!(
+                element,
+                Element::VoltageSource { .. } | Element::CurrentSource { .. }
+            )
+        {
+            return Err(format!(
+                "{}: pole-zero input must be an independent source",
+                input
+            ));
+        }
+    }
+
+    if !input_found {
+        return Err(format!("Unknown element '{}'", input));
+    }
+    Ok(Descriptor {
+        g: g.matrix.to_dense(),
+        c: c.matrix.to_dense(),
+        b,
+    })
+}
+
+/// Nominal shift magnitude ‖G‖ / ‖C‖ (None without dynamic elements)
+fn pencil_scale(g: &[Vec<f64>], c: &[Vec<f64>]) -> Option<f64> {
+    let norm = |m: &[Vec<f64>]| m.iter().flatten().fold(0.0_f64, |acc, v| acc.max(v.abs()));
+    let (ng, nc) = (norm(g), norm(c));
+    if nc == 0.0 {
+        None
+    } else if ng == 0.0 {
+        Some(1.0)
+    } else {
+        Some(ng / nc)
+    }
+}
+
+/// (row, column, G, C) for every position where G or C is nonzero
+fn entries<'a>(
+    g: &'a [Vec<f64>],
+    c: &'a [Vec<f64>],
+) -> impl Iterator<Item = (usize, usize, f64, f64)> + 'a {
+    g.iter().zip(c).enumerate().flat_map(|(i, (g_row, c_row))| {
+        g_row
+            .iter()
+            .zip(c_row)
+            .enumerate()
+            .filter(|(_, (&gv, &cv))| gv != 0.0 || cv != 0.0)
+            .map(move |(j, (&gv, &cv))| (i, j, gv, cv))
+    })
+}
+
+/// Finite roots s of det(G + sC) = 0, sorted by magnitude
+fn pencil_roots(g: &[Vec<f64>], c: &[Vec<f64>]) -> Result<Vec<Complex>, String> {
+    let n = g.len();
+    let Some(scale) = pencil_scale(g, c) else {
+        return Ok(Vec::new());
+    };
+
+    // Shift off any pole at the origin (integrators, floating capacitors)
+    let mut factored = None;
+    for factor in SHIFT_FACTORS {
+        let sigma = -scale * factor;
+        let mut shifted = SparseMatrix::new(n);
+        for (i, j, gv, cv) in entries(g, c) {
+            let v = gv + sigma * cv;
+            if v != 0.0 {
+                shifted.add(i, j, v);
+            }
+        }
+        if let Ok(lu) = SparseLu::factor(&shifted) {
+            factored = Some((sigma, lu));
+            break;
+        }
+    }
+    let (sigma, lu) =
+        factored.ok_or_else(|| "Pole-zero: G + sC is singular for every s".to_string())?;
+
+    // M = (G + σC)⁻¹ C, column by column
+    let mut m = vec![vec![0.0; n]; n];
+    for j in 0..n {
+        let column: Vec<f64> = c.iter().map(|row| row[j]).collect();
+        if column.iter().all(|&v| v == 0.0) {
+            continue;
+        }
+        for (i, v) in lu.solve(&column).into_iter().enumerate() {
+            m[i][j] = v;
+        }
+    }
+
+    let mut roots: Vec<Complex> = eigenvalues(&m)?
+        .into_iter()
+        .filter(|mu| mu.magnitude() * sigma.abs() > INFINITE_EIGENVALUE)
+        .map(|mu| Complex::new(sigma, 0.0) - Complex::new(1.0, 0.0) / mu)
+        .collect();
+    roots.sort_by(|a, b| a.magnitude().total_cmp(&b.magnitude()));
+    Ok(roots)
+}
+
+/// Remove pole-zero pairs closer than `tolerance` relative to their size
+fn cancel_pairs(poles: &mut Vec<Complex>, zeros: &mut Vec<Complex>, tolerance: f64) {
+    let mut p: Vec<control::Complex> = poles.iter().map(|&c| c.into()).collect();
+    let mut z: Vec<control::Complex> = zeros.iter().map(|&c| c.into()).collect();
+    control::cancel_common_roots(&mut p, &mut z, tolerance);
+    *poles = p.into_iter().map(Complex::from).collect();
+    *zeros = z.into_iter().map(Complex::from).collect();
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DENSE EIGENVALUES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Eigenvalues of a general real matrix
+///
+/// Balancing, Gaussian reduction to upper Hessenberg form and Francis
+/// double-shift QR. Complex eigenvalues come out as exact conjugate pairs.
+pub(super) fn eigenvalues(matrix: &[Vec<f64>]) -> Result<Vec<Complex>, String> {
+    let mut a = matrix.to_vec();
+    balance(&mut a);
+    hessenberg(&mut a);
+    hessenberg_qr(&mut a)
+}
+
+/// Similarity scaling by powers of two so row and column norms match
+fn balance(a: &mut [Vec<f64>]) {
+    const RADIX: f64 = 2.0;
+    let n = a.len();
+    let mut done = false;
+    while !done {
+        done = true;
+        for i in 0..n {
+            let (mut c, mut r) = (0.0, 0.0);
+            for j in (0..n).filter(|&j| j != i) {
+                c += a[j][i].abs();
+                r += a[i][j].abs();
+            }
+            if c == 0.0 || r == 0.0 {
+                continue;
+            }
+            let s = c + r;
+            let mut f = 1.0;
+            while c < r / RADIX {
+                f *= RADIX;
+                c *= RADIX * RADIX;
+            }
+            while c > r * RADIX {
+                f /= RADIX;
+                c /= RADIX * RADIX;
+            }
+            if (c + r) / f < 0.95 * s {
+                done = false;
+                for v in a[i].iter_mut() {
+                    *v /= f;
+                }
+                for row in a.iter_mut() {
+                    row[i] *= f;
+                }
+            }
+        }
+    }
+}
+
+/// Reduce to upper Hessenberg form by stabilized elementary similarity
+fn hessenberg(a: &mut [Vec<f64>]) {
+    let n = a.len();
+    for m in 1..n.saturating_sub(1) {
+        // Pivot: largest entry below the diagonal in column m - 1
+        let mut x = 0.0;
+        let mut pivot = m;
+        for (j, row) in a.iter().enumerate().skip(m) {
+            if row[m - 1].abs() > f64::abs(x) {
+                x = row[m - 1];
+                pivot = j;
+            }
+        }
+        if pivot != m {
+            a.swap(pivot, m);
+            for row in a.iter_mut() {
+                row.swap(pivot, m);
+            }
+        }
+        if x == 0.0 {
+            continue;
+        }
+        for i in m + 1..n {
+            let y = a[i][m - 1] / x;
+            if y == 0.0 {
+                continue;
+            }
+            a[i][m - 1] = 0.0;
+            #[allow(clippy::needless_range_loop)]
+            for j in m..n {
+                a[i][j] -= y * a[m][j];
+            }
+            for row in a.iter_mut() {
+                row[m] += y * row[i];
+            }
+        }
+    }
+}
+
+/// Francis double-shift QR on an upper Hessenberg matrix (destroys `a`)
+fn hessenberg_qr(a: &mut [Vec<f64>]) -> Result<Vec<Complex>, String> {
+    let n = a.len();
+    let mut roots = vec![Complex::zero(); n];
+    let mut norm = 0.0;
+    for (i, row) in a.iter().enumerate() {
+        norm += row[i.saturating_sub(1)..]
+            .iter()
+            .map(|v| v.abs())
+            .sum::<f64>();
+    }
+
+    // Accumulated exceptional shifts
+    let mut t = 0.0;
+    // Active block is rows/columns l..=nn
+    let mut nn = n as isize - 1;
+    while nn >= 0 {
+        let mut iterations = 0;
+        loop {
+            let nu = nn as usize;
+
+            // Look for a negligible subdiagonal element
+            let mut l = nu;
+            while l >= 1 {
+                let mut s = a[l - 1][l - 1].abs() + a[l][l].abs();
+                if s == 0.0 {
+                    s = norm;
+                }
+                if a[l][l - 1].abs() + s == s {
+                    a[l][l - 1] = 0.0;
+                    break;
+                }
+                l -= 1;
+            }
+
+            let mut x = a[nu][nu];
+            if l == nu {
+                // One root found
+                roots[nu] = Complex::new(x + t, 0.0);
+                nn -= 1;
+            } else {
+                let mut y = a[nu - 1][nu - 1];
+                let mut w = a[nu][nu - 1] * a[nu - 1][nu];
+                if l == nu - 1 {
+                    // Two roots from the trailing 2×2 block
+                    let p = 0.5 * (y - x);
+                    let q = p * p + w;
+                    let z = q.abs().sqrt();
+                    x += t;
+                    if q >= 0.0 {
+                        let z = p + z.copysign(p);
+                        roots[nu - 1] = Complex::new(x + z, 0.0);
+                        roots[nu] = Complex::new(if z != 0.0 { x - w / z } else { x + z }, 0.0);
+                    } else {
+                        roots[nu - 1] = Complex::new(x + p, -z);
+                        roots[nu] = Complex::new(x + p, z);
+                    }
+                    nn -= 2;
+                } else {
+                    if iterations == MAX_QR_ITERATIONS {
+                        return Err("Eigenvalues: QR iteration did not converge".to_string());
+                    }
+                    if iterations == 10 || iterations == 20 {
+                        // Exceptional shift
+                        t += x;
+                        for (i, row) in a.iter_mut().enumerate().take(nu + 1) {
+                            row[i] -= x;
+                        }
+                        let s = a[nu][nu - 1].abs() + a[nu - 1][nu - 2].abs();
+                        x = 0.75 * s;
+                        y = x;
+                        w = -0.4375 * s * s;
+                    }
+                    iterations += 1;
+
+                    // Two consecutive small subdiagonal elements
+                    let mut m = nu - 2;
+                    let (mut p, mut q, mut r);
+                    loop {
+                        let z = a[m][m];
+                        r = x - z;
+                        let s = y - z;
+                        p = (r * s - w) / a[m + 1][m] + a[m][m + 1];
+                        q = a[m + 1][m + 1] - z - r - s;
+                        r = a[m + 2][m + 1];
+                        let s = p.abs() + q.abs() + r.abs();
+                        p /= s;
+                        q /= s;
+                        r /= s;
+                        if m == l {
+                            break;
+                        }
+                        let u = a[m][m - 1].abs() * (q.abs() + r.abs());
+                        let v = p.abs() * (a[m - 1][m - 1].abs() + z.abs() + a[m + 1][m + 1].abs());
+                        if u + v == v {
+                            break;
+                        }
+                        m -= 1;
+                    }
+                    for i in m + 2..=nu {
+                        a[i][i - 2] = 0.0;
+                        if i != m + 2 {
+                            a[i][i - 3] = 0.0;
+                        }
+                    }
+
+                    // Double QR step on rows l..=nn and columns m..=nn
+                    for k in m..nu {
+                        let mut scale = 0.0;
+                        if k != m {
+                            p = a[k][k - 1];
+                            q = a[k + 1][k - 1];
+                            r = if k != nu - 1 { a[k + 2][k - 1] } else { 0.0 };
+                            scale = p.abs() + q.abs() + r.abs();
+                            if scale != 0.0 {
+                                p /= scale;
+                                q /= scale;
+                                r /= scale;
+                            }
+                        }
+                        let s = (p * p + q * q + r * r).sqrt().copysign(p);
+                        if s == 0.0 {
+                            continue;
+                        }
+                        if k == m {
+                            if l != m {
+                                a[k][k - 1] = -a[k][k - 1];
+                            }
+                        } else {
+                            a[k][k - 1] = -s * scale;
+                        }
+                        p += s;
+                        let (x, y, z) = (p / s, q / s, r / s);
+                        q /= p;
+                        r /= p;
+                        #[allow(clippy::needless_range_loop)]
+                        for j in k..=nu {
+                            let mut h = a[k][j] + q * a[k + 1][j];
+                            if k != nu - 1 {
+                                h += r * a[k + 2][j];
+                                a[k + 2][j] -= h * z;
+                            }
+                            a[k + 1][j] -= h * y;
+                            a[k][j] -= h * x;
+                        }
+                        for row in a.iter_mut().take(nu.min(k + 3) + 1).skip(l) {
+                            let mut h = x * row[k] + y * row[k + 1];
+                            if k != nu - 1 {
+                                h += z * row[k + 2];
+                                row[k + 2] -= h * r;
+                            }
+                            row[k + 1] -= h * q;
+                            row[k] -= h;
+                        }
+                    }
+                }
+            }
+
+            if l as isize >= nn - 1 {
+                break;
+            }
+        }
+    }
+
+    Ok(roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::electromagnetics::lumped::{ac_point, SourceValue};
+    fn close(a: f64, b: f64, tol: f64) -> bool {
+        (a - b).abs() <= tol * b.abs().max(1e-30)
+    }
+
+    fn ac_source(name: &str, node: &str) -> Element {
+        Element::VoltageSource {
+            name: name.to_string(),
+            node_p: node.to_string(),
+            node_n: "0".to_string(),
+            value: SourceValue::AC {
+                magnitude: 1.0,
+                phase: 0.0,
+            },
+        }
+    }
+
+    fn passive(kind: char, name: &str, p: &str, n: &str, value: f64) -> Element {
+        let (name, node_p, node_n) = (name.to_string(), p.to_string(), n.to_string());
+        match kind {
+            'R' => Element::Resistor {
+                name,
+                node_p,
+                node_n,
+                value,
+            },
+            'C' => Element::Capacitor {
+                name,
+                node_p,
+                node_n,
+                value,
+            },
+            _ => Element::Inductor {
+                name,
+                node_p,
+                node_n,
+                value,
+            },
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // Eigenvalues
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_eigenvalues_companion() {
+        // Companion matrix of (s + 1)(s + 2)(s² + 2s + 5): roots -1, -2, -1 ± 2j
+        // s⁴ + 5s³ + 13s² + 19s + 10
+        let a = vec![
+            vec![-5.0, -13.0, -19.0, -10.0],
+            vec![1.0, 0.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0, 0.0],
+            vec![0.0, 0.0, 1.0, 0.0],
+        ];
+        let mut roots = eigenvalues(&a).unwrap();
+        roots.sort_by(|a, b| a.magnitude().total_cmp(&b.magnitude()));
+        assert!((roots[0] - Complex::new(-1.0, 0.0)).magnitude() < 1e-10);
+        assert!((roots[1] - Complex::new(-2.0, 0.0)).magnitude() < 1e-10);
+        assert!((roots[2].real + 1.0).abs() < 1e-10);
+        assert!((roots[2].imag.abs() - 2.0).abs() < 1e-10);
+        assert_eq!(roots[2].imag, -roots[3].imag);
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // Pole-zero analysis
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_rc_lowpass_pole() {
+        let mut netlist = Netlist::new("RC".to_string());
+        netlist.add_element(ac_source("V1", "in"));
+        netlist.add_element(passive('R', "R1", "in", "out", 1e3));
+        netlist.add_element(passive('C', "C1", "out", "0", 1e-6));
+
+        let pz = pole_zero(&netlist, "V1", "out").unwrap();
+        assert_eq!(pz.poles.len(), 1);
+        assert!(pz.zeros.is_empty());
+        assert!(close(pz.poles[0].real, -1e3, 1e-9));
+        assert!(pz.poles[0].imag.abs() < 1e-6);
+        // H(s) = (1/RC) / (s + 1/RC)
+        assert!(close(pz.gain, 1e3, 1e-9));
+        assert!(pz.is_stable());
+    }
+
+    #[test]
+    fn test_rlc_bandpass_matches_ac() {
+        // Series RLC, output across R: zero at the origin, complex pole pair
+        let (r, l, c) = (10.0, 1e-3, 1e-6);
+        let mut netlist = Netlist::new("RLC".to_string());
+        netlist.add_element(ac_source("V1", "in"));
+        netlist.add_element(passive('L', "L1", "in", "a", l));
+        netlist.add_element(passive('C', "C1", "a", "out", c));
+        netlist.add_element(passive('R', "R1", "out", "0", r));
+
+        let pz = pole_zero(&netlist, "V1", "out").unwrap();
+        assert_eq!(pz.poles.len(), 2);
+        assert_eq!(pz.zeros.len(), 1);
+        assert!(pz.zeros[0].magnitude() < 1e-6);
+
+        let w0 = 1.0 / (l * c).sqrt();
+        let alpha = r / (2.0 * l);
+        for p in &pz.poles {
+            assert!(close(p.magnitude(), w0, 1e-9));
+            assert!(close(p.real, -alpha, 1e-9));
+        }
+        assert!(close(pz.gain, r / l, 1e-9));
+
+        // The rational form reproduces the AC sweep
+        for freq in [100.0, 5e3, 2e5] {
+            let node = netlist.node_index("out").unwrap();
+            let v = ac_point(&netlist, freq).unwrap()[node - 1];
+            assert!((pz.evaluate(freq) - v).magnitude() < 1e-9 * v.magnitude().max(1e-3));
+        }
+    }
+
+    #[test]
+    fn test_capacitor_across_source() {
+        // C0 across V1 is an infinite (algebraic) mode, not a pole
+        let mut netlist = Netlist::new("bypass".to_string());
+        netlist.add_element(ac_source("V1", "in"));
+        netlist.add_element(passive('C', "C0", "in", "0", 10e-6));
+        netlist.add_element(passive('R', "R1", "in", "out", 1e3));
+        netlist.add_element(passive('C', "C1", "out", "0", 1e-9));
+
+        let pz = pole_zero(&netlist, "V1", "out").unwrap();
+        assert_eq!(pz.poles.len(), 1);
+        assert!(close(pz.poles[0].real, -1e6, 1e-9));
+        assert!(pz.zeros.is_empty());
+    }
+
+    #[test]
+    fn test_integrator_pole_at_origin() {
+        // Current into a capacitor with a parallel leak resistor removed:
+        // Z(s) = 1/(sC) has its pole at s = 0
+        let mut netlist = Netlist::new("integrator".to_string());
+        netlist.add_element(Element::CurrentSource {
+            name: "I1".to_string(),
+            node_p: "0".to_string(),
+            node_n: "x".to_string(),
+            value: SourceValue::DC(0.0),
+        });
+        netlist.add_element(passive('C', "C1", "x", "0", 1e-9));
+        netlist.add_element(passive('R', "R1", "x", "y", 1e3));
+        netlist.add_element(passive('C', "C2", "y", "0", 1e-9));
+
+        let pz = pole_zero(&netlist, "I1", "x").unwrap();
+        assert_eq!(pz.poles.len(), 2);
+        assert!(pz.poles[0].magnitude() < 1e-3);
+        // Second pole from C1 in series with C2 through R1
+        assert!(close(pz.poles[1].real, -2.0 / (1e3 * 1e-9), 1e-9));
+        assert_eq!(pz.zeros.len(), 1);
+        assert!(close(pz.zeros[0].real, -1.0 / (1e3 * 1e-9), 1e-9));
+        assert!(!pz.is_stable());
+    }
+
+    #[test]
+    fn test_unobservable_mode_cancels() {
+        // A second RC hanging off the source never reaches the output
+        let mut netlist = Netlist::new("cancel".to_string());
+        netlist.add_element(ac_source("V1", "in"));
+        netlist.add_element(passive('R', "R1", "in", "out", 1e3));
+        netlist.add_element(passive('C', "C1", "out", "0", 1e-6));
+        netlist.add_element(Element::VCCS {
+            name: "G1".to_string(),
+            node_out_p: "0".to_string(),
+            node_out_n: "side".to_string(),
+            node_ctrl_p: "out".to_string(),
+            node_ctrl_n: "0".to_string(),
+            transconductance: 1e-3,
+        });
+        netlist.add_element(passive('R', "R2", "side", "0", 1e3));
+        netlist.add_element(passive('C', "C2", "side", "0", 1e-7));
+
+        let pz = pole_zero(&netlist, "V1", "out").unwrap();
+        assert_eq!(pz.poles.len(), 1);
+        assert!(pz.zeros.is_empty());
+
+        let pz_all = pole_zero_with(
+            &netlist,
+            "V1",
+            "out",
+            &PoleZeroOptions {
+                cancel_tolerance: 0.0,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(pz_all.poles.len(), 2);
+        assert!(close(pz_all.poles[1].real, -1e4, 1e-9));
+    }
+
+    #[test]
+    fn test_buck_filter_matches_small_signal_model() {
+        use crate::power::control::BuckSmallSignal;
+
+        // Averaged buck power stage: Vin·d drives L into C + ESR || R
+        let buck = BuckSmallSignal::new(12.0, 5.0, 2.0, 10e-6, 100e-6, 0.02, 500e3);
+        let mut netlist = Netlist::new("buck".to_string());
+        netlist.add_element(ac_source("VD", "sw"));
+        netlist.add_element(passive('L', "L1", "sw", "out", buck.l));
+        netlist.add_element(passive('C', "C1", "out", "esr", buck.c));
+        netlist.add_element(passive('R', "RESR", "esr", "0", buck.esr));
+        netlist.add_element(passive('R', "RL", "out", "0", buck.r_load));
+
+        let pz = pole_zero(&netlist, "VD", "out").unwrap();
+        let model = buck.control_to_output();
+        assert_eq!(pz.poles.len(), model.poles.len());
+        assert_eq!(pz.zeros.len(), model.zeros.len());
+
+        // ESR zero is exact; the LC pair agrees with the approximated Q
+        assert!(close(pz.zeros[0].real, model.zeros[0].re, 1e-9));
+        let w0 = 1.0 / (buck.l * buck.c * (1.0 + buck.esr / buck.r_load)).sqrt();
+        assert!(close(pz.poles[0].magnitude(), w0, 1e-6));
+        let f_lc = buck.characteristic_frequencies().f_lc;
+        assert!(close(pz.pole_frequencies()[0], f_lc, 0.01));
+
+        // Unity at DC: the inductor shorts and the ESR branch is open
+        let tf = pz.transfer_function();
+        assert!(close(tf.evaluate(1e-3).magnitude(), 1.0, 1e-6));
+        let h = tf.evaluate(20e3);
+        assert!((h.magnitude() - pz.evaluate(20e3).magnitude()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,780 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: sparse.rs | DNA/src/physics/electromagnetics/lumped/sparse.rs
+//! PURPOSE: Sparse LU factorization with Markowitz ordering for MNA systems
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Sparse LU factorization with Markowitz ordering for MNA systems
+//!
+//! LAYER: DNA → PHYSICS → ELECTROMAGNETICS → LUMPED
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM: Markowitz sparse LU with symbolic reuse                          │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Analysis (first factorization):                                             │
+//! │   For each step pick the pivot a_ij minimising (r_i - 1)(c_j - 1)           │
+//! │   among entries with |a_ij| ≥ PIVOT_REL · max|a_*j| (threshold pivoting)    │
+//! │   r_i, c_j = nonzeros remaining in row i / column j                         │
+//! │                                                                             │
+//! │ Symbolic: permute rows/columns, compute fill-in, then compile the           │
+//! │   elimination into a flat list of (target, l, u) slot updates               │
+//! │                                                                             │
+//! │ Numeric refactor (per frequency / Newton iteration / timestep):             │
+//! │   load values slot to slot → divide column k by pivot → a_ij -= l_ik · u_kj │
+//! │   Pivot check fails or new nonzero appears → re-run the analysis            │
+//! │                                                                             │
+//! │ Matrices are stamped directly into value slots; an entry keeps its slot     │
+//! │ even when it sums to zero, so restamping keeps the analysed pattern         │
+//! │                                                                             │
+//! │ Solve: L y = P b (unit lower), U z = y, x = Q z                             │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Scalar             Field element trait (f64, Complex)                       │
+//! │ SparseMatrix       Slot-per-entry sparse matrix that MNA stamps write into  │
+//! │ SparseLu           Ordered, compiled LU factors                             │
+//! │ SparseSolver       Factorization cache reused across solves                 │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! DEPENDS ON:
+//!   • super::ac → Complex
+//!
+//! USED BY:
+//!   • physics/electromagnetics/lumped/matrix.rs → MNAMatrix storage and solves
+//!   • physics/electromagnetics/lumped/ac.rs → Per-frequency refactor
+//!   • physics/electromagnetics/lumped/dc.rs → Newton / transient solves
+//!   • physics/electromagnetics/lumped/noise.rs → Adjoint (transposed) solves
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// CODE BELOW - Optimized for ML development
+// ─────────────────────────────────────────────────────────────────────────────────
+
+use super::ac::Complex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Sub};
+use std::sync::Arc;
+
+/// Relative pivot threshold (SPICE `PIVREL`)
+pub const PIVOT_REL: f64 = 1e-3;
+
+/// Absolute pivot threshold (SPICE `PIVTOL`)
+pub const PIVOT_ABS: f64 = 1e-13;
+
+/// Field element the sparse solver operates on
+pub trait Scalar:
+    Copy + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+    fn zero() -> Self;
+
+    /// Magnitude used for pivot selection
+    fn norm(self) -> f64;
+}
+
+impl Scalar for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn norm(self) -> f64 {
+        self.abs()
+    }
+}
+
+impl Scalar for Complex {
+    fn zero() -> Self {
+        Complex::zero()
+    }
+
+    fn norm(self) -> f64 {
+        self.magnitude()
+    }
+}
+
+/// Positions of a matrix's value slots, in the order they were stamped
+#[derive(Clone, Debug, Default)]
+struct Pattern {
+    keys: Vec<(usize, usize)>,
+    index: HashMap<(usize, usize), usize>,
+}
+
+/// Sparse matrix with one value slot per stamped entry (duplicate stamps
+/// accumulate)
+///
+/// An entry keeps its slot once stamped, even if it sums to zero, so a
+/// circuit restamped with new values keeps the pattern its factorization
+/// was analysed for. Clones share the pattern until one gains an entry.
+#[derive(Clone, Debug)]
+pub struct SparseMatrix<T> {
+    size: usize,
+    pattern: Arc<Pattern>,
+    values: Vec<T>,
+}
+
+impl<T: Scalar> SparseMatrix<T> {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            pattern: Arc::default(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Add `value` to entry (row, col)
+    pub fn add(&mut self, row: usize, col: usize, value: T) {
+        if let Some(&slot) = self.pattern.index.get(&(row, col)) {
+            self.values[slot] = self.values[slot] + value;
+            return;
+        }
+        let pattern = Arc::make_mut(&mut self.pattern);
+        pattern.index.insert((row, col), pattern.keys.len());
+        pattern.keys.push((row, col));
+        self.values.push(value);
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        self.pattern
+            .index
+            .get(&(row, col))
+            .map_or_else(T::zero, |&slot| self.values[slot])
+    }
+
+    /// Zero every entry, keeping the pattern for restamping
+    pub fn clear(&mut self) {
+        self.values.fill(T::zero());
+    }
+
+    /// Overwrite the values with `other`'s; entries only this matrix has
+    /// are zeroed and keep their slots
+    pub fn assign(&mut self, other: &SparseMatrix<T>) {
+        self.clear();
+        for (i, j, v) in other.iter() {
+            self.add(i, j, v);
+        }
+    }
+
+    /// Iterate (row, col, value) in stamping order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        self.pattern
+            .keys
+            .iter()
+            .zip(&self.values)
+            .map(|(&(i, j), &v)| (i, j, v))
+    }
+
+    /// Transposed copy Aᵀ (adjoint systems, e.g. noise analysis)
+    pub fn transpose(&self) -> Self {
+        let keys: Vec<(usize, usize)> = self.pattern.keys.iter().map(|&(i, j)| (j, i)).collect();
+        let index = keys.iter().enumerate().map(|(s, &key)| (key, s)).collect();
+        Self {
+            size: self.size,
+            pattern: Arc::new(Pattern { keys, index }),
+            values: self.values.clone(),
+        }
+    }
+
+    /// Matrix-vector product A·x
+    pub fn multiply(&self, x: &[T]) -> Vec<T> {
+        let mut y = vec![T::zero(); self.size];
+        for (i, j, v) in self.iter() {
+            y[i] = y[i] + v * x[j];
+        }
+        y
+    }
+
+    /// Dense row-major copy (small systems and eigenvalue work)
+    pub fn to_dense(&self) -> Vec<Vec<T>> {
+        let mut rows = vec![vec![T::zero(); self.size]; self.size];
+        for (i, j, v) in self.iter() {
+            rows[i][j] = v;
+        }
+        rows
+    }
+
+    /// Same slots at the same positions as `other`
+    fn same_pattern(&self, other: &Arc<Pattern>) -> bool {
+        Arc::ptr_eq(&self.pattern, other) || self.pattern.keys == other.keys
+    }
+}
+
+/// LU factors in Markowitz order with a compiled elimination sequence
+///
+/// Row k of the factors is original row `row_perm[k]`, column k is original
+/// column `col_perm[k]`. Factors are stored row-wise (L strictly below the
+/// diagonal with unit diagonal implied, U on and above it).
+#[derive(Clone, Debug)]
+pub struct SparseLu<T> {
+    size: usize,
+    row_perm: Vec<usize>,
+    col_perm: Vec<usize>,
+    /// Inverse permutations: original row/column → factor row/column
+    inv_row: Vec<usize>,
+    inv_col: Vec<usize>,
+    /// Pattern the factors were analysed for, and the factor slot of each
+    /// of its value slots
+    pattern: Arc<Pattern>,
+    entry_slots: Vec<usize>,
+    values: Vec<T>,
+    row_start: Vec<usize>,
+    cols: Vec<usize>,
+    diag: Vec<usize>,
+    /// Per step: slots of column k below the pivot
+    lower_start: Vec<usize>,
+    lower: Vec<usize>,
+    /// Per step: (target, l, u) updates `target -= l * u`
+    ops_start: Vec<usize>,
+    ops: Vec<(usize, usize, usize)>,
+}
+
+impl<T: Scalar> SparseLu<T> {
+    /// Order, analyse and factor `matrix`
+    pub fn factor(matrix: &SparseMatrix<T>) -> Result<Self, String> {
+        let (row_perm, col_perm) = markowitz_order(matrix)?;
+        let mut lu = Self::compile(matrix, row_perm, col_perm);
+        lu.numeric(matrix, false)?;
+        Ok(lu)
+    }
+
+    /// Refactor with new values, reusing the pivot order and fill pattern
+    ///
+    /// Fails if `matrix` has a nonzero outside the analysed pattern or a pivot
+    /// no longer passes the threshold test; the caller should then `factor`.
+    pub fn refactor(&mut self, matrix: &SparseMatrix<T>) -> Result<(), String> {
+        if matrix.size() != self.size {
+            return Err("Matrix size changed".to_string());
+        }
+        self.numeric(matrix, true)
+    }
+
+    /// Solve A·x = b with the current factors
+    pub fn solve(&self, rhs: &[T]) -> Vec<T> {
+        let n = self.size;
+
+        // Forward substitution (L y = P b)
+        let mut y = vec![T::zero(); n];
+        for k in 0..n {
+            let mut sum = rhs[self.row_perm[k]];
+            for s in self.row_start[k]..self.diag[k] {
+                sum = sum - self.values[s] * y[self.cols[s]];
+            }
+            y[k] = sum;
+        }
+
+        // Back substitution (U z = y)
+        for k in (0..n).rev() {
+            let mut sum = y[k];
+            for s in self.diag[k] + 1..self.row_start[k + 1] {
+                sum = sum - self.values[s] * y[self.cols[s]];
+            }
+            y[k] = sum / self.values[self.diag[k]];
+        }
+
+        let mut x = vec![T::zero(); n];
+        for (k, &col) in self.col_perm.iter().enumerate() {
+            x[col] = y[k];
+        }
+        x
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Stored entries in L + U (original nonzeros plus fill-in)
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Multiply-subtract operations per numeric refactor
+    pub fn op_count(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Compute the fill pattern for the given ordering and flatten it
+    fn compile(matrix: &SparseMatrix<T>, row_perm: Vec<usize>, col_perm: Vec<usize>) -> Self {
+        let n = matrix.size();
+        let mut inv_row = vec![0; n];
+        let mut inv_col = vec![0; n];
+        for k in 0..n {
+            inv_row[row_perm[k]] = k;
+            inv_col[col_perm[k]] = k;
+        }
+
+        let mut pattern: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); n];
+        let mut col_rows: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); n];
+        for (i, j, _) in matrix.iter() {
+            pattern[inv_row[i]].insert(inv_col[j]);
+            col_rows[inv_col[j]].insert(inv_row[i]);
+        }
+        for k in 0..n {
+            pattern[k].insert(k);
+            col_rows[k].insert(k);
+        }
+
+        // Symbolic elimination: row i inherits row k's upper part
+        for k in 0..n {
+            let upper: Vec<usize> = pattern[k].range(k + 1..).copied().collect();
+            let below: Vec<usize> = col_rows[k].range(k + 1..).copied().collect();
+            for i in below {
+                for &j in &upper {
+                    if pattern[i].insert(j) {
+                        col_rows[j].insert(i);
+                    }
+                }
+            }
+        }
+
+        let mut row_start = Vec::with_capacity(n + 1);
+        let mut cols = Vec::new();
+        let mut diag = Vec::with_capacity(n);
+        for row in &pattern {
+            row_start.push(cols.len());
+            cols.extend(row.iter().copied());
+        }
+        row_start.push(cols.len());
+        for (k, row) in pattern.iter().enumerate() {
+            diag.push(row_start[k] + row.range(..k).count());
+        }
+
+        let slot = |i: usize, j: usize| -> usize {
+            let row = &cols[row_start[i]..row_start[i + 1]];
+            row_start[i] + row.binary_search(&j).expect("entry in fill pattern")
+        };
+
+        let mut lower_start = Vec::with_capacity(n + 1);
+        let mut lower = Vec::new();
+        let mut ops_start = Vec::with_capacity(n + 1);
+        let mut ops = Vec::new();
+        for k in 0..n {
+            lower_start.push(lower.len());
+            ops_start.push(ops.len());
+            let upper = &cols[diag[k] + 1..row_start[k + 1]];
+            for &i in col_rows[k].range(k + 1..) {
+                let l = slot(i, k);
+                lower.push(l);
+                for (offset, &j) in upper.iter().enumerate() {
+                    ops.push((slot(i, j), l, diag[k] + 1 + offset));
+                }
+            }
+        }
+        lower_start.push(lower.len());
+        ops_start.push(ops.len());
+
+        let entry_slots = matrix
+            .iter()
+            .map(|(i, j, _)| slot(inv_row[i], inv_col[j]))
+            .collect();
+
+        Self {
+            size: n,
+            row_perm,
+            col_perm,
+            inv_row,
+            inv_col,
+            pattern: matrix.pattern.clone(),
+            entry_slots,
+            values: vec![T::zero(); cols.len()],
+            row_start,
+            cols,
+            diag,
+            lower_start,
+            lower,
+            ops_start,
+            ops,
+        }
+    }
+
+    /// Factor slot holding original entry (row, col), if in the fill pattern
+    fn slot(&self, row: usize, col: usize) -> Option<usize> {
+        let (i, j) = (self.inv_row[row], self.inv_col[col]);
+        let start = self.row_start[i];
+        self.cols[start..self.row_start[i + 1]]
+            .binary_search(&j)
+            .ok()
+            .map(|offset| start + offset)
+    }
+
+    /// Load values and run the compiled elimination
+    fn numeric(&mut self, matrix: &SparseMatrix<T>, check_pivots: bool) -> Result<(), String> {
+        self.values.fill(T::zero());
+        if matrix.same_pattern(&self.pattern) {
+            // Adopt the caller's copy so the next check is a pointer compare
+            self.pattern = matrix.pattern.clone();
+            for (&s, &v) in self.entry_slots.iter().zip(&matrix.values) {
+                self.values[s] = v;
+            }
+        } else {
+            for (i, j, v) in matrix.iter() {
+                match self.slot(i, j) {
+                    Some(s) => self.values[s] = v,
+                    None if v.norm() == 0.0 => {}
+                    None => return Err(format!("Entry ({}, {}) outside analysed pattern", i, j)),
+                }
+            }
+        }
+
+        for k in 0..self.size {
+            let pivot = self.values[self.diag[k]];
+            let lower = &self.lower[self.lower_start[k]..self.lower_start[k + 1]];
+            if pivot.norm() <= PIVOT_ABS {
+                return Err(format!("Matrix is singular at row {}", self.row_perm[k]));
+            }
+            if check_pivots {
+                let col_max = lower
+                    .iter()
+                    .map(|&s| self.values[s].norm())
+                    .fold(pivot.norm(), f64::max);
+                if pivot.norm() < PIVOT_REL * col_max {
+                    return Err(format!("Pivot {} below threshold", k));
+                }
+            }
+            for &s in lower {
+                self.values[s] = self.values[s] / pivot;
+            }
+            for &(target, l, u) in &self.ops[self.ops_start[k]..self.ops_start[k + 1]] {
+                self.values[target] = self.values[target] - self.values[l] * self.values[u];
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Choose row and column pivot order by Markowitz cost with threshold pivoting
+fn markowitz_order<T: Scalar>(
+    matrix: &SparseMatrix<T>,
+) -> Result<(Vec<usize>, Vec<usize>), String> {
+    let n = matrix.size();
+    let mut rows: Vec<BTreeMap<usize, T>> = vec![BTreeMap::new(); n];
+    let mut cols: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); n];
+    // Stamped zeros count towards fill like any entry; the magnitude
+    // test below keeps them from being chosen as pivots
+    for (i, j, v) in matrix.iter() {
+        rows[i].insert(j, v);
+        cols[j].insert(i);
+    }
+
+    let mut col_done = vec![false; n];
+    let mut row_perm = Vec::with_capacity(n);
+    let mut col_perm = Vec::with_capacity(n);
+
+    for k in 0..n {
+        // (cost, magnitude, row, col)
+        let mut best: Option<(usize, f64, usize, usize)> = None;
+        'search: for j in (0..n).filter(|&j| !col_done[j]) {
+            let col_max = cols[j]
+                .iter()
+                .map(|&i| rows[i][&j].norm())
+                .fold(0.0, f64::max);
+            if col_max <= PIVOT_ABS {
+                continue;
+            }
+            let c = cols[j].len() - 1;
+            for &i in &cols[j] {
+                let magnitude = rows[i][&j].norm();
+                if magnitude < PIVOT_REL * col_max || magnitude <= PIVOT_ABS {
+                    continue;
+                }
+                let cost = (rows[i].len() - 1) * c;
+                let better = match best {
+                    None => true,
+                    Some((best_cost, best_mag, _, _)) => {
+                        cost < best_cost || (cost == best_cost && magnitude > best_mag)
+                    }
+                };
+                if better {
+                    best = Some((cost, magnitude, i, j));
+                }
+            }
+            if matches!(best, Some((0, _, _, _))) {
+                break 'search;
+            }
+        }
+
+        let (_, _, p, q) = best.ok_or_else(|| format!("Matrix is singular at row {}", k))?;
+
+        let pivot = rows[p][&q];
+        let pivot_row: Vec<(usize, T)> = rows[p]
+            .iter()
+            .filter(|(&j, _)| j != q)
+            .map(|(&j, &v)| (j, v))
+            .collect();
+        let targets: Vec<usize> = cols[q].iter().copied().filter(|&i| i != p).collect();
+        for i in targets {
+            let l = rows[i].remove(&q).expect("column entry present") / pivot;
+            for &(j, u) in &pivot_row {
+                let entry = rows[i].entry(j).or_insert_with(T::zero);
+                *entry = *entry - l * u;
+                cols[j].insert(i);
+            }
+        }
+
+        for &j in rows[p].keys() {
+            cols[j].remove(&p);
+        }
+        rows[p].clear();
+        cols[q].clear();
+        col_done[q] = true;
+        row_perm.push(p);
+        col_perm.push(q);
+    }
+
+    Ok((row_perm, col_perm))
+}
+
+/// Factorization cache: analyses once, then refactors numerically
+///
+/// Falls back to a fresh Markowitz analysis when the pattern grows or a
+/// reused pivot becomes too small.
+#[derive(Clone, Debug)]
+pub struct SparseSolver<T> {
+    lu: Option<SparseLu<T>>,
+    /// Full (ordering + symbolic) factorizations performed
+    pub factorizations: usize,
+    /// Numeric-only refactorizations performed
+    pub refactorizations: usize,
+}
+
+impl<T: Scalar> Default for SparseSolver<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Scalar> SparseSolver<T> {
+    pub fn new() -> Self {
+        Self {
+            lu: None,
+            factorizations: 0,
+            refactorizations: 0,
+        }
+    }
+
+    /// Solve `matrix · x = rhs`, reusing the previous ordering when possible
+    pub fn solve(&mut self, matrix: &SparseMatrix<T>, rhs: &[T]) -> Result<Vec<T>, String> {
+        if let Some(lu) = &mut self.lu {
+            if lu.refactor(matrix).is_ok() {
+                self.refactorizations += 1;
+                return Ok(lu.solve(rhs));
+            }
+        }
+
+        self.lu = None;
+        let lu = SparseLu::factor(matrix)?;
+        self.factorizations += 1;
+        let x = lu.solve(rhs);
+        self.lu = Some(lu);
+        Ok(x)
+    }
+
+    /// Current factors, if any
+    pub fn factors(&self) -> Option<&SparseLu<T>> {
+        self.lu.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // HELPERS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Deterministic pseudo-random values in [-1, 1)
+    fn lcg(seed: &mut u64) -> f64 {
+        *seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((*seed >> 11) as f64 / (1u64 << 53) as f64) * 2.0 - 1.0
+    }
+
+    fn random_sparse(n: usize, per_row: usize, seed: u64) -> SparseMatrix<f64> {
+        let mut seed = seed;
+        let mut m = SparseMatrix::new(n);
+        for i in 0..n {
+            m.add(i, i, 4.0 + lcg(&mut seed));
+            for _ in 0..per_row {
+                let j = ((lcg(&mut seed) + 1.0) * 0.5 * n as f64) as usize % n;
+                m.add(i, j, lcg(&mut seed));
+            }
+        }
+        m
+    }
+
+    fn residual(m: &SparseMatrix<f64>, x: &[f64], b: &[f64]) -> f64 {
+        m.multiply(x)
+            .iter()
+            .zip(b)
+            .map(|(ax, b)| (ax - b).abs())
+            .fold(0.0, f64::max)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // FACTORIZATION
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_solve_random_system() {
+        let m = random_sparse(60, 3, 7);
+        let b: Vec<f64> = (0..60).map(|i| (i as f64).sin()).collect();
+        let lu = SparseLu::factor(&m).unwrap();
+        let x = lu.solve(&b);
+        assert!(residual(&m, &x, &b) < 1e-10);
+    }
+
+    #[test]
+    fn test_zero_diagonal_needs_pivoting() {
+        // MNA voltage-source block: [[g, 1], [1, 0]]
+        let mut m = SparseMatrix::new(2);
+        m.add(0, 0, 1e-3);
+        m.add(0, 1, 1.0);
+        m.add(1, 0, 1.0);
+        let x = SparseLu::factor(&m).unwrap().solve(&[0.0, 5.0]);
+        assert!((x[0] - 5.0).abs() < 1e-12);
+        assert!((x[1] + 5e-3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_markowitz_avoids_fill_in_arrowhead() {
+        // Dense first row/column: natural order fills the whole matrix
+        let n = 20;
+        let mut m = SparseMatrix::new(n);
+        for i in 0..n {
+            m.add(i, i, 10.0);
+            if i > 0 {
+                m.add(0, i, 1.0);
+                m.add(i, 0, 1.0);
+            }
+        }
+        let lu = SparseLu::factor(&m).unwrap();
+        assert_eq!(lu.nnz(), m.nnz());
+    }
+
+    #[test]
+    fn test_singular_matrix() {
+        let mut m = SparseMatrix::new(2);
+        m.add(0, 0, 1.0);
+        m.add(0, 1, 1.0);
+        m.add(1, 0, 1.0);
+        m.add(1, 1, 1.0);
+        assert!(SparseLu::factor(&m).is_err());
+    }
+
+    #[test]
+    fn test_complex_solve() {
+        let mut m = SparseMatrix::new(2);
+        m.add(0, 0, Complex::new(1.0, 1.0));
+        m.add(0, 1, Complex::new(0.0, -1.0));
+        m.add(1, 0, Complex::new(0.0, -1.0));
+        m.add(1, 1, Complex::new(2.0, 0.0));
+        let b = [Complex::new(1.0, 0.0), Complex::zero()];
+        let x = SparseLu::factor(&m).unwrap().solve(&b);
+        let ax = m.multiply(&x);
+        assert!((ax[0] - b[0]).magnitude() < 1e-12);
+        assert!(ax[1].magnitude() < 1e-12);
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // REUSE
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_refactor_reuses_ordering() {
+        let mut solver = SparseSolver::new();
+        let b: Vec<f64> = (0..40).map(|i| i as f64).collect();
+        for scale in [1.0, 2.0, 0.5, 3.0] {
+            let mut m = random_sparse(40, 2, 11);
+            for i in 0..40 {
+                m.add(i, i, scale);
+            }
+            let x = solver.solve(&m, &b).unwrap();
+            assert!(residual(&m, &x, &b) < 1e-9);
+        }
+        assert_eq!(solver.factorizations, 1);
+        assert_eq!(solver.refactorizations, 3);
+    }
+
+    #[test]
+    fn test_pattern_change_triggers_analysis() {
+        let mut solver = SparseSolver::new();
+        let mut m = SparseMatrix::new(3);
+        for i in 0..3 {
+            m.add(i, i, 2.0);
+        }
+        solver.solve(&m, &[1.0, 1.0, 1.0]).unwrap();
+
+        m.add(0, 2, 1.0);
+        let x = solver.solve(&m, &[1.0, 1.0, 1.0]).unwrap();
+        assert!((x[0] - 0.25).abs() < 1e-12);
+        assert_eq!(solver.factorizations, 2);
+    }
+
+    #[test]
+    fn test_stamped_zero_keeps_pattern() {
+        // (0, 1) is stamped as an exact zero, then becomes nonzero
+        let stamp = |g: f64| {
+            let mut m = SparseMatrix::new(2);
+            m.add(0, 0, 2.0);
+            m.add(0, 1, g);
+            m.add(1, 0, g);
+            m.add(1, 1, 2.0);
+            m
+        };
+        let mut solver = SparseSolver::new();
+        solver.solve(&stamp(0.0), &[1.0, 1.0]).unwrap();
+        assert_eq!(stamp(0.0).nnz(), 4);
+
+        let m = stamp(1.0);
+        let x = solver.solve(&m, &[1.0, 1.0]).unwrap();
+        assert!(residual(&m, &x, &[1.0, 1.0]) < 1e-12);
+        assert_eq!(solver.factorizations, 1);
+        assert_eq!(solver.refactorizations, 1);
+    }
+
+    #[test]
+    fn test_clear_and_restamp_shares_pattern() {
+        let mut m = random_sparse(30, 2, 3);
+        let before = m.nnz();
+        let copy = m.clone();
+        m.clear();
+        m.assign(&copy);
+        assert_eq!(m.nnz(), before);
+        assert!(m.same_pattern(&copy.pattern));
+        assert!(Arc::ptr_eq(&m.pattern, &copy.pattern));
+    }
+
+    #[test]
+    fn test_small_pivot_triggers_reordering() {
+        let dense = |a: f64| {
+            let mut m = SparseMatrix::new(2);
+            m.add(0, 0, a);
+            m.add(0, 1, 1.0);
+            m.add(1, 0, 1.0);
+            m.add(1, 1, 1.0);
+            m
+        };
+        let mut solver = SparseSolver::new();
+        solver.solve(&dense(4.0), &[1.0, 2.0]).unwrap();
+
+        // Old pivot (0,0) is now tiny relative to its column
+        let m = dense(1e-9);
+        let x = solver.solve(&m, &[1.0, 2.0]).unwrap();
+        assert!(residual(&m, &x, &[1.0, 2.0]) < 1e-12);
+        assert_eq!(solver.factorizations, 2);
+    }
+}
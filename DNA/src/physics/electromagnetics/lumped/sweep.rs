@@ -0,0 +1,702 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: sweep.rs | DNA/src/physics/electromagnetics/lumped/sweep.rs
+//! PURPOSE: DC source sweeps, parametric sweeps and tolerance (Monte Carlo) analysis
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: DC source sweeps, parametric sweeps and tolerance (Monte Carlo) analysis
+//!
+//! LAYER: DNA → PHYSICS → ELECTROMAGNETICS → LUMPED
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM: Sweeps and tolerance analysis                                    │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ .dc sweep: step a V/I source, each Newton solve starts from the previous    │
+//! │   point (continuation), falling back to a cold operating point              │
+//! │                                                                             │
+//! │ Parametric: set an element value, run any analysis closure per value        │
+//! │                                                                             │
+//! │ Monte Carlo: value = nominal · (1 + δ), δ per element per run               │
+//! │   Uniform:  δ ~ U(-tol, +tol)                                               │
+//! │   Gaussian: δ ~ N(0, tol/3), clipped to ±tol (tolerance = 3σ)               │
+//! │                                                                             │
+//! │ Worst case: perturb each element +tol alone to get the sign of ∂m/∂x,       │
+//! │   then evaluate the all-high and all-low corners per measurement            │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ DcSweepResult      Operating point at each swept source value               │
+//! │ ParametricResult   Analysis output for each element value                   │
+//! │ Measurement        Node voltage (DC) or AC magnitude/phase at a frequency   │
+//! │ Tolerance          Element name, tolerance % and distribution               │
+//! │ MonteCarloResult   Measurement samples, statistics and histograms           │
+//! │ WorstCaseResult    Nominal, minimum and maximum of a measurement            │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! DEPENDS ON:
+//!   • super::dc → Operating point, warm-started Newton
+//!   • super::ac → Single-frequency AC solve
+//!   • math::random → Seeded uniform / Gaussian samples
+//!   • statistics → SampleStatistics, Histogram
+//!   • power::types → SelectedComponent tolerances
+//!
+//! USED BY:
+//!   • CORE/SPICE_ENGINE → .dc, .step and tolerance analysis
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// CODE BELOW - Optimized for ML development
+// ─────────────────────────────────────────────────────────────────────────────────
+
+use super::ac::{ac_point, Complex};
+use super::dc::{Circuit, ConvergenceAid, DcOperatingPoint, DcOptions, GMIN};
+use super::netlist::{Element, Netlist};
+use crate::math::random::{sample_gaussian, sample_uniform};
+use crate::power::types::SelectedComponent;
+use crate::statistics::{Histogram, SampleStatistics};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DC SWEEP
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Result of a `.dc` source sweep
+#[derive(Clone, Debug)]
+pub struct DcSweepResult {
+    pub source: String,
+    pub values: Vec<f64>,
+    pub points: Vec<DcOperatingPoint>,
+}
+
+impl DcSweepResult {
+    /// Voltage of `node` at every sweep point
+    pub fn voltages(&self, node: &str) -> Option<Vec<f64>> {
+        self.points.iter().map(|op| op.voltage(node)).collect()
+    }
+}
+
+/// Values from `start` to `stop` (inclusive) in increments of `step`
+pub fn sweep_values(start: f64, stop: f64, step: f64) -> Result<Vec<f64>, String> {
+    if step == 0.0 || !step.is_finite() || (stop - start) * step < 0.0 {
+        return Err(format!(
+            "Invalid sweep: {} to {} in steps of {}",
+            start, stop, step
+        ));
+    }
+    let count = ((stop - start) / step + 1e-9).floor() as usize + 1;
+    Ok((0..count).map(|i| start + step * i as f64).collect())
+}
+
+/// Sweep the DC value of an independent source (SPICE `.dc`)
+pub fn dc_sweep(
+    netlist: &Netlist,
+    source: &str,
+    start: f64,
+    stop: f64,
+    step: f64,
+) -> Result<DcSweepResult, String> {
+    dc_sweep_with(netlist, source, start, stop, step, &DcOptions::default())
+}
+
+/// `.dc` sweep with explicit Newton options
+pub fn dc_sweep_with(
+    netlist: &Netlist,
+    source: &str,
+    start: f64,
+    stop: f64,
+    step: f64,
+    options: &DcOptions,
+) -> Result<DcSweepResult, String> {
+    match netlist.element(source) {
+        Some(Element::VoltageSource { .. } | Element::CurrentSource { .. }) => {}
+        Some(_) => return Err(format!("{}: .dc sweeps independent sources only", source)),
+        None => return Err(format!("Unknown element '{}'", source)),
+    }
+
+    let values = sweep_values(start, stop, step)?;
+    let mut swept = netlist.clone();
+    let mut points = Vec::with_capacity(values.len());
+    let mut previous: Option<Vec<f64>> = None;
+
+    for &value in &values {
+        swept.set_value(source, value)?;
+        let circuit = Circuit::new(&swept)?;
+
+        // Continuation from the previous point, cold start as fallback
+        let warm = previous
+            .take()
+            .and_then(|x| {
+                circuit
+                    .newton(&circuit.dc_matrix(GMIN, 1.0), x, options)
+                    .ok()
+            })
+            .map(|(x, iterations)| (x, iterations, ConvergenceAid::None));
+        let (x, iterations, aid) = match warm {
+            Some(solution) => solution,
+            None => circuit
+                .operating_point(options)
+                .map_err(|e| format!("{} = {}: {}", source, value, e))?,
+        };
+
+        points.push(DcOperatingPoint {
+            solution: x[..circuit.num_nodes + circuit.num_vsources].to_vec(),
+            node_names: circuit.node_names.clone(),
+            iterations,
+            aid,
+        });
+        previous = Some(x);
+    }
+
+    Ok(DcSweepResult {
+        source: source.to_string(),
+        values,
+        points,
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PARAMETRIC SWEEP
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Result of a parametric sweep (SPICE `.step`)
+#[derive(Clone, Debug)]
+pub struct ParametricResult<T> {
+    pub element: String,
+    pub values: Vec<f64>,
+    pub results: Vec<T>,
+}
+
+/// Run `analysis` once for each value of `element`
+///
+/// `analysis` can be any of the lumped analyses, e.g.
+/// `|n| ac_analysis(n, 10.0, 1e6, 20)` or `dc_operating_point`.
+pub fn parametric_sweep<T, F>(
+    netlist: &Netlist,
+    element: &str,
+    values: &[f64],
+    mut analysis: F,
+) -> Result<ParametricResult<T>, String>
+where
+    F: FnMut(&Netlist) -> Result<T, String>,
+{
+    let mut stepped = netlist.clone();
+    let mut results = Vec::with_capacity(values.len());
+    for &value in values {
+        stepped.set_value(element, value)?;
+        results.push(analysis(&stepped).map_err(|e| format!("{} = {}: {}", element, value, e))?);
+    }
+    Ok(ParametricResult {
+        element: element.to_string(),
+        values: values.to_vec(),
+        results,
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MEASUREMENTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Scalar circuit quantity tracked by tolerance analysis
+#[derive(Clone, Debug, PartialEq)]
+pub enum Measurement {
+    /// DC operating-point voltage of a node
+    DcVoltage(String),
+    /// AC magnitude of a node voltage (dB) at a frequency (Hz)
+    AcMagnitudeDb { node: String, frequency: f64 },
+    /// AC phase of a node voltage (degrees) at a frequency (Hz)
+    AcPhaseDeg { node: String, frequency: f64 },
+}
+
+impl Measurement {
+    /// Short label, e.g. "V(out)" or "|V(out)| @ 1000 Hz"
+    pub fn label(&self) -> String {
+        match self {
+            Measurement::DcVoltage(node) => format!("V({})", node),
+            Measurement::AcMagnitudeDb { node, frequency } => {
+                format!("|V({})| @ {} Hz", node, frequency)
+            }
+            Measurement::AcPhaseDeg { node, frequency } => {
+                format!("∠V({}) @ {} Hz", node, frequency)
+            }
+        }
+    }
+}
+
+/// Evaluate every measurement on `netlist`, solving each analysis once
+fn measure(netlist: &Netlist, measurements: &[Measurement]) -> Result<Vec<f64>, String> {
+    let mut op: Option<DcOperatingPoint> = None;
+    let mut ac: Vec<(f64, Vec<Complex>)> = Vec::new();
+
+    let mut ac_voltage = |node: &str, frequency: f64| -> Result<Complex, String> {
+        let index = netlist
+            .node_index(node)
+            .ok_or_else(|| format!("Unknown node '{}'", node))?;
+        if index == 0 {
+            return Ok(Complex::zero());
+        }
+        let solution = match ac.iter().position(|(f, _)| *f == frequency) {
+            Some(i) => &ac[i].1,
+            None => {
+                ac.push((frequency, ac_point(netlist, frequency)?));
+                &ac[ac.len() - 1].1
+            }
+        };
+        Ok(solution[index - 1])
+    };
+
+    let mut values = Vec::with_capacity(measurements.len());
+    for measurement in measurements {
+        let value = match measurement {
+            Measurement::DcVoltage(node) => {
+                if op.is_none() {
+                    op = Some(super::dc::dc_operating_point(netlist)?);
+                }
+                op.as_ref()
+                    .and_then(|op| op.voltage(node))
+                    .ok_or_else(|| format!("Unknown node '{}'", node))?
+            }
+            Measurement::AcMagnitudeDb { node, frequency } => {
+                20.0 * ac_voltage(node, *frequency)?.magnitude().log10()
+            }
+            Measurement::AcPhaseDeg { node, frequency } => {
+                ac_voltage(node, *frequency)?.phase_deg()
+            }
+        };
+        values.push(value);
+    }
+    Ok(values)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TOLERANCES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// How an element value is distributed within its tolerance band
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToleranceDistribution {
+    /// Flat over ±tolerance
+    Uniform,
+    /// Normal with tolerance = 3σ, clipped to ±tolerance
+    Gaussian,
+}
+
+/// Tolerance applied to one netlist element
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tolerance {
+    pub element: String,
+    pub tolerance_pct: f64,
+    pub distribution: ToleranceDistribution,
+}
+
+impl Tolerance {
+    pub fn uniform(element: &str, tolerance_pct: f64) -> Self {
+        Self {
+            element: element.to_string(),
+            tolerance_pct,
+            distribution: ToleranceDistribution::Uniform,
+        }
+    }
+
+    pub fn gaussian(element: &str, tolerance_pct: f64) -> Self {
+        Self {
+            element: element.to_string(),
+            tolerance_pct,
+            distribution: ToleranceDistribution::Gaussian,
+        }
+    }
+
+    /// Gaussian tolerance for a designed component (designator = element name)
+    pub fn from_component(component: &SelectedComponent) -> Self {
+        Self::gaussian(&component.designator, component.tolerance_pct)
+    }
+
+    /// Random relative deviation δ
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        let tol = self.tolerance_pct / 100.0;
+        match self.distribution {
+            ToleranceDistribution::Uniform => sample_uniform(rng, -tol, tol),
+            ToleranceDistribution::Gaussian => {
+                sample_gaussian(rng, 0.0, tol / 3.0).clamp(-tol, tol)
+            }
+        }
+    }
+}
+
+/// Nominal value of every toleranced element
+fn nominal_values(netlist: &Netlist, tolerances: &[Tolerance]) -> Result<Vec<f64>, String> {
+    tolerances
+        .iter()
+        .map(|t| {
+            netlist
+                .element(&t.element)
+                .ok_or_else(|| format!("Unknown element '{}'", t.element))?
+                .value()
+                .ok_or_else(|| format!("{}: element has no adjustable value", t.element))
+        })
+        .collect()
+}
+
+/// Copy of `netlist` with element i scaled by (1 + deviations[i])
+fn perturbed(
+    netlist: &Netlist,
+    tolerances: &[Tolerance],
+    nominal: &[f64],
+    deviations: &[f64],
+) -> Result<Netlist, String> {
+    let mut netlist = netlist.clone();
+    for ((t, value), delta) in tolerances.iter().zip(nominal).zip(deviations) {
+        netlist.set_value(&t.element, value * (1.0 + delta))?;
+    }
+    Ok(netlist)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MONTE CARLO
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy, Debug)]
+pub struct MonteCarloOptions {
+    pub runs: usize,
+    /// RNG seed; the same seed reproduces the same runs
+    pub seed: u64,
+}
+
+impl Default for MonteCarloOptions {
+    fn default() -> Self {
+        Self { runs: 200, seed: 1 }
+    }
+}
+
+/// Monte Carlo samples for each measurement
+#[derive(Clone, Debug)]
+pub struct MonteCarloResult {
+    pub measurements: Vec<Measurement>,
+    /// Measurement values with every element at nominal
+    pub nominal: Vec<f64>,
+    /// samples[measurement][run]
+    pub samples: Vec<Vec<f64>>,
+    /// Runs whose analysis failed (excluded from `samples`)
+    pub failed_runs: usize,
+}
+
+impl MonteCarloResult {
+    pub fn statistics(&self, measurement: usize) -> Option<SampleStatistics> {
+        SampleStatistics::from_samples(&self.samples[measurement])
+    }
+
+    pub fn histogram(&self, measurement: usize, bins: usize) -> Histogram {
+        Histogram::from_samples(&self.samples[measurement], bins)
+    }
+
+    /// Fraction of successful runs with the measurement inside [min, max]
+    pub fn yield_within(&self, measurement: usize, min: f64, max: f64) -> f64 {
+        let samples = &self.samples[measurement];
+        if samples.is_empty() {
+            return 0.0;
+        }
+        let passed = samples.iter().filter(|&&v| v >= min && v <= max).count();
+        passed as f64 / samples.len() as f64
+    }
+}
+
+/// Perturb toleranced elements at random and collect the measurements
+pub fn monte_carlo(
+    netlist: &Netlist,
+    tolerances: &[Tolerance],
+    measurements: &[Measurement],
+    options: &MonteCarloOptions,
+) -> Result<MonteCarloResult, String> {
+    let nominal_values = nominal_values(netlist, tolerances)?;
+    let nominal = measure(netlist, measurements)?;
+
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let mut samples = vec![Vec::with_capacity(options.runs); measurements.len()];
+    let mut failed_runs = 0;
+    for _ in 0..options.runs {
+        let deviations: Vec<f64> = tolerances.iter().map(|t| t.sample(&mut rng)).collect();
+        let run = perturbed(netlist, tolerances, &nominal_values, &deviations)?;
+        match measure(&run, measurements) {
+            Ok(values) => {
+                for (column, value) in samples.iter_mut().zip(values) {
+                    column.push(value);
+                }
+            }
+            Err(_) => failed_runs += 1,
+        }
+    }
+
+    Ok(MonteCarloResult {
+        measurements: measurements.to_vec(),
+        nominal,
+        samples,
+        failed_runs,
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// WORST CASE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Extreme values of one measurement over the tolerance corners
+#[derive(Clone, Debug)]
+pub struct WorstCaseResult {
+    pub measurement: Measurement,
+    pub nominal: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Sensitivity-directed worst-case analysis
+///
+/// Costs one run per element plus two corners per measurement, instead of
+/// the 2^n corners of an exhaustive search. Assumes each measurement is
+/// monotonic in every element over its tolerance band.
+pub fn worst_case(
+    netlist: &Netlist,
+    tolerances: &[Tolerance],
+    measurements: &[Measurement],
+) -> Result<Vec<WorstCaseResult>, String> {
+    let nominal_values = nominal_values(netlist, tolerances)?;
+    let nominal = measure(netlist, measurements)?;
+    let tol: Vec<f64> = tolerances.iter().map(|t| t.tolerance_pct / 100.0).collect();
+
+    // One element high at a time → sensitivity sign per (element, measurement)
+    let mut high = Vec::with_capacity(tolerances.len());
+    for i in 0..tolerances.len() {
+        let mut deviations = vec![0.0; tolerances.len()];
+        deviations[i] = tol[i];
+        let run = perturbed(netlist, tolerances, &nominal_values, &deviations)?;
+        high.push(measure(&run, measurements)?);
+    }
+
+    let mut results = Vec::with_capacity(measurements.len());
+    for (m, measurement) in measurements.iter().enumerate() {
+        let toward_max: Vec<f64> = tol
+            .iter()
+            .zip(&high)
+            .map(|(t, h)| if h[m] >= nominal[m] { *t } else { -*t })
+            .collect();
+        let toward_min: Vec<f64> = toward_max.iter().map(|d| -d).collect();
+
+        let mut min = nominal[m];
+        let mut max = nominal[m];
+        for deviations in [&toward_max, &toward_min] {
+            let run = perturbed(netlist, tolerances, &nominal_values, deviations)?;
+            let value = measure(&run, std::slice::from_ref(measurement))?[0];
+            min = min.min(value);
+            max = max.max(value);
+        }
+        for h in &high {
+            min = min.min(h[m]);
+            max = max.max(h[m]);
+        }
+
+        results.push(WorstCaseResult {
+            measurement: measurement.clone(),
+            nominal: nominal[m],
+            min,
+            max,
+        });
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::devices::DiodeModel;
+    use super::super::netlist::SourceValue;
+    use super::*;
+
+    fn divider() -> Netlist {
+        let mut netlist = Netlist::new("Divider".to_string());
+        netlist.add_element(Element::VoltageSource {
+            name: "V1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "0".to_string(),
+            value: SourceValue::DC(10.0),
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "out".to_string(),
+            value: 1e3,
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R2".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: 1e3,
+        });
+        netlist
+    }
+
+    fn rc_lowpass() -> Netlist {
+        let mut netlist = Netlist::new("RC".to_string());
+        netlist.add_element(Element::VoltageSource {
+            name: "V1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "0".to_string(),
+            value: SourceValue::AC {
+                magnitude: 1.0,
+                phase: 0.0,
+            },
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "out".to_string(),
+            value: 1e3,
+        });
+        netlist.add_element(Element::Capacitor {
+            name: "C1".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: 1e-6,
+        });
+        netlist
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // SWEEPS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_sweep_values() {
+        assert_eq!(sweep_values(0.0, 1.0, 0.25).unwrap().len(), 5);
+        assert_eq!(sweep_values(1.0, 0.0, -0.5).unwrap(), vec![1.0, 0.5, 0.0]);
+        assert!(sweep_values(0.0, 1.0, -0.1).is_err());
+        assert!(sweep_values(0.0, 1.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_dc_sweep_linear() {
+        let result = dc_sweep(&divider(), "V1", 0.0, 10.0, 1.0).unwrap();
+        let out = result.voltages("out").unwrap();
+        assert_eq!(out.len(), 11);
+        for (v, vout) in result.values.iter().zip(&out) {
+            assert!((vout - v / 2.0).abs() < 1e-6);
+        }
+        assert!(dc_sweep(&divider(), "R1", 0.0, 1.0, 0.5).is_err());
+    }
+
+    #[test]
+    fn test_dc_sweep_diode_warm_start() {
+        let mut netlist = divider();
+        netlist.add_element(Element::Diode {
+            name: "D1".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            model: DiodeModel::default(),
+        });
+        let result = dc_sweep(&netlist, "V1", 0.0, 10.0, 0.5).unwrap();
+        let out = result.voltages("out").unwrap();
+        // Follows V/2 until the diode clamps near 0.6-0.7 V
+        assert!((out[1] - 0.25).abs() < 1e-3);
+        assert!(out.windows(2).all(|w| w[1] >= w[0]));
+        assert!(out[20] > 0.55 && out[20] < 0.75, "Vout = {}", out[20]);
+        // Continuation keeps later points cheap
+        assert!(result.points[20].iterations < result.points[10].iterations + 5);
+    }
+
+    #[test]
+    fn test_parametric_sweep() {
+        let result = parametric_sweep(&divider(), "R2", &[1e3, 3e3, 9e3], |n| {
+            super::super::dc::dc_operating_point(n).map(|op| op.voltage("out").unwrap())
+        })
+        .unwrap();
+        let expected = [5.0, 7.5, 9.0];
+        for (v, e) in result.results.iter().zip(expected) {
+            assert!((v - e).abs() < 1e-6);
+        }
+        assert!(parametric_sweep(&divider(), "R7", &[1.0], |_| Ok(())).is_err());
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // TOLERANCE ANALYSIS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_monte_carlo_divider() {
+        let tolerances = [Tolerance::uniform("R1", 5.0), Tolerance::uniform("R2", 5.0)];
+        let measurements = [Measurement::DcVoltage("out".to_string())];
+        let options = MonteCarloOptions { runs: 300, seed: 7 };
+        let result = monte_carlo(&divider(), &tolerances, &measurements, &options).unwrap();
+
+        assert_eq!(result.failed_runs, 0);
+        assert!((result.nominal[0] - 5.0).abs() < 1e-6);
+        let stats = result.statistics(0).unwrap();
+        assert_eq!(stats.count, 300);
+        assert!((stats.mean - 5.0).abs() < 0.02);
+        // ±5 % on both resistors bounds the output to 5 V ± 5 %
+        assert!(stats.min >= 4.75 && stats.max <= 5.25);
+        assert!(stats.std_dev > 0.05 && stats.std_dev < 0.15);
+        assert_eq!(result.histogram(0, 10).counts.iter().sum::<usize>(), 300);
+        assert_eq!(result.yield_within(0, 4.0, 6.0), 1.0);
+
+        // Same seed, same samples
+        let again = monte_carlo(&divider(), &tolerances, &measurements, &options).unwrap();
+        assert_eq!(result.samples, again.samples);
+    }
+
+    #[test]
+    fn test_monte_carlo_ac_metric() {
+        let tolerances = [
+            Tolerance::gaussian("R1", 10.0),
+            Tolerance::gaussian("C1", 20.0),
+        ];
+        let fc = 1.0 / (2.0 * std::f64::consts::PI * 1e-3);
+        let measurements = [
+            Measurement::AcMagnitudeDb {
+                node: "out".to_string(),
+                frequency: fc,
+            },
+            Measurement::AcPhaseDeg {
+                node: "out".to_string(),
+                frequency: fc,
+            },
+        ];
+        let options = MonteCarloOptions { runs: 100, seed: 3 };
+        let result = monte_carlo(&rc_lowpass(), &tolerances, &measurements, &options).unwrap();
+        assert!((result.nominal[0] + 3.0103).abs() < 1e-3);
+        assert!((result.nominal[1] + 45.0).abs() < 1e-6);
+        let stats = result.statistics(1).unwrap();
+        assert!(stats.std_dev > 0.5 && stats.std_dev < 10.0);
+    }
+
+    #[test]
+    fn test_worst_case_bounds_monte_carlo() {
+        let tolerances = [Tolerance::uniform("R1", 5.0), Tolerance::uniform("R2", 5.0)];
+        let measurements = [Measurement::DcVoltage("out".to_string())];
+        let wc = worst_case(&divider(), &tolerances, &measurements).unwrap();
+
+        // Exact corners: R1 low / R2 high and vice versa
+        let high = 10.0 * 1.05 / (0.95 + 1.05);
+        assert!((wc[0].max - high).abs() < 1e-6);
+        assert!((wc[0].min - (10.0 - high)).abs() < 1e-6);
+
+        let options = MonteCarloOptions { runs: 200, seed: 5 };
+        let mc = monte_carlo(&divider(), &tolerances, &measurements, &options).unwrap();
+        let stats = mc.statistics(0).unwrap();
+        assert!(stats.min >= wc[0].min - 1e-9 && stats.max <= wc[0].max + 1e-9);
+    }
+
+    #[test]
+    fn test_tolerance_from_component() {
+        let component = SelectedComponent::new("R2", 1000.0, 1000.0, "Ohm").with_tolerance(1.0);
+        let tolerance = Tolerance::from_component(&component);
+        assert_eq!(tolerance.element, "R2");
+        assert_eq!(tolerance.tolerance_pct, 1.0);
+        assert_eq!(tolerance.distribution, ToleranceDistribution::Gaussian);
+
+        let mut rng = StdRng::seed_from_u64(9);
+        for _ in 0..1000 {
+            assert!(tolerance.sample(&mut rng).abs() <= 0.01);
+        }
+    }
+}
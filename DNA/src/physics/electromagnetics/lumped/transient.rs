@@ -0,0 +1,841 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: transient.rs | DNA/src/physics/electromagnetics/lumped/transient.rs
+//! PURPOSE: Transient (time-domain) circuit analysis using companion models
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Transient (time-domain) circuit analysis using companion models
+//!
+//! LAYER: DNA → PHYSICS → ELECTROMAGNETICS → LUMPED
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM: Companion-model integration with LTE step control                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Each timestep replaces reactive elements by a conductance plus a current    │
+//! │ source (Norton companion) and solves the resulting DC MNA system, with      │
+//! │ Newton iteration when the circuit has diodes or transistors:                │
+//! │   Capacitor:  i = Geq·v + Ieq                                               │
+//! │   Inductor:   i = Geq·v + Ieq                                               │
+//! │                                                                             │
+//! │ Trapezoidal:  Geq = 2C/h,   Ieq = -(2C/h)·vₙ - iₙ                           │
+//! │ Gear-2:       dx/dt ≈ a₀xₙ₊₁ + a₁xₙ + a₂xₙ₋₁  (variable-step BDF2)          │
+//! │ First step and steps after source breakpoints use backward Euler            │
+//! │                                                                             │
+//! │ LTE ≈ k·(x_corrector - x_predictor), predictor = quadratic extrapolation    │
+//! │ Step scales by (tol/LTE)^(1/3); rejected steps are retried smaller          │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ IntegrationMethod   Trapezoidal or Gear-2 (BDF2)                            │
+//! │ TransientOptions    Tolerances, method and step limits                      │
+//! │ TransientResult     Accepted timepoints and per-node waveforms              │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! DEPENDS ON:
+//!   • super::netlist → Netlist, Element, SourceValue
+//!   • super::dc → Circuit preparation, operating point, Newton solves
+//!
+//! USED BY:
+//!   • CORE/SPICE_ENGINE → Transient analysis
+//!   • TOOLS/SPICE → Waveform plots
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// CODE BELOW - Optimized for ML development
+// ─────────────────────────────────────────────────────────────────────────────────
+
+use super::dc::{node_v, Circuit, DcOptions, Stamp};
+use super::netlist::{Netlist, SourceValue};
+
+/// Numerical integration method for reactive elements
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntegrationMethod {
+    /// Second-order, A-stable, no numerical damping (may ring on steps)
+    Trapezoidal,
+    /// Second-order backward differentiation, damps ringing
+    Gear2,
+}
+
+impl IntegrationMethod {
+    /// Milne factor turning corrector − predictor into the corrector's LTE
+    fn lte_factor(self) -> f64 {
+        match self {
+            // |C₃| = 1/12 against a quadratic predictor with C₃ = 1
+            IntegrationMethod::Trapezoidal => 1.0 / 13.0,
+            // |C₃| = 2/9
+            IntegrationMethod::Gear2 => 2.0 / 11.0,
+        }
+    }
+}
+
+/// Transient analysis settings
+#[derive(Clone, Debug)]
+pub struct TransientOptions {
+    pub method: IntegrationMethod,
+    /// Relative error tolerance per timestep
+    pub reltol: f64,
+    /// Absolute voltage tolerance (V)
+    pub vntol: f64,
+    /// Absolute current tolerance (A)
+    pub abstol: f64,
+    /// Largest allowed step; defaults to the output step `tstep`
+    pub max_step: Option<f64>,
+}
+
+impl TransientOptions {
+    /// Newton settings for each timepoint, sharing the LTE tolerances
+    fn newton_options(&self) -> DcOptions {
+        DcOptions {
+            reltol: self.reltol,
+            vntol: self.vntol,
+            abstol: self.abstol,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for TransientOptions {
+    fn default() -> Self {
+        Self {
+            method: IntegrationMethod::Trapezoidal,
+            reltol: 1e-3,
+            vntol: 1e-6,
+            abstol: 1e-12,
+            max_step: None,
+        }
+    }
+}
+
+/// Transient analysis result
+#[derive(Clone, Debug)]
+pub struct TransientResult {
+    /// Accepted timepoints (s), including every source breakpoint
+    pub times: Vec<f64>,
+    /// Full MNA solution at each timepoint: [time][node voltages, source currents]
+    pub node_voltages: Vec<Vec<f64>>,
+    /// Node name for each voltage row (row i is netlist node index i + 1)
+    pub node_names: Vec<String>,
+    /// Steps thrown away by the truncation error check
+    pub rejected_steps: usize,
+}
+
+impl TransientResult {
+    /// Voltage waveform of a named node, one sample per timepoint
+    pub fn waveform(&self, node: &str) -> Option<Vec<f64>> {
+        let row = self.row(node)?;
+        Some(
+            self.node_voltages
+                .iter()
+                .map(|x| row.map_or(0.0, |r| x[r]))
+                .collect(),
+        )
+    }
+
+    /// Node voltage at time `t`, linearly interpolated between timepoints
+    pub fn voltage_at(&self, node: &str, t: f64) -> Option<f64> {
+        let Some(r) = self.row(node)? else {
+            return Some(0.0);
+        };
+        let k = self.times.partition_point(|&ti| ti < t);
+        if k == 0 {
+            return self.node_voltages.first().map(|x| x[r]);
+        }
+        if k == self.times.len() {
+            return self.node_voltages.last().map(|x| x[r]);
+        }
+        let (t0, t1) = (self.times[k - 1], self.times[k]);
+        let (v0, v1) = (self.node_voltages[k - 1][r], self.node_voltages[k][r]);
+        Some(v0 + (v1 - v0) * (t - t0) / (t1 - t0))
+    }
+
+    /// Matrix row of a node: `Some(None)` for ground
+    fn row(&self, node: &str) -> Option<Option<usize>> {
+        if node == "0" {
+            return Some(None);
+        }
+        self.node_names.iter().position(|n| n == node).map(Some)
+    }
+}
+
+/// Perform transient analysis from t = 0 to `tstop` with the default options
+///
+/// The DC operating point (capacitors open, inductors shorted) at t = 0 is
+/// the initial condition. `tstep` is the largest step taken; the actual
+/// steps adapt to the local truncation error.
+pub fn transient_analysis(
+    netlist: &Netlist,
+    tstop: f64,
+    tstep: f64,
+) -> Result<TransientResult, String> {
+    transient_analysis_with(netlist, tstop, tstep, &TransientOptions::default())
+}
+
+/// Perform transient analysis with explicit options
+pub fn transient_analysis_with(
+    netlist: &Netlist,
+    tstop: f64,
+    tstep: f64,
+    options: &TransientOptions,
+) -> Result<TransientResult, String> {
+    if !(tstop > 0.0 && tstep > 0.0) {
+        return Err(format!(
+            "tstop ({}) and tstep ({}) must be positive",
+            tstop, tstep
+        ));
+    }
+
+    let circuit = Circuit::new(netlist)?;
+    let max_step = options.max_step.unwrap_or(tstep).min(tstop);
+    let first_step = max_step / 100.0;
+    let min_step = max_step * 1e-9;
+    let breakpoints = circuit.breakpoints(tstop);
+
+    let newton = options.newton_options();
+    let start = circuit.initial_point(&newton)?;
+    let mut result = TransientResult {
+        times: vec![0.0],
+        node_voltages: vec![start.x.clone()],
+        node_names: circuit.node_names.clone(),
+        rejected_steps: 0,
+    };
+
+    // Last accepted points, newest last; cleared back to one at breakpoints
+    let mut history = vec![start];
+    let mut h = first_step;
+
+    while let Some(last) = history.last() {
+        let t = last.t;
+        if t >= tstop * (1.0 - 1e-12) {
+            break;
+        }
+        let next_break = breakpoints
+            .iter()
+            .copied()
+            .find(|&b| b > t + min_step)
+            .unwrap_or(tstop);
+        h = h.min(max_step);
+        // Land exactly on the breakpoint rather than leaving a sliver
+        if t + h * 1.1 >= next_break {
+            h = next_break - t;
+        }
+
+        let method = if history.len() < 2 {
+            None
+        } else {
+            Some(options.method)
+        };
+        let point = match circuit.step(&history, t + h, method, &newton) {
+            Ok(point) => point,
+            Err(e) => {
+                // Newton failed from the last point; retry much closer
+                result.rejected_steps += 1;
+                h /= 8.0;
+                if h < min_step {
+                    return Err(e);
+                }
+                continue;
+            }
+        };
+
+        let mut grow = 2.0;
+        if let (Some(method), Some(predicted)) = (method, predict(&history, t + h)) {
+            let ratio = circuit.error_ratio(&point, &predicted, last, method, options);
+            let scale = 0.9 * ratio.max(1e-12).powf(-1.0 / 3.0);
+            if ratio > 1.0 {
+                result.rejected_steps += 1;
+                h *= scale.max(0.25);
+                if h < min_step {
+                    return Err(format!("Timestep too small at t = {:e} s", t));
+                }
+                continue;
+            }
+            grow = scale.min(2.0);
+        }
+
+        result.times.push(point.t);
+        result.node_voltages.push(point.x.clone());
+
+        if (point.t - next_break).abs() <= min_step {
+            // Derivatives jump at a breakpoint, so history before it would
+            // poison both the predictor and the Gear-2 formula
+            history.clear();
+            h = first_step;
+        } else {
+            h *= grow;
+        }
+        history.push(point);
+        if history.len() > 3 {
+            history.remove(0);
+        }
+    }
+
+    Ok(result)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TIMESTEPPING
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Accepted solution at one timepoint
+#[derive(Clone, Debug)]
+struct TimePoint {
+    t: f64,
+    /// MNA solution vector
+    x: Vec<f64>,
+    /// Capacitor currents, p → n
+    cap_i: Vec<f64>,
+    /// Inductor currents, p → n
+    ind_i: Vec<f64>,
+}
+
+impl Circuit<'_> {
+    /// Source corners in (0, tstop], sorted, ending with tstop
+    fn breakpoints(&self, tstop: f64) -> Vec<f64> {
+        let mut points = vec![tstop];
+        for stamp in &self.stamps {
+            let (Stamp::Voltage(_, _, _, source) | Stamp::Current(_, _, source)) = stamp else {
+                continue;
+            };
+            match **source {
+                SourceValue::Pulse {
+                    delay,
+                    rise_time,
+                    fall_time,
+                    pulse_width,
+                    period,
+                    ..
+                } => {
+                    let corners = [
+                        0.0,
+                        rise_time,
+                        rise_time + pulse_width,
+                        rise_time + pulse_width + fall_time,
+                    ];
+                    let mut start = delay;
+                    while start < tstop {
+                        points.extend(corners.iter().map(|c| start + c));
+                        if period <= 0.0 {
+                            break;
+                        }
+                        start += period;
+                    }
+                }
+                SourceValue::Sin { delay, .. } => points.push(delay),
+                _ => {}
+            }
+        }
+        points.retain(|&p| p > 0.0 && p <= tstop);
+        points.sort_by(f64::total_cmp);
+        points.dedup_by(|a, b| (*a - *b).abs() <= tstop * 1e-12);
+        points
+    }
+
+    /// DC operating point as the t = 0 timepoint
+    fn initial_point(&self, options: &DcOptions) -> Result<TimePoint, String> {
+        let (full, _, _) = self.operating_point(options)?;
+        let size = self.num_nodes + self.num_vsources;
+        Ok(TimePoint {
+            t: 0.0,
+            x: full[..size].to_vec(),
+            cap_i: vec![0.0; self.capacitors.len()],
+            ind_i: full[size..].to_vec(),
+        })
+    }
+
+    /// Solve one step to `t`, from the newest point in `history`
+    ///
+    /// `method` is `None` for a backward Euler step.
+    fn step(
+        &self,
+        history: &[TimePoint],
+        t: f64,
+        method: Option<IntegrationMethod>,
+        newton: &DcOptions,
+    ) -> Result<TimePoint, String> {
+        let now = &history[history.len() - 1];
+        let h = t - now.t;
+        let coeffs = Coefficients::new(history, t, method);
+        let mut m = self.static_matrix(t, 0, 1.0);
+
+        let mut cap_model = Vec::with_capacity(self.capacitors.len());
+        for (k, &(p, n, c)) in self.capacitors.iter().enumerate() {
+            let v = |pt: &TimePoint| node_v(&pt.x, p) - node_v(&pt.x, n);
+            let (g, ieq) = match coeffs {
+                Coefficients::Euler => (c / h, -c / h * v(now)),
+                Coefficients::Trapezoidal => (2.0 * c / h, -2.0 * c / h * v(now) - now.cap_i[k]),
+                Coefficients::Gear { a0, a1, a2 } => {
+                    let prev = &history[history.len() - 2];
+                    (c * a0, c * (a1 * v(now) + a2 * v(prev)))
+                }
+            };
+            m.stamp_conductance(p, n, g);
+            m.stamp_current_source(p, n, ieq);
+            cap_model.push((g, ieq));
+        }
+
+        let mut ind_model = Vec::with_capacity(self.inductors.len());
+        for (k, &(p, n, l)) in self.inductors.iter().enumerate() {
+            let (g, ieq) = match coeffs {
+                Coefficients::Euler => (h / l, now.ind_i[k]),
+                Coefficients::Trapezoidal => {
+                    let v = node_v(&now.x, p) - node_v(&now.x, n);
+                    (h / (2.0 * l), now.ind_i[k] + h / (2.0 * l) * v)
+                }
+                Coefficients::Gear { a0, a1, a2 } => {
+                    let prev = &history[history.len() - 2];
+                    (
+                        1.0 / (l * a0),
+                        -(a1 * now.ind_i[k] + a2 * prev.ind_i[k]) / a0,
+                    )
+                }
+            };
+            m.stamp_conductance(p, n, g);
+            m.stamp_current_source(p, n, ieq);
+            ind_model.push((g, ieq));
+        }
+
+        let (x, _) = self
+            .newton(&m, now.x.clone(), newton)
+            .map_err(|e| format!("Timestep at t = {:e} s failed: {}", t, e))?;
+
+        let branch = |(p, n, _): (usize, usize, f64), (g, ieq): (f64, f64)| {
+            g * (node_v(&x, p) - node_v(&x, n)) + ieq
+        };
+        let cap_i = self
+            .capacitors
+            .iter()
+            .zip(&cap_model)
+            .map(|(&e, &model)| branch(e, model))
+            .collect();
+        let ind_i = self
+            .inductors
+            .iter()
+            .zip(&ind_model)
+            .map(|(&e, &model)| branch(e, model))
+            .collect();
+
+        Ok(TimePoint { t, x, cap_i, ind_i })
+    }
+
+    /// Largest LTE estimate over node voltages and inductor currents,
+    /// relative to its tolerance (≤ 1 accepts the step)
+    fn error_ratio(
+        &self,
+        point: &TimePoint,
+        predicted: &TimePoint,
+        last: &TimePoint,
+        method: IntegrationMethod,
+        options: &TransientOptions,
+    ) -> f64 {
+        let k = method.lte_factor();
+        let ratio = |new: f64, pred: f64, old: f64, abs: f64| {
+            let tol = options.reltol * new.abs().max(old.abs()) + abs;
+            k * (new - pred).abs() / tol
+        };
+        let voltages = (0..self.num_nodes)
+            .map(|i| ratio(point.x[i], predicted.x[i], last.x[i], options.vntol));
+        let currents = (0..self.inductors.len()).map(|i| {
+            ratio(
+                point.ind_i[i],
+                predicted.ind_i[i],
+                last.ind_i[i],
+                options.abstol,
+            )
+        });
+        voltages.chain(currents).fold(0.0, f64::max)
+    }
+}
+
+// INTEGRATION FORMULAS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Derivative formula for one step
+#[derive(Clone, Copy, Debug)]
+enum Coefficients {
+    Euler,
+    Trapezoidal,
+    /// dx/dt ≈ a0·xₙ₊₁ + a1·xₙ + a2·xₙ₋₁
+    Gear {
+        a0: f64,
+        a1: f64,
+        a2: f64,
+    },
+}
+
+impl Coefficients {
+    fn new(history: &[TimePoint], t: f64, method: Option<IntegrationMethod>) -> Self {
+        match method {
+            None => Coefficients::Euler,
+            Some(IntegrationMethod::Trapezoidal) => Coefficients::Trapezoidal,
+            Some(IntegrationMethod::Gear2) => {
+                let n = history.len();
+                let h = t - history[n - 1].t;
+                let w = h / (history[n - 1].t - history[n - 2].t);
+                Coefficients::Gear {
+                    a0: (1.0 + 2.0 * w) / (h * (1.0 + w)),
+                    a1: -(1.0 + w) / h,
+                    a2: w * w / (h * (1.0 + w)),
+                }
+            }
+        }
+    }
+}
+
+/// Quadratic extrapolation of the last three points to `t`
+fn predict(history: &[TimePoint], t: f64) -> Option<TimePoint> {
+    let [p0, p1, p2] = history else {
+        return None;
+    };
+    let (t0, t1, t2) = (p0.t, p1.t, p2.t);
+    let l0 = (t - t1) * (t - t2) / ((t0 - t1) * (t0 - t2));
+    let l1 = (t - t0) * (t - t2) / ((t1 - t0) * (t1 - t2));
+    let l2 = (t - t0) * (t - t1) / ((t2 - t0) * (t2 - t1));
+    let blend = |a: &[f64], b: &[f64], c: &[f64]| -> Vec<f64> {
+        a.iter()
+            .zip(b)
+            .zip(c)
+            .map(|((x0, x1), x2)| l0 * x0 + l1 * x1 + l2 * x2)
+            .collect()
+    };
+    Some(TimePoint {
+        t,
+        x: blend(&p0.x, &p1.x, &p2.x),
+        cap_i: blend(&p0.cap_i, &p1.cap_i, &p2.cap_i),
+        ind_i: blend(&p0.ind_i, &p1.ind_i, &p2.ind_i),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::devices::DiodeModel;
+    use super::super::netlist::Element;
+    use super::*;
+
+    fn step_source(v2: f64) -> SourceValue {
+        SourceValue::Pulse {
+            v1: 0.0,
+            v2,
+            delay: 0.0,
+            rise_time: 1e-9,
+            fall_time: 1e-9,
+            pulse_width: 1.0,
+            period: 0.0,
+        }
+    }
+
+    fn rc_netlist(source: SourceValue) -> Netlist {
+        let mut netlist = Netlist::new("RC".to_string());
+        netlist.add_element(Element::VoltageSource {
+            name: "V1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "0".to_string(),
+            value: source,
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "out".to_string(),
+            value: 1000.0,
+        });
+        netlist.add_element(Element::Capacitor {
+            name: "C1".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: 1e-6,
+        });
+        netlist
+    }
+
+    #[test]
+    fn test_rc_step_response() {
+        // τ = RC = 1 ms
+        let netlist = rc_netlist(step_source(1.0));
+        for method in [IntegrationMethod::Trapezoidal, IntegrationMethod::Gear2] {
+            let options = TransientOptions {
+                method,
+                ..Default::default()
+            };
+            let result = transient_analysis_with(&netlist, 5e-3, 1e-4, &options).unwrap();
+
+            for &t in &[0.5e-3, 1e-3, 2e-3, 4e-3] {
+                let expected = 1.0 - (-t / 1e-3_f64).exp();
+                let v = result.voltage_at("out", t).unwrap();
+                assert!(
+                    (v - expected).abs() < 5e-3,
+                    "{:?}: v({}) = {}, expected {}",
+                    method,
+                    t,
+                    v,
+                    expected
+                );
+            }
+            assert_eq!(result.voltage_at("0", 1e-3), Some(0.0));
+        }
+    }
+
+    #[test]
+    fn test_rl_current_rise() {
+        // V -> R (10 Ω) -> L (10 mH) -> GND, τ = L/R = 1 ms
+        let mut netlist = Netlist::new("RL".to_string());
+        netlist.add_element(Element::VoltageSource {
+            name: "V1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "0".to_string(),
+            value: step_source(1.0),
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "x".to_string(),
+            value: 10.0,
+        });
+        netlist.add_element(Element::Inductor {
+            name: "L1".to_string(),
+            node_p: "x".to_string(),
+            node_n: "0".to_string(),
+            value: 10e-3,
+        });
+
+        let result = transient_analysis(&netlist, 5e-3, 1e-4).unwrap();
+        for &t in &[0.5e-3, 1e-3, 3e-3] {
+            // Inductor voltage decays as the current builds up
+            let expected = (-t / 1e-3_f64).exp();
+            let v = result.voltage_at("x", t).unwrap();
+            assert!((v - expected).abs() < 5e-3, "v({}) = {}", t, v);
+        }
+    }
+
+    #[test]
+    fn test_inductor_initial_current_from_operating_point() {
+        // DC source through R into L: the operating point already carries
+        // I = V/R, so nothing moves
+        let mut netlist = Netlist::new("RL DC".to_string());
+        netlist.add_element(Element::VoltageSource {
+            name: "V1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "0".to_string(),
+            value: SourceValue::DC(2.0),
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "x".to_string(),
+            value: 100.0,
+        });
+        netlist.add_element(Element::Inductor {
+            name: "L1".to_string(),
+            node_p: "x".to_string(),
+            node_n: "0".to_string(),
+            value: 1e-3,
+        });
+
+        let result = transient_analysis(&netlist, 1e-3, 1e-4).unwrap();
+        let x = result.waveform("x").unwrap();
+        assert!(x.iter().all(|v| v.abs() < 1e-9));
+        // Source branch current stays at -V/R by the MNA sign convention
+        let i = result.node_voltages.last().unwrap()[2];
+        assert!((i + 0.02).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_series_rlc_ringing() {
+        // Underdamped series RLC step: L = 1 mH, C = 1 µF, R = 10 Ω
+        // ω₀ = 31623 rad/s, α = R/2L = 5000 1/s
+        let mut netlist = Netlist::new("RLC".to_string());
+        netlist.add_element(Element::VoltageSource {
+            name: "V1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "0".to_string(),
+            value: step_source(1.0),
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "a".to_string(),
+            value: 10.0,
+        });
+        netlist.add_element(Element::Inductor {
+            name: "L1".to_string(),
+            node_p: "a".to_string(),
+            node_n: "out".to_string(),
+            value: 1e-3,
+        });
+        netlist.add_element(Element::Capacitor {
+            name: "C1".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: 1e-6,
+        });
+
+        let alpha: f64 = 5000.0;
+        let w0: f64 = (1.0 / (1e-3 * 1e-6_f64)).sqrt();
+        let wd = (w0 * w0 - alpha * alpha).sqrt();
+        let exact =
+            |t: f64| 1.0 - (-alpha * t).exp() * ((wd * t).cos() + alpha / wd * (wd * t).sin());
+
+        for method in [IntegrationMethod::Trapezoidal, IntegrationMethod::Gear2] {
+            let options = TransientOptions {
+                method,
+                ..Default::default()
+            };
+            let result = transient_analysis_with(&netlist, 1e-3, 1e-5, &options).unwrap();
+            for &t in &[5e-5, 1e-4, 2e-4, 5e-4] {
+                let v = result.voltage_at("out", t).unwrap();
+                assert!(
+                    (v - exact(t)).abs() < 1e-2,
+                    "{:?}: v({}) = {}, expected {}",
+                    method,
+                    t,
+                    v,
+                    exact(t)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pulse_breakpoints_and_adaptive_steps() {
+        let source = SourceValue::Pulse {
+            v1: 0.0,
+            v2: 1.0,
+            delay: 1e-3,
+            rise_time: 1e-6,
+            fall_time: 1e-6,
+            pulse_width: 2e-3,
+            period: 0.0,
+        };
+        let netlist = rc_netlist(source);
+        let result = transient_analysis(&netlist, 10e-3, 1e-4).unwrap();
+
+        // Every pulse corner is a timepoint
+        for corner in [1e-3, 1e-3 + 1e-6, 3e-3 + 1e-6, 3e-3 + 2e-6] {
+            assert!(
+                result.times.iter().any(|&t| (t - corner).abs() < 1e-12),
+                "missing breakpoint {}",
+                corner
+            );
+        }
+        // Flat stretches run at the maximum step, so far fewer points than
+        // a fixed step at the post-edge resolution would need
+        assert!(result.times.len() < 1000, "{} points", result.times.len());
+        assert!(result.times.windows(2).all(|w| w[1] - w[0] <= 1e-4 + 1e-15));
+
+        // Capacitor still at zero before the pulse, discharging after it
+        assert!(result.voltage_at("out", 0.9e-3).unwrap().abs() < 1e-9);
+        let peak = 1.0 - (-2.0_f64).exp();
+        assert!((result.voltage_at("out", 3e-3).unwrap() - peak).abs() < 5e-3);
+        let after = peak * (-2.0_f64).exp();
+        assert!((result.voltage_at("out", 5e-3).unwrap() - after).abs() < 5e-3);
+    }
+
+    #[test]
+    fn test_pulsed_current_source_breakpoints() {
+        // 1 mA pulse into 1 kΩ ∥ 1 µF: same 1 V, τ = 1 ms response as the
+        // voltage-driven RC
+        let mut netlist = Netlist::new("I-RC".to_string());
+        netlist.add_element(Element::CurrentSource {
+            name: "I1".to_string(),
+            node_p: "0".to_string(),
+            node_n: "out".to_string(),
+            value: SourceValue::Pulse {
+                v1: 0.0,
+                v2: 1e-3,
+                delay: 1e-3,
+                rise_time: 1e-6,
+                fall_time: 1e-6,
+                pulse_width: 2e-3,
+                period: 0.0,
+            },
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: 1000.0,
+        });
+        netlist.add_element(Element::Capacitor {
+            name: "C1".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: 1e-6,
+        });
+        let result = transient_analysis(&netlist, 10e-3, 1e-4).unwrap();
+
+        for corner in [1e-3, 1e-3 + 1e-6, 3e-3 + 1e-6, 3e-3 + 2e-6] {
+            assert!(
+                result.times.iter().any(|&t| (t - corner).abs() < 1e-12),
+                "missing breakpoint {}",
+                corner
+            );
+        }
+        assert!(result.voltage_at("out", 0.9e-3).unwrap().abs() < 1e-9);
+        let peak = 1.0 - (-2.0_f64).exp();
+        assert!((result.voltage_at("out", 3e-3).unwrap() - peak).abs() < 5e-3);
+    }
+
+    #[test]
+    fn test_sine_source_through_rc() {
+        // Well below the corner the output follows the input
+        let source = SourceValue::Sin {
+            offset: 0.0,
+            amplitude: 1.0,
+            freq: 10.0,
+            delay: 0.0,
+            damping: 0.0,
+        };
+        let netlist = rc_netlist(source);
+        let result = transient_analysis(&netlist, 0.1, 1e-3).unwrap();
+        let v = result.voltage_at("out", 0.025).unwrap();
+        assert!((v - 1.0).abs() < 0.01, "v = {}", v);
+    }
+
+    #[test]
+    fn test_invalid_times_rejected() {
+        let netlist = rc_netlist(SourceValue::DC(1.0));
+        assert!(transient_analysis(&netlist, 0.0, 1e-3).is_err());
+        assert!(transient_analysis(&netlist, 1e-3, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_half_wave_rectifier() {
+        // 5 V, 50 Hz sine through a diode into 1 kΩ
+        let mut netlist = Netlist::new("Rectifier".to_string());
+        netlist.add_element(Element::VoltageSource {
+            name: "V1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "0".to_string(),
+            value: SourceValue::Sin {
+                offset: 0.0,
+                amplitude: 5.0,
+                freq: 50.0,
+                delay: 0.0,
+                damping: 0.0,
+            },
+        });
+        netlist.add_element(Element::Diode {
+            name: "D1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "out".to_string(),
+            model: DiodeModel::default(),
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: 1e3,
+        });
+
+        let result = transient_analysis(&netlist, 40e-3, 2e-4).unwrap();
+        // Positive peak loses one junction drop, negative half is blocked
+        let peak = result.voltage_at("out", 5e-3).unwrap();
+        assert!(peak > 4.2 && peak < 4.5, "peak = {}", peak);
+        let blocked = result.voltage_at("out", 15e-3).unwrap();
+        assert!(blocked.abs() < 1e-6, "blocked = {}", blocked);
+        assert!(result.voltage_at("out", 25e-3).unwrap() > 4.2);
+    }
+}
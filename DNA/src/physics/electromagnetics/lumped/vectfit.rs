@@ -0,0 +1,589 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: vectfit.rs | DNA/src/physics/electromagnetics/lumped/vectfit.rs
+//! PURPOSE: Vector fitting of sampled AC responses to rational transfer functions
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Vector fitting of sampled AC responses to rational transfer functions
+//!
+//! LAYER: DNA → PHYSICS → ELECTROMAGNETICS → LUMPED
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM: Vector fitting (Gustavsen & Semlyen)                             │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Model:  H(s) ≈ Σ rₙ / (s - aₙ) + d                                          │
+//! │ Frequencies normalized by ω₀ = √(ω_min ω_max); weights 1/|H_k| (relative)   │
+//! │                                                                             │
+//! │ Pole relocation (repeat):                                                   │
+//! │   σ(s) = 1 + Σ c̃ₙ / (s - aₙ)                                                │
+//! │   least squares:  Σ cₙ/(s-aₙ) + d - H(s) Σ c̃ₙ/(s-aₙ) = H(s)                 │
+//! │   new poles = zeros of σ = eig(A - b c̃ᵀ), unstable poles reflected          │
+//! │   Conjugate pairs use the real basis 1/(s-a) ± j-weighted 1/(s-a*)          │
+//! │                                                                             │
+//! │ Residues: least squares with the final poles                                │
+//! │ Zeros: roots of N(s) = d Π(s - a) + Σ rₙ Π_{m≠n}(s - aₘ) (companion eig)    │
+//! │   leading terms negligible across the band are dropped first                │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ VectorFitOptions   Order, relocation iterations, direct term                │
+//! │ VectorFitResult    Pole-residue model with zeros, gain and fit error        │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! DEPENDS ON:
+//!   • super::ac → ACResult samples
+//!   • super::polezero → eigenvalues, TransferFunction conversion
+//!
+//! USED BY:
+//!   • power/control → Verifying compensators against simulated plants
+//!   • CORE/SPICE_ENGINE → Rational fits of .ac results
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// CODE BELOW - Optimized for ML development
+// ─────────────────────────────────────────────────────────────────────────────────
+
+use super::ac::{ACResult, Complex};
+use super::polezero::{eigenvalues, rational, to_transfer_function};
+use crate::power::control::{self, TransferFunction};
+use std::f64::consts::TAU;
+
+/// Numerator terms smaller than this (relative, over the band) are dropped
+const NEGLIGIBLE_TERM: f64 = 1e-9;
+
+/// Vector fitting options
+#[derive(Clone, Debug)]
+pub struct VectorFitOptions {
+    /// Number of poles
+    pub order: usize,
+    /// Pole relocation iterations
+    pub iterations: usize,
+    /// Fit a constant term d (false forces a strictly proper model)
+    pub direct_term: bool,
+}
+
+impl Default for VectorFitOptions {
+    fn default() -> Self {
+        Self {
+            order: 4,
+            iterations: 20,
+            direct_term: true,
+        }
+    }
+}
+
+/// Rational model of a sampled response
+///
+/// Poles, residues and zeros are in rad/s; the same model in pole-zero form
+/// is H(s) = gain · Π(s - z) / Π(s - p).
+#[derive(Clone, Debug)]
+pub struct VectorFitResult {
+    pub poles: Vec<Complex>,
+    pub residues: Vec<Complex>,
+    pub direct: f64,
+    pub zeros: Vec<Complex>,
+    pub gain: f64,
+    /// RMS of |fit - H| / |H| over the samples
+    pub rms_error: f64,
+}
+
+impl VectorFitResult {
+    /// Fitted H(jω) at frequency `freq` (Hz)
+    pub fn evaluate(&self, freq: f64) -> Complex {
+        let s = Complex::new(0.0, TAU * freq);
+        self.poles
+            .iter()
+            .zip(&self.residues)
+            .fold(Complex::new(self.direct, 0.0), |h, (&p, &r)| {
+                h + r / (s - p)
+            })
+    }
+
+    /// Same model in pole-zero form
+    pub fn evaluate_pole_zero(&self, freq: f64) -> Complex {
+        rational(
+            self.gain,
+            &self.zeros,
+            &self.poles,
+            Complex::new(0.0, TAU * freq),
+        )
+    }
+
+    /// Equivalent `power::control` transfer function
+    pub fn transfer_function(&self) -> TransferFunction {
+        to_transfer_function(self.gain, &self.zeros, &self.poles)
+    }
+}
+
+/// Fit the response of `node` (index from `Netlist::node_index`) in an AC sweep
+pub fn fit_transfer_function(
+    result: &ACResult,
+    node: usize,
+    options: &VectorFitOptions,
+) -> Result<VectorFitResult, String> {
+    if node == 0 {
+        return Err("Vector fit: ground has no response".to_string());
+    }
+    let response = result
+        .node_voltages
+        .iter()
+        .map(|v| v.get(node - 1).copied())
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| format!("Vector fit: node {} not in AC result", node))?;
+    vector_fit(&result.frequencies, &response, options)
+}
+
+/// One real pole, or a conjugate pair stored by its upper half-plane member
+#[derive(Clone, Copy, Debug)]
+enum Pole {
+    Real(f64),
+    Pair(Complex),
+}
+
+impl Pole {
+    fn columns(&self) -> usize {
+        match self {
+            Pole::Real(_) => 1,
+            Pole::Pair(_) => 2,
+        }
+    }
+}
+
+/// Real basis functions of every pole at `s`
+fn basis(poles: &[Pole], s: Complex) -> Vec<Complex> {
+    let one = Complex::new(1.0, 0.0);
+    let j = Complex::new(0.0, 1.0);
+    let mut phi = Vec::new();
+    for pole in poles {
+        match *pole {
+            Pole::Real(a) => phi.push(one / (s - Complex::new(a, 0.0))),
+            Pole::Pair(a) => {
+                let (u, v) = (one / (s - a), one / (s - a.conjugate()));
+                phi.push(u + v);
+                phi.push(j * u - j * v);
+            }
+        }
+    }
+    phi
+}
+
+/// Group eigenvalues into real poles and conjugate pairs, reflecting unstable ones
+fn group_poles(roots: &[Complex]) -> Vec<Pole> {
+    let mut poles = Vec::new();
+    for root in roots {
+        let real = -root.real.abs();
+        if root.imag == 0.0 {
+            poles.push(Pole::Real(real));
+        } else if root.imag > 0.0 {
+            poles.push(Pole::Pair(Complex::new(real, root.imag)));
+        }
+    }
+    poles
+}
+
+/// Fit `response` sampled at `frequencies` (Hz) with `options.order` poles
+pub fn vector_fit(
+    frequencies: &[f64],
+    response: &[Complex],
+    options: &VectorFitOptions,
+) -> Result<VectorFitResult, String> {
+    let order = options.order;
+    if order == 0 {
+        return Err("Vector fit: order must be at least 1".to_string());
+    }
+    if frequencies.len() != response.len() {
+        return Err("Vector fit: frequency and response lengths differ".to_string());
+    }
+    let unknowns = 2 * order + options.direct_term as usize;
+    if 2 * frequencies.len() < unknowns {
+        return Err(format!(
+            "Vector fit: {} samples cannot fit {} poles",
+            frequencies.len(),
+            order
+        ));
+    }
+    if frequencies.iter().any(|&f| f <= 0.0 || !f.is_finite()) {
+        return Err("Vector fit: frequencies must be positive".to_string());
+    }
+    if response.iter().any(|h| h.magnitude() == 0.0) {
+        return Err("Vector fit: response has zero samples".to_string());
+    }
+
+    let w_min = frequencies.iter().fold(f64::INFINITY, |a, &f| a.min(f)) * TAU;
+    let w_max = frequencies.iter().fold(0.0_f64, |a, &f| a.max(f)) * TAU;
+    let w0 = (w_min * w_max).sqrt();
+    let s: Vec<Complex> = frequencies
+        .iter()
+        .map(|&f| Complex::new(0.0, TAU * f / w0))
+        .collect();
+    let weights: Vec<f64> = response.iter().map(|h| 1.0 / h.magnitude()).collect();
+
+    // Starting poles: lightly damped pairs spread log-uniformly over the band
+    let (lo, hi) = (w_min / w0, w_max / w0);
+    let pairs = order / 2;
+    let mut poles: Vec<Pole> = (0..pairs)
+        .map(|k| {
+            let t = if pairs > 1 {
+                k as f64 / (pairs - 1) as f64
+            } else {
+                0.5
+            };
+            let beta = lo * (hi / lo).powf(t);
+            Pole::Pair(Complex::new(-beta / 100.0, beta))
+        })
+        .collect();
+    if order % 2 == 1 {
+        poles.push(Pole::Real(-1.0));
+    }
+
+    for _ in 0..options.iterations {
+        // Unknowns: [c (order), d?, c̃ (order)]
+        let mut rows = Vec::with_capacity(2 * s.len());
+        let mut rhs = Vec::with_capacity(2 * s.len());
+        for ((&sk, &hk), &wk) in s.iter().zip(response).zip(&weights) {
+            let phi = basis(&poles, sk);
+            let mut row: Vec<Complex> = phi.iter().map(|&p| p * wk).collect();
+            if options.direct_term {
+                row.push(Complex::new(wk, 0.0));
+            }
+            row.extend(phi.iter().map(|&p| Complex::zero() - hk * p * wk));
+            let target = hk * wk;
+            rows.push(row.iter().map(|c| c.real).collect());
+            rows.push(row.iter().map(|c| c.imag).collect());
+            rhs.push(target.real);
+            rhs.push(target.imag);
+        }
+        let x = least_squares(rows, rhs)?;
+        let sigma = &x[unknowns - order..];
+
+        // Zeros of σ(s): eig(A - b c̃ᵀ) in the real pair form
+        let mut a = vec![vec![0.0; order]; order];
+        let mut b = vec![0.0; order];
+        let mut col = 0;
+        for pole in &poles {
+            match *pole {
+                Pole::Real(p) => {
+                    a[col][col] = p;
+                    b[col] = 1.0;
+                }
+                Pole::Pair(p) => {
+                    a[col][col] = p.real;
+                    a[col][col + 1] = p.imag;
+                    a[col + 1][col] = -p.imag;
+                    a[col + 1][col + 1] = p.real;
+                    b[col] = 2.0;
+                }
+            }
+            col += pole.columns();
+        }
+        for (i, row) in a.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v -= b[i] * sigma[j];
+            }
+        }
+        poles = group_poles(&eigenvalues(&a)?);
+    }
+
+    // Residues with the final poles
+    let mut rows = Vec::with_capacity(2 * s.len());
+    let mut rhs = Vec::with_capacity(2 * s.len());
+    for ((&sk, &hk), &wk) in s.iter().zip(response).zip(&weights) {
+        let mut row: Vec<Complex> = basis(&poles, sk).iter().map(|&p| p * wk).collect();
+        if options.direct_term {
+            row.push(Complex::new(wk, 0.0));
+        }
+        rows.push(row.iter().map(|c| c.real).collect());
+        rows.push(row.iter().map(|c| c.imag).collect());
+        rhs.push(hk.real * wk);
+        rhs.push(hk.imag * wk);
+    }
+    let x = least_squares(rows, rhs)?;
+    let direct = if options.direct_term { x[order] } else { 0.0 };
+
+    // Expand pairs into both conjugates (normalized units)
+    let mut norm_poles = Vec::with_capacity(order);
+    let mut norm_residues = Vec::with_capacity(order);
+    let mut col = 0;
+    for pole in &poles {
+        match *pole {
+            Pole::Real(p) => {
+                norm_poles.push(Complex::new(p, 0.0));
+                norm_residues.push(Complex::new(x[col], 0.0));
+            }
+            Pole::Pair(p) => {
+                let r = Complex::new(x[col], x[col + 1]);
+                norm_poles.extend([p, p.conjugate()]);
+                norm_residues.extend([r, r.conjugate()]);
+            }
+        }
+        col += pole.columns();
+    }
+
+    // Numerator N(s) = d Π(s - a) + Σ rₙ Π_{m≠n}(s - aₘ), ascending powers
+    let mut numerator = vec![Complex::zero(); order + 1];
+    for (k, c) in poly_from_roots(&norm_poles).into_iter().enumerate() {
+        numerator[k] = c * direct;
+    }
+    for (n, &r) in norm_residues.iter().enumerate() {
+        let others: Vec<Complex> = norm_poles
+            .iter()
+            .enumerate()
+            .filter(|&(m, _)| m != n)
+            .map(|(_, &p)| p)
+            .collect();
+        for (k, c) in poly_from_roots(&others).into_iter().enumerate() {
+            numerator[k] = numerator[k] + r * c;
+        }
+    }
+    let mut numerator: Vec<f64> = numerator.iter().map(|c| c.real).collect();
+    let reach = |k: usize, c: f64| c.abs() * lo.powi(k as i32).max(hi.powi(k as i32));
+    let largest = numerator
+        .iter()
+        .enumerate()
+        .fold(0.0_f64, |m, (k, &c)| m.max(reach(k, c)));
+    while numerator.len() > 1 {
+        let k = numerator.len() - 1;
+        if reach(k, numerator[k]) > NEGLIGIBLE_TERM * largest {
+            break;
+        }
+        numerator.pop();
+    }
+
+    // Zeros from the companion matrix of the monic numerator
+    let degree = numerator.len() - 1;
+    let lead = numerator[degree];
+    let mut companion = vec![vec![0.0; degree]; degree];
+    for j in 0..degree {
+        companion[0][j] = -numerator[degree - 1 - j] / lead;
+        if j + 1 < degree {
+            companion[j + 1][j] = 1.0;
+        }
+    }
+    let mut norm_zeros = eigenvalues(&companion)?;
+    norm_zeros.sort_by(|a, b| a.magnitude().total_cmp(&b.magnitude()));
+
+    // Back to rad/s: r/(s' - a') = ω₀ r/(s - ω₀ a')
+    let scale = |c: &Complex| *c * w0;
+    let poles: Vec<Complex> = norm_poles.iter().map(scale).collect();
+    let residues: Vec<Complex> = norm_residues.iter().map(scale).collect();
+    let zeros: Vec<Complex> = norm_zeros.iter().map(scale).collect();
+    let gain = lead * w0.powi(order as i32 - degree as i32);
+
+    let mut fit = VectorFitResult {
+        poles,
+        residues,
+        direct,
+        zeros,
+        gain,
+        rms_error: 0.0,
+    };
+    let sum: f64 = frequencies
+        .iter()
+        .zip(response)
+        .map(|(&f, &h)| ((fit.evaluate(f) - h).magnitude() / h.magnitude()).powi(2))
+        .sum();
+    fit.rms_error = (sum / frequencies.len() as f64).sqrt();
+    Ok(fit)
+}
+
+/// Coefficients (ascending powers) of the monic Π(s - r)
+fn poly_from_roots(roots: &[Complex]) -> Vec<Complex> {
+    let roots: Vec<control::Complex> = roots.iter().map(|&r| r.into()).collect();
+    control::poly_from_roots(&roots)
+        .into_iter()
+        .map(Complex::from)
+        .collect()
+}
+
+/// Least-squares solution of `rows · x = rhs` by Householder QR
+///
+/// Columns are scaled to unit norm first; the pole basis spans decades.
+fn least_squares(mut rows: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Result<Vec<f64>, String> {
+    let n = rows.first().map_or(0, |r| r.len());
+    let scale: Vec<f64> = (0..n)
+        .map(|j| {
+            let norm = rows.iter().map(|r| r[j] * r[j]).sum::<f64>().sqrt();
+            if norm > 0.0 {
+                norm
+            } else {
+                1.0
+            }
+        })
+        .collect();
+    for row in rows.iter_mut() {
+        for (v, s) in row.iter_mut().zip(&scale) {
+            *v /= s;
+        }
+    }
+
+    for k in 0..n {
+        let norm = rows[k..].iter().map(|r| r[k] * r[k]).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            continue;
+        }
+        let alpha = -norm.copysign(rows[k][k]);
+        let mut v: Vec<f64> = rows[k..].iter().map(|r| r[k]).collect();
+        v[0] -= alpha;
+        let v_norm2: f64 = v.iter().map(|x| x * x).sum();
+        if v_norm2 == 0.0 {
+            continue;
+        }
+        for j in k..n {
+            let dot: f64 = rows[k..].iter().zip(&v).map(|(r, vi)| r[j] * vi).sum();
+            let f = 2.0 * dot / v_norm2;
+            for (r, vi) in rows[k..].iter_mut().zip(&v) {
+                r[j] -= f * vi;
+            }
+        }
+        let dot: f64 = rhs[k..].iter().zip(&v).map(|(b, vi)| b * vi).sum();
+        let f = 2.0 * dot / v_norm2;
+        for (b, vi) in rhs[k..].iter_mut().zip(&v) {
+            *b -= f * vi;
+        }
+    }
+
+    let diagonal = (0..n).fold(0.0_f64, |a, k| a.max(rows[k][k].abs()));
+    let mut x = vec![0.0; n];
+    for k in (0..n).rev() {
+        if rows[k][k].abs() <= 1e-14 * diagonal {
+            return Err("Vector fit: least-squares system is rank deficient".to_string());
+        }
+        let sum: f64 = (k + 1..n).map(|j| rows[k][j] * x[j]).sum();
+        x[k] = (rhs[k] - sum) / rows[k][k];
+    }
+    Ok(x.iter().zip(&scale).map(|(v, s)| v / s).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::electromagnetics::lumped::{
+        ac_analysis, pole_zero, Element, Netlist, SourceValue,
+    };
+
+    fn closest(roots: &[Complex], target: Complex) -> f64 {
+        roots
+            .iter()
+            .map(|r| (*r - target).magnitude() / target.magnitude())
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[test]
+    fn test_fit_recovers_rational_function() {
+        // H(s) = 2e9 (s + 3e3) / ((s + 1e3)(s² + 2e4 s + 2.6e9))
+        let zeros = [Complex::new(-3e3, 0.0)];
+        let poles = [
+            Complex::new(-1e3, 0.0),
+            Complex::new(-1e4, 5e4),
+            Complex::new(-1e4, -5e4),
+        ];
+        let frequencies: Vec<f64> = (0..60)
+            .map(|i| 10f64.powf(1.0 + 5.0 * i as f64 / 59.0))
+            .collect();
+        let response: Vec<Complex> = frequencies
+            .iter()
+            .map(|&f| rational(2e9, &zeros, &poles, Complex::new(0.0, TAU * f)))
+            .collect();
+
+        let options = VectorFitOptions {
+            order: 3,
+            direct_term: false,
+            ..Default::default()
+        };
+        let fit = vector_fit(&frequencies, &response, &options).unwrap();
+        assert!(fit.rms_error < 1e-8, "rms {}", fit.rms_error);
+        for p in poles {
+            assert!(closest(&fit.poles, p) < 1e-6);
+        }
+        assert_eq!(fit.zeros.len(), 1);
+        assert!(closest(&fit.zeros, zeros[0]) < 1e-6);
+        assert!((fit.gain - 2e9).abs() < 1e-6 * 2e9);
+
+        // Pole-zero form and the control TransferFunction agree with the data
+        let tf = fit.transfer_function();
+        for (&f, &h) in frequencies.iter().zip(&response).step_by(7) {
+            let a = tf.evaluate(f);
+            assert!((Complex::new(a.re, a.im) - h).magnitude() < 1e-6 * h.magnitude());
+            assert!((fit.evaluate_pole_zero(f) - h).magnitude() < 1e-6 * h.magnitude());
+        }
+    }
+
+    #[test]
+    fn test_fit_ac_result_matches_pole_zero() {
+        // Two-section RC ladder with a bypassed series resistor (lead network)
+        let mut netlist = Netlist::new("lead".to_string());
+        netlist.add_element(Element::VoltageSource {
+            name: "V1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "0".to_string(),
+            value: SourceValue::AC {
+                magnitude: 1.0,
+                phase: 0.0,
+            },
+        });
+        let parts = [
+            ("R1", "in", "a", 1e3, false),
+            ("C1", "in", "a", 100e-9, true),
+            ("R2", "a", "0", 1e3, false),
+            ("R3", "a", "out", 10e3, false),
+            ("C2", "out", "0", 1e-9, true),
+        ];
+        for (name, p, n, value, capacitor) in parts {
+            let (name, node_p, node_n) = (name.to_string(), p.to_string(), n.to_string());
+            netlist.add_element(if capacitor {
+                Element::Capacitor {
+                    name,
+                    node_p,
+                    node_n,
+                    value,
+                }
+            } else {
+                Element::Resistor {
+                    name,
+                    node_p,
+                    node_n,
+                    value,
+                }
+            });
+        }
+
+        let ac = ac_analysis(&netlist, 10.0, 1e7, 10).unwrap();
+        let out = netlist.node_index("out").unwrap();
+        let options = VectorFitOptions {
+            order: 2,
+            ..Default::default()
+        };
+        let fit = fit_transfer_function(&ac, out, &options).unwrap();
+        assert!(fit.rms_error < 1e-6, "rms {}", fit.rms_error);
+
+        let pz = pole_zero(&netlist, "V1", "out").unwrap();
+        assert_eq!(fit.poles.len(), pz.poles.len());
+        for &p in &pz.poles {
+            assert!(closest(&fit.poles, p) < 1e-5);
+        }
+        assert_eq!(fit.zeros.len(), pz.zeros.len());
+        for &z in &pz.zeros {
+            assert!(closest(&fit.zeros, z) < 1e-5);
+        }
+        assert!((fit.gain - pz.gain).abs() < 1e-5 * pz.gain.abs());
+    }
+
+    #[test]
+    fn test_fit_rejects_bad_input() {
+        let options = VectorFitOptions::default();
+        let h = [Complex::new(1.0, 0.0); 3];
+        assert!(vector_fit(&[1.0, 2.0, 3.0], &h[..2], &options).is_err());
+        assert!(vector_fit(&[1.0, 2.0, 3.0], &h, &options).is_err());
+        let ac = ACResult {
+            frequencies: vec![1.0],
+            node_voltages: vec![vec![Complex::new(1.0, 0.0)]],
+        };
+        assert!(fit_transfer_function(&ac, 0, &options).is_err());
+        assert!(fit_transfer_function(&ac, 2, &options).is_err());
+    }
+}
@@ -0,0 +1,941 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: behavioral.rs | DNA/src/pll/behavioral.rs
+//! PURPOSE: Event-driven behavioral PLL simulator (PFD, charge pump, loop filter, VCO, divider)
+//! MODIFIED: 2026-10-17
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Unlike `transient::simulate_step_response`, which averages the phase detector
+//! into a continuous current, this simulator walks the loop edge by edge:
+//!
+//! - Reference edges arrive every R / f_ref; divider edges fire when the VCO has
+//!   accumulated N_k cycles, where N_k comes from a MASH modulator in fractional-N
+//! - A tri-state PFD sets UP/DN on those edges and resets both after a delay;
+//!   an edge that arrives while its flip-flop is already set is a cycle slip
+//! - The charge pump conducts only after the dead-zone time, with UP/DN mismatch
+//!   and a constant leakage current
+//! - Between events the pump current is constant and the RC ladder of the
+//!   2nd/3rd/4th order `LoopFilterDesign` is integrated with RK4
+//! - The VCO follows a piecewise-linear tuning curve clamped at its rails
+//!
+//! Reference and fractional spurs are read from the tuning-voltage ripple once
+//! the loop has locked, using narrowband FM: spur = 20·log10(Kvco·Vm / 2fm).
+
+use super::components::VCOSpec;
+use super::fractional_n::MashModulator;
+use super::types::{
+    DividerConfig, LoopFilterDesign, LoopFilterTopology, PLLDesign, PLLRequirements,
+    TransientResult,
+};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Tuning-voltage samples taken per PFD period for spur analysis
+const SAMPLES_PER_PFD: f64 = 8.0;
+/// Upper bound on stored tuning-voltage samples (the tail of the run is kept)
+const MAX_SPUR_SAMPLES: u64 = 1 << 16;
+/// Upper bound on waveform points returned in the transient result
+const MAX_TRACE_POINTS: usize = 2000;
+/// Divider cycles that must stay within tolerance to declare lock
+const MIN_LOCKED_CYCLES: usize = 32;
+/// Integration steps before the run is abandoned
+const MAX_STEPS: usize = 20_000_000;
+/// Reported level when a spur is below numerical resolution
+const SPUR_FLOOR_DBC: f64 = -200.0;
+
+// ============================================================================
+// MODEL PARAMETERS
+// ============================================================================
+
+/// PFD and charge pump non-idealities
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChargePumpModel {
+    /// Nominal pump current (A)
+    pub current_a: f64,
+    /// UP/DN mismatch as a fraction of nominal: UP = I·(1+m/2), DN = I·(1−m/2)
+    pub mismatch: f64,
+    /// Leakage drawn out of the loop filter node (A)
+    pub leakage_a: f64,
+    /// PFD reset (anti-backlash) delay once both outputs are set (s)
+    pub reset_delay_s: f64,
+    /// Time an UP/DN output must be high before the pump conducts (s)
+    pub dead_zone_s: f64,
+}
+
+impl ChargePumpModel {
+    /// Matched, leakage-free pump with a 1 ns anti-backlash pulse
+    pub fn ideal(current_a: f64) -> Self {
+        Self {
+            current_a,
+            mismatch: 0.0,
+            leakage_a: 0.0,
+            reset_delay_s: 1e-9,
+            dead_zone_s: 0.0,
+        }
+    }
+
+    fn up_current(&self) -> f64 {
+        self.current_a * (1.0 + self.mismatch / 2.0)
+    }
+
+    fn down_current(&self) -> f64 {
+        self.current_a * (1.0 - self.mismatch / 2.0)
+    }
+}
+
+/// Piecewise-linear VCO tuning curve, (tuning voltage V, frequency Hz) points
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VcoTuningCurve {
+    pub points: Vec<(f64, f64)>,
+}
+
+impl VcoTuningCurve {
+    /// Constant-gain curve centred on `center_freq_hz` at the middle of [v_min, v_max]
+    pub fn linear(center_freq_hz: f64, kvco_hz_per_v: f64, v_min: f64, v_max: f64) -> Self {
+        let v_mid = (v_min + v_max) / 2.0;
+        Self {
+            points: vec![
+                (v_min, center_freq_hz + kvco_hz_per_v * (v_min - v_mid)),
+                (v_max, center_freq_hz + kvco_hz_per_v * (v_max - v_mid)),
+            ],
+        }
+    }
+
+    /// Datasheet VCO: its Kvco through the middle of the tuning range, clamped there
+    pub fn from_vco_spec(vco: &VCOSpec) -> Self {
+        Self::linear(
+            (vco.freq_min_hz + vco.freq_max_hz) / 2.0,
+            vco.kvco_mhz_per_v * 1e6,
+            vco.vtune_min_v,
+            vco.vtune_max_v,
+        )
+    }
+
+    /// Measured curve; voltages must be strictly increasing and frequencies rising
+    pub fn from_points(points: Vec<(f64, f64)>) -> Result<Self, String> {
+        let curve = Self { points };
+        curve.validate()?;
+        Ok(curve)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.points.len() < 2 {
+            return Err("VCO tuning curve needs at least two points".to_string());
+        }
+        for pair in self.points.windows(2) {
+            let ((v0, f0), (v1, f1)) = (pair[0], pair[1]);
+            if v1 <= v0 {
+                return Err(format!(
+                    "VCO tuning voltages must increase ({} V then {} V)",
+                    v0, v1
+                ));
+            }
+            if f1 < f0 {
+                return Err(format!(
+                    "VCO frequency must not fall with tuning voltage ({} Hz at {} V)",
+                    f1, v1
+                ));
+            }
+        }
+        if self.points[0].1 <= 0.0 {
+            return Err("VCO frequency must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    fn segment(&self, v: f64) -> usize {
+        let last = self.points.len() - 2;
+        self.points
+            .windows(2)
+            .position(|pair| v < pair[1].0)
+            .unwrap_or(last)
+            .min(last)
+    }
+
+    /// Output frequency, clamped at the ends of the curve
+    pub fn frequency(&self, v: f64) -> f64 {
+        let first = self.points[0];
+        let last = self.points[self.points.len() - 1];
+        if v <= first.0 {
+            return first.1;
+        }
+        if v >= last.0 {
+            return last.1;
+        }
+        let i = self.segment(v);
+        let ((v0, f0), (v1, f1)) = (self.points[i], self.points[i + 1]);
+        f0 + (f1 - f0) * (v - v0) / (v1 - v0)
+    }
+
+    /// Local tuning gain (Hz/V)
+    pub fn gain_at(&self, v: f64) -> f64 {
+        let i = self.segment(v);
+        let ((v0, f0), (v1, f1)) = (self.points[i], self.points[i + 1]);
+        (f1 - f0) / (v1 - v0)
+    }
+
+    /// Tuning voltage giving `freq_hz`, clamped to the rails
+    pub fn voltage_for(&self, freq_hz: f64) -> f64 {
+        let first = self.points[0];
+        let last = self.points[self.points.len() - 1];
+        if freq_hz <= first.1 {
+            return first.0;
+        }
+        if freq_hz >= last.1 {
+            return last.0;
+        }
+        let i = self
+            .points
+            .windows(2)
+            .position(|pair| freq_hz <= pair[1].1)
+            .unwrap_or(self.points.len() - 2);
+        let ((v0, f0), (v1, f1)) = (self.points[i], self.points[i + 1]);
+        if f1 > f0 {
+            v0 + (v1 - v0) * (freq_hz - f0) / (f1 - f0)
+        } else {
+            v0
+        }
+    }
+
+    /// Frequency range covered by the curve
+    pub fn range_hz(&self) -> (f64, f64) {
+        (self.points[0].1, self.points[self.points.len() - 1].1)
+    }
+}
+
+/// Behavioral simulation setup
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BehavioralConfig {
+    /// Reference oscillator frequency (Hz)
+    pub ref_freq_hz: f64,
+    /// Reference divider R
+    pub r_divider: u32,
+    /// Feedback divider (integer or MASH fractional)
+    pub divider: DividerConfig,
+    pub charge_pump: ChargePumpModel,
+    pub loop_filter: LoopFilterDesign,
+    pub vco: VcoTuningCurve,
+    /// VCO frequency at t = 0 (the loop filter starts at the matching voltage)
+    pub start_freq_hz: f64,
+    /// Simulated time (s)
+    pub sim_time_s: f64,
+    /// Frequency error accepted as locked (Hz)
+    pub lock_tolerance_hz: f64,
+}
+
+impl BehavioralConfig {
+    /// Simulate an existing design, stepping from the low end of its band to the centre
+    ///
+    /// An external VCO uses its datasheet tuning range; the integrated one the
+    /// constant-gain model of `from_requirements`.
+    pub fn from_design(design: &PLLDesign, charge_pump: ChargePumpModel) -> Self {
+        let mut config = Self::from_requirements(
+            &design.requirements,
+            design.divider_r,
+            design.divider_n.clone(),
+            charge_pump,
+            design.loop_filter.clone(),
+            design.vco_gain_mhz_per_v * 1e6,
+        );
+        if let Some(vco) = &design.vco {
+            config.vco = VcoTuningCurve::from_vco_spec(vco);
+        }
+        config
+    }
+
+    /// Step from the low end of the requested band to its centre
+    ///
+    /// The VCO is a constant-gain model; its tuning range is widened past the
+    /// supply when the gain alone cannot cover the requested band.
+    pub fn from_requirements(
+        req: &PLLRequirements,
+        r_divider: u32,
+        divider: DividerConfig,
+        charge_pump: ChargePumpModel,
+        loop_filter: LoopFilterDesign,
+        kvco_hz_per_v: f64,
+    ) -> Self {
+        let center = (req.output_freq_min_hz + req.output_freq_max_hz) / 2.0;
+        let v_mid = req.supply_voltage / 2.0;
+        let half_span =
+            v_mid.max(2.0 * (req.output_freq_max_hz - req.output_freq_min_hz) / kvco_hz_per_v);
+
+        Self {
+            ref_freq_hz: req.ref_freq_hz,
+            r_divider,
+            divider,
+            charge_pump,
+            loop_filter,
+            vco: VcoTuningCurve::linear(
+                center,
+                kvco_hz_per_v,
+                v_mid - half_span,
+                v_mid + half_span,
+            ),
+            start_freq_hz: req.output_freq_min_hz,
+            sim_time_s: 500e-6,
+            lock_tolerance_hz: center * 1e-3,
+        }
+    }
+
+    /// Phase detector comparison frequency (Hz)
+    pub fn pfd_freq_hz(&self) -> f64 {
+        self.ref_freq_hz / self.r_divider.max(1) as f64
+    }
+
+    /// Average feedback division ratio
+    pub fn n_effective(&self) -> f64 {
+        match &self.divider {
+            DividerConfig::IntegerN { n, .. } => *n as f64,
+            DividerConfig::FractionalN {
+                n_int,
+                n_frac,
+                modulus,
+                ..
+            } => *n_int as f64 + *n_frac as f64 / *modulus as f64,
+        }
+    }
+
+    /// Frequency the loop should settle at (Hz)
+    pub fn target_freq_hz(&self) -> f64 {
+        self.pfd_freq_hz() * self.n_effective()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.ref_freq_hz <= 0.0 || self.r_divider == 0 {
+            return Err("Reference frequency and R divider must be positive".to_string());
+        }
+        match &self.divider {
+            DividerConfig::IntegerN { n, .. } if *n == 0 => {
+                return Err("N divider must be positive".to_string())
+            }
+            DividerConfig::FractionalN {
+                n_int,
+                n_frac,
+                modulus,
+                modulator_order,
+            } => {
+                if *modulus == 0 || n_frac >= modulus {
+                    return Err(format!(
+                        "Fractional word {}/{} must be below the modulus",
+                        n_frac, modulus
+                    ));
+                }
+                // The MASH output swings the ratio down by up to 2^(order-1) - 1
+                let swing = (1u32 << (modulator_order.clamp(&1, &3) - 1)) - 1;
+                if *n_int <= swing {
+                    return Err(format!(
+                        "N integer {} too small for a order-{} modulator",
+                        n_int, modulator_order
+                    ));
+                }
+            }
+            _ => {}
+        }
+        if self.charge_pump.current_a <= 0.0 || self.charge_pump.mismatch.abs() >= 2.0 {
+            return Err("Charge pump current must be positive with |mismatch| < 2".to_string());
+        }
+        if self.charge_pump.reset_delay_s < 0.0 || self.charge_pump.dead_zone_s < 0.0 {
+            return Err("PFD delays must be non-negative".to_string());
+        }
+        self.vco.validate()?;
+        LoopLadder::from_design(&self.loop_filter)?;
+        if self.sim_time_s <= 0.0 || self.start_freq_hz <= 0.0 || self.lock_tolerance_hz <= 0.0 {
+            return Err(
+                "Simulation time, start frequency and lock tolerance must be positive".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Behavioral simulation outcome
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BehavioralResult {
+    /// Per-divider-cycle frequency and phase error, downsampled
+    pub transient: TransientResult,
+    /// Tuning voltage at the `transient.time_s` points (V)
+    pub vtune_v: Vec<f64>,
+    pub target_freq_hz: f64,
+    pub final_freq_hz: f64,
+    pub locked: bool,
+    /// Time after which the frequency stays within tolerance (µs)
+    pub lock_time_us: f64,
+    /// Edges dropped by the PFD because its flip-flop was already set
+    pub cycle_slips: u32,
+    /// Mean PFD phase error while locked (degrees, positive = reference leads)
+    pub static_phase_offset_deg: f64,
+    /// Spur at the PFD frequency offset (dBc), once locked
+    pub reference_spur_dbc: Option<f64>,
+    /// Offset of the nearest fractional spur (Hz), fractional-N only
+    pub fractional_spur_offset_hz: Option<f64>,
+    /// Level of the nearest fractional spur (dBc), when the window resolves it
+    pub fractional_spur_dbc: Option<f64>,
+}
+
+// ============================================================================
+// LOOP FILTER LADDER
+// ============================================================================
+
+/// Passive ladder: C1 in series with R1 and C2 at the pump node, then optional
+/// R2-C3 and R3-C4 pole sections. State is [V(C1), V(C2), V(C3), V(C4)].
+#[derive(Clone, Copy, Debug)]
+struct LoopLadder {
+    order: usize,
+    r: [f64; 3],
+    c: [f64; 4],
+}
+
+impl LoopLadder {
+    fn from_design(filter: &LoopFilterDesign) -> Result<Self, String> {
+        let order = match filter.topology {
+            LoopFilterTopology::PassiveSecondOrder => 2,
+            LoopFilterTopology::PassiveThirdOrder => 3,
+            LoopFilterTopology::PassiveFourthOrder => 4,
+        };
+        let missing = |what: &str| format!("{:?} loop filter is missing {}", filter.topology, what);
+
+        let mut r = [filter.r1_ohms, f64::INFINITY, f64::INFINITY];
+        let mut c = [filter.c1_pf * 1e-12, filter.c2_pf * 1e-12, 0.0, 0.0];
+        if order >= 3 {
+            r[1] = filter.r2_ohms.ok_or_else(|| missing("R2"))?;
+            c[2] = filter.c3_pf.ok_or_else(|| missing("C3"))? * 1e-12;
+        }
+        if order >= 4 {
+            r[2] = filter.r3_ohms.ok_or_else(|| missing("R3"))?;
+            c[3] = filter.c4_pf.ok_or_else(|| missing("C4"))? * 1e-12;
+        }
+        let used_r = &r[..order - 1];
+        let used_c = &c[..order];
+        if used_r
+            .iter()
+            .chain(used_c)
+            .any(|v| !(v.is_finite() && *v > 0.0))
+        {
+            return Err("Loop filter components must be positive".to_string());
+        }
+
+        Ok(Self { order, r, c })
+    }
+
+    /// Fastest time constant, bounding the RK4 step
+    fn min_time_constant(&self) -> f64 {
+        let series = |a: f64, b: f64| a * b / (a + b);
+        let mut tau = self.r[0] * series(self.c[0], self.c[1]);
+        if self.order >= 3 {
+            tau = tau.min(self.r[1] * series(self.c[1], self.c[2]));
+        }
+        if self.order >= 4 {
+            tau = tau.min(self.r[2] * series(self.c[2], self.c[3]));
+        }
+        tau
+    }
+
+    fn tune_voltage(&self, x: &[f64; 4]) -> f64 {
+        x[self.order - 1]
+    }
+
+    fn derivative(&self, x: &[f64; 4], i_cp: f64) -> [f64; 4] {
+        let i_r1 = (x[1] - x[0]) / self.r[0];
+        let i_r2 = if self.order >= 3 {
+            (x[1] - x[2]) / self.r[1]
+        } else {
+            0.0
+        };
+        let i_r3 = if self.order >= 4 {
+            (x[2] - x[3]) / self.r[2]
+        } else {
+            0.0
+        };
+
+        let mut dx = [0.0; 4];
+        dx[0] = i_r1 / self.c[0];
+        dx[1] = (i_cp - i_r1 - i_r2) / self.c[1];
+        if self.order >= 3 {
+            dx[2] = (i_r2 - i_r3) / self.c[2];
+        }
+        if self.order >= 4 {
+            dx[3] = i_r3 / self.c[3];
+        }
+        dx
+    }
+
+    /// One RK4 step of length h with constant pump current
+    fn step(&self, x: &[f64; 4], i_cp: f64, h: f64) -> [f64; 4] {
+        let offset = |base: &[f64; 4], k: &[f64; 4], scale: f64| {
+            let mut out = *base;
+            for (o, d) in out.iter_mut().zip(k) {
+                *o += d * scale;
+            }
+            out
+        };
+        let k1 = self.derivative(x, i_cp);
+        let k2 = self.derivative(&offset(x, &k1, h / 2.0), i_cp);
+        let k3 = self.derivative(&offset(x, &k2, h / 2.0), i_cp);
+        let k4 = self.derivative(&offset(x, &k3, h), i_cp);
+
+        let mut out = *x;
+        for i in 0..4 {
+            out[i] += h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+        }
+        out
+    }
+}
+
+// ============================================================================
+// SIMULATION
+// ============================================================================
+
+/// Feedback divider sequence
+enum DividerSequence {
+    Integer(u32),
+    Mash {
+        n_int: u32,
+        modulator: MashModulator,
+    },
+}
+
+impl DividerSequence {
+    fn new(config: &DividerConfig) -> Self {
+        match config {
+            DividerConfig::IntegerN { n, .. } => Self::Integer(*n),
+            DividerConfig::FractionalN {
+                n_int,
+                n_frac,
+                modulus,
+                modulator_order,
+            } => Self::Mash {
+                n_int: *n_int,
+                modulator: MashModulator::new(*modulator_order, *n_frac, *modulus),
+            },
+        }
+    }
+
+    fn next_ratio(&mut self) -> f64 {
+        match self {
+            Self::Integer(n) => *n as f64,
+            Self::Mash { n_int, modulator } => {
+                (*n_int as i64 + modulator.next_offset() as i64) as f64
+            }
+        }
+    }
+}
+
+/// One divider cycle as seen at the feedback edge
+struct CycleRecord {
+    time_s: f64,
+    freq_hz: f64,
+    phase_error_deg: f64,
+    vtune_v: f64,
+}
+
+/// Run the event-driven simulation
+pub fn simulate_behavioral(config: &BehavioralConfig) -> Result<BehavioralResult, String> {
+    config.validate()?;
+    let ladder = LoopLadder::from_design(&config.loop_filter)?;
+    let cp = &config.charge_pump;
+    let vco = &config.vco;
+
+    let f_pfd = config.pfd_freq_hz();
+    let t_pfd = 1.0 / f_pfd;
+    let t_end = config.sim_time_s;
+    let target = config.target_freq_hz();
+    let h_max = (ladder.min_time_constant() / 5.0).min(t_pfd / 4.0);
+
+    // Uniform tuning-voltage sampling for the spur estimate (tail of the run only)
+    let sample_dt = t_pfd / SAMPLES_PER_PFD;
+    let total_samples = (t_end / sample_dt) as u64;
+    let mut sample_index = total_samples.saturating_sub(MAX_SPUR_SAMPLES);
+    let mut samples: Vec<(f64, f64)> = Vec::with_capacity((total_samples - sample_index) as usize);
+
+    let mut divider = DividerSequence::new(&config.divider);
+    let mut n_current = divider.next_ratio();
+
+    let v0 = vco.voltage_for(config.start_freq_hz);
+    let mut x = [v0; 4];
+    let mut t = 0.0;
+    let mut vco_cycles = 0.0;
+
+    let mut ref_index = 1u64;
+    let mut next_ref = t_pfd;
+    let mut up: Option<f64> = None;
+    let mut dn: Option<f64> = None;
+    let mut reset_at: Option<f64> = None;
+    let mut last_div_edge = 0.0;
+    let mut phase_error_deg = 0.0;
+    let mut cycle_slips = 0u32;
+    let mut records: Vec<CycleRecord> = Vec::new();
+
+    for _ in 0..MAX_STEPS {
+        if t >= t_end {
+            break;
+        }
+
+        // Next scheduled event bounds the step
+        let mut t_next = t_end.min(next_ref).min(t + h_max);
+        if let Some(r) = reset_at {
+            t_next = t_next.min(r);
+        }
+        for edge in [up, dn].into_iter().flatten() {
+            if edge + cp.dead_zone_s > t {
+                t_next = t_next.min(edge + cp.dead_zone_s);
+            }
+        }
+        let next_sample = sample_index as f64 * sample_dt;
+        if sample_index < total_samples {
+            t_next = t_next.min(next_sample);
+        }
+
+        let conducting = |edge: Option<f64>| edge.is_some_and(|e| t >= e + cp.dead_zone_s);
+        let mut i_cp = -cp.leakage_a;
+        if conducting(up) {
+            i_cp += cp.up_current();
+        }
+        if conducting(dn) {
+            i_cp -= cp.down_current();
+        }
+
+        let h = t_next - t;
+        let f0 = vco.frequency(ladder.tune_voltage(&x));
+        let x_next = ladder.step(&x, i_cp, h);
+        let f1 = vco.frequency(ladder.tune_voltage(&x_next));
+        let cycles = 0.5 * (f0 + f1) * h;
+        let remaining = n_current - vco_cycles;
+
+        if cycles >= remaining && h > 0.0 {
+            // Divider edge inside the step: solve f0·τ + (f1-f0)/(2h)·τ² = remaining
+            let a = (f1 - f0) / (2.0 * h);
+            let tau = if a.abs() * h < 1e-9 * f0 {
+                remaining / f0
+            } else {
+                (-f0 + (f0 * f0 + 4.0 * a * remaining).max(0.0).sqrt()) / (2.0 * a)
+            }
+            .clamp(0.0, h);
+
+            x = ladder.step(&x, i_cp, tau);
+            t += tau;
+            vco_cycles = 0.0;
+
+            records.push(CycleRecord {
+                time_s: t,
+                freq_hz: n_current / (t - last_div_edge),
+                phase_error_deg,
+                vtune_v: ladder.tune_voltage(&x),
+            });
+            last_div_edge = t;
+            n_current = divider.next_ratio();
+
+            if dn.is_some() {
+                cycle_slips += 1;
+            } else {
+                dn = Some(t);
+                if let Some(t_up) = up {
+                    phase_error_deg = (t - t_up) * f_pfd * 360.0;
+                    reset_at = Some(t + cp.reset_delay_s);
+                }
+            }
+            continue;
+        }
+
+        x = x_next;
+        vco_cycles += cycles;
+        t = t_next;
+
+        if reset_at == Some(t) {
+            up = None;
+            dn = None;
+            reset_at = None;
+        }
+        if t == next_ref {
+            ref_index += 1;
+            next_ref = ref_index as f64 * t_pfd;
+            if up.is_some() {
+                cycle_slips += 1;
+            } else {
+                up = Some(t);
+                if let Some(t_dn) = dn {
+                    phase_error_deg = (t_dn - t) * f_pfd * 360.0;
+                    reset_at = Some(t + cp.reset_delay_s);
+                }
+            }
+        }
+        if sample_index < total_samples && t == next_sample {
+            samples.push((t, ladder.tune_voltage(&x)));
+            sample_index += 1;
+        }
+    }
+    if t < t_end {
+        return Err(format!(
+            "Behavioral simulation exceeded {} steps at t = {:.3e} s",
+            MAX_STEPS, t
+        ));
+    }
+    if records.is_empty() {
+        return Err("No divider edges in the simulated interval".to_string());
+    }
+
+    // Lock: first cycle after the last one outside the frequency tolerance
+    let last_unlocked = records
+        .iter()
+        .rposition(|r| (r.freq_hz - target).abs() > config.lock_tolerance_hz);
+    let lock_index = last_unlocked.map_or(0, |i| i + 1);
+    let locked = records.len() - lock_index >= MIN_LOCKED_CYCLES;
+    let lock_time_s = if locked {
+        records[lock_index].time_s
+    } else {
+        t_end
+    };
+
+    let locked_records = &records[lock_index..];
+    let static_phase_offset_deg = if locked {
+        locked_records
+            .iter()
+            .map(|r| r.phase_error_deg)
+            .sum::<f64>()
+            / locked_records.len() as f64
+    } else {
+        0.0
+    };
+
+    let overshoot_percent = overshoot(&records, config.start_freq_hz, target);
+
+    // Spurs from the locked tail of the tuning voltage
+    let window: Vec<(f64, f64)> = samples
+        .into_iter()
+        .filter(|(ts, _)| *ts >= lock_time_s)
+        .collect();
+    let window_s = window.last().map_or(0.0, |l| l.0) - window.first().map_or(0.0, |f| f.0);
+    let enough = locked && window_s >= MIN_LOCKED_CYCLES as f64 * t_pfd;
+    let reference_spur_dbc = if enough {
+        Some(spur_dbc(&window, f_pfd, vco))
+    } else {
+        None
+    };
+
+    let fractional_spur_offset_hz = match &config.divider {
+        DividerConfig::FractionalN {
+            n_frac, modulus, ..
+        } if *n_frac > 0 => {
+            let eps = *n_frac as f64 / *modulus as f64;
+            Some(f_pfd * eps.min(1.0 - eps))
+        }
+        _ => None,
+    };
+    // Resolving a tone at f needs several of its periods inside the window
+    let fractional_spur_dbc = fractional_spur_offset_hz
+        .filter(|f| enough && window_s * f >= 4.0)
+        .map(|f| spur_dbc(&window, f, vco));
+
+    // Downsample the per-cycle records for plotting
+    let stride = records.len().div_ceil(MAX_TRACE_POINTS).max(1);
+    let trace: Vec<&CycleRecord> = records.iter().step_by(stride).collect();
+
+    Ok(BehavioralResult {
+        transient: TransientResult {
+            time_s: trace.iter().map(|r| r.time_s).collect(),
+            freq_hz: trace.iter().map(|r| r.freq_hz).collect(),
+            phase_error_deg: trace.iter().map(|r| r.phase_error_deg).collect(),
+            lock_time_us: lock_time_s * 1e6,
+            overshoot_percent,
+        },
+        vtune_v: trace.iter().map(|r| r.vtune_v).collect(),
+        target_freq_hz: target,
+        final_freq_hz: records[records.len() - 1].freq_hz,
+        locked,
+        lock_time_us: lock_time_s * 1e6,
+        cycle_slips,
+        static_phase_offset_deg,
+        reference_spur_dbc,
+        fractional_spur_offset_hz,
+        fractional_spur_dbc,
+    })
+}
+
+/// Peak excursion past the target relative to the step size (%)
+fn overshoot(records: &[CycleRecord], start_hz: f64, target_hz: f64) -> f64 {
+    let step = (target_hz - start_hz).abs();
+    if step == 0.0 {
+        return 0.0;
+    }
+    let excursion = if target_hz >= start_hz {
+        records
+            .iter()
+            .map(|r| r.freq_hz - target_hz)
+            .fold(0.0, f64::max)
+    } else {
+        records
+            .iter()
+            .map(|r| target_hz - r.freq_hz)
+            .fold(0.0, f64::max)
+    };
+    excursion / step * 100.0
+}
+
+/// Narrowband-FM spur level from the tuning-voltage tone at `freq_hz`
+///
+/// The tone amplitude comes from a Hann-windowed single-bin DFT.
+fn spur_dbc(samples: &[(f64, f64)], freq_hz: f64, vco: &VcoTuningCurve) -> f64 {
+    let n = samples.len();
+    if n < 2 {
+        return SPUR_FLOOR_DBC;
+    }
+    let mean = samples.iter().map(|s| s.1).sum::<f64>() / n as f64;
+
+    let (mut re, mut im, mut w_sum) = (0.0, 0.0, 0.0);
+    for (k, (ts, v)) in samples.iter().enumerate() {
+        let w = 0.5 - 0.5 * (2.0 * PI * k as f64 / (n - 1) as f64).cos();
+        let arg = 2.0 * PI * freq_hz * ts;
+        re += w * (v - mean) * arg.cos();
+        im -= w * (v - mean) * arg.sin();
+        w_sum += w;
+    }
+    let amplitude = 2.0 * (re * re + im * im).sqrt() / w_sum;
+
+    let beta = vco.gain_at(mean).abs() * amplitude / freq_hz;
+    if beta > 0.0 {
+        (20.0 * (beta / 2.0).log10()).max(SPUR_FLOOR_DBC)
+    } else {
+        SPUR_FLOOR_DBC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pll::loop_filter::{
+        add_fourth_order_section, create_loop_filter_design, design_passive_fourth_order,
+        design_passive_third_order,
+    };
+
+    // 10 MHz reference, N = 100, 1 GHz output, 100 kHz bandwidth, 20 MHz/V VCO
+    const KVCO: f64 = 20e6;
+    const ICP: f64 = 1e-3;
+
+    fn third_order_filter() -> LoopFilterDesign {
+        let (c1, r1, c2, r2, c3) = design_passive_third_order(
+            ICP / (2.0 * PI),
+            KVCO * 2.0 * PI,
+            100.0,
+            2.0 * PI * 100e3,
+            50.0,
+        );
+        create_loop_filter_design(c1, r1, c2, Some(c3), Some(r2))
+    }
+
+    fn base_config() -> BehavioralConfig {
+        BehavioralConfig {
+            ref_freq_hz: 10e6,
+            r_divider: 1,
+            divider: DividerConfig::IntegerN {
+                n: 100,
+                prescaler: None,
+            },
+            charge_pump: ChargePumpModel::ideal(ICP),
+            loop_filter: third_order_filter(),
+            vco: VcoTuningCurve::linear(1e9, KVCO, 0.0, 5.0),
+            start_freq_hz: 990e6,
+            sim_time_s: 150e-6,
+            lock_tolerance_hz: 10e3,
+        }
+    }
+
+    #[test]
+    fn test_tuning_curve_interpolation() {
+        let curve =
+            VcoTuningCurve::from_points(vec![(0.0, 900e6), (2.0, 1000e6), (4.0, 1050e6)]).unwrap();
+        assert!((curve.frequency(1.0) - 950e6).abs() < 1.0);
+        assert!((curve.frequency(5.0) - 1050e6).abs() < 1.0); // clamped at the rail
+        assert!((curve.voltage_for(1025e6) - 3.0).abs() < 1e-9);
+        assert!((curve.gain_at(3.0) - 25e6).abs() < 1.0);
+
+        assert!(VcoTuningCurve::from_points(vec![(1.0, 1e9), (0.5, 2e9)]).is_err());
+    }
+
+    #[test]
+    fn test_integer_n_locks() {
+        let result = simulate_behavioral(&base_config()).unwrap();
+
+        assert!(result.locked, "lock time {} µs", result.lock_time_us);
+        assert!(result.lock_time_us < 100.0);
+        assert!((result.final_freq_hz - 1e9).abs() < 10e3);
+        assert_eq!(result.cycle_slips, 0);
+        assert!(result.fractional_spur_offset_hz.is_none());
+        // Ideal pump: matched currents and no leakage leave almost no ripple
+        assert!(result.reference_spur_dbc.unwrap() < -100.0);
+    }
+
+    #[test]
+    fn test_leakage_raises_reference_spur() {
+        let ideal = simulate_behavioral(&base_config()).unwrap();
+
+        let mut config = base_config();
+        config.charge_pump.leakage_a = 100e-9;
+        config.charge_pump.mismatch = 0.1;
+        let leaky = simulate_behavioral(&config).unwrap();
+
+        assert!(leaky.locked);
+        assert!(leaky.reference_spur_dbc.unwrap() > ideal.reference_spur_dbc.unwrap() + 20.0);
+        // Net pump charge per cycle must be cancelled by a static phase offset
+        assert!(leaky.static_phase_offset_deg.abs() > ideal.static_phase_offset_deg.abs());
+    }
+
+    #[test]
+    fn test_large_step_slips_cycles() {
+        let mut config = base_config();
+        config.vco = VcoTuningCurve::linear(1e9, KVCO, -5.0, 10.0);
+        config.start_freq_hz = 900e6;
+        config.sim_time_s = 300e-6;
+        let result = simulate_behavioral(&config).unwrap();
+
+        // A 10% step is far outside the loop bandwidth: the PFD must slip to pull in
+        assert!(result.cycle_slips > 0);
+        assert!(result.locked);
+        assert!(result.lock_time_us > simulate_behavioral(&base_config()).unwrap().lock_time_us);
+    }
+
+    #[test]
+    fn test_fractional_n_mash_divider() {
+        let mut config = base_config();
+        // N = 100.25 with a 3rd order MASH; 2.5 MHz fractional offset
+        config.divider = DividerConfig::FractionalN {
+            n_int: 100,
+            n_frac: 1 << 22,
+            modulus: 1 << 24,
+            modulator_order: 3,
+        };
+        config.start_freq_hz = 995e6;
+        config.charge_pump.mismatch = 0.05;
+        // MASH quantization noise keeps the per-cycle frequency moving by a few kHz
+        config.lock_tolerance_hz = 50e3;
+        let result = simulate_behavioral(&config).unwrap();
+
+        assert!(result.locked);
+        assert!((result.target_freq_hz - 1002.5e6).abs() < 1.0);
+        assert!((result.final_freq_hz - 1002.5e6).abs() < 50e3);
+        assert!((result.fractional_spur_offset_hz.unwrap() - 2.5e6).abs() < 1.0);
+        assert!(result.fractional_spur_dbc.is_some());
+    }
+
+    #[test]
+    fn test_fourth_order_filter_and_validation() {
+        let (c1, r1, c2, r2, c3, r3, c4) = design_passive_fourth_order(
+            ICP / (2.0 * PI),
+            KVCO * 2.0 * PI,
+            100.0,
+            2.0 * PI * 100e3,
+            50.0,
+        );
+        let mut config = base_config();
+        config.loop_filter = add_fourth_order_section(
+            create_loop_filter_design(c1, r1, c2, Some(c3), Some(r2)),
+            r3,
+            c4,
+        );
+        let result = simulate_behavioral(&config).unwrap();
+        assert!(result.locked);
+
+        let mut broken = base_config();
+        broken.loop_filter.topology = LoopFilterTopology::PassiveFourthOrder;
+        assert!(simulate_behavioral(&broken).is_err());
+    }
+}
@@ -269,4 +269,48 @@ mod tests {
         // First frequency should be around 1 kHz
         assert!(bode.frequencies_hz[0] >= 1e3);
     }
+
+    #[test]
+    fn test_loop_filter_noise() {
+        let requirements = PLLRequirements {
+            ref_freq_hz: 10e6,
+            output_freq_min_hz: 2.4e9,
+            output_freq_max_hz: 2.5e9,
+            loop_bandwidth_hz: 100e3,
+            phase_margin_deg: 45.0,
+            architecture: PLLArchitecture::IntegerN,
+            supply_voltage: 3.3,
+        };
+
+        let design = design_pll(&requirements).unwrap();
+        let netlist = build_pll_netlist(&design);
+        let options = NoiseOptions {
+            sources: vec![NoiseSource::white("G_pfd_cp", 1e-12)],
+            ..NoiseOptions::default()
+        };
+        let noise = noise_analysis_with(
+            &netlist,
+            "filter_out",
+            Some("V_phase"),
+            1.0,
+            1e10,
+            20,
+            &options,
+        )
+        .unwrap();
+
+        // R1 sits in the C1-R1-C2 loop: <V²> = kT·C1 / (C2·(C1 + C2))
+        let c1 = design.loop_filter.c1_pf * 1e-12;
+        let c2 = design.loop_filter.c2_pf * 1e-12;
+        let r1_vrms = noise
+            .integrated_contribution("R1", 0.0, f64::INFINITY)
+            .unwrap();
+        let expected = (BOLTZMANN * NOMINAL_TEMPERATURE * c1 / (c2 * (c1 + c2))).sqrt();
+        assert!((r1_vrms / expected - 1.0).abs() < 0.02);
+
+        // Charge-pump noise dominates inside the loop bandwidth
+        let cp = &noise.contribution("G_pfd_cp").unwrap().psd;
+        assert!(cp[0] > noise.contribution("R1").unwrap().psd[0]);
+        assert!(noise.gain.as_ref().unwrap()[0] > 0.0);
+    }
 }
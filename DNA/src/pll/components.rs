@@ -64,7 +64,7 @@ pub struct PFDSpec {
 }
 
 /// Complete PLL IC specification (integrated PFD + VCO)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct PLLICSpec {
     /// Manufacturer part number
     pub part_number: String,
@@ -76,6 +76,10 @@ pub struct PLLICSpec {
     pub freq_max_hz: f64,
     /// Maximum reference frequency (Hz)
     pub max_ref_freq_hz: f64,
+    /// Maximum phase detector frequency (Hz)
+    pub max_pfd_freq_hz: f64,
+    /// Dual-modulus prescaler options P (divides by P/P+1 ahead of the N counter)
+    pub prescalers: Vec<u32>,
     /// Charge pump current options (mA)
     pub icp_options_ma: Vec<f64>,
     /// VCO gain (MHz/V)
@@ -167,6 +171,8 @@ pub fn get_pll_ic_library() -> Vec<PLLICSpec> {
             freq_min_hz: 35e6,
             freq_max_hz: 4.4e9,
             max_ref_freq_hz: 250e6,
+            max_pfd_freq_hz: 32e6,
+            prescalers: vec![4, 8],
             icp_options_ma: vec![
                 0.31, 0.63, 0.94, 1.25, 1.56, 1.88, 2.19, 2.5, 2.81, 3.13, 3.44, 3.75, 4.06, 4.38,
                 4.69, 5.0,
@@ -184,6 +190,8 @@ pub fn get_pll_ic_library() -> Vec<PLLICSpec> {
             freq_min_hz: 137.5e6,
             freq_max_hz: 4.4e9,
             max_ref_freq_hz: 250e6,
+            max_pfd_freq_hz: 32e6,
+            prescalers: vec![4, 8],
             icp_options_ma: vec![
                 0.31, 0.63, 0.94, 1.25, 1.56, 1.88, 2.19, 2.5, 2.81, 3.13, 3.44, 3.75, 4.06, 4.38,
                 4.69, 5.0,
@@ -201,6 +209,8 @@ pub fn get_pll_ic_library() -> Vec<PLLICSpec> {
             freq_min_hz: 23.5e6,
             freq_max_hz: 6.0e9,
             max_ref_freq_hz: 200e6,
+            max_pfd_freq_hz: 50e6,
+            prescalers: vec![4, 8],
             icp_options_ma: vec![0.32, 0.64, 0.96, 1.28, 1.92, 2.56, 3.2, 3.84, 4.48, 5.12],
             kvco_mhz_per_v: 50.0,
             fractional_n: true,
@@ -215,6 +225,8 @@ pub fn get_pll_ic_library() -> Vec<PLLICSpec> {
             freq_min_hz: 10e6,
             freq_max_hz: 15e9,
             max_ref_freq_hz: 1.4e9,
+            max_pfd_freq_hz: 300e6,
+            prescalers: vec![2, 4],
             icp_options_ma: vec![0.625, 1.25, 1.875, 2.5, 3.125, 3.75, 4.375, 5.0, 6.25, 7.5],
             kvco_mhz_per_v: 25.0,
             fractional_n: true,
@@ -229,6 +241,8 @@ pub fn get_pll_ic_library() -> Vec<PLLICSpec> {
             freq_min_hz: 25e6,
             freq_max_hz: 6.0e9,
             max_ref_freq_hz: 350e6,
+            max_pfd_freq_hz: 100e6,
+            prescalers: vec![4, 8],
             icp_options_ma: vec![0.02, 0.04, 0.08, 0.16, 0.32, 0.64, 1.28, 2.56],
             kvco_mhz_per_v: 35.0,
             fractional_n: true,
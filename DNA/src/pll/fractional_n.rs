@@ -1,6 +1,6 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: fractional_n.rs | DNA/src/pll/fractional_n.rs
-//! PURPOSE: Provides 4 public functions for pll
+//! PURPOSE: Provides 4 public functions and the MASH modulator for pll
 //! MODIFIED: 2025-12-02
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
@@ -129,6 +129,74 @@ pub fn adjust_bandwidth_for_fractional(integer_n_bandwidth_hz: f64, modulator_or
     integer_n_bandwidth_hz * bandwidth_multiplier
 }
 
+/// MASH 1-1-1 sigma-delta modulator driving the fractional divider
+///
+/// Cascades up to three first-order accumulators and combines their carries
+/// through (1 - z⁻¹) differentiators, so the instantaneous divide ratio
+/// `n_int + next()` averages to `n_int + n_frac / modulus` while the
+/// quantization error is pushed towards half the PFD frequency.
+#[derive(Clone, Debug)]
+pub struct MashModulator {
+    order: u32,
+    n_frac: u64,
+    modulus: u64,
+    acc: [u64; 3],
+    // Previous carries of stage 2 and the last two carries of stage 3
+    c2_prev: i32,
+    c3_prev: [i32; 2],
+}
+
+impl MashModulator {
+    /// Create a modulator of order 1..=3 (other orders clamp to that range)
+    pub fn new(order: u32, n_frac: u32, modulus: u32) -> Self {
+        Self {
+            order: order.clamp(1, 3),
+            n_frac: n_frac as u64,
+            modulus: modulus.max(1) as u64,
+            acc: [0; 3],
+            c2_prev: 0,
+            c3_prev: [0; 2],
+        }
+    }
+
+    /// Modulator order actually in use
+    pub fn order(&self) -> u32 {
+        self.order
+    }
+
+    /// Next divide-ratio offset added to the integer part
+    ///
+    /// Order 1 yields {0, 1}, order 2 {-1..2} and order 3 {-3..4}.
+    pub fn next_offset(&mut self) -> i32 {
+        let mut carry = [0i32; 3];
+        let mut input = self.n_frac;
+        for (acc, c) in self
+            .acc
+            .iter_mut()
+            .zip(carry.iter_mut())
+            .take(self.order as usize)
+        {
+            *acc += input;
+            if *acc >= self.modulus {
+                *acc -= self.modulus;
+                *c = 1;
+            }
+            input = *acc;
+        }
+
+        let mut out = carry[0];
+        if self.order >= 2 {
+            out += carry[1] - self.c2_prev;
+            self.c2_prev = carry[1];
+        }
+        if self.order >= 3 {
+            out += carry[2] - 2 * self.c3_prev[0] + self.c3_prev[1];
+            self.c3_prev = [carry[2], self.c3_prev[0]];
+        }
+        out
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,4 +269,31 @@ mod tests {
         assert!(bw_order2 > bw_order3);
         assert!(bw_order3 >= base_bw * 2.0);
     }
+
+    #[test]
+    fn test_mash_average_and_range() {
+        for order in 1..=3 {
+            let mut mash = MashModulator::new(order, 8888, 1 << 16);
+            let cycles = 1 << 16;
+            let mut sum = 0i64;
+            let (mut lo, mut hi) = (i32::MAX, i32::MIN);
+            for _ in 0..cycles {
+                let y = mash.next_offset();
+                sum += y as i64;
+                lo = lo.min(y);
+                hi = hi.max(y);
+            }
+
+            // Over one full modulus period the carries sum to exactly n_frac
+            assert!((sum - 8888).abs() <= 3, "order {} sum {}", order, sum);
+            let span = 1 << (order - 1);
+            assert!(
+                lo >= 1 - span && hi <= span,
+                "order {}: {}..{}",
+                order,
+                lo,
+                hi
+            );
+        }
+    }
 }
@@ -1,6 +1,6 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: integer_n.rs | DNA/src/pll/integer_n.rs
-//! PURPOSE: Provides 4 public functions for pll
+//! PURPOSE: Provides 5 public functions for pll
 //! MODIFIED: 2025-12-09
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
@@ -63,6 +63,29 @@ pub fn create_integer_n_config(n: u32) -> DividerConfig {
     }
 }
 
+/// Highest input frequency the programmable N counter accepts (Hz)
+///
+/// ADF435x parts allow their 4/5 prescaler up to 3.6 GHz, i.e. ~900 MHz at the counter.
+pub const N_COUNTER_MAX_FREQ_HZ: f64 = 1e9;
+
+/// Pick the smallest usable dual-modulus prescaler
+///
+/// Direct counting is used when the VCO is slow enough. A P/P+1 prescaler
+/// only covers every ratio above P·(P−1), so `n_min` must reach that.
+///
+/// Returns `None` when no option works, `Some(None)` for direct counting.
+pub fn select_prescaler(vco_freq_hz: f64, n_min: u32, options: &[u32]) -> Option<Option<u32>> {
+    if vco_freq_hz <= N_COUNTER_MAX_FREQ_HZ {
+        return Some(None);
+    }
+    let mut sorted = options.to_vec();
+    sorted.sort_unstable();
+    sorted
+        .into_iter()
+        .find(|&p| vco_freq_hz / p as f64 <= N_COUNTER_MAX_FREQ_HZ && n_min >= p * (p - 1))
+        .map(Some)
+}
+
 /// Calculate actual output frequency achieved
 pub fn calculate_output_freq(ref_freq_hz: f64, r: u32, n: u32) -> f64 {
     let pfd_freq = ref_freq_hz / r as f64;
@@ -122,4 +145,15 @@ mod tests {
             _ => panic!("Expected IntegerN config"),
         }
     }
+
+    #[test]
+    fn test_select_prescaler() {
+        // Slow VCO: the counter runs directly
+        assert_eq!(select_prescaler(500e6, 50, &[4, 8]), Some(None));
+        // 2.45 GHz needs /4 ahead of the counter
+        assert_eq!(select_prescaler(2.45e9, 100, &[4, 8]), Some(Some(4)));
+        // 4.4 GHz needs /8, which only covers N ≥ 56
+        assert_eq!(select_prescaler(4.4e9, 100, &[4, 8]), Some(Some(8)));
+        assert_eq!(select_prescaler(4.4e9, 40, &[4, 8]), None);
+    }
 }
@@ -1,6 +1,6 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: loop_filter.rs | DNA/src/pll/loop_filter.rs
-//! PURPOSE: Provides 3 public functions for pll
+//! PURPOSE: Provides 5 public functions for pll
 //! MODIFIED: 2025-12-09
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
@@ -29,16 +29,17 @@ pub fn design_passive_second_order(
     // Convert phase margin to radians
     let pm_rad = phase_margin_deg * PI / 180.0;
 
-    // Calculate T1 and T2 time constants
+    // T1 is the pole (C2 with R1) and T2 the zero (R1 with C1) time constant
     let t1 = (1.0 / pm_rad.cos() - pm_rad.tan()) / omega_c;
     let t2 = 1.0 / (omega_c * omega_c * t1);
 
-    // Calculate C1 from loop gain requirement
-    let c1 = (k_phi * k_vco) / (n * omega_c * omega_c);
+    // C2 (shunt) from the loop gain requirement at crossover
+    let gain_correction = ((1.0 + (omega_c * t2).powi(2)) / (1.0 + (omega_c * t1).powi(2))).sqrt();
+    let c2 = (k_phi * k_vco) / (n * omega_c * omega_c) * (t1 / t2) * gain_correction;
 
-    // Calculate R1 and C2
-    let r1 = t1 / c1;
-    let c2 = t2 / r1;
+    // C1 (series, integrating) and R1 place the zero at T2
+    let c1 = c2 * (t2 / t1 - 1.0);
+    let r1 = t2 / c1;
 
     (c1, r1, c2)
 }
@@ -159,9 +160,64 @@ pub fn create_loop_filter_design(
         r1_ohms: r1_actual,
         c3_pf: c3_val.map(|v| v * 1e12),
         r2_ohms: r2_val,
+        c4_pf: None,
+        r3_ohms: None,
     }
 }
 
+/// Design a passive 4th order loop filter (second extra RC pole section)
+///
+/// The 4th pole sits above the 3rd at 15x the crossover frequency
+///
+/// Returns: (C1, R1, C2, R2, C3, R3, C4) in SI units
+pub fn design_passive_fourth_order(
+    k_phi: f64,
+    k_vco: f64,
+    n: f64,
+    omega_c: f64,
+    phase_margin_deg: f64,
+) -> (f64, f64, f64, f64, f64, f64, f64) {
+    let (c1, r1, c2, r2, c3) =
+        design_passive_third_order(k_phi, k_vco, n, omega_c, phase_margin_deg);
+
+    let omega_p4 = 15.0 * omega_c;
+    let c4 = c3 / 5.0;
+    let r3 = 1.0 / (omega_p4 * c4);
+
+    (c1, r1, c2, r2, c3, r3, c4)
+}
+
+/// Append the R3/C4 section to a 3rd order design, making it 4th order
+pub fn add_fourth_order_section(design: LoopFilterDesign, r3: f64, c4: f64) -> LoopFilterDesign {
+    let mut design = design;
+    let c4_actual = if c4 < 1e-9 {
+        c4
+    } else {
+        nearest_e96(c4 / 1e-9) * 1e-9
+    };
+    let r3_actual = nearest_e96(r3);
+
+    design.components.push(FilterComponent {
+        designator: "C4".to_string(),
+        value: c4,
+        actual_value: c4_actual,
+        unit: format_capacitance(c4_actual),
+        tolerance_pct: 10.0,
+    });
+    design.components.push(FilterComponent {
+        designator: "R3".to_string(),
+        value: r3,
+        actual_value: r3_actual,
+        unit: format_resistance(r3_actual),
+        tolerance_pct: 1.0,
+    });
+
+    design.topology = LoopFilterTopology::PassiveFourthOrder;
+    design.c4_pf = Some(c4_actual * 1e12);
+    design.r3_ohms = Some(r3_actual);
+    design
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,4 +271,23 @@ mod tests {
         assert_eq!(design.components[1].designator, "R1");
         assert_eq!(design.components[2].designator, "C2");
     }
+
+    #[test]
+    fn test_fourth_order_design() {
+        let omega_c = 2.0 * PI * 100e3;
+        let (c1, r1, c2, r2, c3, r3, c4) =
+            design_passive_fourth_order(1e-3, 10e6, 240.0, omega_c, 45.0);
+        assert!(c4 < c3);
+        // 4th pole above the 3rd
+        assert!(r3 * c4 < r2 * c3);
+
+        let design = add_fourth_order_section(
+            create_loop_filter_design(c1, r1, c2, Some(c3), Some(r2)),
+            r3,
+            c4,
+        );
+        assert_eq!(design.topology, LoopFilterTopology::PassiveFourthOrder);
+        assert_eq!(design.components.len(), 7);
+        assert!(design.c4_pf.is_some() && design.r3_ohms.is_some());
+    }
 }
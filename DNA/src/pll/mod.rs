@@ -5,21 +5,25 @@
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
+pub mod behavioral;
 pub mod circuit;
 pub mod components;
 pub mod fractional_n;
 pub mod integer_n;
 pub mod loop_filter;
 pub mod noise;
+pub mod planner;
 pub mod stability;
 pub mod transient;
 pub mod types;
 
-use std::f64::consts::PI;
-
 pub use types::*;
 
 /// Main entry point: Design a PLL from requirements
+///
+/// Searches R/N/prescaler plans across the PLL IC and VCO libraries (see
+/// `planner`), builds the best candidate with its parts' Kvco, charge pump and
+/// noise figures, and keeps every ranked candidate in `PLLDesign::candidates`.
 pub fn design_pll(requirements: &PLLRequirements) -> Result<PLLDesign, String> {
     // Validate requirements
     let validation = requirements.validate();
@@ -27,136 +31,58 @@ pub fn design_pll(requirements: &PLLRequirements) -> Result<PLLDesign, String> {
         return Err(format!("Invalid requirements: {:?}", validation.errors));
     }
 
-    // Use center frequency for design
-    let output_freq_hz = (requirements.output_freq_min_hz + requirements.output_freq_max_hz) / 2.0;
-
-    // Calculate dividers based on architecture
-    let (divider_n, pfd_freq_hz) = match requirements.architecture {
-        PLLArchitecture::IntegerN => {
-            let (r, n, pfd) =
-                integer_n::calculate_dividers(requirements.ref_freq_hz, output_freq_hz);
-            if r != 1 {
-                return Err(format!(
-                    "Reference divider R={} not supported (only R=1 currently)",
-                    r
-                ));
-            }
-            (integer_n::create_integer_n_config(n), pfd)
-        }
-        PLLArchitecture::FractionalN => {
-            // Use order-3 sigma-delta modulator for best noise performance
-            let modulator_order = 3;
-            let pfd = requirements.ref_freq_hz; // R=1 for simplicity
-            let (n_int, n_frac, modulus) =
-                fractional_n::calculate_fractional_divider(pfd, output_freq_hz, modulator_order);
-            (
-                fractional_n::create_fractional_n_config(n_int, n_frac, modulus, modulator_order),
-                pfd,
-            )
-        }
-    };
-
-    let n_effective = match &divider_n {
-        DividerConfig::IntegerN { n, .. } => *n as f64,
-        DividerConfig::FractionalN {
-            n_int,
-            n_frac,
-            modulus,
-            ..
-        } => *n_int as f64 + (*n_frac as f64 / *modulus as f64),
-    };
-
-    let r = 1; // Fixed for now
-
-    // Typical PLL IC parameters
-    let charge_pump_current_ua = 1000.0; // 1 mA
-    let k_phi = charge_pump_current_ua * 1e-6; // Convert to A
-
-    // Typical VCO gain (will be replaced with component selection later)
-    let vco_gain_mhz_per_v = 10.0;
-    let k_vco = vco_gain_mhz_per_v * 1e6 * 2.0 * PI; // Convert to rad/s/V
-
-    // Adjust loop bandwidth for fractional-N if needed
-    let loop_bandwidth_hz = match requirements.architecture {
-        PLLArchitecture::IntegerN => requirements.loop_bandwidth_hz,
-        PLLArchitecture::FractionalN => {
-            if let DividerConfig::FractionalN {
-                modulator_order, ..
-            } = &divider_n
-            {
-                fractional_n::adjust_bandwidth_for_fractional(
-                    requirements.loop_bandwidth_hz,
-                    *modulator_order,
-                )
-            } else {
-                requirements.loop_bandwidth_hz
-            }
-        }
+    // Rank every divider plan and part combination
+    let ranked = planner::search(requirements)?;
+    let candidates: Vec<DesignCandidate> = ranked.iter().map(|c| c.summary.clone()).collect();
+    let best = ranked
+        .into_iter()
+        .next()
+        .ok_or_else(|| "No PLL design candidates".to_string())?;
+    let plan = best.plan;
+    let evaluation = best.evaluation;
+
+    let mut design = PLLDesign {
+        requirements: requirements.clone(),
+        divider_r: plan.divider_r,
+        divider_n: plan.divider_n,
+        prescaler: plan.prescaler,
+        pfd_freq_hz: plan.pfd_freq_hz,
+        loop_filter: evaluation.loop_filter,
+        charge_pump_current_ua: evaluation.charge_pump_current_ua,
+        vco_gain_mhz_per_v: evaluation.vco_gain_mhz_per_v,
+        pll_ic: best.ic,
+        vco: best.vco,
+        performance: evaluation.performance,
+        bode_plot: evaluation.bode_plot,
+        phase_noise: evaluation.phase_noise,
+        transient: TransientResult::default(),
+        candidates,
     };
 
-    // Design loop filter
-    let omega_c = 2.0 * PI * loop_bandwidth_hz;
-    let (c1, r1, c2) = loop_filter::design_passive_second_order(
-        k_phi,
-        k_vco,
-        n_effective,
-        omega_c,
-        requirements.phase_margin_deg,
-    );
-
-    let loop_filter_design = loop_filter::create_loop_filter_design(c1, r1, c2, None, None);
-
-    // Generate Bode plot
-    let bode_plot = stability::generate_bode_plot(
-        k_phi,
-        k_vco,
-        n_effective,
-        loop_filter_design.r1_ohms,
-        loop_filter_design.c1_pf * 1e-12,
-        loop_filter_design.c2_pf * 1e-12,
-        1e3,                             // 1 kHz start
-        10.0 * requirements.ref_freq_hz, // 10x ref freq stop
-        50,                              // 50 points per decade
-    );
-
-    // Analyze stability
-    let performance = stability::analyze_stability(&bode_plot);
-
-    // Calculate Phase Noise
-    let phase_noise = noise::calculate_phase_noise(
-        &bode_plot,
-        n_effective,
-        requirements.ref_freq_hz,
-        output_freq_hz,
-    );
-
     // Run Transient Simulation
-    // Simulate a jump from min freq to center freq
-    let transient = transient::simulate_step_response(
-        k_phi,
-        vco_gain_mhz_per_v * 1e6, // Hz/V for transient sim
-        n_effective,
-        loop_filter_design.r1_ohms,
-        loop_filter_design.c1_pf * 1e-12,
-        loop_filter_design.c2_pf * 1e-12,
-        requirements.output_freq_min_hz,
-        output_freq_hz,
-        500e-6, // 500 us simulation
+    // Step from min freq to center freq through the event-driven model, falling
+    // back to the averaged model if the behavioral run cannot complete
+    let k_phi = design.charge_pump_current_ua * 1e-6; // A
+    let sim_config = behavioral::BehavioralConfig::from_design(
+        &design,
+        behavioral::ChargePumpModel::ideal(k_phi),
     );
+    design.transient = match behavioral::simulate_behavioral(&sim_config) {
+        Ok(result) => result.transient,
+        Err(_) => transient::simulate_step_response(
+            k_phi,
+            design.vco_gain_mhz_per_v * 1e6, // Hz/V for transient sim
+            plan.n_effective,
+            design.loop_filter.r1_ohms,
+            design.loop_filter.c1_pf * 1e-12,
+            design.loop_filter.c2_pf * 1e-12,
+            requirements.output_freq_min_hz,
+            plan.output_freq_hz,
+            500e-6, // 500 us simulation
+        ),
+    };
 
-    Ok(PLLDesign {
-        requirements: requirements.clone(),
-        divider_r: r,
-        divider_n,
-        pfd_freq_hz,
-        loop_filter: loop_filter_design,
-        charge_pump_current_ua,
-        vco_gain_mhz_per_v,
-        performance,
-        bode_plot,
-        phase_noise,
-        transient,
-    })
+    Ok(design)
 }
 
 #[cfg(test)]
@@ -181,6 +107,60 @@ mod tests {
         let design = result.unwrap();
         assert_eq!(design.divider_r, 1);
         assert!(design.performance.phase_margin_deg > 0.0);
+        // Behavioral step from the band edge settles inside the 500 µs run
+        assert!(design.transient.lock_time_us < 500.0);
+        // Parts come from the libraries rather than fixed defaults
+        assert_eq!(design.candidates[0].pll_ic, design.pll_ic.part_number);
+        assert!(design
+            .pll_ic
+            .icp_options_ma
+            .contains(&(design.charge_pump_current_ua / 1e3)));
+    }
+
+    #[test]
+    fn test_design_without_part_fields_deserializes() {
+        let requirements = PLLRequirements {
+            ref_freq_hz: 10e6,
+            output_freq_min_hz: 2.4e9,
+            output_freq_max_hz: 2.5e9,
+            loop_bandwidth_hz: 100e3,
+            phase_margin_deg: 45.0,
+            architecture: PLLArchitecture::IntegerN,
+            supply_voltage: 3.3,
+        };
+        let design = design_pll(&requirements).unwrap();
+
+        // Designs saved before part selection lack these fields
+        let mut json = serde_json::to_value(&design).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        for key in ["prescaler", "pll_ic", "vco", "candidates"] {
+            fields.remove(key);
+        }
+        let old: PLLDesign = serde_json::from_value(json).unwrap();
+        assert_eq!(old.divider_r, design.divider_r);
+        assert!(old.prescaler.is_none() && old.vco.is_none());
+        assert!(old.pll_ic.part_number.is_empty());
+        assert!(old.candidates.is_empty());
+    }
+
+    #[test]
+    fn test_design_pll_reference_divider() {
+        // 26 MHz reference needs R = 13 for an exact integer-N 2.45 GHz
+        let requirements = PLLRequirements {
+            ref_freq_hz: 26e6,
+            output_freq_min_hz: 2.4e9,
+            output_freq_max_hz: 2.5e9,
+            loop_bandwidth_hz: 100e3,
+            phase_margin_deg: 45.0,
+            architecture: PLLArchitecture::IntegerN,
+            supply_voltage: 3.3,
+        };
+
+        let design = design_pll(&requirements).unwrap();
+        assert_eq!(design.divider_r, 13);
+        assert!((design.pfd_freq_hz - 2e6).abs() < 1e-6);
+        assert_eq!(design.prescaler, Some(4));
+        assert!(design.candidates.len() > 1);
     }
 
     #[test]
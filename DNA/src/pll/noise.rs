@@ -1,19 +1,87 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: noise.rs | DNA/src/pll/noise.rs
-//! PURPOSE: Provides 1 public functions for pll
+//! PURPOSE: Provides 2 public functions and part noise sources for pll
 //! MODIFIED: 2025-12-02
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
+use super::components::{PLLICSpec, VCOSpec};
 use super::types::{BodePlot, NoiseComponents, PhaseNoiseProfile};
 use std::f64::consts::PI;
 
+/// Datasheet conditions assumed for PLL IC in-band phase noise figures
+const DATASHEET_PFD_HZ: f64 = 10e6;
+const DATASHEET_N: f64 = 240.0;
+
+/// Part-dependent noise sources feeding the phase noise model
+#[derive(Clone, Debug)]
+pub struct NoiseSources {
+    /// PFD/charge pump floor normalized to 1 Hz PFD and N = 1 (dBc/Hz)
+    pub pfd_floor_norm_dbc_hz: f64,
+    /// Free-running VCO noise at 100 kHz offset (dBc/Hz)
+    pub vco_100khz_dbc_hz: f64,
+    /// VCO 1/f³ corner (Hz)
+    pub vco_flicker_corner_hz: f64,
+}
+
+impl Default for NoiseSources {
+    fn default() -> Self {
+        Self {
+            pfd_floor_norm_dbc_hz: -220.0,
+            vco_100khz_dbc_hz: -100.0,
+            vco_flicker_corner_hz: 10e3,
+        }
+    }
+}
+
+impl NoiseSources {
+    /// Normalize a PLL IC's in-band 10 kHz figure (quoted at 2.4 GHz from a 10 MHz PFD)
+    pub fn with_pll_ic(mut self, ic: &PLLICSpec) -> Self {
+        self.pfd_floor_norm_dbc_hz =
+            ic.phase_noise_10khz_dbc - 10.0 * DATASHEET_PFD_HZ.log10() - 20.0 * DATASHEET_N.log10();
+        self
+    }
+
+    /// Take the VCO's 100 kHz figure and infer its 1/f³ corner from the 10 kHz point
+    ///
+    /// L(f) = L(100k)·(100k/f)²·(1 + fc/f), so anything above 20 dB/decade
+    /// between 10 kHz and 100 kHz is flicker.
+    pub fn with_vco(mut self, vco: &VCOSpec) -> Self {
+        let excess_db = vco.phase_noise_10khz_dbc - vco.phase_noise_100khz_dbc - 20.0;
+        self.vco_100khz_dbc_hz = vco.phase_noise_100khz_dbc;
+        self.vco_flicker_corner_hz = (10e3 * (10f64.powf(excess_db / 10.0) - 1.0)).max(0.0);
+        self
+    }
+
+    /// In-band PFD noise referred to the output (dBc/Hz)
+    pub fn inband_dbc_hz(&self, pfd_freq_hz: f64, n_total: f64) -> f64 {
+        self.pfd_floor_norm_dbc_hz + 10.0 * pfd_freq_hz.log10() + 20.0 * n_total.log10()
+    }
+}
+
 /// Calculate phase noise profile for the PLL
 pub fn calculate_phase_noise(
     bode: &BodePlot,
     n_total: f64,
     ref_freq_hz: f64,
     vco_freq_hz: f64,
+) -> PhaseNoiseProfile {
+    calculate_phase_noise_with_sources(
+        bode,
+        n_total,
+        ref_freq_hz,
+        vco_freq_hz,
+        &NoiseSources::default(),
+    )
+}
+
+/// Calculate phase noise profile using part-specific noise sources
+pub fn calculate_phase_noise_with_sources(
+    bode: &BodePlot,
+    n_total: f64,
+    pfd_freq_hz: f64,
+    vco_freq_hz: f64,
+    sources: &NoiseSources,
 ) -> PhaseNoiseProfile {
     let mut offsets = Vec::new();
     let mut total_noise = Vec::new();
@@ -46,20 +114,20 @@ pub fn calculate_phase_noise(
 
         // 2. PFD/CP Noise Model
         // Flat noise floor dominated by CP current
-        // Normalized to 1Hz: -220 + 10log10(f_pfd) + 20log10(N)
+        // Normalized floor (default -220 dBc/Hz) + 10log10(f_pfd) + 20log10(N)
         // Here we model it as input referred noise
-        let pfd_noise_floor = 1e-22 * ref_freq_hz; // simplified
+        let pfd_noise_floor = 10f64.powf(sources.pfd_floor_norm_dbc_hz / 10.0) * pfd_freq_hz;
         let out_pfd_noise = pfd_noise_floor * n_total * n_total * tf_lowpass_sq;
 
         // 3. VCO Noise Model (Leeson's Equation)
         // -20dB/dec slope, -30dB/dec flicker
-        // Typical VCO: -100 dBc/Hz @ 100kHz offset for 2.4GHz
+        // Default VCO: -100 dBc/Hz @ 100kHz offset, 10kHz flicker corner
         let vco_corner = 100e3;
-        let vco_noise_at_corner = 1e-10; // -100 dBc/Hz
+        let vco_noise_at_corner = 10f64.powf(sources.vco_100khz_dbc_hz / 10.0);
         let vco_thermal = 1e-16; // -160 dBc/Hz floor
 
         let vco_1_f2 = vco_noise_at_corner * (vco_corner / freq).powi(2);
-        let vco_1_f3 = vco_1_f2 * (10e3 / freq); // Flicker corner 10kHz
+        let vco_1_f3 = vco_1_f2 * (sources.vco_flicker_corner_hz / freq);
         let vco_noise_power = vco_thermal + vco_1_f2 + vco_1_f3;
         let out_vco_noise = vco_noise_power * tf_highpass_sq;
 
@@ -145,4 +213,29 @@ mod tests {
         assert!(profile.integrated_jitter_fs > 0.0);
         assert!(profile.components[0].total_dbc_hz < 0.0);
     }
+
+    #[test]
+    fn test_part_noise_sources() {
+        let bode = BodePlot {
+            frequencies_hz: vec![1e3, 1e4, 1e5, 1e6, 1e7],
+            magnitude_db: vec![40.0, 20.0, 0.0, -20.0, -40.0],
+            phase_deg: vec![-90.0; 5],
+        };
+        let vco = crate::pll::components::get_vco_library().remove(0);
+        let sources = NoiseSources::default().with_vco(&vco);
+        // Crystek part: -95 / -118 dBc/Hz is 23 dB/decade, a ~10 kHz flicker corner
+        assert!((sources.vco_flicker_corner_hz - 10e3).abs() < 1e3);
+
+        let default = calculate_phase_noise(&bode, 100.0, 10e6, 1e9);
+        let quiet = calculate_phase_noise_with_sources(&bode, 100.0, 10e6, 1e9, &sources);
+        // Outside the loop the Crystek part's contribution is 18 dB lower
+        assert!(quiet.components[3].vco_dbc_hz < default.components[3].vco_dbc_hz - 15.0);
+
+        let ic = crate::pll::components::get_pll_ic_library()
+            .into_iter()
+            .find(|ic| ic.part_number == "HMC833LP6GE")
+            .unwrap();
+        let hmc = NoiseSources::default().with_pll_ic(&ic);
+        assert!((hmc.inband_dbc_hz(10e6, 240.0) - ic.phase_noise_10khz_dbc).abs() < 1e-9);
+    }
 }
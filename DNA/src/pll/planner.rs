@@ -0,0 +1,635 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: planner.rs | DNA/src/pll/planner.rs
+//! PURPOSE: R/N/prescaler search and PLL IC / VCO selection for design_pll
+//! MODIFIED: 2026-10-17
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Every PLL IC that covers the band is paired with each library VCO its passive
+//! loop filter can tune (plus its own integrated VCO). For each pair the R
+//! divider is swept under the IC's PFD limit, N and the prescaler follow, the
+//! charge pump current is picked from the IC's options, and the loop is
+//! analysed with that part's Kvco and noise floors. Candidates are ranked by
+//! frequency accuracy, then integrated jitter.
+
+use super::components::{get_pll_ic_library, get_vco_library, score_pll_ic, score_vco};
+use super::components::{PLLICSpec, VCOSpec};
+use super::noise::{self, NoiseSources};
+use super::types::*;
+use super::{fractional_n, integer_n, loop_filter, stability};
+use std::f64::consts::PI;
+
+/// Lowest PFD frequency considered, as a multiple of the loop bandwidth
+const MIN_PFD_PER_BANDWIDTH: f64 = 10.0;
+/// R counter width (14 bits on common parts)
+const MAX_R: u32 = 16383;
+/// N counter limit (16 bits)
+const MAX_N: u32 = 65535;
+/// Integer plans within this relative error count as exact
+const FREQ_TOLERANCE: f64 = 1e-6;
+/// Fractional-N only needs the highest few PFD frequencies per part
+const FRACTIONAL_R_OPTIONS: usize = 4;
+/// MASH 1-1-1 modulator swings the ratio by -3..+4
+const MODULATOR_ORDER: u32 = 3;
+const MASH_SWING_DOWN: u32 = 3;
+const MASH_SWING_UP: u32 = 4;
+/// Practical loop filter capacitor range (F)
+const MIN_FILTER_CAP_F: f64 = 10e-12;
+const MAX_FILTER_CAP_F: f64 = 1e-6;
+/// Highest tuning voltage a passive filter can deliver (charge pump rails run up to 5.5 V)
+const CHARGE_PUMP_COMPLIANCE_V: f64 = 5.0;
+
+// ============================================================================
+// FREQUENCY PLANS
+// ============================================================================
+
+/// One R/N/prescaler choice for a PLL IC
+#[derive(Clone, Debug)]
+pub struct FrequencyPlan {
+    pub divider_r: u32,
+    pub divider_n: DividerConfig,
+    pub prescaler: Option<u32>,
+    pub pfd_freq_hz: f64,
+    pub n_effective: f64,
+    pub output_freq_hz: f64,
+    pub freq_error_hz: f64,
+}
+
+/// Enumerate R/N/prescaler plans for `ic`, highest PFD first
+///
+/// Integer-N keeps every exact plan (or the closest in-band one when none is
+/// exact); fractional-N keeps the highest few PFD frequencies.
+pub fn frequency_plans(requirements: &PLLRequirements, ic: &PLLICSpec) -> Vec<FrequencyPlan> {
+    let target = (requirements.output_freq_min_hz + requirements.output_freq_max_hz) / 2.0;
+    let min_pfd = requirements.loop_bandwidth_hz * MIN_PFD_PER_BANDWIDTH;
+    let fractional = requirements.architecture == PLLArchitecture::FractionalN;
+    if fractional && ic.frac_modulus.is_none() {
+        return Vec::new();
+    }
+
+    let mut plans = Vec::new();
+    for r in 1..=MAX_R {
+        let pfd = requirements.ref_freq_hz / r as f64;
+        if pfd < min_pfd {
+            break;
+        }
+        if pfd > ic.max_pfd_freq_hz {
+            continue;
+        }
+
+        let plan = if fractional {
+            fractional_plan(requirements, ic, r, pfd, target)
+        } else {
+            integer_plan(requirements, ic, r, pfd, target)
+        };
+        if let Some(plan) = plan {
+            plans.push(plan);
+        }
+        if fractional && plans.len() >= FRACTIONAL_R_OPTIONS {
+            break;
+        }
+    }
+
+    if fractional {
+        return plans;
+    }
+    let exact: Vec<FrequencyPlan> = plans
+        .iter()
+        .filter(|p| p.freq_error_hz.abs() <= target * FREQ_TOLERANCE)
+        .cloned()
+        .collect();
+    if !exact.is_empty() {
+        return exact;
+    }
+    plans
+        .into_iter()
+        .filter(|p| {
+            p.output_freq_hz >= requirements.output_freq_min_hz
+                && p.output_freq_hz <= requirements.output_freq_max_hz
+        })
+        .min_by(|a, b| a.freq_error_hz.abs().total_cmp(&b.freq_error_hz.abs()))
+        .into_iter()
+        .collect()
+}
+
+fn integer_plan(
+    requirements: &PLLRequirements,
+    ic: &PLLICSpec,
+    r: u32,
+    pfd: f64,
+    target: f64,
+) -> Option<FrequencyPlan> {
+    let n = (target / pfd).round() as u32;
+    if n == 0 || n > MAX_N {
+        return None;
+    }
+    let prescaler =
+        integer_n::select_prescaler(requirements.output_freq_max_hz, n, &ic.prescalers)?;
+    let output = pfd * n as f64;
+
+    Some(FrequencyPlan {
+        divider_r: r,
+        divider_n: DividerConfig::IntegerN { n, prescaler },
+        prescaler,
+        pfd_freq_hz: pfd,
+        n_effective: n as f64,
+        output_freq_hz: output,
+        freq_error_hz: output - target,
+    })
+}
+
+fn fractional_plan(
+    requirements: &PLLRequirements,
+    ic: &PLLICSpec,
+    r: u32,
+    pfd: f64,
+    target: f64,
+) -> Option<FrequencyPlan> {
+    let modulus = ic.frac_modulus?;
+    let n_exact = target / pfd;
+    let mut n_int = n_exact.floor() as u32;
+    let mut n_frac = ((n_exact - n_int as f64) * modulus as f64).round() as u32;
+    if n_frac >= modulus {
+        n_int += 1;
+        n_frac = 0;
+    }
+    if n_int <= MASH_SWING_DOWN || n_int + MASH_SWING_UP > MAX_N {
+        return None;
+    }
+    // The prescaler must cover the lowest instantaneous ratio the modulator asks for
+    let prescaler = integer_n::select_prescaler(
+        requirements.output_freq_max_hz,
+        n_int - MASH_SWING_DOWN,
+        &ic.prescalers,
+    )?;
+
+    let n_effective = n_int as f64 + n_frac as f64 / modulus as f64;
+    let output = pfd * n_effective;
+    Some(FrequencyPlan {
+        divider_r: r,
+        divider_n: fractional_n::create_fractional_n_config(
+            n_int,
+            n_frac,
+            modulus,
+            MODULATOR_ORDER,
+        ),
+        prescaler,
+        pfd_freq_hz: pfd,
+        n_effective,
+        output_freq_hz: output,
+        freq_error_hz: output - target,
+    })
+}
+
+// ============================================================================
+// PART SELECTION
+// ============================================================================
+
+/// PLL ICs that cover the whole band, accept the reference and support the architecture
+pub fn candidate_pll_ics(requirements: &PLLRequirements) -> Vec<PLLICSpec> {
+    let center = (requirements.output_freq_min_hz + requirements.output_freq_max_hz) / 2.0;
+    let needs_fractional = requirements.architecture == PLLArchitecture::FractionalN;
+    get_pll_ic_library()
+        .into_iter()
+        .filter(|ic| {
+            ic.freq_min_hz <= requirements.output_freq_min_hz
+                && ic.freq_max_hz >= requirements.output_freq_max_hz
+                && score_pll_ic(
+                    ic,
+                    center,
+                    requirements.ref_freq_hz,
+                    ic.icp_options_ma.first().copied().unwrap_or(1.0),
+                    needs_fractional,
+                ) > 0.0
+        })
+        .collect()
+}
+
+/// VCO choices: library parts covering the band whose tuning voltage stays within
+/// the charge pump compliance (passive filter), then the IC's integrated VCO (`None`)
+pub fn vco_options(requirements: &PLLRequirements) -> Vec<Option<VCOSpec>> {
+    let center = (requirements.output_freq_min_hz + requirements.output_freq_max_hz) / 2.0;
+    let mut options: Vec<Option<VCOSpec>> = get_vco_library()
+        .into_iter()
+        .filter(|vco| {
+            vco.freq_min_hz <= requirements.output_freq_min_hz
+                && vco.freq_max_hz >= requirements.output_freq_max_hz
+                && score_vco(vco, center, vco.kvco_mhz_per_v) > 0.0
+        })
+        .filter(|vco| {
+            let span = (requirements.output_freq_max_hz - vco.freq_min_hz)
+                / (vco.freq_max_hz - vco.freq_min_hz);
+            let v_top = vco.vtune_min_v + span * (vco.vtune_max_v - vco.vtune_min_v);
+            v_top <= CHARGE_PUMP_COMPLIANCE_V
+        })
+        .map(Some)
+        .collect();
+    options.push(None);
+    options
+}
+
+// ============================================================================
+// LOOP EVALUATION
+// ============================================================================
+
+/// Loop filter, small-signal analysis and noise for one plan and part pair
+#[derive(Clone, Debug)]
+pub(crate) struct LoopEvaluation {
+    pub loop_filter: LoopFilterDesign,
+    pub charge_pump_current_ua: f64,
+    pub vco_gain_mhz_per_v: f64,
+    pub loop_bandwidth_hz: f64,
+    pub bode_plot: BodePlot,
+    pub performance: PLLPerformance,
+    pub phase_noise: PhaseNoiseProfile,
+    pub inband_noise_dbc_hz: f64,
+    /// Charge pump options could not keep the filter capacitors in range
+    pub filter_out_of_range: bool,
+}
+
+pub(crate) fn evaluate_loop(
+    requirements: &PLLRequirements,
+    plan: &FrequencyPlan,
+    ic: &PLLICSpec,
+    vco: Option<&VCOSpec>,
+) -> LoopEvaluation {
+    let vco_gain_mhz_per_v = vco.map_or(ic.kvco_mhz_per_v, |v| v.kvco_mhz_per_v);
+    let k_vco = vco_gain_mhz_per_v * 1e6 * 2.0 * PI; // rad/s/V
+
+    let loop_bandwidth_hz = match &plan.divider_n {
+        DividerConfig::FractionalN {
+            modulator_order, ..
+        } => fractional_n::adjust_bandwidth_for_fractional(
+            requirements.loop_bandwidth_hz,
+            *modulator_order,
+        ),
+        DividerConfig::IntegerN { .. } => requirements.loop_bandwidth_hz,
+    };
+    let omega_c = 2.0 * PI * loop_bandwidth_hz;
+
+    // Highest pump current whose filter capacitors stay buildable: more current
+    // lowers charge pump noise but scales every capacitor up
+    let mut best: Option<(f64, f64, (f64, f64, f64))> = None;
+    let mut icp_options = ic.icp_options_ma.clone();
+    icp_options.sort_by(|a, b| b.total_cmp(a));
+    for icp_ma in icp_options {
+        let (c1, r1, c2) = loop_filter::design_passive_second_order(
+            icp_ma * 1e-3,
+            k_vco,
+            plan.n_effective,
+            omega_c,
+            requirements.phase_margin_deg,
+        );
+        let violation = (MIN_FILTER_CAP_F / c1.min(c2)).ln().max(0.0)
+            + (c1.max(c2) / MAX_FILTER_CAP_F).ln().max(0.0);
+        if best.as_ref().is_none_or(|b| violation < b.1) {
+            best = Some((icp_ma, violation, (c1, r1, c2)));
+        }
+        if violation == 0.0 {
+            break;
+        }
+    }
+    let (icp_ma, violation, (c1, r1, c2)) = best.unwrap_or((1.0, 0.0, (0.0, 0.0, 0.0)));
+    let k_phi = icp_ma * 1e-3;
+
+    let loop_filter_design = loop_filter::create_loop_filter_design(c1, r1, c2, None, None);
+    let bode_plot = stability::generate_bode_plot(
+        k_phi,
+        k_vco,
+        plan.n_effective,
+        loop_filter_design.r1_ohms,
+        loop_filter_design.c1_pf * 1e-12,
+        loop_filter_design.c2_pf * 1e-12,
+        1e3,
+        10.0 * plan.pfd_freq_hz,
+        50,
+    );
+    let performance = stability::analyze_stability(&bode_plot);
+
+    let mut sources = NoiseSources::default().with_pll_ic(ic);
+    if let Some(vco) = vco {
+        sources = sources.with_vco(vco);
+    }
+    let phase_noise = noise::calculate_phase_noise_with_sources(
+        &bode_plot,
+        plan.n_effective,
+        plan.pfd_freq_hz,
+        plan.output_freq_hz,
+        &sources,
+    );
+
+    LoopEvaluation {
+        loop_filter: loop_filter_design,
+        charge_pump_current_ua: icp_ma * 1e3,
+        vco_gain_mhz_per_v,
+        loop_bandwidth_hz,
+        bode_plot,
+        performance,
+        phase_noise,
+        inband_noise_dbc_hz: sources.inband_dbc_hz(plan.pfd_freq_hz, plan.n_effective),
+        filter_out_of_range: violation > 0.0,
+    }
+}
+
+// ============================================================================
+// SEARCH
+// ============================================================================
+
+/// A ranked candidate with everything needed to build the final design
+#[derive(Clone, Debug)]
+pub(crate) struct EvaluatedCandidate {
+    pub summary: DesignCandidate,
+    pub plan: FrequencyPlan,
+    pub ic: PLLICSpec,
+    pub vco: Option<VCOSpec>,
+    pub evaluation: LoopEvaluation,
+}
+
+pub(crate) fn search(requirements: &PLLRequirements) -> Result<Vec<EvaluatedCandidate>, String> {
+    let target = (requirements.output_freq_min_hz + requirements.output_freq_max_hz) / 2.0;
+    let ics = candidate_pll_ics(requirements);
+    if ics.is_empty() {
+        return Err(format!(
+            "No PLL IC in the library covers {:.1}-{:.1} MHz from a {:.3} MHz reference",
+            requirements.output_freq_min_hz / 1e6,
+            requirements.output_freq_max_hz / 1e6,
+            requirements.ref_freq_hz / 1e6
+        ));
+    }
+
+    let vcos = vco_options(requirements);
+    let mut candidates = Vec::new();
+    for ic in &ics {
+        let plans = frequency_plans(requirements, ic);
+        for vco in &vcos {
+            // Only plans this IC and VCO can both reach get a loop analysis
+            let reachable = plans
+                .iter()
+                .filter(|plan| within_tuning_range(plan, ic, vco.as_ref()));
+            for plan in reachable {
+                let evaluation = evaluate_loop(requirements, plan, ic, vco.as_ref());
+                let summary = summarize(requirements, plan, ic, vco.as_ref(), &evaluation);
+                candidates.push(EvaluatedCandidate {
+                    summary,
+                    plan: plan.clone(),
+                    ic: ic.clone(),
+                    vco: vco.clone(),
+                    evaluation,
+                });
+            }
+        }
+    }
+    if candidates.is_empty() {
+        return Err(format!(
+            "No R/N/prescaler plan reaches {:.3} MHz with PFD between {:.3} MHz and the IC limits",
+            target / 1e6,
+            requirements.loop_bandwidth_hz * MIN_PFD_PER_BANDWIDTH / 1e6
+        ));
+    }
+
+    // Exact frequency first, then buildable filters, then lowest jitter
+    let tolerance = target * FREQ_TOLERANCE;
+    candidates.sort_by(|a, b| {
+        let key = |c: &EvaluatedCandidate| {
+            (
+                c.plan.freq_error_hz.abs() > tolerance,
+                c.evaluation.filter_out_of_range,
+            )
+        };
+        key(a).cmp(&key(b)).then(
+            a.summary
+                .integrated_jitter_fs
+                .total_cmp(&b.summary.integrated_jitter_fs),
+        )
+    });
+    Ok(candidates)
+}
+
+/// Plan output inside the IC's range and the VCO's (the IC's own when integrated)
+fn within_tuning_range(plan: &FrequencyPlan, ic: &PLLICSpec, vco: Option<&VCOSpec>) -> bool {
+    let (low, high) = vco.map_or((ic.freq_min_hz, ic.freq_max_hz), |v| {
+        (
+            v.freq_min_hz.max(ic.freq_min_hz),
+            v.freq_max_hz.min(ic.freq_max_hz),
+        )
+    });
+    (low..=high).contains(&plan.output_freq_hz)
+}
+
+/// Evaluate and rank every R/N/prescaler and part combination, best first
+pub fn design_candidates(requirements: &PLLRequirements) -> Result<Vec<DesignCandidate>, String> {
+    Ok(search(requirements)?
+        .into_iter()
+        .map(|c| c.summary)
+        .collect())
+}
+
+fn summarize(
+    requirements: &PLLRequirements,
+    plan: &FrequencyPlan,
+    ic: &PLLICSpec,
+    vco: Option<&VCOSpec>,
+    evaluation: &LoopEvaluation,
+) -> DesignCandidate {
+    let fractional_spur_offset_hz = match &plan.divider_n {
+        DividerConfig::FractionalN {
+            n_frac, modulus, ..
+        } if *n_frac > 0 => {
+            let eps = *n_frac as f64 / *modulus as f64;
+            Some(plan.pfd_freq_hz * eps.min(1.0 - eps))
+        }
+        _ => None,
+    };
+
+    let mut tradeoffs = Vec::new();
+    if plan.freq_error_hz.abs() > plan.output_freq_hz * FREQ_TOLERANCE {
+        tradeoffs.push(format!(
+            "Output is {:+.1} kHz from the requested centre",
+            plan.freq_error_hz / 1e3
+        ));
+    }
+    if plan.divider_r > 1 {
+        tradeoffs.push(format!(
+            "R = {} lowers the PFD to {:.3} MHz: in-band noise {:.1} dBc/Hz and reference spur closer in",
+            plan.divider_r,
+            plan.pfd_freq_hz / 1e6,
+            evaluation.inband_noise_dbc_hz
+        ));
+    }
+    if plan.pfd_freq_hz > 0.8 * ic.max_pfd_freq_hz {
+        tradeoffs.push(format!(
+            "PFD at {:.0}% of the {} limit",
+            plan.pfd_freq_hz / ic.max_pfd_freq_hz * 100.0,
+            ic.part_number
+        ));
+    }
+    if let Some(p) = plan.prescaler {
+        tradeoffs.push(format!(
+            "{}/{} prescaler restricts N to at least {}",
+            p,
+            p + 1,
+            p * (p - 1)
+        ));
+    }
+    if let Some(offset) = fractional_spur_offset_hz {
+        let where_ = if offset < evaluation.loop_bandwidth_hz {
+            "inside"
+        } else {
+            "outside"
+        };
+        tradeoffs.push(format!(
+            "Fractional spur at {:.1} kHz offset, {} the {:.0} kHz loop bandwidth",
+            offset / 1e3,
+            where_,
+            evaluation.loop_bandwidth_hz / 1e3
+        ));
+    }
+    match vco {
+        Some(v) => tradeoffs.push(format!(
+            "External {} VCO ({:.0} dBc/Hz at 100 kHz, +{:.0} mA)",
+            v.part_number, v.phase_noise_100khz_dbc, v.icc_ma
+        )),
+        None => tradeoffs.push(format!("Integrated {} VCO, no extra part", ic.part_number)),
+    }
+    if evaluation.filter_out_of_range {
+        tradeoffs.push(
+            "No charge pump setting keeps the loop filter capacitors between 10 pF and 1 µF"
+                .to_string(),
+        );
+    }
+    if evaluation.performance.phase_margin_deg < requirements.phase_margin_deg - 5.0 {
+        tradeoffs.push(format!(
+            "Phase margin {:.1}° is short of the {:.0}° target",
+            evaluation.performance.phase_margin_deg, requirements.phase_margin_deg
+        ));
+    }
+
+    DesignCandidate {
+        pll_ic: ic.part_number.clone(),
+        vco: vco.map_or_else(
+            || format!("{} integrated", ic.part_number),
+            |v| v.part_number.clone(),
+        ),
+        divider_r: plan.divider_r,
+        divider_n: plan.divider_n.clone(),
+        prescaler: plan.prescaler,
+        pfd_freq_hz: plan.pfd_freq_hz,
+        output_freq_hz: plan.output_freq_hz,
+        freq_error_hz: plan.freq_error_hz,
+        charge_pump_current_ua: evaluation.charge_pump_current_ua,
+        vco_gain_mhz_per_v: evaluation.vco_gain_mhz_per_v,
+        loop_bandwidth_hz: evaluation.loop_bandwidth_hz,
+        phase_margin_deg: evaluation.performance.phase_margin_deg,
+        inband_noise_dbc_hz: evaluation.inband_noise_dbc_hz,
+        integrated_jitter_fs: evaluation.phase_noise.integrated_jitter_fs,
+        fractional_spur_offset_hz,
+        tradeoffs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirements(ref_hz: f64, architecture: PLLArchitecture) -> PLLRequirements {
+        PLLRequirements {
+            ref_freq_hz: ref_hz,
+            output_freq_min_hz: 2.4e9,
+            output_freq_max_hz: 2.5e9,
+            loop_bandwidth_hz: 100e3,
+            phase_margin_deg: 45.0,
+            architecture,
+            supply_voltage: 3.3,
+        }
+    }
+
+    #[test]
+    fn test_integer_plans_need_reference_divider() {
+        // 26 MHz cannot reach 2.45 GHz with R = 1; gcd(2450, 26) = 2 MHz PFD,
+        // and 1 MHz (R = 26) is the last PFD above 10x the loop bandwidth
+        let req = requirements(26e6, PLLArchitecture::IntegerN);
+        let adf4351 = candidate_pll_ics(&req)
+            .into_iter()
+            .find(|ic| ic.part_number == "ADF4351")
+            .unwrap();
+        let plans = frequency_plans(&req, &adf4351);
+
+        let rs: Vec<u32> = plans.iter().map(|p| p.divider_r).collect();
+        assert_eq!(rs, vec![13, 26]);
+        let plan = &plans[0];
+        assert_eq!(plan.divider_r, 13);
+        assert!((plan.pfd_freq_hz - 2e6).abs() < 1e-6);
+        assert!(plan.freq_error_hz.abs() < 1e-3);
+        assert_eq!(plan.prescaler, Some(4));
+        assert!(matches!(
+            plan.divider_n,
+            DividerConfig::IntegerN { n: 1225, .. }
+        ));
+    }
+
+    #[test]
+    fn test_pfd_limit_forces_reference_division() {
+        // 100 MHz reference exceeds the ADF4351's 32 MHz PFD limit
+        let req = requirements(100e6, PLLArchitecture::IntegerN);
+        let adf4351 = get_pll_ic_library()
+            .into_iter()
+            .find(|ic| ic.part_number == "ADF4351")
+            .unwrap();
+        let plans = frequency_plans(&req, &adf4351);
+        assert!(!plans.is_empty());
+        assert!(plans
+            .iter()
+            .all(|p| p.pfd_freq_hz <= 32e6 && p.divider_r >= 4));
+    }
+
+    #[test]
+    fn test_vco_options_respect_tuning_range() {
+        let req = requirements(10e6, PLLArchitecture::IntegerN);
+        let options = vco_options(&req);
+
+        // Only the Crystek part tunes 2.4-2.5 GHz within 5 V; integrated VCO last
+        let parts: Vec<String> = options
+            .iter()
+            .map(|v| {
+                v.as_ref()
+                    .map_or("integrated".to_string(), |v| v.part_number.clone())
+            })
+            .collect();
+        assert_eq!(parts, vec!["CVCO55CC-2400-2500", "integrated"]);
+    }
+
+    #[test]
+    fn test_plans_outside_vco_range_skipped() {
+        let req = requirements(10e6, PLLArchitecture::IntegerN);
+        let ic = candidate_pll_ics(&req).into_iter().next().unwrap();
+        let plan = &frequency_plans(&req, &ic)[0];
+        let crystek = vco_options(&req)[0].clone().unwrap();
+
+        assert!(within_tuning_range(plan, &ic, None));
+        assert!(within_tuning_range(plan, &ic, Some(&crystek)));
+        let high_band = VCOSpec {
+            freq_min_hz: 2.5e9,
+            freq_max_hz: 2.7e9,
+            ..crystek
+        };
+        assert!(!within_tuning_range(plan, &ic, Some(&high_band)));
+    }
+
+    #[test]
+    fn test_candidates_ranked_and_explained() {
+        let req = requirements(10e6, PLLArchitecture::FractionalN);
+        let candidates = design_candidates(&req).unwrap();
+
+        assert!(candidates.len() > 5);
+        // Part-specific Kvco and charge pump options are used
+        assert!(candidates
+            .iter()
+            .any(|c| (c.vco_gain_mhz_per_v - 40.0).abs() < 1e-9));
+        assert!(candidates
+            .iter()
+            .all(|c| c.charge_pump_current_ua >= 20.0 && c.charge_pump_current_ua <= 7500.0));
+        // Best first by jitter among exact plans
+        assert!(candidates[0].integrated_jitter_fs <= candidates[1].integrated_jitter_fs);
+        assert!(candidates.iter().all(|c| !c.tradeoffs.is_empty()));
+    }
+}
@@ -1,10 +1,11 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: types.rs | DNA/src/pll/types.rs
-//! PURPOSE: Defines NoiseComponents, PhaseNoiseProfile, TransientResult types
+//! PURPOSE: Defines NoiseComponents, PhaseNoiseProfile, TransientResult, DesignCandidate types
 //! MODIFIED: 2025-12-02
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
+use super::components::{PLLICSpec, VCOSpec};
 use serde::{Deserialize, Serialize};
 
 /// Phase noise components at a specific offset
@@ -28,7 +29,7 @@ pub struct PhaseNoiseProfile {
 }
 
 /// Results of a transient simulation
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct TransientResult {
     pub time_s: Vec<f64>,
     pub freq_hz: Vec<f64>,
@@ -98,6 +99,8 @@ pub struct LoopFilterDesign {
     pub r1_ohms: f64,
     pub c3_pf: Option<f64>,
     pub r2_ohms: Option<f64>,
+    pub c4_pf: Option<f64>,
+    pub r3_ohms: Option<f64>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -139,14 +142,52 @@ pub struct PLLDesign {
     pub requirements: PLLRequirements,
     pub divider_r: u32,
     pub divider_n: DividerConfig,
+    /// Dual-modulus prescaler ahead of the N counter, if one is needed
+    #[serde(default)]
+    pub prescaler: Option<u32>,
     pub pfd_freq_hz: f64,
     pub loop_filter: LoopFilterDesign,
     pub charge_pump_current_ua: f64,
     pub vco_gain_mhz_per_v: f64,
+    /// Selected PLL IC
+    #[serde(default)]
+    pub pll_ic: PLLICSpec,
+    /// External VCO, or `None` when the IC's integrated VCO is used
+    #[serde(default)]
+    pub vco: Option<VCOSpec>,
     pub performance: PLLPerformance,
     pub bode_plot: BodePlot,
     pub phase_noise: PhaseNoiseProfile,
     pub transient: TransientResult,
+    /// Every evaluated plan, best first (the first entry is this design)
+    #[serde(default)]
+    pub candidates: Vec<DesignCandidate>,
+}
+
+/// One evaluated divider plan and part combination
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DesignCandidate {
+    pub pll_ic: String,
+    /// VCO part number, or "<IC> integrated"
+    pub vco: String,
+    pub divider_r: u32,
+    pub divider_n: DividerConfig,
+    pub prescaler: Option<u32>,
+    pub pfd_freq_hz: f64,
+    pub output_freq_hz: f64,
+    /// Synthesized minus requested centre frequency (Hz)
+    pub freq_error_hz: f64,
+    pub charge_pump_current_ua: f64,
+    pub vco_gain_mhz_per_v: f64,
+    pub loop_bandwidth_hz: f64,
+    pub phase_margin_deg: f64,
+    /// PFD/charge pump floor referred to the output (dBc/Hz)
+    pub inband_noise_dbc_hz: f64,
+    pub integrated_jitter_fs: f64,
+    /// Nearest fractional spur offset (Hz), fractional-N only
+    pub fractional_spur_offset_hz: Option<f64>,
+    /// Human-readable notes on what this candidate gives up or gains
+    pub tradeoffs: Vec<String>,
 }
 
 /// Validation errors
@@ -6,6 +6,8 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 use super::components::{next_higher_capacitor, next_higher_inductor};
+use super::library::{builtin_parts, PartLibrary};
+use super::magnetics::{design_inductor, InductorDesign, InductorRequirements};
 
 #[cfg(test)]
 use super::components::format_inductance;
@@ -152,8 +154,16 @@ pub fn determine_operating_mode(iout: f64, critical_current: f64) -> OperatingMo
 // MAIN DESIGN FUNCTION
 // ============================================================================
 
-/// Design a boost converter from requirements
+/// Design a boost converter from requirements with the built-in parts
 pub fn design_boost(requirements: &BoostRequirements) -> Result<BoostDesign, String> {
+    design_boost_with(requirements, builtin_parts())
+}
+
+/// Design a boost converter choosing the inductor core from `library`
+pub fn design_boost_with(
+    requirements: &BoostRequirements,
+    library: &PartLibrary,
+) -> Result<BoostDesign, String> {
     // Validate requirements
     if requirements.vout <= requirements.vin.max_v {
         return Err(format!(
@@ -279,13 +289,26 @@ pub fn design_boost(requirements: &BoostRequirements) -> Result<BoostDesign, Str
     let switch_voltage = calculate_switch_voltage_stress(requirements.vout);
     let switch_current = calculate_switch_current_stress(requirements.iout_max, duty_nom, delta_il);
 
+    // Design the inductor itself: core, gap, winding and losses
+    let inductor_design = design_inductor(
+        library,
+        &InductorRequirements {
+            duty_cycle: duty_nom,
+            ambient_temp: requirements.ambient_temp_c,
+            ..InductorRequirements::new(l_selected, i_in, delta_il, requirements.switching_freq_hz)
+        },
+    );
+
     // Create component selections
-    let inductor = SelectedComponent::new("L1", l_ideal, l_selected, "H")
+    let mut inductor = SelectedComponent::new("L1", l_ideal, l_selected, "H")
         .with_tolerance(20.0)
         .with_note(&format!(
             "Isat > {:.2}A, DCR < 30mOhm recommended",
             il_peak * 1.3
         ));
+    if let Some(design) = &inductor_design {
+        inductor = inductor.with_note(&design.description());
+    }
 
     let output_capacitor = SelectedComponent::new("Cout", cout_ideal, cout_selected, "F")
         .with_tolerance(20.0)
@@ -308,6 +331,7 @@ pub fn design_boost(requirements: &BoostRequirements) -> Result<BoostDesign, Str
         requirements.iout_max,
         duty_nom,
         i_in,
+        inductor_design.as_ref(),
     );
 
     // Thermal analysis
@@ -344,6 +368,7 @@ pub fn design_boost(requirements: &BoostRequirements) -> Result<BoostDesign, Str
         duty_cycle_nom: duty_nom,
         duty_cycle_min: duty_min,
         inductor,
+        inductor_design,
         output_capacitor,
         input_capacitor,
         inductor_current_ripple_a: delta_il,
@@ -367,6 +392,7 @@ fn calculate_efficiency_estimate(
     iout: f64,
     duty: f64,
     i_in: f64,
+    inductor: Option<&InductorDesign>,
 ) -> EfficiencyBreakdown {
     let p_out = vout * iout;
 
@@ -378,14 +404,19 @@ fn calculate_efficiency_estimate(
     // Conduction losses - note inductor current is higher than output current
     let p_cond_sw = i_in * i_in * rds_on * duty;
     let p_diode = vf * iout; // Diode conducts full output current during off-time
-    let p_dcr = i_in * i_in * dcr;
+
+    // Inductor losses from the magnetics design when available
+    let (p_dcr, p_core) = match inductor {
+        Some(l) => (l.copper_loss, l.core_loss),
+        None => (i_in * i_in * dcr, 0.0),
+    };
 
     // Switching losses (higher for boost due to hard switching at Vout)
     let p_sw = 0.07 * p_out; // ~7% switching loss for boost
 
     let p_q = 0.015; // 15mW controller (boost controllers often more complex)
 
-    let total_loss = p_cond_sw + p_diode + p_dcr + p_sw + p_q;
+    let total_loss = p_cond_sw + p_diode + p_dcr + p_core + p_sw + p_q;
     let p_in = p_out + total_loss;
 
     EfficiencyBreakdown {
@@ -394,6 +425,7 @@ fn calculate_efficiency_estimate(
         switching_loss_w: p_sw,
         diode_loss_w: p_diode,
         inductor_dcr_loss_w: p_dcr,
+        inductor_core_loss_w: p_core,
         capacitor_esr_loss_w: 0.002,
         quiescent_loss_w: p_q,
         output_power_w: p_out,
@@ -500,4 +532,20 @@ mod tests {
         let result = design_boost(&req);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_design_boost_inductor_carries_input_current() {
+        let design = design_boost(&BoostRequirements::default()).unwrap();
+        let inductor = design
+            .inductor_design
+            .as_ref()
+            .expect("Inductor should fit the core database");
+
+        // Peak flux is set by the input (inductor) current, not Iout
+        let ae = inductor.core.ae * 1e-6;
+        let b_pk =
+            inductor.inductance * design.inductor_peak_current_a / (inductor.turns as f64 * ae);
+        assert!((inductor.b_peak - b_pk).abs() / b_pk < 0.05);
+        assert!(design.efficiency.inductor_core_loss_w > 0.0);
+    }
 }
@@ -6,6 +6,8 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 use super::components::{next_higher_capacitor, next_higher_inductor};
+use super::library::{builtin_parts, PartLibrary};
+use super::magnetics::{design_inductor, InductorDesign, InductorRequirements};
 
 #[cfg(test)]
 use super::components::format_inductance;
@@ -136,8 +138,16 @@ pub fn determine_operating_mode(iout: f64, critical_current: f64) -> OperatingMo
 // MAIN DESIGN FUNCTION
 // ============================================================================
 
-/// Design a buck converter from requirements
+/// Design a buck converter from requirements with the built-in parts
 pub fn design_buck(requirements: &BuckRequirements) -> Result<BuckDesign, String> {
+    design_buck_with(requirements, builtin_parts())
+}
+
+/// Design a buck converter choosing the inductor core from `library`
+pub fn design_buck_with(
+    requirements: &BuckRequirements,
+    library: &PartLibrary,
+) -> Result<BuckDesign, String> {
     // Validate requirements
     if requirements.vout >= requirements.vin.min_v {
         return Err(format!(
@@ -242,10 +252,28 @@ pub fn design_buck(requirements: &BuckRequirements) -> Result<BuckDesign, String
     );
     let cin_selected = next_higher_capacitor(cin_ideal);
 
+    // Design the inductor itself: core, gap, winding and losses
+    let inductor_design = design_inductor(
+        library,
+        &InductorRequirements {
+            duty_cycle: duty_nom,
+            ambient_temp: requirements.ambient_temp_c,
+            ..InductorRequirements::new(
+                l_selected,
+                requirements.iout_max,
+                delta_il,
+                requirements.switching_freq_hz,
+            )
+        },
+    );
+
     // Create component selections
-    let inductor = SelectedComponent::new("L1", l_ideal, l_selected, "H")
+    let mut inductor = SelectedComponent::new("L1", l_ideal, l_selected, "H")
         .with_tolerance(20.0)
         .with_note(&format!("Isat > {:.2}A, DCR < 50mOhm", il_peak * 1.2));
+    if let Some(design) = &inductor_design {
+        inductor = inductor.with_note(&design.description());
+    }
 
     let output_capacitor = SelectedComponent::new("Cout", cout_ideal, cout_selected, "F")
         .with_tolerance(20.0)
@@ -268,7 +296,7 @@ pub fn design_buck(requirements: &BuckRequirements) -> Result<BuckDesign, String
         requirements.iout_max,
         duty_nom,
         requirements.switching_freq_hz,
-        l_selected,
+        inductor_design.as_ref(),
     );
 
     // Calculate thermal (simplified - assumes integrated solution)
@@ -305,6 +333,7 @@ pub fn design_buck(requirements: &BuckRequirements) -> Result<BuckDesign, String
         duty_cycle_nom: duty_nom,
         duty_cycle_min: duty_min,
         inductor,
+        inductor_design,
         output_capacitor,
         input_capacitor,
         inductor_current_ripple_a: delta_il,
@@ -325,7 +354,7 @@ fn calculate_efficiency_estimate(
     iout: f64,
     duty: f64,
     _fsw: f64,
-    _inductance: f64,
+    inductor: Option<&InductorDesign>,
 ) -> EfficiencyBreakdown {
     let p_out = vout * iout;
 
@@ -338,7 +367,12 @@ fn calculate_efficiency_estimate(
     // Conduction losses
     let p_cond_sw = iout * iout * rds_on * duty;
     let p_diode = vf * iout * (1.0 - duty);
-    let p_dcr = iout * iout * dcr;
+
+    // Inductor losses from the magnetics design when available
+    let (p_dcr, p_core) = match inductor {
+        Some(l) => (l.copper_loss, l.core_loss),
+        None => (iout * iout * dcr, 0.0),
+    };
 
     // Switching losses (simplified)
     let p_sw = 0.05 * p_out; // Assume 5% switching loss
@@ -346,7 +380,7 @@ fn calculate_efficiency_estimate(
     // Quiescent
     let p_q = 0.010; // 10mW controller
 
-    let total_loss = p_cond_sw + p_diode + p_dcr + p_sw + p_q;
+    let total_loss = p_cond_sw + p_diode + p_dcr + p_core + p_sw + p_q;
     let p_in = p_out + total_loss;
 
     EfficiencyBreakdown {
@@ -355,6 +389,7 @@ fn calculate_efficiency_estimate(
         switching_loss_w: p_sw,
         diode_loss_w: p_diode,
         inductor_dcr_loss_w: p_dcr,
+        inductor_core_loss_w: p_core,
         capacitor_esr_loss_w: 0.001, // Negligible
         quiescent_loss_w: p_q,
         output_power_w: p_out,
@@ -464,6 +499,21 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_design_buck_inductor_losses() {
+        let design = design_buck(&BuckRequirements::default()).unwrap();
+        let inductor = design
+            .inductor_design
+            .as_ref()
+            .expect("Inductor should fit the core database");
+
+        // Magnetics losses feed the efficiency breakdown
+        assert_eq!(design.efficiency.inductor_dcr_loss_w, inductor.copper_loss);
+        assert_eq!(design.efficiency.inductor_core_loss_w, inductor.core_loss);
+        assert!(inductor.saturation_margin > 0.0);
+        assert!(inductor.inductance >= design.inductor.selected_value * 0.99);
+    }
+
     #[test]
     fn test_operating_mode_detection() {
         assert_eq!(determine_operating_mode(2.0, 0.5), OperatingMode::CCM);
@@ -18,6 +18,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::power::library::PartLibrary;
+
 /// Diode type classification
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DiodeType {
@@ -685,18 +687,16 @@ pub fn diode_database() -> Vec<DiodeSpec> {
     ]
 }
 
-/// Find suitable diodes for given requirements
+/// Find suitable diodes in `library` for given requirements
 pub fn find_suitable_diodes(
+    library: &PartLibrary,
     vr_required: f64,
     if_avg: f64,
     if_rms: f64,
     preference: DiodePreference,
-) -> Vec<&'static DiodeSpec> {
-    // Lazy static for database
-    static DATABASE: std::sync::OnceLock<Vec<DiodeSpec>> = std::sync::OnceLock::new();
-    let db = DATABASE.get_or_init(diode_database);
-
-    let mut suitable: Vec<&DiodeSpec> = db
+) -> Vec<&DiodeSpec> {
+    let mut suitable: Vec<&DiodeSpec> = library
+        .diodes
         .iter()
         .filter(|d| d.is_suitable(vr_required, if_avg, if_rms))
         .collect();
@@ -875,7 +875,8 @@ mod tests {
 
     #[test]
     fn test_find_suitable_diodes() {
-        let suitable = find_suitable_diodes(50.0, 2.0, 3.0, DiodePreference::LowVf);
+        let library = PartLibrary::builtin();
+        let suitable = find_suitable_diodes(&library, 50.0, 2.0, 3.0, DiodePreference::LowVf);
         assert!(!suitable.is_empty());
 
         // All should be rated above 50V × 0.8 = 62.5V
@@ -13,6 +13,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::power::library::PartLibrary;
+
 /// MOSFET package type
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MOSFETPackage {
@@ -499,18 +501,16 @@ pub fn mosfet_database() -> Vec<MOSFETSpec> {
     ]
 }
 
-/// Find suitable MOSFETs for given requirements
+/// Find suitable MOSFETs in `library` for given requirements
 pub fn find_suitable_mosfets(
+    library: &PartLibrary,
     vds_required: f64,
     id_rms: f64,
     id_peak: f64,
     preference: MOSFETPreference,
-) -> Vec<&'static MOSFETSpec> {
-    // Lazy static for database
-    static DATABASE: std::sync::OnceLock<Vec<MOSFETSpec>> = std::sync::OnceLock::new();
-    let db = DATABASE.get_or_init(mosfet_database);
-
-    let mut suitable: Vec<&MOSFETSpec> = db
+) -> Vec<&MOSFETSpec> {
+    let mut suitable: Vec<&MOSFETSpec> = library
+        .mosfets
         .iter()
         .filter(|m| m.is_suitable(vds_required, id_rms, id_peak))
         .collect();
@@ -600,7 +600,9 @@ mod tests {
 
     #[test]
     fn test_find_suitable_mosfets() {
-        let suitable = find_suitable_mosfets(50.0, 5.0, 10.0, MOSFETPreference::LowLosses);
+        let library = PartLibrary::builtin();
+        let suitable =
+            find_suitable_mosfets(&library, 50.0, 5.0, 10.0, MOSFETPreference::LowLosses);
         assert!(!suitable.is_empty());
         // All should be rated above 50V × 0.8 = 62.5V
         for m in &suitable {
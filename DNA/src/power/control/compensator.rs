@@ -693,6 +693,224 @@ pub fn snap_to_standard_values(components: &CompensatorComponents) -> Compensato
     }
 }
 
+// ============================================================================
+// DIGITAL IMPLEMENTATION
+// ============================================================================
+
+/// One second-order section b(z)/a(z) in transposed direct form II
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    state: [f64; 2],
+}
+
+impl Biquad {
+    /// Tustin map of (n2·s² + n1·s + n0) / (d2·s² + d1·s + d0) with s = k(z-1)/(z+1)
+    ///
+    /// First-order sections (n2 = d2 = 0) are multiplied through by (z+1)
+    /// only, so no spurious z = -1 pole/zero pair is introduced.
+    fn tustin(num: [f64; 3], den: [f64; 3], k: f64) -> Self {
+        let [n0, n1, n2] = num;
+        let [d0, d1, d2] = den;
+        let (b, a) = if n2 == 0.0 && d2 == 0.0 {
+            (
+                [n1 * k + n0, n0 - n1 * k, 0.0],
+                [d1 * k + d0, d0 - d1 * k, 0.0],
+            )
+        } else {
+            let k2 = k * k;
+            (
+                [
+                    n2 * k2 + n1 * k + n0,
+                    2.0 * (n0 - n2 * k2),
+                    n2 * k2 - n1 * k + n0,
+                ],
+                [
+                    d2 * k2 + d1 * k + d0,
+                    2.0 * (d0 - d2 * k2),
+                    d2 * k2 - d1 * k + d0,
+                ],
+            )
+        };
+        Self {
+            b: [b[0] / a[0], b[1] / a[0], b[2] / a[0]],
+            a: [a[1] / a[0], a[2] / a[0]],
+            state: [0.0; 2],
+        }
+    }
+
+    /// Output and next state for one input sample (state is not committed)
+    fn advance(&self, x: f64) -> (f64, [f64; 2]) {
+        let y = self.b[0] * x + self.state[0];
+        let next = [
+            self.b[1] * x - self.a[0] * y + self.state[1],
+            self.b[2] * x - self.a[1] * y,
+        ];
+        (y, next)
+    }
+}
+
+/// Compensator discretized for a sampled control loop
+///
+/// The continuous K·Π(s - z)/Π(s - p) is split into real first- and
+/// second-order sections (conjugate pairs kept together, real roots paired
+/// in order) and each section is mapped with the bilinear transform. A
+/// cascade of sections stays well conditioned at sample rates far above the
+/// compensator corners, where a single high-order polynomial would not.
+#[derive(Clone, Debug)]
+pub struct DiscreteCompensator {
+    sections: Vec<Biquad>,
+    gain: f64,
+    sample_time: f64,
+    last_output: f64,
+}
+
+impl DiscreteCompensator {
+    /// Discretize a transfer function at the given sample period (s)
+    pub fn from_transfer_function(tf: &TransferFunction, sample_time: f64) -> Result<Self, String> {
+        if sample_time <= 0.0 || !sample_time.is_finite() {
+            return Err("Sample time must be positive".to_string());
+        }
+        if tf.zeros.len() > tf.poles.len() {
+            return Err(format!(
+                "Improper transfer function: {} zeros, {} poles",
+                tf.zeros.len(),
+                tf.poles.len()
+            ));
+        }
+
+        let k = 2.0 / sample_time;
+        let (mut quadratic, mut linear): (Vec<_>, Vec<_>) = real_factors(&tf.zeros)?
+            .into_iter()
+            .partition(|f| f[2] != 0.0);
+
+        // Quadratic numerators go with quadratic denominators; the single
+        // linear numerator (if any) takes the first section with room left
+        let mut sections = Vec::new();
+        for den in real_factors(&tf.poles)? {
+            let num = if den[2] != 0.0 {
+                quadratic.pop().or_else(|| linear.pop())
+            } else {
+                linear.pop()
+            };
+            sections.push(Biquad::tustin(num.unwrap_or([1.0, 0.0, 0.0]), den, k));
+        }
+        if !quadratic.is_empty() || !linear.is_empty() {
+            return Err("Could not pair compensator zeros with poles".to_string());
+        }
+
+        Ok(Self {
+            sections,
+            gain: tf.dc_gain,
+            sample_time,
+            last_output: 0.0,
+        })
+    }
+
+    /// Discretize a compensator design at the given sample period (s)
+    pub fn from_design(design: &CompensatorDesign, sample_time: f64) -> Result<Self, String> {
+        Self::from_transfer_function(&design.transfer_function, sample_time)
+    }
+
+    /// Sample period (s)
+    pub fn sample_time(&self) -> f64 {
+        self.sample_time
+    }
+
+    /// Clear all section states
+    pub fn reset(&mut self) {
+        for section in &mut self.sections {
+            section.state = [0.0; 2];
+        }
+        self.last_output = 0.0;
+    }
+
+    /// Process one input sample
+    pub fn step(&mut self, input: f64) -> f64 {
+        let mut signal = input * self.gain;
+        for section in &mut self.sections {
+            let (y, next) = section.advance(signal);
+            section.state = next;
+            signal = y;
+        }
+        self.last_output = signal;
+        signal
+    }
+
+    /// Process one sample with the output limited to [min, max]
+    ///
+    /// Conditional integration: while the output is saturated, section
+    /// states are only updated when they move the output back toward the
+    /// allowed range, so integrators do not wind up during startup or
+    /// large transients.
+    pub fn step_clamped(&mut self, input: f64, min: f64, max: f64) -> f64 {
+        let mut signal = input * self.gain;
+        let mut next_states = Vec::with_capacity(self.sections.len());
+        for section in &self.sections {
+            let (y, next) = section.advance(signal);
+            next_states.push(next);
+            signal = y;
+        }
+
+        let winding_up = (signal > max && signal > self.last_output)
+            || (signal < min && signal < self.last_output);
+        if !winding_up {
+            for (section, next) in self.sections.iter_mut().zip(next_states) {
+                section.state = next;
+            }
+            self.last_output = signal;
+        }
+        signal.clamp(min, max)
+    }
+
+    /// Frequency response of the discrete compensator at f (Hz)
+    pub fn evaluate(&self, freq_hz: f64) -> Complex {
+        let z = Complex::from_polar(1.0, 2.0 * PI * freq_hz * self.sample_time);
+        let z2 = z * z;
+        let mut h = Complex::new(self.gain, 0.0);
+        for section in &self.sections {
+            let b = Complex::new(section.b[0], 0.0) * z2
+                + Complex::new(section.b[1], 0.0) * z
+                + Complex::new(section.b[2], 0.0);
+            let a = z2 + Complex::new(section.a[0], 0.0) * z + Complex::new(section.a[1], 0.0);
+            h = h * b / a;
+        }
+        h
+    }
+}
+
+/// Group roots into real polynomial factors [c0, c1, c2] (ascending powers)
+///
+/// Conjugate pairs become s² - 2Re(r)s + |r|², real roots are paired into
+/// quadratics, and an odd real root is left as a linear factor (s - r).
+fn real_factors(roots: &[Complex]) -> Result<Vec<[f64; 3]>, String> {
+    let mut factors = Vec::new();
+    let mut real = Vec::new();
+    let mut unpaired = 0i32;
+    for root in roots {
+        if root.im.abs() <= 1e-9 * root.magnitude().max(1.0) {
+            real.push(root.re);
+        } else if root.im > 0.0 {
+            unpaired += 1;
+            factors.push([root.re * root.re + root.im * root.im, -2.0 * root.re, 1.0]);
+        } else {
+            unpaired -= 1;
+        }
+    }
+    if unpaired != 0 {
+        return Err("Complex roots must come in conjugate pairs".to_string());
+    }
+    for pair in real.chunks(2) {
+        match *pair {
+            [r1, r2] => factors.push([r1 * r2, -(r1 + r2), 1.0]),
+            [r] => factors.push([-r, 1.0, 0.0]),
+            _ => unreachable!(),
+        }
+    }
+    Ok(factors)
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -811,4 +1029,57 @@ mod tests {
         // fp = 1/(2π × 47kΩ × 22pF) ≈ 154 kHz
         assert!(fp > 100e3 && fp < 200e3);
     }
+
+    #[test]
+    fn test_discrete_compensator_matches_continuous() {
+        let plant = BuckSmallSignal::new(12.0, 5.0, 2.0, 10e-6, 100e-6, 0.02, 500e3);
+        let req = CompensatorRequirements {
+            crossover_freq: 30e3,
+            ..Default::default()
+        };
+        let design =
+            design_compensator(&plant.control_to_output(), &req, CompensatorType::TypeIII).unwrap();
+        let digital = DiscreteCompensator::from_design(&design, 1e-7).unwrap();
+
+        // Bilinear warping is negligible far below the 10 MHz sample rate
+        for f in [100.0, 3e3, 30e3, 100e3] {
+            let analog = design.transfer_function.evaluate(f);
+            let discrete = digital.evaluate(f);
+            assert!((discrete.magnitude() / analog.magnitude() - 1.0).abs() < 0.01);
+            assert!((discrete.phase_deg() - analog.phase_deg()).abs() < 1.0);
+        }
+
+        // A complex pole pair survives discretization too
+        let resonator = TransferFunction::new(
+            1e8,
+            vec![],
+            vec![Complex::new(-1e3, 1e4), Complex::new(-1e3, -1e4)],
+        );
+        let digital = DiscreteCompensator::from_transfer_function(&resonator, 1e-6).unwrap();
+        let ratio = digital.evaluate(1.0).magnitude() / resonator.evaluate(1.0).magnitude();
+        assert!((ratio - 1.0).abs() < 1e-6);
+
+        let improper = TransferFunction::new(1.0, vec![Complex::new(-1.0, 0.0)], vec![]);
+        assert!(DiscreteCompensator::from_transfer_function(&improper, 1e-6).is_err());
+    }
+
+    #[test]
+    fn test_discrete_compensator_anti_windup() {
+        // Integrator 1000/s sampled at 1 ms: unit input adds 1 per sample
+        let integrator = TransferFunction::new(1000.0, vec![], vec![Complex::new(0.0, 0.0)]);
+        let mut free = DiscreteCompensator::from_transfer_function(&integrator, 1e-3).unwrap();
+        let mut clamped = free.clone();
+        for _ in 0..10 {
+            free.step(1.0);
+            clamped.step_clamped(1.0, -2.0, 2.0);
+        }
+        assert!((free.step(0.0) - 10.0).abs() < 1e-9);
+        assert_eq!(clamped.step_clamped(0.0, -2.0, 2.0), 2.0);
+
+        // Reversing the input leaves saturation immediately
+        assert!(clamped.step_clamped(-1.0, -2.0, 2.0) < 2.0);
+
+        clamped.reset();
+        assert_eq!(clamped.step(0.0), 0.0);
+    }
 }
@@ -23,6 +23,10 @@
 //! - Phase margin and gain margin calculation
 //! - Crossover frequency analysis
 //!
+//! ## Transient Response
+//! - Closed-loop output impedance and audio susceptibility
+//! - Load-step and line-step waveforms with overshoot, undershoot, settling
+//!
 //! # Example Usage
 //!
 //! ```rust
@@ -60,10 +64,14 @@
 //! ```
 
 pub mod compensator;
+pub mod polynomial;
 pub mod small_signal;
 pub mod stability;
+pub mod step_response;
 
 // Re-export main types
 pub use compensator::*;
+pub use polynomial::*;
 pub use small_signal::*;
 pub use stability::*;
+pub use step_response::*;
@@ -0,0 +1,214 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! Polynomial and root helpers for pole-zero transfer functions
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Shared by the closed-loop step response and by the SPICE pole-zero and
+//! vector-fitting analyses:
+//!
+//! - Expanding a root list into polynomial coefficients
+//! - Finding the roots of a real polynomial (Aberth-Ehrlich)
+//! - Cancelling coincident pole-zero pairs
+
+use super::small_signal::Complex;
+
+/// Maximum Aberth iterations per root solve
+const MAX_ROOT_ITERATIONS: usize = 500;
+
+/// Coefficients (ascending powers) of the monic Π(s - r)
+///
+/// Complex roots give complex coefficients; a root set closed under
+/// conjugation gives real coefficients up to rounding.
+pub fn poly_from_roots(roots: &[Complex]) -> Vec<Complex> {
+    let mut coeffs = vec![Complex::new(1.0, 0.0)];
+    for &root in roots {
+        let mut next = vec![Complex::new(0.0, 0.0); coeffs.len() + 1];
+        for (k, &c) in coeffs.iter().enumerate() {
+            next[k + 1] = next[k + 1] + c;
+            next[k] = next[k] - c * root;
+        }
+        coeffs = next;
+    }
+    coeffs
+}
+
+/// Remove pole-zero pairs closer than `tolerance` relative to their size
+///
+/// Each pole is matched against its closest remaining zero.
+pub fn cancel_common_roots(poles: &mut Vec<Complex>, zeros: &mut Vec<Complex>, tolerance: f64) {
+    let mut i = 0;
+    while i < poles.len() {
+        let p = poles[i];
+        let closest = zeros
+            .iter()
+            .enumerate()
+            .map(|(j, z)| (j, (p - *z).magnitude()))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        match closest {
+            Some((j, distance)) if distance <= tolerance * p.magnitude().max(1e-30) => {
+                poles.remove(i);
+                zeros.remove(j);
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// Horner evaluation of p(s) and p'(s)
+fn poly_eval(coeffs: &[f64], s: Complex) -> (Complex, Complex) {
+    let mut p = Complex::new(0.0, 0.0);
+    let mut dp = Complex::new(0.0, 0.0);
+    for &c in coeffs.iter().rev() {
+        dp = dp * s + p;
+        p = p * s + Complex::new(c, 0.0);
+    }
+    (p, dp)
+}
+
+/// Roots of a real polynomial (ascending coefficients)
+///
+/// Aberth-Ehrlich iteration on the frequency-scaled polynomial, so that
+/// loop roots spread over several decades stay well conditioned. Roots are
+/// returned as exact conjugate pairs or exactly real values.
+pub fn polynomial_roots(coeffs: &[f64]) -> Result<Vec<Complex>, String> {
+    let mut coeffs = coeffs.to_vec();
+    while coeffs.len() > 1 && coeffs.last() == Some(&0.0) {
+        coeffs.pop();
+    }
+    // Roots at the origin
+    let mut roots = Vec::new();
+    while coeffs.len() > 1 && coeffs[0] == 0.0 {
+        coeffs.remove(0);
+        roots.push(Complex::new(0.0, 0.0));
+    }
+    let n = coeffs.len() - 1;
+    if n == 0 {
+        return Ok(roots);
+    }
+
+    // s = w·σ with w the geometric mean root magnitude
+    let w = (coeffs[0] / coeffs[n]).abs().powf(1.0 / n as f64);
+    let mut scaled: Vec<f64> = coeffs
+        .iter()
+        .enumerate()
+        .map(|(k, &c)| c * w.powi(k as i32))
+        .collect();
+    let norm = scaled.iter().fold(0.0_f64, |m, c| m.max(c.abs()));
+    scaled.iter_mut().for_each(|c| *c /= norm);
+
+    let mut z: Vec<Complex> = (0..n)
+        .map(|k| Complex::from_polar(1.0, 0.4 + 2.0 * std::f64::consts::PI * k as f64 / n as f64))
+        .collect();
+    let mut converged = false;
+    for _ in 0..MAX_ROOT_ITERATIONS {
+        let mut largest_step: f64 = 0.0;
+        for i in 0..n {
+            let (p, dp) = poly_eval(&scaled, z[i]);
+            if p.magnitude() == 0.0 {
+                continue;
+            }
+            let ratio = p / dp;
+            let mut repulsion = Complex::new(0.0, 0.0);
+            for (j, &zj) in z.iter().enumerate() {
+                if j != i {
+                    repulsion = repulsion + (z[i] - zj).inv();
+                }
+            }
+            let step = ratio / (Complex::new(1.0, 0.0) - ratio * repulsion);
+            z[i] = z[i] - step;
+            largest_step = largest_step.max(step.magnitude() / z[i].magnitude().max(1e-300));
+        }
+        if largest_step < 1e-14 {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err("Polynomial root iteration did not converge".to_string());
+    }
+
+    let found: Vec<Complex> = z.into_iter().map(|r| r * w).collect();
+    roots.extend(conjugate_pairs(found));
+    Ok(roots)
+}
+
+/// Snap nearly-real roots onto the axis and average conjugate partners
+fn conjugate_pairs(roots: Vec<Complex>) -> Vec<Complex> {
+    let is_real = |r: &Complex| r.im.abs() <= 1e-8 * r.magnitude();
+    let mut lower: Vec<Complex> = roots
+        .iter()
+        .filter(|r| !is_real(r) && r.im < 0.0)
+        .copied()
+        .collect();
+    let mut result: Vec<Complex> = roots
+        .iter()
+        .filter(|r| is_real(r))
+        .map(|r| Complex::new(r.re, 0.0))
+        .collect();
+    for upper in roots.iter().filter(|r| !is_real(r) && r.im > 0.0) {
+        let partner = lower
+            .iter()
+            .enumerate()
+            .min_by(|a, b| {
+                let da = (*a.1 - upper.conj()).magnitude();
+                let db = (*b.1 - upper.conj()).magnitude();
+                da.total_cmp(&db)
+            })
+            .map(|(j, _)| j);
+        match partner {
+            Some(j) => {
+                let mean = (*upper + lower.swap_remove(j).conj()) * 0.5;
+                result.push(mean);
+                result.push(mean.conj());
+            }
+            None => result.push(Complex::new(upper.re, 0.0)),
+        }
+    }
+    result.extend(lower.into_iter().map(|r| Complex::new(r.re, 0.0)));
+    result
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polynomial_roots() {
+        // (s + 1)(s + 1e4)(s² + 2e5 s + 1e12): roots spread over six decades
+        let roots = vec![
+            Complex::new(-1.0, 0.0),
+            Complex::new(-1e4, 0.0),
+            Complex::new(-1e5, 9.949874e5),
+            Complex::new(-1e5, -9.949874e5),
+        ];
+        let coeffs: Vec<f64> = poly_from_roots(&roots).iter().map(|c| c.re).collect();
+        let found = polynomial_roots(&coeffs).unwrap();
+        assert_eq!(found.len(), 4);
+        for r in &roots {
+            let nearest = found
+                .iter()
+                .map(|f| (*f - *r).magnitude())
+                .fold(f64::INFINITY, f64::min);
+            assert!(nearest < 1e-8 * r.magnitude(), "missing root {:?}", r);
+        }
+        // Exact conjugates come back
+        let complex: Vec<_> = found.iter().filter(|r| r.im != 0.0).collect();
+        assert_eq!(complex.len(), 2);
+        assert_eq!(*complex[0], complex[1].conj());
+    }
+
+    #[test]
+    fn test_cancel_common_roots() {
+        let mut poles = vec![Complex::new(-1.0, 0.0), Complex::new(-1e3, 0.0)];
+        let mut zeros = vec![
+            Complex::new(-1e3 * (1.0 + 1e-9), 0.0),
+            Complex::new(5.0, 0.0),
+        ];
+        cancel_common_roots(&mut poles, &mut zeros, 1e-6);
+        assert_eq!(poles, vec![Complex::new(-1.0, 0.0)]);
+        assert_eq!(zeros, vec![Complex::new(5.0, 0.0)]);
+    }
+}
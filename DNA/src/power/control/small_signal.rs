@@ -165,6 +165,19 @@ impl TransferFunction {
         self
     }
 
+    /// Rescale the leading coefficient so that H(0) = gain
+    ///
+    /// The pole-zero form K×Π(s - z)/Π(s - p) has H(0) = K×Π(-z)/Π(-p),
+    /// which only equals K when every root sits at -1. Requires no poles or
+    /// zeros at the origin.
+    pub fn normalized_dc_gain(mut self, gain: f64) -> Self {
+        let h0 = self.evaluate(0.0).re;
+        if h0 != 0.0 && h0.is_finite() {
+            self.dc_gain *= gain / h0;
+        }
+        self
+    }
+
     /// Evaluate the transfer function at frequency f (Hz)
     pub fn evaluate(&self, freq_hz: f64) -> Complex {
         let s = Complex::jw(freq_hz);
@@ -279,6 +292,22 @@ pub struct BodeData {
     pub phases_deg: Vec<f64>,
 }
 
+/// s×L || (1/(s×C) + Rc) || R over the LC filter poles
+///
+/// = s×L×R(1 + s×Rc×C) / (s²×L×C(R + Rc) + s(L + R×Rc×C) + R), whose
+/// leading coefficient R×Rc/(R + Rc) does not depend on L
+fn lc_output_impedance(r: f64, rc: f64, c: f64, poles: Vec<Complex>) -> TransferFunction {
+    let (gain, zeros) = if rc > 0.0 {
+        (
+            r * rc / (r + rc),
+            vec![Complex::new(0.0, 0.0), Complex::new(-1.0 / (rc * c), 0.0)],
+        )
+    } else {
+        (1.0 / c, vec![Complex::new(0.0, 0.0)])
+    };
+    TransferFunction::new(gain, zeros, poles)
+}
+
 // ============================================================================
 // BUCK CONVERTER SMALL-SIGNAL MODEL
 // ============================================================================
@@ -343,9 +372,9 @@ impl BuckSmallSignal {
         // LC resonance: ω0 = 1/√(L×C×(1 + Rc/R))
         let w0 = 1.0 / (self.l * self.c * (1.0 + rc / r)).sqrt();
 
-        // Q factor: Q = R×√(C×(1+Rc/R)/L) / (1 + R/Rc×(1+Rc/R))
-        // Simplified: Q ≈ R × √(C/L) for small Rc
-        let q = r * (self.c / self.l).sqrt() / (1.0 + rc / r);
+        // Q factor from the denominator s²×LC(R+Rc) + s(L + R×Rc×C) + R:
+        // Q = ω0×LC(R+Rc) / (L + R×Rc×C), ≈ R×√(C/L) for small Rc
+        let q = w0 * self.l * self.c * (r + rc) / (self.l + r * rc * self.c);
 
         // Convert to complex poles
         let (p1, p2) = if q > 0.5 {
@@ -377,27 +406,25 @@ impl BuckSmallSignal {
     ///
     /// Same poles as control-to-output, but DC gain = D
     pub fn line_to_output(&self) -> TransferFunction {
-        let mut tf = self.control_to_output();
-        tf.dc_gain = self.duty;
+        let mut tf = self.control_to_output().normalized_dc_gain(self.duty);
         tf.description = "Buck Gvg(s)".to_string();
         tf
     }
 
     /// Output impedance Zout(s)
     ///
-    /// Zout(s) = (s×L + R_dson) || (1/(s×C) + Rc) || R
+    /// Zout(s) = s×L || (1/(s×C) + Rc) || R
+    ///
+    /// Zero at DC (the inductor ties the output to the source), peaks at
+    /// the LC resonance and flattens to Rc || R above the ESR zero.
     pub fn output_impedance(&self) -> TransferFunction {
-        // Simplified: dominated by capacitor ESR at high frequency
-        // Zout ≈ Rc + 1/(s×C) at high freq, limited by R at DC
-        let wz = 1.0 / (self.esr * self.c);
-        let wp = 1.0 / (self.r_load * self.c);
-
-        TransferFunction {
-            dc_gain: self.r_load,
-            zeros: vec![Complex::new(-wz, 0.0)],
-            poles: vec![Complex::new(-wp, 0.0)],
-            description: "Buck Zout(s)".to_string(),
-        }
+        lc_output_impedance(
+            self.r_load,
+            self.esr,
+            self.c,
+            self.control_to_output().poles,
+        )
+        .with_description("Buck Zout(s)")
     }
 
     /// Get characteristic frequencies
@@ -499,11 +526,12 @@ impl BoostSmallSignal {
         // ESR zero (LHP - helpful)
         let w_esr = 1.0 / (rc * self.c);
 
-        // Resonant frequency (shifted by D')
-        let w0 = d_prime / (self.l * self.c).sqrt();
+        // Resonant frequency of the effective inductance Le = L/D'²
+        let le = self.l / (d_prime * d_prime);
+        let w0 = 1.0 / (le * self.c * (1.0 + rc / r)).sqrt();
 
-        // Q factor
-        let q = d_prime * r * (self.c / self.l).sqrt();
+        // Q factor (as for the buck with L → Le), ≈ D'×R×√(C/L) for small Rc
+        let q = w0 * le * self.c * (r + rc) / (le + r * rc * self.c);
 
         // DC gain
         let dc_gain = self.vout / d_prime;
@@ -532,6 +560,36 @@ impl BoostSmallSignal {
         }
     }
 
+    /// Line-to-output transfer function Gvg(s)
+    ///
+    /// Gvg(s) = (1/D') × (1 + s×Rc×C) / (1 + s×L/(D'²×R) + s²×L×C/D'²)
+    ///
+    /// No RHP zero: the input reaches the output through the inductor
+    pub fn line_to_output(&self) -> TransferFunction {
+        let d_prime = 1.0 - self.duty;
+        let w_esr = 1.0 / (self.esr * self.c);
+        TransferFunction::new(
+            1.0,
+            vec![Complex::new(-w_esr, 0.0)],
+            self.control_to_output().poles,
+        )
+        .normalized_dc_gain(1.0 / d_prime)
+        .with_description("Boost Gvg(s)")
+    }
+
+    /// Output impedance Zout(s)
+    ///
+    /// Zout(s) = s×Le || (1/(s×C) + Rc) || R with Le = L/D'²
+    pub fn output_impedance(&self) -> TransferFunction {
+        lc_output_impedance(
+            self.r_load,
+            self.esr,
+            self.c,
+            self.control_to_output().poles,
+        )
+        .with_description("Boost Zout(s)")
+    }
+
     /// Get the RHP zero frequency - THIS LIMITS BANDWIDTH
     pub fn rhp_zero_freq(&self) -> f64 {
         let d_prime = 1.0 - self.duty;
@@ -978,6 +1036,134 @@ impl ForwardSmallSignal {
     }
 }
 
+// ============================================================================
+// LLC RESONANT CONVERTER SMALL-SIGNAL MODEL
+// ============================================================================
+
+/// First-harmonic approximation (FHA) voltage gain of an LLC tank
+///
+/// M = Ln×fn² / √(((Ln+1)×fn² - 1)² + (fn² - 1)²×fn²×Q²×Ln²)
+/// with fn = fsw/fr, Ln = Lm/Lr and Q = √(Lr/Cr)/Rac. M = 1 at resonance
+/// for every load.
+pub fn llc_fha_gain(fn_: f64, ln: f64, q: f64) -> f64 {
+    let f2 = fn_ * fn_;
+    let re = (ln + 1.0) * f2 - 1.0;
+    let im = (f2 - 1.0) * fn_ * q * ln;
+    ln * f2 / (re * re + im * im).sqrt()
+}
+
+/// Small-signal model for the half-bridge LLC resonant converter
+///
+/// The control input is switching frequency, so Gvf(s) is in V/Hz. Well
+/// below the tank's beat frequency, tank and rectifier act as a current
+/// source into the output capacitor: one pole from R×C plus the ESR zero.
+/// Raising frequency lowers the output; the magnitude is reported and the
+/// inversion is left to the feedback network.
+#[derive(Clone, Debug)]
+pub struct LLCSmallSignal {
+    /// Input (bus) voltage (V)
+    pub vin: f64,
+    /// Output voltage (V)
+    pub vout: f64,
+    /// Output current (A)
+    pub iout: f64,
+    /// Output capacitor (F)
+    pub c: f64,
+    /// Capacitor ESR (Ω)
+    pub esr: f64,
+    /// Operating switching frequency (Hz)
+    pub fsw: f64,
+    /// Load resistance (Ω)
+    pub r_load: f64,
+    /// Resonant inductance (H)
+    pub lr: f64,
+    /// Resonant capacitance (F)
+    pub cr: f64,
+    /// Magnetizing inductance (H)
+    pub lm: f64,
+    /// Transformer turns ratio (Np/Ns)
+    pub n: f64,
+}
+
+impl LLCSmallSignal {
+    /// Create a new LLC model; set the tank with `with_tank`
+    pub fn new(vin: f64, vout: f64, iout: f64, c: f64, esr: f64, fsw: f64) -> Self {
+        let r_load = if iout > 0.0 { vout / iout } else { 1e6 };
+        Self {
+            vin,
+            vout,
+            iout,
+            c,
+            esr,
+            fsw,
+            r_load,
+            lr: 0.0,
+            cr: 0.0,
+            lm: 0.0,
+            // Half-bridge at resonance: Vout = Vin / (2n)
+            n: vin / (2.0 * vout),
+        }
+    }
+
+    /// Set the resonant tank and turns ratio (Np/Ns)
+    pub fn with_tank(mut self, lr: f64, cr: f64, lm: f64, n: f64) -> Self {
+        self.lr = lr;
+        self.cr = cr;
+        self.lm = lm;
+        self.n = n;
+        self
+    }
+
+    /// Series resonant frequency fr = 1/(2π√(Lr×Cr))
+    pub fn resonant_freq(&self) -> f64 {
+        1.0 / (2.0 * PI * (self.lr * self.cr).sqrt())
+    }
+
+    /// Tank quality factor Q = √(Lr/Cr) / Rac, with Rac = 8n²R/π²
+    pub fn quality_factor(&self) -> f64 {
+        let rac = 8.0 * self.n * self.n * self.r_load / (PI * PI);
+        (self.lr / self.cr).sqrt() / rac
+    }
+
+    /// FHA voltage gain at a switching frequency
+    pub fn voltage_gain(&self, freq: f64) -> f64 {
+        llc_fha_gain(
+            freq / self.resonant_freq(),
+            self.lm / self.lr,
+            self.quality_factor(),
+        )
+    }
+
+    /// Slope of output voltage against switching frequency (V/Hz)
+    pub fn frequency_gain(&self) -> f64 {
+        let df = self.fsw * 1e-4;
+        let dm = self.voltage_gain(self.fsw + df) - self.voltage_gain(self.fsw - df);
+        self.vin / (2.0 * self.n) * dm / (2.0 * df)
+    }
+
+    /// Control-to-output transfer function Gvf(s) in V/Hz
+    pub fn control_to_output(&self) -> TransferFunction {
+        // Output pole from R-C
+        let wp = 1.0 / (self.r_load * self.c);
+
+        // ESR zero
+        let wz = 1.0 / (self.esr * self.c);
+
+        TransferFunction::new(
+            1.0,
+            vec![Complex::new(-wz, 0.0)],
+            vec![Complex::new(-wp, 0.0)],
+        )
+        .normalized_dc_gain(self.frequency_gain().abs())
+        .with_description("LLC Gvf(s) - V/Hz, frequency control")
+    }
+
+    /// Highest sensible crossover - FHA breaks down near the beat frequency
+    pub fn max_crossover(&self) -> f64 {
+        self.fsw.min(self.resonant_freq()) / 10.0
+    }
+}
+
 // ============================================================================
 // CURRENT MODE CONTROL
 // ============================================================================
@@ -1205,6 +1391,47 @@ mod tests {
         assert!(tf.zeros[0].re > 0.0);
     }
 
+    #[test]
+    fn test_buck_output_impedance_and_audio_susceptibility() {
+        let buck = BuckSmallSignal::new(12.0, 5.0, 2.0, 10e-6, 100e-6, 0.02, 500e3);
+        let zout = buck.output_impedance();
+
+        // Direct evaluation of sL || (Rc + 1/sC) || R
+        for f in [100.0, 3e3, 5e3, 50e3, 1e6] {
+            let s = Complex::jw(f);
+            let y = (s * buck.l).inv()
+                + (Complex::new(buck.esr, 0.0) + (s * buck.c).inv()).inv()
+                + Complex::new(1.0 / buck.r_load, 0.0);
+            let direct = y.inv();
+            let model = zout.evaluate(f);
+            assert!((model - direct).magnitude() < 1e-9 * direct.magnitude());
+        }
+
+        // Gvg(0) = D, and shares the Gvd resonance
+        let gvg = buck.line_to_output();
+        assert!((gvg.evaluate(0.0).re - buck.duty).abs() < 1e-12);
+        assert_eq!(gvg.poles, buck.control_to_output().poles);
+    }
+
+    #[test]
+    fn test_boost_line_and_impedance_models() {
+        let boost = BoostSmallSignal::new(5.0, 12.0, 0.5, 22e-6, 100e-6, 0.05, 300e3);
+        let d_prime = 1.0 - boost.duty;
+
+        let gvg = boost.line_to_output();
+        assert!((gvg.evaluate(0.0).re - 1.0 / d_prime).abs() < 1e-9);
+        assert!(gvg.zeros.iter().all(|z| z.re < 0.0));
+
+        // Effective inductance L/D'² in parallel with the output network
+        let le = boost.l / (d_prime * d_prime);
+        let s = Complex::jw(2e3);
+        let y = (s * le).inv()
+            + (Complex::new(boost.esr, 0.0) + (s * boost.c).inv()).inv()
+            + Complex::new(1.0 / boost.r_load, 0.0);
+        let model = boost.output_impedance().evaluate(2e3);
+        assert!((model - y.inv()).magnitude() < 1e-9 * model.magnitude());
+    }
+
     #[test]
     fn test_flyback_ccm_vs_dcm() {
         // CCM flyback has RHP zero
@@ -1256,6 +1483,33 @@ mod tests {
         assert!(tf.zeros.iter().all(|z| z.re <= 0.0));
     }
 
+    #[test]
+    fn test_llc_fha_gain() {
+        // Unity gain at resonance regardless of load
+        for q in [0.1, 0.5, 1.0] {
+            assert!((llc_fha_gain(1.0, 6.0, q) - 1.0).abs() < 1e-12);
+        }
+        // Boost below resonance, buck above; lighter load boosts more
+        assert!(llc_fha_gain(0.7, 6.0, 0.3) > 1.0);
+        assert!(llc_fha_gain(1.5, 6.0, 0.3) < 1.0);
+        assert!(llc_fha_gain(0.7, 6.0, 0.1) > llc_fha_gain(0.7, 6.0, 0.3));
+
+        // 400V bus, 24V out, 100kHz tank (Lr=60µH, Cr=42nF), n = 8.33
+        let llc = LLCSmallSignal::new(400.0, 24.0, 10.0, 2e-3, 0.01, 90e3).with_tank(
+            60e-6,
+            42.2e-9,
+            360e-6,
+            400.0 / 48.0,
+        );
+        assert!((llc.resonant_freq() - 100e3).abs() < 200.0);
+
+        // Below resonance, raising frequency lowers the gain
+        assert!(llc.frequency_gain() < 0.0);
+        let tf = llc.control_to_output();
+        assert!((tf.evaluate(0.0).re - llc.frequency_gain().abs()).abs() < 1e-12);
+        assert!(llc.max_crossover() <= 9e3);
+    }
+
     #[test]
     fn test_bode_data() {
         let buck = BuckSmallSignal::new(12.0, 5.0, 2.0, 10e-6, 100e-6, 0.02, 500e3);
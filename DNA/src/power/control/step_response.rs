@@ -0,0 +1,601 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! Closed-loop load-step and line-step response prediction
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Predicts how a regulated converter reacts to disturbances:
+//!
+//! - **Load step**: ΔVout(s) = -Zout(s) / (1 + T(s)) × ΔIout / s
+//! - **Line step**: ΔVout(s) = Gvg(s) / (1 + T(s)) × ΔVin / s
+//!
+//! The closed-loop transfer function is formed exactly in pole-zero form:
+//! with T = K×N/D, F/(1 + T) has the zeros of F plus the poles of T, and the
+//! poles of F plus the roots of D + K×N. The time response is then obtained
+//! by running the rational function through the bilinear-transform cascade
+//! used for digital compensators.
+//!
+//! Design-level helpers (`buck_transient`, `boost_transient`) build the
+//! small-signal plant from a `BuckDesign`/`BoostDesign`, synthesize a Type III
+//! compensator and report overshoot, undershoot and settling time.
+
+use super::compensator::{
+    design_compensator, CompensatorDesign, CompensatorRequirements, CompensatorType,
+    DiscreteCompensator,
+};
+use super::polynomial::{cancel_common_roots, poly_from_roots, polynomial_roots};
+use super::small_signal::{BoostSmallSignal, BuckSmallSignal, TransferFunction};
+use super::stability::{LoopAnalysis, StabilityMetrics};
+use crate::power::types::{BoostDesign, BuckDesign};
+
+/// Relative distance below which a pole and zero cancel
+const CANCEL_TOLERANCE: f64 = 1e-6;
+
+// ============================================================================
+// CLOSED-LOOP TRANSFER FUNCTIONS
+// ============================================================================
+
+/// Closed-loop transfer function F(s) / (1 + T(s))
+///
+/// `forward` is the open-loop disturbance path (output impedance or
+/// audio susceptibility) and `loop_gain` the complete T(s) including
+/// modulator and feedback divider.
+pub fn closed_loop(
+    forward: &TransferFunction,
+    loop_gain: &TransferFunction,
+) -> Result<TransferFunction, String> {
+    if loop_gain.zeros.len() > loop_gain.poles.len() {
+        return Err("Loop gain has more zeros than poles".to_string());
+    }
+
+    // Characteristic polynomial D + K×N (ascending powers, D monic)
+    let mut characteristic: Vec<f64> = poly_from_roots(&loop_gain.poles)
+        .iter()
+        .map(|c| c.re)
+        .collect();
+    let numerator = poly_from_roots(&loop_gain.zeros);
+    for (c, n) in characteristic.iter_mut().zip(&numerator) {
+        *c += loop_gain.dc_gain * n.re;
+    }
+    let leading = *characteristic.last().unwrap_or(&1.0);
+    if leading == 0.0 {
+        return Err("Closed loop is improper (1 + T vanishes at high frequency)".to_string());
+    }
+    let closed_poles = polynomial_roots(&characteristic)?;
+
+    let mut zeros = forward.zeros.clone();
+    zeros.extend(loop_gain.poles.iter().copied());
+    let mut poles = forward.poles.clone();
+    poles.extend(closed_poles);
+    cancel_common_roots(&mut poles, &mut zeros, CANCEL_TOLERANCE);
+
+    Ok(TransferFunction {
+        dc_gain: forward.dc_gain / leading,
+        zeros,
+        poles,
+        description: format!("{} / (1 + T)", forward.description),
+    })
+}
+
+// ============================================================================
+// STEP RESPONSE
+// ============================================================================
+
+/// Time-domain step response settings
+#[derive(Clone, Debug)]
+pub struct StepResponseOptions {
+    /// Response window (s), 0 to size it from the slowest closed-loop pole
+    pub duration: f64,
+    /// Number of output samples
+    pub points: usize,
+    /// Settling band as a fraction of the nominal output (0.01 = ±1%)
+    pub settling_band: f64,
+}
+
+impl Default for StepResponseOptions {
+    fn default() -> Self {
+        Self {
+            duration: 0.0,
+            points: 500,
+            settling_band: 0.01,
+        }
+    }
+}
+
+/// Output voltage response to a step disturbance
+#[derive(Clone, Debug)]
+pub struct StepResponse {
+    /// Closed-loop transfer function from disturbance to ΔVout
+    pub closed_loop: TransferFunction,
+    /// Disturbance amplitude (A for load steps, V for line steps)
+    pub step: f64,
+    /// Output voltage before the step (V)
+    pub nominal: f64,
+    /// Time after the step (s)
+    pub time: Vec<f64>,
+    /// Output voltage waveform (V)
+    pub v_out: Vec<f64>,
+    /// Largest rise above nominal (V)
+    pub overshoot: f64,
+    /// Largest drop below nominal (V)
+    pub undershoot: f64,
+    /// Time of the largest excursion (s)
+    pub peak_time: f64,
+    /// Time until the output stays within the settling band (s)
+    pub settling_time: f64,
+    /// Steady-state output change after the step (V)
+    pub final_deviation: f64,
+}
+
+impl StepResponse {
+    /// Largest excursion from nominal in either direction (V)
+    pub fn peak_deviation(&self) -> f64 {
+        self.overshoot.max(self.undershoot)
+    }
+
+    /// Output deviation ΔVout(t) (V)
+    pub fn deviation(&self) -> Vec<f64> {
+        self.v_out.iter().map(|v| v - self.nominal).collect()
+    }
+}
+
+/// Response of `closed_loop` to a step of the given amplitude
+pub fn step_response(
+    closed_loop: TransferFunction,
+    amplitude: f64,
+    nominal: f64,
+    options: &StepResponseOptions,
+) -> Result<StepResponse, String> {
+    if options.points < 2 {
+        return Err("At least two response points are required".to_string());
+    }
+    if let Some(p) = closed_loop.poles.iter().find(|p| p.re >= 0.0) {
+        return Err(format!(
+            "Closed loop is unstable: pole at {:.3e} {:+.3e}j rad/s",
+            p.re, p.im
+        ));
+    }
+
+    let slowest = closed_loop
+        .poles
+        .iter()
+        .map(|p| -p.re)
+        .fold(f64::INFINITY, f64::min);
+    let fastest = closed_loop
+        .poles
+        .iter()
+        .map(|p| p.magnitude())
+        .fold(0.0_f64, f64::max);
+    let duration = if options.duration > 0.0 {
+        options.duration
+    } else if slowest.is_finite() {
+        (8.0 / slowest).min(1.0)
+    } else {
+        return Err("Response has no poles to size the window from".to_string());
+    };
+
+    // Oversample so the fastest pole is well resolved by the bilinear map
+    let dt_out = duration / (options.points - 1) as f64;
+    let oversample = (dt_out * fastest / 0.05).ceil().clamp(1.0, 1000.0) as usize;
+    let h = dt_out / oversample as f64;
+    let mut filter = DiscreteCompensator::from_transfer_function(&closed_loop, h)?;
+
+    // The trapezoidal rule sees the step as a ramp across the first
+    // sub-step, which delays the response by h/2; report times accordingly
+    let mut time = Vec::with_capacity(options.points);
+    let mut deviation = Vec::with_capacity(options.points);
+    time.push(0.0);
+    deviation.push(0.0);
+    for k in 1..options.points {
+        let mut y = 0.0;
+        for _ in 0..oversample {
+            y = filter.step(amplitude);
+        }
+        time.push(k as f64 * dt_out - 0.5 * h);
+        deviation.push(y);
+    }
+
+    let h0 = closed_loop.evaluate(0.0).re * amplitude;
+    let final_deviation = if h0.is_finite() {
+        h0
+    } else {
+        *deviation.last().unwrap_or(&0.0)
+    };
+
+    let overshoot = deviation.iter().fold(0.0_f64, |m, &d| m.max(d));
+    let undershoot = deviation.iter().fold(0.0_f64, |m, &d| m.max(-d));
+    let peak_index = deviation
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+        .map_or(0, |(i, _)| i);
+    let band = options.settling_band * nominal.abs();
+    let settling_time = match deviation
+        .iter()
+        .rposition(|d| (d - final_deviation).abs() > band)
+    {
+        None => 0.0,
+        Some(i) if i + 1 < time.len() => time[i + 1],
+        Some(_) => f64::INFINITY,
+    };
+
+    Ok(StepResponse {
+        closed_loop,
+        step: amplitude,
+        nominal,
+        v_out: deviation.iter().map(|d| nominal + d).collect(),
+        time: time.clone(),
+        overshoot,
+        undershoot,
+        peak_time: time[peak_index],
+        settling_time,
+        final_deviation,
+    })
+}
+
+/// Output response to a load current step ΔI (A, positive = more load)
+pub fn load_step_response(
+    output_impedance: &TransferFunction,
+    loop_gain: &TransferFunction,
+    vout: f64,
+    delta_i: f64,
+    options: &StepResponseOptions,
+) -> Result<StepResponse, String> {
+    let zcl = closed_loop(output_impedance, loop_gain)?;
+    // A load increase draws current out of the output node
+    step_response(zcl, -delta_i, vout, options).map(|mut r| {
+        r.step = delta_i;
+        r
+    })
+}
+
+/// Output response to an input voltage step ΔV (V)
+pub fn line_step_response(
+    line_to_output: &TransferFunction,
+    loop_gain: &TransferFunction,
+    vout: f64,
+    delta_v: f64,
+    options: &StepResponseOptions,
+) -> Result<StepResponse, String> {
+    let gcl = closed_loop(line_to_output, loop_gain)?;
+    step_response(gcl, delta_v, vout, options)
+}
+
+// ============================================================================
+// DESIGN-LEVEL PREDICTION
+// ============================================================================
+
+/// Conditions for predicting the transient response of a design
+#[derive(Clone, Debug)]
+pub struct TransientSpec {
+    /// Load step (A), 0 for half the rated output current
+    pub load_step_a: f64,
+    /// Input step (V), 0 for 10% of nominal input
+    pub line_step_v: f64,
+    /// Output capacitor ESR (Ω) - designs do not carry one
+    pub output_esr: f64,
+    /// Loop crossover (Hz), 0 for fsw/10 (and f_RHP/5 for boost)
+    pub crossover_freq: f64,
+    /// Target phase margin (degrees)
+    pub phase_margin_deg: f64,
+    /// Reference voltage (V)
+    pub vref: f64,
+    /// PWM ramp amplitude (V)
+    pub ramp_amplitude: f64,
+    /// Settling band as a fraction of Vout
+    pub settling_band: f64,
+}
+
+impl Default for TransientSpec {
+    fn default() -> Self {
+        Self {
+            load_step_a: 0.0,
+            line_step_v: 0.0,
+            output_esr: 0.01,
+            crossover_freq: 0.0,
+            phase_margin_deg: 55.0,
+            vref: 0.8,
+            ramp_amplitude: 1.0,
+            settling_band: 0.01,
+        }
+    }
+}
+
+/// Predicted closed-loop transient behaviour of a designed converter
+#[derive(Clone, Debug)]
+pub struct ConverterTransient {
+    /// Control-to-output plant Gvd(s), normalized to its DC gain
+    pub plant: TransferFunction,
+    /// Synthesized compensator
+    pub compensator: CompensatorDesign,
+    /// Requirements used for the compensator (divider, modulator)
+    pub requirements: CompensatorRequirements,
+    /// Loop stability margins
+    pub stability: StabilityMetrics,
+    /// Response to the load step
+    pub load_step: StepResponse,
+    /// Response to the line step
+    pub line_step: StepResponse,
+}
+
+/// Predict load- and line-step response of a buck design at nominal input
+pub fn buck_transient(
+    design: &BuckDesign,
+    spec: &TransientSpec,
+) -> Result<ConverterTransient, String> {
+    let req = &design.requirements;
+    let model = BuckSmallSignal::new(
+        req.vin.nom_v,
+        req.vout,
+        req.iout_max,
+        design.inductor.selected_value,
+        design.output_capacitor.selected_value,
+        spec.output_esr,
+        req.switching_freq_hz,
+    );
+    let plant = model.control_to_output().normalized_dc_gain(model.vin);
+    let max_crossover = req.switching_freq_hz / 10.0;
+    predict(
+        plant,
+        &model.output_impedance(),
+        &model.line_to_output(),
+        Operating {
+            vin: req.vin.nom_v,
+            vout: req.vout,
+            iout: req.iout_max,
+            fsw: req.switching_freq_hz,
+            max_crossover,
+        },
+        spec,
+    )
+}
+
+/// Predict load- and line-step response of a boost design at nominal input
+pub fn boost_transient(
+    design: &BoostDesign,
+    spec: &TransientSpec,
+) -> Result<ConverterTransient, String> {
+    let req = &design.requirements;
+    let model = BoostSmallSignal::new(
+        req.vin.nom_v,
+        req.vout,
+        req.iout_max,
+        design.inductor.selected_value,
+        design.output_capacitor.selected_value,
+        spec.output_esr,
+        req.switching_freq_hz,
+    );
+    let plant = model
+        .control_to_output()
+        .normalized_dc_gain(model.vout / (1.0 - model.duty));
+    let max_crossover = (req.switching_freq_hz / 10.0).min(model.max_crossover());
+    predict(
+        plant,
+        &model.output_impedance(),
+        &model.line_to_output(),
+        Operating {
+            vin: req.vin.nom_v,
+            vout: req.vout,
+            iout: req.iout_max,
+            fsw: req.switching_freq_hz,
+            max_crossover,
+        },
+        spec,
+    )
+}
+
+/// Nominal operating point shared by the design-level helpers
+struct Operating {
+    vin: f64,
+    vout: f64,
+    iout: f64,
+    fsw: f64,
+    max_crossover: f64,
+}
+
+fn predict(
+    plant: TransferFunction,
+    output_impedance: &TransferFunction,
+    line_to_output: &TransferFunction,
+    op: Operating,
+    spec: &TransientSpec,
+) -> Result<ConverterTransient, String> {
+    let requirements = CompensatorRequirements {
+        crossover_freq: if spec.crossover_freq > 0.0 {
+            spec.crossover_freq
+        } else {
+            op.max_crossover
+        },
+        phase_margin_deg: spec.phase_margin_deg,
+        vref: spec.vref,
+        divider_ratio: spec.vref / op.vout,
+        modulator_gain: 1.0 / spec.ramp_amplitude,
+        ..Default::default()
+    };
+    let compensator = design_compensator(&plant, &requirements, CompensatorType::TypeIII)?;
+    let analysis = LoopAnalysis::new(
+        plant.clone(),
+        compensator.transfer_function.clone(),
+        requirements.modulator_gain,
+        requirements.divider_ratio,
+        op.fsw,
+    );
+
+    let options = StepResponseOptions {
+        settling_band: spec.settling_band,
+        ..Default::default()
+    };
+    let delta_i = if spec.load_step_a > 0.0 {
+        spec.load_step_a
+    } else {
+        0.5 * op.iout
+    };
+    let delta_v = if spec.line_step_v > 0.0 {
+        spec.line_step_v
+    } else {
+        0.1 * op.vin
+    };
+    let load_step = load_step_response(
+        output_impedance,
+        &analysis.loop_gain,
+        op.vout,
+        delta_i,
+        &options,
+    )?;
+    let line_step = line_step_response(
+        line_to_output,
+        &analysis.loop_gain,
+        op.vout,
+        delta_v,
+        &options,
+    )?;
+
+    Ok(ConverterTransient {
+        plant,
+        compensator,
+        requirements,
+        stability: analysis.stability,
+        load_step,
+        line_step,
+    })
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::control::Complex;
+
+    #[test]
+    fn test_first_order_closed_loop() {
+        // F = 1/(s + 1), T = 9/(s + 1): F/(1 + T) = 1/(s + 10)
+        let f = TransferFunction::new(1.0, vec![], vec![Complex::new(-1.0, 0.0)]);
+        let t = TransferFunction::new(9.0, vec![], vec![Complex::new(-1.0, 0.0)]);
+        let cl = closed_loop(&f, &t).unwrap();
+        assert_eq!(cl.zeros.len(), 0);
+        assert_eq!(cl.poles.len(), 1);
+        assert!((cl.poles[0].re + 10.0).abs() < 1e-9);
+
+        // Step response 0.1·(1 - e^(-10t))
+        let options = StepResponseOptions {
+            duration: 1.0,
+            points: 101,
+            settling_band: 0.01,
+        };
+        let response = step_response(cl, 1.0, 1.0, &options).unwrap();
+        for (t, v) in response.time.iter().zip(&response.v_out) {
+            let expected = 1.0 + 0.1 * (1.0 - (-10.0 * t).exp());
+            assert!((v - expected).abs() < 1e-4);
+        }
+        assert!((response.final_deviation - 0.1).abs() < 1e-12);
+        assert!((response.overshoot - 0.1).abs() < 1e-4);
+        // |Δ - 0.1| < 0.01 once e^(-10t) < 0.1
+        assert!((response.settling_time - 0.24).abs() < 0.011);
+    }
+
+    #[test]
+    fn test_unstable_loop_is_rejected() {
+        // Positive feedback: T = -2/(s + 1) puts the closed-loop pole at +1
+        let f = TransferFunction::new(1.0, vec![], vec![Complex::new(-1.0, 0.0)]);
+        let t = TransferFunction::new(-2.0, vec![], vec![Complex::new(-1.0, 0.0)]);
+        let cl = closed_loop(&f, &t).unwrap();
+        assert!(step_response(cl, 1.0, 1.0, &StepResponseOptions::default()).is_err());
+    }
+
+    fn buck_design() -> BuckDesign {
+        crate::power::quick_buck(12.0, 5.0, 2.0, 500e3).unwrap()
+    }
+
+    #[test]
+    fn test_buck_load_step() {
+        let design = buck_design();
+        let transient = buck_transient(&design, &TransientSpec::default()).unwrap();
+
+        assert!(transient.stability.phase_margin_deg > 30.0);
+        let load = &transient.load_step;
+        assert!((load.step - 1.0).abs() < 1e-12);
+
+        // Integral action: the dip recovers fully
+        assert!(load.undershoot > 0.0);
+        assert!(load.final_deviation.abs() < 1e-9);
+        assert!((load.v_out.last().unwrap() - 5.0).abs() < 0.01 * 5.0);
+        assert!(load.settling_time.is_finite());
+        assert!(load.peak_time < load.settling_time);
+
+        // Classic estimate ΔV ≈ ΔI / (2π·fc·C) to within a factor of two
+        let c = design.output_capacitor.selected_value;
+        let estimate =
+            1.0 / (2.0 * std::f64::consts::PI * transient.requirements.crossover_freq * c);
+        assert!(load.undershoot > 0.5 * estimate && load.undershoot < 2.0 * estimate);
+
+        // Line step raises the output momentarily, then returns
+        let line = &transient.line_step;
+        assert!(line.overshoot > 0.0);
+        assert!(line.final_deviation.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_load_step_matches_switched_simulation() {
+        use crate::power::switched::{
+            simulate_switched, DutyControl, SwitchedCircuit, SwitchedEvent, SwitchedOptions,
+            VoltageLoop,
+        };
+
+        let design = buck_design();
+        let spec = TransientSpec {
+            output_esr: 0.02,
+            crossover_freq: 20e3,
+            ..Default::default()
+        };
+        let transient = buck_transient(&design, &spec).unwrap();
+
+        let circuit = SwitchedCircuit::from_buck_design(&design).with_parasitics(0.0, 0.02);
+        let control = DutyControl::Voltage(VoltageLoop::from_design(
+            &transient.compensator,
+            &transient.requirements,
+        ));
+        let step_time = 1.5e-3;
+        let options = SwitchedOptions {
+            duration: 2.5e-3,
+            initial_il: 2.0,
+            initial_vc: 5.0,
+            events: vec![SwitchedEvent::LoadStep {
+                time: step_time,
+                resistance: 5.0 / 3.0,
+            }],
+            ..Default::default()
+        };
+        let result = simulate_switched(&circuit, &control, &options).unwrap();
+        let simulated = result.step_metrics(step_time, 5.0, 0.01);
+
+        // Cycle averages smooth the peak a little; agreement within 30%
+        let predicted = transient.load_step.undershoot;
+        let ratio = simulated.undershoot / predicted;
+        assert!(
+            ratio > 0.7 && ratio < 1.3,
+            "sim {} vs predicted {}",
+            simulated.undershoot,
+            predicted
+        );
+        assert!(simulated.settled);
+    }
+
+    #[test]
+    fn test_boost_transient_respects_rhp_zero() {
+        let design = crate::power::quick_boost(5.0, 12.0, 0.5, 300e3).unwrap();
+        let transient = boost_transient(&design, &TransientSpec::default()).unwrap();
+        let model = BoostSmallSignal::new(
+            5.0,
+            12.0,
+            0.5,
+            design.inductor.selected_value,
+            design.output_capacitor.selected_value,
+            0.01,
+            300e3,
+        );
+        assert!(transient.requirements.crossover_freq <= model.max_crossover() + 1e-9);
+        assert!(transient.load_step.undershoot > 0.0);
+        assert!(transient.load_step.final_deviation.abs() < 1e-9);
+    }
+}
@@ -0,0 +1,1499 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: library.rs | DNA/src/power/library.rs
+//! PURPOSE: Vendor part library import (CSV/JSON) merged with the built-in parts
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Loads MOSFETs, diodes, core materials and core geometries from vendor
+//! parameter files into the existing component structs, so an approved-vendor
+//! list can drive the designers instead of the small in-source databases.
+//! Pass the library to a designer's `_with` variant (`design_flyback_with`,
+//! `design_buck_with`, ...); the plain designers use `builtin_parts()`.
+//!
+//! # Values and units
+//!
+//! Every numeric field may be a bare number in the field's storage unit (see
+//! the struct docs, e.g. Ω for `rds_on_25c`, mm² for `ae`) or a string with a
+//! unit and optional SI prefix: `"4.5 mΩ"`, `"25nC"`, `"100 kHz"`, `"1.2 cm²"`.
+//! A unit of the wrong dimension is an error, so `"4.5 mV"` for an on-resistance
+//! is rejected rather than silently misread.
+//!
+//! # Curves
+//!
+//! Datasheet curves are reduced to the fields the loss models use:
+//!
+//! | Field            | Points              | Fills                           |
+//! |------------------|---------------------|---------------------------------|
+//! | `rds_on_vs_tj`   | (Tj, Rds(on))       | `rds_on_25c`, `rds_on_100c`     |
+//! | `coss_vs_vds`    | (Vds, Coss)         | `coss` at 25 V (log-log interp) |
+//! | `vf_vs_if`       | (If, Vf) at 25 °C   | `vf_typical` at rated `if_avg`  |
+//! | `vf_vs_tj`       | (Tj, Vf)            | `vf_temp_coeff` (least squares) |
+//! | `core_loss`      | (f, B, Pv)          | Steinmetz k, α, β (log fit)     |
+//!
+//! Explicit scalar fields take precedence over curves.
+//!
+//! # Formats
+//!
+//! JSON holds any of the arrays `mosfets`, `diodes`, `materials` and `cores`:
+//!
+//! ```json
+//! { "mosfets": [{ "part_number": "BSC070N10NS5", "package": "PowerPAK",
+//!                 "vds_max": "100 V", "id_continuous_25c": "80 A",
+//!                 "rds_on_vs_tj": [["25 °C", "6 mΩ"], ["100 °C", "9.6 mΩ"]],
+//!                 "qg_total": "35 nC", "coss": "410 pF" }] }
+//! ```
+//!
+//! CSV holds one part kind per file. Headers may carry a unit for bare
+//! numbers (`rds_on_25c [mΩ]`); curve cells are `x:y` points separated by `;`
+//! with per-axis header units (`rds_on_vs_tj [°C:mΩ]`).
+//!
+//! # Example
+//!
+//! ```rust
+//! use dna::power::{PartKind, PartLibrary};
+//!
+//! let csv = "part_number,diode_type,package,vrrm [V],if_avg [A],vf_typical [V]\n\
+//!            SS54,Schottky,DO214,40,5,0.5\n";
+//! let vendor = PartLibrary::from_csv(PartKind::Diode, csv).unwrap();
+//!
+//! let mut library = PartLibrary::builtin();
+//! library.merge(vendor);
+//! assert!(library.diodes.iter().any(|d| d.part_number == "SS54"));
+//! ```
+
+use std::sync::OnceLock;
+
+use serde_json::{Map, Value};
+
+use super::components::{
+    diode_database, mosfet_database, DiodePackage, DiodeSpec, DiodeType, MOSFETPackage, MOSFETSpec,
+};
+use super::magnetics::{
+    core_geometry_database, ferrite_database, powder_core_database, CoreGeometry, CoreMaterial,
+    CoreType, MaterialType,
+};
+
+// ============================================================================
+// PART LIBRARY
+// ============================================================================
+
+/// Kind of part held in a library file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartKind {
+    Mosfet,
+    Diode,
+    Material,
+    Core,
+}
+
+impl PartKind {
+    fn label(&self) -> &'static str {
+        match self {
+            PartKind::Mosfet => "MOSFET",
+            PartKind::Diode => "Diode",
+            PartKind::Material => "Material",
+            PartKind::Core => "Core",
+        }
+    }
+
+    /// Identifying field of a record
+    fn key_field(&self) -> &'static str {
+        match self {
+            PartKind::Material => "name",
+            _ => "part_number",
+        }
+    }
+}
+
+/// Collection of parts available to the designers
+#[derive(Clone, Debug, Default)]
+pub struct PartLibrary {
+    pub mosfets: Vec<MOSFETSpec>,
+    pub diodes: Vec<DiodeSpec>,
+    /// Ferrite and powder core materials
+    pub materials: Vec<CoreMaterial>,
+    pub cores: Vec<CoreGeometry>,
+}
+
+impl PartLibrary {
+    /// The built-in in-source databases
+    pub fn builtin() -> Self {
+        Self {
+            mosfets: mosfet_database(),
+            diodes: diode_database(),
+            materials: ferrite_database()
+                .into_iter()
+                .chain(powder_core_database())
+                .collect(),
+            cores: core_geometry_database(),
+        }
+    }
+
+    /// Load a JSON library with `mosfets`, `diodes`, `materials` and `cores` arrays
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let root: Value = serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+        let root = root
+            .as_object()
+            .ok_or("Library JSON must be an object of part arrays")?;
+
+        let mut library = Self::default();
+        let mut errors = Vec::new();
+        for (section, parts) in root {
+            let kind = match section.as_str() {
+                "mosfets" => PartKind::Mosfet,
+                "diodes" => PartKind::Diode,
+                "materials" => PartKind::Material,
+                "cores" => PartKind::Core,
+                other => {
+                    errors.push(format!("Unknown section '{}'", other));
+                    continue;
+                }
+            };
+            let Some(parts) = parts.as_array() else {
+                errors.push(format!("Section '{}' must be an array", section));
+                continue;
+            };
+            for (i, part) in parts.iter().enumerate() {
+                match part.as_object() {
+                    Some(fields) => {
+                        if let Err(e) = library.add_record(kind, fields, i) {
+                            errors.push(e);
+                        }
+                    }
+                    None => errors.push(format!("{} #{}: must be an object", kind.label(), i)),
+                }
+            }
+        }
+
+        library.finish(errors)
+    }
+
+    /// Load a CSV library holding one kind of part
+    pub fn from_csv(kind: PartKind, text: &str) -> Result<Self, String> {
+        let mut rows = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+        let header = rows.next().ok_or("CSV library is empty")?;
+        let columns: Vec<(String, Option<String>)> = split_csv_line(header)
+            .into_iter()
+            .map(|h| split_header(&h))
+            .collect();
+
+        let mut library = Self::default();
+        let mut errors = Vec::new();
+        for (i, row) in rows.enumerate() {
+            let cells = split_csv_line(row);
+            if cells.len() > columns.len() {
+                errors.push(format!(
+                    "Row {}: {} cells but {} columns",
+                    i + 1,
+                    cells.len(),
+                    columns.len()
+                ));
+                continue;
+            }
+            let mut fields = Map::new();
+            for ((name, unit), cell) in columns.iter().zip(cells) {
+                if cell.is_empty() {
+                    continue;
+                }
+                fields.insert(name.clone(), csv_value(name, unit.as_deref(), &cell));
+            }
+            if let Err(e) = library.add_record(kind, &fields, i) {
+                errors.push(e);
+            }
+        }
+
+        library.finish(errors)
+    }
+
+    /// Merge another library into this one
+    ///
+    /// Parts with a matching part number (material name) replace the existing
+    /// entry; new parts are appended.
+    pub fn merge(&mut self, other: PartLibrary) {
+        merge_by(&mut self.mosfets, other.mosfets, |m| &m.part_number);
+        merge_by(&mut self.diodes, other.diodes, |d| &d.part_number);
+        merge_by(&mut self.materials, other.materials, |m| &m.name);
+        merge_by(&mut self.cores, other.cores, |c| &c.part_number);
+    }
+
+    /// Check every part against plausible datasheet ranges
+    pub fn validate(&self) -> Result<(), String> {
+        let errors: Vec<String> = self
+            .mosfets
+            .iter()
+            .flat_map(validate_mosfet)
+            .chain(self.diodes.iter().flat_map(validate_diode))
+            .chain(self.materials.iter().flat_map(validate_material))
+            .chain(self.cores.iter().flat_map(validate_core))
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// Number of parts of all kinds
+    pub fn len(&self) -> usize {
+        self.mosfets.len() + self.diodes.len() + self.materials.len() + self.cores.len()
+    }
+
+    /// True if the library holds no parts
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// MnZn and NiZn ferrite materials
+    pub fn ferrites(&self) -> impl Iterator<Item = &CoreMaterial> {
+        self.materials.iter().filter(|m| {
+            matches!(
+                m.material_type,
+                MaterialType::MnZnFerrite | MaterialType::NiZnFerrite
+            )
+        })
+    }
+
+    /// Distributed-gap powder materials
+    pub fn powders(&self) -> impl Iterator<Item = &CoreMaterial> {
+        self.materials.iter().filter(|m| {
+            matches!(
+                m.material_type,
+                MaterialType::IronPowder
+                    | MaterialType::Sendust
+                    | MaterialType::MPP
+                    | MaterialType::HighFlux
+            )
+        })
+    }
+
+    fn add_record(
+        &mut self,
+        kind: PartKind,
+        fields: &Map<String, Value>,
+        index: usize,
+    ) -> Result<(), String> {
+        let record = Record::new(kind, fields, index)?;
+        match kind {
+            PartKind::Mosfet => self.mosfets.push(mosfet_from_record(&record)?),
+            PartKind::Diode => self.diodes.push(diode_from_record(&record)?),
+            PartKind::Material => self.materials.push(material_from_record(&record)?),
+            PartKind::Core => self.cores.push(core_from_record(&record)?),
+        }
+        Ok(())
+    }
+
+    fn finish(self, mut errors: Vec<String>) -> Result<Self, String> {
+        if let Err(e) = self.validate() {
+            errors.push(e);
+        }
+        if errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+fn merge_by<T, F>(existing: &mut Vec<T>, incoming: Vec<T>, key: F)
+where
+    F: Fn(&T) -> &String,
+{
+    for part in incoming {
+        match existing.iter().position(|p| key(p) == key(&part)) {
+            Some(i) => existing[i] = part,
+            None => existing.push(part),
+        }
+    }
+}
+
+// ============================================================================
+// BUILT-IN PARTS
+// ============================================================================
+
+/// Shared copy of `PartLibrary::builtin()`, used by the designers that are
+/// not given a library
+pub fn builtin_parts() -> &'static PartLibrary {
+    static BUILTIN: OnceLock<PartLibrary> = OnceLock::new();
+    BUILTIN.get_or_init(PartLibrary::builtin)
+}
+
+// ============================================================================
+// UNITS
+// ============================================================================
+
+/// Storage unit of a library field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    Volt,
+    Ampere,
+    Ohm,
+    Coulomb,
+    Farad,
+    Second,
+    Hertz,
+    Tesla,
+    Celsius,
+    CelsiusPerWatt,
+    MilliVoltPerCelsius,
+    OhmCentimetre,
+    WattPerCubicCentimetre,
+    GramPerCubicCentimetre,
+    Gram,
+    Millimetre,
+    SquareMillimetre,
+    CubicMillimetre,
+    SquareCentimetre,
+    NanoHenry,
+    /// Plain number (permeability, Steinmetz coefficients)
+    Number,
+}
+
+impl Unit {
+    /// Accepted symbols: (symbol, scale to storage unit, SI prefix exponent)
+    ///
+    /// A prefix exponent of 0 forbids prefixes; 2 and 3 square and cube the
+    /// prefix for areas and volumes.
+    fn symbols(&self) -> &'static [(&'static str, f64, i32)] {
+        match self {
+            Unit::Volt => &[("V", 1.0, 1)],
+            Unit::Ampere => &[("A", 1.0, 1)],
+            Unit::Ohm => &[("Ω", 1.0, 1), ("ohm", 1.0, 1), ("Ohm", 1.0, 1)],
+            Unit::Coulomb => &[("C", 1.0, 1)],
+            Unit::Farad => &[("F", 1.0, 1)],
+            Unit::Second => &[("s", 1.0, 1)],
+            Unit::Hertz => &[("Hz", 1.0, 1)],
+            Unit::Tesla => &[("T", 1.0, 1)],
+            Unit::Celsius => &[("°C", 1.0, 0), ("degC", 1.0, 0)],
+            Unit::CelsiusPerWatt => &[("°C/W", 1.0, 0), ("K/W", 1.0, 0)],
+            Unit::MilliVoltPerCelsius => &[("V/°C", 1e3, 1), ("V/K", 1e3, 1)],
+            Unit::OhmCentimetre => &[
+                ("Ω·cm", 1.0, 0),
+                ("ohm-cm", 1.0, 0),
+                ("Ω·m", 100.0, 0),
+                ("ohm-m", 100.0, 0),
+            ],
+            Unit::WattPerCubicCentimetre => &[
+                ("W/cm³", 1.0, 1),
+                ("W/cm^3", 1.0, 1),
+                ("W/m³", 1e-6, 1),
+                ("W/m^3", 1e-6, 1),
+            ],
+            Unit::GramPerCubicCentimetre => {
+                &[("g/cm³", 1.0, 0), ("g/cm^3", 1.0, 0), ("kg/m³", 1e-3, 0)]
+            }
+            Unit::Gram => &[("g", 1.0, 1)],
+            Unit::Millimetre => &[("m", 1e3, 1)],
+            Unit::SquareMillimetre => &[("m²", 1e6, 2), ("m^2", 1e6, 2)],
+            Unit::CubicMillimetre => &[("m³", 1e9, 3), ("m^3", 1e9, 3)],
+            Unit::SquareCentimetre => &[("m²", 1e4, 2), ("m^2", 1e4, 2)],
+            Unit::NanoHenry => &[("H", 1e9, 1), ("H/N²", 1e9, 1), ("H/turn²", 1e9, 1)],
+            Unit::Number => &[],
+        }
+    }
+
+    /// Display symbol of the storage unit
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Unit::Volt => "V",
+            Unit::Ampere => "A",
+            Unit::Ohm => "Ω",
+            Unit::Coulomb => "C",
+            Unit::Farad => "F",
+            Unit::Second => "s",
+            Unit::Hertz => "Hz",
+            Unit::Tesla => "T",
+            Unit::Celsius => "°C",
+            Unit::CelsiusPerWatt => "°C/W",
+            Unit::MilliVoltPerCelsius => "mV/°C",
+            Unit::OhmCentimetre => "Ω·cm",
+            Unit::WattPerCubicCentimetre => "W/cm³",
+            Unit::GramPerCubicCentimetre => "g/cm³",
+            Unit::Gram => "g",
+            Unit::Millimetre => "mm",
+            Unit::SquareMillimetre => "mm²",
+            Unit::CubicMillimetre => "mm³",
+            Unit::SquareCentimetre => "cm²",
+            Unit::NanoHenry => "nH",
+            Unit::Number => "(number)",
+        }
+    }
+
+    /// Scale factor from `text` to the storage unit
+    fn scale(&self, text: &str) -> Option<f64> {
+        let symbols = self.symbols();
+        if let Some(&(_, scale, _)) = symbols.iter().find(|(s, _, _)| *s == text) {
+            return Some(scale);
+        }
+        symbols.iter().find_map(|&(symbol, scale, power)| {
+            if power == 0 {
+                return None;
+            }
+            let prefix = text.strip_suffix(symbol)?;
+            Some(scale * si_prefix(prefix)?.powi(power))
+        })
+    }
+}
+
+fn si_prefix(prefix: &str) -> Option<f64> {
+    Some(match prefix {
+        "p" => 1e-12,
+        "n" => 1e-9,
+        "u" | "µ" | "μ" => 1e-6,
+        "m" => 1e-3,
+        "c" => 1e-2,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        _ => return None,
+    })
+}
+
+/// Parse `"4.5 mΩ"`-style text into the storage unit; bare numbers pass through
+pub fn parse_quantity(text: &str, unit: Unit) -> Result<f64, String> {
+    let text = text.trim();
+    let bytes = text.as_bytes();
+    let mut end = 0;
+    while end < bytes.len() {
+        let c = bytes[end];
+        let exponent = (c == b'e' || c == b'E')
+            && end > 0
+            && bytes
+                .get(end + 1)
+                .is_some_and(|&n| n.is_ascii_digit() || n == b'-' || n == b'+');
+        if c.is_ascii_digit() || c == b'.' || exponent {
+            end += if exponent { 2 } else { 1 };
+        } else if (c == b'-' || c == b'+') && end == 0 {
+            end += 1;
+        } else {
+            break;
+        }
+    }
+
+    let value: f64 = text[..end]
+        .parse()
+        .map_err(|_| format!("'{}' is not a number", text))?;
+    let suffix = text[end..].trim();
+    if suffix.is_empty() {
+        return Ok(value);
+    }
+    unit.scale(suffix).map(|s| value * s).ok_or_else(|| {
+        format!(
+            "'{}' has unit '{}', expected {}",
+            text,
+            suffix,
+            unit.symbol()
+        )
+    })
+}
+
+// ============================================================================
+// RECORDS
+// ============================================================================
+
+/// One part's fields, from a JSON object or a CSV row
+struct Record<'a> {
+    label: String,
+    fields: &'a Map<String, Value>,
+}
+
+impl<'a> Record<'a> {
+    fn new(kind: PartKind, fields: &'a Map<String, Value>, index: usize) -> Result<Self, String> {
+        let label = match fields.get(kind.key_field()).and_then(Value::as_str) {
+            Some(id) => format!("{} '{}'", kind.label(), id),
+            None => format!("{} #{}", kind.label(), index),
+        };
+        let record = Self { label, fields };
+        let known = known_fields(kind);
+        if let Some(unknown) = fields.keys().find(|k| !known.contains(&k.as_str())) {
+            return Err(record.error(format!("unknown field '{}'", unknown)));
+        }
+        Ok(record)
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> String {
+        format!("{}: {}", self.label, message)
+    }
+
+    fn text(&self, key: &str) -> Result<Option<String>, String> {
+        match self.fields.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s.trim().to_string())),
+            Some(other) => Ok(Some(other.to_string())),
+        }
+    }
+
+    fn required_text(&self, key: &str) -> Result<String, String> {
+        self.text(key)?
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| self.error(format!("missing '{}'", key)))
+    }
+
+    fn quantity(&self, key: &str, unit: Unit) -> Result<Option<f64>, String> {
+        match self.fields.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => value_quantity(value, unit)
+                .map(Some)
+                .map_err(|e| self.error(format!("{}: {}", key, e))),
+        }
+    }
+
+    fn required(&self, key: &str, unit: Unit) -> Result<f64, String> {
+        self.quantity(key, unit)?
+            .ok_or_else(|| self.error(format!("missing '{}'", key)))
+    }
+
+    /// Curve points with one value per unit, sorted by the first axis
+    fn points(&self, key: &str, units: &[Unit]) -> Result<Option<Vec<Vec<f64>>>, String> {
+        let Some(value) = self.fields.get(key) else {
+            return Ok(None);
+        };
+        let err = |m: String| self.error(format!("{}: {}", key, m));
+        let rows = value
+            .as_array()
+            .ok_or_else(|| err("must be a list of points".into()))?;
+
+        let mut points = Vec::with_capacity(rows.len());
+        for row in rows {
+            let row = row
+                .as_array()
+                .filter(|r| r.len() == units.len())
+                .ok_or_else(|| err(format!("each point needs {} values", units.len())))?;
+            let point = row
+                .iter()
+                .zip(units)
+                .map(|(v, &u)| value_quantity(v, u))
+                .collect::<Result<Vec<f64>, String>>()
+                .map_err(err)?;
+            points.push(point);
+        }
+        if points.len() < 2 {
+            return Err(err("needs at least two points".into()));
+        }
+        points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        Ok(Some(points))
+    }
+}
+
+fn value_quantity(value: &Value, unit: Unit) -> Result<f64, String> {
+    match value {
+        Value::Number(n) => n.as_f64().ok_or_else(|| "invalid number".to_string()),
+        Value::String(s) => parse_quantity(s, unit),
+        other => Err(format!("expected a value, found {}", other)),
+    }
+}
+
+fn known_fields(kind: PartKind) -> &'static [&'static str] {
+    match kind {
+        PartKind::Mosfet => &[
+            "part_number",
+            "manufacturer",
+            "package",
+            "vds_max",
+            "id_continuous_25c",
+            "id_continuous_100c",
+            "id_pulsed",
+            "rds_on_25c",
+            "rds_on_100c",
+            "rds_on_vs_tj",
+            "qg_total",
+            "qgd",
+            "qgs",
+            "qrr",
+            "coss",
+            "coss_vs_vds",
+            "vgs_th",
+            "vgs_max",
+            "rth_jc",
+            "rth_ja",
+            "tj_max",
+            "tr",
+            "tf",
+        ],
+        PartKind::Diode => &[
+            "part_number",
+            "manufacturer",
+            "diode_type",
+            "package",
+            "vrrm",
+            "vr",
+            "if_avg",
+            "if_rms",
+            "ifsm",
+            "vf_typical",
+            "vf_max",
+            "vf_vs_if",
+            "vf_temp_coeff",
+            "vf_vs_tj",
+            "trr",
+            "qrr",
+            "cj",
+            "ir_25c",
+            "ir_125c",
+            "rth_jc",
+            "rth_ja",
+            "tj_max",
+        ],
+        PartKind::Material => &[
+            "name",
+            "manufacturer",
+            "material_type",
+            "initial_permeability",
+            "bsat_25c",
+            "bsat_100c",
+            "curie_temp",
+            "resistivity",
+            "steinmetz_k",
+            "steinmetz_alpha",
+            "steinmetz_beta",
+            "core_loss",
+            "steinmetz_freq_min",
+            "steinmetz_freq_max",
+            "temp_coeff_permeability",
+            "density",
+        ],
+        PartKind::Core => &[
+            "part_number",
+            "core_type",
+            "ae",
+            "le",
+            "ve",
+            "al",
+            "window_area",
+            "bobbin_window",
+            "mlt",
+            "weight",
+            "surface_area",
+        ],
+    }
+}
+
+/// Curve fields and their axis units, used to expand CSV cells
+fn curve_units(field: &str) -> Option<&'static [Unit]> {
+    match field {
+        "rds_on_vs_tj" => Some(&[Unit::Celsius, Unit::Ohm]),
+        "coss_vs_vds" => Some(&[Unit::Volt, Unit::Farad]),
+        "vf_vs_if" => Some(&[Unit::Ampere, Unit::Volt]),
+        "vf_vs_tj" => Some(&[Unit::Celsius, Unit::Volt]),
+        "core_loss" => Some(&[Unit::Hertz, Unit::Tesla, Unit::WattPerCubicCentimetre]),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// CSV
+// ============================================================================
+
+/// Split one CSV line, honouring double-quoted cells
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+/// `"rds_on_25c [mΩ]"` → (`rds_on_25c`, `Some("mΩ")`)
+fn split_header(header: &str) -> (String, Option<String>) {
+    match header.split_once('[') {
+        Some((name, unit)) => (
+            name.trim().to_string(),
+            Some(unit.trim_end_matches(']').trim().to_string()),
+        ),
+        None => (header.trim().to_string(), None),
+    }
+}
+
+/// Apply a header unit to a bare number
+fn with_unit(cell: &str, unit: Option<&str>) -> String {
+    match unit {
+        Some(unit) if !unit.is_empty() && cell.trim().parse::<f64>().is_ok() => {
+            format!("{} {}", cell.trim(), unit)
+        }
+        _ => cell.trim().to_string(),
+    }
+}
+
+/// Convert a CSV cell to the JSON value the record reader expects
+fn csv_value(field: &str, unit: Option<&str>, cell: &str) -> Value {
+    if curve_units(field).is_none() {
+        return Value::String(with_unit(cell, unit));
+    }
+    let axis_units: Vec<&str> = unit.map(|u| u.split(':').collect()).unwrap_or_default();
+    let points = cell
+        .split(';')
+        .filter(|p| !p.trim().is_empty())
+        .map(|point| {
+            let values = point
+                .split(':')
+                .enumerate()
+                .map(|(i, v)| Value::String(with_unit(v, axis_units.get(i).copied())))
+                .collect();
+            Value::Array(values)
+        })
+        .collect();
+    Value::Array(points)
+}
+
+// ============================================================================
+// CURVE REDUCTION
+// ============================================================================
+
+/// Linear interpolation, extrapolating the end segments
+fn interpolate(points: &[Vec<f64>], x: f64) -> f64 {
+    let i = points
+        .windows(2)
+        .position(|w| x <= w[1][0])
+        .unwrap_or(points.len() - 2);
+    let (a, b) = (&points[i], &points[i + 1]);
+    if (b[0] - a[0]).abs() < f64::EPSILON {
+        return a[1];
+    }
+    a[1] + (x - a[0]) * (b[1] - a[1]) / (b[0] - a[0])
+}
+
+/// Interpolation on log-log axes (capacitance vs voltage)
+fn interpolate_log(points: &[Vec<f64>], x: f64) -> f64 {
+    if points.iter().any(|p| p[0] <= 0.0 || p[1] <= 0.0) {
+        return interpolate(points, x);
+    }
+    let logs: Vec<Vec<f64>> = points.iter().map(|p| vec![p[0].ln(), p[1].ln()]).collect();
+    interpolate(&logs, x.ln()).exp()
+}
+
+/// Least-squares slope dy/dx
+fn slope(points: &[Vec<f64>]) -> f64 {
+    let n = points.len() as f64;
+    let mx = points.iter().map(|p| p[0]).sum::<f64>() / n;
+    let my = points.iter().map(|p| p[1]).sum::<f64>() / n;
+    let sxy: f64 = points.iter().map(|p| (p[0] - mx) * (p[1] - my)).sum();
+    let sxx: f64 = points.iter().map(|p| (p[0] - mx).powi(2)).sum();
+    if sxx > 0.0 {
+        sxy / sxx
+    } else {
+        0.0
+    }
+}
+
+/// Fit Pv = k × f^α × B^β to (f, B, Pv) points by least squares in log space
+pub fn fit_steinmetz(points: &[(f64, f64, f64)]) -> Result<(f64, f64, f64), String> {
+    if points.len() < 3 {
+        return Err("Steinmetz fit needs at least three loss points".to_string());
+    }
+    if points
+        .iter()
+        .any(|&(f, b, p)| f <= 0.0 || b <= 0.0 || p <= 0.0)
+    {
+        return Err("Core loss points must be positive".to_string());
+    }
+
+    // Normal equations for ln Pv = ln k + α ln f + β ln B
+    let mut a = [[0.0; 3]; 3];
+    let mut rhs = [0.0; 3];
+    for &(f, b, p) in points {
+        let row = [1.0, f.ln(), b.ln()];
+        for i in 0..3 {
+            for j in 0..3 {
+                a[i][j] += row[i] * row[j];
+            }
+            rhs[i] += row[i] * p.ln();
+        }
+    }
+
+    let det = |m: &[[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let d = det(&a);
+    if d.abs() < 1e-12 {
+        return Err("Core loss points need at least two frequencies and two flux levels".into());
+    }
+    let solve = |col: usize| {
+        let mut m = a;
+        for (row, &r) in m.iter_mut().zip(&rhs) {
+            row[col] = r;
+        }
+        det(&m) / d
+    };
+
+    Ok((solve(0).exp(), solve(1), solve(2)))
+}
+
+// ============================================================================
+// PART BUILDERS
+// ============================================================================
+
+/// Normalize an enum name: `"TO-220"` → `"TO220"`
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+fn mosfet_package(name: &str) -> Option<MOSFETPackage> {
+    Some(match normalize(name).as_str() {
+        "TO220" => MOSFETPackage::TO220,
+        "TO220F" | "TO220FP" => MOSFETPackage::TO220F,
+        "TO247" => MOSFETPackage::TO247,
+        "D2PAK" | "TO263" => MOSFETPackage::D2PAK,
+        "DPAK" | "TO252" => MOSFETPackage::DPAK,
+        "SO8" | "SOIC8" => MOSFETPackage::SO8,
+        "POWERPAK" | "SUPERSO8" | "TDSON8" => MOSFETPackage::PowerPAK,
+        "QFN" | "DFN" => MOSFETPackage::QFN,
+        "LFPAK" | "LFPAK56" => MOSFETPackage::LFPAK,
+        _ => return None,
+    })
+}
+
+fn diode_package(name: &str) -> Option<DiodePackage> {
+    Some(match normalize(name).as_str() {
+        "AXIAL" | "DO41" | "DO201" | "DO201AD" => DiodePackage::Axial,
+        "DO214" | "SMA" | "SMB" | "SMC" => DiodePackage::DO214,
+        "TO220" | "TO220AC" => DiodePackage::TO220,
+        "TO247" => DiodePackage::TO247,
+        "D2PAK" | "TO263" => DiodePackage::D2PAK,
+        "DPAK" | "TO252" => DiodePackage::DPAK,
+        "POWERDI" | "POWERDI5" | "POWERDI123" => DiodePackage::PowerDI,
+        _ => return None,
+    })
+}
+
+fn diode_type(name: &str) -> Option<DiodeType> {
+    Some(match normalize(name).as_str() {
+        "STANDARD" | "RECTIFIER" => DiodeType::Standard,
+        "SCHOTTKY" => DiodeType::Schottky,
+        "FASTRECOVERY" | "FAST" => DiodeType::FastRecovery,
+        "ULTRAFAST" => DiodeType::Ultrafast,
+        "SICSCHOTTKY" | "SIC" => DiodeType::SiCSchottky,
+        _ => return None,
+    })
+}
+
+fn material_type(name: &str) -> Option<MaterialType> {
+    Some(match normalize(name).as_str() {
+        "MNZNFERRITE" | "MNZN" => MaterialType::MnZnFerrite,
+        "NIZNFERRITE" | "NIZN" => MaterialType::NiZnFerrite,
+        "IRONPOWDER" => MaterialType::IronPowder,
+        "SENDUST" | "KOOLMU" => MaterialType::Sendust,
+        "MPP" => MaterialType::MPP,
+        "HIGHFLUX" => MaterialType::HighFlux,
+        "AMORPHOUS" => MaterialType::Amorphous,
+        "NANOCRYSTALLINE" => MaterialType::Nanocrystalline,
+        _ => return None,
+    })
+}
+
+fn core_type(name: &str) -> Option<CoreType> {
+    Some(match normalize(name).as_str() {
+        "E" | "ECORE" | "EE" | "EI" => CoreType::ECore,
+        "ETD" => CoreType::ETD,
+        "EFD" => CoreType::EFD,
+        "PQ" => CoreType::PQ,
+        "RM" => CoreType::RM,
+        "POT" | "P" => CoreType::Pot,
+        "TOROID" | "T" => CoreType::Toroid,
+        "EQ" => CoreType::EQ,
+        "EP" => CoreType::EP,
+        _ => return None,
+    })
+}
+
+fn enum_field<T>(record: &Record, key: &str, parse: fn(&str) -> Option<T>) -> Result<T, String> {
+    let name = record.required_text(key)?;
+    parse(&name).ok_or_else(|| record.error(format!("unknown {} '{}'", key, name)))
+}
+
+fn mosfet_from_record(r: &Record) -> Result<MOSFETSpec, String> {
+    let package = enum_field(r, "package", mosfet_package)?;
+    let id_25c = r.required("id_continuous_25c", Unit::Ampere)?;
+    let qg_total = r.required("qg_total", Unit::Coulomb)?;
+
+    let rds_curve = r.points("rds_on_vs_tj", &[Unit::Celsius, Unit::Ohm])?;
+    let rds_on_25c = match (r.quantity("rds_on_25c", Unit::Ohm)?, &rds_curve) {
+        (Some(v), _) => v,
+        (None, Some(curve)) => interpolate(curve, 25.0),
+        (None, None) => return Err(r.error("missing 'rds_on_25c' or 'rds_on_vs_tj'")),
+    };
+    let rds_on_100c = match (r.quantity("rds_on_100c", Unit::Ohm)?, &rds_curve) {
+        (Some(v), _) => v,
+        (None, Some(curve)) => interpolate(curve, 100.0),
+        // Typical silicon temperature coefficient
+        (None, None) => rds_on_25c * 1.6,
+    };
+
+    let coss = match r.quantity("coss", Unit::Farad)? {
+        Some(v) => v,
+        None => match r.points("coss_vs_vds", &[Unit::Volt, Unit::Farad])? {
+            Some(curve) => interpolate_log(&curve, 25.0),
+            None => return Err(r.error("missing 'coss' or 'coss_vs_vds'")),
+        },
+    };
+
+    let d = MOSFETSpec::default();
+    Ok(MOSFETSpec {
+        part_number: r.required_text("part_number")?,
+        manufacturer: r.text("manufacturer")?.unwrap_or_default(),
+        vds_max: r.required("vds_max", Unit::Volt)?,
+        id_continuous_25c: id_25c,
+        id_continuous_100c: r
+            .quantity("id_continuous_100c", Unit::Ampere)?
+            .unwrap_or(id_25c * 0.7),
+        id_pulsed: r
+            .quantity("id_pulsed", Unit::Ampere)?
+            .unwrap_or(id_25c * 4.0),
+        rds_on_25c,
+        rds_on_100c,
+        qg_total,
+        qgd: r.quantity("qgd", Unit::Coulomb)?.unwrap_or(qg_total * 0.3),
+        qgs: r.quantity("qgs", Unit::Coulomb)?.unwrap_or(qg_total * 0.25),
+        qrr: r.quantity("qrr", Unit::Coulomb)?.unwrap_or(d.qrr),
+        coss,
+        vgs_th: r.quantity("vgs_th", Unit::Volt)?.unwrap_or(d.vgs_th),
+        vgs_max: r.quantity("vgs_max", Unit::Volt)?.unwrap_or(d.vgs_max),
+        package,
+        rth_jc: r
+            .quantity("rth_jc", Unit::CelsiusPerWatt)?
+            .unwrap_or(package.typical_rth_jc()),
+        rth_ja: r
+            .quantity("rth_ja", Unit::CelsiusPerWatt)?
+            .unwrap_or(package.typical_rth_ja()),
+        tj_max: r.quantity("tj_max", Unit::Celsius)?.unwrap_or(d.tj_max),
+        tr: r.quantity("tr", Unit::Second)?.unwrap_or(d.tr),
+        tf: r.quantity("tf", Unit::Second)?.unwrap_or(d.tf),
+    })
+}
+
+fn diode_from_record(r: &Record) -> Result<DiodeSpec, String> {
+    let diode_type = enum_field(r, "diode_type", diode_type)?;
+    let package = enum_field(r, "package", diode_package)?;
+    let vrrm = r.required("vrrm", Unit::Volt)?;
+    let if_avg = r.required("if_avg", Unit::Ampere)?;
+
+    let vf_typical = match r.quantity("vf_typical", Unit::Volt)? {
+        Some(v) => v,
+        None => match r.points("vf_vs_if", &[Unit::Ampere, Unit::Volt])? {
+            Some(curve) => interpolate(&curve, if_avg),
+            None => return Err(r.error("missing 'vf_typical' or 'vf_vs_if'")),
+        },
+    };
+    let vf_temp_coeff = match r.quantity("vf_temp_coeff", Unit::MilliVoltPerCelsius)? {
+        Some(v) => v,
+        None => match r.points("vf_vs_tj", &[Unit::Celsius, Unit::Volt])? {
+            Some(curve) => slope(&curve) * 1e3,
+            None => DiodeSpec::default().vf_temp_coeff,
+        },
+    };
+
+    let d = DiodeSpec::default();
+    Ok(DiodeSpec {
+        part_number: r.required_text("part_number")?,
+        manufacturer: r.text("manufacturer")?.unwrap_or_default(),
+        diode_type,
+        vrrm,
+        vr: r.quantity("vr", Unit::Volt)?.unwrap_or(vrrm),
+        if_avg,
+        if_rms: r.quantity("if_rms", Unit::Ampere)?.unwrap_or(if_avg * 1.6),
+        ifsm: r.quantity("ifsm", Unit::Ampere)?.unwrap_or(if_avg * 20.0),
+        vf_typical,
+        vf_max: r
+            .quantity("vf_max", Unit::Volt)?
+            .unwrap_or(vf_typical * 1.2),
+        vf_temp_coeff,
+        trr: r.quantity("trr", Unit::Second)?.unwrap_or(0.0),
+        qrr: r.quantity("qrr", Unit::Coulomb)?.unwrap_or(0.0),
+        cj: r.quantity("cj", Unit::Farad)?.unwrap_or(d.cj),
+        ir_25c: r.quantity("ir_25c", Unit::Ampere)?.unwrap_or(d.ir_25c),
+        ir_125c: r.quantity("ir_125c", Unit::Ampere)?.unwrap_or(d.ir_125c),
+        package,
+        rth_jc: r
+            .quantity("rth_jc", Unit::CelsiusPerWatt)?
+            .unwrap_or(package.typical_rth_jc()),
+        rth_ja: r
+            .quantity("rth_ja", Unit::CelsiusPerWatt)?
+            .unwrap_or(package.typical_rth_ja()),
+        tj_max: r.quantity("tj_max", Unit::Celsius)?.unwrap_or(d.tj_max),
+    })
+}
+
+fn material_from_record(r: &Record) -> Result<CoreMaterial, String> {
+    let material_type = enum_field(r, "material_type", material_type)?;
+    let bsat_25c = r.required("bsat_25c", Unit::Tesla)?;
+    let ferrite = !material_type.good_dc_bias();
+
+    let loss_points = r.points(
+        "core_loss",
+        &[Unit::Hertz, Unit::Tesla, Unit::WattPerCubicCentimetre],
+    )?;
+    let fitted = match &loss_points {
+        Some(points) => {
+            let triples: Vec<(f64, f64, f64)> = points.iter().map(|p| (p[0], p[1], p[2])).collect();
+            Some(fit_steinmetz(&triples).map_err(|e| r.error(e))?)
+        }
+        None => None,
+    };
+    let coefficient = |key: &str, fit: Option<f64>| -> Result<f64, String> {
+        r.quantity(key, Unit::Number)?
+            .or(fit)
+            .ok_or_else(|| r.error(format!("missing '{}' or 'core_loss' points", key)))
+    };
+
+    let (default_f_min, default_f_max) = match &loss_points {
+        Some(points) => (points[0][0], points[points.len() - 1][0]),
+        None => material_type.frequency_range(),
+    };
+
+    let d = CoreMaterial::default();
+    Ok(CoreMaterial {
+        name: r.required_text("name")?,
+        manufacturer: r.text("manufacturer")?.unwrap_or_default(),
+        material_type,
+        initial_permeability: r.required("initial_permeability", Unit::Number)?,
+        bsat_25c,
+        bsat_100c: r.quantity("bsat_100c", Unit::Tesla)?.unwrap_or(if ferrite {
+            bsat_25c * 0.8
+        } else {
+            bsat_25c
+        }),
+        curie_temp: r
+            .quantity("curie_temp", Unit::Celsius)?
+            .unwrap_or(d.curie_temp),
+        resistivity: r
+            .quantity("resistivity", Unit::OhmCentimetre)?
+            .unwrap_or(d.resistivity),
+        steinmetz_k: coefficient("steinmetz_k", fitted.map(|f| f.0))?,
+        steinmetz_alpha: coefficient("steinmetz_alpha", fitted.map(|f| f.1))?,
+        steinmetz_beta: coefficient("steinmetz_beta", fitted.map(|f| f.2))?,
+        steinmetz_freq_range: (
+            r.quantity("steinmetz_freq_min", Unit::Hertz)?
+                .unwrap_or(default_f_min),
+            r.quantity("steinmetz_freq_max", Unit::Hertz)?
+                .unwrap_or(default_f_max),
+        ),
+        temp_coeff_permeability: r
+            .quantity("temp_coeff_permeability", Unit::Number)?
+            .unwrap_or(0.0),
+        density: r
+            .quantity("density", Unit::GramPerCubicCentimetre)?
+            .unwrap_or(d.density),
+    })
+}
+
+fn core_from_record(r: &Record) -> Result<CoreGeometry, String> {
+    let ae = r.required("ae", Unit::SquareMillimetre)?;
+    let le = r.required("le", Unit::Millimetre)?;
+    let ve = r.quantity("ve", Unit::CubicMillimetre)?.unwrap_or(ae * le);
+    let window_area = r.required("window_area", Unit::SquareMillimetre)?;
+
+    Ok(CoreGeometry {
+        core_type: enum_field(r, "core_type", core_type)?,
+        part_number: r.required_text("part_number")?,
+        ae,
+        le,
+        ve,
+        al: r.required("al", Unit::NanoHenry)?,
+        window_area,
+        bobbin_window: r
+            .quantity("bobbin_window", Unit::SquareMillimetre)?
+            .unwrap_or(window_area * 0.75),
+        mlt: r.required("mlt", Unit::Millimetre)?,
+        // Ferrite density ~4.8 g/cm³
+        weight: r
+            .quantity("weight", Unit::Gram)?
+            .unwrap_or(ve / 1000.0 * 4.8),
+        // Surface of a cube of the same volume
+        surface_area: r
+            .quantity("surface_area", Unit::SquareCentimetre)?
+            .unwrap_or(6.0 * ve.powf(2.0 / 3.0) / 100.0),
+    })
+}
+
+// ============================================================================
+// RANGE VALIDATION
+// ============================================================================
+
+/// Collects range violations for one part
+struct RangeCheck {
+    label: String,
+    errors: Vec<String>,
+}
+
+impl RangeCheck {
+    fn new(kind: PartKind, id: &str) -> Self {
+        Self {
+            label: format!("{} '{}'", kind.label(), id),
+            errors: Vec::new(),
+        }
+    }
+
+    fn range(&mut self, field: &str, value: f64, min: f64, max: f64, unit: Unit) -> &mut Self {
+        if !value.is_finite() || value < min || value > max {
+            self.errors.push(format!(
+                "{}: {} = {:.4e} {} outside {:e}..{:e} (check units)",
+                self.label,
+                field,
+                value,
+                unit.symbol(),
+                min,
+                max
+            ));
+        }
+        self
+    }
+
+    fn require(&mut self, ok: bool, message: &str) -> &mut Self {
+        if !ok {
+            self.errors.push(format!("{}: {}", self.label, message));
+        }
+        self
+    }
+
+    fn finish(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.errors)
+    }
+}
+
+fn validate_mosfet(m: &MOSFETSpec) -> Vec<String> {
+    RangeCheck::new(PartKind::Mosfet, &m.part_number)
+        .require(!m.part_number.is_empty(), "empty part number")
+        .range("vds_max", m.vds_max, 1.0, 10e3, Unit::Volt)
+        .range(
+            "id_continuous_25c",
+            m.id_continuous_25c,
+            0.01,
+            2000.0,
+            Unit::Ampere,
+        )
+        .range(
+            "id_continuous_100c",
+            m.id_continuous_100c,
+            0.0,
+            m.id_continuous_25c,
+            Unit::Ampere,
+        )
+        .range(
+            "id_pulsed",
+            m.id_pulsed,
+            m.id_continuous_25c,
+            1e4,
+            Unit::Ampere,
+        )
+        .range("rds_on_25c", m.rds_on_25c, 1e-5, 100.0, Unit::Ohm)
+        .range(
+            "rds_on_100c",
+            m.rds_on_100c,
+            m.rds_on_25c,
+            m.rds_on_25c * 4.0,
+            Unit::Ohm,
+        )
+        .range("qg_total", m.qg_total, 1e-12, 1e-5, Unit::Coulomb)
+        .range("qgd", m.qgd, 0.0, m.qg_total, Unit::Coulomb)
+        .range("qgs", m.qgs, 0.0, m.qg_total, Unit::Coulomb)
+        .range("qrr", m.qrr, 0.0, 1e-4, Unit::Coulomb)
+        .range("coss", m.coss, 1e-13, 1e-7, Unit::Farad)
+        .range("vgs_th", m.vgs_th, 0.3, 10.0, Unit::Volt)
+        .range("vgs_max", m.vgs_max, 2.0, 40.0, Unit::Volt)
+        .range("rth_jc", m.rth_jc, 0.01, 100.0, Unit::CelsiusPerWatt)
+        .range("rth_ja", m.rth_ja, m.rth_jc, 500.0, Unit::CelsiusPerWatt)
+        .range("tj_max", m.tj_max, 100.0, 250.0, Unit::Celsius)
+        .range("tr", m.tr, 1e-11, 1e-5, Unit::Second)
+        .range("tf", m.tf, 1e-11, 1e-5, Unit::Second)
+        .finish()
+}
+
+fn validate_diode(d: &DiodeSpec) -> Vec<String> {
+    RangeCheck::new(PartKind::Diode, &d.part_number)
+        .require(!d.part_number.is_empty(), "empty part number")
+        .range("vrrm", d.vrrm, 1.0, 20e3, Unit::Volt)
+        .range("vr", d.vr, 0.0, d.vrrm, Unit::Volt)
+        .range("if_avg", d.if_avg, 0.01, 2000.0, Unit::Ampere)
+        .range("if_rms", d.if_rms, d.if_avg, d.if_avg * 4.0, Unit::Ampere)
+        .range("ifsm", d.ifsm, d.if_avg, 1e5, Unit::Ampere)
+        .range("vf_typical", d.vf_typical, 0.1, 5.0, Unit::Volt)
+        .range("vf_max", d.vf_max, d.vf_typical, 6.0, Unit::Volt)
+        .range(
+            "vf_temp_coeff",
+            d.vf_temp_coeff,
+            -5.0,
+            5.0,
+            Unit::MilliVoltPerCelsius,
+        )
+        .range("trr", d.trr, 0.0, 1e-5, Unit::Second)
+        .range("qrr", d.qrr, 0.0, 1e-4, Unit::Coulomb)
+        .range("cj", d.cj, 0.0, 1e-8, Unit::Farad)
+        .range("ir_25c", d.ir_25c, 0.0, 1.0, Unit::Ampere)
+        .range("ir_125c", d.ir_125c, d.ir_25c, 1.0, Unit::Ampere)
+        .range("rth_jc", d.rth_jc, 0.01, 100.0, Unit::CelsiusPerWatt)
+        .range("rth_ja", d.rth_ja, d.rth_jc, 500.0, Unit::CelsiusPerWatt)
+        .range("tj_max", d.tj_max, 100.0, 250.0, Unit::Celsius)
+        .require(
+            !d.diode_type.has_reverse_recovery() || d.qrr > 0.0,
+            "bipolar diode needs 'qrr' for recovery loss",
+        )
+        .finish()
+}
+
+fn validate_material(m: &CoreMaterial) -> Vec<String> {
+    let (f_min, f_max) = m.steinmetz_freq_range;
+    RangeCheck::new(PartKind::Material, &m.name)
+        .require(!m.name.is_empty(), "empty name")
+        .range(
+            "initial_permeability",
+            m.initial_permeability,
+            1.0,
+            1e6,
+            Unit::Number,
+        )
+        .range("bsat_25c", m.bsat_25c, 0.05, 3.0, Unit::Tesla)
+        .range(
+            "bsat_100c",
+            m.bsat_100c,
+            0.05,
+            m.bsat_25c * 1.1,
+            Unit::Tesla,
+        )
+        .range("curie_temp", m.curie_temp, 80.0, 1200.0, Unit::Celsius)
+        .range("resistivity", m.resistivity, 0.0, 1e12, Unit::OhmCentimetre)
+        .range("steinmetz_k", m.steinmetz_k, 1e-15, 1.0, Unit::Number)
+        .range("steinmetz_alpha", m.steinmetz_alpha, 0.8, 3.5, Unit::Number)
+        .range("steinmetz_beta", m.steinmetz_beta, 1.2, 4.0, Unit::Number)
+        .range("steinmetz_freq_min", f_min, 1.0, f_max, Unit::Hertz)
+        .range("steinmetz_freq_max", f_max, f_min, 1e9, Unit::Hertz)
+        .range(
+            "density",
+            m.density,
+            1.0,
+            10.0,
+            Unit::GramPerCubicCentimetre,
+        )
+        .finish()
+}
+
+fn validate_core(c: &CoreGeometry) -> Vec<String> {
+    let ae_le = c.ae * c.le;
+    RangeCheck::new(PartKind::Core, &c.part_number)
+        .require(!c.part_number.is_empty(), "empty part number")
+        .range("ae", c.ae, 0.1, 1e5, Unit::SquareMillimetre)
+        .range("le", c.le, 1.0, 2000.0, Unit::Millimetre)
+        // Ve = Ae × le by definition of the effective parameters
+        .range("ve", c.ve, ae_le * 0.7, ae_le * 1.3, Unit::CubicMillimetre)
+        .range("al", c.al, 1.0, 1e5, Unit::NanoHenry)
+        .range(
+            "window_area",
+            c.window_area,
+            0.1,
+            1e5,
+            Unit::SquareMillimetre,
+        )
+        .range(
+            "bobbin_window",
+            c.bobbin_window,
+            0.0,
+            c.window_area,
+            Unit::SquareMillimetre,
+        )
+        .range("mlt", c.mlt, 1.0, 2000.0, Unit::Millimetre)
+        .range("weight", c.weight, 0.01, 1e5, Unit::Gram)
+        .range(
+            "surface_area",
+            c.surface_area,
+            0.01,
+            1e5,
+            Unit::SquareCentimetre,
+        )
+        .finish()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_parts_validate() {
+        let library = PartLibrary::builtin();
+        assert!(!library.is_empty());
+        library.validate().unwrap();
+    }
+
+    #[test]
+    fn test_parse_quantity_units() {
+        assert!((parse_quantity("4.5 mΩ", Unit::Ohm).unwrap() - 4.5e-3).abs() < 1e-15);
+        assert!((parse_quantity("25nC", Unit::Coulomb).unwrap() - 25e-9).abs() < 1e-18);
+        assert!((parse_quantity("1.2 cm²", Unit::SquareMillimetre).unwrap() - 120.0).abs() < 1e-9);
+        assert!((parse_quantity("2 cm³", Unit::CubicMillimetre).unwrap() - 2000.0).abs() < 1e-9);
+        assert!((parse_quantity("2.5 µH", Unit::NanoHenry).unwrap() - 2500.0).abs() < 1e-9);
+        assert!(
+            (parse_quantity("-1.8 mV/°C", Unit::MilliVoltPerCelsius).unwrap() + 1.8).abs() < 1e-12
+        );
+        assert_eq!(parse_quantity("1e-3", Unit::Ohm).unwrap(), 1e-3);
+
+        let err = parse_quantity("4.5 mV", Unit::Ohm).unwrap_err();
+        assert!(err.contains("expected Ω"));
+    }
+
+    #[test]
+    fn test_mosfet_from_json_curves() {
+        let json = r#"{ "mosfets": [{
+            "part_number": "TEST100N", "manufacturer": "Acme", "package": "TO-220",
+            "vds_max": "100 V", "id_continuous_25c": "80 A",
+            "rds_on_vs_tj": [["25 °C", "6 mΩ"], ["100 °C", "9 mΩ"], ["150 °C", "11.5 mΩ"]],
+            "qg_total": "35 nC",
+            "coss_vs_vds": [["10 V", "1 nF"], ["50 V", "400 pF"]]
+        }] }"#;
+        let library = PartLibrary::from_json(json).unwrap();
+        let m = &library.mosfets[0];
+        assert_eq!(m.package, MOSFETPackage::TO220);
+        assert!((m.rds_on_25c - 6e-3).abs() < 1e-12);
+        assert!((m.rds_on_100c - 9e-3).abs() < 1e-12);
+        // Log-log interpolation between the Coss points
+        assert!(m.coss < 1e-9 && m.coss > 400e-12);
+        assert_eq!(m.rth_ja, MOSFETPackage::TO220.typical_rth_ja());
+    }
+
+    #[test]
+    fn test_diode_from_csv() {
+        let csv = "\
+# Approved rectifiers
+part_number,diode_type,package,vrrm [V],if_avg [A],vf_vs_if [A:V],vf_vs_tj [°C:V],qrr [nC]
+UF5408,Ultrafast,Axial,1000,3,1:1.2;3:1.5;5:1.7,25:1.5;125:1.3,150
+\"SS5,4\",Schottky,SMC,40,5,1:0.35;5:0.5,,
+";
+        let library = PartLibrary::from_csv(PartKind::Diode, csv).unwrap();
+        assert_eq!(library.diodes.len(), 2);
+
+        let uf = &library.diodes[0];
+        assert_eq!(uf.package, DiodePackage::Axial);
+        assert!((uf.vf_typical - 1.5).abs() < 1e-12);
+        assert!((uf.vf_temp_coeff + 2.0).abs() < 1e-9);
+        assert!((uf.qrr - 150e-9).abs() < 1e-18);
+
+        let ss = &library.diodes[1];
+        assert_eq!(ss.part_number, "SS5,4");
+        assert_eq!(ss.package, DiodePackage::DO214);
+        assert!((ss.vf_typical - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_design_uses_imported_parts() {
+        use crate::power::topologies::{design_sepic, design_sepic_with, SEPICRequirements};
+
+        let req = SEPICRequirements::default();
+        // A design on the built-in parts first must not pin the library
+        let builtin = design_sepic(&req).unwrap();
+
+        let mosfets = "\
+part_number,package,vds_max [V],id_continuous_25c [A],rds_on_25c [mΩ],qg_total [nC],coss [pF]
+VND-60N,D2PAK,60,40,5,20,300
+";
+        let diodes = "\
+part_number,diode_type,package,vrrm [V],if_avg [A],vf_typical [V]
+VND-SB60,Schottky,SMC,60,5,0.45
+";
+        let mut library = PartLibrary::builtin();
+        library.mosfets = PartLibrary::from_csv(PartKind::Mosfet, mosfets)
+            .unwrap()
+            .mosfets;
+        library.diodes = PartLibrary::from_csv(PartKind::Diode, diodes)
+            .unwrap()
+            .diodes;
+
+        let vendor = design_sepic_with(&req, &library).unwrap();
+        assert_eq!(vendor.main_switch.spec.part_number, "VND-60N");
+        assert_eq!(vendor.diode.spec.part_number, "VND-SB60");
+        assert_ne!(builtin.main_switch.spec.part_number, "VND-60N");
+        assert_eq!(
+            design_sepic(&req).unwrap().main_switch.spec.part_number,
+            builtin.main_switch.spec.part_number
+        );
+    }
+
+    #[test]
+    fn test_steinmetz_fit_recovers_coefficients() {
+        let (k, alpha, beta) = (2e-6, 1.4, 2.6);
+        let points: Vec<(f64, f64, f64)> = [50e3_f64, 100e3, 200e3]
+            .iter()
+            .flat_map(|&f| {
+                [0.05_f64, 0.1, 0.2].map(move |b| (f, b, k * f.powf(alpha) * b.powf(beta)))
+            })
+            .collect();
+        let fit = fit_steinmetz(&points).unwrap();
+        assert!((fit.0 / k - 1.0).abs() < 1e-6);
+        assert!((fit.1 - alpha).abs() < 1e-9);
+        assert!((fit.2 - beta).abs() < 1e-9);
+
+        let json = r#"{ "materials": [{
+            "name": "X95", "material_type": "MnZn", "initial_permeability": 3000,
+            "bsat_25c": "510 mT",
+            "core_loss": [[100000, 0.1, "0.08 W/cm³"], [100000, 0.2, "0.45 W/cm³"],
+                          [200000, 0.1, "0.2 W/cm³"]]
+        }] }"#;
+        let m = &PartLibrary::from_json(json).unwrap().materials[0];
+        assert_eq!(m.steinmetz_freq_range, (100e3, 200e3));
+        assert!((m.core_loss_density(100e3, 0.1) - 0.08).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        // Ve given in cm³ as a bare number
+        let json = r#"{ "cores": [{ "part_number": "E99", "core_type": "E", "ae": 100,
+            "le": 60, "ve": 6, "al": 2000, "window_area": 80, "mlt": 50 }] }"#;
+        let err = PartLibrary::from_json(json).unwrap_err();
+        assert!(err.contains("Core 'E99': ve"));
+
+        let json = r#"{ "cores": [{ "part_number": "E99", "core_type": "E", "ae": 100,
+            "le": 60, "al": 2000, "window_area": 80, "mlt": 50, "gap": 1 }] }"#;
+        assert!(PartLibrary::from_json(json)
+            .unwrap_err()
+            .contains("unknown field 'gap'"));
+
+        let json = r#"{ "diodes": [{ "part_number": "D1", "diode_type": "Ultrafast",
+            "package": "TO-220", "vrrm": 600, "if_avg": 8, "vf_typical": 1.3 }] }"#;
+        assert!(PartLibrary::from_json(json).unwrap_err().contains("qrr"));
+    }
+
+    #[test]
+    fn test_merge_overrides_and_appends() {
+        let mut library = PartLibrary::builtin();
+        let count = library.mosfets.len();
+        let existing = library.mosfets[0].part_number.clone();
+
+        let mut replacement = library.mosfets[0].clone();
+        replacement.rds_on_25c *= 0.5;
+        let mut added = replacement.clone();
+        added.part_number = "VENDOR-NEW".to_string();
+
+        library.merge(PartLibrary {
+            mosfets: vec![replacement, added],
+            ..Default::default()
+        });
+        assert_eq!(library.mosfets.len(), count + 1);
+        let merged = library
+            .mosfets
+            .iter()
+            .find(|m| m.part_number == existing)
+            .unwrap();
+        assert!(
+            (merged.rds_on_25c - PartLibrary::builtin().mosfets[0].rds_on_25c * 0.5).abs() < 1e-15
+        );
+    }
+}
@@ -18,6 +18,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::power::library::PartLibrary;
+
 /// Core material type classification
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MaterialType {
@@ -852,8 +854,9 @@ pub fn core_geometry_database() -> Vec<CoreGeometry> {
     ]
 }
 
-/// Find suitable cores for given power and frequency
+/// Find suitable cores in `library` for given power and frequency
 pub fn find_suitable_cores(
+    library: &PartLibrary,
     power_va: f64,
     frequency: f64,
     core_type_filter: Option<CoreType>,
@@ -868,8 +871,9 @@ pub fn find_suitable_cores(
     let area_product_min = base_ap * 0.3; // Allow smaller cores
     let area_product_max = base_ap * 5.0; // 500% margin for larger options
 
-    core_geometry_database()
-        .into_iter()
+    library
+        .cores
+        .iter()
         .filter(|c| {
             let ap = c.area_product();
             ap >= area_product_min && ap <= area_product_max
@@ -881,6 +885,7 @@ pub fn find_suitable_cores(
                 true
             }
         })
+        .cloned()
         .collect()
 }
 
@@ -969,7 +974,8 @@ mod tests {
     #[test]
     fn test_find_suitable_cores() {
         // For a 50W transformer
-        let suitable = find_suitable_cores(50.0, 100e3, Some(CoreType::ETD));
+        let suitable =
+            find_suitable_cores(&PartLibrary::builtin(), 50.0, 100e3, Some(CoreType::ETD));
         assert!(!suitable.is_empty());
 
         // Should include ETD29 or ETD34 for this power level
@@ -0,0 +1,477 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: inductor.rs | DNA/src/power/magnetics/inductor.rs
+//! PURPOSE: Power inductor design for DC-biased converter inductors
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Designs the magnetic part behind a buck/boost inductance value:
+//!
+//! - **Material**: Gapped MnZn ferrite or distributed-gap powder
+//! - **Turns and Gap**: From peak flux (L × Ipk = N × B × Ae) and the gap
+//!   needed to bring the ungapped AL down to the target inductance
+//! - **Winding**: Solid wire or Litz, whichever loses less and fits
+//! - **Losses**: iGSE core loss on the ripple swing, DC + Dowell AC copper loss
+//! - **Thermal Estimation**: Same natural-convection estimate as transformers
+//!
+//! Flux density in an inductor has a DC part set by the load current and a
+//! small AC part set by the ripple. Saturation depends on the peak, core loss
+//! only on the AC swing.
+
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use super::core_materials::{CoreGeometry, CoreMaterial, CoreType, MaterialType};
+use super::wire::{
+    find_litz_wire, total_ac_resistance_factor, wire_for_current, CurrentDensity, InsulationClass,
+    LitzWireSpec, WireSpec, COPPER_TEMP_COEFF,
+};
+use crate::power::library::PartLibrary;
+
+/// Permeability of free space (H/m)
+const MU_0: f64 = 4.0e-7 * PI;
+
+/// Inductor requirements for design
+#[derive(Clone, Debug)]
+pub struct InductorRequirements {
+    /// Target inductance (H)
+    pub inductance: f64,
+    /// DC (average) current (A)
+    pub current_dc: f64,
+    /// Peak-to-peak ripple current (A)
+    pub ripple_pp: f64,
+    /// Switching frequency (Hz)
+    pub frequency: f64,
+    /// Switch duty cycle (shapes the iGSE core loss)
+    pub duty_cycle: f64,
+    /// Operating temperature (°C)
+    pub ambient_temp: f64,
+    /// Maximum temperature rise (°C)
+    pub max_temp_rise: f64,
+    /// Restrict the search to one core family
+    pub core_type: Option<CoreType>,
+}
+
+impl InductorRequirements {
+    /// Requirements with 25°C ambient, 40°C rise and D = 0.5
+    pub fn new(inductance: f64, current_dc: f64, ripple_pp: f64, frequency: f64) -> Self {
+        Self {
+            inductance,
+            current_dc,
+            ripple_pp,
+            frequency,
+            duty_cycle: 0.5,
+            ambient_temp: 25.0,
+            max_temp_rise: 40.0,
+            core_type: None,
+        }
+    }
+
+    /// Peak current (A)
+    pub fn current_peak(&self) -> f64 {
+        self.current_dc + self.ripple_pp / 2.0
+    }
+
+    /// RMS of the triangular ripple component (A)
+    pub fn current_ac_rms(&self) -> f64 {
+        self.ripple_pp / (2.0 * 3.0_f64.sqrt())
+    }
+
+    /// Total RMS current (A)
+    pub fn current_rms(&self) -> f64 {
+        (self.current_dc.powi(2) + self.current_ac_rms().powi(2)).sqrt()
+    }
+}
+
+/// Inductor winding conductor
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum InductorWire {
+    /// Solid magnet wire
+    Solid(WireSpec),
+    /// Litz bundle
+    Litz(LitzWireSpec),
+}
+
+impl InductorWire {
+    /// Outer diameter including insulation (mm)
+    pub fn outer_diameter(&self) -> f64 {
+        match self {
+            InductorWire::Solid(w) => w.outer_diameter(InsulationClass::Heavy),
+            InductorWire::Litz(l) => l.outer_diameter,
+        }
+    }
+
+    /// DC resistance per metre at temperature (Ω/m)
+    pub fn resistance_per_m(&self, temp_c: f64) -> f64 {
+        match self {
+            InductorWire::Solid(w) => w.resistance_at_temp(temp_c),
+            InductorWire::Litz(l) => {
+                l.dc_resistance_per_m * (1.0 + COPPER_TEMP_COEFF * (temp_c - 20.0))
+            }
+        }
+    }
+
+    /// AC/DC resistance ratio including proximity effect for solid wire
+    pub fn ac_resistance_factor(&self, frequency: f64, layers: u32) -> f64 {
+        match self {
+            InductorWire::Solid(w) => {
+                total_ac_resistance_factor(w.diameter_mm, frequency, layers, 0.7)
+            }
+            InductorWire::Litz(l) => l.ac_resistance_factor(frequency),
+        }
+    }
+
+    /// Short description (e.g. "AWG 18" or "100×AWG 36 Litz")
+    pub fn description(&self) -> String {
+        match self {
+            InductorWire::Solid(w) => format!("AWG {}", w.awg),
+            InductorWire::Litz(l) => format!("{}×AWG {} Litz", l.strand_count, l.strand_awg),
+        }
+    }
+}
+
+/// Complete inductor design
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InductorDesign {
+    /// Core geometry
+    pub core: CoreGeometry,
+    /// Core material
+    pub material: CoreMaterial,
+    /// Achieved inductance (H)
+    pub inductance: f64,
+    /// Number of turns
+    pub turns: u32,
+    /// Air gap length (mm), zero for distributed-gap powder cores
+    pub gap_length: f64,
+    /// Winding conductor
+    pub wire: InductorWire,
+    /// Number of winding layers
+    pub layers: u32,
+    /// Peak flux density at peak current (T)
+    pub b_peak: f64,
+    /// AC flux swing amplitude (T, peak)
+    pub b_ac: f64,
+    /// Margin to saturation at operating temperature (fraction of Bsat)
+    pub saturation_margin: f64,
+    /// DC resistance at operating temperature (Ω)
+    pub dcr: f64,
+    /// Ripple-frequency AC resistance at operating temperature (Ω)
+    pub ac_resistance: f64,
+    /// Core loss (W)
+    pub core_loss: f64,
+    /// Copper loss, DC plus ripple (W)
+    pub copper_loss: f64,
+    /// Total losses (W)
+    pub total_loss: f64,
+    /// Estimated temperature rise (°C)
+    pub temp_rise: f64,
+    /// Window fill factor
+    pub fill_factor: f64,
+}
+
+impl InductorDesign {
+    /// Short description of the part
+    pub fn description(&self) -> String {
+        if self.gap_length > 0.0 {
+            format!(
+                "{} {}, {}T {}, gap {:.2}mm",
+                self.core.part_number,
+                self.material.name,
+                self.turns,
+                self.wire.description(),
+                self.gap_length
+            )
+        } else {
+            format!(
+                "{} {}, {}T {}",
+                self.core.part_number,
+                self.material.name,
+                self.turns,
+                self.wire.description()
+            )
+        }
+    }
+}
+
+/// Inductor design result
+#[derive(Clone, Debug)]
+pub enum InductorDesignResult {
+    /// Successful design
+    Success(Box<InductorDesign>),
+    /// Peak flux exceeds the usable limit
+    Saturated { b_peak: f64, b_max: f64 },
+    /// Gap too long for fringing flux to stay reasonable
+    GapTooLong { gap: f64, max_gap: f64 },
+    /// Window too small (can't fit the winding)
+    WindowTooSmall {
+        required_area: f64,
+        available_area: f64,
+    },
+    /// Temperature rise too high
+    TemperatureExceeded { rise: f64, max_rise: f64 },
+}
+
+// ============================================================================
+// INDUCTOR DESIGN ALGORITHMS
+// ============================================================================
+
+/// Design an inductor on a given core and material
+pub fn design_inductor_on_core(
+    req: &InductorRequirements,
+    core: &CoreGeometry,
+    material: &CoreMaterial,
+) -> InductorDesignResult {
+    let operating_temp = req.ambient_temp + req.max_temp_rise / 2.0;
+    let ae = core.ae * 1e-6; // m²
+    let le = core.le * 1e-3; // m
+    let i_peak = req.current_peak();
+    let distributed_gap = material.material_type.good_dc_bias();
+
+    // Powder permeability rolls off with DC bias well before hard saturation;
+    // keep the peak below half of Bsat. Gapped ferrite gets the usual 20% margin.
+    let b_max = if distributed_gap {
+        material.max_flux_with_margin(operating_temp, 0.5)
+    } else {
+        material.max_flux_with_margin(operating_temp, 0.2)
+    };
+
+    let (turns, inductance, gap_length) = if distributed_gap {
+        // L = μ0 × μi × N² × Ae / le
+        let al = MU_0 * material.initial_permeability * ae / le;
+        let n = ((req.inductance / al).sqrt().ceil() as u32).max(1);
+        (n, al * (n as f64).powi(2), 0.0)
+    } else {
+        // Saturation sets the minimum turns: L × Ipk = N × Bmax × Ae
+        let n_sat = (req.inductance * i_peak / (b_max * ae)).ceil() as u32;
+        let n_al = core.turns_for_inductance(req.inductance).ceil() as u32;
+        if n_al >= n_sat {
+            // Ungapped core already reaches L below the flux limit
+            (n_al.max(1), core.inductance(n_al.max(1)), 0.0)
+        } else {
+            // lg = μ0 × N² × Ae / L - le / μi
+            let n = n_sat;
+            let gap = MU_0 * (n as f64).powi(2) * ae / req.inductance
+                - le / material.initial_permeability;
+            (n, req.inductance, gap.max(0.0) * 1e3)
+        }
+    };
+
+    // Fringing grows quickly once the gap approaches the centre-leg width
+    let max_gap = 0.25 * core.ae.sqrt();
+    if gap_length > max_gap {
+        return InductorDesignResult::GapTooLong {
+            gap: gap_length,
+            max_gap,
+        };
+    }
+
+    let b_peak = inductance * i_peak / (turns as f64 * ae);
+    if b_peak > b_max {
+        return InductorDesignResult::Saturated { b_peak, b_max };
+    }
+    let b_ac = inductance * req.ripple_pp / (2.0 * turns as f64 * ae);
+
+    // Winding: solid wire sized for RMS current, Litz as the alternative
+    let i_rms = req.current_rms();
+    let i_ac_rms = req.current_ac_rms();
+    let length_m = turns as f64 * core.mlt / 1000.0;
+    let winding_width = core.bobbin_window.sqrt();
+
+    let mut candidates = vec![InductorWire::Solid(wire_for_current(
+        i_rms,
+        CurrentDensity::Ventilated.value(),
+    ))];
+    if let Some(litz) = find_litz_wire(i_rms, req.frequency, CurrentDensity::Ventilated) {
+        candidates.push(InductorWire::Litz(litz));
+    }
+
+    let mut best: Option<(InductorWire, u32, f64, f64, f64, f64)> = None;
+    let mut smallest_area = f64::MAX;
+    for wire in candidates {
+        let od = wire.outer_diameter();
+        let area = PI * (od / 2.0).powi(2) * turns as f64;
+        smallest_area = smallest_area.min(area);
+        let fill = area / core.bobbin_window;
+        if fill > 0.5 {
+            continue;
+        }
+
+        let turns_per_layer = ((winding_width / od).floor() as u32).max(1);
+        let layers = turns.div_ceil(turns_per_layer);
+        let dcr = wire.resistance_per_m(operating_temp) * length_m;
+        let r_ac = dcr * wire.ac_resistance_factor(req.frequency, layers);
+        let copper_loss = req.current_dc.powi(2) * dcr + i_ac_rms.powi(2) * r_ac;
+
+        if best.as_ref().is_none_or(|b| copper_loss < b.4) {
+            best = Some((wire, layers, dcr, r_ac, copper_loss, fill));
+        }
+    }
+
+    let Some((wire, layers, dcr, ac_resistance, copper_loss, fill_factor)) = best else {
+        return InductorDesignResult::WindowTooSmall {
+            required_area: smallest_area / 0.5,
+            available_area: core.bobbin_window,
+        };
+    };
+
+    // Core loss from the AC swing only
+    let pv = material.core_loss_density_igse(req.frequency, b_ac, req.duty_cycle);
+    let core_loss = pv * core.volume_cm3();
+    let total_loss = core_loss + copper_loss;
+
+    // ΔT ≈ P_loss / (h × A), h ≈ 10 W/(m²·K) for natural convection
+    let temp_rise = total_loss / (10.0 * core.surface_area * 1e-4);
+    if temp_rise > req.max_temp_rise {
+        return InductorDesignResult::TemperatureExceeded {
+            rise: temp_rise,
+            max_rise: req.max_temp_rise,
+        };
+    }
+
+    InductorDesignResult::Success(Box::new(InductorDesign {
+        core: core.clone(),
+        material: material.clone(),
+        inductance,
+        turns,
+        gap_length,
+        wire,
+        layers,
+        b_peak,
+        b_ac,
+        saturation_margin: 1.0 - b_peak / material.bsat_at_temp(operating_temp),
+        dcr,
+        ac_resistance,
+        core_loss,
+        copper_loss,
+        total_loss,
+        temp_rise,
+        fill_factor,
+    }))
+}
+
+/// Candidate materials for a DC-biased inductor at the given frequency
+fn inductor_materials(library: &PartLibrary, frequency: f64) -> Vec<CoreMaterial> {
+    library
+        .ferrites()
+        .filter(|m| m.material_type == MaterialType::MnZnFerrite)
+        .chain(library.powders())
+        .filter(|m| m.is_frequency_suitable(frequency))
+        .cloned()
+        .collect()
+}
+
+/// Design an inductor with automatic material and core selection from
+/// `library`
+///
+/// Scores candidates the same way as `find_optimal_core`: loss first, then
+/// fill margin, then core size.
+pub fn design_inductor(
+    library: &PartLibrary,
+    req: &InductorRequirements,
+) -> Option<InductorDesign> {
+    if req.inductance <= 0.0 || req.frequency <= 0.0 || req.current_peak() <= 0.0 {
+        return None;
+    }
+
+    let cores: Vec<&CoreGeometry> = library
+        .cores
+        .iter()
+        .filter(|c| req.core_type.is_none_or(|ct| c.core_type == ct))
+        .collect();
+
+    let mut best_design: Option<InductorDesign> = None;
+    let mut best_score = f64::MAX;
+
+    for material in inductor_materials(library, req.frequency) {
+        for core in &cores {
+            if let InductorDesignResult::Success(design) =
+                design_inductor_on_core(req, core, &material)
+            {
+                let score = design.total_loss * 10.0 + design.fill_factor * 5.0 + core.ve / 1000.0;
+                if score < best_score {
+                    best_score = score;
+                    best_design = Some(*design);
+                }
+            }
+        }
+    }
+
+    best_design
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::magnetics::core_materials::{core_geometry_database, ferrite_database};
+
+    fn e25() -> CoreGeometry {
+        core_geometry_database()
+            .into_iter()
+            .find(|c| c.part_number == "E25/13/7")
+            .unwrap()
+    }
+
+    fn n87() -> CoreMaterial {
+        ferrite_database()
+            .into_iter()
+            .find(|m| m.name == "N87")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_gapped_ferrite_turns_and_gap() {
+        // 22µH at 5A DC, 1.5A ripple, 200kHz
+        let req = InductorRequirements::new(22e-6, 5.0, 1.5, 200e3);
+        let design = match design_inductor_on_core(&req, &e25(), &n87()) {
+            InductorDesignResult::Success(d) => d,
+            other => panic!("Design failed: {:?}", other),
+        };
+
+        // N ≥ L × Ipk / (Bmax × Ae)
+        let n_min = 22e-6 * 5.75 / (0.8 * n87().bsat_at_temp(45.0) * 52e-6);
+        assert!(design.turns as f64 >= n_min);
+        assert!(design.gap_length > 0.0);
+
+        // The gap reproduces the target inductance
+        let reluctance = (design.gap_length * 1e-3 + 49e-3 / 2200.0) / (MU_0 * 52e-6);
+        let l = (design.turns as f64).powi(2) / reluctance;
+        assert!((l - 22e-6).abs() / 22e-6 < 1e-6);
+
+        assert!(design.saturation_margin >= 0.2);
+        assert!(design.b_ac < design.b_peak);
+    }
+
+    #[test]
+    fn test_saturated_core_rejected() {
+        // 1mH at 20A does not fit an E25
+        let req = InductorRequirements::new(1e-3, 20.0, 2.0, 100e3);
+        let result = design_inductor_on_core(&req, &e25(), &n87());
+        assert!(!matches!(result, InductorDesignResult::Success(_)));
+    }
+
+    #[test]
+    fn test_auto_design_losses() {
+        let req = InductorRequirements::new(10e-6, 2.0, 0.6, 500e3);
+        let design = design_inductor(&PartLibrary::builtin(), &req).expect("No inductor found");
+
+        assert!(design.inductance >= 10e-6 * 0.99);
+        assert!(design.temp_rise <= req.max_temp_rise);
+        assert!((design.total_loss - design.core_loss - design.copper_loss).abs() < 1e-12);
+        // DC copper loss is a lower bound on copper loss
+        assert!(design.copper_loss >= 4.0 * design.dcr);
+    }
+
+    #[test]
+    fn test_high_ripple_prefers_litz() {
+        // Large ripple at high frequency: AC copper loss dominates solid wire
+        let mut req = InductorRequirements::new(4.7e-6, 4.0, 4.0, 1e6);
+        req.core_type = Some(CoreType::ETD);
+        let design = design_inductor(&PartLibrary::builtin(), &req).expect("No inductor found");
+        assert!(matches!(design.wire, InductorWire::Litz(_)));
+        assert!(design.ac_resistance < design.dcr * 3.0);
+    }
+}
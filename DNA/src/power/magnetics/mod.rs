@@ -10,6 +10,7 @@
 //! - **Core Materials**: Ferrite, iron powder, Sendust with Steinmetz loss models
 //! - **Wire Properties**: AWG specs, skin effect, proximity effect calculations
 //! - **Transformer Design**: Core selection, turns calculation, winding design
+//! - **Inductor Design**: Gapped ferrite or powder cores for DC-biased inductors
 //!
 //! # Example Usage
 //!
@@ -31,9 +32,11 @@
 //! ```
 
 pub mod core_materials;
+pub mod inductor;
 pub mod transformer;
 pub mod wire;
 
 pub use core_materials::*;
+pub use inductor::*;
 pub use transformer::*;
 pub use wire::*;
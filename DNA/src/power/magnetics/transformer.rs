@@ -21,6 +21,7 @@ use super::wire::{
     awg_spec, copper_skin_depth, total_ac_resistance_factor, CurrentDensity, InsulationClass,
     LitzWireSpec, WireSpec,
 };
+use crate::power::library::PartLibrary;
 
 /// Transformer winding type
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -283,6 +284,26 @@ pub fn design_transformer(
     // Calculate maximum flux density with margin
     let b_max = material.max_flux_with_margin(operating_temp, 0.3); // 30% margin
 
+    // Bridge-driven cores swing the full ±B every cycle, so flux density is
+    // limited by core loss long before saturation. Give the core half of the
+    // temperature-rise budget (same convection estimate as below).
+    let b_max = match req.topology {
+        TransformerTopology::PushPull
+        | TransformerTopology::HalfBridge
+        | TransformerTopology::FullBridge => {
+            let core_loss_budget = 0.5 * req.max_temp_rise * 10.0 * core.surface_area * 1e-4;
+            let pv_budget = core_loss_budget / core.volume_cm3();
+            let pv_at_b_max =
+                material.core_loss_density_igse(req.frequency, b_max, req.duty_cycle_max);
+            if pv_at_b_max > pv_budget {
+                b_max * (pv_budget / pv_at_b_max).powf(1.0 / material.steinmetz_beta)
+            } else {
+                b_max
+            }
+        }
+        _ => b_max,
+    };
+
     // Calculate primary turns from volt-seconds
     // V × t = N × Ae × ΔB
     let volt_seconds = req.primary_voltage * req.duty_cycle_max / req.frequency;
@@ -557,22 +578,19 @@ pub fn find_optimal_core(
     best_design
 }
 
-/// Design transformer with automatic core selection
+/// Design transformer with automatic selection from `library`'s cores
 pub fn auto_design_transformer(
+    library: &PartLibrary,
     req: &TransformerRequirements,
     material: &CoreMaterial,
     core_type: Option<CoreType>,
 ) -> Option<TransformerDesign> {
-    let all_cores = super::core_materials::core_geometry_database();
-
-    let filtered_cores: Vec<CoreGeometry> = if let Some(ct) = core_type {
-        all_cores
-            .into_iter()
-            .filter(|c| c.core_type == ct)
-            .collect()
-    } else {
-        all_cores
-    };
+    let filtered_cores: Vec<CoreGeometry> = library
+        .cores
+        .iter()
+        .filter(|c| core_type.is_none_or(|ct| c.core_type == ct))
+        .cloned()
+        .collect();
 
     find_optimal_core(req, &filtered_cores, material)
 }
@@ -817,7 +835,12 @@ mod tests {
             topology: TransformerTopology::Flyback,
         };
 
-        let design = auto_design_transformer(&req, &material, Some(CoreType::ETD));
+        let design = auto_design_transformer(
+            &PartLibrary::builtin(),
+            &req,
+            &material,
+            Some(CoreType::ETD),
+        );
         assert!(
             design.is_some(),
             "Expected auto_design to find a valid core"
@@ -36,8 +36,11 @@ pub mod buck;
 pub mod components;
 pub mod control;
 pub mod ldo;
+pub mod library;
 pub mod magnetics;
+pub mod optimizer;
 pub mod state_space;
+pub mod switched;
 pub mod topologies;
 pub mod transient;
 pub mod types;
@@ -46,8 +49,8 @@ pub mod types;
 pub use types::*;
 
 // Re-export design functions
-pub use boost::design_boost;
-pub use buck::design_buck;
+pub use boost::{design_boost, design_boost_with};
+pub use buck::{design_buck, design_buck_with};
 pub use ldo::design_ldo;
 
 // Re-export component utilities
@@ -58,8 +61,21 @@ pub use components::{
     E24, E6, E96, STANDARD_CERAMICS_UF, STANDARD_ELECTROLYTICS_UF, STANDARD_INDUCTORS_UH,
 };
 
+// Re-export part library import
+pub use library::{builtin_parts, PartKind, PartLibrary};
+
+// Re-export design optimizer
+pub use optimizer::{
+    optimize_boost, optimize_boost_with, optimize_buck, optimize_buck_with, DesignPoint, Objective,
+    OptimizerSpec, ParetoFront,
+};
+
 // Re-export simulation types
 pub use state_space::{Matrix1x2, Matrix2x1, Matrix2x2, StateSpace2, SwitchedConverter};
+pub use switched::{
+    simulate_switched, ConductionState, CycleSummary, DutyControl, Rectifier, StepMetrics,
+    SwitchedCircuit, SwitchedEvent, SwitchedOptions, SwitchedResult, SwitchedTopology, VoltageLoop,
+};
 pub use transient::{
     boost_duty_for_vout, boost_steady_state_vout, buck_duty_for_vout, buck_steady_state_vout,
     simulate_boost, simulate_buck, SimulationStats, TransientConfig, TransientResult,
@@ -164,8 +180,19 @@ pub fn recommend_topology(
             TopologyType::BuckBoost,
             "Buck-boost for wide Vin range".to_string(),
         ));
+        alternatives.push((
+            TopologyType::SEPIC,
+            "SEPIC if Vin range may exceed Vout".to_string(),
+        ));
         TopologyType::Boost
     } else if is_step_down {
+        if vin / vout > 10.0 {
+            let isolated = recommend_isolated_topology(vin, vout, iout, priority);
+            alternatives.push((
+                isolated.recommended,
+                "Isolated converter for large step-down ratio".to_string(),
+            ));
+        }
         match priority {
             DesignPriority::Noise => {
                 // LDO has best noise performance
@@ -210,6 +237,14 @@ pub fn recommend_topology(
         }
     } else {
         // Vin ≈ Vout
+        alternatives.push((
+            TopologyType::BuckBoost,
+            "Inverting buck-boost if negative output is acceptable".to_string(),
+        ));
+        alternatives.push((
+            TopologyType::Cuk,
+            "Ćuk for continuous input and output current (inverted)".to_string(),
+        ));
         alternatives.push((
             TopologyType::Buck,
             "Buck if Vin slightly higher".to_string(),
@@ -218,7 +253,7 @@ pub fn recommend_topology(
             TopologyType::Boost,
             "Boost if Vin slightly lower".to_string(),
         ));
-        TopologyType::BuckBoost
+        TopologyType::SEPIC
     };
 
     let reasoning = match recommended {
@@ -245,7 +280,12 @@ pub fn recommend_topology(
             ldo_dissipation
         ),
         TopologyType::BuckBoost => format!("Buck-boost for Vin ≈ Vout or wide input range.",),
-        _ => "See alternatives".to_string(),
+        TopologyType::SEPIC => format!(
+            "SEPIC converter: {:.1}V to {:.1}V at {:.2}A. \
+            Non-inverting step-up/down with continuous input current.",
+            vin, vout, iout
+        ),
+        _ => topology_reasoning(recommended, vin, vout, iout),
     };
 
     TopologyRecommendation {
@@ -255,6 +295,109 @@ pub fn recommend_topology(
     }
 }
 
+/// Recommend an isolated topology based on output power
+///
+/// Flyback up to ~100W, forward or push-pull up to ~300W, half-bridge
+/// (or LLC when efficiency/noise matter) up to ~600W, full-bridge above.
+pub fn recommend_isolated_topology(
+    vin: f64,
+    vout: f64,
+    iout: f64,
+    priority: DesignPriority,
+) -> TopologyRecommendation {
+    let mut alternatives = Vec::new();
+    let p_out = vout * iout;
+    let soft_switching = matches!(priority, DesignPriority::Efficiency | DesignPriority::Noise);
+
+    let recommended = if p_out <= 100.0 {
+        alternatives.push((
+            TopologyType::Forward,
+            "Forward for lower output ripple".to_string(),
+        ));
+        TopologyType::Flyback
+    } else if p_out <= 300.0 {
+        if vin >= 100.0 {
+            alternatives.push((
+                TopologyType::HalfBridge,
+                "Half-bridge for better core utilization".to_string(),
+            ));
+            TopologyType::Forward
+        } else {
+            alternatives.push((
+                TopologyType::Forward,
+                "Forward for fewer switches".to_string(),
+            ));
+            TopologyType::PushPull
+        }
+    } else if p_out <= 600.0 {
+        if soft_switching {
+            alternatives.push((
+                TopologyType::HalfBridge,
+                "PWM half-bridge for wide input range".to_string(),
+            ));
+            TopologyType::LLC
+        } else {
+            alternatives.push((
+                TopologyType::LLC,
+                "LLC for ZVS and higher efficiency".to_string(),
+            ));
+            TopologyType::HalfBridge
+        }
+    } else {
+        alternatives.push((TopologyType::LLC, "LLC for narrow input range".to_string()));
+        TopologyType::FullBridge
+    };
+
+    TopologyRecommendation {
+        recommended,
+        reasoning: topology_reasoning(recommended, vin, vout, iout),
+        alternatives,
+    }
+}
+
+/// Reasoning text for the isolated and coupled-inductor topologies
+fn topology_reasoning(topology: TopologyType, vin: f64, vout: f64, iout: f64) -> String {
+    let p_out = vout * iout;
+    match topology {
+        TopologyType::Cuk => format!(
+            "Ćuk converter: {:.1}V to -{:.1}V at {:.2}A. \
+            Inverted output with continuous input and output current.",
+            vin, vout, iout
+        ),
+        TopologyType::Flyback => format!(
+            "Flyback converter: {:.1}V to {:.1}V, {:.0}W. \
+            Lowest part count isolated solution.",
+            vin, vout, p_out
+        ),
+        TopologyType::Forward => format!(
+            "Forward converter: {:.1}V to {:.1}V, {:.0}W. \
+            Non-storing transformer with LC output filter.",
+            vin, vout, p_out
+        ),
+        TopologyType::PushPull => format!(
+            "Push-pull converter: {:.1}V to {:.1}V, {:.0}W. \
+            Ground-referenced switches suit low input voltage; switches see 2×Vin.",
+            vin, vout, p_out
+        ),
+        TopologyType::HalfBridge => format!(
+            "Half-bridge converter: {:.1}V to {:.1}V, {:.0}W. \
+            Switches rated for Vin, transformer sees Vin/2.",
+            vin, vout, p_out
+        ),
+        TopologyType::FullBridge => format!(
+            "Phase-shifted full-bridge: {:.1}V to {:.1}V, {:.0}W. \
+            Full Vin across the transformer with ZVS switching.",
+            vin, vout, p_out
+        ),
+        TopologyType::LLC => format!(
+            "LLC resonant half-bridge: {:.1}V to {:.1}V, {:.0}W. \
+            ZVS primary and ZCS rectifiers; best with a regulated bus.",
+            vin, vout, p_out
+        ),
+        _ => "See alternatives".to_string(),
+    }
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -312,4 +455,53 @@ mod tests {
         // LDO should be recommended for noise-sensitive applications
         assert_eq!(rec.recommended, TopologyType::LDO);
     }
+
+    #[test]
+    fn test_topology_recommendation_vin_equals_vout() {
+        let rec = recommend_topology(12.0, 12.0, 1.0, DesignPriority::Efficiency);
+        assert_eq!(rec.recommended, TopologyType::SEPIC);
+        assert!(rec
+            .alternatives
+            .iter()
+            .any(|(t, _)| *t == TopologyType::Cuk));
+    }
+
+    #[test]
+    fn test_topology_recommendation_large_ratio_suggests_isolation() {
+        let rec = recommend_topology(400.0, 12.0, 5.0, DesignPriority::Efficiency);
+        assert_eq!(rec.recommended, TopologyType::Buck);
+        assert!(rec
+            .alternatives
+            .iter()
+            .any(|(t, _)| *t == TopologyType::Flyback));
+    }
+
+    #[test]
+    fn test_isolated_topology_by_power() {
+        let p = DesignPriority::Cost;
+        assert_eq!(
+            recommend_isolated_topology(48.0, 12.0, 2.0, p).recommended,
+            TopologyType::Flyback
+        );
+        assert_eq!(
+            recommend_isolated_topology(24.0, 12.0, 15.0, p).recommended,
+            TopologyType::PushPull
+        );
+        assert_eq!(
+            recommend_isolated_topology(400.0, 24.0, 10.0, p).recommended,
+            TopologyType::Forward
+        );
+        assert_eq!(
+            recommend_isolated_topology(400.0, 24.0, 20.0, p).recommended,
+            TopologyType::HalfBridge
+        );
+        assert_eq!(
+            recommend_isolated_topology(400.0, 24.0, 20.0, DesignPriority::Efficiency).recommended,
+            TopologyType::LLC
+        );
+        assert_eq!(
+            recommend_isolated_topology(400.0, 48.0, 20.0, p).recommended,
+            TopologyType::FullBridge
+        );
+    }
 }
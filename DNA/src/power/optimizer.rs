@@ -0,0 +1,795 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: optimizer.rs | DNA/src/power/optimizer.rs
+//! PURPOSE: Multi-objective buck/boost design optimizer returning a Pareto front
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! `design_buck`/`design_boost` produce one design for one switching frequency.
+//! The optimizer sweeps the design space around them:
+//!
+//! - **Switching frequency**: log-spaced between `fsw_min` and `fsw_max`
+//! - **Inductor**: ripple ratio × core family, each part built by `design_inductor`
+//! - **Semiconductors**: suitable MOSFETs and diodes from `power::components`
+//! - **Compensator**: loop crossover candidates; the stable setting with the
+//!   smallest load-step dip is kept for each power stage
+//!
+//! Every candidate is scored on four objectives and the non-dominated set is
+//! returned:
+//!
+//! | Objective      | Source                                          | Sense    |
+//! |----------------|-------------------------------------------------|----------|
+//! | Efficiency     | Part-level MOSFET/diode losses + inductor design | maximize |
+//! | Volume         | `CoreGeometry::volume_cm3` of the inductor core  | minimize |
+//! | BOM cost       | Estimated prices of power-stage parts            | minimize |
+//! | Thermal margin | Worst of MOSFET, diode and inductor headroom     | maximize |
+//!
+//! # Example
+//!
+//! ```rust
+//! use dna::power::{optimize_buck, BuckRequirements, Objective, OptimizerSpec};
+//!
+//! let front = optimize_buck(&BuckRequirements::default(), &OptimizerSpec::default())
+//!     .expect("No feasible design");
+//! for (volume, efficiency) in front.series(Objective::Volume, Objective::Efficiency) {
+//!     println!("{:.2} cm³ → {:.1}%", volume, efficiency * 100.0);
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::boost::design_boost_with;
+use super::buck::design_buck_with;
+use super::components::{
+    find_suitable_diodes, find_suitable_mosfets, DiodeOperatingPoint, DiodePreference, DiodeSpec,
+    DiodeType, MOSFETOperatingPoint, MOSFETPackage, MOSFETPreference, MOSFETSpec,
+};
+use super::control::{boost_transient, buck_transient, ConverterTransient, TransientSpec};
+use super::library::{builtin_parts, PartLibrary};
+use super::magnetics::{
+    design_inductor, CoreType, InductorDesign, InductorRequirements, InductorWire, MaterialType,
+};
+use super::types::*;
+
+/// Junction temperature guess for the first loss pass (°C)
+const TJ_INITIAL: f64 = 75.0;
+
+// ============================================================================
+// OPTIMIZER SETTINGS
+// ============================================================================
+
+/// Design space swept by the optimizer
+#[derive(Clone, Debug)]
+pub struct OptimizerSpec {
+    /// Lowest switching frequency (Hz)
+    pub fsw_min: f64,
+    /// Highest switching frequency (Hz)
+    pub fsw_max: f64,
+    /// Number of log-spaced frequency points
+    pub fsw_steps: usize,
+    /// Inductor ripple ratios (ΔI / I_L) to try
+    pub ripple_ratios: Vec<f64>,
+    /// Core families to try, one inductor design each
+    pub core_types: Vec<CoreType>,
+    /// Loop crossover candidates as a fraction of fsw
+    pub crossover_fractions: Vec<f64>,
+    /// Minimum acceptable phase margin (degrees)
+    pub min_phase_margin: f64,
+    /// Maximum MOSFET and diode candidates per power stage
+    pub max_parts: usize,
+    /// Output capacitor ESR for the loop model (Ω)
+    pub output_esr: f64,
+}
+
+impl Default for OptimizerSpec {
+    fn default() -> Self {
+        Self {
+            fsw_min: 100e3,
+            fsw_max: 1e6,
+            fsw_steps: 5,
+            ripple_ratios: vec![0.2, 0.3, 0.4],
+            core_types: vec![
+                CoreType::ECore,
+                CoreType::ETD,
+                CoreType::EFD,
+                CoreType::PQ,
+                CoreType::RM,
+            ],
+            crossover_fractions: vec![0.05, 0.1, 0.2],
+            min_phase_margin: 45.0,
+            max_parts: 3,
+            output_esr: 0.01,
+        }
+    }
+}
+
+impl OptimizerSpec {
+    /// Check the sweep is well formed
+    pub fn validate(&self) -> Result<(), String> {
+        if self.fsw_min <= 0.0 || self.fsw_max < self.fsw_min {
+            return Err("Frequency range must satisfy 0 < fsw_min <= fsw_max".to_string());
+        }
+        if self.fsw_steps == 0 {
+            return Err("At least one frequency step is required".to_string());
+        }
+        if self.ripple_ratios.iter().any(|&r| r <= 0.0 || r >= 2.0) {
+            return Err("Ripple ratios must be between 0 and 2".to_string());
+        }
+        if self.ripple_ratios.is_empty()
+            || self.core_types.is_empty()
+            || self.crossover_fractions.is_empty()
+        {
+            return Err("Ripple ratios, core types and crossovers must not be empty".to_string());
+        }
+        if self.max_parts == 0 {
+            return Err("At least one MOSFET/diode candidate is required".to_string());
+        }
+        Ok(())
+    }
+
+    /// Log-spaced switching frequencies (Hz)
+    pub fn frequencies(&self) -> Vec<f64> {
+        if self.fsw_steps == 1 {
+            return vec![self.fsw_min];
+        }
+        let ratio = (self.fsw_max / self.fsw_min).ln();
+        (0..self.fsw_steps)
+            .map(|i| self.fsw_min * (ratio * i as f64 / (self.fsw_steps - 1) as f64).exp())
+            .collect()
+    }
+}
+
+// ============================================================================
+// RESULTS
+// ============================================================================
+
+/// Optimization objective
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Objective {
+    /// Full-load efficiency at nominal input (0-1)
+    Efficiency,
+    /// Inductor core volume (cm³)
+    Volume,
+    /// Estimated power-stage BOM cost (USD)
+    Cost,
+    /// Smallest temperature headroom of any power part (°C)
+    ThermalMargin,
+}
+
+impl Objective {
+    /// All objectives, in report order
+    pub const ALL: [Objective; 4] = [
+        Objective::Efficiency,
+        Objective::Volume,
+        Objective::Cost,
+        Objective::ThermalMargin,
+    ];
+
+    /// Larger values are better
+    pub fn maximize(&self) -> bool {
+        matches!(self, Objective::Efficiency | Objective::ThermalMargin)
+    }
+
+    /// Objective value of a design point
+    pub fn value(&self, point: &DesignPoint) -> f64 {
+        match self {
+            Objective::Efficiency => point.efficiency,
+            Objective::Volume => point.volume_cm3,
+            Objective::Cost => point.cost,
+            Objective::ThermalMargin => point.thermal_margin_c,
+        }
+    }
+
+    /// Axis label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Objective::Efficiency => "Efficiency",
+            Objective::Volume => "Volume",
+            Objective::Cost => "Cost",
+            Objective::ThermalMargin => "Thermal Margin",
+        }
+    }
+
+    /// Axis unit
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Objective::Efficiency => "%",
+            Objective::Volume => "cm³",
+            Objective::Cost => "$",
+            Objective::ThermalMargin => "°C",
+        }
+    }
+}
+
+/// One evaluated converter design
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DesignPoint {
+    /// Converter topology
+    pub topology: TopologyType,
+    /// Switching frequency (Hz)
+    pub switching_freq: f64,
+    /// Inductor ripple ratio the stage was designed for
+    pub ripple_ratio: f64,
+    /// Selected inductance (H)
+    pub inductance: f64,
+    /// Selected output capacitance (F)
+    pub output_capacitance: f64,
+    /// Inductor magnetics design
+    pub inductor: InductorDesign,
+    /// Main switch part number
+    pub mosfet: String,
+    /// Rectifier diode part number
+    pub diode: String,
+    /// Loop crossover frequency (Hz)
+    pub crossover_freq: f64,
+    /// Loop phase margin (degrees)
+    pub phase_margin_deg: f64,
+    /// Predicted load-step dip (V) for a 50% load step
+    pub load_step_undershoot: f64,
+    /// Loss breakdown at full load, nominal input
+    pub losses: EfficiencyBreakdown,
+    /// Efficiency (0-1)
+    pub efficiency: f64,
+    /// Inductor core volume (cm³)
+    pub volume_cm3: f64,
+    /// Estimated power-stage BOM cost (USD)
+    pub cost: f64,
+    /// Smallest temperature headroom of MOSFET, diode and inductor (°C)
+    pub thermal_margin_c: f64,
+}
+
+impl DesignPoint {
+    /// True if `self` is at least as good in every objective and better in one
+    pub fn dominates(&self, other: &DesignPoint) -> bool {
+        let mut strictly_better = false;
+        for objective in Objective::ALL {
+            let (a, b) = (objective.value(self), objective.value(other));
+            let (a, b) = if objective.maximize() {
+                (a, b)
+            } else {
+                (-a, -b)
+            };
+            if a < b {
+                return false;
+            }
+            if a > b {
+                strictly_better = true;
+            }
+        }
+        strictly_better
+    }
+}
+
+/// Non-dominated set of converter designs
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParetoFront {
+    /// Pareto-optimal designs, sorted by switching frequency
+    pub points: Vec<DesignPoint>,
+    /// Number of feasible candidates evaluated
+    pub evaluated: usize,
+}
+
+impl ParetoFront {
+    /// Keep the non-dominated candidates
+    pub fn from_candidates(candidates: Vec<DesignPoint>, evaluated: usize) -> Self {
+        let mut points: Vec<DesignPoint> = candidates
+            .iter()
+            .filter(|p| !candidates.iter().any(|q| q.dominates(p)))
+            .cloned()
+            .collect();
+        points.sort_by(|a, b| a.switching_freq.total_cmp(&b.switching_freq));
+        Self { points, evaluated }
+    }
+
+    /// Best design for a single objective
+    pub fn best(&self, objective: Objective) -> Option<&DesignPoint> {
+        let key = |p: &&DesignPoint| {
+            let v = objective.value(p);
+            if objective.maximize() {
+                v
+            } else {
+                -v
+            }
+        };
+        self.points.iter().max_by(|a, b| key(a).total_cmp(&key(b)))
+    }
+
+    /// (x, y) pairs for plotting two objectives, sorted by x
+    pub fn series(&self, x: Objective, y: Objective) -> Vec<(f64, f64)> {
+        let mut series: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .map(|p| (x.value(p), y.value(p)))
+            .collect();
+        series.sort_by(|a, b| a.0.total_cmp(&b.0));
+        series
+    }
+}
+
+// ============================================================================
+// OPTIMIZATION
+// ============================================================================
+
+/// Optimize a buck converter across frequency, magnetics, parts and loop
+/// with the built-in parts
+pub fn optimize_buck(req: &BuckRequirements, spec: &OptimizerSpec) -> Result<ParetoFront, String> {
+    optimize_buck_with(req, spec, builtin_parts())
+}
+
+/// Optimize a buck converter over the parts in `library`
+pub fn optimize_buck_with(
+    req: &BuckRequirements,
+    spec: &OptimizerSpec,
+    library: &PartLibrary,
+) -> Result<ParetoFront, String> {
+    spec.validate()?;
+    let mut candidates = Vec::new();
+    let mut evaluated = 0;
+
+    for fsw in spec.frequencies() {
+        for &ripple_ratio in &spec.ripple_ratios {
+            let stage_req = BuckRequirements {
+                switching_freq_hz: fsw,
+                ripple: RippleSpec {
+                    current_ratio: ripple_ratio,
+                    ..req.ripple
+                },
+                ..req.clone()
+            };
+            let Ok(design) = design_buck_with(&stage_req, library) else {
+                continue;
+            };
+            let Some(loop_choice) = best_loop(spec, fsw, |ts| buck_transient(&design, ts)) else {
+                continue;
+            };
+            let stage = PowerStage::buck(&design, ripple_ratio);
+            evaluated += evaluate_stage(library, &stage, &loop_choice, spec, &mut candidates);
+        }
+    }
+
+    finish(candidates, evaluated)
+}
+
+/// Optimize a boost converter across frequency, magnetics, parts and loop
+/// with the built-in parts
+pub fn optimize_boost(
+    req: &BoostRequirements,
+    spec: &OptimizerSpec,
+) -> Result<ParetoFront, String> {
+    optimize_boost_with(req, spec, builtin_parts())
+}
+
+/// Optimize a boost converter over the parts in `library`
+pub fn optimize_boost_with(
+    req: &BoostRequirements,
+    spec: &OptimizerSpec,
+    library: &PartLibrary,
+) -> Result<ParetoFront, String> {
+    spec.validate()?;
+    let mut candidates = Vec::new();
+    let mut evaluated = 0;
+
+    for fsw in spec.frequencies() {
+        for &ripple_ratio in &spec.ripple_ratios {
+            let stage_req = BoostRequirements {
+                switching_freq_hz: fsw,
+                ripple: RippleSpec {
+                    current_ratio: ripple_ratio,
+                    ..req.ripple
+                },
+                ..req.clone()
+            };
+            let Ok(design) = design_boost_with(&stage_req, library) else {
+                continue;
+            };
+            let Some(loop_choice) = best_loop(spec, fsw, |ts| boost_transient(&design, ts)) else {
+                continue;
+            };
+            let stage = PowerStage::boost(&design, ripple_ratio);
+            evaluated += evaluate_stage(library, &stage, &loop_choice, spec, &mut candidates);
+        }
+    }
+
+    finish(candidates, evaluated)
+}
+
+fn finish(candidates: Vec<DesignPoint>, evaluated: usize) -> Result<ParetoFront, String> {
+    if candidates.is_empty() {
+        return Err(
+            "No feasible design: widen the frequency range or relax the loop requirements"
+                .to_string(),
+        );
+    }
+    Ok(ParetoFront::from_candidates(candidates, evaluated))
+}
+
+/// Compensator setting kept for a power stage
+struct LoopChoice {
+    crossover_freq: f64,
+    phase_margin_deg: f64,
+    undershoot: f64,
+}
+
+/// Stable crossover with the smallest load-step dip
+fn best_loop<F>(spec: &OptimizerSpec, fsw: f64, transient: F) -> Option<LoopChoice>
+where
+    F: Fn(&TransientSpec) -> Result<ConverterTransient, String>,
+{
+    spec.crossover_fractions
+        .iter()
+        .filter_map(|&fraction| {
+            let ts = TransientSpec {
+                crossover_freq: fraction * fsw,
+                output_esr: spec.output_esr,
+                ..Default::default()
+            };
+            transient(&ts).ok()
+        })
+        .filter(|t| t.stability.phase_margin_deg >= spec.min_phase_margin)
+        .map(|t| LoopChoice {
+            crossover_freq: t.stability.crossover_freq,
+            phase_margin_deg: t.stability.phase_margin_deg,
+            undershoot: t.load_step.undershoot,
+        })
+        .min_by(|a, b| a.undershoot.total_cmp(&b.undershoot))
+}
+
+/// Operating stresses of a designed buck/boost power stage
+struct PowerStage {
+    topology: TopologyType,
+    fsw: f64,
+    duty: f64,
+    ripple_ratio: f64,
+    inductance: f64,
+    il_avg: f64,
+    il_ripple: f64,
+    il_peak: f64,
+    output_capacitance: f64,
+    input_capacitance: f64,
+    vout: f64,
+    iout: f64,
+    ambient: f64,
+    /// Switch blocking voltage (V)
+    vds: f64,
+    /// Diode reverse voltage (V)
+    vr: f64,
+    /// Diode average current (A)
+    diode_avg: f64,
+    /// Capacitor ESR and controller losses carried over from the design
+    fixed_losses: EfficiencyBreakdown,
+}
+
+impl PowerStage {
+    fn buck(design: &BuckDesign, ripple_ratio: f64) -> Self {
+        let req = &design.requirements;
+        let d = design.duty_cycle_nom;
+        Self {
+            topology: TopologyType::Buck,
+            fsw: req.switching_freq_hz,
+            duty: d,
+            ripple_ratio,
+            inductance: design.inductor.selected_value,
+            il_avg: req.iout_max,
+            il_ripple: design.inductor_current_ripple_a,
+            il_peak: design.inductor_peak_current_a,
+            output_capacitance: design.output_capacitor.selected_value,
+            input_capacitance: design.input_capacitor.selected_value,
+            vout: req.vout,
+            iout: req.iout_max,
+            ambient: req.ambient_temp_c,
+            vds: req.vin.max_v,
+            vr: req.vin.max_v,
+            diode_avg: req.iout_max * (1.0 - d),
+            fixed_losses: design.efficiency.clone(),
+        }
+    }
+
+    fn boost(design: &BoostDesign, ripple_ratio: f64) -> Self {
+        let req = &design.requirements;
+        let d = design.duty_cycle_nom;
+        Self {
+            topology: TopologyType::Boost,
+            fsw: req.switching_freq_hz,
+            duty: d,
+            ripple_ratio,
+            inductance: design.inductor.selected_value,
+            il_avg: design.input_current_a,
+            il_ripple: design.inductor_current_ripple_a,
+            il_peak: design.inductor_peak_current_a,
+            output_capacitance: design.output_capacitor.selected_value,
+            input_capacitance: design.input_capacitor.selected_value,
+            vout: req.vout,
+            iout: req.iout_max,
+            ambient: req.ambient_temp_c,
+            vds: design.switch_voltage_stress_v,
+            vr: req.vout,
+            diode_avg: req.iout_max,
+            fixed_losses: design.efficiency.clone(),
+        }
+    }
+
+    /// RMS inductor current (A)
+    fn il_rms(&self) -> f64 {
+        (self.il_avg.powi(2) + self.il_ripple.powi(2) / 12.0).sqrt()
+    }
+}
+
+/// Score every magnetics/MOSFET/diode combination of one power stage,
+/// returning how many passed the thermal check
+fn evaluate_stage(
+    library: &PartLibrary,
+    stage: &PowerStage,
+    loop_choice: &LoopChoice,
+    spec: &OptimizerSpec,
+    candidates: &mut Vec<DesignPoint>,
+) -> usize {
+    let il_rms = stage.il_rms();
+    let id_rms = il_rms * stage.duty.sqrt();
+    let diode_rms = il_rms * (1.0 - stage.duty).sqrt();
+
+    let mosfets: Vec<&MOSFETSpec> = find_suitable_mosfets(
+        library,
+        stage.vds,
+        id_rms,
+        stage.il_peak,
+        MOSFETPreference::LowLosses,
+    )
+    .into_iter()
+    .take(spec.max_parts)
+    .collect();
+    let diodes: Vec<&DiodeSpec> = find_suitable_diodes(
+        library,
+        stage.vr,
+        stage.diode_avg,
+        diode_rms,
+        DiodePreference::LowLosses,
+    )
+    .into_iter()
+    .take(spec.max_parts)
+    .collect();
+
+    let inductor_req = InductorRequirements {
+        duty_cycle: stage.duty,
+        ambient_temp: stage.ambient,
+        ..InductorRequirements::new(stage.inductance, stage.il_avg, stage.il_ripple, stage.fsw)
+    };
+    let inductors: Vec<InductorDesign> = spec
+        .core_types
+        .iter()
+        .filter_map(|&ct| {
+            design_inductor(
+                library,
+                &InductorRequirements {
+                    core_type: Some(ct),
+                    ..inductor_req.clone()
+                },
+            )
+        })
+        .collect();
+
+    let capacitor_cost = capacitor_cost(stage.output_capacitance, stage.vout)
+        + capacitor_cost(stage.input_capacitance, stage.vds);
+
+    let mut count = 0;
+    for inductor in &inductors {
+        let inductor_margin = inductor_req.max_temp_rise - inductor.temp_rise;
+        for mosfet in &mosfets {
+            let mut op = MOSFETOperatingPoint {
+                id_rms,
+                id_peak: stage.il_peak,
+                vds_switch: stage.vds,
+                fsw: stage.fsw,
+                duty_cycle: stage.duty,
+                tj_estimate: TJ_INITIAL,
+                ..Default::default()
+            };
+            // One refinement pass with the junction temperature from the first
+            let first = mosfet.calculate_losses(&op);
+            op.tj_estimate = mosfet.junction_temperature(
+                first.total - first.reverse_recovery,
+                stage.ambient,
+                None,
+            );
+            let m_losses = mosfet.calculate_losses(&op);
+            // The body diode never conducts with an external rectifier diode
+            let p_mosfet = m_losses.total - m_losses.reverse_recovery;
+            let tj_mosfet = mosfet.junction_temperature(p_mosfet, stage.ambient, None);
+
+            for diode in &diodes {
+                let d_losses = diode.calculate_losses(&DiodeOperatingPoint {
+                    if_avg: stage.diode_avg,
+                    if_rms: diode_rms,
+                    if_peak: stage.il_peak,
+                    vr_off: stage.vr,
+                    fsw: stage.fsw,
+                    tj_estimate: TJ_INITIAL,
+                    ..Default::default()
+                });
+                let tj_diode = diode.junction_temperature(d_losses.total, stage.ambient, None);
+
+                let thermal_margin = (mosfet.tj_max - tj_mosfet)
+                    .min(diode.tj_max - tj_diode)
+                    .min(inductor_margin);
+                if thermal_margin < 0.0 {
+                    continue;
+                }
+                count += 1;
+
+                let p_out = stage.vout * stage.iout;
+                let mut losses = EfficiencyBreakdown {
+                    total_efficiency: 0.0,
+                    conduction_loss_w: m_losses.conduction,
+                    switching_loss_w: p_mosfet - m_losses.conduction,
+                    diode_loss_w: d_losses.total,
+                    inductor_dcr_loss_w: inductor.copper_loss,
+                    inductor_core_loss_w: inductor.core_loss,
+                    capacitor_esr_loss_w: stage.fixed_losses.capacitor_esr_loss_w,
+                    quiescent_loss_w: stage.fixed_losses.quiescent_loss_w,
+                    output_power_w: p_out,
+                    input_power_w: 0.0,
+                };
+                losses.input_power_w = p_out + losses.total_losses();
+                losses.total_efficiency = p_out / losses.input_power_w;
+
+                candidates.push(DesignPoint {
+                    topology: stage.topology,
+                    switching_freq: stage.fsw,
+                    ripple_ratio: stage.ripple_ratio,
+                    inductance: stage.inductance,
+                    output_capacitance: stage.output_capacitance,
+                    inductor: inductor.clone(),
+                    mosfet: mosfet.part_number.clone(),
+                    diode: diode.part_number.clone(),
+                    crossover_freq: loop_choice.crossover_freq,
+                    phase_margin_deg: loop_choice.phase_margin_deg,
+                    load_step_undershoot: loop_choice.undershoot,
+                    efficiency: losses.total_efficiency,
+                    losses,
+                    volume_cm3: inductor.core.volume_cm3(),
+                    cost: mosfet_cost(mosfet)
+                        + diode_cost(diode)
+                        + inductor_cost(inductor)
+                        + capacitor_cost,
+                    thermal_margin_c: thermal_margin,
+                });
+            }
+        }
+    }
+    count
+}
+
+// ============================================================================
+// COST MODEL
+// ============================================================================
+//
+// Rough 1k-quantity distributor prices (USD). Only relative cost matters for
+// the Pareto ranking, so the model captures trends: bigger packages and
+// higher voltage cost more, SiC costs several times silicon, magnetics cost
+// scales with core weight and copper length.
+
+fn mosfet_cost(mosfet: &MOSFETSpec) -> f64 {
+    let package = match mosfet.package {
+        MOSFETPackage::SO8 => 0.35,
+        MOSFETPackage::PowerPAK | MOSFETPackage::QFN | MOSFETPackage::LFPAK => 0.55,
+        MOSFETPackage::DPAK => 0.45,
+        MOSFETPackage::D2PAK => 0.85,
+        MOSFETPackage::TO220 | MOSFETPackage::TO220F => 0.75,
+        MOSFETPackage::TO247 => 2.0,
+    };
+    package * (1.0 + mosfet.vds_max / 300.0)
+}
+
+fn diode_cost(diode: &DiodeSpec) -> f64 {
+    let base = match diode.diode_type {
+        DiodeType::Standard => 0.10,
+        DiodeType::Schottky => 0.30,
+        DiodeType::FastRecovery => 0.30,
+        DiodeType::Ultrafast => 0.40,
+        DiodeType::SiCSchottky => 2.00,
+    };
+    base * (1.0 + diode.if_avg / 20.0)
+}
+
+fn inductor_cost(inductor: &InductorDesign) -> f64 {
+    let per_gram = match inductor.material.material_type {
+        MaterialType::MnZnFerrite | MaterialType::NiZnFerrite => 0.03,
+        _ => 0.06,
+    };
+    let per_metre = match inductor.wire {
+        InductorWire::Solid(_) => 0.05,
+        InductorWire::Litz(_) => 0.40,
+    };
+    let wire_m = inductor.turns as f64 * inductor.core.mlt / 1000.0;
+    let gapping = if inductor.gap_length > 0.0 { 0.10 } else { 0.0 };
+    // Bobbin, clips and winding labour
+    0.30 + inductor.core.weight * per_gram + wire_m * per_metre + gapping
+}
+
+fn capacitor_cost(capacitance: f64, voltage: f64) -> f64 {
+    // Cost tracks stored energy rating (C × V): ~$0.02 per µF at 25 V
+    0.05 + 0.02 * capacitance * 1e6 * (voltage / 25.0).max(0.4)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quick_spec() -> OptimizerSpec {
+        OptimizerSpec {
+            fsw_steps: 3,
+            ripple_ratios: vec![0.3],
+            core_types: vec![CoreType::ECore, CoreType::PQ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_frequencies_log_spaced() {
+        let f = OptimizerSpec::default().frequencies();
+        assert_eq!(f.len(), 5);
+        assert!((f[0] - 100e3).abs() < 1e-6);
+        assert!((f[4] - 1e6).abs() < 1e-3);
+        assert!((f[2] / f[1] - f[1] / f[0]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_buck_front_is_non_dominated() {
+        let front = optimize_buck(&BuckRequirements::default(), &quick_spec()).unwrap();
+        assert!(!front.points.is_empty());
+        assert!(front.evaluated >= front.points.len());
+
+        for a in &front.points {
+            assert!(!front.points.iter().any(|b| b.dominates(a)));
+            assert!(a.phase_margin_deg >= 45.0);
+            assert!(a.thermal_margin_c >= 0.0);
+            let total = a.losses.total_losses();
+            assert!(
+                (a.efficiency - a.losses.output_power_w / (a.losses.output_power_w + total)).abs()
+                    < 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_front_spans_tradeoff() {
+        let front = optimize_buck(&BuckRequirements::default(), &OptimizerSpec::default()).unwrap();
+        let smallest = front.best(Objective::Volume).unwrap();
+        let most_efficient = front.best(Objective::Efficiency).unwrap();
+
+        // Higher frequency buys smaller magnetics at some efficiency cost
+        assert!(smallest.volume_cm3 <= most_efficient.volume_cm3);
+        assert!(smallest.switching_freq >= most_efficient.switching_freq);
+
+        let series = front.series(Objective::Volume, Objective::Efficiency);
+        assert_eq!(series.len(), front.points.len());
+        assert!(series.windows(2).all(|w| w[0].0 <= w[1].0));
+    }
+
+    #[test]
+    fn test_boost_front() {
+        // The RHP zero limits boost phase margin, so accept a lower one
+        let spec = OptimizerSpec {
+            min_phase_margin: 35.0,
+            crossover_fractions: vec![0.02, 0.05],
+            ..quick_spec()
+        };
+        let front = optimize_boost(&BoostRequirements::default(), &spec).unwrap();
+        let best = front.best(Objective::Efficiency).unwrap();
+        assert_eq!(best.topology, TopologyType::Boost);
+        assert!(best.phase_margin_deg >= 35.0);
+        assert!(best.crossover_freq < best.switching_freq / 10.0);
+    }
+
+    #[test]
+    fn test_invalid_spec() {
+        let spec = OptimizerSpec {
+            fsw_max: 10e3,
+            ..Default::default()
+        };
+        assert!(optimize_buck(&BuckRequirements::default(), &spec).is_err());
+    }
+}
@@ -0,0 +1,1044 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: switched.rs | DNA/src/power/switched.rs
+//! PURPOSE: Cycle-by-cycle switched converter simulation with parasitics, DCM and closed-loop PWM
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Unlike the fixed-duty ideal models in `transient`, this simulator resolves
+//! every switching edge of a non-ideal converter:
+//!
+//! - **Parasitics**: inductor DCR, capacitor ESR, MOSFET Rds(on), diode Vf or
+//!   synchronous rectifier Rds(on), taken from `power::components` specs
+//! - **DCM**: a third state when the diode current reaches zero, with the
+//!   inductor current held at zero until the next switching cycle
+//! - **Closed loop**: a `CompensatorDesign` discretized with the bilinear
+//!   transform drives a trailing-edge PWM comparator, with soft-start and
+//!   duty clamping
+//! - **Events**: load and line steps at arbitrary times
+//!
+//! # Algorithm
+//!
+//! 1. **Piecewise-linear states**: ON, OFF (rectifier conducting) and DCM
+//!    each have an affine model dx/dt = A·x + b with x = [iL, vC]
+//! 2. **Integration**: Trapezoidal rule with sub-steps that land exactly on
+//!    the PWM turn-off instant and on the diode current zero crossing
+//! 3. **Control**: The compensator is sampled once per integration step; the
+//!    comparator latches off for the rest of the cycle once the ramp crosses
+//!    the control voltage
+//!
+//! # Example
+//!
+//! ```rust
+//! use dna::power::switched::{simulate_switched, DutyControl, SwitchedCircuit, SwitchedOptions};
+//!
+//! let circuit = SwitchedCircuit::buck(12.0, 10e-6, 100e-6, 2.5, 500e3)
+//!     .with_parasitics(0.02, 0.01);
+//! let options = SwitchedOptions {
+//!     duration: 400e-6,
+//!     ..Default::default()
+//! };
+//!
+//! let result = simulate_switched(&circuit, &DutyControl::Open { duty: 0.42 }, &options)
+//!     .expect("Simulation failed");
+//! println!("Vout: {:.3} V", result.steady_state_vout());
+//! println!("Efficiency: {:.1}%", result.efficiency() * 100.0);
+//! ```
+
+use super::components::{DiodeSpec, MOSFETSpec};
+use super::control::{
+    CompensatorDesign, CompensatorRequirements, DiscreteCompensator, TransferFunction,
+};
+use super::state_space::Matrix2x2;
+use super::types::{BoostDesign, BuckDesign};
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// CIRCUIT DEFINITION
+// ============================================================================
+
+/// Power stage topology
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SwitchedTopology {
+    /// Step-down: switch from Vin to the inductor, rectifier to ground
+    Buck,
+    /// Step-up: inductor from Vin, switch to ground, rectifier to the output
+    Boost,
+}
+
+/// Freewheeling path used while the main switch is off
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Rectifier {
+    /// Diode with constant forward drop (V); blocks reverse current, so the
+    /// converter enters DCM at light load
+    Diode { vf: f64 },
+    /// Synchronous MOSFET with on-resistance (Ohm); conducts in both
+    /// directions, so the converter stays in forced CCM
+    Synchronous { rds_on: f64 },
+}
+
+/// Non-ideal switched power stage
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SwitchedCircuit {
+    /// Topology
+    pub topology: SwitchedTopology,
+    /// Input voltage (V)
+    pub vin: f64,
+    /// Switching frequency (Hz)
+    pub fsw: f64,
+    /// Inductance (H)
+    pub inductance: f64,
+    /// Inductor DC resistance (Ohm)
+    pub dcr: f64,
+    /// Output capacitance (F)
+    pub capacitance: f64,
+    /// Output capacitor ESR (Ohm)
+    pub esr: f64,
+    /// Main switch on-resistance (Ohm)
+    pub rds_on: f64,
+    /// Freewheeling rectifier
+    pub rectifier: Rectifier,
+    /// Initial load resistance (Ohm)
+    pub load_resistance: f64,
+}
+
+impl SwitchedCircuit {
+    /// Ideal buck stage (no parasitics, zero-drop diode)
+    pub fn buck(
+        vin: f64,
+        inductance: f64,
+        capacitance: f64,
+        load_resistance: f64,
+        fsw: f64,
+    ) -> Self {
+        Self {
+            topology: SwitchedTopology::Buck,
+            vin,
+            fsw,
+            inductance,
+            dcr: 0.0,
+            capacitance,
+            esr: 0.0,
+            rds_on: 0.0,
+            rectifier: Rectifier::Diode { vf: 0.0 },
+            load_resistance,
+        }
+    }
+
+    /// Ideal boost stage (no parasitics, zero-drop diode)
+    pub fn boost(
+        vin: f64,
+        inductance: f64,
+        capacitance: f64,
+        load_resistance: f64,
+        fsw: f64,
+    ) -> Self {
+        Self {
+            topology: SwitchedTopology::Boost,
+            ..Self::buck(vin, inductance, capacitance, load_resistance, fsw)
+        }
+    }
+
+    /// Power stage of a buck design at nominal input and full load
+    pub fn from_buck_design(design: &BuckDesign) -> Self {
+        let req = &design.requirements;
+        Self::buck(
+            req.vin.nom_v,
+            design.inductor.selected_value,
+            design.output_capacitor.selected_value,
+            req.vout / req.iout_max,
+            req.switching_freq_hz,
+        )
+    }
+
+    /// Power stage of a boost design at nominal input and full load
+    pub fn from_boost_design(design: &BoostDesign) -> Self {
+        let req = &design.requirements;
+        Self::boost(
+            req.vin.nom_v,
+            design.inductor.selected_value,
+            design.output_capacitor.selected_value,
+            req.vout / req.iout_max,
+            req.switching_freq_hz,
+        )
+    }
+
+    /// Set inductor DCR and capacitor ESR (Ohm)
+    pub fn with_parasitics(mut self, dcr: f64, esr: f64) -> Self {
+        self.dcr = dcr;
+        self.esr = esr;
+        self
+    }
+
+    /// Use a MOSFET as the main switch at junction temperature tj (°C)
+    pub fn with_mosfet(mut self, mosfet: &MOSFETSpec, tj: f64) -> Self {
+        self.rds_on = mosfet.rds_on_at_temp(tj);
+        self
+    }
+
+    /// Use a diode as the rectifier at junction temperature tj (°C)
+    pub fn with_diode(mut self, diode: &DiodeSpec, tj: f64) -> Self {
+        self.rectifier = Rectifier::Diode {
+            vf: diode.vf_at_temp(tj),
+        };
+        self
+    }
+
+    /// Use a MOSFET as synchronous rectifier at junction temperature tj (°C)
+    pub fn with_synchronous_rectifier(mut self, mosfet: &MOSFETSpec, tj: f64) -> Self {
+        self.rectifier = Rectifier::Synchronous {
+            rds_on: mosfet.rds_on_at_temp(tj),
+        };
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let positive = [
+            ("Switching frequency", self.fsw),
+            ("Inductance", self.inductance),
+            ("Capacitance", self.capacitance),
+            ("Load resistance", self.load_resistance),
+        ];
+        for (name, value) in positive {
+            if !(value > 0.0 && value.is_finite()) {
+                return Err(format!("{} must be positive", name));
+            }
+        }
+        let rectifier_loss = match self.rectifier {
+            Rectifier::Diode { vf } => vf,
+            Rectifier::Synchronous { rds_on } => rds_on,
+        };
+        if self.dcr < 0.0 || self.esr < 0.0 || self.rds_on < 0.0 || rectifier_loss < 0.0 {
+            return Err("Parasitic resistances and drops must be non-negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// CONTROL AND EVENTS
+// ============================================================================
+
+/// How the duty cycle is commanded
+#[derive(Clone, Debug)]
+pub enum DutyControl {
+    /// Fixed duty cycle (0.0 to 1.0)
+    Open { duty: f64 },
+    /// Voltage-mode feedback loop
+    Voltage(VoltageLoop),
+}
+
+/// Voltage-mode feedback loop: divider, error amplifier and PWM ramp
+///
+/// The error amplifier computes vc = Gc(s)·(Vref - k·Vout); the comparator
+/// keeps the switch on while the ramp (0 to `ramp_amplitude`) is below vc.
+#[derive(Clone, Debug)]
+pub struct VoltageLoop {
+    /// Compensator transfer function Gc(s)
+    pub compensator: TransferFunction,
+    /// Reference voltage (V)
+    pub vref: f64,
+    /// Feedback divider ratio k = Vfb/Vout
+    pub divider_ratio: f64,
+    /// PWM ramp peak-to-peak amplitude (V)
+    pub ramp_amplitude: f64,
+    /// Maximum duty cycle
+    pub max_duty: f64,
+    /// Reference ramp-up time (s), 0 for a hard start
+    pub soft_start: f64,
+}
+
+impl VoltageLoop {
+    /// Loop built from a compensator design and its requirements
+    pub fn from_design(design: &CompensatorDesign, requirements: &CompensatorRequirements) -> Self {
+        Self {
+            compensator: design.transfer_function.clone(),
+            vref: requirements.vref,
+            divider_ratio: requirements.divider_ratio,
+            ramp_amplitude: 1.0 / requirements.modulator_gain,
+            max_duty: 0.95,
+            soft_start: 0.0,
+        }
+    }
+
+    /// Set the soft-start time (s)
+    pub fn with_soft_start(mut self, soft_start: f64) -> Self {
+        self.soft_start = soft_start;
+        self
+    }
+
+    /// Set the maximum duty cycle
+    pub fn with_max_duty(mut self, max_duty: f64) -> Self {
+        self.max_duty = max_duty;
+        self
+    }
+
+    /// Output voltage the loop regulates to (V)
+    pub fn regulated_vout(&self) -> f64 {
+        self.vref / self.divider_ratio
+    }
+}
+
+/// Disturbance applied during the simulation
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SwitchedEvent {
+    /// Change the load resistance (Ohm) at the given time (s)
+    LoadStep { time: f64, resistance: f64 },
+    /// Change the input voltage (V) at the given time (s)
+    LineStep { time: f64, vin: f64 },
+}
+
+impl SwitchedEvent {
+    /// Time at which the event is applied (s)
+    pub fn time(&self) -> f64 {
+        match *self {
+            SwitchedEvent::LoadStep { time, .. } | SwitchedEvent::LineStep { time, .. } => time,
+        }
+    }
+}
+
+/// Simulation settings
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SwitchedOptions {
+    /// Simulation duration (s)
+    pub duration: f64,
+    /// Integration steps per switching period
+    pub steps_per_cycle: usize,
+    /// Time step for output waveforms (s), 0 to keep every integration step
+    pub output_step: f64,
+    /// Initial inductor current (A)
+    pub initial_il: f64,
+    /// Initial capacitor voltage (V)
+    pub initial_vc: f64,
+    /// Load and line steps
+    pub events: Vec<SwitchedEvent>,
+}
+
+impl Default for SwitchedOptions {
+    fn default() -> Self {
+        Self {
+            duration: 1e-3,
+            steps_per_cycle: 200,
+            output_step: 0.0,
+            initial_il: 0.0,
+            initial_vc: 0.0,
+            events: Vec::new(),
+        }
+    }
+}
+
+// ============================================================================
+// SIMULATION RESULTS
+// ============================================================================
+
+/// Power stage state during an interval
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConductionState {
+    /// Main switch conducting
+    On,
+    /// Rectifier conducting
+    Off,
+    /// Discontinuous: switch off, diode blocking, inductor current zero
+    Dcm,
+}
+
+/// Averages and energies over one switching cycle
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CycleSummary {
+    /// Cycle start time (s)
+    pub start: f64,
+    /// Actual duty cycle (switch on-time / period)
+    pub duty: f64,
+    /// Average output voltage (V)
+    pub vout_avg: f64,
+    /// Average inductor current (A)
+    pub il_avg: f64,
+    /// Peak inductor current (A)
+    pub il_max: f64,
+    /// Minimum inductor current (A)
+    pub il_min: f64,
+    /// Whether the cycle contained a DCM interval
+    pub dcm: bool,
+    /// Energy drawn from the input (J)
+    pub input_energy: f64,
+    /// Energy delivered to the load (J)
+    pub output_energy: f64,
+}
+
+/// Deviation and recovery after a disturbance
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct StepMetrics {
+    /// Peak rise of the cycle-averaged output above target (V)
+    pub overshoot: f64,
+    /// Peak drop of the cycle-averaged output below target (V)
+    pub undershoot: f64,
+    /// Time from the event until the output stays inside the band (s)
+    pub settling_time: f64,
+    /// Whether the output ends inside the band
+    pub settled: bool,
+}
+
+/// Results from a switched simulation
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SwitchedResult {
+    /// Time points (s)
+    pub time: Vec<f64>,
+    /// Output voltage including ESR ripple (V)
+    pub v_out: Vec<f64>,
+    /// Inductor current (A)
+    pub i_l: Vec<f64>,
+    /// Commanded duty cycle
+    pub duty: Vec<f64>,
+    /// Power stage state at the end of each time point's step
+    pub state: Vec<ConductionState>,
+    /// Per-cycle summaries
+    pub cycles: Vec<CycleSummary>,
+}
+
+impl SwitchedResult {
+    /// Average output voltage over the last ten cycles
+    pub fn steady_state_vout(&self) -> f64 {
+        let n = self.cycles.len().min(10);
+        if n == 0 {
+            return 0.0;
+        }
+        self.cycles
+            .iter()
+            .rev()
+            .take(n)
+            .map(|c| c.vout_avg)
+            .sum::<f64>()
+            / n as f64
+    }
+
+    /// Peak-to-peak output ripple over the last cycle
+    pub fn output_ripple_pp(&self) -> f64 {
+        let Some(last) = self.cycles.last() else {
+            return 0.0;
+        };
+        let window = self
+            .time
+            .iter()
+            .zip(&self.v_out)
+            .filter(|(t, _)| **t > last.start)
+            .map(|(_, v)| *v);
+        let (min, max) = window.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        });
+        if max >= min {
+            max - min
+        } else {
+            0.0
+        }
+    }
+
+    /// Output/input energy ratio over the last ten cycles
+    pub fn efficiency(&self) -> f64 {
+        let n = self.cycles.len().min(10);
+        let (e_in, e_out) = self
+            .cycles
+            .iter()
+            .rev()
+            .take(n)
+            .fold((0.0, 0.0), |(i, o), c| {
+                (i + c.input_energy, o + c.output_energy)
+            });
+        if e_in > 0.0 {
+            e_out / e_in
+        } else {
+            0.0
+        }
+    }
+
+    /// Number of cycles that contained a DCM interval
+    pub fn dcm_cycles(&self) -> usize {
+        self.cycles.iter().filter(|c| c.dcm).count()
+    }
+
+    /// Overshoot, undershoot and settling after an event at `event_time`
+    ///
+    /// Uses cycle averages so switching ripple does not count as deviation;
+    /// `band` is the relative settling tolerance (e.g. 0.02 for ±2%).
+    pub fn step_metrics(&self, event_time: f64, target: f64, band: f64) -> StepMetrics {
+        let after: Vec<&CycleSummary> = self
+            .cycles
+            .iter()
+            .filter(|c| c.start >= event_time)
+            .collect();
+        let tolerance = band * target.abs();
+
+        let mut overshoot: f64 = 0.0;
+        let mut undershoot: f64 = 0.0;
+        let mut settled_from = event_time;
+        for (i, cycle) in after.iter().enumerate() {
+            let error = cycle.vout_avg - target;
+            overshoot = overshoot.max(error);
+            undershoot = undershoot.max(-error);
+            if error.abs() > tolerance {
+                settled_from = after.get(i + 1).map_or(f64::INFINITY, |next| next.start);
+            }
+        }
+
+        StepMetrics {
+            overshoot,
+            undershoot,
+            settling_time: settled_from - event_time,
+            settled: settled_from.is_finite() && !after.is_empty(),
+        }
+    }
+}
+
+// ============================================================================
+// STATE MODELS
+// ============================================================================
+
+/// Element values that can change during a run
+#[derive(Clone, Copy, Debug)]
+struct Operating {
+    vin: f64,
+    load: f64,
+}
+
+/// Affine model dx/dt = A·x + b for x = [iL, vC]
+fn state_model(
+    circuit: &SwitchedCircuit,
+    op: Operating,
+    state: ConductionState,
+) -> (Matrix2x2, [f64; 2]) {
+    let l = circuit.inductance;
+    let c = circuit.capacitance;
+    let r = op.load;
+    // Output node divides between load and ESR branch
+    let k = r / (r + circuit.esr);
+
+    // Inductor current flows into the output node
+    let feeding = |r_path: f64, v_src: f64| {
+        (
+            Matrix2x2::new(-(r_path + k * circuit.esr) / l, -k / l, k / c, -k / (r * c)),
+            [v_src / l, 0.0],
+        )
+    };
+    // Inductor loop is separate from the output (boost ON)
+    let isolated = |r_path: f64, v_src: f64| {
+        (
+            Matrix2x2::new(-r_path / l, 0.0, 0.0, -k / (r * c)),
+            [v_src / l, 0.0],
+        )
+    };
+
+    let (r_rect, v_rect) = match circuit.rectifier {
+        Rectifier::Diode { vf } => (0.0, vf),
+        Rectifier::Synchronous { rds_on } => (rds_on, 0.0),
+    };
+
+    match (circuit.topology, state) {
+        (_, ConductionState::Dcm) => isolated(0.0, 0.0),
+        (SwitchedTopology::Buck, ConductionState::On) => {
+            feeding(circuit.rds_on + circuit.dcr, op.vin)
+        }
+        (SwitchedTopology::Buck, ConductionState::Off) => feeding(circuit.dcr + r_rect, -v_rect),
+        (SwitchedTopology::Boost, ConductionState::On) => {
+            isolated(circuit.rds_on + circuit.dcr, op.vin)
+        }
+        (SwitchedTopology::Boost, ConductionState::Off) => {
+            feeding(circuit.dcr + r_rect, op.vin - v_rect)
+        }
+    }
+}
+
+/// Output voltage including the ESR drop
+fn output_voltage(
+    circuit: &SwitchedCircuit,
+    op: Operating,
+    state: ConductionState,
+    x: [f64; 2],
+) -> f64 {
+    let k = op.load / (op.load + circuit.esr);
+    let feeds_output = match (circuit.topology, state) {
+        (_, ConductionState::Dcm) => false,
+        (SwitchedTopology::Buck, _) => true,
+        (SwitchedTopology::Boost, ConductionState::On) => false,
+        (SwitchedTopology::Boost, ConductionState::Off) => true,
+    };
+    let i_feed = if feeds_output { x[0] } else { 0.0 };
+    k * (x[1] + circuit.esr * i_feed)
+}
+
+/// Current drawn from the input source
+fn input_current(circuit: &SwitchedCircuit, state: ConductionState, il: f64) -> f64 {
+    match (circuit.topology, state) {
+        (_, ConductionState::Dcm) => 0.0,
+        (SwitchedTopology::Buck, ConductionState::On) => il,
+        (SwitchedTopology::Buck, ConductionState::Off) => 0.0,
+        (SwitchedTopology::Boost, _) => il,
+    }
+}
+
+/// State with the switch open: the diode conducts unless it would need
+/// reverse current, in which case the inductor current stays at zero
+fn off_state(circuit: &SwitchedCircuit, op: Operating, x: [f64; 2]) -> ConductionState {
+    if matches!(circuit.rectifier, Rectifier::Synchronous { .. }) || x[0] > 0.0 {
+        return ConductionState::Off;
+    }
+    let (a, b) = state_model(circuit, op, ConductionState::Off);
+    let rising = b[0] + a.m[0][1] * x[1] > 0.0;
+    if rising {
+        ConductionState::Off
+    } else {
+        ConductionState::Dcm
+    }
+}
+
+/// Trapezoidal step: (I - dt/2·A)·x' = (I + dt/2·A)·x + dt·b
+fn trapezoidal_step(x: [f64; 2], a: &Matrix2x2, b: &[f64; 2], dt: f64) -> [f64; 2] {
+    let half = 0.5 * dt;
+    let lhs = Matrix2x2::new(
+        1.0 - half * a.m[0][0],
+        -half * a.m[0][1],
+        -half * a.m[1][0],
+        1.0 - half * a.m[1][1],
+    );
+    let ax = a.mul_vec(x);
+    let rhs = [
+        x[0] + half * ax[0] + dt * b[0],
+        x[1] + half * ax[1] + dt * b[1],
+    ];
+    match lhs.inverse() {
+        Some(inv) => inv.mul_vec(rhs),
+        None => x,
+    }
+}
+
+// ============================================================================
+// SIMULATION ENGINE
+// ============================================================================
+
+/// Sampled voltage loop state
+struct Regulator<'a> {
+    config: &'a VoltageLoop,
+    compensator: DiscreteCompensator,
+}
+
+impl Regulator<'_> {
+    /// Commanded duty from the sampled output voltage at time t
+    fn duty(&mut self, t: f64, vout: f64) -> f64 {
+        let cfg = self.config;
+        let reference = if cfg.soft_start > 0.0 {
+            cfg.vref * (t / cfg.soft_start).min(1.0)
+        } else {
+            cfg.vref
+        };
+        let error = reference - cfg.divider_ratio * vout;
+        let vc = self
+            .compensator
+            .step_clamped(error, 0.0, cfg.ramp_amplitude * cfg.max_duty);
+        vc / cfg.ramp_amplitude
+    }
+}
+
+/// Simulate a switched converter cycle by cycle
+pub fn simulate_switched(
+    circuit: &SwitchedCircuit,
+    control: &DutyControl,
+    options: &SwitchedOptions,
+) -> Result<SwitchedResult, String> {
+    circuit.validate()?;
+    if options.steps_per_cycle < 2 {
+        return Err("At least two steps per switching cycle are required".to_string());
+    }
+    if options.duration.is_nan() || options.duration <= 0.0 {
+        return Err("Duration must be positive".to_string());
+    }
+
+    let period = 1.0 / circuit.fsw;
+    let h = period / options.steps_per_cycle as f64;
+    let n_cycles = (options.duration / period).ceil() as usize;
+
+    let mut regulator = match control {
+        DutyControl::Open { duty } => {
+            if !(0.0..=1.0).contains(duty) {
+                return Err(format!("Duty cycle {} outside [0, 1]", duty));
+            }
+            None
+        }
+        DutyControl::Voltage(config) => {
+            if config.divider_ratio <= 0.0 || config.ramp_amplitude <= 0.0 {
+                return Err("Divider ratio and ramp amplitude must be positive".to_string());
+            }
+            Some(Regulator {
+                config,
+                compensator: DiscreteCompensator::from_transfer_function(&config.compensator, h)?,
+            })
+        }
+    };
+
+    let mut events = options.events.clone();
+    events.sort_by(|a, b| a.time().total_cmp(&b.time()));
+    let mut pending = events.into_iter().peekable();
+
+    let mut op = Operating {
+        vin: circuit.vin,
+        load: circuit.load_resistance,
+    };
+    let mut x = [options.initial_il, options.initial_vc];
+    let mut state = off_state(circuit, op, x);
+
+    let mut result = SwitchedResult {
+        time: Vec::new(),
+        v_out: Vec::new(),
+        i_l: Vec::new(),
+        duty: Vec::new(),
+        state: Vec::new(),
+        cycles: Vec::with_capacity(n_cycles),
+    };
+    let mut next_output_time = 0.0;
+
+    for cycle in 0..n_cycles {
+        let t0 = cycle as f64 * period;
+        let mut switch_on = true;
+        let mut summary = CycleSummary {
+            start: t0,
+            duty: 0.0,
+            vout_avg: 0.0,
+            il_avg: 0.0,
+            il_max: x[0],
+            il_min: x[0],
+            dcm: false,
+            input_energy: 0.0,
+            output_energy: 0.0,
+        };
+
+        for step in 0..options.steps_per_cycle {
+            let t = t0 + step as f64 * h;
+            while let Some(event) = pending.next_if(|e| e.time() <= t + 0.5 * h) {
+                match event {
+                    SwitchedEvent::LoadStep { resistance, .. } => op.load = resistance,
+                    SwitchedEvent::LineStep { vin, .. } => op.vin = vin,
+                }
+            }
+
+            let duty = match (&mut regulator, control) {
+                (Some(reg), _) => reg.duty(t, output_voltage(circuit, op, state, x)),
+                (None, DutyControl::Open { duty }) => *duty,
+                (None, DutyControl::Voltage(_)) => unreachable!(),
+            };
+            let t_off = t0 + duty * period;
+            let end = t + h;
+
+            // Sub-steps split at the comparator trip and the diode zero crossing
+            let mut tau = t;
+            while end - tau > 1e-9 * h {
+                if switch_on && tau >= t_off {
+                    switch_on = false;
+                }
+                let mode = if switch_on {
+                    ConductionState::On
+                } else {
+                    off_state(circuit, op, x)
+                };
+                let trips = switch_on && t_off < end;
+                let mut dt = if trips { t_off - tau } else { end - tau };
+
+                let (a, b) = state_model(circuit, op, mode);
+                let mut next = trapezoidal_step(x, &a, &b, dt);
+                if mode == ConductionState::Off
+                    && matches!(circuit.rectifier, Rectifier::Diode { .. })
+                    && x[0] > 0.0
+                    && next[0] < 0.0
+                {
+                    dt *= x[0] / (x[0] - next[0]);
+                    next = trapezoidal_step(x, &a, &b, dt);
+                    next[0] = 0.0;
+                }
+
+                let v0 = output_voltage(circuit, op, mode, x);
+                let v1 = output_voltage(circuit, op, mode, next);
+                let i0 = input_current(circuit, mode, x[0]);
+                let i1 = input_current(circuit, mode, next[0]);
+                summary.input_energy += op.vin * 0.5 * (i0 + i1) * dt;
+                summary.output_energy += 0.5 * (v0 * v0 + v1 * v1) / op.load * dt;
+                match mode {
+                    ConductionState::On => summary.duty += dt / period,
+                    ConductionState::Dcm => summary.dcm = true,
+                    ConductionState::Off => {}
+                }
+
+                x = next;
+                tau += dt;
+                state = mode;
+                if trips {
+                    switch_on = false;
+                }
+            }
+
+            let vout = output_voltage(circuit, op, state, x);
+            summary.vout_avg += vout / options.steps_per_cycle as f64;
+            summary.il_avg += x[0] / options.steps_per_cycle as f64;
+            summary.il_max = summary.il_max.max(x[0]);
+            summary.il_min = summary.il_min.min(x[0]);
+
+            if end >= next_output_time {
+                result.time.push(end);
+                result.v_out.push(vout);
+                result.i_l.push(x[0]);
+                result.duty.push(duty);
+                result.state.push(state);
+                next_output_time = end + options.output_step;
+            }
+        }
+
+        result.cycles.push(summary);
+    }
+
+    Ok(result)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::control::{design_compensator, CompensatorType, Complex};
+
+    /// Simulate from the initial state [iL, vC]
+    fn run(
+        circuit: &SwitchedCircuit,
+        control: &DutyControl,
+        duration: f64,
+        initial: [f64; 2],
+    ) -> SwitchedResult {
+        let options = SwitchedOptions {
+            duration,
+            initial_il: initial[0],
+            initial_vc: initial[1],
+            ..Default::default()
+        };
+        simulate_switched(circuit, control, &options).unwrap()
+    }
+
+    #[test]
+    fn test_ideal_buck_matches_duty_ratio() {
+        let circuit = SwitchedCircuit::buck(12.0, 22e-6, 100e-6, 2.5, 500e3);
+        let ripple_i = 4.8 * (1.0 - 0.4) / (22e-6 * 500e3);
+        let valley = 1.92 - ripple_i / 2.0;
+        let result = run(
+            &circuit,
+            &DutyControl::Open { duty: 0.4 },
+            1e-3,
+            [valley, 4.8],
+        );
+
+        let vout = result.steady_state_vout();
+        assert!((vout - 4.8).abs() < 0.02, "Vout = {}", vout);
+        assert_eq!(result.dcm_cycles(), 0);
+        assert!(result.efficiency() > 0.99);
+
+        // Ripple agrees with ΔI/(8·fsw·C)
+        let expected = ripple_i / (8.0 * 500e3 * 100e-6);
+        let ripple = result.output_ripple_pp();
+        assert!(
+            (ripple - expected).abs() / expected < 0.1,
+            "ripple = {}",
+            ripple
+        );
+    }
+
+    #[test]
+    fn test_parasitics_reduce_output_and_efficiency() {
+        let duty = 0.45;
+        let (rds, rsync, dcr) = (0.05, 0.03, 0.02);
+        let circuit = SwitchedCircuit {
+            rds_on: rds,
+            rectifier: Rectifier::Synchronous { rds_on: rsync },
+            ..SwitchedCircuit::buck(12.0, 10e-6, 100e-6, 2.5, 500e3).with_parasitics(dcr, 0.01)
+        };
+        let result = run(&circuit, &DutyControl::Open { duty }, 2e-3, [0.0, 0.0]);
+
+        // Averaged model: Vout = D·Vin·R / (R + DCR + D·Rds + (1-D)·Rsync)
+        let r_loss = dcr + duty * rds + (1.0 - duty) * rsync;
+        let expected = duty * 12.0 * 2.5 / (2.5 + r_loss);
+        let vout = result.steady_state_vout();
+        assert!(
+            (vout - expected).abs() / expected < 0.005,
+            "Vout = {}",
+            vout
+        );
+
+        // Conduction losses only: η ≈ R / (R + r_loss)
+        let eta = result.efficiency();
+        assert!((eta - 2.5 / (2.5 + r_loss)).abs() < 0.01, "η = {}", eta);
+    }
+
+    #[test]
+    fn test_buck_enters_dcm_at_light_load() {
+        let (vin, duty, l, r, fsw) = (12.0, 0.3, 4.7e-6, 50.0, 200e3);
+        let circuit = SwitchedCircuit::buck(vin, l, 47e-6, r, fsw);
+        let result = run(&circuit, &DutyControl::Open { duty }, 3e-3, [0.0, 0.0]);
+
+        let last = result.cycles.last().unwrap();
+        assert!(last.dcm);
+        assert!(last.il_min >= 0.0);
+        assert!(result.i_l.iter().all(|&i| i >= 0.0));
+
+        // DCM conversion ratio M = 2 / (1 + √(1 + 4K/D²)), K = 2L/(R·T)
+        let k = 2.0 * l * fsw / r;
+        let m = 2.0 / (1.0 + (1.0 + 4.0 * k / (duty * duty)).sqrt());
+        let vout = result.steady_state_vout();
+        assert!(vout > duty * vin);
+        assert!((vout - m * vin).abs() / (m * vin) < 0.01, "Vout = {}", vout);
+
+        // A synchronous rectifier forces CCM and the ideal ratio
+        let forced = SwitchedCircuit {
+            rectifier: Rectifier::Synchronous { rds_on: 0.0 },
+            ..circuit
+        };
+        let valley = duty * vin / r - duty * vin * (1.0 - duty) / (2.0 * l * fsw);
+        let result = run(
+            &forced,
+            &DutyControl::Open { duty },
+            1e-3,
+            [valley, duty * vin],
+        );
+        assert_eq!(result.dcm_cycles(), 0);
+        assert!((result.steady_state_vout() - duty * vin).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_boost_with_diode_drop() {
+        let (vin, duty, vf) = (5.0, 0.5, 0.4);
+        let circuit = SwitchedCircuit {
+            rectifier: Rectifier::Diode { vf },
+            ..SwitchedCircuit::boost(vin, 22e-6, 100e-6, 20.0, 500e3)
+        };
+        let result = run(&circuit, &DutyControl::Open { duty }, 2e-3, [0.846, 9.6]);
+
+        // Volt-second balance: Vin = (1-D)(Vout + Vf)
+        let expected = vin / (1.0 - duty) - vf;
+        let vout = result.steady_state_vout();
+        assert!((vout - expected).abs() / expected < 0.01, "Vout = {}", vout);
+        assert!(result.efficiency() < 1.0);
+    }
+
+    /// Exact averaged control-to-output transfer function of a buck stage
+    fn buck_plant(circuit: &SwitchedCircuit) -> TransferFunction {
+        let (l, c, r) = (
+            circuit.inductance,
+            circuit.capacitance,
+            circuit.load_resistance,
+        );
+        let (rl, rc) = (circuit.dcr, circuit.esr);
+        // Vin·R(1 + s·rc·C) / [(sL + rl)(1 + s(R + rc)C) + R(1 + s·rc·C)]
+        let a2 = l * (r + rc) * c;
+        let a1 = l + rl * (r + rc) * c + r * rc * c;
+        let a0 = rl + r;
+        let disc = a1 * a1 - 4.0 * a2 * a0;
+        let poles = if disc < 0.0 {
+            let re = -a1 / (2.0 * a2);
+            let im = (-disc).sqrt() / (2.0 * a2);
+            vec![Complex::new(re, im), Complex::new(re, -im)]
+        } else {
+            let sq = disc.sqrt();
+            vec![
+                Complex::new((-a1 + sq) / (2.0 * a2), 0.0),
+                Complex::new((-a1 - sq) / (2.0 * a2), 0.0),
+            ]
+        };
+        TransferFunction::new(
+            circuit.vin * r * rc * c / a2,
+            vec![Complex::new(-1.0 / (rc * c), 0.0)],
+            poles,
+        )
+    }
+
+    #[test]
+    fn test_closed_loop_startup_and_load_step() {
+        let circuit =
+            SwitchedCircuit::buck(12.0, 10e-6, 100e-6, 5.0, 500e3).with_parasitics(0.02, 0.02);
+        let requirements = CompensatorRequirements {
+            crossover_freq: 20e3,
+            phase_margin_deg: 55.0,
+            vref: 1.0,
+            divider_ratio: 0.2,
+            ..Default::default()
+        };
+        let design = design_compensator(
+            &buck_plant(&circuit),
+            &requirements,
+            CompensatorType::TypeIII,
+        )
+        .unwrap();
+        let control = DutyControl::Voltage(
+            VoltageLoop::from_design(&design, &requirements).with_soft_start(500e-6),
+        );
+
+        let options = SwitchedOptions {
+            duration: 2.5e-3,
+            events: vec![SwitchedEvent::LoadStep {
+                time: 1.5e-3,
+                resistance: 2.5,
+            }],
+            ..Default::default()
+        };
+        let result = simulate_switched(&circuit, &control, &options).unwrap();
+
+        // Regulated before the step
+        let before: Vec<f64> = result
+            .cycles
+            .iter()
+            .filter(|c| c.start > 1.3e-3 && c.start < 1.5e-3)
+            .map(|c| c.vout_avg)
+            .collect();
+        assert!(before.iter().all(|v| (v - 5.0).abs() < 0.005));
+
+        // Doubling the load dips the output, then the integrator restores it
+        let metrics = result.step_metrics(1.5e-3, 5.0, 0.01);
+        assert!(
+            metrics.undershoot > 0.01,
+            "undershoot = {}",
+            metrics.undershoot
+        );
+        assert!(metrics.undershoot < 0.5);
+        assert!(metrics.settled);
+        assert!(
+            metrics.settling_time < 0.5e-3,
+            "t_settle = {}",
+            metrics.settling_time
+        );
+        assert!((result.steady_state_vout() - 5.0).abs() < 0.01);
+
+        // Soft start keeps the startup overshoot small
+        let startup = result.step_metrics(0.0, 5.0, 0.02);
+        assert!(
+            startup.overshoot < 0.25,
+            "overshoot = {}",
+            startup.overshoot
+        );
+    }
+
+    #[test]
+    fn test_from_design_and_validation() {
+        let design = crate::power::quick_buck(12.0, 3.3, 2.0, 500e3).unwrap();
+        let circuit = SwitchedCircuit::from_buck_design(&design);
+        assert!((circuit.load_resistance - 1.65).abs() < 1e-12);
+
+        let result = run(
+            &circuit,
+            &DutyControl::Open { duty: 3.3 / 12.0 },
+            1e-3,
+            [2.0, 3.3],
+        );
+        assert!((result.steady_state_vout() - 3.3).abs() < 0.05);
+
+        let options = SwitchedOptions::default();
+        let overdriven = DutyControl::Open { duty: 1.5 };
+        assert!(simulate_switched(&circuit, &overdriven, &options).is_err());
+        let broken = SwitchedCircuit {
+            inductance: 0.0,
+            ..circuit
+        };
+        let control = DutyControl::Open { duty: 0.5 };
+        assert!(simulate_switched(&broken, &control, &options).is_err());
+    }
+}
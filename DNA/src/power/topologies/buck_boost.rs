@@ -24,6 +24,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::power::components::diode::{find_suitable_diodes, DiodePreference};
 use crate::power::components::mosfet::{find_suitable_mosfets, MOSFETPreference};
+use crate::power::library::{builtin_parts, PartLibrary};
 use crate::power::types::VoltageRange;
 use crate::power::{format_capacitance, format_current, format_inductance, format_voltage};
 
@@ -208,8 +209,16 @@ pub struct BuckBoostLosses {
 // DESIGN ALGORITHM
 // ============================================================================
 
-/// Design a buck-boost converter from requirements
+/// Design a buck-boost converter from requirements with the built-in parts
 pub fn design_buck_boost(req: &BuckBoostRequirements) -> Result<BuckBoostDesign, String> {
+    design_buck_boost_with(req, builtin_parts())
+}
+
+/// Design a buck-boost converter choosing parts from `library`
+pub fn design_buck_boost_with(
+    req: &BuckBoostRequirements,
+    library: &PartLibrary,
+) -> Result<BuckBoostDesign, String> {
     // Validate inputs
     if req.output_power() <= 0.0 {
         return Err("Output power must be positive".to_string());
@@ -302,8 +311,13 @@ pub fn design_buck_boost(req: &BuckBoostRequirements) -> Result<BuckBoostDesign,
 
     // Select MOSFET
     let vds_margin = vds_max * 1.25;
-    let mosfet_candidates =
-        find_suitable_mosfets(vds_margin, id_rms, id_peak, MOSFETPreference::LowLosses);
+    let mosfet_candidates = find_suitable_mosfets(
+        library,
+        vds_margin,
+        id_rms,
+        id_peak,
+        MOSFETPreference::LowLosses,
+    );
 
     let selected_mosfet = mosfet_candidates
         .first()
@@ -330,6 +344,7 @@ pub fn design_buck_boost(req: &BuckBoostRequirements) -> Result<BuckBoostDesign,
         id_peak,
         loss_conduction: mosfet_conduction,
         loss_switching: mosfet_switching,
+        loss_gate: mosfet_gate,
         loss_total: mosfet_conduction + mosfet_switching + mosfet_gate,
     };
 
@@ -339,8 +354,13 @@ pub fn design_buck_boost(req: &BuckBoostRequirements) -> Result<BuckBoostDesign,
     let if_avg = iout;
     let _if_rms = il_rms * (1.0 - duty_nom).sqrt(); // For RMS rating validation
 
-    let diode_candidates =
-        find_suitable_diodes(vr_diode * 1.3, if_avg, il_peak, DiodePreference::LowVf);
+    let diode_candidates = find_suitable_diodes(
+        library,
+        vr_diode * 1.3,
+        if_avg,
+        il_peak,
+        DiodePreference::LowVf,
+    );
 
     let selected_diode = diode_candidates
         .first()
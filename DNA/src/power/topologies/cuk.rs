@@ -0,0 +1,458 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: cuk.rs | DNA/src/power/topologies/cuk.rs
+//! PURPOSE: Ćuk converter topology design
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Complete Ćuk converter design including:
+//! - Input and output inductor design (separate or coupled)
+//! - Energy transfer capacitor sizing
+//! - Switch and diode selection
+//! - Capacitor sizing
+//! - Efficiency estimation
+//!
+//! The Ćuk converter steps up or down with an inverted output. Unlike the
+//! buck-boost, both input and output currents are continuous, so it needs
+//! far less filtering on either side. Energy moves through the series
+//! capacitor C1, which charges to Vin + |Vout|.
+//!
+//! Key equations:
+//! - Output voltage: Vout = -Vin × D / (1-D)
+//! - Input inductor current: IL1 = Iout × D / (1-D)
+//! - Output inductor current: IL2 = Iout
+//! - Switch and diode see Vin + |Vout|
+
+use serde::{Deserialize, Serialize};
+
+use crate::power::control::BuckBoostSmallSignal;
+use crate::power::library::{builtin_parts, PartLibrary};
+use crate::power::types::VoltageRange;
+use crate::power::{format_capacitance, format_current, format_inductance, format_voltage};
+
+use super::buck_boost::BuckBoostInductor;
+use super::flyback::{CapacitorType, OutputCapacitor, SelectedDiode, SelectedMOSFET};
+use super::sepic::{coupling_resonance, equivalent_inductance, inductor_pair, SEPICLosses};
+
+// ============================================================================
+// DESIGN REQUIREMENTS
+// ============================================================================
+
+/// Ćuk converter design requirements
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CukRequirements {
+    /// Input voltage range
+    pub vin: VoltageRange,
+    /// Output voltage (magnitude, output is inverted)
+    pub vout: f64,
+    /// Maximum output current (A)
+    pub iout_max: f64,
+    /// Minimum output current (A)
+    pub iout_min: f64,
+    /// Maximum output ripple voltage (V peak-to-peak)
+    pub ripple_pp: f64,
+    /// Switching frequency (Hz)
+    pub switching_freq: f64,
+    /// Wind L1 and L2 on one core (1:1 coupled inductor)
+    pub coupled_inductor: bool,
+    /// Transfer capacitor ripple (fraction of Vin_min + |Vout|)
+    pub coupling_cap_ripple: f64,
+    /// Ambient temperature (°C)
+    pub ambient_temp: f64,
+    /// Maximum temperature rise (°C)
+    pub max_temp_rise: f64,
+    /// Target efficiency (0.0-1.0)
+    pub efficiency_target: f64,
+    /// Inductor current ripple ratio (ΔI / Iin)
+    pub inductor_ripple_ratio: f64,
+}
+
+impl Default for CukRequirements {
+    fn default() -> Self {
+        Self {
+            vin: VoltageRange::range(9.0, 16.0), // Automotive 12V nominal
+            vout: 12.0,                          // Inverted -12V output
+            iout_max: 1.0,
+            iout_min: 0.1,
+            ripple_pp: 0.012, // Continuous output current: low ripple
+            switching_freq: 300e3,
+            coupled_inductor: false,
+            coupling_cap_ripple: 0.05,
+            ambient_temp: 25.0,
+            max_temp_rise: 50.0,
+            efficiency_target: 0.88,
+            inductor_ripple_ratio: 0.4,
+        }
+    }
+}
+
+impl CukRequirements {
+    /// Calculate output power
+    pub fn output_power(&self) -> f64 {
+        self.vout * self.iout_max
+    }
+
+    /// Calculate estimated input power
+    pub fn estimated_input_power(&self) -> f64 {
+        self.output_power() / self.efficiency_target
+    }
+
+    /// Calculate duty cycle for given input voltage
+    /// D = |Vout| / (Vin + |Vout|)
+    pub fn duty_cycle_for_vin(&self, vin: f64) -> f64 {
+        self.vout / (vin + self.vout)
+    }
+}
+
+// ============================================================================
+// COMPLETE DESIGN
+// ============================================================================
+
+/// Complete Ćuk converter design
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CukDesign {
+    /// Design requirements
+    pub requirements: CukRequirements,
+    /// Duty cycle at Vin_min (maximum D)
+    pub duty_cycle_max: f64,
+    /// Duty cycle at Vin_nom
+    pub duty_cycle_nom: f64,
+    /// Duty cycle at Vin_max (minimum D)
+    pub duty_cycle_min: f64,
+    /// Main switch (MOSFET)
+    pub main_switch: SelectedMOSFET,
+    /// Freewheeling diode
+    pub diode: SelectedDiode,
+    /// Input inductor L1 (one winding when coupled)
+    pub input_inductor: BuckBoostInductor,
+    /// Output inductor L2 (other winding when coupled)
+    pub output_inductor: BuckBoostInductor,
+    /// Energy transfer capacitor C1
+    pub coupling_capacitor: OutputCapacitor,
+    /// Output capacitor
+    pub output_capacitor: OutputCapacitor,
+    /// Input capacitor
+    pub input_capacitor: OutputCapacitor,
+    /// Total efficiency estimate
+    pub efficiency: f64,
+    /// Loss breakdown
+    pub losses: SEPICLosses,
+}
+
+// ============================================================================
+// DESIGN ALGORITHM
+// ============================================================================
+
+/// Design a Ćuk converter from requirements with the built-in parts
+pub fn design_cuk(req: &CukRequirements) -> Result<CukDesign, String> {
+    design_cuk_with(req, builtin_parts())
+}
+
+/// Design a Ćuk converter choosing parts from `library`
+pub fn design_cuk_with(req: &CukRequirements, library: &PartLibrary) -> Result<CukDesign, String> {
+    // Validate inputs
+    if req.output_power() <= 0.0 {
+        return Err("Output power must be positive".to_string());
+    }
+    if req.switching_freq < 10e3 || req.switching_freq > 5e6 {
+        return Err("Switching frequency must be between 10kHz and 5MHz".to_string());
+    }
+    if req.coupling_cap_ripple <= 0.0 {
+        return Err("Transfer capacitor ripple must be positive".to_string());
+    }
+
+    let vin_min = req.vin.min_v;
+    let vin_max = req.vin.max_v;
+    let vin_nom = req.vin.nom_v;
+    let vout = req.vout; // Magnitude of output voltage
+    let iout = req.iout_max;
+    let fsw = req.switching_freq;
+    let pout = req.output_power();
+
+    // Forward voltage drops
+    let vd = 0.5; // Diode forward drop
+
+    // Calculate duty cycles
+    // D = (Vout + Vd) / (Vin + Vout + Vd)
+    let duty_max = (vout + vd) / (vin_min + vout + vd);
+    let duty_nom = (vout + vd) / (vin_nom + vout + vd);
+    let duty_min = (vout + vd) / (vin_max + vout + vd);
+
+    if duty_max > 0.85 {
+        return Err(format!(
+            "Duty cycle too high ({:.1}%). Consider higher input voltage or lower output.",
+            duty_max * 100.0
+        ));
+    }
+
+    // Average inductor currents at Vin_min
+    let il1_avg = iout * duty_max / (1.0 - duty_max);
+    let il2_avg = iout;
+
+    let (input_inductor, output_inductor) = inductor_pair(
+        vin_min,
+        duty_max,
+        il1_avg,
+        il2_avg,
+        req.inductor_ripple_ratio,
+        fsw,
+        req.coupled_inductor,
+    );
+
+    // Switch carries IL1 + IL2 during the on-time and blocks Vc1 = Vin + |Vout|
+    let i_sw_peak = input_inductor.current_peak + output_inductor.current_peak;
+    let vds_max = vin_max + vout + vd;
+    let id_rms = (il1_avg + il2_avg) * duty_max.sqrt();
+    let main_switch = SelectedMOSFET::select(library, vds_max, id_rms, i_sw_peak, fsw)?;
+
+    let diode = SelectedDiode::select(library, vds_max, iout, i_sw_peak)?;
+
+    // Transfer capacitor: ΔVc1 = Iout × D / (C1 × fsw)
+    let dv_c1 = req.coupling_cap_ripple * (vin_min + vout);
+    let c1_ripple_rms = iout * (duty_max / (1.0 - duty_max)).sqrt();
+    let coupling_capacitor = OutputCapacitor {
+        capacitance: iout * duty_max / (dv_c1 * fsw),
+        voltage_rating: (vds_max * 1.25).ceil(),
+        max_esr: dv_c1 * 0.5 / i_sw_peak,
+        ripple_current_rms: c1_ripple_rms,
+        cap_type: CapacitorType::Ceramic,
+        parallel_count: 1,
+    };
+
+    // Output capacitor sizing - continuous current through L2, as in a buck
+    // Half the ripple budget to ESR, half to capacitance
+    let delta_i2 = output_inductor.ripple_current_pp;
+    let c_out_min = delta_i2 / (8.0 * fsw * req.ripple_pp * 0.5);
+    let c_out_ripple_rms = delta_i2 / (2.0 * 3.0_f64.sqrt());
+    let max_esr = req.ripple_pp * 0.5 / delta_i2;
+
+    let output_capacitor = OutputCapacitor {
+        capacitance: c_out_min * 1.5, // Add margin
+        voltage_rating: (vout * 1.5).ceil(),
+        max_esr,
+        ripple_current_rms: c_out_ripple_rms,
+        cap_type: CapacitorType::Ceramic,
+        parallel_count: 1,
+    };
+
+    // Input capacitor sizing - continuous input current through L1
+    let iin_rms = input_inductor.ripple_current_pp / (2.0 * 3.0_f64.sqrt());
+    let c_in_min = input_inductor.ripple_current_pp / (8.0 * fsw * 0.01 * vin_min);
+    let cin_esr = 0.01 * vin_min / input_inductor.ripple_current_pp;
+
+    let input_capacitor = OutputCapacitor {
+        capacitance: c_in_min * 2.0,
+        voltage_rating: (vin_max * 1.25).ceil(),
+        max_esr: cin_esr,
+        ripple_current_rms: iin_rms,
+        cap_type: CapacitorType::Ceramic,
+        parallel_count: 1,
+    };
+
+    let mut losses = SEPICLosses {
+        mosfet_conduction: main_switch.loss_conduction,
+        mosfet_switching: main_switch.loss_switching,
+        mosfet_gate: main_switch.loss_gate,
+        diode_conduction: diode.loss_conduction,
+        inductor_core: input_inductor.core_loss + output_inductor.core_loss,
+        inductor_copper: input_inductor.copper_loss + output_inductor.copper_loss,
+        coupling_cap_esr: c1_ripple_rms.powi(2) * coupling_capacitor.max_esr * 0.1,
+        input_cap_esr: iin_rms.powi(2) * cin_esr * 0.1,
+        output_cap_esr: c_out_ripple_rms.powi(2) * max_esr * 0.1,
+        total: 0.0,
+    };
+    losses.total = losses.mosfet_conduction
+        + losses.mosfet_switching
+        + losses.mosfet_gate
+        + losses.diode_conduction
+        + losses.inductor_core
+        + losses.inductor_copper
+        + losses.coupling_cap_esr
+        + losses.input_cap_esr
+        + losses.output_cap_esr;
+
+    let efficiency = pout / (pout + losses.total);
+
+    Ok(CukDesign {
+        requirements: req.clone(),
+        duty_cycle_max: duty_max,
+        duty_cycle_nom: duty_nom,
+        duty_cycle_min: duty_min,
+        main_switch,
+        diode,
+        input_inductor,
+        output_inductor,
+        coupling_capacitor,
+        output_capacitor,
+        input_capacitor,
+        efficiency,
+        losses,
+    })
+}
+
+// ============================================================================
+// SMALL-SIGNAL MODEL
+// ============================================================================
+
+impl CukDesign {
+    /// Equivalent buck-boost inductance of L1/L2
+    pub fn equivalent_inductance(&self) -> f64 {
+        equivalent_inductance(
+            self.input_inductor.inductance,
+            self.output_inductor.inductance,
+            self.requirements.coupled_inductor,
+        )
+    }
+
+    /// Small-signal model at nominal input
+    ///
+    /// Below the C1-L resonance the Ćuk converter reduces to an inverting
+    /// buck-boost with the equivalent inductance, RHP zero included.
+    pub fn small_signal(&self, esr: f64) -> BuckBoostSmallSignal {
+        let req = &self.requirements;
+        BuckBoostSmallSignal::new(
+            req.vin.nom_v,
+            req.vout,
+            req.iout_max,
+            self.equivalent_inductance(),
+            self.output_capacitor.capacitance,
+            esr,
+            req.switching_freq,
+        )
+    }
+
+    /// Transfer capacitor resonance at nominal duty (Hz)
+    pub fn coupling_resonance_freq(&self) -> f64 {
+        coupling_resonance(
+            self.input_inductor.inductance,
+            self.output_inductor.inductance,
+            self.coupling_capacitor.capacitance,
+            self.duty_cycle_nom,
+        )
+    }
+
+    /// Highest sensible crossover: below the RHP zero, the C1-L resonance
+    /// and a tenth of the switching frequency
+    pub fn max_crossover(&self, esr: f64) -> f64 {
+        self.small_signal(esr)
+            .max_crossover()
+            .min(self.coupling_resonance_freq() / 3.0)
+            .min(self.requirements.switching_freq / 10.0)
+    }
+}
+
+// ============================================================================
+// DISPLAY IMPLEMENTATION
+// ============================================================================
+
+impl CukDesign {
+    /// Generate a summary string
+    pub fn summary(&self) -> String {
+        let req = &self.requirements;
+        format!(
+            "Ćuk Converter Design\n\
+             ====================\n\
+             Input: {} to {}\n\
+             Output: -{} @ {}\n\
+             Power: {:.1} W\n\
+             Frequency: {:.0} kHz\n\
+             Inductors: {}\n\
+             \n\
+             Duty Cycle: {:.1}%-{:.1}%\n\
+             \n\
+             MOSFET: {} (Vds_pk={:.0}V)\n\
+             Diode: {} (Vr={:.0}V)\n\
+             \n\
+             L1: {} (IL_avg={:.2}A, ΔI={:.2}A)\n\
+             L2: {} (IL_avg={:.2}A)\n\
+             Transfer Cap: {} ({:.0}V)\n\
+             Output Cap: {} (ESR<{:.0}mΩ)\n\
+             Input Cap: {}\n\
+             \n\
+             Efficiency: {:.1}%\n\
+             Total Losses: {:.2} W",
+            format_voltage(req.vin.min_v),
+            format_voltage(req.vin.max_v),
+            format_voltage(req.vout),
+            format_current(req.iout_max),
+            req.output_power(),
+            req.switching_freq / 1000.0,
+            if req.coupled_inductor {
+                "1:1 coupled"
+            } else {
+                "separate"
+            },
+            self.duty_cycle_min * 100.0,
+            self.duty_cycle_max * 100.0,
+            self.main_switch.spec.part_number,
+            self.main_switch.vds_peak,
+            self.diode.spec.part_number,
+            self.diode.vr_peak,
+            format_inductance(self.input_inductor.inductance),
+            self.input_inductor.current_avg,
+            self.input_inductor.ripple_current_pp,
+            format_inductance(self.output_inductor.inductance),
+            self.output_inductor.current_avg,
+            format_capacitance(self.coupling_capacitor.capacitance),
+            self.coupling_capacitor.voltage_rating,
+            format_capacitance(self.output_capacitor.capacitance),
+            self.output_capacitor.max_esr * 1000.0,
+            format_capacitance(self.input_capacitor.capacitance),
+            self.efficiency * 100.0,
+            self.losses.total,
+        )
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::topologies::sepic::{design_sepic, SEPICRequirements};
+
+    #[test]
+    fn test_cuk_basic_design() {
+        let req = CukRequirements::default();
+        let result = design_cuk(&req);
+        assert!(result.is_ok(), "Design should succeed: {:?}", result.err());
+
+        let design = result.unwrap();
+
+        // Transfer capacitor holds Vin + |Vout|
+        assert!(design.coupling_capacitor.voltage_rating >= req.vin.max_v + req.vout);
+
+        assert!(
+            design.efficiency > 0.75 && design.efficiency < 0.98,
+            "Efficiency {:.1}% out of range",
+            design.efficiency * 100.0
+        );
+    }
+
+    #[test]
+    fn test_cuk_continuous_output_needs_less_capacitance() {
+        // Same conversion as a SEPIC: pulsed vs continuous output current
+        let cuk = design_cuk(&CukRequirements {
+            ripple_pp: 0.12,
+            ..Default::default()
+        })
+        .unwrap();
+        let sepic = design_sepic(&SEPICRequirements::default()).unwrap();
+
+        assert!(cuk.output_capacitor.capacitance < sepic.output_capacitor.capacitance);
+        assert!(
+            cuk.output_capacitor.ripple_current_rms < sepic.output_capacitor.ripple_current_rms
+        );
+    }
+
+    #[test]
+    fn test_cuk_small_signal() {
+        let design = design_cuk(&CukRequirements::default()).unwrap();
+        let model = design.small_signal(0.005);
+
+        assert!(model.control_to_output().zeros.iter().any(|z| z.re > 0.0));
+        assert!(design.max_crossover(0.005) <= design.coupling_resonance_freq() / 3.0);
+    }
+}
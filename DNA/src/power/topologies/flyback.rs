@@ -20,13 +20,16 @@ use crate::power::components::diode::{
     find_suitable_diodes, DiodePreference, DiodeSpec, DiodeType,
 };
 use crate::power::components::mosfet::{find_suitable_mosfets, MOSFETPreference, MOSFETSpec};
+use crate::power::library::{builtin_parts, PartLibrary};
 use crate::power::magnetics::{
-    auto_design_transformer, ferrite_database, CoreMaterial, CoreType, IsolationClass,
-    TransformerDesign, TransformerRequirements, TransformerTopology,
+    auto_design_transformer, CoreType, IsolationClass, TransformerDesign, TransformerRequirements,
+    TransformerTopology,
 };
 use crate::power::types::VoltageRange;
 use crate::power::{format_capacitance, format_current, format_inductance, format_voltage};
 
+use super::isolated::select_core_material;
+
 // ============================================================================
 // OPERATING MODES
 // ============================================================================
@@ -187,6 +190,9 @@ pub struct SelectedMOSFET {
     pub loss_conduction: f64,
     /// Switching loss (W)
     pub loss_switching: f64,
+    /// Gate drive loss (W)
+    #[serde(default)]
+    pub loss_gate: f64,
     /// Total loss (W)
     pub loss_total: f64,
 }
@@ -209,6 +215,88 @@ pub struct SelectedDiode {
     pub loss_total: f64,
 }
 
+impl SelectedMOSFET {
+    /// Select the lowest-loss MOSFET for a hard-switched position
+    ///
+    /// Uses the same estimates as the designers: 25% Vds margin, Rds_on
+    /// derated 1.5× for temperature, 30ns edges and 10V gate drive.
+    pub fn select(
+        library: &PartLibrary,
+        vds_peak: f64,
+        id_rms: f64,
+        id_peak: f64,
+        fsw: f64,
+    ) -> Result<Self, String> {
+        let vds_margin = vds_peak * 1.25;
+        let preference = MOSFETPreference::LowLosses;
+        let spec = find_suitable_mosfets(library, vds_margin, id_rms, id_peak, preference)
+            .first()
+            .map(|m| (*m).clone())
+            .ok_or_else(|| {
+                format!(
+                    "No suitable MOSFET found for Vds={:.0}V, Id={:.2}A",
+                    vds_margin, id_peak
+                )
+            })?;
+
+        let loss_conduction = id_rms.powi(2) * spec.rds_on_25c * 1.5;
+        let loss_switching = 0.5 * vds_peak * id_peak * 30e-9 * fsw;
+        let loss_gate = spec.qg_total * 10.0 * fsw;
+
+        Ok(Self {
+            spec,
+            vds_peak,
+            id_rms,
+            id_peak,
+            loss_conduction,
+            loss_switching,
+            loss_gate,
+            loss_total: loss_conduction + loss_switching + loss_gate,
+        })
+    }
+
+    /// Zero-voltage switching: no turn-on loss, turn-off snubbed by Coss
+    pub fn with_zvs(mut self) -> Self {
+        self.loss_switching *= 0.1;
+        self.loss_total = self.loss_conduction + self.loss_switching + self.loss_gate;
+        self
+    }
+}
+
+impl SelectedDiode {
+    /// Select the lowest-Vf diode with 30% reverse voltage margin
+    pub fn select(
+        library: &PartLibrary,
+        vr_peak: f64,
+        if_avg: f64,
+        if_peak: f64,
+    ) -> Result<Self, String> {
+        let preference = DiodePreference::LowVf;
+        let spec = find_suitable_diodes(library, vr_peak * 1.3, if_avg, if_peak, preference)
+            .first()
+            .map(|d| (*d).clone())
+            .ok_or_else(|| {
+                format!(
+                    "No suitable diode found for Vr={:.0}V, If={:.2}A",
+                    vr_peak * 1.3,
+                    if_avg
+                )
+            })?;
+
+        let loss_conduction = if_avg * spec.vf_typical;
+
+        Ok(Self {
+            spec,
+            vr_peak,
+            if_avg,
+            if_peak,
+            loss_conduction,
+            loss_recovery: 0.0, // Schottky assumed
+            loss_total: loss_conduction,
+        })
+    }
+}
+
 /// Output capacitor specification
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OutputCapacitor {
@@ -347,8 +435,16 @@ pub enum FlybackDesignResult {
     DesignConstraintViolation(String),
 }
 
-/// Design a flyback converter from requirements
+/// Design a flyback converter from requirements with the built-in parts
 pub fn design_flyback(req: &FlybackRequirements) -> Result<FlybackDesign, String> {
+    design_flyback_with(req, builtin_parts())
+}
+
+/// Design a flyback converter choosing parts from `library`
+pub fn design_flyback_with(
+    req: &FlybackRequirements,
+    library: &PartLibrary,
+) -> Result<FlybackDesign, String> {
     // Validate inputs
     if req.outputs.is_empty() {
         return Err("At least one output required".to_string());
@@ -379,7 +475,7 @@ pub fn design_flyback(req: &FlybackRequirements) -> Result<FlybackDesign, String
         calculate_primary_currents(p_in, req.vin.min_v, d_max, mode, req.switching_freq);
 
     // Design transformer
-    let material = select_core_material(req.switching_freq);
+    let material = select_core_material(library, req.switching_freq);
     let transformer_req = TransformerRequirements {
         primary_voltage: req.vin.min_v,
         secondary_voltages: req.outputs.iter().map(|o| o.voltage).collect(),
@@ -392,8 +488,9 @@ pub fn design_flyback(req: &FlybackRequirements) -> Result<FlybackDesign, String
         topology: TransformerTopology::Flyback,
     };
 
-    let transformer = auto_design_transformer(&transformer_req, &material, req.preferred_core)
-        .ok_or_else(|| "No suitable core found for transformer".to_string())?;
+    let transformer =
+        auto_design_transformer(library, &transformer_req, &material, req.preferred_core)
+            .ok_or_else(|| "No suitable core found for transformer".to_string())?;
 
     // Calculate magnetizing inductance
     let l_mag = transformer.magnetizing_inductance;
@@ -405,7 +502,14 @@ pub fn design_flyback(req: &FlybackRequirements) -> Result<FlybackDesign, String
     let vds_peak = req.vin.max_v + v_clamp + 50.0; // Add margin for leakage spike
 
     // Select primary MOSFET
-    let mosfet = select_primary_mosfet(vds_peak, i_pri_peak, i_pri_rms, req.switching_freq, d_max)?;
+    let mosfet = select_primary_mosfet(
+        library,
+        vds_peak,
+        i_pri_peak,
+        i_pri_rms,
+        req.switching_freq,
+        d_max,
+    )?;
 
     // Select output diodes
     let output_diodes: Result<Vec<_>, String> = req
@@ -413,7 +517,13 @@ pub fn design_flyback(req: &FlybackRequirements) -> Result<FlybackDesign, String
         .iter()
         .map(|out| {
             let vr = req.vin.max_v / transformer.turns_ratio + out.voltage;
-            select_output_diode(vr, out.current_max, req.switching_freq, 1.0 - d_max)
+            select_output_diode(
+                library,
+                vr,
+                out.current_max,
+                req.switching_freq,
+                1.0 - d_max,
+            )
         })
         .collect();
     let output_diodes = output_diodes?;
@@ -543,22 +653,9 @@ fn calculate_primary_currents(
     }
 }
 
-/// Select core material based on frequency
-fn select_core_material(frequency: f64) -> CoreMaterial {
-    ferrite_database()
-        .into_iter()
-        .find(|m| m.is_frequency_suitable(frequency))
-        .unwrap_or_else(|| {
-            // Default to N87 which works well for 25kHz-500kHz
-            ferrite_database()
-                .into_iter()
-                .find(|m| m.name == "N87")
-                .unwrap()
-        })
-}
-
 /// Select primary MOSFET
 fn select_primary_mosfet(
+    library: &PartLibrary,
     vds_peak: f64,
     i_peak: f64,
     i_rms: f64,
@@ -569,6 +666,7 @@ fn select_primary_mosfet(
     let vds_required = vds_peak * 1.2;
 
     let candidates = find_suitable_mosfets(
+        library,
         vds_required,
         i_rms,
         i_peak * 1.5,
@@ -605,12 +703,14 @@ fn select_primary_mosfet(
         id_peak: i_peak,
         loss_conduction,
         loss_switching,
+        loss_gate,
         loss_total,
     })
 }
 
 /// Select output diode
 fn select_output_diode(
+    library: &PartLibrary,
     vr_peak: f64,
     i_avg: f64,
     fsw: f64,
@@ -628,7 +728,7 @@ fn select_output_diode(
 
     // if_rms ≈ if_avg × sqrt(D_off) for discontinuous conduction
     let i_rms = i_avg * off_duty.sqrt();
-    let candidates = find_suitable_diodes(vr_required, i_avg * 2.0, i_rms, pref);
+    let candidates = find_suitable_diodes(library, vr_required, i_avg * 2.0, i_rms, pref);
 
     if candidates.is_empty() {
         return Err(format!(
@@ -813,7 +913,7 @@ fn calculate_losses(
     FlybackLosses {
         mosfet_conduction: mosfet.loss_conduction,
         mosfet_switching: mosfet.loss_switching,
-        mosfet_gate: mosfet.loss_total - mosfet.loss_conduction - mosfet.loss_switching,
+        mosfet_gate: mosfet.loss_gate,
         diode_conduction,
         diode_recovery,
         transformer_core: transformer.core_loss,
@@ -1027,6 +1127,18 @@ mod tests {
         assert!(d_min > 0.1, "Min duty should be reasonable");
     }
 
+    #[test]
+    fn test_zvs_keeps_gate_loss() {
+        let hard = SelectedMOSFET::select(builtin_parts(), 400.0, 2.0, 4.0, 100e3).unwrap();
+        assert!(hard.loss_gate > 0.0);
+
+        let soft = hard.clone().with_zvs();
+        assert_eq!(soft.loss_gate, hard.loss_gate);
+        assert!((soft.loss_switching - 0.1 * hard.loss_switching).abs() < 1e-12);
+        let sum = soft.loss_conduction + soft.loss_switching + soft.loss_gate;
+        assert!((soft.loss_total - sum).abs() < 1e-12);
+    }
+
     #[test]
     fn test_clamp_design() {
         let clamp = design_clamp(5e-6, 2.0, 30.0, 72.0, 100e3);
@@ -24,14 +24,16 @@ use serde::{Deserialize, Serialize};
 
 use crate::power::components::diode::{find_suitable_diodes, DiodePreference, DiodeSpec};
 use crate::power::components::mosfet::{find_suitable_mosfets, MOSFETPreference, MOSFETSpec};
+use crate::power::library::{builtin_parts, PartLibrary};
 use crate::power::magnetics::{
-    auto_design_transformer, ferrite_database, CoreMaterial, CoreType, IsolationClass,
-    TransformerDesign, TransformerRequirements, TransformerTopology,
+    auto_design_transformer, CoreType, IsolationClass, TransformerDesign, TransformerRequirements,
+    TransformerTopology,
 };
 use crate::power::types::VoltageRange;
 use crate::power::{format_capacitance, format_current, format_inductance, format_voltage};
 
 use super::flyback::{CapacitorType, OutputCapacitor, SelectedDiode, SelectedMOSFET};
+use super::isolated::select_core_material;
 
 // ============================================================================
 // RESET METHODS
@@ -281,8 +283,16 @@ pub struct ForwardLosses {
 // DESIGN ALGORITHM
 // ============================================================================
 
-/// Design a forward converter from requirements
+/// Design a forward converter from requirements with the built-in parts
 pub fn design_forward(req: &ForwardRequirements) -> Result<ForwardDesign, String> {
+    design_forward_with(req, builtin_parts())
+}
+
+/// Design a forward converter choosing parts from `library`
+pub fn design_forward_with(
+    req: &ForwardRequirements,
+    library: &PartLibrary,
+) -> Result<ForwardDesign, String> {
     // Validate inputs
     if req.outputs_power() <= 0.0 {
         return Err("Output power must be positive".to_string());
@@ -383,6 +393,7 @@ pub fn design_forward(req: &ForwardRequirements) -> Result<ForwardDesign, String
     // Select MOSFET
     let vds_margin = vds_max * 1.25; // 25% margin
     let mosfet_candidates = find_suitable_mosfets(
+        library,
         vds_margin,
         i_pri_rms,
         i_pri_peak,
@@ -415,6 +426,7 @@ pub fn design_forward(req: &ForwardRequirements) -> Result<ForwardDesign, String
         id_peak: i_pri_peak,
         loss_conduction: mosfet_conduction,
         loss_switching: mosfet_switching,
+        loss_gate: mosfet_gate,
         loss_total: mosfet_conduction + mosfet_switching + mosfet_gate,
     };
 
@@ -430,6 +442,7 @@ pub fn design_forward(req: &ForwardRequirements) -> Result<ForwardDesign, String
     // Select forward diode
     let diode_margin = 1.3;
     let forward_diode_candidates = find_suitable_diodes(
+        library,
         vr_forward * diode_margin,
         if_forward_avg,
         i_l_peak,
@@ -455,6 +468,7 @@ pub fn design_forward(req: &ForwardRequirements) -> Result<ForwardDesign, String
 
     // Select freewheeling diode
     let freewheel_diode_candidates = find_suitable_diodes(
+        library,
         vr_freewheel * diode_margin,
         if_freewheel_avg,
         i_l_peak,
@@ -493,12 +507,13 @@ pub fn design_forward(req: &ForwardRequirements) -> Result<ForwardDesign, String
     };
 
     // Select core material based on frequency
-    let material = select_core_material(fsw);
-    let transformer = auto_design_transformer(&transformer_req, &material, req.preferred_core)
-        .ok_or_else(|| "No suitable core found for transformer".to_string())?;
+    let material = select_core_material(library, fsw);
+    let transformer =
+        auto_design_transformer(library, &transformer_req, &material, req.preferred_core)
+            .ok_or_else(|| "No suitable core found for transformer".to_string())?;
 
     // Design reset circuit
-    let reset_circuit = design_reset_circuit(req, &transformer, vin_max, i_pri_peak, fsw)?;
+    let reset_circuit = design_reset_circuit(library, req, &transformer, vin_max, i_pri_peak, fsw)?;
 
     // Output capacitor sizing
     // Ripple from ESR: ΔV_esr = ΔI * ESR
@@ -601,22 +616,9 @@ impl ForwardRequirements {
     }
 }
 
-/// Select core material based on frequency
-fn select_core_material(frequency: f64) -> CoreMaterial {
-    ferrite_database()
-        .into_iter()
-        .find(|m| m.is_frequency_suitable(frequency))
-        .unwrap_or_else(|| {
-            // Default to N87 which works well for 25kHz-500kHz
-            ferrite_database()
-                .into_iter()
-                .find(|m| m.name == "N87")
-                .unwrap()
-        })
-}
-
 /// Design the reset circuit based on selected method
 fn design_reset_circuit(
+    library: &PartLibrary,
     req: &ForwardRequirements,
     transformer: &TransformerDesign,
     vin_max: f64,
@@ -639,6 +641,7 @@ fn design_reset_circuit(
 
             // Select reset diode (fast recovery type)
             let reset_diode = find_suitable_diodes(
+                library,
                 vin_max * 1.5,
                 i_reset_peak * 0.5,
                 i_reset_peak,
@@ -681,6 +684,7 @@ fn design_reset_circuit(
             let p_resistor = v_clamp.powi(2) / r_clamp * (1.0 - req.max_duty_cycle());
 
             let clamp_diode = find_suitable_diodes(
+                library,
                 v_clamp * 1.5,
                 p_reset / v_clamp,
                 i_pri_peak,
@@ -716,6 +720,7 @@ fn design_reset_circuit(
 
             // Clamp MOSFET
             let clamp_mosfet = find_suitable_mosfets(
+                library,
                 v_clamp + vin_max,
                 i_pri_peak * 0.3,
                 i_pri_peak,
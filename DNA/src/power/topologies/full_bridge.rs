@@ -0,0 +1,502 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: full_bridge.rs | DNA/src/power/topologies/full_bridge.rs
+//! PURPOSE: Phase-shifted full-bridge (PSFB) converter topology design
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Complete phase-shifted full-bridge design including:
+//! - Transformer design with the full bus across the primary
+//! - Bridge switch (MOSFET) selection with zero-voltage switching
+//! - Commutating (ZVS) inductor sizing against MOSFET Coss
+//! - Duty cycle loss from the commutating inductance
+//! - Secondary rectifier selection (center-tapped or bridge)
+//! - Output LC filter sizing
+//! - Efficiency estimation
+//!
+//! Both bridge legs run at 50% duty; output is regulated by the phase shift
+//! between them. The energy in the leakage plus series inductance discharges
+//! the switch output capacitance before each turn-on. Key properties:
+//! - Output voltage: Vout = Vin × (Ns/Np) × D_eff
+//! - Each switch blocks Vin; ZVS removes turn-on losses above a load threshold
+//! - The commutating inductance costs duty: ΔD = 4 × L × n × Iout × fsw / Vin
+//!
+//! The workhorse above roughly 500W, typically from a 400V PFC bus.
+
+use serde::{Deserialize, Serialize};
+
+use crate::power::control::ForwardSmallSignal;
+use crate::power::library::{builtin_parts, PartLibrary};
+use crate::power::magnetics::{CoreType, IsolationClass, TransformerDesign, TransformerTopology};
+use crate::power::types::VoltageRange;
+use crate::power::{format_capacitance, format_current, format_inductance, format_voltage};
+
+use super::flyback::{OutputCapacitor, SelectedDiode, SelectedMOSFET};
+use super::forward::OutputInductor;
+use super::isolated::{
+    build_transformer, input_capacitor, output_filter, secondary_ratio, select_rectifier,
+    transformer_requirements, IsolatedLosses, SecondaryRectifier,
+};
+
+// ============================================================================
+// DESIGN REQUIREMENTS
+// ============================================================================
+
+/// Phase-shifted full-bridge design requirements
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FullBridgeRequirements {
+    /// Input voltage range
+    pub vin: VoltageRange,
+    /// Output voltage (V)
+    pub vout: f64,
+    /// Maximum output current (A)
+    pub iout_max: f64,
+    /// Minimum output current (A)
+    pub iout_min: f64,
+    /// Maximum output ripple voltage (V peak-to-peak)
+    pub ripple_pp: f64,
+    /// Switching frequency of each leg (Hz)
+    pub switching_freq: f64,
+    /// Maximum effective duty cycle (phase shift), including duty loss
+    pub duty_cycle_max: f64,
+    /// Secondary rectifier configuration
+    pub rectifier: SecondaryRectifier,
+    /// Load fraction (of Iout_max) down to which ZVS is maintained
+    pub zvs_load_fraction: f64,
+    /// Isolation requirement
+    pub isolation: IsolationClass,
+    /// Ambient temperature (°C)
+    pub ambient_temp: f64,
+    /// Maximum temperature rise (°C)
+    pub max_temp_rise: f64,
+    /// Target efficiency (0.0-1.0)
+    pub efficiency_target: f64,
+    /// Inductor current ripple ratio (ΔI / Iout)
+    pub inductor_ripple_ratio: f64,
+    /// Preferred core type (or None for automatic)
+    pub preferred_core: Option<CoreType>,
+}
+
+impl Default for FullBridgeRequirements {
+    fn default() -> Self {
+        Self {
+            vin: VoltageRange::range(360.0, 400.0), // PFC bus
+            vout: 48.0,
+            iout_max: 10.0,
+            iout_min: 1.0,
+            ripple_pp: 0.48,
+            switching_freq: 200e3,
+            duty_cycle_max: 0.9,
+            rectifier: SecondaryRectifier::FullBridge,
+            zvs_load_fraction: 0.5,
+            isolation: IsolationClass::Basic,
+            ambient_temp: 25.0,
+            max_temp_rise: 50.0,
+            efficiency_target: 0.93,
+            inductor_ripple_ratio: 0.3,
+            preferred_core: None,
+        }
+    }
+}
+
+impl FullBridgeRequirements {
+    /// Calculate output power
+    pub fn output_power(&self) -> f64 {
+        self.vout * self.iout_max
+    }
+
+    /// Calculate estimated input power
+    pub fn estimated_input_power(&self) -> f64 {
+        self.output_power() / self.efficiency_target
+    }
+}
+
+// ============================================================================
+// COMPLETE DESIGN
+// ============================================================================
+
+/// Complete phase-shifted full-bridge design
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FullBridgeDesign {
+    /// Design requirements
+    pub requirements: FullBridgeRequirements,
+    /// Effective (secondary) duty cycle at Vin_min
+    pub effective_duty_max: f64,
+    /// Effective duty cycle at Vin_nom
+    pub effective_duty_nom: f64,
+    /// Effective duty cycle at Vin_max
+    pub effective_duty_min: f64,
+    /// Duty cycle lost to commutation at Vin_min, full load
+    pub duty_loss: f64,
+    /// Transformer turns ratio (Np:Ns)
+    pub turns_ratio: f64,
+    /// Transformer design
+    pub transformer: TransformerDesign,
+    /// Bridge switch (one of four identical)
+    pub switch: SelectedMOSFET,
+    /// Rectifier diode (one of `rectifier.diode_count()`)
+    pub rectifier_diode: SelectedDiode,
+    /// Output inductor
+    pub output_inductor: OutputInductor,
+    /// Output capacitor
+    pub output_capacitor: OutputCapacitor,
+    /// Input capacitor
+    pub input_capacitor: OutputCapacitor,
+    /// Total commutating inductance needed for ZVS (H)
+    pub zvs_inductance: f64,
+    /// External series inductance added to the transformer leakage (H)
+    pub series_inductance: f64,
+    /// Peak primary current (A)
+    pub i_pri_peak: f64,
+    /// RMS current per switch (A)
+    pub i_switch_rms: f64,
+    /// Total efficiency estimate
+    pub efficiency: f64,
+    /// Loss breakdown
+    pub losses: IsolatedLosses,
+}
+
+// ============================================================================
+// DESIGN ALGORITHM
+// ============================================================================
+
+/// Design a phase-shifted full-bridge converter from requirements with the built-in parts
+pub fn design_full_bridge(req: &FullBridgeRequirements) -> Result<FullBridgeDesign, String> {
+    design_full_bridge_with(req, builtin_parts())
+}
+
+/// Design a phase-shifted full-bridge converter choosing parts from `library`
+pub fn design_full_bridge_with(
+    req: &FullBridgeRequirements,
+    library: &PartLibrary,
+) -> Result<FullBridgeDesign, String> {
+    // Validate inputs
+    if req.output_power() <= 0.0 {
+        return Err("Output power must be positive".to_string());
+    }
+    if req.switching_freq < 10e3 || req.switching_freq > 1e6 {
+        return Err("Switching frequency must be between 10kHz and 1MHz".to_string());
+    }
+    if req.duty_cycle_max <= 0.2 || req.duty_cycle_max >= 1.0 {
+        return Err("Full-bridge duty cycle limit must be between 0.2 and 1.0".to_string());
+    }
+    if req.zvs_load_fraction <= 0.0 || req.zvs_load_fraction > 1.0 {
+        return Err("ZVS load fraction must be between 0 and 1".to_string());
+    }
+
+    // Start with 10% of the duty budget reserved for commutation, then
+    // re-size the transformer until the commutation loss fits. A lower
+    // target raises Ns/Np, which shrinks the ZVS inductance and its loss.
+    let mut d_eff_target = req.duty_cycle_max * 0.9;
+    let mut design = design_with_duty_target(library, req, d_eff_target)?;
+    for _ in 0..4 {
+        let required = design.effective_duty_max + design.duty_loss;
+        if required <= req.duty_cycle_max {
+            return Ok(design);
+        }
+        // Step past the turns rounding
+        d_eff_target -= required - req.duty_cycle_max + 0.02;
+        if d_eff_target <= 0.1 {
+            break;
+        }
+        design = design_with_duty_target(library, req, d_eff_target)?;
+    }
+
+    if design.effective_duty_max + design.duty_loss > req.duty_cycle_max {
+        return Err(format!(
+            "Cannot achieve required duty cycle. D_max={:.2} but need {:.2} (incl. {:.2} commutation loss)",
+            req.duty_cycle_max,
+            design.effective_duty_max + design.duty_loss,
+            design.duty_loss
+        ));
+    }
+    Ok(design)
+}
+
+/// Design with the transformer sized for a given effective duty at Vin_min
+fn design_with_duty_target(
+    library: &PartLibrary,
+    req: &FullBridgeRequirements,
+    d_eff_target: f64,
+) -> Result<FullBridgeDesign, String> {
+    let vin_min = req.vin.min_v;
+    let vin_max = req.vin.max_v;
+    let vin_nom = req.vin.nom_v;
+    let vout = req.vout;
+    let iout = req.iout_max;
+    let fsw = req.switching_freq;
+    let pout = req.output_power();
+
+    // Rectifier forward drop
+    let vd = 0.5 * req.rectifier.diodes_in_path() as f64;
+
+    // Vout + Vd = Vin × (Ns/Np) × D_eff
+    let transformer_req = transformer_requirements(
+        TransformerTopology::FullBridge,
+        vin_min,
+        vout + vd,
+        req.rectifier.winding_rms(iout, d_eff_target),
+        fsw,
+        d_eff_target,
+        req.isolation,
+        req.ambient_temp,
+        req.max_temp_rise,
+    );
+    let transformer = build_transformer(library, &transformer_req, req.preferred_core)?;
+    let ns_np = secondary_ratio(&transformer);
+
+    // Effective duty cycle with the rounded turns
+    let duty_for = |vin: f64| (vout + vd) / (vin * ns_np);
+    let effective_duty_max = duty_for(vin_min);
+    let effective_duty_nom = duty_for(vin_nom);
+    let effective_duty_min = duty_for(vin_max);
+
+    // Output filter sees a rectified square wave at 2 × fsw
+    let (output_inductor, output_capacitor) = output_filter(
+        vout,
+        iout,
+        req.inductor_ripple_ratio,
+        req.ripple_pp,
+        effective_duty_nom,
+        2.0 * fsw,
+    );
+
+    // The primary carries the reflected inductor current during both power
+    // transfer and freewheeling, so each switch sees half of it (rms)
+    let i_pri_peak = output_inductor.current_peak * ns_np;
+    let i_pri_rms = output_inductor.current_rms * ns_np;
+    let i_switch_rms = i_pri_rms / 2.0_f64.sqrt();
+
+    let switch =
+        SelectedMOSFET::select(library, vin_max, i_switch_rms, i_pri_peak, fsw)?.with_zvs();
+
+    // ZVS: ½ × L × I² must cover the two switch capacitances of a leg,
+    // with 4/3 for the nonlinear Coss: L ≥ (8/3) × Coss × Vin² / I²
+    let i_zvs = req.zvs_load_fraction * iout * ns_np;
+    let zvs_inductance = 8.0 / 3.0 * switch.spec.coss * vin_max.powi(2) / i_zvs.powi(2);
+    let series_inductance = (zvs_inductance - transformer.leakage_inductance).max(0.0);
+    let commutating_inductance = transformer.leakage_inductance + series_inductance;
+
+    // Primary current reverses through the commutating inductance each
+    // half cycle, during which no power is transferred
+    let duty_loss = 4.0 * commutating_inductance * ns_np * iout * fsw / vin_min;
+
+    // Secondary rectifier
+    let (rectifier_diode, rectifier_loss) = select_rectifier(
+        library,
+        req.rectifier,
+        vin_max * ns_np,
+        iout,
+        output_inductor.current_peak,
+    )?;
+
+    // Input current flows only during power transfer, at 2 × fsw
+    let i_in_ripple = iout * ns_np * (effective_duty_nom * (1.0 - effective_duty_nom)).sqrt();
+    let input_capacitor = input_capacitor(i_in_ripple, i_pri_peak, vin_min, vin_max, 2.0 * fsw);
+
+    // External series inductor: 5mΩ winding plus a core loss estimate
+    let resonant_tank = if series_inductance > 0.0 {
+        i_pri_rms.powi(2) * 0.005 + 0.2
+    } else {
+        0.0
+    };
+
+    let losses = IsolatedLosses {
+        switch_conduction: 4.0 * switch.loss_conduction,
+        switch_switching: 4.0 * switch.loss_switching,
+        switch_gate: 4.0 * switch.loss_gate,
+        rectifier: rectifier_loss,
+        transformer_core: transformer.core_loss,
+        transformer_copper: transformer.primary_copper_loss + transformer.secondary_copper_loss,
+        inductor_core: output_inductor.core_loss,
+        inductor_copper: output_inductor.copper_loss,
+        resonant_tank,
+        input_cap_esr: i_in_ripple.powi(2) * input_capacitor.max_esr * 0.1,
+        output_cap_esr: output_capacitor.ripple_current_rms.powi(2)
+            * output_capacitor.max_esr
+            * 0.1,
+        total: 0.0,
+    }
+    .with_total();
+
+    let efficiency = pout / (pout + losses.total);
+
+    Ok(FullBridgeDesign {
+        requirements: req.clone(),
+        effective_duty_max,
+        effective_duty_nom,
+        effective_duty_min,
+        duty_loss,
+        turns_ratio: 1.0 / ns_np,
+        transformer,
+        switch,
+        rectifier_diode,
+        output_inductor,
+        output_capacitor,
+        input_capacitor,
+        zvs_inductance,
+        series_inductance,
+        i_pri_peak,
+        i_switch_rms,
+        efficiency,
+        losses,
+    })
+}
+
+// ============================================================================
+// SMALL-SIGNAL MODEL
+// ============================================================================
+
+impl FullBridgeDesign {
+    /// Small-signal model at nominal input
+    ///
+    /// Buck-derived: the forward model applies with the effective duty as
+    /// the control input. The commutating inductance adds some damping
+    /// that this model ignores, so phase margin is slightly pessimistic.
+    pub fn small_signal(&self, esr: f64) -> ForwardSmallSignal {
+        let req = &self.requirements;
+        ForwardSmallSignal::new(
+            req.vin.nom_v,
+            req.vout,
+            req.iout_max,
+            self.output_inductor.inductance,
+            self.output_capacitor.capacitance,
+            esr,
+            1.0 / self.turns_ratio,
+            req.switching_freq,
+        )
+    }
+}
+
+// ============================================================================
+// DISPLAY IMPLEMENTATION
+// ============================================================================
+
+impl FullBridgeDesign {
+    /// Generate a summary string
+    pub fn summary(&self) -> String {
+        let req = &self.requirements;
+        format!(
+            "Phase-Shifted Full-Bridge Design\n\
+             ================================\n\
+             Input: {} to {}\n\
+             Output: {} @ {}\n\
+             Power: {:.1} W\n\
+             Frequency: {:.0} kHz (ripple at {:.0} kHz)\n\
+             Rectifier: {:?}\n\
+             \n\
+             Effective Duty: {:.1}%-{:.1}% (+{:.1}% commutation)\n\
+             Turns Ratio (Np:Ns): {:.2}:1\n\
+             \n\
+             Transformer: {:?} (Np={}, Llk={})\n\
+             Series Inductor: {} (ZVS above {:.0}% load)\n\
+             MOSFETs: 4 × {} (Vds_pk={:.0}V)\n\
+             Rectifiers: {} × {} (Vr={:.0}V)\n\
+             \n\
+             Output Inductor: {} (ΔI={:.2}A)\n\
+             Output Cap: {} (ESR<{:.0}mΩ)\n\
+             \n\
+             Efficiency: {:.1}%\n\
+             Total Losses: {:.2} W",
+            format_voltage(req.vin.min_v),
+            format_voltage(req.vin.max_v),
+            format_voltage(req.vout),
+            format_current(req.iout_max),
+            req.output_power(),
+            req.switching_freq / 1000.0,
+            2.0 * req.switching_freq / 1000.0,
+            req.rectifier,
+            self.effective_duty_min * 100.0,
+            self.effective_duty_max * 100.0,
+            self.duty_loss * 100.0,
+            self.turns_ratio,
+            self.transformer.core.core_type,
+            self.transformer.primary.turns,
+            format_inductance(self.transformer.leakage_inductance),
+            format_inductance(self.series_inductance),
+            req.zvs_load_fraction * 100.0,
+            self.switch.spec.part_number,
+            self.switch.vds_peak,
+            req.rectifier.diode_count(),
+            self.rectifier_diode.spec.part_number,
+            self.rectifier_diode.vr_peak,
+            format_inductance(self.output_inductor.inductance),
+            self.output_inductor.ripple_current_pp,
+            format_capacitance(self.output_capacitor.capacitance),
+            self.output_capacitor.max_esr * 1000.0,
+            self.efficiency * 100.0,
+            self.losses.total,
+        )
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_bridge_basic_design() {
+        let req = FullBridgeRequirements::default();
+        let result = design_full_bridge(&req);
+        assert!(result.is_ok(), "Design should succeed: {:?}", result.err());
+
+        let design = result.unwrap();
+
+        // Phase shift plus commutation fits within the duty budget
+        assert!(design.effective_duty_max + design.duty_loss <= req.duty_cycle_max);
+        assert!((design.switch.vds_peak - req.vin.max_v).abs() < 1e-9);
+
+        assert!(
+            design.efficiency > 0.85 && design.efficiency < 0.99,
+            "Efficiency {:.1}% out of range",
+            design.efficiency * 100.0
+        );
+    }
+
+    #[test]
+    fn test_full_bridge_zvs_energy() {
+        let design = design_full_bridge(&FullBridgeRequirements::default()).unwrap();
+        let req = &design.requirements;
+        let ns_np = 1.0 / design.turns_ratio;
+
+        // Commutating inductor energy at the ZVS load threshold covers
+        // the leg capacitance: ½LI² ≥ (4/3) Coss Vin²
+        let l = design.transformer.leakage_inductance + design.series_inductance;
+        let i = req.zvs_load_fraction * req.iout_max * ns_np;
+        let e_inductor = 0.5 * l * i * i;
+        let e_coss = 4.0 / 3.0 * design.switch.spec.coss * req.vin.max_v.powi(2);
+        assert!(e_inductor >= e_coss * (1.0 - 1e-9));
+
+        // ZVS leaves only a fraction of the hard-switched loss
+        assert!(
+            design.switch.loss_switching
+                < 0.5 * req.vin.max_v * design.i_pri_peak * 30e-9 * req.switching_freq
+        );
+    }
+
+    #[test]
+    fn test_full_bridge_lighter_zvs_costs_duty() {
+        let req = FullBridgeRequirements::default();
+        let nominal = design_full_bridge(&req).unwrap();
+        let light = design_full_bridge(&FullBridgeRequirements {
+            zvs_load_fraction: 0.3,
+            ..req
+        })
+        .unwrap();
+
+        assert!(light.zvs_inductance > nominal.zvs_inductance);
+        assert!(light.duty_loss > nominal.duty_loss);
+    }
+
+    #[test]
+    fn test_full_bridge_small_signal() {
+        let design = design_full_bridge(&FullBridgeRequirements::default()).unwrap();
+        let req = &design.requirements;
+        let tf = design.small_signal(0.01).control_to_output();
+        assert!((tf.dc_gain - req.vin.nom_v / design.turns_ratio).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,456 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: half_bridge.rs | DNA/src/power/topologies/half_bridge.rs
+//! PURPOSE: Hard-switched (PWM) half-bridge converter topology design
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Complete half-bridge converter design including:
+//! - Transformer design driven from the split-capacitor midpoint
+//! - High-side/low-side switch (MOSFET) selection
+//! - Split input capacitor sizing
+//! - Secondary rectifier selection (center-tapped or bridge)
+//! - Output LC filter sizing
+//! - Efficiency estimation
+//!
+//! Two switches alternately connect the transformer primary to either rail,
+//! with the other end held at Vin/2 by a capacitive divider. Key properties:
+//! - Output voltage: Vout = (Vin/2) × (Ns/Np) × 2D, with D ≤ 0.5 per switch
+//! - Each switch blocks only Vin, half the push-pull stress
+//! - The series split capacitors block DC, so flux walking is self-correcting
+//! - Primary current is doubled for the same power compared to full-bridge
+//!
+//! Common choice for 200-1000W off-line supplies after a PFC stage.
+//! See `llc` for the resonant variant of the same bridge.
+
+use serde::{Deserialize, Serialize};
+
+use crate::power::control::ForwardSmallSignal;
+use crate::power::library::{builtin_parts, PartLibrary};
+use crate::power::magnetics::{CoreType, IsolationClass, TransformerDesign, TransformerTopology};
+use crate::power::types::VoltageRange;
+use crate::power::{format_capacitance, format_current, format_inductance, format_voltage};
+
+use super::flyback::{CapacitorType, OutputCapacitor, SelectedDiode, SelectedMOSFET};
+use super::forward::OutputInductor;
+use super::isolated::{
+    build_transformer, input_capacitor, output_filter, secondary_ratio, select_rectifier,
+    transformer_requirements, IsolatedLosses, SecondaryRectifier,
+};
+
+// ============================================================================
+// DESIGN REQUIREMENTS
+// ============================================================================
+
+/// Half-bridge converter design requirements
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HalfBridgeRequirements {
+    /// Input voltage range
+    pub vin: VoltageRange,
+    /// Output voltage (V)
+    pub vout: f64,
+    /// Maximum output current (A)
+    pub iout_max: f64,
+    /// Minimum output current (A)
+    pub iout_min: f64,
+    /// Maximum output ripple voltage (V peak-to-peak)
+    pub ripple_pp: f64,
+    /// Switching frequency of each switch (Hz)
+    pub switching_freq: f64,
+    /// Maximum duty cycle per switch (below 0.5 to leave dead time)
+    pub duty_cycle_max: f64,
+    /// Secondary rectifier configuration
+    pub rectifier: SecondaryRectifier,
+    /// Allowed midpoint ripple on the split capacitors (fraction of Vin/2)
+    pub split_cap_ripple: f64,
+    /// Isolation requirement
+    pub isolation: IsolationClass,
+    /// Ambient temperature (°C)
+    pub ambient_temp: f64,
+    /// Maximum temperature rise (°C)
+    pub max_temp_rise: f64,
+    /// Target efficiency (0.0-1.0)
+    pub efficiency_target: f64,
+    /// Inductor current ripple ratio (ΔI / Iout)
+    pub inductor_ripple_ratio: f64,
+    /// Preferred core type (or None for automatic)
+    pub preferred_core: Option<CoreType>,
+}
+
+impl Default for HalfBridgeRequirements {
+    fn default() -> Self {
+        Self {
+            vin: VoltageRange::range(360.0, 400.0), // PFC bus
+            vout: 24.0,
+            iout_max: 10.0,
+            iout_min: 1.0,
+            ripple_pp: 0.24,
+            switching_freq: 100e3,
+            duty_cycle_max: 0.45,
+            rectifier: SecondaryRectifier::CenterTapped,
+            split_cap_ripple: 0.05,
+            isolation: IsolationClass::Basic,
+            ambient_temp: 25.0,
+            max_temp_rise: 50.0,
+            efficiency_target: 0.9,
+            inductor_ripple_ratio: 0.3,
+            preferred_core: None,
+        }
+    }
+}
+
+impl HalfBridgeRequirements {
+    /// Calculate output power
+    pub fn output_power(&self) -> f64 {
+        self.vout * self.iout_max
+    }
+
+    /// Calculate estimated input power
+    pub fn estimated_input_power(&self) -> f64 {
+        self.output_power() / self.efficiency_target
+    }
+}
+
+// ============================================================================
+// COMPLETE DESIGN
+// ============================================================================
+
+/// Complete half-bridge converter design
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HalfBridgeDesign {
+    /// Design requirements
+    pub requirements: HalfBridgeRequirements,
+    /// Duty cycle per switch at Vin_min
+    pub duty_cycle_max: f64,
+    /// Duty cycle per switch at Vin_nom
+    pub duty_cycle_nom: f64,
+    /// Duty cycle per switch at Vin_max
+    pub duty_cycle_min: f64,
+    /// Transformer turns ratio (Np:Ns)
+    pub turns_ratio: f64,
+    /// Transformer design
+    pub transformer: TransformerDesign,
+    /// Bridge switch (one of two identical)
+    pub switch: SelectedMOSFET,
+    /// Rectifier diode (one of `rectifier.diode_count()`)
+    pub rectifier_diode: SelectedDiode,
+    /// Output inductor
+    pub output_inductor: OutputInductor,
+    /// Output capacitor
+    pub output_capacitor: OutputCapacitor,
+    /// Input bulk capacitor
+    pub input_capacitor: OutputCapacitor,
+    /// Split capacitor (one of two identical)
+    pub split_capacitor: OutputCapacitor,
+    /// Peak primary current (A)
+    pub i_pri_peak: f64,
+    /// RMS current per switch (A)
+    pub i_switch_rms: f64,
+    /// Total efficiency estimate
+    pub efficiency: f64,
+    /// Loss breakdown
+    pub losses: IsolatedLosses,
+}
+
+// ============================================================================
+// DESIGN ALGORITHM
+// ============================================================================
+
+/// Design a hard-switched half-bridge converter from requirements with the built-in parts
+pub fn design_half_bridge(req: &HalfBridgeRequirements) -> Result<HalfBridgeDesign, String> {
+    design_half_bridge_with(req, builtin_parts())
+}
+
+/// Design a hard-switched half-bridge converter choosing parts from `library`
+pub fn design_half_bridge_with(
+    req: &HalfBridgeRequirements,
+    library: &PartLibrary,
+) -> Result<HalfBridgeDesign, String> {
+    // Validate inputs
+    if req.output_power() <= 0.0 {
+        return Err("Output power must be positive".to_string());
+    }
+    if req.switching_freq < 10e3 || req.switching_freq > 1e6 {
+        return Err("Switching frequency must be between 10kHz and 1MHz".to_string());
+    }
+    if req.duty_cycle_max <= 0.1 || req.duty_cycle_max >= 0.5 {
+        return Err("Half-bridge duty cycle limit must be between 0.1 and 0.5".to_string());
+    }
+    if req.split_cap_ripple <= 0.0 {
+        return Err("Split capacitor ripple must be positive".to_string());
+    }
+
+    let vin_min = req.vin.min_v;
+    let vin_max = req.vin.max_v;
+    let vin_nom = req.vin.nom_v;
+    let vout = req.vout;
+    let iout = req.iout_max;
+    let fsw = req.switching_freq;
+    let pout = req.output_power();
+
+    // Rectifier forward drop
+    let vd = 0.5 * req.rectifier.diodes_in_path() as f64;
+
+    // The primary sees ±Vin/2
+    // Vout + Vd = (Vin/2) × (Ns/Np) × D_eff, where D_eff = 2D
+    let d_eff_target = 2.0 * req.duty_cycle_max * 0.95;
+    let transformer_req = transformer_requirements(
+        TransformerTopology::HalfBridge,
+        vin_min / 2.0,
+        vout + vd,
+        req.rectifier.winding_rms(iout, d_eff_target),
+        fsw,
+        d_eff_target,
+        req.isolation,
+        req.ambient_temp,
+        req.max_temp_rise,
+    );
+    let transformer = build_transformer(library, &transformer_req, req.preferred_core)?;
+    let ns_np = secondary_ratio(&transformer);
+
+    // Per-switch duty cycle with the rounded turns
+    let duty_for = |vin: f64| (vout + vd) / (vin * ns_np);
+    let duty_max = duty_for(vin_min);
+    let duty_nom = duty_for(vin_nom);
+    let duty_min = duty_for(vin_max);
+
+    if duty_max > req.duty_cycle_max {
+        return Err(format!(
+            "Cannot achieve required duty cycle. D_max={:.2} but need {:.2}",
+            req.duty_cycle_max, duty_max
+        ));
+    }
+
+    // Output filter sees a rectified square wave at 2 × fsw
+    let (output_inductor, output_capacitor) = output_filter(
+        vout,
+        iout,
+        req.inductor_ripple_ratio,
+        req.ripple_pp,
+        2.0 * duty_nom,
+        2.0 * fsw,
+    );
+
+    // Primary currents - the winding carries the reflected inductor current
+    // in both directions, each switch for its own half cycle
+    let i_pri_peak = output_inductor.current_peak * ns_np;
+    let i_switch_rms = output_inductor.current_rms * ns_np * duty_nom.sqrt();
+
+    // Each switch blocks the full bus
+    let switch = SelectedMOSFET::select(library, vin_max, i_switch_rms, i_pri_peak, fsw)?;
+
+    // Secondary rectifier
+    let (rectifier_diode, rectifier_loss) = select_rectifier(
+        library,
+        req.rectifier,
+        vin_max / 2.0 * ns_np,
+        iout,
+        output_inductor.current_peak,
+    )?;
+
+    // Input current pulses at 2 × fsw, each pulse carrying the primary current
+    let d_eff_nom = 2.0 * duty_nom;
+    let i_in_ripple = iout * ns_np * (d_eff_nom * (1.0 - d_eff_nom)).sqrt();
+    let input_capacitor = input_capacitor(i_in_ripple, i_pri_peak, vin_min, vin_max, 2.0 * fsw);
+
+    // Split capacitors: each half cycle moves the midpoint by
+    // ΔV = I_pri × D / (2C × fsw), as both capacitors share the current
+    let i_pri_rms = output_inductor.current_rms * ns_np * d_eff_nom.sqrt();
+    let dv_mid = req.split_cap_ripple * vin_min / 2.0;
+    let split_capacitance = iout * ns_np * duty_max / (2.0 * fsw * dv_mid);
+    let split_capacitor = OutputCapacitor {
+        capacitance: split_capacitance,
+        voltage_rating: (vin_max * 1.25).ceil(),
+        max_esr: dv_mid / i_pri_peak,
+        ripple_current_rms: i_pri_rms / 2.0,
+        cap_type: CapacitorType::Ceramic,
+        parallel_count: 1,
+    };
+
+    let losses = IsolatedLosses {
+        switch_conduction: 2.0 * switch.loss_conduction,
+        switch_switching: 2.0 * switch.loss_switching,
+        switch_gate: 2.0 * switch.loss_gate,
+        rectifier: rectifier_loss,
+        transformer_core: transformer.core_loss,
+        transformer_copper: transformer.primary_copper_loss + transformer.secondary_copper_loss,
+        inductor_core: output_inductor.core_loss,
+        inductor_copper: output_inductor.copper_loss,
+        resonant_tank: 0.0,
+        input_cap_esr: i_in_ripple.powi(2) * input_capacitor.max_esr * 0.1
+            + 2.0 * split_capacitor.ripple_current_rms.powi(2) * split_capacitor.max_esr * 0.1,
+        output_cap_esr: output_capacitor.ripple_current_rms.powi(2)
+            * output_capacitor.max_esr
+            * 0.1,
+        total: 0.0,
+    }
+    .with_total();
+
+    let efficiency = pout / (pout + losses.total);
+
+    Ok(HalfBridgeDesign {
+        requirements: req.clone(),
+        duty_cycle_max: duty_max,
+        duty_cycle_nom: duty_nom,
+        duty_cycle_min: duty_min,
+        turns_ratio: 1.0 / ns_np,
+        transformer,
+        switch,
+        rectifier_diode,
+        output_inductor,
+        output_capacitor,
+        input_capacitor,
+        split_capacitor,
+        i_pri_peak,
+        i_switch_rms,
+        efficiency,
+        losses,
+    })
+}
+
+// ============================================================================
+// SMALL-SIGNAL MODEL
+// ============================================================================
+
+impl HalfBridgeDesign {
+    /// Small-signal model at nominal input
+    ///
+    /// Buck-derived with the primary driven from Vin/2: the forward model
+    /// applies with effective duty D_eff = 2D and DC gain (Vin/2) × Ns/Np.
+    pub fn small_signal(&self, esr: f64) -> ForwardSmallSignal {
+        let req = &self.requirements;
+        ForwardSmallSignal::new(
+            req.vin.nom_v / 2.0,
+            req.vout,
+            req.iout_max,
+            self.output_inductor.inductance,
+            self.output_capacitor.capacitance,
+            esr,
+            1.0 / self.turns_ratio,
+            req.switching_freq,
+        )
+    }
+}
+
+// ============================================================================
+// DISPLAY IMPLEMENTATION
+// ============================================================================
+
+impl HalfBridgeDesign {
+    /// Generate a summary string
+    pub fn summary(&self) -> String {
+        let req = &self.requirements;
+        format!(
+            "Half-Bridge Converter Design\n\
+             ============================\n\
+             Input: {} to {}\n\
+             Output: {} @ {}\n\
+             Power: {:.1} W\n\
+             Frequency: {:.0} kHz (ripple at {:.0} kHz)\n\
+             Rectifier: {:?}\n\
+             \n\
+             Duty Cycle (per switch): {:.1}%-{:.1}%\n\
+             Turns Ratio (Np:Ns): {:.2}:1\n\
+             \n\
+             Transformer: {:?} (Np={})\n\
+             MOSFETs: 2 × {} (Vds_pk={:.0}V)\n\
+             Split Caps: 2 × {} ({:.0}V)\n\
+             Rectifiers: {} × {} (Vr={:.0}V)\n\
+             \n\
+             Output Inductor: {} (ΔI={:.2}A)\n\
+             Output Cap: {} (ESR<{:.0}mΩ)\n\
+             \n\
+             Efficiency: {:.1}%\n\
+             Total Losses: {:.2} W",
+            format_voltage(req.vin.min_v),
+            format_voltage(req.vin.max_v),
+            format_voltage(req.vout),
+            format_current(req.iout_max),
+            req.output_power(),
+            req.switching_freq / 1000.0,
+            2.0 * req.switching_freq / 1000.0,
+            req.rectifier,
+            self.duty_cycle_min * 100.0,
+            self.duty_cycle_max * 100.0,
+            self.turns_ratio,
+            self.transformer.core.core_type,
+            self.transformer.primary.turns,
+            self.switch.spec.part_number,
+            self.switch.vds_peak,
+            format_capacitance(self.split_capacitor.capacitance),
+            self.split_capacitor.voltage_rating,
+            req.rectifier.diode_count(),
+            self.rectifier_diode.spec.part_number,
+            self.rectifier_diode.vr_peak,
+            format_inductance(self.output_inductor.inductance),
+            self.output_inductor.ripple_current_pp,
+            format_capacitance(self.output_capacitor.capacitance),
+            self.output_capacitor.max_esr * 1000.0,
+            self.efficiency * 100.0,
+            self.losses.total,
+        )
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_half_bridge_basic_design() {
+        let req = HalfBridgeRequirements::default();
+        let result = design_half_bridge(&req);
+        assert!(result.is_ok(), "Design should succeed: {:?}", result.err());
+
+        let design = result.unwrap();
+
+        assert!(design.duty_cycle_max <= req.duty_cycle_max);
+        // Switch blocks the bus, not twice the bus
+        assert!((design.switch.vds_peak - req.vin.max_v).abs() < 1e-9);
+        assert!(design.switch.spec.vds_max >= 1.25 * req.vin.max_v);
+
+        assert!(
+            design.efficiency > 0.8 && design.efficiency < 0.99,
+            "Efficiency {:.1}% out of range",
+            design.efficiency * 100.0
+        );
+    }
+
+    #[test]
+    fn test_half_bridge_volt_second_balance() {
+        let design = design_half_bridge(&HalfBridgeRequirements::default()).unwrap();
+        let req = &design.requirements;
+        let ns_np = 1.0 / design.turns_ratio;
+
+        // Vout + Vd = (Vin/2) × Ns/Np × 2D
+        let vout = req.vin.min_v / 2.0 * ns_np * 2.0 * design.duty_cycle_max - 0.5;
+        assert!((vout - req.vout).abs() < 1e-9);
+
+        let model = design.small_signal(0.01);
+        assert!((model.control_to_output().dc_gain - req.vin.nom_v / 2.0 * ns_np).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_half_bridge_split_capacitor() {
+        let req = HalfBridgeRequirements::default();
+        let design = design_half_bridge(&req).unwrap();
+        let ns_np = 1.0 / design.turns_ratio;
+
+        // Midpoint excursion at Vin_min equals the allowed ripple
+        let dv = req.iout_max * ns_np * design.duty_cycle_max
+            / (2.0 * req.switching_freq * design.split_capacitor.capacitance);
+        assert!((dv - req.split_cap_ripple * req.vin.min_v / 2.0).abs() < 1e-9);
+
+        // Tighter ripple needs more capacitance
+        let tight = design_half_bridge(&HalfBridgeRequirements {
+            split_cap_ripple: 0.01,
+            ..req
+        })
+        .unwrap();
+        assert!(tight.split_capacitor.capacitance > design.split_capacitor.capacitance);
+    }
+}
@@ -0,0 +1,326 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: isolated.rs | DNA/src/power/topologies/isolated.rs
+//! PURPOSE: Shared building blocks for bridge-family isolated converters
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Push-pull, half-bridge, full-bridge and LLC converters all drive their
+//! transformer with a bipolar voltage and rectify the secondary into an
+//! output filter. The pieces they share live here:
+//! - Secondary rectifier configuration (center-tapped or diode bridge)
+//! - Output LC filter sizing at twice the switching frequency
+//! - Transformer synthesis with frequency-based core material selection
+//! - Input capacitor sizing and the common loss breakdown
+
+use serde::{Deserialize, Serialize};
+
+use crate::power::library::PartLibrary;
+use crate::power::magnetics::{
+    auto_design_transformer, ferrite_database, CoreMaterial, CoreType, IsolationClass,
+    TransformerDesign, TransformerRequirements, TransformerTopology,
+};
+
+use super::flyback::{CapacitorType, OutputCapacitor, SelectedDiode};
+use super::forward::OutputInductor;
+
+// ============================================================================
+// SECONDARY RECTIFIER
+// ============================================================================
+
+/// Secondary rectifier configuration
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SecondaryRectifier {
+    /// Center-tapped secondary with two diodes
+    /// One diode drop, but each diode blocks twice the winding voltage
+    CenterTapped,
+    /// Single secondary winding with a four-diode bridge
+    /// Two diode drops, each diode blocks the winding voltage
+    FullBridge,
+}
+
+impl SecondaryRectifier {
+    /// Number of rectifier diodes
+    pub fn diode_count(&self) -> u32 {
+        match self {
+            SecondaryRectifier::CenterTapped => 2,
+            SecondaryRectifier::FullBridge => 4,
+        }
+    }
+
+    /// Diodes in the conduction path at any instant
+    pub fn diodes_in_path(&self) -> u32 {
+        match self {
+            SecondaryRectifier::CenterTapped => 1,
+            SecondaryRectifier::FullBridge => 2,
+        }
+    }
+
+    /// Diode reverse voltage for a secondary winding swinging ±v_sec
+    pub fn reverse_voltage(&self, v_sec: f64) -> f64 {
+        match self {
+            SecondaryRectifier::CenterTapped => 2.0 * v_sec,
+            SecondaryRectifier::FullBridge => v_sec,
+        }
+    }
+
+    /// RMS current per secondary winding with a square-wave secondary
+    ///
+    /// Center tap: each half carries Iout while active and Iout/2 while
+    /// freewheeling, so I_rms = Iout × √((1 + D_eff) / 4).
+    /// Bridge: the winding carries Iout only while power is transferred.
+    pub fn winding_rms(&self, iout: f64, d_eff: f64) -> f64 {
+        match self {
+            SecondaryRectifier::CenterTapped => iout * ((1.0 + d_eff) / 4.0).sqrt(),
+            SecondaryRectifier::FullBridge => iout * d_eff.sqrt(),
+        }
+    }
+
+    /// Description of the rectifier
+    pub fn description(&self) -> &'static str {
+        match self {
+            SecondaryRectifier::CenterTapped => "Center-tapped - one drop, best for low Vout",
+            SecondaryRectifier::FullBridge => "Diode bridge - two drops, best for high Vout",
+        }
+    }
+}
+
+// ============================================================================
+// LOSS BREAKDOWN
+// ============================================================================
+
+/// Loss breakdown shared by push-pull, bridge and resonant converters
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct IsolatedLosses {
+    /// Primary switch conduction (all switches)
+    pub switch_conduction: f64,
+    /// Primary switch switching (all switches)
+    pub switch_switching: f64,
+    /// Primary switch gate drive (all switches)
+    pub switch_gate: f64,
+    /// Secondary rectifier conduction
+    pub rectifier: f64,
+    /// Transformer core loss
+    pub transformer_core: f64,
+    /// Transformer copper loss
+    pub transformer_copper: f64,
+    /// Output inductor core loss
+    pub inductor_core: f64,
+    /// Output inductor copper loss
+    pub inductor_copper: f64,
+    /// Resonant or commutation inductor and capacitor losses
+    pub resonant_tank: f64,
+    /// Input capacitor ESR
+    pub input_cap_esr: f64,
+    /// Output capacitor ESR
+    pub output_cap_esr: f64,
+    /// Total losses
+    pub total: f64,
+}
+
+impl IsolatedLosses {
+    /// Fill in `total` from the individual contributions
+    pub fn with_total(mut self) -> Self {
+        self.total = self.switch_conduction
+            + self.switch_switching
+            + self.switch_gate
+            + self.rectifier
+            + self.transformer_core
+            + self.transformer_copper
+            + self.inductor_core
+            + self.inductor_copper
+            + self.resonant_tank
+            + self.input_cap_esr
+            + self.output_cap_esr;
+        self
+    }
+}
+
+// ============================================================================
+// SHARED DESIGN STEPS
+// ============================================================================
+
+/// Select a ferrite from `library` suited to the frequency
+pub(super) fn select_core_material(library: &PartLibrary, frequency: f64) -> CoreMaterial {
+    library
+        .ferrites()
+        .find(|m| m.is_frequency_suitable(frequency))
+        .cloned()
+        .unwrap_or_else(|| {
+            // Default to N87 which works well for 25kHz-500kHz
+            ferrite_database()
+                .into_iter()
+                .find(|m| m.name == "N87")
+                .unwrap()
+        })
+}
+
+/// Rectifier drop `design_transformer` already adds to each secondary (V)
+pub(super) const TRANSFORMER_RECTIFIER_ALLOWANCE: f64 = 0.5;
+
+/// Transformer requirements for a secondary feeding `v_rectified` (Vout plus
+/// the real rectifier drop) into the rectifier
+#[allow(clippy::too_many_arguments)]
+pub(super) fn transformer_requirements(
+    topology: TransformerTopology,
+    primary_voltage: f64,
+    v_rectified: f64,
+    secondary_current: f64,
+    frequency: f64,
+    duty_cycle_max: f64,
+    isolation: IsolationClass,
+    ambient_temp: f64,
+    max_temp_rise: f64,
+) -> TransformerRequirements {
+    TransformerRequirements {
+        topology,
+        primary_voltage,
+        secondary_voltages: vec![v_rectified - TRANSFORMER_RECTIFIER_ALLOWANCE],
+        secondary_currents: vec![secondary_current],
+        frequency,
+        duty_cycle_max,
+        isolation,
+        ambient_temp,
+        max_temp_rise,
+    }
+}
+
+/// Design the power transformer with automatic core selection
+pub(super) fn build_transformer(
+    library: &PartLibrary,
+    req: &TransformerRequirements,
+    preferred_core: Option<CoreType>,
+) -> Result<TransformerDesign, String> {
+    let material = select_core_material(library, req.frequency);
+    auto_design_transformer(library, req, &material, preferred_core)
+        .ok_or_else(|| "No suitable core found for transformer".to_string())
+}
+
+/// Secondary-to-primary turns ratio (Ns/Np) of a designed transformer
+pub(super) fn secondary_ratio(transformer: &TransformerDesign) -> f64 {
+    let ns = transformer.secondaries.first().map_or(1, |s| s.turns);
+    ns as f64 / transformer.primary.turns as f64
+}
+
+/// Output LC filter fed by a rectified square wave
+///
+/// The inductor sees (Vout × (1 - D_eff)) for the freewheel interval at
+/// `f_ripple` (twice the switching frequency for bipolar drive):
+/// L = Vout × (1 - D_eff) / (ΔI × f_ripple).
+pub(super) fn output_filter(
+    vout: f64,
+    iout: f64,
+    ripple_ratio: f64,
+    ripple_pp: f64,
+    d_eff: f64,
+    f_ripple: f64,
+) -> (OutputInductor, OutputCapacitor) {
+    let delta_i = iout * ripple_ratio;
+    let inductance = vout * (1.0 - d_eff).max(0.05) / (delta_i * f_ripple);
+
+    let current_peak = iout + delta_i / 2.0;
+    let current_rms = (iout.powi(2) + (delta_i / (2.0 * 3.0_f64.sqrt())).powi(2)).sqrt();
+
+    // Estimate inductor losses
+    let dcr = 0.005; // 5mΩ estimate
+    let copper_loss = current_rms.powi(2) * dcr;
+    let core_loss = 0.2; // Rough estimate
+
+    let inductor = OutputInductor {
+        inductance,
+        current_dc: iout,
+        current_peak,
+        current_rms,
+        ripple_current_pp: delta_i,
+        dcr,
+        core_loss,
+        copper_loss,
+    };
+
+    // Half the ripple budget to ESR, half to capacitance
+    let max_esr = ripple_pp * 0.5 / delta_i;
+    let c_min = delta_i / (8.0 * f_ripple * ripple_pp * 0.5);
+
+    let capacitor = OutputCapacitor {
+        capacitance: c_min * 1.5, // Add margin
+        voltage_rating: (vout * 1.5).ceil(),
+        max_esr,
+        ripple_current_rms: delta_i / (2.0 * 3.0_f64.sqrt()),
+        cap_type: if vout <= 5.0 {
+            CapacitorType::Ceramic
+        } else {
+            CapacitorType::Hybrid
+        },
+        parallel_count: 1,
+    };
+
+    (inductor, capacitor)
+}
+
+/// Select the secondary rectifier diodes
+///
+/// Each diode carries Iout/2 on average in both configurations.
+/// Returns the selected diode and the total rectifier conduction loss.
+pub(super) fn select_rectifier(
+    library: &PartLibrary,
+    rectifier: SecondaryRectifier,
+    v_sec: f64,
+    iout: f64,
+    i_peak: f64,
+) -> Result<(SelectedDiode, f64), String> {
+    let vr = rectifier.reverse_voltage(v_sec);
+    let diode = SelectedDiode::select(library, vr, iout / 2.0, i_peak)?;
+    let loss = iout * diode.spec.vf_typical * rectifier.diodes_in_path() as f64;
+    Ok((diode, loss))
+}
+
+/// Input bulk capacitor for a pulsed primary current
+pub(super) fn input_capacitor(
+    ripple_rms: f64,
+    i_peak: f64,
+    vin_min: f64,
+    vin_max: f64,
+    f_ripple: f64,
+) -> OutputCapacitor {
+    // 2% input ripple
+    let c_min = ripple_rms / (f_ripple * 0.02 * vin_min);
+    OutputCapacitor {
+        capacitance: c_min * 2.0,
+        voltage_rating: (vin_max * 1.25).ceil(),
+        max_esr: 0.02 * vin_min / i_peak,
+        ripple_current_rms: ripple_rms,
+        cap_type: CapacitorType::Electrolytic,
+        parallel_count: 1,
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rectifier_stress() {
+        let ct = SecondaryRectifier::CenterTapped;
+        let fb = SecondaryRectifier::FullBridge;
+        assert_eq!(ct.reverse_voltage(10.0), 20.0);
+        assert_eq!(fb.reverse_voltage(10.0), 10.0);
+        assert_eq!(fb.diodes_in_path(), 2 * ct.diodes_in_path());
+
+        // Full power transfer: each half of a center tap carries Iout/√2
+        assert!((ct.winding_rms(2.0, 1.0) - 2.0 / 2.0_f64.sqrt()).abs() < 1e-12);
+        assert!((fb.winding_rms(2.0, 1.0) - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_output_filter_ripple() {
+        let (inductor, capacitor) = output_filter(12.0, 5.0, 0.3, 0.12, 0.6, 200e3);
+        // ΔI = Vout(1-D)/(L f) recovers the requested ripple
+        let delta_i = 12.0 * 0.4 / (inductor.inductance * 200e3);
+        assert!((delta_i - 1.5).abs() < 1e-9);
+        assert!(capacitor.max_esr * inductor.ripple_current_pp <= 0.06 + 1e-12);
+    }
+}
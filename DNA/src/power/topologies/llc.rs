@@ -0,0 +1,644 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: llc.rs | DNA/src/power/topologies/llc.rs
+//! PURPOSE: Half-bridge LLC resonant converter topology design
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Complete LLC resonant converter design including:
+//! - Resonant tank (Lr, Cr, Lm) from the required gain range
+//! - Frequency range from first-harmonic approximation (FHA)
+//! - Transformer design at the lowest operating frequency
+//! - Bridge switch (MOSFET) selection with zero-voltage switching
+//! - Secondary rectifier selection (center-tapped or bridge)
+//! - Capacitive output filter sizing
+//! - Efficiency estimation
+//!
+//! A half-bridge drives a square wave at 50% duty into a series Lr-Cr tank
+//! and the transformer magnetizing inductance Lm. Output is regulated by
+//! switching frequency. Key properties:
+//! - At fsw = fr the gain is 1 at every load: n = (Vin_nom/2) / Vout
+//! - Below fr the tank boosts (down to the peak-gain limit); above fr it bucks
+//! - ZVS on the primary and ZCS on the secondary across the inductive region
+//! - Gain never drops below Ln/(Ln+1) at no load
+//!
+//! Best efficiency for narrow input ranges, e.g. after a PFC stage.
+
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use crate::power::control::{llc_fha_gain, LLCSmallSignal};
+use crate::power::library::{builtin_parts, PartLibrary};
+use crate::power::magnetics::{CoreType, IsolationClass, TransformerDesign, TransformerTopology};
+use crate::power::types::VoltageRange;
+use crate::power::{
+    format_capacitance, format_current, format_frequency, format_inductance, format_voltage,
+};
+
+use super::flyback::{CapacitorType, OutputCapacitor, SelectedDiode, SelectedMOSFET};
+use super::isolated::{
+    build_transformer, input_capacitor, secondary_ratio, select_rectifier,
+    transformer_requirements, IsolatedLosses, SecondaryRectifier,
+};
+
+// ============================================================================
+// DESIGN REQUIREMENTS
+// ============================================================================
+
+/// LLC resonant converter design requirements
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LLCRequirements {
+    /// Input voltage range (nominal sets the resonant operating point)
+    pub vin: VoltageRange,
+    /// Output voltage (V)
+    pub vout: f64,
+    /// Maximum output current (A)
+    pub iout_max: f64,
+    /// Minimum output current (A)
+    pub iout_min: f64,
+    /// Maximum output ripple voltage (V peak-to-peak)
+    pub ripple_pp: f64,
+    /// Series resonant frequency fr (Hz)
+    pub resonant_freq: f64,
+    /// Highest allowed switching frequency (Hz)
+    pub max_switching_freq: f64,
+    /// Inductance ratio Ln = Lm / Lr
+    pub inductance_ratio: f64,
+    /// Secondary rectifier configuration
+    pub rectifier: SecondaryRectifier,
+    /// Isolation requirement
+    pub isolation: IsolationClass,
+    /// Ambient temperature (°C)
+    pub ambient_temp: f64,
+    /// Maximum temperature rise (°C)
+    pub max_temp_rise: f64,
+    /// Target efficiency (0.0-1.0)
+    pub efficiency_target: f64,
+    /// Preferred core type (or None for automatic)
+    pub preferred_core: Option<CoreType>,
+}
+
+impl Default for LLCRequirements {
+    fn default() -> Self {
+        Self {
+            vin: VoltageRange::with_nominal(370.0, 390.0, 410.0), // PFC bus
+            vout: 24.0,
+            iout_max: 10.0,
+            iout_min: 1.0,
+            ripple_pp: 0.24,
+            resonant_freq: 100e3,
+            max_switching_freq: 250e3,
+            inductance_ratio: 6.0,
+            rectifier: SecondaryRectifier::CenterTapped,
+            isolation: IsolationClass::Basic,
+            ambient_temp: 25.0,
+            max_temp_rise: 50.0,
+            efficiency_target: 0.95,
+            preferred_core: None,
+        }
+    }
+}
+
+impl LLCRequirements {
+    /// Calculate output power
+    pub fn output_power(&self) -> f64 {
+        self.vout * self.iout_max
+    }
+
+    /// Calculate estimated input power
+    pub fn estimated_input_power(&self) -> f64 {
+        self.output_power() / self.efficiency_target
+    }
+}
+
+// ============================================================================
+// COMPLETE DESIGN
+// ============================================================================
+
+/// Complete LLC resonant converter design
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LLCDesign {
+    /// Design requirements
+    pub requirements: LLCRequirements,
+    /// Transformer turns ratio (Np:Ns)
+    pub turns_ratio: f64,
+    /// Tank quality factor at full load
+    pub quality_factor: f64,
+    /// Resonant inductance Lr (H)
+    pub resonant_inductance: f64,
+    /// Resonant capacitance Cr (F)
+    pub resonant_capacitance: f64,
+    /// Required magnetizing inductance Lm (H), set by gapping the core
+    pub magnetizing_inductance: f64,
+    /// External series inductance added to the transformer leakage (H)
+    pub series_inductance: f64,
+    /// Tank gain needed at Vin_max
+    pub gain_min: f64,
+    /// Tank gain needed at Vin_min (with 10% margin)
+    pub gain_max: f64,
+    /// Switching frequency at Vin_min, full load (Hz)
+    pub freq_min: f64,
+    /// Switching frequency at Vin_max, minimum load (Hz)
+    pub freq_max: f64,
+    /// Transformer design
+    pub transformer: TransformerDesign,
+    /// Bridge switch (one of two identical)
+    pub switch: SelectedMOSFET,
+    /// Rectifier diode (one of `rectifier.diode_count()`)
+    pub rectifier_diode: SelectedDiode,
+    /// Output capacitor
+    pub output_capacitor: OutputCapacitor,
+    /// Input capacitor
+    pub input_capacitor: OutputCapacitor,
+    /// RMS primary (tank) current at resonance, full load (A)
+    pub i_pri_rms: f64,
+    /// Peak magnetizing current, the ZVS commutation current (A)
+    pub i_mag_peak: f64,
+    /// Minimum dead time to complete the ZVS transition (s)
+    pub min_dead_time: f64,
+    /// Total efficiency estimate
+    pub efficiency: f64,
+    /// Loss breakdown
+    pub losses: IsolatedLosses,
+}
+
+// ============================================================================
+// RESONANT TANK
+// ============================================================================
+
+/// Tank values and operating range for one turns ratio
+struct TankSolution {
+    q: f64,
+    lr: f64,
+    cr: f64,
+    lm: f64,
+    gain_min: f64,
+    gain_max: f64,
+    freq_min: f64,
+    freq_max: f64,
+}
+
+/// Peak FHA gain below resonance and the normalized frequency where it occurs
+fn peak_gain(ln: f64, q: f64) -> (f64, f64) {
+    (1..=400)
+        .map(|i| {
+            let fn_ = i as f64 / 400.0;
+            (llc_fha_gain(fn_, ln, q), fn_)
+        })
+        .fold((0.0, 1.0), |best, p| if p.0 > best.0 { p } else { best })
+}
+
+/// Normalized frequency on the inductive side where the gain equals `gain`
+///
+/// The FHA gain falls monotonically from the peak through resonance and on
+/// up to `fn_max`. Returns None if `gain` is out of that range.
+fn frequency_for_gain(ln: f64, q: f64, gain: f64, fn_max: f64) -> Option<f64> {
+    let (peak, fn_peak) = peak_gain(ln, q);
+    let (mut lo, mut hi) = if gain >= 1.0 {
+        (fn_peak, 1.0)
+    } else {
+        (1.0, fn_max)
+    };
+    if gain > peak || llc_fha_gain(hi, ln, q) > gain {
+        return None;
+    }
+    for _ in 0..60 {
+        let mid = 0.5 * (lo + hi);
+        if llc_fha_gain(mid, ln, q) > gain {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(0.5 * (lo + hi))
+}
+
+/// Size the tank for turns ratio `n` (Np/Ns) and rectifier input voltage `vo`
+fn solve_tank(req: &LLCRequirements, n: f64, vo: f64) -> Result<TankSolution, String> {
+    let ln = req.inductance_ratio;
+    let fr = req.resonant_freq;
+
+    // Half-bridge: Vo = M × (Vin/2) / n
+    let gain_min = 2.0 * n * vo / req.vin.max_v;
+    let gain_max = 2.0 * n * vo / req.vin.min_v * 1.1;
+
+    let gain_floor = ln / (ln + 1.0);
+    if gain_min <= gain_floor {
+        return Err(format!(
+            "No-load gain floor Ln/(Ln+1)={:.3} exceeds the {:.3} needed at Vin_max; lower the inductance ratio",
+            gain_floor, gain_min
+        ));
+    }
+
+    // Largest Q whose peak gain still reaches gain_max
+    if peak_gain(ln, 1e-3).0 < gain_max {
+        return Err(format!(
+            "Inductance ratio Ln={:.1} cannot reach gain {:.2}; lower it or narrow the input range",
+            ln, gain_max
+        ));
+    }
+    let (mut q_lo, mut q_hi) = (1e-3, 5.0);
+    for _ in 0..60 {
+        let mid = 0.5 * (q_lo + q_hi);
+        if peak_gain(ln, mid).0 >= gain_max {
+            q_lo = mid;
+        } else {
+            q_hi = mid;
+        }
+    }
+    // 10% margin keeps full load clear of the capacitive region
+    let q = 0.9 * q_lo;
+
+    // Rac = 8n²R/π² and Q = √(Lr/Cr)/Rac
+    let rac = 8.0 * n * n * (req.vout / req.iout_max) / (PI * PI);
+    let cr = 1.0 / (2.0 * PI * q * fr * rac);
+    let lr = 1.0 / ((2.0 * PI * fr).powi(2) * cr);
+    let lm = ln * lr;
+
+    let fn_max = req.max_switching_freq / fr;
+    let freq_min = frequency_for_gain(ln, q, gain_max, fn_max)
+        .ok_or_else(|| "Full-load gain not reachable below resonance".to_string())?
+        * fr;
+
+    // Light load needs the highest frequency to pull the gain down
+    let load = (req.iout_min / req.iout_max).max(0.0);
+    let freq_max = frequency_for_gain(ln, q * load, gain_min, fn_max).ok_or_else(|| {
+        format!(
+            "Cannot regulate minimum load below {}; raise the frequency limit or minimum load",
+            format_frequency(req.max_switching_freq)
+        )
+    })? * fr;
+
+    Ok(TankSolution {
+        q,
+        lr,
+        cr,
+        lm,
+        gain_min,
+        gain_max,
+        freq_min,
+        freq_max,
+    })
+}
+
+// ============================================================================
+// DESIGN ALGORITHM
+// ============================================================================
+
+/// Design a half-bridge LLC resonant converter from requirements with the built-in parts
+pub fn design_llc(req: &LLCRequirements) -> Result<LLCDesign, String> {
+    design_llc_with(req, builtin_parts())
+}
+
+/// Design a half-bridge LLC resonant converter choosing parts from `library`
+pub fn design_llc_with(req: &LLCRequirements, library: &PartLibrary) -> Result<LLCDesign, String> {
+    // Validate inputs
+    if req.output_power() <= 0.0 {
+        return Err("Output power must be positive".to_string());
+    }
+    if req.resonant_freq < 20e3 || req.resonant_freq > 1e6 {
+        return Err("Resonant frequency must be between 20kHz and 1MHz".to_string());
+    }
+    if req.max_switching_freq <= req.resonant_freq {
+        return Err("Maximum switching frequency must be above resonance".to_string());
+    }
+    if req.inductance_ratio < 1.0 {
+        return Err("Inductance ratio Lm/Lr must be at least 1".to_string());
+    }
+
+    let vin_min = req.vin.min_v;
+    let vin_max = req.vin.max_v;
+    let vin_nom = req.vin.nom_v;
+    let iout = req.iout_max;
+    let pout = req.output_power();
+
+    // Rectifier input voltage
+    let vo = req.vout + 0.5 * req.rectifier.diodes_in_path() as f64;
+
+    // Unity gain at nominal input sets the ideal turns ratio
+    let n_ideal = vin_nom / 2.0 / vo;
+    let tank = solve_tank(req, n_ideal, vo)?;
+
+    // Square-wave drive: ±Vin/2 for a full half period at the lowest frequency
+    let transformer_req = transformer_requirements(
+        TransformerTopology::HalfBridge,
+        vin_nom / 2.0,
+        vo,
+        req.rectifier.winding_rms(iout, 1.0),
+        tank.freq_min,
+        1.0,
+        req.isolation,
+        req.ambient_temp,
+        req.max_temp_rise,
+    );
+    let mut transformer = build_transformer(library, &transformer_req, req.preferred_core)?;
+
+    // The gain range is narrow, so trim the primary to the ideal ratio
+    // rather than accept the rounded-up secondary. Extra primary turns only
+    // lower the flux density.
+    let ns = (transformer.primary.turns as f64 * secondary_ratio(&transformer)).round();
+    let np = (n_ideal * ns).round().max(transformer.primary.turns as f64);
+    let scale = np / transformer.primary.turns as f64;
+    transformer.primary.turns = np as u32;
+    transformer.turns_ratio = np / ns;
+    transformer.b_peak /= scale;
+    transformer.core_loss /= scale.powf(transformer.material.steinmetz_beta);
+    transformer.primary_copper_loss *= scale;
+    transformer.magnetizing_inductance *= scale * scale;
+    transformer.leakage_inductance *= scale * scale;
+    transformer.total_loss =
+        transformer.core_loss + transformer.primary_copper_loss + transformer.secondary_copper_loss;
+
+    // Re-solve with the actual turns
+    let n = transformer.turns_ratio;
+    let tank = solve_tank(req, n, vo)?;
+    let fr = req.resonant_freq;
+
+    // Tank current: sinusoidal load component plus triangular magnetizing
+    let i_mag_peak = n * vo / (4.0 * fr * tank.lm);
+    let i_load_peak = PI * iout / (2.0 * n);
+    let i_pri_rms =
+        ((i_load_peak / 2.0_f64.sqrt()).powi(2) + (i_mag_peak / 3.0_f64.sqrt()).powi(2)).sqrt();
+    let i_pri_peak = i_load_peak + i_mag_peak;
+    let i_switch_rms = i_pri_rms / 2.0_f64.sqrt();
+
+    // Switches turn on at zero voltage and off at the magnetizing current
+    let switch = SelectedMOSFET::select(library, vin_max, i_switch_rms, i_pri_peak, fr)?.with_zvs();
+    let min_dead_time = 2.0 * switch.spec.coss * vin_max / i_mag_peak;
+
+    // Secondary rectifier carries half-sine pulses
+    let (rectifier_diode, rectifier_loss) =
+        select_rectifier(library, req.rectifier, vo, iout, PI * iout / 2.0)?;
+
+    // Capacitive output filter: full-wave rectified sine at 2 × fr.
+    // Charge above average per half period is ≈ 0.21 × Io / (2fr);
+    // half the ripple budget to capacitance, half to ESR
+    let ripple_rms = iout * (PI * PI / 8.0 - 1.0).sqrt();
+    let c_min = 0.21 * iout / (2.0 * fr * req.ripple_pp * 0.5);
+    let output_capacitor = OutputCapacitor {
+        capacitance: c_min * 1.5, // Add margin
+        voltage_rating: (req.vout * 1.5).ceil(),
+        max_esr: req.ripple_pp * 0.5 / (PI * iout / 2.0),
+        ripple_current_rms: ripple_rms,
+        cap_type: if req.vout <= 5.0 {
+            CapacitorType::Ceramic
+        } else {
+            CapacitorType::Polymer
+        },
+        parallel_count: 1,
+    };
+
+    let input_capacitor = input_capacitor(i_switch_rms, i_pri_peak, vin_min, vin_max, fr);
+
+    // Resonant inductor: integrated into the leakage where possible,
+    // otherwise an external part with 5mΩ winding and a core loss estimate
+    let series_inductance = (tank.lr - transformer.leakage_inductance).max(0.0);
+    let resonant_tank = if series_inductance > 0.0 {
+        i_pri_rms.powi(2) * 0.005 + 0.2
+    } else {
+        0.0
+    };
+
+    let losses = IsolatedLosses {
+        switch_conduction: 2.0 * switch.loss_conduction,
+        switch_switching: 2.0 * switch.loss_switching,
+        switch_gate: 2.0 * switch.loss_gate,
+        rectifier: rectifier_loss,
+        transformer_core: transformer.core_loss,
+        transformer_copper: transformer.primary_copper_loss + transformer.secondary_copper_loss,
+        inductor_core: 0.0,
+        inductor_copper: 0.0,
+        resonant_tank,
+        input_cap_esr: i_switch_rms.powi(2) * input_capacitor.max_esr * 0.1,
+        output_cap_esr: ripple_rms.powi(2) * output_capacitor.max_esr * 0.1,
+        total: 0.0,
+    }
+    .with_total();
+
+    let efficiency = pout / (pout + losses.total);
+
+    Ok(LLCDesign {
+        requirements: req.clone(),
+        turns_ratio: n,
+        quality_factor: tank.q,
+        resonant_inductance: tank.lr,
+        resonant_capacitance: tank.cr,
+        magnetizing_inductance: tank.lm,
+        series_inductance,
+        gain_min: tank.gain_min,
+        gain_max: tank.gain_max,
+        freq_min: tank.freq_min,
+        freq_max: tank.freq_max,
+        transformer,
+        switch,
+        rectifier_diode,
+        output_capacitor,
+        input_capacitor,
+        i_pri_rms,
+        i_mag_peak,
+        min_dead_time,
+        efficiency,
+        losses,
+    })
+}
+
+// ============================================================================
+// OPERATING POINT AND SMALL-SIGNAL MODEL
+// ============================================================================
+
+impl LLCDesign {
+    /// FHA tank gain at a switching frequency and load (fraction of Iout_max)
+    pub fn voltage_gain(&self, freq: f64, load_fraction: f64) -> f64 {
+        llc_fha_gain(
+            freq / self.requirements.resonant_freq,
+            self.requirements.inductance_ratio,
+            self.quality_factor * load_fraction,
+        )
+    }
+
+    /// Switching frequency that regulates `vin` at full load
+    pub fn operating_frequency(&self, vin: f64) -> Option<f64> {
+        let req = &self.requirements;
+        let vo = req.vout + 0.5 * req.rectifier.diodes_in_path() as f64;
+        let fr = req.resonant_freq;
+        frequency_for_gain(
+            req.inductance_ratio,
+            self.quality_factor,
+            2.0 * self.turns_ratio * vo / vin,
+            req.max_switching_freq / fr,
+        )
+        .map(|fn_| fn_ * fr)
+    }
+
+    /// Small-signal model at nominal input and full load
+    pub fn small_signal(&self, esr: f64) -> LLCSmallSignal {
+        let req = &self.requirements;
+        let fsw = self
+            .operating_frequency(req.vin.nom_v)
+            .unwrap_or(req.resonant_freq);
+        LLCSmallSignal::new(
+            req.vin.nom_v,
+            req.vout,
+            req.iout_max,
+            self.output_capacitor.capacitance,
+            esr,
+            fsw,
+        )
+        .with_tank(
+            self.resonant_inductance,
+            self.resonant_capacitance,
+            self.magnetizing_inductance,
+            self.turns_ratio,
+        )
+    }
+}
+
+// ============================================================================
+// DISPLAY IMPLEMENTATION
+// ============================================================================
+
+impl LLCDesign {
+    /// Generate a summary string
+    pub fn summary(&self) -> String {
+        let req = &self.requirements;
+        format!(
+            "LLC Resonant Converter Design\n\
+             =============================\n\
+             Input: {} to {}\n\
+             Output: {} @ {}\n\
+             Power: {:.1} W\n\
+             Rectifier: {:?}\n\
+             \n\
+             Resonant Tank (fr={}, Ln={:.1}, Q={:.2}):\n\
+             - Lr: {} (external {})\n\
+             - Cr: {}\n\
+             - Lm: {}\n\
+             Gain Range: {:.3}-{:.3}\n\
+             Frequency Range: {} to {}\n\
+             \n\
+             Turns Ratio (Np:Ns): {:.2}:1\n\
+             Transformer: {:?} (Np={})\n\
+             MOSFETs: 2 × {} (dead time ≥ {:.0}ns)\n\
+             Rectifiers: {} × {} (Vr={:.0}V)\n\
+             Output Cap: {} (ESR<{:.0}mΩ)\n\
+             \n\
+             Efficiency: {:.1}%\n\
+             Total Losses: {:.2} W",
+            format_voltage(req.vin.min_v),
+            format_voltage(req.vin.max_v),
+            format_voltage(req.vout),
+            format_current(req.iout_max),
+            req.output_power(),
+            req.rectifier,
+            format_frequency(req.resonant_freq),
+            req.inductance_ratio,
+            self.quality_factor,
+            format_inductance(self.resonant_inductance),
+            format_inductance(self.series_inductance),
+            format_capacitance(self.resonant_capacitance),
+            format_inductance(self.magnetizing_inductance),
+            self.gain_min,
+            self.gain_max,
+            format_frequency(self.freq_min),
+            format_frequency(self.freq_max),
+            self.turns_ratio,
+            self.transformer.core.core_type,
+            self.transformer.primary.turns,
+            self.switch.spec.part_number,
+            self.min_dead_time * 1e9,
+            req.rectifier.diode_count(),
+            self.rectifier_diode.spec.part_number,
+            self.rectifier_diode.vr_peak,
+            format_capacitance(self.output_capacitor.capacitance),
+            self.output_capacitor.max_esr * 1000.0,
+            self.efficiency * 100.0,
+            self.losses.total,
+        )
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_llc_basic_design() {
+        let req = LLCRequirements::default();
+        let result = design_llc(&req);
+        assert!(result.is_ok(), "Design should succeed: {:?}", result.err());
+
+        let design = result.unwrap();
+
+        // Tank resonates at fr
+        let fr =
+            1.0 / (2.0 * PI * (design.resonant_inductance * design.resonant_capacitance).sqrt());
+        assert!((fr - req.resonant_freq).abs() / req.resonant_freq < 1e-9);
+        assert!(
+            (design.magnetizing_inductance / design.resonant_inductance - req.inductance_ratio)
+                .abs()
+                < 1e-9
+        );
+
+        // Boost below resonance at low line, buck above at high line
+        assert!(design.freq_min < req.resonant_freq);
+        assert!(design.freq_max > design.freq_min);
+        assert!(design.freq_max <= req.max_switching_freq);
+
+        assert!(
+            design.efficiency > 0.85 && design.efficiency < 0.995,
+            "Efficiency {:.1}% out of range",
+            design.efficiency * 100.0
+        );
+    }
+
+    #[test]
+    fn test_llc_gain_covers_input_range() {
+        let design = design_llc(&LLCRequirements::default()).unwrap();
+
+        // Full load at Vin_min reaches the required gain at freq_min
+        assert!((design.voltage_gain(design.freq_min, 1.0) - design.gain_max).abs() < 1e-6);
+        // Peak gain at full load stays above the requirement
+        let (peak, _) = peak_gain(design.requirements.inductance_ratio, design.quality_factor);
+        assert!(peak > design.gain_max);
+
+        // Nominal input runs close to resonance
+        let f_nom = design
+            .operating_frequency(design.requirements.vin.nom_v)
+            .unwrap();
+        assert!((f_nom / design.requirements.resonant_freq - 1.0).abs() < 0.25);
+    }
+
+    #[test]
+    fn test_llc_small_signal_matches_tank() {
+        let design = design_llc(&LLCRequirements::default()).unwrap();
+        let model = design.small_signal(0.01);
+
+        assert!((model.resonant_freq() - design.requirements.resonant_freq).abs() < 1.0);
+        assert!((model.quality_factor() - design.quality_factor).abs() < 1e-9);
+        // Frequency control: output falls as frequency rises
+        assert!(model.frequency_gain() < 0.0);
+    }
+
+    #[test]
+    fn test_llc_rejects_unregulatable_range() {
+        // High line needs gain below the no-load floor Ln/(Ln+1)
+        let req = LLCRequirements {
+            vin: VoltageRange::with_nominal(370.0, 390.0, 600.0),
+            ..Default::default()
+        };
+        let err = design_llc(&req).unwrap_err();
+        assert!(err.contains("gain floor"), "{}", err);
+
+        // Light load cannot be pulled down within the frequency limit
+        let req = LLCRequirements {
+            max_switching_freq: 105e3,
+            ..Default::default()
+        };
+        let err = design_llc(&req).unwrap_err();
+        assert!(err.contains("minimum load"), "{}", err);
+    }
+}
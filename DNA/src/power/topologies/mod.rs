@@ -43,10 +43,24 @@
 //! ```
 
 pub mod buck_boost;
+pub mod cuk;
 pub mod flyback;
 pub mod forward;
+pub mod full_bridge;
+pub mod half_bridge;
+pub mod isolated;
+pub mod llc;
+pub mod push_pull;
+pub mod sepic;
 
 // Re-export main types
 pub use buck_boost::*;
+pub use cuk::*;
 pub use flyback::*;
 pub use forward::*;
+pub use full_bridge::*;
+pub use half_bridge::*;
+pub use isolated::*;
+pub use llc::*;
+pub use push_pull::*;
+pub use sepic::*;
@@ -0,0 +1,443 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: push_pull.rs | DNA/src/power/topologies/push_pull.rs
+//! PURPOSE: Push-pull converter topology design
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Complete push-pull converter design including:
+//! - Transformer design with center-tapped primary
+//! - Primary switch (MOSFET) selection
+//! - Secondary rectifier selection (center-tapped or bridge)
+//! - Output LC filter sizing
+//! - Efficiency estimation
+//!
+//! Two ground-referenced switches alternately drive the two halves of a
+//! center-tapped primary, so the core is excited in both directions and no
+//! reset winding is needed. Key properties:
+//! - Output voltage: Vout = Vin × (Ns/Np) × 2D, with D ≤ 0.5 per switch
+//! - Each switch blocks 2 × Vin (plus leakage ringing)
+//! - Output filter ripple at 2 × fsw
+//! - Any volt-second imbalance walks the core into saturation; use
+//!   current-mode control or a gapped core
+//!
+//! Best suited to low input voltages (12-48V) where the 2 × Vin switch
+//! stress is acceptable and ground-referenced gate drive is convenient.
+
+use serde::{Deserialize, Serialize};
+
+use crate::power::control::ForwardSmallSignal;
+use crate::power::library::{builtin_parts, PartLibrary};
+use crate::power::magnetics::{CoreType, IsolationClass, TransformerDesign, TransformerTopology};
+use crate::power::types::VoltageRange;
+use crate::power::{format_capacitance, format_current, format_inductance, format_voltage};
+
+use super::flyback::{OutputCapacitor, SelectedDiode, SelectedMOSFET};
+use super::forward::OutputInductor;
+use super::isolated::{
+    build_transformer, input_capacitor, output_filter, secondary_ratio, select_rectifier,
+    transformer_requirements, IsolatedLosses, SecondaryRectifier,
+};
+
+// ============================================================================
+// DESIGN REQUIREMENTS
+// ============================================================================
+
+/// Push-pull converter design requirements
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PushPullRequirements {
+    /// Input voltage range
+    pub vin: VoltageRange,
+    /// Output voltage (V)
+    pub vout: f64,
+    /// Maximum output current (A)
+    pub iout_max: f64,
+    /// Minimum output current (A)
+    pub iout_min: f64,
+    /// Maximum output ripple voltage (V peak-to-peak)
+    pub ripple_pp: f64,
+    /// Switching frequency of each switch (Hz)
+    pub switching_freq: f64,
+    /// Maximum duty cycle per switch (below 0.5 to leave dead time)
+    pub duty_cycle_max: f64,
+    /// Secondary rectifier configuration
+    pub rectifier: SecondaryRectifier,
+    /// Isolation requirement
+    pub isolation: IsolationClass,
+    /// Ambient temperature (°C)
+    pub ambient_temp: f64,
+    /// Maximum temperature rise (°C)
+    pub max_temp_rise: f64,
+    /// Target efficiency (0.0-1.0)
+    pub efficiency_target: f64,
+    /// Inductor current ripple ratio (ΔI / Iout)
+    pub inductor_ripple_ratio: f64,
+    /// Preferred core type (or None for automatic)
+    pub preferred_core: Option<CoreType>,
+}
+
+impl Default for PushPullRequirements {
+    fn default() -> Self {
+        Self {
+            vin: VoltageRange::range(18.0, 32.0), // 24V industrial bus
+            vout: 12.0,
+            iout_max: 5.0,
+            iout_min: 0.5,
+            ripple_pp: 0.12,
+            switching_freq: 100e3,
+            duty_cycle_max: 0.45,
+            rectifier: SecondaryRectifier::CenterTapped,
+            isolation: IsolationClass::Basic,
+            ambient_temp: 25.0,
+            max_temp_rise: 50.0,
+            efficiency_target: 0.88,
+            inductor_ripple_ratio: 0.3,
+            preferred_core: None,
+        }
+    }
+}
+
+impl PushPullRequirements {
+    /// Calculate output power
+    pub fn output_power(&self) -> f64 {
+        self.vout * self.iout_max
+    }
+
+    /// Calculate estimated input power
+    pub fn estimated_input_power(&self) -> f64 {
+        self.output_power() / self.efficiency_target
+    }
+}
+
+// ============================================================================
+// COMPLETE DESIGN
+// ============================================================================
+
+/// Complete push-pull converter design
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PushPullDesign {
+    /// Design requirements
+    pub requirements: PushPullRequirements,
+    /// Duty cycle per switch at Vin_min
+    pub duty_cycle_max: f64,
+    /// Duty cycle per switch at Vin_nom
+    pub duty_cycle_nom: f64,
+    /// Duty cycle per switch at Vin_max
+    pub duty_cycle_min: f64,
+    /// Transformer turns ratio (Np:Ns, per primary half)
+    pub turns_ratio: f64,
+    /// Transformer design
+    pub transformer: TransformerDesign,
+    /// Primary switch (one of two identical)
+    pub switch: SelectedMOSFET,
+    /// Rectifier diode (one of `rectifier.diode_count()`)
+    pub rectifier_diode: SelectedDiode,
+    /// Output inductor
+    pub output_inductor: OutputInductor,
+    /// Output capacitor
+    pub output_capacitor: OutputCapacitor,
+    /// Input capacitor
+    pub input_capacitor: OutputCapacitor,
+    /// Peak primary current (A)
+    pub i_pri_peak: f64,
+    /// RMS current per switch (A)
+    pub i_switch_rms: f64,
+    /// Total efficiency estimate
+    pub efficiency: f64,
+    /// Loss breakdown
+    pub losses: IsolatedLosses,
+}
+
+// ============================================================================
+// DESIGN ALGORITHM
+// ============================================================================
+
+/// Design a push-pull converter from requirements with the built-in parts
+pub fn design_push_pull(req: &PushPullRequirements) -> Result<PushPullDesign, String> {
+    design_push_pull_with(req, builtin_parts())
+}
+
+/// Design a push-pull converter choosing parts from `library`
+pub fn design_push_pull_with(
+    req: &PushPullRequirements,
+    library: &PartLibrary,
+) -> Result<PushPullDesign, String> {
+    // Validate inputs
+    if req.output_power() <= 0.0 {
+        return Err("Output power must be positive".to_string());
+    }
+    if req.switching_freq < 10e3 || req.switching_freq > 1e6 {
+        return Err("Switching frequency must be between 10kHz and 1MHz".to_string());
+    }
+    if req.duty_cycle_max <= 0.1 || req.duty_cycle_max >= 0.5 {
+        return Err("Push-pull duty cycle limit must be between 0.1 and 0.5".to_string());
+    }
+
+    let vin_min = req.vin.min_v;
+    let vin_max = req.vin.max_v;
+    let vin_nom = req.vin.nom_v;
+    let vout = req.vout;
+    let iout = req.iout_max;
+    let fsw = req.switching_freq;
+    let pout = req.output_power();
+
+    // Rectifier forward drop
+    let vd = 0.5 * req.rectifier.diodes_in_path() as f64;
+
+    // Size the transformer so the full duty range is reached at Vin_min
+    // Vout + Vd = Vin × (Ns/Np) × D_eff, where D_eff = 2D
+    let d_eff_target = 2.0 * req.duty_cycle_max * 0.95;
+    let transformer_req = transformer_requirements(
+        TransformerTopology::PushPull,
+        vin_min,
+        vout + vd,
+        req.rectifier.winding_rms(iout, d_eff_target),
+        fsw,
+        d_eff_target,
+        req.isolation,
+        req.ambient_temp,
+        req.max_temp_rise,
+    );
+    let transformer = build_transformer(library, &transformer_req, req.preferred_core)?;
+    let ns_np = secondary_ratio(&transformer);
+
+    // Per-switch duty cycle with the rounded turns
+    let duty_for = |vin: f64| (vout + vd) / (2.0 * vin * ns_np);
+    let duty_max = duty_for(vin_min);
+    let duty_nom = duty_for(vin_nom);
+    let duty_min = duty_for(vin_max);
+
+    if duty_max > req.duty_cycle_max {
+        return Err(format!(
+            "Cannot achieve required duty cycle. D_max={:.2} but need {:.2}",
+            req.duty_cycle_max, duty_max
+        ));
+    }
+
+    // Output filter sees a rectified square wave at 2 × fsw
+    let (output_inductor, output_capacitor) = output_filter(
+        vout,
+        iout,
+        req.inductor_ripple_ratio,
+        req.ripple_pp,
+        2.0 * duty_nom,
+        2.0 * fsw,
+    );
+
+    // Primary currents - each switch carries the reflected inductor current
+    // for its own half cycle
+    let i_pri_peak = output_inductor.current_peak * ns_np;
+    let i_switch_rms = output_inductor.current_rms * ns_np * duty_nom.sqrt();
+
+    // The off switch sees Vin from the supply plus Vin induced by the other
+    // half of the primary; leakage ringing is covered by the selection margin
+    let vds_peak = 2.0 * vin_max;
+    let switch = SelectedMOSFET::select(library, vds_peak, i_switch_rms, i_pri_peak, fsw)?;
+
+    // Secondary rectifier
+    let (rectifier_diode, rectifier_loss) = select_rectifier(
+        library,
+        req.rectifier,
+        vin_max * ns_np,
+        iout,
+        output_inductor.current_peak,
+    )?;
+
+    // Input current flows for D_eff of each half period at 2 × fsw
+    let d_eff_nom = 2.0 * duty_nom;
+    let i_in_ripple = iout * ns_np * (d_eff_nom * (1.0 - d_eff_nom)).sqrt();
+    let input_capacitor = input_capacitor(i_in_ripple, i_pri_peak, vin_min, vin_max, 2.0 * fsw);
+
+    let losses = IsolatedLosses {
+        switch_conduction: 2.0 * switch.loss_conduction,
+        switch_switching: 2.0 * switch.loss_switching,
+        switch_gate: 2.0 * switch.loss_gate,
+        rectifier: rectifier_loss,
+        transformer_core: transformer.core_loss,
+        transformer_copper: transformer.primary_copper_loss + transformer.secondary_copper_loss,
+        inductor_core: output_inductor.core_loss,
+        inductor_copper: output_inductor.copper_loss,
+        resonant_tank: 0.0,
+        input_cap_esr: i_in_ripple.powi(2) * input_capacitor.max_esr * 0.1,
+        output_cap_esr: output_capacitor.ripple_current_rms.powi(2)
+            * output_capacitor.max_esr
+            * 0.1,
+        total: 0.0,
+    }
+    .with_total();
+
+    let efficiency = pout / (pout + losses.total);
+
+    Ok(PushPullDesign {
+        requirements: req.clone(),
+        duty_cycle_max: duty_max,
+        duty_cycle_nom: duty_nom,
+        duty_cycle_min: duty_min,
+        turns_ratio: 1.0 / ns_np,
+        transformer,
+        switch,
+        rectifier_diode,
+        output_inductor,
+        output_capacitor,
+        input_capacitor,
+        i_pri_peak,
+        i_switch_rms,
+        efficiency,
+        losses,
+    })
+}
+
+// ============================================================================
+// SMALL-SIGNAL MODEL
+// ============================================================================
+
+impl PushPullDesign {
+    /// Small-signal model at nominal input
+    ///
+    /// Push-pull is buck-derived, so the forward model applies with the
+    /// effective duty D_eff = 2D; its DC gain is dVout/dD_eff = Vin × Ns/Np.
+    pub fn small_signal(&self, esr: f64) -> ForwardSmallSignal {
+        let req = &self.requirements;
+        ForwardSmallSignal::new(
+            req.vin.nom_v,
+            req.vout,
+            req.iout_max,
+            self.output_inductor.inductance,
+            self.output_capacitor.capacitance,
+            esr,
+            1.0 / self.turns_ratio,
+            req.switching_freq,
+        )
+    }
+}
+
+// ============================================================================
+// DISPLAY IMPLEMENTATION
+// ============================================================================
+
+impl PushPullDesign {
+    /// Generate a summary string
+    pub fn summary(&self) -> String {
+        let req = &self.requirements;
+        format!(
+            "Push-Pull Converter Design\n\
+             ==========================\n\
+             Input: {} to {}\n\
+             Output: {} @ {}\n\
+             Power: {:.1} W\n\
+             Frequency: {:.0} kHz (ripple at {:.0} kHz)\n\
+             Rectifier: {:?}\n\
+             \n\
+             Duty Cycle (per switch): {:.1}%-{:.1}%\n\
+             Turns Ratio (Np:Ns): {:.2}:1\n\
+             \n\
+             Transformer: {:?} ({} turns per primary half)\n\
+             MOSFETs: 2 × {} (Vds_pk={:.0}V)\n\
+             Rectifiers: {} × {} (Vr={:.0}V)\n\
+             \n\
+             Output Inductor: {} (ΔI={:.2}A)\n\
+             Output Cap: {} (ESR<{:.0}mΩ)\n\
+             \n\
+             Efficiency: {:.1}%\n\
+             Total Losses: {:.2} W",
+            format_voltage(req.vin.min_v),
+            format_voltage(req.vin.max_v),
+            format_voltage(req.vout),
+            format_current(req.iout_max),
+            req.output_power(),
+            req.switching_freq / 1000.0,
+            2.0 * req.switching_freq / 1000.0,
+            req.rectifier,
+            self.duty_cycle_min * 100.0,
+            self.duty_cycle_max * 100.0,
+            self.turns_ratio,
+            self.transformer.core.core_type,
+            self.transformer.primary.turns,
+            self.switch.spec.part_number,
+            self.switch.vds_peak,
+            req.rectifier.diode_count(),
+            self.rectifier_diode.spec.part_number,
+            self.rectifier_diode.vr_peak,
+            format_inductance(self.output_inductor.inductance),
+            self.output_inductor.ripple_current_pp,
+            format_capacitance(self.output_capacitor.capacitance),
+            self.output_capacitor.max_esr * 1000.0,
+            self.efficiency * 100.0,
+            self.losses.total,
+        )
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_pull_basic_design() {
+        let req = PushPullRequirements::default();
+        let result = design_push_pull(&req);
+        assert!(result.is_ok(), "Design should succeed: {:?}", result.err());
+
+        let design = result.unwrap();
+
+        // Per-switch duty stays below 50% across the input range
+        assert!(design.duty_cycle_max <= req.duty_cycle_max);
+        assert!(design.duty_cycle_min > 0.1);
+
+        // Switch blocks twice the maximum input
+        assert!((design.switch.vds_peak - 2.0 * req.vin.max_v).abs() < 1e-9);
+
+        assert!(
+            design.efficiency > 0.8 && design.efficiency < 0.98,
+            "Efficiency {:.1}% out of range",
+            design.efficiency * 100.0
+        );
+    }
+
+    #[test]
+    fn test_push_pull_volt_second_balance() {
+        let design = design_push_pull(&PushPullRequirements::default()).unwrap();
+        let req = &design.requirements;
+        let ns_np = 1.0 / design.turns_ratio;
+
+        // Vout + Vd = Vin × Ns/Np × 2D at every input voltage
+        let vout = req.vin.nom_v * ns_np * 2.0 * design.duty_cycle_nom - 0.5;
+        assert!((vout - req.vout).abs() < 1e-9);
+
+        // Model DC gain is dVout/dD_eff
+        let model = design.small_signal(0.01);
+        assert!((model.control_to_output().dc_gain - req.vin.nom_v * ns_np).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_push_pull_bridge_rectifier() {
+        let req = PushPullRequirements {
+            vin: VoltageRange::range(36.0, 60.0),
+            vout: 48.0,
+            iout_max: 2.0,
+            iout_min: 0.2,
+            ripple_pp: 0.48,
+            rectifier: SecondaryRectifier::FullBridge,
+            ..Default::default()
+        };
+        let design = design_push_pull(&req).unwrap();
+
+        // Bridge diodes block the winding voltage only
+        let ns_np = 1.0 / design.turns_ratio;
+        assert!((design.rectifier_diode.vr_peak - req.vin.max_v * ns_np).abs() < 1e-9);
+        assert!(design.losses.rectifier > 2.0 * 0.9 * design.rectifier_diode.loss_conduction);
+    }
+
+    #[test]
+    fn test_push_pull_rejects_invalid_duty() {
+        let req = PushPullRequirements {
+            duty_cycle_max: 0.6,
+            ..Default::default()
+        };
+        assert!(design_push_pull(&req).is_err());
+    }
+}
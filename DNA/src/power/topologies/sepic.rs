@@ -0,0 +1,582 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: sepic.rs | DNA/src/power/topologies/sepic.rs
+//! PURPOSE: SEPIC (single-ended primary-inductor converter) topology design
+//! MODIFIED: 2026-01-08
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Complete SEPIC converter design including:
+//! - Input and output inductor design (separate or coupled)
+//! - Coupling capacitor sizing
+//! - Switch and diode selection
+//! - Capacitor sizing
+//! - Efficiency estimation
+//!
+//! The SEPIC steps up or down with a non-inverted output and a continuous
+//! input current. The coupling capacitor Cs charges to Vin and transfers
+//! energy from L1 to L2; the switch is ground-referenced.
+//!
+//! Key equations:
+//! - Output voltage: Vout = Vin × D / (1-D)
+//! - Input inductor current: IL1 = Iout × D / (1-D)
+//! - Output inductor current: IL2 = Iout
+//! - Switch and diode see Vin + Vout
+//!
+//! Small-signal behaviour is that of a buck-boost with L1 ‖ L2, plus a
+//! Cs-L resonance that limits the crossover frequency.
+
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use crate::power::control::BuckBoostSmallSignal;
+use crate::power::library::{builtin_parts, PartLibrary};
+use crate::power::types::VoltageRange;
+use crate::power::{format_capacitance, format_current, format_inductance, format_voltage};
+
+use super::buck_boost::BuckBoostInductor;
+use super::flyback::{CapacitorType, OutputCapacitor, SelectedDiode, SelectedMOSFET};
+
+// ============================================================================
+// DESIGN REQUIREMENTS
+// ============================================================================
+
+/// SEPIC converter design requirements
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SEPICRequirements {
+    /// Input voltage range
+    pub vin: VoltageRange,
+    /// Output voltage (V)
+    pub vout: f64,
+    /// Maximum output current (A)
+    pub iout_max: f64,
+    /// Minimum output current (A)
+    pub iout_min: f64,
+    /// Maximum output ripple voltage (V peak-to-peak)
+    pub ripple_pp: f64,
+    /// Switching frequency (Hz)
+    pub switching_freq: f64,
+    /// Wind L1 and L2 on one core (1:1 coupled inductor)
+    pub coupled_inductor: bool,
+    /// Coupling capacitor ripple (fraction of Vin_min)
+    pub coupling_cap_ripple: f64,
+    /// Ambient temperature (°C)
+    pub ambient_temp: f64,
+    /// Maximum temperature rise (°C)
+    pub max_temp_rise: f64,
+    /// Target efficiency (0.0-1.0)
+    pub efficiency_target: f64,
+    /// Inductor current ripple ratio (ΔI / Iin)
+    pub inductor_ripple_ratio: f64,
+}
+
+impl Default for SEPICRequirements {
+    fn default() -> Self {
+        Self {
+            vin: VoltageRange::range(9.0, 16.0), // Automotive 12V nominal
+            vout: 12.0,                          // Regulated 12V across the range
+            iout_max: 1.0,
+            iout_min: 0.1,
+            ripple_pp: 0.12, // 1% of output
+            switching_freq: 300e3,
+            coupled_inductor: false,
+            coupling_cap_ripple: 0.05,
+            ambient_temp: 25.0,
+            max_temp_rise: 50.0,
+            efficiency_target: 0.88,
+            inductor_ripple_ratio: 0.4,
+        }
+    }
+}
+
+impl SEPICRequirements {
+    /// Calculate output power
+    pub fn output_power(&self) -> f64 {
+        self.vout * self.iout_max
+    }
+
+    /// Calculate estimated input power
+    pub fn estimated_input_power(&self) -> f64 {
+        self.output_power() / self.efficiency_target
+    }
+
+    /// Calculate duty cycle for given input voltage
+    /// D = Vout / (Vin + Vout)
+    pub fn duty_cycle_for_vin(&self, vin: f64) -> f64 {
+        self.vout / (vin + self.vout)
+    }
+}
+
+// ============================================================================
+// COMPLETE DESIGN
+// ============================================================================
+
+/// Complete SEPIC converter design
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SEPICDesign {
+    /// Design requirements
+    pub requirements: SEPICRequirements,
+    /// Duty cycle at Vin_min (maximum D)
+    pub duty_cycle_max: f64,
+    /// Duty cycle at Vin_nom
+    pub duty_cycle_nom: f64,
+    /// Duty cycle at Vin_max (minimum D)
+    pub duty_cycle_min: f64,
+    /// Main switch (MOSFET)
+    pub main_switch: SelectedMOSFET,
+    /// Output diode
+    pub diode: SelectedDiode,
+    /// Input inductor L1 (one winding when coupled)
+    pub input_inductor: BuckBoostInductor,
+    /// Output inductor L2 (other winding when coupled)
+    pub output_inductor: BuckBoostInductor,
+    /// Series coupling capacitor Cs
+    pub coupling_capacitor: OutputCapacitor,
+    /// Output capacitor
+    pub output_capacitor: OutputCapacitor,
+    /// Input capacitor
+    pub input_capacitor: OutputCapacitor,
+    /// Total efficiency estimate
+    pub efficiency: f64,
+    /// Loss breakdown
+    pub losses: SEPICLosses,
+}
+
+/// Detailed loss breakdown for SEPIC and Ćuk converters
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SEPICLosses {
+    /// MOSFET conduction loss
+    pub mosfet_conduction: f64,
+    /// MOSFET switching loss
+    pub mosfet_switching: f64,
+    /// MOSFET gate drive loss
+    pub mosfet_gate: f64,
+    /// Diode conduction loss
+    pub diode_conduction: f64,
+    /// Inductor core loss (both inductors)
+    pub inductor_core: f64,
+    /// Inductor copper loss (both inductors)
+    pub inductor_copper: f64,
+    /// Coupling capacitor ESR loss
+    pub coupling_cap_esr: f64,
+    /// Input capacitor ESR loss
+    pub input_cap_esr: f64,
+    /// Output capacitor ESR loss
+    pub output_cap_esr: f64,
+    /// Total losses
+    pub total: f64,
+}
+
+// ============================================================================
+// SHARED DESIGN STEPS
+// ============================================================================
+
+/// Inductor pair for the two-inductor (SEPIC/Ćuk) topologies
+///
+/// Both inductors see Vin during the on-time, so L = Vin_min × D / (ΔI × fsw)
+/// with ΔI set from the input current. A 1:1 coupled pair shares one core
+/// and splits the ripple, halving the inductance for the same ΔI.
+pub(super) fn inductor_pair(
+    vin_min: f64,
+    duty_max: f64,
+    il1_avg: f64,
+    il2_avg: f64,
+    ripple_ratio: f64,
+    fsw: f64,
+    coupled: bool,
+) -> (BuckBoostInductor, BuckBoostInductor) {
+    let delta_i = il1_avg.max(il2_avg) * ripple_ratio;
+    let l_single = vin_min * duty_max / (delta_i * fsw);
+    let inductance = if coupled { l_single / 2.0 } else { l_single };
+
+    // Estimate losses - one core when coupled
+    let dcr = 0.01; // 10mΩ estimate
+    let core_loss = if coupled { 0.075 } else { 0.15 };
+
+    let make = |current_avg: f64| {
+        let current_rms = (current_avg.powi(2) + (delta_i / (2.0 * 3.0_f64.sqrt())).powi(2)).sqrt();
+        BuckBoostInductor {
+            inductance,
+            current_avg,
+            current_peak: current_avg + delta_i / 2.0,
+            current_rms,
+            ripple_current_pp: delta_i,
+            dcr,
+            core_loss,
+            copper_loss: current_rms.powi(2) * dcr,
+        }
+    };
+
+    (make(il1_avg), make(il2_avg))
+}
+
+/// Effective buck-boost inductance of an L1/L2 pair
+///
+/// Separate inductors act as L1 ‖ L2; a tightly coupled 1:1 pair acts as
+/// a single winding's inductance.
+pub(super) fn equivalent_inductance(l1: f64, l2: f64, coupled: bool) -> f64 {
+    if coupled {
+        l1
+    } else {
+        l1 * l2 / (l1 + l2)
+    }
+}
+
+/// Cs-L resonance of the two-inductor topologies
+///
+/// f_res ≈ 1 / (2π × √(Cs × (L1×D'² + L2×D²)))
+pub(super) fn coupling_resonance(l1: f64, l2: f64, cs: f64, duty: f64) -> f64 {
+    let d_prime = 1.0 - duty;
+    1.0 / (2.0 * PI * (cs * (l1 * d_prime * d_prime + l2 * duty * duty)).sqrt())
+}
+
+// ============================================================================
+// DESIGN ALGORITHM
+// ============================================================================
+
+/// Design a SEPIC converter from requirements with the built-in parts
+pub fn design_sepic(req: &SEPICRequirements) -> Result<SEPICDesign, String> {
+    design_sepic_with(req, builtin_parts())
+}
+
+/// Design a SEPIC converter choosing parts from `library`
+pub fn design_sepic_with(
+    req: &SEPICRequirements,
+    library: &PartLibrary,
+) -> Result<SEPICDesign, String> {
+    // Validate inputs
+    if req.output_power() <= 0.0 {
+        return Err("Output power must be positive".to_string());
+    }
+    if req.switching_freq < 10e3 || req.switching_freq > 5e6 {
+        return Err("Switching frequency must be between 10kHz and 5MHz".to_string());
+    }
+    if req.coupling_cap_ripple <= 0.0 {
+        return Err("Coupling capacitor ripple must be positive".to_string());
+    }
+
+    let vin_min = req.vin.min_v;
+    let vin_max = req.vin.max_v;
+    let vin_nom = req.vin.nom_v;
+    let vout = req.vout;
+    let iout = req.iout_max;
+    let fsw = req.switching_freq;
+    let pout = req.output_power();
+
+    // Forward voltage drops
+    let vd = 0.5; // Diode forward drop
+
+    // Calculate duty cycles
+    // D = (Vout + Vd) / (Vin + Vout + Vd)
+    let duty_max = (vout + vd) / (vin_min + vout + vd);
+    let duty_nom = (vout + vd) / (vin_nom + vout + vd);
+    let duty_min = (vout + vd) / (vin_max + vout + vd);
+
+    if duty_max > 0.85 {
+        return Err(format!(
+            "Duty cycle too high ({:.1}%). Consider higher input voltage or lower output.",
+            duty_max * 100.0
+        ));
+    }
+
+    // Average inductor currents at Vin_min
+    // IL1 = Iin = Iout × D / (1-D), IL2 = Iout
+    let il1_avg = iout * duty_max / (1.0 - duty_max);
+    let il2_avg = iout;
+
+    let (input_inductor, output_inductor) = inductor_pair(
+        vin_min,
+        duty_max,
+        il1_avg,
+        il2_avg,
+        req.inductor_ripple_ratio,
+        fsw,
+        req.coupled_inductor,
+    );
+
+    // Switch carries IL1 + IL2 during the on-time and blocks Vin + Vout
+    let i_sw_peak = input_inductor.current_peak + output_inductor.current_peak;
+    let vds_max = vin_max + vout + vd;
+    let id_rms = (il1_avg + il2_avg) * duty_max.sqrt();
+    let main_switch = SelectedMOSFET::select(library, vds_max, id_rms, i_sw_peak, fsw)?;
+
+    // Diode carries IL1 + IL2 during the off-time, Iout on average
+    let diode = SelectedDiode::select(library, vds_max, iout, i_sw_peak)?;
+
+    // Coupling capacitor: charges to Vin, carries IL1 off and -IL2 on
+    // ΔVcs = Iout × D / (Cs × fsw)
+    let dv_cs = req.coupling_cap_ripple * vin_min;
+    let cs_ripple_rms = iout * (duty_max / (1.0 - duty_max)).sqrt();
+    let coupling_capacitor = OutputCapacitor {
+        capacitance: iout * duty_max / (dv_cs * fsw),
+        voltage_rating: (vin_max * 1.25).ceil(),
+        max_esr: dv_cs * 0.5 / i_sw_peak,
+        ripple_current_rms: cs_ripple_rms,
+        cap_type: CapacitorType::Ceramic,
+        parallel_count: 1,
+    };
+
+    // Output capacitor sizing - pulsed current as in a boost
+    // Ripple from capacitance: ΔVout = Iout × D / (C × fsw)
+    let c_out_min = iout * duty_nom / (req.ripple_pp * fsw);
+    let c_out_ripple_rms = iout * (duty_nom / (1.0 - duty_nom)).sqrt();
+    let max_esr = req.ripple_pp * 0.5 / i_sw_peak;
+
+    let output_capacitor = OutputCapacitor {
+        capacitance: c_out_min * 2.0, // Add margin
+        voltage_rating: (vout * 1.5).ceil(),
+        max_esr,
+        ripple_current_rms: c_out_ripple_rms,
+        cap_type: CapacitorType::Ceramic,
+        parallel_count: 1,
+    };
+
+    // Input capacitor sizing - continuous input current through L1,
+    // so the capacitor only carries the inductor ripple
+    let iin_rms = input_inductor.ripple_current_pp / (2.0 * 3.0_f64.sqrt());
+    let c_in_min = input_inductor.ripple_current_pp / (8.0 * fsw * 0.01 * vin_min);
+    let cin_esr = 0.01 * vin_min / input_inductor.ripple_current_pp;
+
+    let input_capacitor = OutputCapacitor {
+        capacitance: c_in_min * 2.0,
+        voltage_rating: (vin_max * 1.25).ceil(),
+        max_esr: cin_esr,
+        ripple_current_rms: iin_rms,
+        cap_type: CapacitorType::Ceramic,
+        parallel_count: 1,
+    };
+
+    let mut losses = SEPICLosses {
+        mosfet_conduction: main_switch.loss_conduction,
+        mosfet_switching: main_switch.loss_switching,
+        mosfet_gate: main_switch.loss_gate,
+        diode_conduction: diode.loss_conduction,
+        inductor_core: input_inductor.core_loss + output_inductor.core_loss,
+        inductor_copper: input_inductor.copper_loss + output_inductor.copper_loss,
+        coupling_cap_esr: cs_ripple_rms.powi(2) * coupling_capacitor.max_esr * 0.1,
+        input_cap_esr: iin_rms.powi(2) * cin_esr * 0.1,
+        output_cap_esr: c_out_ripple_rms.powi(2) * max_esr * 0.1,
+        total: 0.0,
+    };
+    losses.total = losses.mosfet_conduction
+        + losses.mosfet_switching
+        + losses.mosfet_gate
+        + losses.diode_conduction
+        + losses.inductor_core
+        + losses.inductor_copper
+        + losses.coupling_cap_esr
+        + losses.input_cap_esr
+        + losses.output_cap_esr;
+
+    let efficiency = pout / (pout + losses.total);
+
+    Ok(SEPICDesign {
+        requirements: req.clone(),
+        duty_cycle_max: duty_max,
+        duty_cycle_nom: duty_nom,
+        duty_cycle_min: duty_min,
+        main_switch,
+        diode,
+        input_inductor,
+        output_inductor,
+        coupling_capacitor,
+        output_capacitor,
+        input_capacitor,
+        efficiency,
+        losses,
+    })
+}
+
+// ============================================================================
+// SMALL-SIGNAL MODEL
+// ============================================================================
+
+impl SEPICDesign {
+    /// Equivalent buck-boost inductance of L1/L2
+    pub fn equivalent_inductance(&self) -> f64 {
+        equivalent_inductance(
+            self.input_inductor.inductance,
+            self.output_inductor.inductance,
+            self.requirements.coupled_inductor,
+        )
+    }
+
+    /// Small-signal model at nominal input
+    ///
+    /// Below the Cs-L resonance a SEPIC behaves as a buck-boost with the
+    /// equivalent inductance, including its RHP zero.
+    pub fn small_signal(&self, esr: f64) -> BuckBoostSmallSignal {
+        let req = &self.requirements;
+        BuckBoostSmallSignal::new(
+            req.vin.nom_v,
+            req.vout,
+            req.iout_max,
+            self.equivalent_inductance(),
+            self.output_capacitor.capacitance,
+            esr,
+            req.switching_freq,
+        )
+    }
+
+    /// Coupling capacitor resonance at nominal duty (Hz)
+    pub fn coupling_resonance_freq(&self) -> f64 {
+        coupling_resonance(
+            self.input_inductor.inductance,
+            self.output_inductor.inductance,
+            self.coupling_capacitor.capacitance,
+            self.duty_cycle_nom,
+        )
+    }
+
+    /// Highest sensible crossover: below the RHP zero, the Cs-L resonance
+    /// and a tenth of the switching frequency
+    pub fn max_crossover(&self, esr: f64) -> f64 {
+        self.small_signal(esr)
+            .max_crossover()
+            .min(self.coupling_resonance_freq() / 3.0)
+            .min(self.requirements.switching_freq / 10.0)
+    }
+}
+
+// ============================================================================
+// DISPLAY IMPLEMENTATION
+// ============================================================================
+
+impl SEPICDesign {
+    /// Generate a summary string
+    pub fn summary(&self) -> String {
+        let req = &self.requirements;
+        format!(
+            "SEPIC Converter Design\n\
+             ======================\n\
+             Input: {} to {}\n\
+             Output: {} @ {}\n\
+             Power: {:.1} W\n\
+             Frequency: {:.0} kHz\n\
+             Inductors: {}\n\
+             \n\
+             Duty Cycle: {:.1}%-{:.1}%\n\
+             \n\
+             MOSFET: {} (Vds_pk={:.0}V)\n\
+             Diode: {} (Vr={:.0}V)\n\
+             \n\
+             L1: {} (IL_avg={:.2}A, ΔI={:.2}A)\n\
+             L2: {} (IL_avg={:.2}A)\n\
+             Coupling Cap: {} ({:.0}V)\n\
+             Output Cap: {} (ESR<{:.0}mΩ)\n\
+             Input Cap: {}\n\
+             \n\
+             Efficiency: {:.1}%\n\
+             Total Losses: {:.2} W",
+            format_voltage(req.vin.min_v),
+            format_voltage(req.vin.max_v),
+            format_voltage(req.vout),
+            format_current(req.iout_max),
+            req.output_power(),
+            req.switching_freq / 1000.0,
+            if req.coupled_inductor {
+                "1:1 coupled"
+            } else {
+                "separate"
+            },
+            self.duty_cycle_min * 100.0,
+            self.duty_cycle_max * 100.0,
+            self.main_switch.spec.part_number,
+            self.main_switch.vds_peak,
+            self.diode.spec.part_number,
+            self.diode.vr_peak,
+            format_inductance(self.input_inductor.inductance),
+            self.input_inductor.current_avg,
+            self.input_inductor.ripple_current_pp,
+            format_inductance(self.output_inductor.inductance),
+            self.output_inductor.current_avg,
+            format_capacitance(self.coupling_capacitor.capacitance),
+            self.coupling_capacitor.voltage_rating,
+            format_capacitance(self.output_capacitor.capacitance),
+            self.output_capacitor.max_esr * 1000.0,
+            format_capacitance(self.input_capacitor.capacitance),
+            self.efficiency * 100.0,
+            self.losses.total,
+        )
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sepic_basic_design() {
+        let req = SEPICRequirements::default();
+        let result = design_sepic(&req);
+        assert!(result.is_ok(), "Design should succeed: {:?}", result.err());
+
+        let design = result.unwrap();
+
+        // 9-16V to 12V spans both sides of D = 0.5
+        assert!(design.duty_cycle_max > 0.5);
+        assert!(design.duty_cycle_min < 0.5);
+
+        // Non-inverting: switch and diode see Vin + Vout
+        let expected_voltage = req.vin.max_v + req.vout + 0.5;
+        assert!((design.main_switch.vds_peak - expected_voltage).abs() < 1e-9);
+        assert!((design.diode.vr_peak - expected_voltage).abs() < 1e-9);
+
+        assert!(
+            design.efficiency > 0.75 && design.efficiency < 0.98,
+            "Efficiency {:.1}% out of range",
+            design.efficiency * 100.0
+        );
+    }
+
+    #[test]
+    fn test_sepic_inductor_currents() {
+        let req = SEPICRequirements::default();
+        let design = design_sepic(&req).unwrap();
+
+        // Power balance: IL1 × Vin_min = IL2 × (Vout + Vd)
+        let d = design.duty_cycle_max;
+        assert!((design.input_inductor.current_avg - req.iout_max * d / (1.0 - d)).abs() < 1e-9);
+        assert!((design.output_inductor.current_avg - req.iout_max).abs() < 1e-9);
+
+        // Coupling capacitor ripple at Vin_min equals the allowed fraction
+        let dv = req.iout_max * d / (design.coupling_capacitor.capacitance * req.switching_freq);
+        assert!((dv - req.coupling_cap_ripple * req.vin.min_v).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sepic_coupled_inductor() {
+        let req = SEPICRequirements::default();
+        let separate = design_sepic(&req).unwrap();
+        let coupled = design_sepic(&SEPICRequirements {
+            coupled_inductor: true,
+            ..req
+        })
+        .unwrap();
+
+        // Same ripple with half the inductance per winding
+        assert!(
+            (coupled.input_inductor.inductance * 2.0 - separate.input_inductor.inductance).abs()
+                < 1e-12
+        );
+        // Both behave as the same buck-boost inductance
+        assert!((coupled.equivalent_inductance() - separate.equivalent_inductance()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sepic_small_signal() {
+        let design = design_sepic(&SEPICRequirements::default()).unwrap();
+        let model = design.small_signal(0.005);
+
+        // Buck-boost behaviour with an RHP zero
+        let tf = model.control_to_output();
+        assert!(tf.zeros.iter().any(|z| z.re > 0.0));
+
+        let fc_max = design.max_crossover(0.005);
+        assert!(fc_max <= model.rhp_zero_freq() / 5.0);
+        assert!(fc_max <= design.coupling_resonance_freq() / 3.0);
+    }
+}
@@ -7,6 +7,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::magnetics::InductorDesign;
+
 // ============================================================================
 // COMMON TYPES
 // ============================================================================
@@ -86,6 +88,12 @@ pub enum TopologyType {
     LDO,
     Flyback,
     Forward,
+    SEPIC,
+    Cuk,
+    PushPull,
+    HalfBridge,
+    FullBridge,
+    LLC,
 }
 
 // ============================================================================
@@ -155,8 +163,11 @@ pub struct EfficiencyBreakdown {
     pub switching_loss_w: f64,
     /// Diode conduction loss (W)
     pub diode_loss_w: f64,
-    /// Inductor DCR loss (W)
+    /// Inductor copper loss, DCR plus ripple AC resistance (W)
     pub inductor_dcr_loss_w: f64,
+    /// Inductor core loss (W)
+    #[serde(default)]
+    pub inductor_core_loss_w: f64,
     /// Capacitor ESR loss (W)
     pub capacitor_esr_loss_w: f64,
     /// Quiescent/controller loss (W)
@@ -174,6 +185,7 @@ impl EfficiencyBreakdown {
             + self.switching_loss_w
             + self.diode_loss_w
             + self.inductor_dcr_loss_w
+            + self.inductor_core_loss_w
             + self.capacitor_esr_loss_w
             + self.quiescent_loss_w
     }
@@ -295,6 +307,8 @@ pub struct BuckDesign {
     // Selected components
     /// Main inductor
     pub inductor: SelectedComponent,
+    /// Magnetics design for the inductor (None if no core in the database fits)
+    pub inductor_design: Option<InductorDesign>,
     /// Output capacitor
     pub output_capacitor: SelectedComponent,
     /// Input capacitor
@@ -376,6 +390,8 @@ pub struct BoostDesign {
     // Selected components
     /// Main inductor
     pub inductor: SelectedComponent,
+    /// Magnetics design for the inductor (None if no core in the database fits)
+    pub inductor_design: Option<InductorDesign>,
     /// Output capacitor
     pub output_capacitor: SelectedComponent,
     /// Input capacitor
@@ -546,4 +562,23 @@ mod tests {
         assert_eq!(req.vout, 3.3);
         assert_eq!(req.dropout_voltage, 0.3);
     }
+
+    #[test]
+    fn test_breakdown_without_core_loss_deserializes() {
+        // Saved before inductor core loss was split out
+        let json = r#"{
+            "total_efficiency": 0.9,
+            "conduction_loss_w": 0.2,
+            "switching_loss_w": 0.1,
+            "diode_loss_w": 0.0,
+            "inductor_dcr_loss_w": 0.3,
+            "capacitor_esr_loss_w": 0.05,
+            "quiescent_loss_w": 0.01,
+            "output_power_w": 10.0,
+            "input_power_w": 11.1
+        }"#;
+        let breakdown: EfficiencyBreakdown = serde_json::from_str(json).unwrap();
+        assert_eq!(breakdown.inductor_core_loss_w, 0.0);
+        assert_eq!(breakdown.inductor_dcr_loss_w, 0.3);
+    }
 }
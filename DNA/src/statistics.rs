@@ -12,10 +12,12 @@
 //! - Diversity indices
 //! - Generation tracking
 //! - Energy statistics
+//! - Sample summaries and histograms (Monte Carlo results)
 //!
 //! ## Traceability
 //! - Used by: too.foo (status display), future simulations
-//! - Tests: test_population_metrics, test_diversity_score
+//! - Used by: physics/electromagnetics/lumped/sweep.rs (tolerance analysis)
+//! - Tests: test_population_metrics, test_diversity_score, test_sample_statistics
 
 use crate::{BoidArena, BoidRole};
 
@@ -225,6 +227,98 @@ impl MetricsHistory {
     }
 }
 
+/// Summary of a set of f64 samples
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SampleStatistics {
+    pub count: usize,
+    pub mean: f64,
+    /// Sample standard deviation (n - 1)
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl SampleStatistics {
+    /// Summarize `samples`; None when empty
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let count = samples.len();
+        let mean = samples.iter().sum::<f64>() / count as f64;
+        let var = if count > 1 {
+            samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count - 1) as f64
+        } else {
+            0.0
+        };
+        Some(Self {
+            count,
+            mean,
+            std_dev: var.sqrt(),
+            min: samples.iter().copied().fold(f64::INFINITY, f64::min),
+            max: samples.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+
+    /// Value at percentile `p` (0-100), linear interpolation between ranks
+    pub fn percentile(samples: &[f64], p: f64) -> Option<f64> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let rank = (p / 100.0).clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+        let lo = rank.floor() as usize;
+        let hi = rank.ceil() as usize;
+        Some(sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64))
+    }
+}
+
+/// Equal-width histogram over [min, max]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    pub min: f64,
+    pub max: f64,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Bin `samples` into `bins` equal-width bins spanning their range
+    pub fn from_samples(samples: &[f64], bins: usize) -> Self {
+        let bins = bins.max(1);
+        let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if samples.is_empty() {
+            return Self {
+                min: 0.0,
+                max: 0.0,
+                counts: vec![0; bins],
+            };
+        }
+
+        let mut counts = vec![0; bins];
+        let width = (max - min) / bins as f64;
+        for &x in samples {
+            let bin = if width > 0.0 {
+                (((x - min) / width) as usize).min(bins - 1)
+            } else {
+                0
+            };
+            counts[bin] += 1;
+        }
+        Self { min, max, counts }
+    }
+
+    pub fn bin_width(&self) -> f64 {
+        (self.max - self.min) / self.counts.len() as f64
+    }
+
+    /// Center of bin `i`
+    pub fn bin_center(&self, i: usize) -> f64 {
+        self.min + (i as f64 + 0.5) * self.bin_width()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -346,4 +440,21 @@ mod tests {
         // Latest should be the last one
         assert_eq!(history.latest().unwrap().total_alive, 100 + 14 * 10);
     }
+
+    #[test]
+    fn test_sample_statistics() {
+        let samples = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let stats = SampleStatistics::from_samples(&samples).unwrap();
+        assert_eq!(stats.count, 5);
+        assert!((stats.mean - 3.0).abs() < 1e-12);
+        assert!((stats.std_dev - 2.5f64.sqrt()).abs() < 1e-12);
+        assert_eq!((stats.min, stats.max), (1.0, 5.0));
+        assert_eq!(SampleStatistics::percentile(&samples, 50.0), Some(3.0));
+        assert_eq!(SampleStatistics::percentile(&samples, 75.0), Some(4.0));
+        assert!(SampleStatistics::from_samples(&[]).is_none());
+
+        let hist = Histogram::from_samples(&samples, 2);
+        assert_eq!(hist.counts, vec![2, 3]);
+        assert!((hist.bin_center(0) - 2.0).abs() < 1e-12);
+    }
 }
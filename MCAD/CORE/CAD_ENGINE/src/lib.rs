@@ -69,23 +69,41 @@ pub use dna::cad::primitives::{
 
 // Mesh triangulation and picking
 pub use dna::cad::intersect::{pick_face, ray_triangle_intersect, FaceHit};
-pub use dna::cad::mesh::{solid_to_mesh, solid_to_pickable_mesh, PickableMesh, TriangleMesh};
+pub use dna::cad::mesh::{
+    solid_to_mesh, solid_to_mesh_with_tolerance, solid_to_pickable_mesh, PickableMesh,
+    TriangleMesh, DEFAULT_CHORD_TOLERANCE,
+};
+
+// Freeform curves and surfaces
+pub use dna::cad::nurbs::{NurbsCurve, NurbsError, NurbsSurface};
 
 // Boolean operations
 pub use dna::cad::boolean::{difference, intersection, union, BooleanError, BooleanOp};
 
+// Edge blends
+pub use dna::cad::fillet::{chamfer_edges, fillet_edges, FilletError};
+
+// Shell, offset and draft
+pub use dna::cad::offset::{draft_faces, offset_face, shell_solid, OffsetError};
+
 // Sketcher
-pub use dna::cad::constraints::{Constraint, DimensionalConstraint, GeometricConstraint};
+pub use dna::cad::constraints::{
+    Constraint, ConstraintEquation, DimensionalConstraint, GeometricConstraint,
+};
 pub use dna::cad::extrude::{extrude_sketch, ExtrudeError, ExtrudeParams};
 pub use dna::cad::pattern::{circular_pattern, linear_pattern};
 pub use dna::cad::revolve::{revolve_sketch, RevolveAxis, RevolveError, RevolveParams};
 pub use dna::cad::sketch::{
     circumcenter, orient2d, ConstraintId, Point2, Sketch, SketchCoordinateFrame, SketchEntity,
-    SketchEntityId, SketchPlane, SketchPoint, SketchPointId,
+    SketchEntityId, SketchPlane, SketchPoint, SketchPointId, SketchSlot,
 };
 pub use dna::cad::solver::{
     ConstraintAnalysis, ConstraintSolver, DofStatus, SolverConfig, SolverResult,
 };
+pub use dna::cad::sweep::{loft_sketches, sweep_sketch, SweepError, SweepPath};
+
+// STEP import
+pub use dna::export::step::{step_to_solids, StepImportError};
 
 // ─────────────────────────────────────────────────────────────────────────────────
 // HIGH-LEVEL API
@@ -298,129 +316,10 @@ pub fn solid_to_stl(solid: &Solid, name: &str) -> Vec<u8> {
 
 /// Convert a Solid to STEP AP242 format
 ///
-/// This exports the B-Rep topology to ISO 10303-21 STEP format.
-/// Currently supports planar faces (boxes, cylinders with flat caps).
+/// This exports the B-Rep topology to ISO 10303-21 STEP format, with lines,
+/// circles and B-splines for edges and analytic or B-spline surfaces for faces.
 pub fn solid_to_step(solid: &Solid, name: &str) -> String {
-    use dna::export::step::StepWriter;
-
-    let mut writer = StepWriter::new();
-
-    // Create points for all vertices
-    let point_ids: Vec<_> = solid
-        .vertices
-        .iter()
-        .map(|v| writer.add_point(None, v.point.x as f64, v.point.y as f64, v.point.z as f64))
-        .collect();
-
-    // Create vertex_point entities
-    let vertex_ids: Vec<_> = point_ids
-        .iter()
-        .map(|&pid| writer.add_vertex_point(None, pid))
-        .collect();
-
-    // Create edge_curve entities for each edge
-    let edge_ids: Vec<_> = solid
-        .edges
-        .iter()
-        .map(|edge| {
-            let start_pt = point_ids[edge.start.0 as usize];
-
-            // Create line geometry for linear edges
-            let start_vertex = &solid.vertices[edge.start.0 as usize];
-            let end_vertex = &solid.vertices[edge.end.0 as usize];
-            let dir = end_vertex.point.to_vec3() - start_vertex.point.to_vec3();
-            let length = dir.length() as f64;
-
-            if length > 0.0 {
-                let dir = dir.normalize();
-                let dir_id = writer.add_direction(None, dir.x as f64, dir.y as f64, dir.z as f64);
-                let vec_id = writer.add_vector(None, dir_id, length);
-                let line_id = writer.add_line(None, start_pt, vec_id);
-
-                writer.add_edge_curve(
-                    None,
-                    vertex_ids[edge.start.0 as usize],
-                    vertex_ids[edge.end.0 as usize],
-                    line_id,
-                    true,
-                )
-            } else {
-                // Degenerate edge - create placeholder
-                let dir_id = writer.add_direction(None, 1.0, 0.0, 0.0);
-                let vec_id = writer.add_vector(None, dir_id, 0.001);
-                let line_id = writer.add_line(None, start_pt, vec_id);
-
-                writer.add_edge_curve(
-                    None,
-                    vertex_ids[edge.start.0 as usize],
-                    vertex_ids[edge.end.0 as usize],
-                    line_id,
-                    true,
-                )
-            }
-        })
-        .collect();
-
-    // Create faces
-    let face_ids: Vec<_> = solid
-        .faces
-        .iter()
-        .map(|face| {
-            // Create oriented edges for the face loop
-            let oriented_edges: Vec<_> = face
-                .outer_loop
-                .edges
-                .iter()
-                .map(|&edge_id| writer.add_oriented_edge(None, edge_ids[edge_id.0 as usize], true))
-                .collect();
-
-            // Create face bound (loop)
-            let face_bound = writer.add_face_bound(None, oriented_edges, true);
-
-            // For planar faces, create a plane
-            // Simplified: use first three vertices to define plane
-            let verts: Vec<&Vertex> = face
-                .outer_loop
-                .edges
-                .iter()
-                .take(3)
-                .filter_map(|&edge_id| solid.edge(edge_id))
-                .filter_map(|e| solid.vertex(e.start))
-                .collect();
-
-            let plane_position = if verts.len() >= 3 {
-                let p0 = verts[0].point.to_vec3();
-                let p1 = verts[1].point.to_vec3();
-                let p2 = verts[2].point.to_vec3();
-
-                let v1 = p1 - p0;
-                let v2 = p2 - p0;
-                let normal = v1.cross(v2).normalize();
-
-                let origin_id = writer.add_point(None, p0.x as f64, p0.y as f64, p0.z as f64);
-                let normal_id =
-                    writer.add_direction(None, normal.x as f64, normal.y as f64, normal.z as f64);
-                writer.add_axis2_placement_3d(None, origin_id, Some(normal_id), None)
-            } else {
-                // Fallback to origin
-                let origin_id = writer.add_point(None, 0.0, 0.0, 0.0);
-                let normal_id = writer.add_direction(None, 0.0, 0.0, 1.0);
-                writer.add_axis2_placement_3d(None, origin_id, Some(normal_id), None)
-            };
-
-            let plane_id = writer.add_plane(None, plane_position);
-
-            writer.add_advanced_face(None, plane_id, vec![face_bound])
-        })
-        .collect();
-
-    // Create closed shell
-    let shell_id = writer.add_closed_shell(None, face_ids);
-
-    // Create manifold solid brep
-    writer.add_manifold_solid_brep(Some(name), shell_id);
-
-    writer.to_string()
+    dna::export::step::solid_to_step_file(solid, name)
 }
 
 // ─────────────────────────────────────────────────────────────────────────────────
@@ -5,7 +5,8 @@
 //! LAYER: MCAD (L1 Bubble)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
-use cad_engine::{SketchEntityId, SketchPointId};
+use crate::sketch_edit::SketchEditKind;
+use cad_engine::{Constraint, Sketch, SketchEntity, SketchEntityId, SketchPoint, SketchPointId};
 
 /// Commands for undo/redo system
 #[derive(Clone, Debug)]
@@ -22,6 +23,42 @@ pub enum SketchCommand {
         point_ids: Vec<SketchPointId>,
         prev_states: Vec<bool>,
     },
+    /// Rewrote existing geometry (trim, extend, offset, fillet)
+    EditGeometry {
+        kind: SketchEditKind,
+        before: SketchSnapshot,
+        after: SketchSnapshot,
+    },
+}
+
+/// Copy of a sketch's points, entities and constraints
+///
+/// Edits that move or rewire existing points cannot be reversed by deleting
+/// what they added, so they record the whole sketch on both sides instead.
+#[derive(Clone, Debug)]
+pub struct SketchSnapshot {
+    pub points: Vec<SketchPoint>,
+    pub entities: Vec<SketchEntity>,
+    pub constraints: Vec<Constraint>,
+}
+
+impl SketchSnapshot {
+    /// Record the current state of a sketch and its constraints
+    pub fn capture(sketch: &Sketch, constraints: &[Constraint]) -> Self {
+        Self {
+            points: sketch.points.clone(),
+            entities: sketch.entities.clone(),
+            constraints: constraints.to_vec(),
+        }
+    }
+
+    /// Put a sketch and its constraints back to the recorded state
+    pub fn restore(&self, sketch: &mut Sketch, constraints: &mut Vec<Constraint>) {
+        sketch.points = self.points.clone();
+        sketch.entities = self.entities.clone();
+        sketch.is_solved = false;
+        *constraints = self.constraints.clone();
+    }
 }
 
 /// Command history for undo/redo operations
@@ -300,4 +337,50 @@ mod tests {
         assert_eq!(history.undo_count(), 2);
         assert_eq!(history.redo_count(), 1);
     }
+
+    #[test]
+    fn test_edit_geometry_snapshots_round_trip() {
+        use cad_engine::{GeometricConstraint, Point2, SketchPlane};
+
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let a = sketch.add_point(Point2::new(0.0, 0.0));
+        let b = sketch.add_point(Point2::new(10.0, 0.0));
+        sketch.add_entity(SketchEntity::Line {
+            id: SketchEntityId(0),
+            start: a,
+            end: b,
+        });
+        let mut constraints = vec![Constraint::Geometric(GeometricConstraint::Horizontal {
+            line: SketchEntityId(0),
+        })];
+        let before = SketchSnapshot::capture(&sketch, &constraints);
+
+        sketch.point_mut(b).unwrap().position = Point2::new(5.0, 0.0);
+        constraints.clear();
+        let after = SketchSnapshot::capture(&sketch, &constraints);
+
+        let mut history = CommandHistory::new();
+        history.push(SketchCommand::EditGeometry {
+            kind: SketchEditKind::Trim,
+            before,
+            after,
+        });
+
+        let cmd = history.pop_undo().unwrap();
+        if let SketchCommand::EditGeometry { before, .. } = &cmd {
+            before.restore(&mut sketch, &mut constraints);
+        }
+        history.push_redo(cmd);
+        assert_eq!(sketch.point(b).unwrap().position.x, 10.0);
+        assert_eq!(constraints.len(), 1);
+
+        let cmd = history.pop_redo().unwrap();
+        if let SketchCommand::EditGeometry { after, .. } = &cmd {
+            after.restore(&mut sketch, &mut constraints);
+        }
+        history.push_undo(cmd);
+        assert_eq!(sketch.point(b).unwrap().position.x, 5.0);
+        assert!(constraints.is_empty());
+        assert!(history.can_undo());
+    }
 }
@@ -10,16 +10,22 @@
 pub mod command_history;
 pub mod renderer;
 pub mod selection3d;
+pub mod sketch_edit;
 pub mod snap;
 
-pub use command_history::{CommandHistory, SketchCommand};
+pub use command_history::{CommandHistory, SketchCommand, SketchSnapshot};
+pub use sketch_edit::{
+    extend_line, fillet_corner, offset_chain, offset_distance_through, trim_line, SketchEditError,
+    SketchEditKind,
+};
 pub use snap::{
-    entity_midpoint, find_nearest_intersection, find_point_at_position, line_line_intersection,
-    perpendicular_foot, point_to_segment_distance, snap_position, snap_position_enhanced,
-    snap_to_grid, SnapResult, SnapType,
+    entity_midpoint, find_nearest_intersection, find_point_at_position, foot_parameter,
+    line_line_intersection, line_line_parameters, perpendicular_foot, point_to_segment_distance,
+    snap_position, snap_position_enhanced, snap_to_grid, SnapResult, SnapType,
 };
 
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::f32::consts::PI;
 use wasm_bindgen::prelude::*;
 use web_sys::{
@@ -28,11 +34,12 @@ use web_sys::{
 };
 
 use cad_engine::{
-    circular_pattern, circumcenter, difference, extrude_sketch, intersection, is_manifold,
-    linear_pattern, make_box, make_cone, make_cylinder, make_sphere, revolve_sketch, solid_to_step,
-    solid_to_stl, surface_area, union, volume, Constraint, ConstraintAnalysis, ConstraintSolver,
-    DofStatus, ExtrudeParams, GeometricConstraint, Point2, Point3, RevolveAxis, RevolveParams,
-    Sketch, SketchCoordinateFrame, SketchEntity, SketchEntityId, SketchPlane, SketchPointId, Solid,
+    chamfer_edges, circular_pattern, circumcenter, difference, draft_faces, extrude_sketch,
+    fillet_edges, intersection, is_manifold, linear_pattern, make_box, make_cone, make_cylinder,
+    make_sphere, revolve_sketch, shell_solid, solid_to_step, solid_to_stl, step_to_solids,
+    surface_area, union, volume, Constraint, ConstraintAnalysis, ConstraintSolver, DofStatus,
+    ExtrudeParams, GeometricConstraint, Plane, Point2, Point3, RevolveAxis, RevolveParams, Sketch,
+    SketchCoordinateFrame, SketchEntity, SketchEntityId, SketchPlane, SketchPointId, Solid,
     Vector3,
 };
 
@@ -60,6 +67,10 @@ enum SketchTool {
     Rectangle,
     Circle,
     Point,
+    Trim,
+    Extend,
+    Offset,
+    Fillet,
 }
 
 /// Status message type for visual feedback
@@ -152,6 +163,14 @@ fn is_entity_construction(sketch: &Sketch, entity: &SketchEntity) -> bool {
             .point(*point)
             .map(|p| p.is_construction)
             .unwrap_or(false),
+        SketchEntity::Spline { .. } | SketchEntity::Ellipse { .. } => {
+            entity.point_ids().iter().any(|id| {
+                sketch
+                    .point(*id)
+                    .map(|p| p.is_construction)
+                    .unwrap_or(false)
+            })
+        }
     }
 }
 
@@ -331,6 +350,19 @@ fn init_ui() -> Result<(), JsValue> {
     )?;
     set_view_closure.forget();
 
+    // Export STEP import to JS (file contents are read on the JS side)
+    let import_step_closure = Closure::wrap(Box::new(|text: String| {
+        if let Err(e) = import_step(&text) {
+            web_sys::console::error_1(&format!("STEP import failed: {:?}", e).into());
+        }
+    }) as Box<dyn Fn(String)>);
+    js_sys::Reflect::set(
+        &window,
+        &JsValue::from_str("importStep"),
+        import_step_closure.as_ref(),
+    )?;
+    import_step_closure.forget();
+
     // Export mode toggle to JS
     let toggle_mode_closure = Closure::wrap(Box::new(|| {
         toggle_mode();
@@ -416,6 +448,56 @@ fn init_ui() -> Result<(), JsValue> {
     )?;
     clear_pattern_closure.forget();
 
+    // Export edge blend functions to JS
+    let fillet_closure = Closure::wrap(Box::new(|| {
+        if let Err(e) = blend_selected_edges("fillet") {
+            web_sys::console::error_1(&format!("Fillet failed: {:?}", e).into());
+        }
+    }) as Box<dyn Fn()>);
+    js_sys::Reflect::set(
+        &window,
+        &JsValue::from_str("filletSelectedEdges"),
+        fillet_closure.as_ref(),
+    )?;
+    fillet_closure.forget();
+
+    let chamfer_closure = Closure::wrap(Box::new(|| {
+        if let Err(e) = blend_selected_edges("chamfer") {
+            web_sys::console::error_1(&format!("Chamfer failed: {:?}", e).into());
+        }
+    }) as Box<dyn Fn()>);
+    js_sys::Reflect::set(
+        &window,
+        &JsValue::from_str("chamferSelectedEdges"),
+        chamfer_closure.as_ref(),
+    )?;
+    chamfer_closure.forget();
+
+    // Export shell and draft functions to JS
+    let shell_closure = Closure::wrap(Box::new(|| {
+        if let Err(e) = modify_selected_faces("shell") {
+            web_sys::console::error_1(&format!("Shell failed: {:?}", e).into());
+        }
+    }) as Box<dyn Fn()>);
+    js_sys::Reflect::set(
+        &window,
+        &JsValue::from_str("shellSelectedFaces"),
+        shell_closure.as_ref(),
+    )?;
+    shell_closure.forget();
+
+    let draft_closure = Closure::wrap(Box::new(|| {
+        if let Err(e) = modify_selected_faces("draft") {
+            web_sys::console::error_1(&format!("Draft failed: {:?}", e).into());
+        }
+    }) as Box<dyn Fn()>);
+    js_sys::Reflect::set(
+        &window,
+        &JsValue::from_str("draftSelectedFaces"),
+        draft_closure.as_ref(),
+    )?;
+    draft_closure.forget();
+
     // Export constraint application to JS
     let constraint_closure = Closure::wrap(Box::new(|constraint_type: String| {
         if let Err(e) = apply_sketch_constraint(&constraint_type) {
@@ -845,6 +927,26 @@ fn setup_viewport_events(document: &Document) -> Result<(), JsValue> {
                         s.temp_points.clear();
                         true
                     }
+                    "t" | "T" => {
+                        s.sketch_tool = SketchTool::Trim;
+                        s.temp_points.clear();
+                        true
+                    }
+                    "e" | "E" if !ctrl => {
+                        s.sketch_tool = SketchTool::Extend;
+                        s.temp_points.clear();
+                        true
+                    }
+                    "o" | "O" => {
+                        s.sketch_tool = SketchTool::Offset;
+                        s.temp_points.clear();
+                        true
+                    }
+                    "f" | "F" => {
+                        s.sketch_tool = SketchTool::Fillet;
+                        s.temp_points.clear();
+                        true
+                    }
                     "x" | "X" => {
                         s.construction_mode = !s.construction_mode;
                         let msg = if s.construction_mode {
@@ -896,6 +998,8 @@ fn setup_viewport_events(document: &Document) -> Result<(), JsValue> {
                                         SketchEntity::Circle { id, .. } => *id,
                                         SketchEntity::Arc { id, .. } => *id,
                                         SketchEntity::Point { id, .. } => *id,
+                                        SketchEntity::Spline { id, .. } => *id,
+                                        SketchEntity::Ellipse { id, .. } => *id,
                                     };
                                     !to_delete.contains(&id)
                                 });
@@ -1294,6 +1398,8 @@ fn draw_sketch_2d(
             SketchEntity::Circle { id, .. } => *id,
             SketchEntity::Arc { id, .. } => *id,
             SketchEntity::Point { id, .. } => *id,
+            SketchEntity::Spline { id, .. } => *id,
+            SketchEntity::Ellipse { id, .. } => *id,
         };
 
         // Check if this is construction geometry
@@ -1379,13 +1485,44 @@ fn draw_sketch_2d(
                     ctx.stroke();
                 }
             }
+            SketchEntity::Spline { .. } | SketchEntity::Ellipse { .. } => {
+                if let Some(polyline) = sketch.entity_polyline(entity_id) {
+                    ctx.begin_path();
+                    for (i, p) in polyline.iter().enumerate() {
+                        let (x, y) = to_screen(*p);
+                        if i == 0 {
+                            ctx.move_to(x, y);
+                        } else {
+                            ctx.line_to(x, y);
+                        }
+                    }
+                    ctx.stroke();
+                }
+            }
             _ => {}
         }
     }
 
-    // Draw points
-    ctx.set_fill_style(&JsValue::from_str("#ffffff"));
+    // Draw points, coloured by remaining DOF: white = fully defined,
+    // blue = still free, red = touched by a redundant/conflicting constraint
+    let analysis = ConstraintAnalysis::analyze(sketch, constraints);
+    let over_constrained: HashSet<SketchPointId> = analysis
+        .redundant
+        .iter()
+        .chain(&analysis.conflicting)
+        .filter_map(|id| constraints.get(id.0 as usize))
+        .flat_map(|c| c.equations(sketch))
+        .flat_map(|eq| eq.jacobian.into_iter().map(|(id, _)| id))
+        .collect();
     for point in &sketch.points {
+        let color = if over_constrained.contains(&point.id) {
+            "#ff4d4d"
+        } else if analysis.dof_of_point(point.id) > 0 {
+            "#4da6ff"
+        } else {
+            "#ffffff"
+        };
+        ctx.set_fill_style(&JsValue::from_str(color));
         let (px, py) = to_screen(point.position);
         ctx.begin_path();
         ctx.arc(px, py, 4.0, 0.0, 2.0 * std::f64::consts::PI)?;
@@ -1750,6 +1887,10 @@ fn update_status_bar() {
                         SketchTool::Rectangle => "Rect [R]",
                         SketchTool::Circle => "Circle [C]",
                         SketchTool::Point => "Point [P]",
+                        SketchTool::Trim => "Trim [T]",
+                        SketchTool::Extend => "Extend [E]",
+                        SketchTool::Offset => "Offset [O]",
+                        SketchTool::Fillet => "Fillet [F]",
                     }
                 } else {
                     "-"
@@ -1840,6 +1981,41 @@ fn export_step() -> Result<(), JsValue> {
     })
 }
 
+fn import_step(text: &str) -> Result<(), JsValue> {
+    show_status("Importing STEP...", StatusType::Info);
+
+    let mut solids = match step_to_solids(text) {
+        Ok(solids) => solids,
+        Err(e) => {
+            show_status(&format!("STEP import failed: {}", e), StatusType::Error);
+            return Err(JsValue::from_str(&e.to_string()));
+        }
+    };
+    let count = solids.len();
+
+    STATE.with(|state| {
+        let mut s = state.borrow_mut();
+        s.solid = Some(solids.remove(0));
+        s.pattern_instances = None;
+        s.selection3d.clear();
+    });
+
+    if count > 1 {
+        show_status(
+            &format!("Imported first of {} solids", count),
+            StatusType::Warning,
+        );
+    } else {
+        show_status("STEP import complete", StatusType::Success);
+    }
+
+    let window = web_sys::window().ok_or("No window")?;
+    let document = window.document().ok_or("No document")?;
+    display_properties(&document)?;
+    render()?;
+    Ok(())
+}
+
 fn download_binary_file(filename: &str, content: &[u8]) -> Result<(), JsValue> {
     let window = web_sys::window().ok_or("No window")?;
     let document = window.document().ok_or("No document")?;
@@ -1966,6 +2142,123 @@ fn clear_pattern() -> Result<(), JsValue> {
     Ok(())
 }
 
+/// Fillet or chamfer the edges picked in edge selection mode
+fn blend_selected_edges(operation: &str) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("No window")?;
+    let document = window.document().ok_or("No document")?;
+
+    let size = match operation {
+        "fillet" => get_input_value(&document, "fillet-radius")?,
+        "chamfer" => get_input_value(&document, "chamfer-distance")?,
+        _ => return Err(JsValue::from_str("Unknown blend operation")),
+    } as f32;
+
+    STATE.with(|state| {
+        let mut s = state.borrow_mut();
+        let solid = s
+            .solid
+            .as_ref()
+            .ok_or_else(|| JsValue::from_str("No solid to modify"))?;
+
+        let edges = s.selection3d.selected_edges.clone();
+        if edges.is_empty() {
+            show_status(
+                "Select edges first (edge selection mode)",
+                StatusType::Warning,
+            );
+            return Ok(());
+        }
+
+        let result = match operation {
+            "fillet" => fillet_edges(solid, &edges, size),
+            _ => chamfer_edges(solid, &edges, size),
+        };
+
+        match result {
+            Ok(new_solid) => {
+                s.solid = Some(new_solid);
+                s.pattern_instances = None; // Clear patterns on topology-changing ops
+                s.selection3d.clear_selection();
+                drop(s);
+
+                show_status(
+                    &format!("{} applied to {} edge(s)", operation, edges.len()),
+                    StatusType::Success,
+                );
+                display_properties(&document)?;
+                render()?;
+                Ok(())
+            }
+            Err(e) => {
+                show_status(&format!("{} failed: {:?}", operation, e), StatusType::Error);
+                Err(JsValue::from_str(&format!("{} failed: {:?}", operation, e)))
+            }
+        }
+    })
+}
+
+/// Shell (removing the picked faces) or draft the faces picked in face
+/// selection mode
+///
+/// Draft pulls along +Z with the neutral plane through the bottom of the part.
+fn modify_selected_faces(operation: &str) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("No window")?;
+    let document = window.document().ok_or("No document")?;
+
+    let value = match operation {
+        "shell" => get_input_value(&document, "shell-thickness")?,
+        "draft" => get_input_value(&document, "draft-angle")?,
+        _ => return Err(JsValue::from_str("Unknown face operation")),
+    } as f32;
+
+    STATE.with(|state| {
+        let mut s = state.borrow_mut();
+        let faces = s.selection3d.selected_faces.clone();
+        let solid = s
+            .solid
+            .as_mut()
+            .ok_or_else(|| JsValue::from_str("No solid to modify"))?;
+
+        if faces.is_empty() && operation == "draft" {
+            show_status(
+                "Select faces first (face selection mode)",
+                StatusType::Warning,
+            );
+            return Ok(());
+        }
+
+        let result = match operation {
+            "shell" => shell_solid(solid, &faces, value),
+            _ => {
+                let bottom = Point3::new(0.0, 0.0, solid.bounding_box().min.z);
+                let neutral = Plane::new(bottom, Vector3::Z).ok_or("Invalid neutral plane")?;
+                draft_faces(solid, &faces, neutral, value)
+            }
+        };
+
+        match result {
+            Ok(new_solid) => {
+                s.solid = Some(new_solid);
+                s.pattern_instances = None; // Clear patterns on topology-changing ops
+                s.selection3d.clear_selection();
+                drop(s);
+
+                show_status(
+                    &format!("{} applied to {} face(s)", operation, faces.len()),
+                    StatusType::Success,
+                );
+                display_properties(&document)?;
+                render()?;
+                Ok(())
+            }
+            Err(e) => {
+                show_status(&format!("{} failed: {:?}", operation, e), StatusType::Error);
+                Err(JsValue::from_str(&format!("{} failed: {:?}", operation, e)))
+            }
+        }
+    })
+}
+
 fn export_stl() -> Result<(), JsValue> {
     STATE.with(|state| {
         let state = state.borrow();
@@ -2106,6 +2399,10 @@ fn set_sketch_tool(tool: &str) {
             "circle" => SketchTool::Circle,
             "point" => SketchTool::Point,
             "select" => SketchTool::Select,
+            "trim" => SketchTool::Trim,
+            "extend" => SketchTool::Extend,
+            "offset" => SketchTool::Offset,
+            "fillet" => SketchTool::Fillet,
             _ => SketchTool::Select,
         };
         s.temp_points.clear(); // Reset tool state
@@ -2505,6 +2802,52 @@ fn handle_sketch_click(screen_x: f64, screen_y: f64) -> Result<(), JsValue> {
                         );
                     }
                 }
+                SketchTool::Trim | SketchTool::Extend => {
+                    let s = &mut *s;
+                    let sketch = s.current_sketch.as_mut().unwrap();
+                    let Some(line) = find_entity_at_point(sketch, raw_pos, 10.0) else {
+                        show_status("Click a line", StatusType::Warning);
+                        return Ok(());
+                    };
+                    let result = if tool == SketchTool::Trim {
+                        trim_line(sketch, &mut s.sketch_constraints, line, raw_pos)
+                    } else {
+                        extend_line(sketch, &mut s.sketch_constraints, line, raw_pos)
+                    };
+                    record_sketch_edit(&mut s.command_history, result);
+                }
+                SketchTool::Offset => {
+                    // First click picks the chain, second sets the distance
+                    let s = &mut *s;
+                    let sketch = s.current_sketch.as_mut().unwrap();
+                    let Some(pick) = s.temp_points.first().copied() else {
+                        if find_entity_at_point(sketch, raw_pos, 10.0).is_some() {
+                            s.temp_points.push(raw_pos);
+                            show_status(
+                                "Offset: click a point to offset through",
+                                StatusType::Info,
+                            );
+                        } else {
+                            show_status("Click a line, arc or circle", StatusType::Warning);
+                        }
+                        return Ok(());
+                    };
+                    s.temp_points.clear();
+                    let Some(entity) = find_entity_at_point(sketch, pick, 10.0) else {
+                        return Ok(());
+                    };
+                    let result = offset_distance_through(sketch, entity, pos)
+                        .and_then(|d| offset_chain(sketch, &mut s.sketch_constraints, entity, d));
+                    record_sketch_edit(&mut s.command_history, result);
+                }
+                SketchTool::Fillet => {
+                    let radius = sketch_fillet_radius();
+                    let s = &mut *s;
+                    let sketch = s.current_sketch.as_mut().unwrap();
+                    let result =
+                        fillet_corner(sketch, &mut s.sketch_constraints, raw_pos, radius, 10.0);
+                    record_sketch_edit(&mut s.command_history, result);
+                }
             }
         }
 
@@ -2514,6 +2857,42 @@ fn handle_sketch_click(screen_x: f64, screen_y: f64) -> Result<(), JsValue> {
     })
 }
 
+/// Push a finished sketch edit onto the undo stack, or report why it failed
+fn record_sketch_edit(
+    history: &mut CommandHistory,
+    result: Result<SketchCommand, SketchEditError>,
+) {
+    match result {
+        Ok(cmd) => {
+            if let SketchCommand::EditGeometry { kind, .. } = &cmd {
+                show_status(&format!("Applied {}", kind.label()), StatusType::Success);
+            }
+            history.push(cmd);
+        }
+        Err(SketchEditError::NoIntersection) => {
+            show_status("Nothing to trim or extend to", StatusType::Warning);
+        }
+        Err(SketchEditError::NoCorner) => {
+            show_status("Click where two lines meet", StatusType::Warning);
+        }
+        Err(SketchEditError::RadiusTooLarge { max }) => {
+            show_status(
+                &format!("Fillet radius too large (max {:.2})", max),
+                StatusType::Error,
+            );
+        }
+        Err(e) => show_status(&format!("Edit failed: {:?}", e), StatusType::Error),
+    }
+}
+
+/// Radius for sketch fillets from the sidebar input
+fn sketch_fillet_radius() -> f32 {
+    web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| get_input_value(&d, "sketch-fillet-radius").ok())
+        .unwrap_or(5.0) as f32
+}
+
 /// Find the closest entity to a point within tolerance
 fn find_entity_at_point(sketch: &Sketch, pos: Point2, tolerance: f32) -> Option<SketchEntityId> {
     let mut best_id: Option<SketchEntityId> = None;
@@ -2551,6 +2930,15 @@ fn find_entity_at_point(sketch: &Sketch, pos: Point2, tolerance: f32) -> Option<
                     f32::MAX
                 }
             }
+            SketchEntity::Spline { id, .. } | SketchEntity::Ellipse { id, .. } => sketch
+                .entity_polyline(*id)
+                .map(|polyline| {
+                    polyline
+                        .windows(2)
+                        .map(|w| point_to_segment_distance(pos, w[0], w[1]))
+                        .fold(f32::MAX, f32::min)
+                })
+                .unwrap_or(f32::MAX),
         };
 
         if dist < best_dist {
@@ -2560,6 +2948,8 @@ fn find_entity_at_point(sketch: &Sketch, pos: Point2, tolerance: f32) -> Option<
                 SketchEntity::Circle { id, .. } => *id,
                 SketchEntity::Arc { id, .. } => *id,
                 SketchEntity::Point { id, .. } => *id,
+                SketchEntity::Spline { id, .. } => *id,
+                SketchEntity::Ellipse { id, .. } => *id,
             });
         }
     }
@@ -2618,6 +3008,14 @@ fn undo_action() -> Result<(), JsValue> {
                         show_status("Undo: toggled construction", StatusType::Info);
                     }
                 }
+                SketchCommand::EditGeometry { kind, before, .. } => {
+                    let s = &mut *s;
+                    if let Some(ref mut sketch) = s.current_sketch {
+                        before.restore(sketch, &mut s.sketch_constraints);
+                        s.selected_entities.clear();
+                        show_status(&format!("Undo: {}", kind.label()), StatusType::Info);
+                    }
+                }
             }
             s.command_history.push_redo(cmd);
         }
@@ -2639,16 +3037,27 @@ fn redo_action() -> Result<(), JsValue> {
         }
 
         // Note: Redo is more complex because we need to recreate the geometry
-        // For now, we just show a message and skip the redo
-        // A full implementation would need to store the actual point/entity data
+        // Edits carry a snapshot of the result; other commands only record
+        // IDs, so they are skipped with a message
         if let Some(cmd) = s.command_history.pop_redo() {
-            show_status(
-                "Redo not yet implemented for this action",
-                StatusType::Warning,
-            );
+            if let SketchCommand::EditGeometry { kind, after, .. } = &cmd {
+                let s = &mut *s;
+                if let Some(ref mut sketch) = s.current_sketch {
+                    after.restore(sketch, &mut s.sketch_constraints);
+                    s.selected_entities.clear();
+                    show_status(&format!("Redo: {}", kind.label()), StatusType::Info);
+                }
+            } else {
+                show_status(
+                    "Redo not yet implemented for this action",
+                    StatusType::Warning,
+                );
+            }
             s.command_history.push_undo(cmd);
         }
 
+        drop(s);
+        let _ = render();
         Ok(())
     })
 }
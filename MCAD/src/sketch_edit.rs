@@ -0,0 +1,1327 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: sketch_edit.rs | MCAD/src/sketch_edit.rs
+//! PURPOSE: Sketch editing operations - trim to intersection, extend to entity,
+//!          offset curve chain and corner fillet, recorded as undoable commands
+//! MODIFIED: 2026-01-08
+//! LAYER: MCAD (L1 Bubble)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::command_history::{SketchCommand, SketchSnapshot};
+use crate::snap::{foot_parameter, line_line_parameters};
+use cad_engine::{
+    Constraint, DimensionalConstraint, GeometricConstraint, Point2, Sketch, SketchEntity,
+    SketchEntityId, SketchPointId,
+};
+use glam::Vec2;
+use std::f32::consts::PI;
+
+/// Distance below which two sketch positions are treated as the same point
+const EDIT_TOLERANCE: f32 = 1e-4;
+
+/// Smallest corner angle (radians) a fillet will round
+const MIN_CORNER_ANGLE: f32 = 1e-3;
+
+/// Which edit produced a `SketchCommand::EditGeometry`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SketchEditKind {
+    Trim,
+    Extend,
+    Offset,
+    Fillet,
+}
+
+impl SketchEditKind {
+    /// Short name for status messages
+    pub fn label(self) -> &'static str {
+        match self {
+            SketchEditKind::Trim => "trim",
+            SketchEditKind::Extend => "extend",
+            SketchEditKind::Offset => "offset",
+            SketchEditKind::Fillet => "fillet",
+        }
+    }
+}
+
+/// Reasons a sketch edit is refused
+#[derive(Clone, Debug, PartialEq)]
+pub enum SketchEditError {
+    /// No entity with this ID exists in the sketch
+    EntityNotFound(SketchEntityId),
+    /// The edit does not apply to this kind of entity
+    UnsupportedEntity(SketchEntityId),
+    /// No other entity crosses the line where the edit needs one
+    NoIntersection,
+    /// Two lines meeting at the picked corner were not found
+    NoCorner,
+    /// The corner lines are parallel, so no arc is tangent to both
+    ParallelLines,
+    /// The fillet needs more length than the corner lines have
+    RadiusTooLarge { max: f32 },
+    /// Radius or offset distance is zero, negative or not finite
+    InvalidDistance(f32),
+    /// Offsetting would shrink this arc or circle to nothing
+    OffsetCollapses(SketchEntityId),
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PUBLIC OPERATIONS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Trim a line back to the intersections either side of `pick`
+///
+/// The piece of the line between the nearest crossings around the pick point
+/// is removed. If only one side has a crossing the line is cut back to it;
+/// if both do, the line is split and the far piece is kept collinear with
+/// `PointOnLine` constraints.
+pub fn trim_line(
+    sketch: &mut Sketch,
+    constraints: &mut Vec<Constraint>,
+    line: SketchEntityId,
+    pick: Point2,
+) -> Result<SketchCommand, SketchEditError> {
+    record(
+        SketchEditKind::Trim,
+        sketch,
+        constraints,
+        |sketch, constraints| {
+            let (start, end, a, b) = line_endpoints(sketch, line)?;
+            let margin = EDIT_TOLERANCE / a.distance(b);
+            let cuts: Vec<f32> = crossings(sketch, line, a, b)
+                .into_iter()
+                .filter(|&t| t > margin && t < 1.0 - margin)
+                .collect();
+            let t_pick = project(to_vec(pick), a, b);
+            let below = cuts.iter().copied().rev().find(|&t| t < t_pick);
+            let above = cuts.iter().copied().find(|&t| t > t_pick);
+
+            match (below, above) {
+                (None, None) => return Err(SketchEditError::NoIntersection),
+                (None, Some(hi)) => {
+                    release_endpoint(sketch, constraints, line, start, a.lerp(b, hi));
+                }
+                (Some(lo), None) => {
+                    release_endpoint(sketch, constraints, line, end, a.lerp(b, lo));
+                }
+                (Some(lo), Some(hi)) => {
+                    let construction = is_construction(sketch, end);
+                    let piece_start = add_point(sketch, a.lerp(b, hi), construction);
+                    let piece = sketch.next_entity_id();
+                    sketch.add_entity(SketchEntity::Line {
+                        id: piece,
+                        start: piece_start,
+                        end,
+                    });
+                    // `end` now belongs to the far piece, so the original line
+                    // is rewired to a fresh point at the lower cut
+                    release_endpoint(sketch, constraints, line, end, a.lerp(b, lo));
+                    for point in [piece_start, end] {
+                        constraints.push(Constraint::Geometric(GeometricConstraint::PointOnLine {
+                            point,
+                            line,
+                        }));
+                    }
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+/// Extend the end of a line nearest `pick` to the first entity it reaches
+pub fn extend_line(
+    sketch: &mut Sketch,
+    constraints: &mut Vec<Constraint>,
+    line: SketchEntityId,
+    pick: Point2,
+) -> Result<SketchCommand, SketchEditError> {
+    record(
+        SketchEditKind::Extend,
+        sketch,
+        constraints,
+        |sketch, constraints| {
+            let (start, end, a, b) = line_endpoints(sketch, line)?;
+            let margin = EDIT_TOLERANCE / a.distance(b);
+            let hits = crossings(sketch, line, a, b);
+
+            let (point, t) = if project(to_vec(pick), a, b) >= 0.5 {
+                let t = hits.iter().copied().find(|&t| t > 1.0 + margin);
+                (end, t)
+            } else {
+                let t = hits.iter().copied().rev().find(|&t| t < -margin);
+                (start, t)
+            };
+            let t = t.ok_or(SketchEditError::NoIntersection)?;
+            release_endpoint(sketch, constraints, line, point, a.lerp(b, t));
+            Ok(())
+        },
+    )
+}
+
+/// Offset the chain of lines and arcs connected to `entity`
+///
+/// The chain is followed through shared endpoints in both directions from
+/// `entity`, which sets the direction of travel. A positive `distance`
+/// offsets to the left of that direction. Corners are re-joined where the
+/// offset curves meet, arcs stay concentric with their originals and offset
+/// lines are constrained parallel to theirs. A circle offsets on its own,
+/// travelling counter-clockwise.
+pub fn offset_chain(
+    sketch: &mut Sketch,
+    constraints: &mut Vec<Constraint>,
+    entity: SketchEntityId,
+    distance: f32,
+) -> Result<SketchCommand, SketchEditError> {
+    record(
+        SketchEditKind::Offset,
+        sketch,
+        constraints,
+        |sketch, constraints| {
+            if distance == 0.0 || !distance.is_finite() {
+                return Err(SketchEditError::InvalidDistance(distance));
+            }
+
+            match sketch.entity(entity) {
+                None => return Err(SketchEditError::EntityNotFound(entity)),
+                Some(&SketchEntity::Circle { center, radius, .. }) => {
+                    let offset = radius - distance;
+                    if offset <= EDIT_TOLERANCE {
+                        return Err(SketchEditError::OffsetCollapses(entity));
+                    }
+                    let id = sketch.next_entity_id();
+                    sketch.add_entity(SketchEntity::Circle {
+                        id,
+                        center,
+                        radius: offset,
+                    });
+                    return Ok(());
+                }
+                Some(_) => {}
+            }
+
+            let (links, closed) = collect_chain(sketch, entity)?;
+            let offsets = links
+                .iter()
+                .map(|link| link.offset(distance))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let n = links.len();
+            let mut joints = Vec::with_capacity(n + 1);
+            if closed {
+                joints.push(join(&offsets[n - 1], &offsets[0], links[0].from));
+            } else {
+                joints.push(offsets[0].from);
+            }
+            for i in 1..n {
+                joints.push(join(&offsets[i - 1], &offsets[i], links[i].from));
+            }
+            if !closed {
+                joints.push(offsets[n - 1].to);
+            }
+
+            let ids: Vec<SketchPointId> = joints
+                .iter()
+                .map(|&p| add_point(sketch, p, false))
+                .collect();
+
+            for (i, (link, offset)) in links.iter().zip(&offsets).enumerate() {
+                let from = ids[i];
+                let to = ids[(i + 1) % ids.len()];
+                let id = sketch.next_entity_id();
+                match offset.shape {
+                    Shape::Line => {
+                        sketch.add_entity(SketchEntity::Line {
+                            id,
+                            start: from,
+                            end: to,
+                        });
+                        constraints.push(Constraint::Geometric(GeometricConstraint::Parallel {
+                            line1: link.entity,
+                            line2: id,
+                        }));
+                    }
+                    Shape::Arc {
+                        center_id, radius, ..
+                    } => {
+                        let (start, end) = if link.reversed {
+                            (to, from)
+                        } else {
+                            (from, to)
+                        };
+                        sketch.add_entity(SketchEntity::Arc {
+                            id,
+                            center: center_id,
+                            start,
+                            end,
+                            radius,
+                            ccw: link.entity_ccw(),
+                        });
+                    }
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+/// Signed offset distance that makes `offset_chain` pass through `through`
+///
+/// Measured from `entity` alone, with the same sign convention as
+/// `offset_chain`.
+pub fn offset_distance_through(
+    sketch: &Sketch,
+    entity: SketchEntityId,
+    through: Point2,
+) -> Result<f32, SketchEditError> {
+    let p = to_vec(through);
+    match sketch.entity(entity) {
+        None => Err(SketchEditError::EntityNotFound(entity)),
+        Some(&SketchEntity::Circle { center, radius, .. }) => {
+            let c = position(sketch, center, entity)?;
+            Ok(radius - p.distance(c))
+        }
+        Some(_) => {
+            let link = Link::of(sketch, entity)?;
+            match link.shape {
+                Shape::Line => {
+                    let d = link.to - link.from;
+                    Ok(d.perp_dot(p - link.from) / d.length())
+                }
+                Shape::Arc {
+                    center,
+                    radius,
+                    ccw,
+                    ..
+                } => {
+                    let inward = radius - p.distance(center);
+                    Ok(if ccw { inward } else { -inward })
+                }
+            }
+        }
+    }
+}
+
+/// Round the corner where two lines meet near `corner` with a tangent arc
+///
+/// Both lines are cut back to the tangent points and joined by a new arc of
+/// the given radius. `Tangent` constraints tie the arc to each line and a
+/// `Radius` constraint holds its size. Constraints pinned to the old corner
+/// point are removed, since that point no longer exists.
+pub fn fillet_corner(
+    sketch: &mut Sketch,
+    constraints: &mut Vec<Constraint>,
+    corner: Point2,
+    radius: f32,
+    tolerance: f32,
+) -> Result<SketchCommand, SketchEditError> {
+    record(
+        SketchEditKind::Fillet,
+        sketch,
+        constraints,
+        |sketch, constraints| {
+            if radius <= 0.0 || !radius.is_finite() {
+                return Err(SketchEditError::InvalidDistance(radius));
+            }
+
+            let pick = to_vec(corner);
+            let mut ends = Vec::new();
+            for entity in &sketch.entities {
+                if let SketchEntity::Line { id, start, end } = *entity {
+                    let (Some(a), Some(b)) = (sketch.point(start), sketch.point(end)) else {
+                        continue;
+                    };
+                    let (a, b) = (to_vec(a.position), to_vec(b.position));
+                    for (point, near, far) in [(start, a, b), (end, b, a)] {
+                        let dist = near.distance(pick);
+                        if dist <= tolerance {
+                            ends.push((dist, id, point, near, far));
+                        }
+                    }
+                }
+            }
+            ends.sort_by(|x, y| x.0.total_cmp(&y.0));
+            let (_, line1, corner1, near1, far1) =
+                *ends.first().ok_or(SketchEditError::NoCorner)?;
+            let (_, line2, corner2, near2, far2) = *ends
+                .iter()
+                .find(|e| e.1 != line1)
+                .ok_or(SketchEditError::NoCorner)?;
+
+            let c = (near1 + near2) * 0.5;
+            let (len1, len2) = (far1.distance(c), far2.distance(c));
+            if len1 <= EDIT_TOLERANCE || len2 <= EDIT_TOLERANCE {
+                return Err(SketchEditError::NoCorner);
+            }
+            let (u1, u2) = ((far1 - c) / len1, (far2 - c) / len2);
+            let angle = u1.dot(u2).clamp(-1.0, 1.0).acos();
+            if !(MIN_CORNER_ANGLE..=PI - MIN_CORNER_ANGLE).contains(&angle) {
+                return Err(SketchEditError::ParallelLines);
+            }
+
+            let half_tan = (angle / 2.0).tan();
+            let setback = radius / half_tan;
+            let room = len1.min(len2);
+            if setback >= room - EDIT_TOLERANCE {
+                return Err(SketchEditError::RadiusTooLarge {
+                    max: room * half_tan,
+                });
+            }
+
+            let t1 = c + u1 * setback;
+            let t2 = c + u2 * setback;
+            let center = c + (u1 + u2).normalize() * (radius / (angle / 2.0).sin());
+
+            let p1 = release_endpoint(sketch, constraints, line1, corner1, t1);
+            let p2 = release_endpoint(sketch, constraints, line2, corner2, t2);
+            let center_id = add_point(sketch, center, false);
+            let arc = sketch.next_entity_id();
+            sketch.add_entity(SketchEntity::Arc {
+                id: arc,
+                center: center_id,
+                start: p1,
+                end: p2,
+                radius,
+                ccw: (t1 - center).perp_dot(t2 - center) > 0.0,
+            });
+
+            for line in [line1, line2] {
+                constraints.push(Constraint::Geometric(GeometricConstraint::Tangent {
+                    entity1: line,
+                    entity2: arc,
+                }));
+            }
+            constraints.push(Constraint::Dimensional(DimensionalConstraint::Radius {
+                entity: arc,
+                value: radius,
+            }));
+            Ok(())
+        },
+    )
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// COMMAND RECORDING
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Run an edit between two snapshots, rolling back if it fails part way
+fn record<F>(
+    kind: SketchEditKind,
+    sketch: &mut Sketch,
+    constraints: &mut Vec<Constraint>,
+    edit: F,
+) -> Result<SketchCommand, SketchEditError>
+where
+    F: FnOnce(&mut Sketch, &mut Vec<Constraint>) -> Result<(), SketchEditError>,
+{
+    let before = SketchSnapshot::capture(sketch, constraints);
+    if let Err(err) = edit(sketch, constraints) {
+        before.restore(sketch, constraints);
+        return Err(err);
+    }
+    sketch.is_solved = false;
+    Ok(SketchCommand::EditGeometry {
+        kind,
+        before,
+        after: SketchSnapshot::capture(sketch, constraints),
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// POINT BOOKKEEPING
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn to_vec(p: Point2) -> Vec2 {
+    Vec2::new(p.x, p.y)
+}
+
+fn to_point(v: Vec2) -> Point2 {
+    Point2::new(v.x, v.y)
+}
+
+fn position(
+    sketch: &Sketch,
+    point: SketchPointId,
+    owner: SketchEntityId,
+) -> Result<Vec2, SketchEditError> {
+    sketch
+        .point(point)
+        .map(|p| to_vec(p.position))
+        .ok_or(SketchEditError::EntityNotFound(owner))
+}
+
+fn is_construction(sketch: &Sketch, point: SketchPointId) -> bool {
+    sketch.point(point).is_some_and(|p| p.is_construction)
+}
+
+fn add_point(sketch: &mut Sketch, position: Vec2, is_construction: bool) -> SketchPointId {
+    let id = sketch.add_point(to_point(position));
+    if let Some(point) = sketch.point_mut(id) {
+        point.is_construction = is_construction;
+    }
+    id
+}
+
+fn line_endpoints(
+    sketch: &Sketch,
+    line: SketchEntityId,
+) -> Result<(SketchPointId, SketchPointId, Vec2, Vec2), SketchEditError> {
+    match sketch.entity(line) {
+        None => Err(SketchEditError::EntityNotFound(line)),
+        Some(&SketchEntity::Line { start, end, .. }) => {
+            let a = position(sketch, start, line)?;
+            let b = position(sketch, end, line)?;
+            if a.distance(b) <= EDIT_TOLERANCE {
+                return Err(SketchEditError::UnsupportedEntity(line));
+            }
+            Ok((start, end, a, b))
+        }
+        Some(_) => Err(SketchEditError::UnsupportedEntity(line)),
+    }
+}
+
+/// Points a constraint refers to directly
+fn constraint_points(constraint: &Constraint) -> Vec<SketchPointId> {
+    match constraint {
+        Constraint::Geometric(g) => match *g {
+            GeometricConstraint::Coincident { p1, p2 }
+            | GeometricConstraint::Symmetric { p1, p2, .. } => vec![p1, p2],
+            GeometricConstraint::Midpoint { point, .. }
+            | GeometricConstraint::PointOnLine { point, .. }
+            | GeometricConstraint::PointOnCircle { point, .. }
+            | GeometricConstraint::Fix { point, .. } => vec![point],
+            _ => Vec::new(),
+        },
+        Constraint::Dimensional(d) => match *d {
+            DimensionalConstraint::Distance { p1, p2, .. }
+            | DimensionalConstraint::HorizontalDistance { p1, p2, .. }
+            | DimensionalConstraint::VerticalDistance { p1, p2, .. } => vec![p1, p2],
+            _ => Vec::new(),
+        },
+    }
+}
+
+/// Move `point` of `entity` to `target`
+///
+/// A point other entities still use stays where it is and `entity` is
+/// rewired to a fresh point. Otherwise the point moves, and constraints
+/// pinned to it are dropped because they describe the old position.
+fn release_endpoint(
+    sketch: &mut Sketch,
+    constraints: &mut Vec<Constraint>,
+    entity: SketchEntityId,
+    point: SketchPointId,
+    target: Vec2,
+) -> SketchPointId {
+    let shared = sketch
+        .entities
+        .iter()
+        .any(|e| e.id() != entity && e.point_ids().contains(&point));
+
+    if !shared {
+        if let Some(p) = sketch.point_mut(point) {
+            p.position = to_point(target);
+        }
+        constraints.retain(|c| !constraint_points(c).contains(&point));
+        return point;
+    }
+
+    let fresh = add_point(sketch, target, is_construction(sketch, point));
+    if let Some(SketchEntity::Line { start, end, .. } | SketchEntity::Arc { start, end, .. }) =
+        sketch.entities.iter_mut().find(|e| e.id() == entity)
+    {
+        if *start == point {
+            *start = fresh;
+        } else if *end == point {
+            *end = fresh;
+        }
+    }
+    fresh
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// INTERSECTIONS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Parameter of the foot of `p` on the line through `a` and `b`
+fn project(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    foot_parameter(to_point(p), to_point(a), to_point(b)).unwrap_or(0.0)
+}
+
+/// Parameters along the infinite line `a + t(b - a)` where it crosses the
+/// other entities of the sketch, sorted ascending
+fn crossings(sketch: &Sketch, line: SketchEntityId, a: Vec2, b: Vec2) -> Vec<f32> {
+    let mut hits = Vec::new();
+    for entity in sketch.entities.iter().filter(|e| e.id() != line) {
+        match *entity {
+            SketchEntity::Line { start, end, .. } => {
+                if let (Some(c), Some(d)) = (sketch.point(start), sketch.point(end)) {
+                    hits.extend(segment_hit(a, b, to_vec(c.position), to_vec(d.position)));
+                }
+            }
+            SketchEntity::Circle { center, radius, .. } => {
+                if let Some(c) = sketch.point(center) {
+                    hits.extend(circle_hits(a, b, to_vec(c.position), radius));
+                }
+            }
+            SketchEntity::Arc {
+                center,
+                start,
+                end,
+                radius,
+                ccw,
+                ..
+            } => {
+                let (Some(c), Some(s), Some(e)) =
+                    (sketch.point(center), sketch.point(start), sketch.point(end))
+                else {
+                    continue;
+                };
+                let (c, s, e) = (to_vec(c.position), to_vec(s.position), to_vec(e.position));
+                hits.extend(
+                    circle_hits(a, b, c, radius)
+                        .into_iter()
+                        .filter(|&t| on_arc(c, s, e, ccw, a.lerp(b, t))),
+                );
+            }
+            SketchEntity::Spline { id, .. } | SketchEntity::Ellipse { id, .. } => {
+                if let Some(polyline) = sketch.entity_polyline(id) {
+                    for w in polyline.windows(2) {
+                        hits.extend(segment_hit(a, b, to_vec(w[0]), to_vec(w[1])));
+                    }
+                }
+            }
+            SketchEntity::Point { .. } => {}
+        }
+    }
+    hits.sort_by(f32::total_cmp);
+    hits
+}
+
+/// Parameter along the infinite line `a + t(b - a)` where it crosses the
+/// segment `c`-`d`
+fn segment_hit(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> Option<f32> {
+    let (t, u) = line_line_parameters(to_point(a), to_point(b), to_point(c), to_point(d))?;
+    let margin = EDIT_TOLERANCE / c.distance(d);
+    (-margin..=1.0 + margin).contains(&u).then_some(t)
+}
+
+/// Parameters along the infinite line `a + t(b - a)` where it meets a circle
+fn circle_hits(a: Vec2, b: Vec2, center: Vec2, radius: f32) -> Vec<f32> {
+    let d = b - a;
+    let f = a - center;
+    let qa = d.length_squared();
+    let qb = 2.0 * f.dot(d);
+    let qc = f.length_squared() - radius * radius;
+    let disc = qb * qb - 4.0 * qa * qc;
+    if disc < 0.0 || qa < 1e-12 {
+        return Vec::new();
+    }
+    let root = disc.sqrt();
+    vec![(-qb - root) / (2.0 * qa), (-qb + root) / (2.0 * qa)]
+}
+
+/// Points where two circles meet
+fn circle_circle(c1: Vec2, r1: f32, c2: Vec2, r2: f32) -> Vec<Vec2> {
+    let d = c2 - c1;
+    let dist = d.length();
+    if dist < EDIT_TOLERANCE || dist > r1 + r2 || dist < (r1 - r2).abs() {
+        return Vec::new();
+    }
+    let along = (r1 * r1 - r2 * r2 + dist * dist) / (2.0 * dist);
+    let h = (r1 * r1 - along * along).max(0.0).sqrt();
+    let base = c1 + d * (along / dist);
+    let n = d.perp() / dist;
+    vec![base + n * h, base - n * h]
+}
+
+/// Whether `p`, on the arc's circle, falls within its sweep
+fn on_arc(center: Vec2, start: Vec2, end: Vec2, ccw: bool, p: Vec2) -> bool {
+    let angle = |v: Vec2| (v.y - center.y).atan2(v.x - center.x);
+    let turn = |from: f32, to: f32| (to - from).rem_euclid(2.0 * PI);
+    let (s, e, q) = (angle(start), angle(end), angle(p));
+    let (sweep, at) = if ccw {
+        (turn(s, e), turn(s, q))
+    } else {
+        (turn(e, s), turn(q, s))
+    };
+    let slack = EDIT_TOLERANCE / start.distance(center).max(EDIT_TOLERANCE);
+    at <= sweep + slack || at >= 2.0 * PI - slack
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CURVE CHAINS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy, Debug)]
+enum Shape {
+    Line,
+    /// `ccw` is the turning direction along the chain, not the entity's own
+    Arc {
+        center_id: SketchPointId,
+        center: Vec2,
+        radius: f32,
+        ccw: bool,
+    },
+}
+
+/// One chain member, traversed from `from` to `to`
+#[derive(Clone, Copy, Debug)]
+struct Link {
+    entity: SketchEntityId,
+    shape: Shape,
+    from: Vec2,
+    to: Vec2,
+    reversed: bool,
+}
+
+impl Link {
+    fn of(sketch: &Sketch, entity: SketchEntityId) -> Result<Self, SketchEditError> {
+        match sketch.entity(entity) {
+            None => Err(SketchEditError::EntityNotFound(entity)),
+            Some(&SketchEntity::Line { start, end, .. }) => Ok(Self {
+                entity,
+                shape: Shape::Line,
+                from: position(sketch, start, entity)?,
+                to: position(sketch, end, entity)?,
+                reversed: false,
+            }),
+            Some(&SketchEntity::Arc {
+                center,
+                start,
+                end,
+                radius,
+                ccw,
+                ..
+            }) => Ok(Self {
+                entity,
+                shape: Shape::Arc {
+                    center_id: center,
+                    center: position(sketch, center, entity)?,
+                    radius,
+                    ccw,
+                },
+                from: position(sketch, start, entity)?,
+                to: position(sketch, end, entity)?,
+                reversed: false,
+            }),
+            Some(_) => Err(SketchEditError::UnsupportedEntity(entity)),
+        }
+    }
+
+    fn reverse(self) -> Self {
+        let shape = match self.shape {
+            Shape::Arc {
+                center_id,
+                center,
+                radius,
+                ccw,
+            } => Shape::Arc {
+                center_id,
+                center,
+                radius,
+                ccw: !ccw,
+            },
+            line => line,
+        };
+        Self {
+            shape,
+            from: self.to,
+            to: self.from,
+            reversed: !self.reversed,
+            ..self
+        }
+    }
+
+    /// Direction flag for an arc entity rebuilt from this link
+    fn entity_ccw(&self) -> bool {
+        match self.shape {
+            Shape::Arc { ccw, .. } => ccw != self.reversed,
+            Shape::Line => true,
+        }
+    }
+
+    /// This link moved `distance` to the left of its direction of travel
+    fn offset(&self, distance: f32) -> Result<Self, SketchEditError> {
+        match self.shape {
+            Shape::Line => {
+                let n = (self.to - self.from).normalize_or_zero().perp() * distance;
+                Ok(Self {
+                    from: self.from + n,
+                    to: self.to + n,
+                    ..*self
+                })
+            }
+            Shape::Arc {
+                center_id,
+                center,
+                radius,
+                ccw,
+            } => {
+                // Travelling counter-clockwise the centre is on the left
+                let offset = if ccw {
+                    radius - distance
+                } else {
+                    radius + distance
+                };
+                if offset <= EDIT_TOLERANCE {
+                    return Err(SketchEditError::OffsetCollapses(self.entity));
+                }
+                let scale = |p: Vec2| center + (p - center) * (offset / radius);
+                Ok(Self {
+                    shape: Shape::Arc {
+                        center_id,
+                        center,
+                        radius: offset,
+                        ccw,
+                    },
+                    from: scale(self.from),
+                    to: scale(self.to),
+                    ..*self
+                })
+            }
+        }
+    }
+}
+
+/// Lines and arcs connected end to end through `seed`, in travel order, and
+/// whether they close into a loop
+fn collect_chain(
+    sketch: &Sketch,
+    seed: SketchEntityId,
+) -> Result<(Vec<Link>, bool), SketchEditError> {
+    let mut links = vec![Link::of(sketch, seed)?];
+    let mut used = vec![seed];
+
+    let next_at = |used: &[SketchEntityId], joint: Vec2, leaving: bool| {
+        sketch
+            .entities
+            .iter()
+            .filter(|e| !used.contains(&e.id()))
+            .filter_map(|e| Link::of(sketch, e.id()).ok())
+            .find_map(|link| {
+                let (own, other) = if leaving {
+                    (link.from, link.to)
+                } else {
+                    (link.to, link.from)
+                };
+                if own.distance(joint) <= EDIT_TOLERANCE {
+                    Some(link)
+                } else if other.distance(joint) <= EDIT_TOLERANCE {
+                    Some(link.reverse())
+                } else {
+                    None
+                }
+            })
+    };
+
+    let mut closed = false;
+    while let Some(link) = next_at(&used, links[links.len() - 1].to, true) {
+        used.push(link.entity);
+        links.push(link);
+        if link.to.distance(links[0].from) <= EDIT_TOLERANCE {
+            closed = true;
+            break;
+        }
+    }
+    if !closed {
+        while let Some(link) = next_at(&used, links[0].from, false) {
+            used.push(link.entity);
+            links.insert(0, link);
+        }
+    }
+    Ok((links, closed))
+}
+
+/// Corner point where two consecutive offset links meet
+///
+/// Tangent joints stay joined after offsetting. Elsewhere the offset curves
+/// are intersected and the crossing nearest the original corner is used.
+fn join(prev: &Link, next: &Link, corner: Vec2) -> Vec2 {
+    if prev.to.distance(next.from) <= EDIT_TOLERANCE {
+        return prev.to;
+    }
+    let candidates = match (prev.shape, next.shape) {
+        (Shape::Line, Shape::Line) => line_line_parameters(
+            to_point(prev.from),
+            to_point(prev.to),
+            to_point(next.from),
+            to_point(next.to),
+        )
+        .map(|(t, _)| vec![prev.from.lerp(prev.to, t)])
+        .unwrap_or_default(),
+        (Shape::Line, Shape::Arc { center, radius, .. }) => {
+            circle_hits(prev.from, prev.to, center, radius)
+                .into_iter()
+                .map(|t| prev.from.lerp(prev.to, t))
+                .collect()
+        }
+        (Shape::Arc { center, radius, .. }, Shape::Line) => {
+            circle_hits(next.from, next.to, center, radius)
+                .into_iter()
+                .map(|t| next.from.lerp(next.to, t))
+                .collect()
+        }
+        (
+            Shape::Arc {
+                center: c1,
+                radius: r1,
+                ..
+            },
+            Shape::Arc {
+                center: c2,
+                radius: r2,
+                ..
+            },
+        ) => circle_circle(c1, r1, c2, r2),
+    };
+    candidates
+        .into_iter()
+        .min_by(|x, y| x.distance(corner).total_cmp(&y.distance(corner)))
+        .unwrap_or_else(|| (prev.to + next.from) * 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cad_engine::{ConstraintAnalysis, SketchPlane};
+
+    fn add_line(sketch: &mut Sketch, a: (f32, f32), b: (f32, f32)) -> SketchEntityId {
+        let start = sketch.add_point(Point2::new(a.0, a.1));
+        let end = sketch.add_point(Point2::new(b.0, b.1));
+        let id = sketch.next_entity_id();
+        sketch.add_entity(SketchEntity::Line { id, start, end })
+    }
+
+    fn line_span(sketch: &Sketch, line: SketchEntityId) -> (Point2, Point2) {
+        match sketch.entity(line) {
+            Some(&SketchEntity::Line { start, end, .. }) => (
+                sketch.point(start).unwrap().position,
+                sketch.point(end).unwrap().position,
+            ),
+            _ => panic!("not a line"),
+        }
+    }
+
+    fn assert_near(p: Point2, x: f32, y: f32) {
+        assert!(
+            (p.x - x).abs() < 1e-3 && (p.y - y).abs() < 1e-3,
+            "expected ({}, {}), got ({}, {})",
+            x,
+            y,
+            p.x,
+            p.y
+        );
+    }
+
+    /// Closed rectangle with shared corner points and H/V constraints
+    fn rectangle(sketch: &mut Sketch, w: f32, h: f32) -> (Vec<SketchEntityId>, Vec<Constraint>) {
+        let corners = [(0.0, 0.0), (w, 0.0), (w, h), (0.0, h)];
+        let ids: Vec<_> = corners
+            .iter()
+            .map(|&(x, y)| sketch.add_point(Point2::new(x, y)))
+            .collect();
+        let mut lines = Vec::new();
+        let mut constraints = Vec::new();
+        for i in 0..4 {
+            let id = sketch.next_entity_id();
+            sketch.add_entity(SketchEntity::Line {
+                id,
+                start: ids[i],
+                end: ids[(i + 1) % 4],
+            });
+            constraints.push(Constraint::Geometric(if i % 2 == 0 {
+                GeometricConstraint::Horizontal { line: id }
+            } else {
+                GeometricConstraint::Vertical { line: id }
+            }));
+            lines.push(id);
+        }
+        (lines, constraints)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // TRIM / EXTEND
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_trim_between_crossings_splits_line() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let line = add_line(&mut sketch, (0.0, 0.0), (100.0, 0.0));
+        add_line(&mut sketch, (30.0, -10.0), (30.0, 10.0));
+        add_line(&mut sketch, (70.0, -10.0), (70.0, 10.0));
+        let mut constraints = Vec::new();
+
+        let cmd = trim_line(&mut sketch, &mut constraints, line, Point2::new(50.0, 1.0)).unwrap();
+        assert!(matches!(
+            cmd,
+            SketchCommand::EditGeometry {
+                kind: SketchEditKind::Trim,
+                ..
+            }
+        ));
+
+        let (a, b) = line_span(&sketch, line);
+        assert_near(a, 0.0, 0.0);
+        assert_near(b, 30.0, 0.0);
+        let piece = sketch.entities.last().unwrap().id();
+        let (c, d) = line_span(&sketch, piece);
+        assert_near(c, 70.0, 0.0);
+        assert_near(d, 100.0, 0.0);
+        assert_eq!(constraints.len(), 2);
+        assert!(constraints.iter().all(|c| c.evaluate(&sketch) < 1e-6));
+    }
+
+    #[test]
+    fn test_trim_end_segment() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let line = add_line(&mut sketch, (0.0, 0.0), (100.0, 0.0));
+        let circle_center = sketch.add_point(Point2::new(60.0, 0.0));
+        let id = sketch.next_entity_id();
+        sketch.add_entity(SketchEntity::Circle {
+            id,
+            center: circle_center,
+            radius: 20.0,
+        });
+        let mut constraints = Vec::new();
+
+        // Crossings at x = 40 and x = 80; picking past 80 removes the tail
+        trim_line(&mut sketch, &mut constraints, line, Point2::new(90.0, 0.0)).unwrap();
+        let (a, b) = line_span(&sketch, line);
+        assert_near(a, 0.0, 0.0);
+        assert_near(b, 80.0, 0.0);
+        assert_eq!(sketch.entities.len(), 2);
+    }
+
+    #[test]
+    fn test_trim_without_crossing_leaves_sketch_untouched() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let line = add_line(&mut sketch, (0.0, 0.0), (100.0, 0.0));
+        add_line(&mut sketch, (0.0, 10.0), (100.0, 10.0));
+        let mut constraints = Vec::new();
+
+        let err = trim_line(&mut sketch, &mut constraints, line, Point2::new(50.0, 0.0));
+        assert_eq!(err.unwrap_err(), SketchEditError::NoIntersection);
+        assert_eq!(sketch.points.len(), 4);
+        assert_eq!(sketch.entities.len(), 2);
+    }
+
+    #[test]
+    fn test_extend_to_line_and_arc() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let line = add_line(&mut sketch, (0.0, 0.0), (50.0, 0.0));
+        add_line(&mut sketch, (120.0, -10.0), (120.0, 10.0));
+
+        // Arc of radius 20 about (80, 0) covering only its left half: the ray
+        // meets it at x = 60, while the right half (x = 100) is not drawn
+        let c = sketch.add_point(Point2::new(80.0, 0.0));
+        let s = sketch.add_point(Point2::new(80.0, 20.0));
+        let e = sketch.add_point(Point2::new(80.0, -20.0));
+        let id = sketch.next_entity_id();
+        sketch.add_entity(SketchEntity::Arc {
+            id,
+            center: c,
+            start: s,
+            end: e,
+            radius: 20.0,
+            ccw: true,
+        });
+        let mut constraints = Vec::new();
+
+        extend_line(&mut sketch, &mut constraints, line, Point2::new(45.0, 0.0)).unwrap();
+        assert_near(line_span(&sketch, line).1, 60.0, 0.0);
+
+        // Picking the start finds nothing behind it
+        let err = extend_line(&mut sketch, &mut constraints, line, Point2::new(5.0, 0.0));
+        assert_eq!(err.unwrap_err(), SketchEditError::NoIntersection);
+    }
+
+    #[test]
+    fn test_extend_keeps_connected_neighbour() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let a = sketch.add_point(Point2::new(0.0, 0.0));
+        let b = sketch.add_point(Point2::new(50.0, 0.0));
+        let c = sketch.add_point(Point2::new(50.0, 30.0));
+        sketch.add_entity(SketchEntity::Line {
+            id: SketchEntityId(0),
+            start: a,
+            end: b,
+        });
+        sketch.add_entity(SketchEntity::Line {
+            id: SketchEntityId(1),
+            start: b,
+            end: c,
+        });
+        add_line(&mut sketch, (80.0, -10.0), (80.0, 10.0));
+        let mut constraints = Vec::new();
+
+        extend_line(
+            &mut sketch,
+            &mut constraints,
+            SketchEntityId(0),
+            Point2::new(49.0, 0.0),
+        )
+        .unwrap();
+        assert_near(line_span(&sketch, SketchEntityId(0)).1, 80.0, 0.0);
+        // The shared corner stays put for the other line
+        assert_near(sketch.point(b).unwrap().position, 50.0, 0.0);
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // OFFSET
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_offset_closed_rectangle_inward() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let (lines, mut constraints) = rectangle(&mut sketch, 100.0, 50.0);
+
+        let d = offset_distance_through(&sketch, lines[0], Point2::new(50.0, 10.0)).unwrap();
+        assert!((d - 10.0).abs() < 1e-5);
+        offset_chain(&mut sketch, &mut constraints, lines[2], d).unwrap();
+
+        // Four new lines on four new shared corners
+        assert_eq!(sketch.entities.len(), 8);
+        assert_eq!(sketch.points.len(), 8);
+        let expected = [(10.0, 40.0), (10.0, 10.0), (90.0, 10.0), (90.0, 40.0)];
+        let mut corners: Vec<_> = sketch.points[4..].iter().map(|p| p.position).collect();
+        corners.sort_by(|p, q| (p.x, p.y).partial_cmp(&(q.x, q.y)).unwrap());
+        let mut expected = expected.to_vec();
+        expected.sort_by(|p, q| p.partial_cmp(q).unwrap());
+        for (p, &(x, y)) in corners.iter().zip(&expected) {
+            assert_near(*p, x, y);
+        }
+
+        let parallel = constraints
+            .iter()
+            .filter(|c| {
+                matches!(
+                    c,
+                    Constraint::Geometric(GeometricConstraint::Parallel { .. })
+                )
+            })
+            .count();
+        assert_eq!(parallel, 4);
+        assert!(constraints.iter().all(|c| c.evaluate(&sketch) < 1e-6));
+    }
+
+    #[test]
+    fn test_offset_open_line_arc_chain() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let p0 = sketch.add_point(Point2::new(0.0, 0.0));
+        let p1 = sketch.add_point(Point2::new(50.0, 0.0));
+        let center = sketch.add_point(Point2::new(50.0, 20.0));
+        let p2 = sketch.add_point(Point2::new(70.0, 20.0));
+        sketch.add_entity(SketchEntity::Line {
+            id: SketchEntityId(0),
+            start: p0,
+            end: p1,
+        });
+        // Stored end-to-start so the chain has to reverse it
+        sketch.add_entity(SketchEntity::Arc {
+            id: SketchEntityId(1),
+            center,
+            start: p2,
+            end: p1,
+            radius: 20.0,
+            ccw: false,
+        });
+        let mut constraints = Vec::new();
+
+        // Right of travel is away from the arc centre
+        offset_chain(&mut sketch, &mut constraints, SketchEntityId(0), -5.0).unwrap();
+
+        let line = sketch.entities[2].id();
+        let (a, b) = line_span(&sketch, line);
+        assert_near(a, 0.0, -5.0);
+        assert_near(b, 50.0, -5.0);
+        match sketch.entities[3] {
+            SketchEntity::Arc {
+                center: c,
+                start,
+                end,
+                radius,
+                ccw,
+                ..
+            } => {
+                assert_eq!(c, center);
+                assert!((radius - 25.0).abs() < 1e-4);
+                assert!(!ccw);
+                assert_near(sketch.point(start).unwrap().position, 75.0, 20.0);
+                assert_near(sketch.point(end).unwrap().position, 50.0, -5.0);
+            }
+            ref other => panic!("expected arc, got {:?}", other),
+        }
+
+        // Offsetting past the centre collapses the arc
+        let err = offset_chain(&mut sketch, &mut constraints, SketchEntityId(0), 25.0);
+        assert_eq!(
+            err.unwrap_err(),
+            SketchEditError::OffsetCollapses(SketchEntityId(1))
+        );
+    }
+
+    #[test]
+    fn test_offset_circle() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let c = sketch.add_point(Point2::new(0.0, 0.0));
+        sketch.add_entity(SketchEntity::Circle {
+            id: SketchEntityId(0),
+            center: c,
+            radius: 10.0,
+        });
+        let mut constraints = Vec::new();
+
+        let d = offset_distance_through(&sketch, SketchEntityId(0), Point2::new(15.0, 0.0));
+        offset_chain(&mut sketch, &mut constraints, SketchEntityId(0), d.unwrap()).unwrap();
+        match sketch.entities[1] {
+            SketchEntity::Circle { center, radius, .. } => {
+                assert_eq!(center, c);
+                assert!((radius - 15.0).abs() < 1e-5);
+            }
+            ref other => panic!("expected circle, got {:?}", other),
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // FILLET
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    #[test]
+    fn test_fillet_rectangle_corner() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let (lines, mut constraints) = rectangle(&mut sketch, 100.0, 50.0);
+
+        fillet_corner(
+            &mut sketch,
+            &mut constraints,
+            Point2::new(99.0, 1.0),
+            10.0,
+            5.0,
+        )
+        .unwrap();
+
+        assert_near(line_span(&sketch, lines[0]).1, 90.0, 0.0);
+        assert_near(line_span(&sketch, lines[1]).0, 100.0, 10.0);
+        let arc = sketch.entities.last().unwrap();
+        match *arc {
+            SketchEntity::Arc {
+                center,
+                radius,
+                ccw,
+                ..
+            } => {
+                assert_near(sketch.point(center).unwrap().position, 90.0, 10.0);
+                assert_eq!(radius, 10.0);
+                assert!(ccw);
+            }
+            ref other => panic!("expected arc, got {:?}", other),
+        }
+
+        let tangents = constraints
+            .iter()
+            .filter(|c| {
+                matches!(
+                    c,
+                    Constraint::Geometric(GeometricConstraint::Tangent { .. })
+                )
+            })
+            .count();
+        assert_eq!(tangents, 2);
+        assert!(constraints.iter().all(|c| c.evaluate(&sketch) < 1e-6));
+
+        let analysis = ConstraintAnalysis::analyze(&sketch, &constraints);
+        assert!(analysis.redundant.is_empty());
+        assert!(analysis.conflicting.is_empty());
+    }
+
+    #[test]
+    fn test_fillet_separate_endpoints_drops_coincidence() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let l1 = add_line(&mut sketch, (0.0, 0.0), (40.0, 0.0));
+        let l2 = add_line(&mut sketch, (40.0, 0.0), (40.0, 40.0));
+        let mut constraints = vec![Constraint::Geometric(GeometricConstraint::Coincident {
+            p1: SketchPointId(1),
+            p2: SketchPointId(2),
+        })];
+
+        fillet_corner(
+            &mut sketch,
+            &mut constraints,
+            Point2::new(40.0, 0.0),
+            5.0,
+            1.0,
+        )
+        .unwrap();
+
+        // Both corner points moved, no new line points were needed
+        assert_eq!(sketch.points.len(), 5);
+        assert_near(line_span(&sketch, l1).1, 35.0, 0.0);
+        assert_near(line_span(&sketch, l2).0, 40.0, 5.0);
+        assert!(!constraints.iter().any(|c| matches!(
+            c,
+            Constraint::Geometric(GeometricConstraint::Coincident { .. })
+        )));
+    }
+
+    #[test]
+    fn test_fillet_errors_leave_sketch_untouched() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let (_, mut constraints) = rectangle(&mut sketch, 20.0, 10.0);
+        let before = SketchSnapshot::capture(&sketch, &constraints);
+
+        let err = fillet_corner(
+            &mut sketch,
+            &mut constraints,
+            Point2::new(20.0, 0.0),
+            15.0,
+            1.0,
+        );
+        match err.unwrap_err() {
+            SketchEditError::RadiusTooLarge { max } => assert!((max - 10.0).abs() < 1e-3),
+            other => panic!("unexpected error {:?}", other),
+        }
+
+        let err = fillet_corner(
+            &mut sketch,
+            &mut constraints,
+            Point2::new(10.0, 5.0),
+            1.0,
+            1.0,
+        );
+        assert_eq!(err.unwrap_err(), SketchEditError::NoCorner);
+
+        assert_eq!(sketch.points.len(), before.points.len());
+        assert_eq!(sketch.entities.len(), before.entities.len());
+        assert_eq!(constraints.len(), before.constraints.len());
+    }
+
+    #[test]
+    fn test_edit_command_undoes_and_redoes() {
+        let mut sketch = Sketch::new(SketchPlane::XY);
+        let (_, mut constraints) = rectangle(&mut sketch, 100.0, 50.0);
+        let original = sketch.clone();
+
+        let cmd = fillet_corner(
+            &mut sketch,
+            &mut constraints,
+            Point2::new(0.0, 0.0),
+            10.0,
+            1.0,
+        )
+        .unwrap();
+        let SketchCommand::EditGeometry { before, after, .. } = cmd else {
+            panic!("expected an edit command");
+        };
+
+        before.restore(&mut sketch, &mut constraints);
+        assert_eq!(sketch.entities.len(), original.entities.len());
+        assert_eq!(sketch.points.len(), original.points.len());
+        assert_eq!(constraints.len(), 4);
+        for (p, q) in sketch.points.iter().zip(&original.points) {
+            assert_near(p.position, q.position.x, q.position.y);
+        }
+
+        after.restore(&mut sketch, &mut constraints);
+        assert_eq!(sketch.entities.len(), 5);
+        assert_eq!(constraints.len(), 7);
+    }
+}
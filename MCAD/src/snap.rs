@@ -223,9 +223,9 @@ pub fn entity_midpoint(sketch: &Sketch, entity: &SketchEntity) -> Option<(Point2
     }
 }
 
-/// Find the closest point on a line segment from a given point (perpendicular foot)
-/// Returns None if the foot is outside the segment (within 5%-95% range)
-pub fn perpendicular_foot(p: Point2, a: Point2, b: Point2) -> Option<Point2> {
+/// Parameter `t` of the perpendicular foot of `p` on the infinite line
+/// `a + t(b - a)`. Returns None if `a` and `b` coincide
+pub fn foot_parameter(p: Point2, a: Point2, b: Point2) -> Option<f32> {
     let ab = Point2::new(b.x - a.x, b.y - a.y);
     let ap = Point2::new(p.x - a.x, p.y - a.y);
 
@@ -235,19 +235,25 @@ pub fn perpendicular_foot(p: Point2, a: Point2, b: Point2) -> Option<Point2> {
     }
 
     // Project p onto ab
-    let t = (ap.x * ab.x + ap.y * ab.y) / ab_len_sq;
+    Some((ap.x * ab.x + ap.y * ab.y) / ab_len_sq)
+}
+
+/// Find the closest point on a line segment from a given point (perpendicular foot)
+/// Returns None if the foot is outside the segment (within 5%-95% range)
+pub fn perpendicular_foot(p: Point2, a: Point2, b: Point2) -> Option<Point2> {
+    let t = foot_parameter(p, a, b)?;
 
     // Only return if foot is within segment (with small margin)
     if (0.05..=0.95).contains(&t) {
-        Some(Point2::new(a.x + t * ab.x, a.y + t * ab.y))
+        Some(Point2::new(a.x + t * (b.x - a.x), a.y + t * (b.y - a.y)))
     } else {
         None
     }
 }
 
-/// Find intersection of two line segments
-/// Returns None if segments are parallel or don't intersect
-pub fn line_line_intersection(a1: Point2, a2: Point2, b1: Point2, b2: Point2) -> Option<Point2> {
+/// Parameters `(t, u)` where the infinite lines `a1 + t(a2 - a1)` and
+/// `b1 + u(b2 - b1)` cross. Returns None if the lines are parallel
+pub fn line_line_parameters(a1: Point2, a2: Point2, b1: Point2, b2: Point2) -> Option<(f32, f32)> {
     let d1 = Point2::new(a2.x - a1.x, a2.y - a1.y);
     let d2 = Point2::new(b2.x - b1.x, b2.y - b1.y);
 
@@ -261,10 +267,20 @@ pub fn line_line_intersection(a1: Point2, a2: Point2, b1: Point2, b2: Point2) ->
 
     let t = (dx * d2.y - dy * d2.x) / cross;
     let u = (dx * d1.y - dy * d1.x) / cross;
+    Some((t, u))
+}
+
+/// Find intersection of two line segments
+/// Returns None if segments are parallel or don't intersect
+pub fn line_line_intersection(a1: Point2, a2: Point2, b1: Point2, b2: Point2) -> Option<Point2> {
+    let (t, u) = line_line_parameters(a1, a2, b1, b2)?;
 
     // Check if intersection is within both segments
     if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
-        Some(Point2::new(a1.x + t * d1.x, a1.y + t * d1.y))
+        Some(Point2::new(
+            a1.x + t * (a2.x - a1.x),
+            a1.y + t * (a2.y - a1.y),
+        ))
     } else {
         None
     }
@@ -428,6 +444,20 @@ mod tests {
         assert!(line_line_intersection(a1, a2, b1, b2).is_none());
     }
 
+    #[test]
+    fn test_line_line_parameters_extended() {
+        let a1 = Point2::new(0.0, 0.0);
+        let a2 = Point2::new(10.0, 10.0);
+        let b1 = Point2::new(50.0, 0.0);
+        let b2 = Point2::new(50.0, 10.0);
+
+        // Crossing lies beyond both segments, at (50, 50)
+        let (t, u) = line_line_parameters(a1, a2, b1, b2).unwrap();
+        assert!((t - 5.0).abs() < 1e-5);
+        assert!((u - 5.0).abs() < 1e-5);
+        assert!((foot_parameter(Point2::new(-10.0, 5.0), b1, b2).unwrap() - 0.5).abs() < 1e-5);
+    }
+
     #[test]
     fn test_point_to_segment_distance_on_segment() {
         let a = Point2::new(0.0, 0.0);
@@ -6,9 +6,12 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //!
 //! SPICE_ENGINE provides circuit simulation capabilities:
-//! - DC analysis (operating point)
+//! - DC analysis (Newton operating point with gmin / source stepping)
 //! - AC analysis (frequency response, Bode plots)
-//! - Transient analysis (time-domain simulation) [TODO]
+//! - Noise analysis (adjoint output / input-referred noise, noise figure)
+//! - Pole-zero analysis and vector fitting of AC results to transfer functions
+//! - Transient analysis (time-domain simulation, trapezoidal / Gear-2)
+//! - Sweeps (.dc source, parametric) and Monte Carlo / worst-case tolerance
 //!
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
 //! │ ARCHITECTURE                                                                │
@@ -17,20 +20,39 @@
 //! │   SpiceEngine                                                               │
 //! │       │                                                                     │
 //! │       ├── Netlist              (DNA/physics/electromagnetics/lumped)        │
+//! │       ├── ParsedNetlist        (SPICE text → Netlist + analyses)            │
 //! │       ├── MNAMatrix            (DNA/physics/electromagnetics/lumped)        │
 //! │       ├── ComplexMNAMatrix     (DNA/physics/electromagnetics/lumped)        │
-//! │       └── ACResult             (DNA/physics/electromagnetics/lumped)        │
+//! │       ├── SparseSolver         (Markowitz LU reused across solves)          │
+//! │       ├── ACResult             (DNA/physics/electromagnetics/lumped)        │
+//! │       ├── DcOperatingPoint     (DNA/physics/electromagnetics/lumped)        │
+//! │       └── TransientResult      (DNA/physics/electromagnetics/lumped)        │
 //! │                                                                             │
 //! │   Analysis types:                                                           │
-//! │   - dc_analysis()   - Find DC operating point                               │
+//! │   - dc_operating_point() - Nonlinear DC bias (diodes, BJTs, MOSFETs)        │
 //! │   - ac_analysis()   - Frequency sweep with complex arithmetic               │
 //! │   - bode_plot()     - Generate magnitude/phase vs frequency                 │
+//! │   - noise_analysis() - Output / input noise PSD, integrated noise, NF       │
+//! │   - pole_zero()     - Poles / zeros of G + sC for one input → output        │
+//! │   - vector_fit()    - Rational fit of a sampled response (TransferFunction) │
+//! │   - transient_analysis() - Time-domain waveforms with adaptive timestep     │
+//! │   - dc_sweep() / parametric_sweep() - Source and element value sweeps       │
+//! │   - monte_carlo() / worst_case() - Component tolerance analysis             │
+//! │   - run_netlist()   - Every directive of a SPICE deck, with parse warnings  │
 //! │                                                                             │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
 //! DEPENDS ON:
 //!   • DNA/physics/electromagnetics/lumped → Netlist, MNA matrices
 //!   • DNA/physics/electromagnetics/lumped/ac → Complex numbers, AC analysis
+//!   • DNA/physics/electromagnetics/lumped/dc → Operating point, device models
+//!   • DNA/physics/electromagnetics/lumped/noise → .noise analysis
+//!   • DNA/physics/electromagnetics/lumped/parser → SPICE netlist text
+//!   • DNA/physics/electromagnetics/lumped/polezero → .pz analysis
+//!   • DNA/physics/electromagnetics/lumped/sparse → Sparse LU for MNA solves
+//!   • DNA/physics/electromagnetics/lumped/sweep → Sweeps, tolerance analysis
+//!   • DNA/physics/electromagnetics/lumped/transient → Transient analysis
+//!   • DNA/physics/electromagnetics/lumped/vectfit → AC response fitting
 //!
 //! USED BY:
 //!   • TOOLS/PLL → PLL frequency response
@@ -46,16 +68,70 @@
 pub use dna::physics::electromagnetics::lumped::{
     // Analysis functions
     ac_analysis,
+    dc_operating_point,
+    dc_operating_point_with,
+    dc_sweep,
+    dc_sweep_with,
+    fit_transfer_function,
+    monte_carlo,
+    noise_analysis,
+    noise_analysis_with,
+    parametric_sweep,
+    // Netlist text
+    parse_netlist,
+    parse_netlist_with_includes,
+    parse_value,
+    pole_zero,
+    pole_zero_with,
+    transient_analysis,
+    transient_analysis_with,
+    vector_fit,
+    worst_case,
     ACResult,
+    AcSweep,
+    Analysis,
     BehavioralExpression,
+    // Device model cards
+    BjtModel,
+    BjtPolarity,
     Complex,
     ComplexMNAMatrix,
+    ConvergenceAid,
+    DcOperatingPoint,
+    DcOptions,
+    DcSweepResult,
+    DiodeModel,
     Element,
+    IntegrationMethod,
     // Matrix types
     MNAMatrix,
+    Measurement,
+    MonteCarloOptions,
+    MonteCarloResult,
+    MosfetModel,
+    MosfetPolarity,
     // Netlist types
     Netlist,
+    NoiseContribution,
+    NoiseOptions,
+    NoiseResult,
+    NoiseSource,
+    ParametricResult,
+    ParsedNetlist,
+    PoleZeroOptions,
+    PoleZeroResult,
     SourceValue,
+    SparseLu,
+    SparseMatrix,
+    SparseSolver,
+    SpiceParseError,
+    Tolerance,
+    ToleranceDistribution,
+    TransientOptions,
+    TransientResult,
+    VectorFitOptions,
+    VectorFitResult,
+    WorstCaseResult,
 };
 
 /// Bode plot data point
@@ -111,6 +187,100 @@ pub fn find_cutoff_frequency(bode: &[BodePoint]) -> Option<f64> {
     None
 }
 
+/// Output of one analysis directive
+#[derive(Clone, Debug)]
+pub enum AnalysisOutput {
+    Op(DcOperatingPoint),
+    Ac(ACResult),
+    Dc(DcSweepResult),
+    Noise(NoiseResult),
+    Tran(TransientResult),
+}
+
+/// Every analysis directive of a deck, run in order
+#[derive(Clone, Debug)]
+pub struct DeckResult {
+    pub outputs: Vec<AnalysisOutput>,
+    /// Lines the parser skipped rather than rejected (unknown element types)
+    pub warnings: Vec<SpiceParseError>,
+}
+
+/// Parse SPICE netlist text and run each of its analysis directives
+///
+/// Parse errors come back with their line number. Frequency sweeps run on
+/// a logarithmic grid, so `lin` sweeps are rejected.
+pub fn run_netlist(text: &str) -> Result<DeckResult, String> {
+    let parsed = parse_netlist(text).map_err(|e| e.to_string())?;
+    let netlist = &parsed.netlist;
+    let per_decade = |sweep: AcSweep, points: usize| match sweep {
+        AcSweep::Decade => Ok(points),
+        AcSweep::Octave => Ok((points as f64 * 10f64.log2()).round() as usize),
+        AcSweep::Linear => Err("Linear frequency sweeps are not supported".to_string()),
+    };
+
+    let mut outputs = Vec::with_capacity(parsed.analyses.len());
+    for analysis in &parsed.analyses {
+        let output = match analysis {
+            Analysis::Op => AnalysisOutput::Op(dc_operating_point(netlist)?),
+            Analysis::Ac {
+                sweep,
+                points,
+                fstart,
+                fstop,
+            } => AnalysisOutput::Ac(ac_analysis(
+                netlist,
+                *fstart,
+                *fstop,
+                per_decade(*sweep, *points)?,
+            )?),
+            Analysis::Dc {
+                source,
+                start,
+                stop,
+                step,
+            } => AnalysisOutput::Dc(dc_sweep(netlist, source, *start, *stop, *step)?),
+            Analysis::Noise {
+                output,
+                reference,
+                source,
+                sweep,
+                points,
+                fstart,
+                fstop,
+            } => {
+                let options = NoiseOptions {
+                    reference: reference.clone(),
+                    ..Default::default()
+                };
+                AnalysisOutput::Noise(noise_analysis_with(
+                    netlist,
+                    output,
+                    Some(source),
+                    *fstart,
+                    *fstop,
+                    per_decade(*sweep, *points)?,
+                    &options,
+                )?)
+            }
+            Analysis::Tran {
+                tstep, tstop, tmax, ..
+            } => {
+                let options = TransientOptions {
+                    max_step: *tmax,
+                    ..Default::default()
+                };
+                AnalysisOutput::Tran(transient_analysis_with(netlist, *tstop, *tstep, &options)?)
+            }
+        };
+        outputs.push(output);
+    }
+
+    Ok(DeckResult {
+        outputs,
+        warnings: parsed.warnings,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,4 +353,19 @@ mod tests {
         // Should be between 100 and 200 Hz
         assert!(fc > 100.0 && fc < 200.0);
     }
+
+    #[test]
+    fn test_run_netlist_reports_warnings() {
+        let deck =
+            "V1 in 0 DC 1\nR1 in out 1k\nA1 out 0 model\nC1 out 0 1u\n.op\n.ac dec 10 10 10k";
+        let result = run_netlist(deck).unwrap();
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].line, 3);
+        assert!(matches!(&result.outputs[0], AnalysisOutput::Op(op)
+            if (op.voltage("out").unwrap() - 1.0).abs() < 1e-6));
+        assert!(matches!(&result.outputs[1], AnalysisOutput::Ac(ac) if ac.frequencies.len() > 20));
+
+        let err = run_netlist("V1 in 0 1\nK1 L1 L2 0.5\n.op").unwrap_err();
+        assert!(err.starts_with("line 2:"), "{}", err);
+    }
 }
@@ -9,6 +9,7 @@
 //! - Loop filter design (passive, active)
 //! - Stability analysis (phase margin, gain margin)
 //! - Transient simulation (lock time, overshoot)
+//! - Behavioral simulation (cycle slips, reference and fractional spurs)
 //! - Noise analysis (phase noise, jitter)
 //!
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
@@ -48,8 +49,11 @@
 pub use dna::pll::{
     // Main design function
     design_pll,
+    // Ranked R/N/prescaler and part candidates
+    planner::design_candidates,
     // Bode plot and noise
     BodePlot,
+    DesignCandidate,
     // Divider config
     DividerConfig,
     // Loop filter
@@ -65,6 +69,11 @@ pub use dna::pll::{
     TransientResult,
 };
 
+// Event-driven behavioral simulation
+pub use dna::pll::behavioral::{
+    simulate_behavioral, BehavioralConfig, BehavioralResult, ChargePumpModel, VcoTuningCurve,
+};
+
 // Re-export SPICE engine for circuit-level simulation
 pub use spice_engine::{ac_analysis, ACResult, Element, Netlist, SourceValue};
 
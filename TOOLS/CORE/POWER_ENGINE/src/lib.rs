@@ -35,6 +35,11 @@
 // Re-export all types from DNA power module
 pub use dna::power::*;
 
+// Closed-loop transient prediction
+pub use dna::power::control::{
+    boost_transient, buck_transient, ConverterTransient, StepResponse, TransientSpec,
+};
+
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -59,6 +64,53 @@ pub struct ComponentEntry {
     pub notes: Vec<String>,
 }
 
+impl DesignReport {
+    /// Append closed-loop stability and step-response metrics
+    pub fn with_transient(mut self, transient: &ConverterTransient) -> Self {
+        let entry = |metric: &str, value: String, unit: &str| PerformanceEntry {
+            metric: metric.to_string(),
+            value,
+            unit: unit.to_string(),
+        };
+        let load = &transient.load_step;
+        let line = &transient.line_step;
+        self.performance.extend([
+            entry(
+                "Loop Crossover",
+                format!("{:.1}", transient.stability.crossover_freq / 1e3),
+                "kHz",
+            ),
+            entry(
+                "Phase Margin",
+                format!("{:.0}", transient.stability.phase_margin_deg),
+                "°",
+            ),
+            entry(
+                &format!("Load Step {:.2}A Dip", load.step),
+                format!("{:.0}", load.undershoot * 1e3),
+                "mV",
+            ),
+            entry(
+                "Load Step Settling",
+                format!("{:.0}", load.settling_time * 1e6),
+                "us",
+            ),
+            entry(
+                &format!("Line Step {:.1}V Peak", line.step),
+                format!("{:.0}", line.peak_deviation() * 1e3),
+                "mV",
+            ),
+        ]);
+        if transient.stability.phase_margin_deg < 45.0 {
+            self.warnings.push(format!(
+                "Low phase margin ({:.0}°) - expect ringing on load steps",
+                transient.stability.phase_margin_deg
+            ));
+        }
+        self
+    }
+}
+
 /// Performance metric in design report
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PerformanceEntry {
@@ -291,6 +343,15 @@ impl PowerDesignResult {
         }
     }
 
+    /// Predict closed-loop load- and line-step response (switching topologies)
+    pub fn transient(&self, spec: &TransientSpec) -> Result<ConverterTransient, String> {
+        match self {
+            PowerDesignResult::Buck(d) => buck_transient(d, spec),
+            PowerDesignResult::Boost(d) => boost_transient(d, spec),
+            PowerDesignResult::LDO(_) => Err("LDO transient response is not modelled".to_string()),
+        }
+    }
+
     /// Get topology type
     pub fn topology(&self) -> TopologyType {
         match self {
@@ -368,6 +429,22 @@ mod tests {
         assert!(!report.components.is_empty());
     }
 
+    #[test]
+    fn test_transient_report() {
+        let result = PowerDesignResult::Buck(quick_buck(12.0, 5.0, 2.0, 500e3).unwrap());
+        let transient = result.transient(&TransientSpec::default()).unwrap();
+        let report = result.to_report().with_transient(&transient);
+
+        assert!(report
+            .performance
+            .iter()
+            .any(|p| p.metric == "Phase Margin"));
+        assert!(transient.load_step.undershoot > 0.0);
+        assert!(PowerDesignResult::LDO(quick_ldo(5.0, 3.3, 0.5).unwrap())
+            .transient(&TransientSpec::default())
+            .is_err());
+    }
+
     #[test]
     fn test_auto_design_step_down() {
         let result = auto_design(12.0, 5.0, 2.0, 500e3, DesignPriority::Efficiency);
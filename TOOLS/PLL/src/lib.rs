@@ -228,6 +228,30 @@ fn display_results(document: &Document, design: &pll_engine::PLLDesign) -> Resul
         "result-pfd",
         &format!("{:.2} MHz", design.pfd_freq_hz / 1e6),
     )?;
+    set_text(
+        document,
+        "result-prescaler",
+        &design
+            .prescaler
+            .map_or("None".to_string(), |p| format!("{}/{}", p, p + 1)),
+    )?;
+
+    // Display selected parts
+    set_text(document, "result-ic", &design.pll_ic.part_number)?;
+    set_text(
+        document,
+        "result-vco",
+        &design
+            .vco
+            .as_ref()
+            .map_or("Integrated".to_string(), |v| v.part_number.clone()),
+    )?;
+    set_text(
+        document,
+        "result-icp",
+        &format!("{:.2} mA", design.charge_pump_current_ua / 1e3),
+    )?;
+    display_candidates(document, &design.candidates)?;
 
     // Display loop filter components (find from components vector)
     let c1 = design
@@ -310,6 +334,46 @@ fn display_results(document: &Document, design: &pll_engine::PLLDesign) -> Resul
     Ok(())
 }
 
+/// Fill the ranked candidate table (best first, the first row is the design shown)
+fn display_candidates(
+    document: &Document,
+    candidates: &[pll_engine::DesignCandidate],
+) -> Result<(), JsValue> {
+    const MAX_ROWS: usize = 12;
+
+    let mut html = String::from(
+        "<table class=\"candidates-table\"><tr><th>PLL IC</th><th>VCO</th><th>R</th>\
+         <th>PFD</th><th>Icp</th><th>In-band</th><th>Jitter</th><th>Trade-offs</th></tr>",
+    );
+    for (i, c) in candidates.iter().take(MAX_ROWS).enumerate() {
+        html.push_str(&format!(
+            "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{:.2} MHz</td><td>{:.2} mA</td>\
+             <td>{:.1} dBc/Hz</td><td>{:.0} fs</td><td>{}</td></tr>",
+            if i == 0 { " class=\"selected\"" } else { "" },
+            c.pll_ic,
+            c.vco,
+            c.divider_r,
+            c.pfd_freq_hz / 1e6,
+            c.charge_pump_current_ua / 1e3,
+            c.inband_noise_dbc_hz,
+            c.integrated_jitter_fs,
+            c.tradeoffs.join("; ")
+        ));
+    }
+    html.push_str("</table>");
+    if candidates.len() > MAX_ROWS {
+        html.push_str(&format!(
+            "<div class=\"result-label\">+{} more candidates</div>",
+            candidates.len() - MAX_ROWS
+        ));
+    }
+
+    if let Some(elem) = document.get_element_by_id("candidates-list") {
+        elem.set_inner_html(&html);
+    }
+    Ok(())
+}
+
 fn set_text(document: &Document, id: &str, text: &str) -> Result<(), JsValue> {
     if let Some(elem) = document.get_element_by_id(id) {
         let elem: HtmlElement = elem.dyn_into()?;
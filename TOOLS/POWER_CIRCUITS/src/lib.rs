@@ -21,6 +21,8 @@ use power_engine::{
     design_boost,
     design_buck,
     design_ldo,
+    optimize_boost,
+    optimize_buck,
     simulate_boost,
     simulate_buck,
     BoostDesign,
@@ -31,11 +33,16 @@ use power_engine::{
     DesignReport,
     LDODesign,
     LDORequirements,
+    Objective,
+    OptimizerSpec,
+    ParetoFront,
     PowerDesignResult,
     RippleSpec,
+    StepResponse,
     TopologyType,
     TransientConfig,
     TransientResult,
+    TransientSpec,
     VoltageRange,
 };
 
@@ -128,6 +135,14 @@ struct AppState {
     priority: DesignPriority,
     // Simulation results
     sim_result: Option<TransientResult>,
+    // Predicted closed-loop load-step response
+    step_result: Option<StepResponse>,
+    // Waveform canvas shows the load step instead of the switching simulation
+    show_step: bool,
+    // Efficiency/volume/cost/thermal Pareto front from the optimizer
+    pareto: Option<ParetoFront>,
+    // Waveform canvas shows the Pareto front
+    show_pareto: bool,
     // Waveform view state
     waveform_view: WaveformView,
 }
@@ -143,6 +158,10 @@ impl Default for AppState {
             fsw_khz: 500.0,
             priority: DesignPriority::Efficiency,
             sim_result: None,
+            step_result: None,
+            show_step: false,
+            pareto: None,
+            show_pareto: false,
             waveform_view: WaveformView::default(),
         }
     }
@@ -211,6 +230,30 @@ fn init_ui() -> Result<(), JsValue> {
         closure.forget();
     }
 
+    // Set up load step button
+    if let Some(btn) = document.get_element_by_id("step-btn") {
+        let btn: HtmlElement = btn.dyn_into()?;
+        let closure = Closure::wrap(Box::new(move || {
+            if let Err(e) = run_load_step() {
+                web_sys::console::error_1(&format!("Load step failed: {:?}", e).into());
+            }
+        }) as Box<dyn FnMut()>);
+        btn.set_onclick(Some(closure.as_ref().unchecked_ref()));
+        closure.forget();
+    }
+
+    // Set up Pareto front button
+    if let Some(btn) = document.get_element_by_id("pareto-btn") {
+        let btn: HtmlElement = btn.dyn_into()?;
+        let closure = Closure::wrap(Box::new(move || {
+            if let Err(e) = run_pareto() {
+                web_sys::console::error_1(&format!("Optimization failed: {:?}", e).into());
+            }
+        }) as Box<dyn FnMut()>);
+        btn.set_onclick(Some(closure.as_ref().unchecked_ref()));
+        closure.forget();
+    }
+
     // Set up reset view button
     if let Some(btn) = document.get_element_by_id("reset-view-btn") {
         let btn: HtmlElement = btn.dyn_into()?;
@@ -423,12 +466,28 @@ fn redraw_waveform() {
         None => return,
     };
 
-    let (sim_result, vout) = STATE.with(|state| {
+    let (sim_result, step_result, show_step, pareto, vout) = STATE.with(|state| {
         let s = state.borrow();
-        (s.sim_result.clone(), s.vout)
+        (
+            s.sim_result.clone(),
+            s.step_result.clone(),
+            s.show_step,
+            s.pareto.clone().filter(|_| s.show_pareto),
+            s.vout,
+        )
     });
 
-    if let Some(result) = sim_result {
+    if let Some(front) = pareto {
+        if let Err(e) = draw_pareto_front(&document, &front) {
+            web_sys::console::error_1(&format!("Redraw failed: {:?}", e).into());
+        }
+    } else if show_step {
+        if let Some(step) = step_result {
+            if let Err(e) = draw_step_response(&document, &step) {
+                web_sys::console::error_1(&format!("Redraw failed: {:?}", e).into());
+            }
+        }
+    } else if let Some(result) = sim_result {
         if let Err(e) = draw_waveforms(&document, &result, vout) {
             web_sys::console::error_1(&format!("Redraw failed: {:?}", e).into());
         }
@@ -635,7 +694,17 @@ fn run_design() -> Result<(), JsValue> {
 
     match result {
         Ok(design) => {
-            let report = design.to_report();
+            let mut report = design.to_report();
+            let transient = match design {
+                PowerDesignResult::LDO(_) => None,
+                _ => design.transient(&transient_spec(&document)).ok(),
+            };
+            if let Some(transient) = &transient {
+                report = report.with_transient(transient);
+            }
+            STATE.with(|state| {
+                state.borrow_mut().step_result = transient.map(|t| t.load_step);
+            });
             display_results(&document, &report)?;
             draw_schematic(&document, topology)?;
             Ok(())
@@ -651,6 +720,14 @@ fn run_design() -> Result<(), JsValue> {
     }
 }
 
+/// Transient prediction settings from the simulation panel
+fn transient_spec(document: &Document) -> TransientSpec {
+    TransientSpec {
+        load_step_a: get_input_value(document, "step-current").unwrap_or(0.0),
+        ..Default::default()
+    }
+}
+
 fn get_input_value(document: &Document, id: &str) -> Result<f64, JsValue> {
     let input = document
         .get_element_by_id(id)
@@ -746,6 +823,8 @@ fn run_simulation() -> Result<(), JsValue> {
     STATE.with(|state| {
         let mut s = state.borrow_mut();
         s.sim_result = Some(result.clone());
+        s.show_step = false;
+        s.show_pareto = false;
         // Reset view to show full simulation
         s.waveform_view.t_start = 0.0;
         s.waveform_view.t_end = t_max;
@@ -769,6 +848,490 @@ fn run_simulation() -> Result<(), JsValue> {
     Ok(())
 }
 
+// ============================================================================
+// LOAD STEP RESPONSE
+// ============================================================================
+
+fn run_load_step() -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("No window")?;
+    let document = window.document().ok_or("No document")?;
+
+    let design = STATE.with(|state| match &state.borrow().design {
+        CurrentDesign::Buck(d) => Some(PowerDesignResult::Buck(d.clone())),
+        CurrentDesign::Boost(d) => Some(PowerDesignResult::Boost(d.clone())),
+        _ => None,
+    });
+    let Some(design) = design else {
+        web_sys::console::log_1(&"Load step prediction needs a switching design".into());
+        clear_waveform_canvas(&document)?;
+        return Ok(());
+    };
+
+    let transient = design.transient(&transient_spec(&document))?;
+    let step = transient.load_step;
+    let t_max = step.time.last().copied().unwrap_or(1e-3);
+
+    web_sys::console::log_1(
+        &format!(
+            "Load step {:.2}A: dip={:.1}mV, settle={:.0}us, PM={:.0}deg",
+            step.step,
+            step.undershoot * 1e3,
+            step.settling_time * 1e6,
+            transient.stability.phase_margin_deg
+        )
+        .into(),
+    );
+
+    STATE.with(|state| {
+        let mut s = state.borrow_mut();
+        s.step_result = Some(step.clone());
+        s.show_step = true;
+        s.show_pareto = false;
+        s.waveform_view.t_start = 0.0;
+        s.waveform_view.t_end = t_max;
+        s.waveform_view.t_max = t_max;
+    });
+
+    draw_step_response(&document, &step)
+}
+
+fn draw_step_response(document: &Document, step: &StepResponse) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("No window")?;
+    let canvas = document
+        .get_element_by_id("waveform-canvas")
+        .ok_or("Waveform canvas not found")?;
+    let canvas: HtmlCanvasElement = canvas.dyn_into()?;
+    let ctx = canvas
+        .get_context("2d")?
+        .ok_or("Could not get 2d context")?
+        .dyn_into::<CanvasRenderingContext2d>()?;
+
+    // Handle high-DPI displays
+    let dpr = window.device_pixel_ratio();
+    let css_width = 600.0;
+    let css_height = 180.0;
+
+    canvas.set_width((css_width * dpr) as u32);
+    canvas.set_height((css_height * dpr) as u32);
+    let _ = canvas
+        .style()
+        .set_property("width", &format!("{}px", css_width));
+    let _ = canvas
+        .style()
+        .set_property("height", &format!("{}px", css_height));
+
+    ctx.scale(dpr, dpr)?;
+
+    let width = css_width;
+    let height = css_height;
+
+    ctx.set_fill_style(&JsValue::from_str("#0a0a12"));
+    ctx.fill_rect(0.0, 0.0, width, height);
+
+    let margin_left = 60.0;
+    let margin_right = 20.0;
+    let margin_top = 20.0;
+    let margin_bottom = 30.0;
+
+    let plot_width = width - margin_left - margin_right;
+    let plot_height = height - margin_top - margin_bottom;
+
+    let (view_t_start, view_t_end) = STATE.with(|state| {
+        let s = state.borrow();
+        (s.waveform_view.t_start, s.waveform_view.t_end)
+    });
+    let view_duration = (view_t_end - view_t_start).max(1e-9);
+
+    // Draw grid
+    ctx.set_stroke_style(&JsValue::from_str("#1a1a24"));
+    ctx.set_line_width(0.5);
+    for i in 0..=4 {
+        let y = margin_top + (i as f64 / 4.0) * plot_height;
+        ctx.begin_path();
+        ctx.move_to(margin_left, y);
+        ctx.line_to(width - margin_right, y);
+        ctx.stroke();
+    }
+    for i in 0..=5 {
+        let x = margin_left + (i as f64 / 5.0) * plot_width;
+        ctx.begin_path();
+        ctx.move_to(x, margin_top);
+        ctx.line_to(x, height - margin_bottom);
+        ctx.stroke();
+    }
+
+    let margin_t = view_duration * 0.01;
+    let start_idx = step
+        .time
+        .iter()
+        .position(|&t| t >= view_t_start - margin_t)
+        .unwrap_or(0);
+    let end_idx = step
+        .time
+        .iter()
+        .position(|&t| t > view_t_end + margin_t)
+        .unwrap_or(step.time.len());
+
+    // Voltage window spans the excursion plus the settling band
+    let band = step.nominal * 0.01;
+    let excursion = step.peak_deviation().max(band) * 1.2;
+    let v_max = step.nominal + excursion;
+    let v_min = step.nominal - excursion;
+    let v_range = v_max - v_min;
+
+    let map_x = |t: f64| margin_left + ((t - view_t_start) / view_duration) * plot_width;
+    let map_v = |v: f64| margin_top + plot_height - ((v - v_min) / v_range) * plot_height;
+
+    // Settling band (dashed)
+    ctx.set_stroke_style(&JsValue::from_str("#ffffff20"));
+    ctx.set_line_width(1.0);
+    let dash_pattern = js_sys::Array::new();
+    dash_pattern.push(&JsValue::from_f64(3.0));
+    dash_pattern.push(&JsValue::from_f64(3.0));
+    ctx.set_line_dash(&dash_pattern)?;
+    for v in [step.nominal + band, step.nominal - band] {
+        ctx.begin_path();
+        ctx.move_to(margin_left, map_v(v));
+        ctx.line_to(width - margin_right, map_v(v));
+        ctx.stroke();
+    }
+
+    // Nominal output (dashed, white)
+    ctx.set_stroke_style(&JsValue::from_str("#ffffff40"));
+    let dash_pattern = js_sys::Array::new();
+    dash_pattern.push(&JsValue::from_f64(5.0));
+    dash_pattern.push(&JsValue::from_f64(5.0));
+    ctx.set_line_dash(&dash_pattern)?;
+    ctx.begin_path();
+    ctx.move_to(margin_left, map_v(step.nominal));
+    ctx.line_to(width - margin_right, map_v(step.nominal));
+    ctx.stroke();
+    ctx.set_line_dash(&js_sys::Array::new())?;
+
+    // Output voltage response (green)
+    if end_idx > start_idx {
+        ctx.set_stroke_style(&JsValue::from_str("#00ffaa"));
+        ctx.set_line_width(1.5);
+        ctx.begin_path();
+        ctx.move_to(map_x(step.time[start_idx]), map_v(step.v_out[start_idx]));
+        for i in (start_idx + 1)..end_idx {
+            ctx.line_to(map_x(step.time[i]), map_v(step.v_out[i]));
+        }
+        ctx.stroke();
+    }
+
+    // Labels
+    ctx.set_fill_style(&JsValue::from_str("#808090"));
+    ctx.set_font("11px Monaco, monospace");
+
+    ctx.set_text_align("right");
+    let _ = ctx.fill_text(
+        &format!("{:.3}V", v_max),
+        margin_left - 5.0,
+        margin_top + 5.0,
+    );
+    let _ = ctx.fill_text(
+        &format!("{:.3}V", v_min),
+        margin_left - 5.0,
+        height - margin_bottom,
+    );
+
+    ctx.set_text_align("center");
+    let t_start_us = view_t_start * 1e6;
+    let t_end_us = view_t_end * 1e6;
+    let _ = ctx.fill_text(
+        &format!("{:.1}us", t_start_us),
+        margin_left,
+        height - margin_bottom + 15.0,
+    );
+    let _ = ctx.fill_text(
+        &format!("{:.1}us", t_end_us),
+        width - margin_right,
+        height - margin_bottom + 15.0,
+    );
+    let _ = ctx.fill_text(
+        &format!("{:.1}us", (t_start_us + t_end_us) / 2.0),
+        width / 2.0,
+        height - margin_bottom + 15.0,
+    );
+
+    // Legend (left side)
+    ctx.set_text_align("left");
+    ctx.set_fill_style(&JsValue::from_str("#00ffaa"));
+    let _ = ctx.fill_text(
+        &format!("Vout, +{:.2}A load step", step.step),
+        margin_left + 10.0,
+        margin_top + 12.0,
+    );
+
+    // Stats (right side)
+    ctx.set_text_align("right");
+    ctx.set_fill_style(&JsValue::from_str("#808090"));
+    let _ = ctx.fill_text(
+        &format!(
+            "Dip: {:.1}mV @ {:.0}us | Settle (1%): {:.0}us",
+            step.undershoot * 1e3,
+            step.peak_time * 1e6,
+            step.settling_time * 1e6
+        ),
+        width - margin_right,
+        margin_top + 12.0,
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// PARETO FRONT
+// ============================================================================
+
+fn run_pareto() -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("No window")?;
+    let document = window.document().ok_or("No document")?;
+
+    let (topology, vin, vout, iout) = STATE.with(|state| {
+        let s = state.borrow();
+        (s.topology, s.vin_nom, s.vout, s.iout)
+    });
+    let vin_range = VoltageRange::range(vin * 0.9, vin * 1.1);
+
+    let front = match topology {
+        TopologyType::Buck => {
+            let req = BuckRequirements {
+                vin: vin_range,
+                vout,
+                iout_max: iout,
+                iout_min: iout * 0.1,
+                ..Default::default()
+            };
+            optimize_buck(&req, &OptimizerSpec::default())
+        }
+        TopologyType::Boost => {
+            let req = BoostRequirements {
+                vin: vin_range,
+                vout,
+                iout_max: iout,
+                iout_min: iout * 0.1,
+                ..Default::default()
+            };
+            // The right-half-plane zero caps achievable boost phase margin
+            let spec = OptimizerSpec {
+                min_phase_margin: 35.0,
+                crossover_fractions: vec![0.02, 0.05],
+                ..Default::default()
+            };
+            optimize_boost(&req, &spec)
+        }
+        _ => {
+            web_sys::console::log_1(&"Pareto optimization needs a switching design".into());
+            clear_waveform_canvas(&document)?;
+            return Ok(());
+        }
+    }?;
+
+    if let Some(best) = front.best(Objective::Efficiency) {
+        web_sys::console::log_1(
+            &format!(
+                "Pareto front: {} of {} designs, best {:.1}% @ {:.0}kHz ({} + {}, {})",
+                front.points.len(),
+                front.evaluated,
+                best.efficiency * 100.0,
+                best.switching_freq / 1e3,
+                best.mosfet,
+                best.diode,
+                best.inductor.core.part_number
+            )
+            .into(),
+        );
+    }
+
+    STATE.with(|state| {
+        let mut s = state.borrow_mut();
+        s.pareto = Some(front.clone());
+        s.show_pareto = true;
+    });
+
+    draw_pareto_front(&document, &front)
+}
+
+fn draw_pareto_front(document: &Document, front: &ParetoFront) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("No window")?;
+    let canvas = document
+        .get_element_by_id("waveform-canvas")
+        .ok_or("Waveform canvas not found")?;
+    let canvas: HtmlCanvasElement = canvas.dyn_into()?;
+    let ctx = canvas
+        .get_context("2d")?
+        .ok_or("Could not get 2d context")?
+        .dyn_into::<CanvasRenderingContext2d>()?;
+
+    // Handle high-DPI displays
+    let dpr = window.device_pixel_ratio();
+    let css_width = 600.0;
+    let css_height = 180.0;
+
+    canvas.set_width((css_width * dpr) as u32);
+    canvas.set_height((css_height * dpr) as u32);
+    let _ = canvas
+        .style()
+        .set_property("width", &format!("{}px", css_width));
+    let _ = canvas
+        .style()
+        .set_property("height", &format!("{}px", css_height));
+
+    ctx.scale(dpr, dpr)?;
+
+    let width = css_width;
+    let height = css_height;
+
+    ctx.set_fill_style(&JsValue::from_str("#0a0a12"));
+    ctx.fill_rect(0.0, 0.0, width, height);
+
+    let margin_left = 60.0;
+    let margin_right = 20.0;
+    let margin_top = 20.0;
+    let margin_bottom = 30.0;
+
+    let plot_width = width - margin_left - margin_right;
+    let plot_height = height - margin_top - margin_bottom;
+
+    // Draw grid
+    ctx.set_stroke_style(&JsValue::from_str("#1a1a24"));
+    ctx.set_line_width(0.5);
+    for i in 0..=4 {
+        let y = margin_top + (i as f64 / 4.0) * plot_height;
+        ctx.begin_path();
+        ctx.move_to(margin_left, y);
+        ctx.line_to(width - margin_right, y);
+        ctx.stroke();
+    }
+    for i in 0..=5 {
+        let x = margin_left + (i as f64 / 5.0) * plot_width;
+        ctx.begin_path();
+        ctx.move_to(x, margin_top);
+        ctx.line_to(x, height - margin_bottom);
+        ctx.stroke();
+    }
+
+    let series = front.series(Objective::Volume, Objective::Efficiency);
+    if series.is_empty() {
+        return Ok(());
+    }
+
+    // Axis ranges with a little padding
+    let (mut x_min, mut x_max) = (f64::MAX, f64::MIN);
+    let (mut y_min, mut y_max) = (f64::MAX, f64::MIN);
+    for &(x, y) in &series {
+        x_min = x_min.min(x);
+        x_max = x_max.max(x);
+        y_min = y_min.min(y * 100.0);
+        y_max = y_max.max(y * 100.0);
+    }
+    let x_pad = ((x_max - x_min) * 0.05).max(0.05);
+    let y_pad = ((y_max - y_min) * 0.1).max(0.1);
+    let (x_min, x_max) = ((x_min - x_pad).max(0.0), x_max + x_pad);
+    let (y_min, y_max) = (y_min - y_pad, (y_max + y_pad).min(100.0));
+
+    let map_x = |v: f64| margin_left + (v - x_min) / (x_max - x_min) * plot_width;
+    let map_y = |e: f64| margin_top + plot_height - (e - y_min) / (y_max - y_min) * plot_height;
+
+    // Points coloured by switching frequency (blue = low, orange = high)
+    let f_min = front
+        .points
+        .first()
+        .map(|p| p.switching_freq)
+        .unwrap_or(1.0);
+    let f_max = front.points.last().map(|p| p.switching_freq).unwrap_or(1.0);
+    let f_span = (f_max / f_min).ln().max(1e-9);
+    for point in &front.points {
+        let t = (point.switching_freq / f_min).ln() / f_span;
+        let hue = 200.0 - 170.0 * t;
+        ctx.set_fill_style(&JsValue::from_str(&format!("hsl({:.0}, 90%, 60%)", hue)));
+        ctx.begin_path();
+        ctx.arc(
+            map_x(point.volume_cm3),
+            map_y(point.efficiency * 100.0),
+            3.0,
+            0.0,
+            2.0 * PI,
+        )?;
+        ctx.fill();
+    }
+
+    // Highlight the most efficient and smallest designs
+    ctx.set_stroke_style(&JsValue::from_str("#00ffaa"));
+    ctx.set_line_width(1.5);
+    for best in [
+        front.best(Objective::Efficiency),
+        front.best(Objective::Volume),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        ctx.begin_path();
+        ctx.arc(
+            map_x(best.volume_cm3),
+            map_y(best.efficiency * 100.0),
+            6.0,
+            0.0,
+            2.0 * PI,
+        )?;
+        ctx.stroke();
+    }
+
+    // Labels
+    ctx.set_fill_style(&JsValue::from_str("#808090"));
+    ctx.set_font("11px Monaco, monospace");
+
+    ctx.set_text_align("right");
+    let _ = ctx.fill_text(
+        &format!("{:.1}%", y_max),
+        margin_left - 5.0,
+        margin_top + 5.0,
+    );
+    let _ = ctx.fill_text(
+        &format!("{:.1}%", y_min),
+        margin_left - 5.0,
+        height - margin_bottom,
+    );
+
+    ctx.set_text_align("center");
+    let _ = ctx.fill_text(
+        &format!("{:.2}cm³", x_min),
+        margin_left,
+        height - margin_bottom + 15.0,
+    );
+    let _ = ctx.fill_text(
+        &format!("{:.2}cm³", x_max),
+        width - margin_right,
+        height - margin_bottom + 15.0,
+    );
+    let _ = ctx.fill_text("Core volume", width / 2.0, height - margin_bottom + 15.0);
+
+    // Legend (left side)
+    ctx.set_text_align("left");
+    ctx.set_fill_style(&JsValue::from_str("#00ffaa"));
+    let _ = ctx.fill_text(
+        &format!(
+            "Efficiency vs volume, {} Pareto designs",
+            front.points.len()
+        ),
+        margin_left + 10.0,
+        margin_top + 12.0,
+    );
+
+    // Frequency scale (right side)
+    ctx.set_text_align("right");
+    ctx.set_fill_style(&JsValue::from_str("#808090"));
+    let _ = ctx.fill_text(
+        &format!("fsw {:.0}kHz → {:.0}kHz", f_min / 1e3, f_max / 1e3),
+        width - margin_right,
+        margin_top + 12.0,
+    );
+
+    Ok(())
+}
+
 fn clear_waveform_canvas(document: &Document) -> Result<(), JsValue> {
     let window = web_sys::window().ok_or("No window")?;
     let canvas = document
@@ -13,8 +13,8 @@ use web_sys::{
 };
 
 use spice_engine::{
-    ac_analysis, find_cutoff_frequency, generate_bode_plot, BodePoint, Element as CircuitElement,
-    Netlist, SourceValue,
+    ac_analysis, find_cutoff_frequency, generate_bode_plot, parse_netlist, AcSweep, Analysis,
+    BodePoint, SpiceParseError,
 };
 
 #[wasm_bindgen(start)]
@@ -68,23 +68,40 @@ fn run_analysis() -> Result<(), JsValue> {
 
     // Read netlist
     let netlist_text = get_textarea_value(&document, "netlist")?;
-    let freq_start = get_input_value(&document, "freq-start")?;
-    let freq_stop = get_input_value(&document, "freq-stop")?;
+    let mut freq_start = get_input_value(&document, "freq-start")?;
+    let mut freq_stop = get_input_value(&document, "freq-stop")?;
+    let mut points_per_decade = 50;
     let output_node = get_input_value(&document, "output-node")? as usize;
 
     // Parse netlist
-    let netlist = match parse_netlist(&netlist_text) {
-        Ok(n) => n,
+    let parsed = match parse_netlist(&netlist_text) {
+        Ok(p) => p,
         Err(e) => {
-            show_error(&document, &e)?;
-            return Err(JsValue::from_str(&e));
+            let message = e.to_string();
+            show_error(&document, &message)?;
+            return Err(JsValue::from_str(&message));
         }
     };
 
+    // A decade .ac directive in the netlist overrides the sweep inputs
+    for analysis in &parsed.analyses {
+        if let Analysis::Ac {
+            sweep: AcSweep::Decade,
+            points,
+            fstart,
+            fstop,
+        } = *analysis
+        {
+            freq_start = fstart;
+            freq_stop = fstop;
+            points_per_decade = points;
+        }
+    }
+
     // Run AC analysis
-    match ac_analysis(&netlist, freq_start, freq_stop, 50) {
+    match ac_analysis(&parsed.netlist, freq_start, freq_stop, points_per_decade) {
         Ok(ac_result) => {
-            hide_error(&document)?;
+            show_warnings(&document, &parsed.warnings)?;
 
             // Generate Bode plot
             let bode = generate_bode_plot(&ac_result, output_node);
@@ -105,166 +122,6 @@ fn run_analysis() -> Result<(), JsValue> {
     }
 }
 
-fn parse_netlist(text: &str) -> Result<Netlist, String> {
-    let mut netlist = Netlist::new("User Circuit".to_string());
-
-    for line in text.lines() {
-        let line = line.trim();
-
-        // Skip comments and empty lines
-        if line.is_empty() || line.starts_with('*') || line.starts_with('#') {
-            continue;
-        }
-
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.is_empty() {
-            continue;
-        }
-
-        let name = parts[0].to_uppercase();
-        let first_char = name.chars().next().unwrap_or(' ');
-
-        match first_char {
-            'R' => {
-                // Resistor: R1 node+ node- value
-                if parts.len() < 4 {
-                    return Err(format!("Invalid resistor: {}", line));
-                }
-                let value = parse_value(parts[3])?;
-                netlist.add_element(CircuitElement::Resistor {
-                    name: name.clone(),
-                    node_p: parts[1].to_string(),
-                    node_n: parts[2].to_string(),
-                    value,
-                });
-            }
-            'C' => {
-                // Capacitor: C1 node+ node- value
-                if parts.len() < 4 {
-                    return Err(format!("Invalid capacitor: {}", line));
-                }
-                let value = parse_value(parts[3])?;
-                netlist.add_element(CircuitElement::Capacitor {
-                    name: name.clone(),
-                    node_p: parts[1].to_string(),
-                    node_n: parts[2].to_string(),
-                    value,
-                });
-            }
-            'L' => {
-                // Inductor: L1 node+ node- value
-                if parts.len() < 4 {
-                    return Err(format!("Invalid inductor: {}", line));
-                }
-                let value = parse_value(parts[3])?;
-                netlist.add_element(CircuitElement::Inductor {
-                    name: name.clone(),
-                    node_p: parts[1].to_string(),
-                    node_n: parts[2].to_string(),
-                    value,
-                });
-            }
-            'V' => {
-                // Voltage source: V1 node+ node- [DC value] [AC mag [phase]]
-                if parts.len() < 4 {
-                    return Err(format!("Invalid voltage source: {}", line));
-                }
-
-                let source_value = parse_source_value(&parts[3..])?;
-                netlist.add_element(CircuitElement::VoltageSource {
-                    name: name.clone(),
-                    node_p: parts[1].to_string(),
-                    node_n: parts[2].to_string(),
-                    value: source_value,
-                });
-            }
-            'I' => {
-                // Current source: I1 node+ node- value
-                if parts.len() < 4 {
-                    return Err(format!("Invalid current source: {}", line));
-                }
-
-                let value = parse_value(parts[3])?;
-                netlist.add_element(CircuitElement::CurrentSource {
-                    name: name.clone(),
-                    node_p: parts[1].to_string(),
-                    node_n: parts[2].to_string(),
-                    value,
-                });
-            }
-            _ => {
-                // Ignore unknown elements
-                web_sys::console::warn_1(&format!("Unknown element: {}", line).into());
-            }
-        }
-    }
-
-    Ok(netlist)
-}
-
-fn parse_value(s: &str) -> Result<f64, String> {
-    let s = s.trim().to_uppercase();
-
-    // Handle SI prefixes
-    let (num_part, multiplier) = if s.ends_with('K') {
-        (&s[..s.len() - 1], 1e3)
-    } else if s.ends_with('M') {
-        (&s[..s.len() - 1], 1e6)
-    } else if s.ends_with('G') {
-        (&s[..s.len() - 1], 1e9)
-    } else if s.ends_with('U') {
-        (&s[..s.len() - 1], 1e-6)
-    } else if s.ends_with('N') {
-        (&s[..s.len() - 1], 1e-9)
-    } else if s.ends_with('P') {
-        (&s[..s.len() - 1], 1e-12)
-    } else if s.ends_with('F') {
-        (&s[..s.len() - 1], 1e-15)
-    } else if s.contains("MEG") {
-        (&s[..s.find("MEG").unwrap()], 1e6)
-    } else {
-        (s.as_str(), 1.0)
-    };
-
-    num_part
-        .parse::<f64>()
-        .map(|v| v * multiplier)
-        .map_err(|_| format!("Invalid value: {}", s))
-}
-
-fn parse_source_value(parts: &[&str]) -> Result<SourceValue, String> {
-    if parts.is_empty() {
-        return Ok(SourceValue::DC(0.0));
-    }
-
-    let first = parts[0].to_uppercase();
-
-    if first == "AC" {
-        let magnitude = if parts.len() > 1 {
-            parse_value(parts[1])?
-        } else {
-            1.0
-        };
-        let phase = if parts.len() > 2 {
-            parse_value(parts[2])?
-        } else {
-            0.0
-        };
-        Ok(SourceValue::AC { magnitude, phase })
-    } else if first == "DC" {
-        let value = if parts.len() > 1 {
-            parse_value(parts[1])?
-        } else {
-            0.0
-        };
-        Ok(SourceValue::DC(value))
-    } else {
-        // Assume DC value
-        let value = parse_value(&first)?;
-        Ok(SourceValue::DC(value))
-    }
-}
-
 fn display_results(document: &Document, bode: &[BodePoint]) -> Result<(), JsValue> {
     if bode.is_empty() {
         return Ok(());
@@ -550,6 +407,20 @@ fn show_error(document: &Document, msg: &str) -> Result<(), JsValue> {
     Ok(())
 }
 
+/// Skipped netlist lines in the message box, or hide it when there are none
+fn show_warnings(document: &Document, warnings: &[SpiceParseError]) -> Result<(), JsValue> {
+    if warnings.is_empty() {
+        return hide_error(document);
+    }
+    if let Some(elem) = document.get_element_by_id("error-msg") {
+        let elem: HtmlElement = elem.dyn_into()?;
+        let lines: Vec<String> = warnings.iter().map(|w| format!("Warning: {}", w)).collect();
+        elem.set_inner_text(&lines.join("\n"));
+        elem.style().set_property("display", "block")?;
+    }
+    Ok(())
+}
+
 fn hide_error(document: &Document) -> Result<(), JsValue> {
     if let Some(elem) = document.get_element_by_id("error-msg") {
         let elem: HtmlElement = elem.dyn_into()?;